@@ -53,6 +53,27 @@ Pinnacle.setup(function()
         })
     end
 
+    -- mod_key + Tab and mod_key + shift + Tab cycle through windows
+    if Snowcap then
+        Input.keybind({ mod_key }, "Tab", function()
+            local switcher = Snowcap.integration.window_switcher()
+            switcher.modifier = mod_key
+            switcher:show()
+        end, {
+            group = "Window",
+            description = "Switch to the next window",
+        })
+
+        Input.keybind({ mod_key, "shift" }, "Tab", function()
+            local switcher = Snowcap.integration.window_switcher()
+            switcher.modifier = mod_key
+            switcher:show_reverse()
+        end, {
+            group = "Window",
+            description = "Switch to the previous window",
+        })
+    end
+
     if Snowcap then
         -- mod_key + shift + q = Quit Prompt
         Input.keybind({
@@ -111,6 +132,18 @@ Pinnacle.setup(function()
         description = "Spawn a terminal",
     })
 
+    -- mod_key + d = Show the application launcher
+    if Snowcap then
+        Input.keybind({ mod_key }, "d", function()
+            local launcher = Snowcap.integration.launcher()
+            launcher.terminal = { terminal, "-e" }
+            launcher:show()
+        end, {
+            group = "Process",
+            description = "Show the application launcher",
+        })
+    end
+
     -- mod_key + ctrl + space = Toggle floating
     Input.keybind({ mod_key, "ctrl" }, key.space, function()
         local focused = Window.get_focused()
@@ -490,6 +523,16 @@ Pinnacle.setup(function()
         end,
     })
 
+    -- Ask before unknown clients capture the screen.
+    -- This only fires when `prompt_screen_capture` is set in `pinnacle.toml`.
+    if Snowcap then
+        Pinnacle.connect_signal({
+            screen_capture_requested = function(executable, pid)
+                Snowcap.integration.screen_capture_prompt(executable, pid):show()
+            end,
+        })
+    end
+
     -- Spawning should happen after you add tags, as Pinnacle currently doesn't render windows without tags.
     Process.spawn_once(terminal)
 end)
@@ -134,6 +134,66 @@ function pinnacle.take_last_error()
     return error and error.error
 end
 
+---Allows or denies an executable to capture the screen.
+---
+---This is used to respond to the `screen_capture_requested` signal when
+---`prompt_screen_capture` is enabled in the `[privileges]` section of `pinnacle.toml`.
+---The decision lasts until the compositor exits or the privilege policy changes.
+---
+---@param executable string The path to the executable.
+---@param allow boolean Whether the executable may capture the screen.
+function pinnacle.set_screen_capture_permission(executable, allow)
+    local _, err = client:pinnacle_v1_PinnacleService_SetScreenCapturePermission({
+        executable = executable,
+        allow = allow,
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
+---@class pinnacle.PinnacleSignal Signals related to the compositor as a whole.
+---@field screen_capture_requested fun(executable: string, pid: integer)? A client not on the privilege allowlist tried to capture the screen.
+
+local signal_name_to_SignalName = {
+    screen_capture_requested = "ScreenCaptureRequested",
+}
+
+---Connects to a compositor-wide signal.
+---
+---`signals` is a table containing the signal(s) you want to connect to along with
+---a corresponding callback that will be called when the signal is signalled.
+---
+---This function returns a table of signal handles with each handle stored at the same key used
+---to connect to the signal. See `SignalHandles` for more information.
+---
+---# Example
+---```lua
+---Pinnacle.connect_signal({
+---    screen_capture_requested = function(executable, pid)
+---        Pinnacle.set_screen_capture_permission(executable, executable == "/usr/bin/obs")
+---    end
+---})
+---```
+---@param signals pinnacle.PinnacleSignal The signal you want to connect to
+---
+---@return pinnacle.signal.SignalHandles signal_handles Handles to every signal you connected to wrapped in a table, with keys being the same as the connected signal.
+---
+---@see pinnacle.signal.SignalHandles.disconnect_all - To disconnect from these signals
+function pinnacle.connect_signal(signals)
+    ---@diagnostic disable-next-line: invisible
+    local handles = require("pinnacle.signal").handles.new()
+
+    for signal, callback in pairs(signals) do
+        local handle =
+            require("pinnacle.signal").add_callback(signal_name_to_SignalName[signal], callback)
+        handles[signal] = handle
+    end
+
+    return handles
+end
+
 ---Initializes the protobuf backend and connects to Pinnacle's gRPC socket.
 ---
 ---If the Snowcap Lua API is installed and Snowcap is running, this will also setup Snowcap and
@@ -2,6 +2,7 @@
 -- License, v. 2.0. If a copy of the MPL was not distributed with this
 -- file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+local log = require("pinnacle.log")
 local client = require("pinnacle.grpc.client").client
 local util_v1 = require("pinnacle.grpc.defs").pinnacle.util.v1
 
@@ -95,4 +96,68 @@ function debug.toggle_process_piping()
     })
 end
 
+---Starts recording gRPC requests, responses, and signals to a file.
+---
+---If `path` is not given, a new file is created in Pinnacle's state directory.
+---If a recording is already in progress, it continues unless a different `path` is given.
+---
+---The recording can be replayed with `pinnacle debug replay <FILE>`.
+---
+---@param path string?
+---
+---@return string? path The path being recorded to, or `nil` if the file couldn't be created.
+function debug.start_trace_recording(path)
+    local response, err = client:pinnacle_debug_v1_DebugService_SetTraceRecording({
+        set_or_toggle = util_v1.SetOrToggle.SET_OR_TOGGLE_SET,
+        path = path,
+    })
+
+    if err then
+        log.error(err)
+        return nil
+    end
+
+    assert(response)
+
+    return response.path
+end
+
+---Stops recording gRPC traffic.
+---
+---@return string? path The path of the finished recording, if one was in progress.
+function debug.stop_trace_recording()
+    local response, err = client:pinnacle_debug_v1_DebugService_SetTraceRecording({
+        set_or_toggle = util_v1.SetOrToggle.SET_OR_TOGGLE_UNSET,
+    })
+
+    if err then
+        log.error(err)
+        return nil
+    end
+
+    assert(response)
+
+    return response.path
+end
+
+---Returns a JSON snapshot of the compositor's state.
+---
+---This includes outputs, tags, windows, layout trees, pending transactions,
+---focus stacks, and binds. The format is meant for bug reports and tests,
+---and may change between releases.
+---
+---@return string? json
+function debug.dump_state()
+    local response, err = client:pinnacle_debug_v1_DebugService_DumpState({})
+
+    if err then
+        log.error(err)
+        return nil
+    end
+
+    assert(response)
+
+    return response.json
+end
+
 return debug
@@ -0,0 +1,642 @@
+-- This Source Code Form is subject to the terms of the Mozilla Public
+-- License, v. 2.0. If a copy of the MPL was not distributed with this
+-- file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+---An application or desktop action that can be launched.
+---@class pinnacle.snowcap.integration.launcher.Entry
+---The desktop file ID, followed by `:<action>` for desktop actions.
+---@field id string
+---@field name string
+---@field description string?
+---@field keywords string[]
+---@field exec string[]
+---@field cwd string?
+---@field terminal boolean
+
+---Indexing of XDG desktop entries for the launcher.
+---
+---This is an internal module.
+---@class pinnacle.desktop_entry
+local desktop_entry = {}
+
+---Field codes that expand to files or URLs. The launcher never passes any.
+local FILE_FIELD_CODES = {
+    ["%f"] = true,
+    ["%F"] = true,
+    ["%u"] = true,
+    ["%U"] = true,
+    ["%d"] = true,
+    ["%D"] = true,
+    ["%n"] = true,
+    ["%N"] = true,
+    ["%v"] = true,
+    ["%m"] = true,
+}
+
+---How much each doubling of an entry's launch count adds to its score.
+local FREQUENCY_WEIGHT = 16
+
+---@return string?
+local function home_dir()
+    local home = os.getenv("HOME")
+    if home and home ~= "" then
+        return home
+    end
+end
+
+---@return string[]
+local function data_dirs()
+    local dirs = {}
+
+    local data_home = os.getenv("XDG_DATA_HOME")
+    if not data_home or data_home == "" then
+        local home = home_dir()
+        data_home = home and (home .. "/.local/share")
+    end
+    if data_home then
+        table.insert(dirs, data_home)
+    end
+
+    local xdg_data_dirs = os.getenv("XDG_DATA_DIRS")
+    if not xdg_data_dirs or xdg_data_dirs == "" then
+        xdg_data_dirs = "/usr/local/share:/usr/share"
+    end
+    for dir in xdg_data_dirs:gmatch("[^:]+") do
+        table.insert(dirs, dir)
+    end
+
+    return dirs
+end
+
+---Returns the locale keys to look for, best match first.
+---
+---For `lang_COUNTRY.ENCODING@MODIFIER`, these are `lang_COUNTRY@MODIFIER`, `lang_COUNTRY`,
+---`lang@MODIFIER`, and `lang`.
+---
+---@return string[]
+local function locales()
+    local locale
+    for _, var in ipairs({ "LC_ALL", "LC_MESSAGES", "LANG" }) do
+        local value = os.getenv(var)
+        if value and value ~= "" then
+            locale = value
+            break
+        end
+    end
+
+    if not locale then
+        return {}
+    end
+
+    local modifier = locale:match("@(.*)$")
+    locale = locale:gsub("@.*$", "")
+    locale = locale:gsub("%..*$", "")
+    local lang, country = locale:match("^([^_]*)_?(.*)$")
+    if country == "" then
+        country = nil
+    end
+
+    local keys = {}
+    if country and modifier then
+        table.insert(keys, lang .. "_" .. country .. "@" .. modifier)
+    end
+    if country then
+        table.insert(keys, lang .. "_" .. country)
+    end
+    if modifier then
+        table.insert(keys, lang .. "@" .. modifier)
+    end
+    table.insert(keys, lang)
+
+    return keys
+end
+
+---Unescapes a `string` or `localestring` value.
+---
+---@param value string
+---@return string
+local function unescape(value)
+    local escapes = { s = " ", n = "\n", t = "\t", r = "\r", [""] = "\\" }
+    return (value:gsub("\\(.?)", function(c)
+        return escapes[c] or c
+    end))
+end
+
+---Splits a `;`-separated list value.
+---
+---@param value string
+---@return string[]
+local function split_list(value)
+    local items = {}
+    local item = ""
+    local i = 1
+
+    while i <= #value do
+        local c = value:sub(i, i)
+        if c == "\\" and value:sub(i + 1, i + 1) == ";" then
+            item = item .. ";"
+            i = i + 1
+        elseif c == ";" then
+            if item ~= "" then
+                table.insert(items, unescape(item))
+            end
+            item = ""
+        else
+            item = item .. c
+        end
+        i = i + 1
+    end
+
+    if item ~= "" then
+        table.insert(items, unescape(item))
+    end
+
+    return items
+end
+
+---Splits an `Exec` value into arguments, expanding or dropping field codes.
+---
+---@param exec string
+---@param name string
+---@param icon string?
+---@param path string
+---@return string[]
+local function parse_exec(exec, name, icon, path)
+    local args = {}
+    local arg = nil
+    local quoted = false
+    local i = 1
+
+    while i <= #exec do
+        local c = exec:sub(i, i)
+        if c == '"' then
+            quoted = not quoted
+            arg = arg or ""
+        elseif c == "\\" and quoted then
+            i = i + 1
+            arg = (arg or "") .. exec:sub(i, i)
+        elseif c:match("%s") and not quoted then
+            if arg then
+                table.insert(args, arg)
+                arg = nil
+            end
+        else
+            arg = (arg or "") .. c
+        end
+        i = i + 1
+    end
+
+    if arg then
+        table.insert(args, arg)
+    end
+
+    local expanded = {}
+
+    for _, arg in ipairs(args) do
+        if FILE_FIELD_CODES[arg] then
+            -- Dropped
+        elseif arg == "%i" then
+            if icon then
+                table.insert(expanded, "--icon")
+                table.insert(expanded, icon)
+            end
+        else
+            local codes = { ["%"] = "%", c = name, k = path }
+            table.insert(
+                expanded,
+                (arg:gsub("%%(.?)", function(code)
+                    return codes[code] or ""
+                end))
+            )
+        end
+    end
+
+    return expanded
+end
+
+---@param program string
+---@return boolean
+local function is_executable(program)
+    local stat = require("posix.sys.stat")
+    local unistd = require("posix.unistd")
+
+    local function is_executable_file(path)
+        local st = stat.stat(path)
+        return st ~= nil and stat.S_ISREG(st.st_mode) ~= 0 and unistd.access(path, "x") == 0
+    end
+
+    if program:sub(1, 1) == "/" then
+        return is_executable_file(program)
+    end
+
+    for dir in (os.getenv("PATH") or ""):gmatch("[^:]+") do
+        if is_executable_file(dir .. "/" .. program) then
+            return true
+        end
+    end
+
+    return false
+end
+
+---@param dir string
+---@param files string[]
+local function collect_desktop_files(dir, files)
+    local dirent = require("posix.dirent")
+    local stat = require("posix.sys.stat")
+
+    local ok, names = pcall(dirent.dir, dir)
+    if not ok or not names then
+        return
+    end
+
+    for _, name in ipairs(names) do
+        if name ~= "." and name ~= ".." then
+            local path = dir .. "/" .. name
+            local st = stat.stat(path)
+            if st and stat.S_ISDIR(st.st_mode) ~= 0 then
+                collect_desktop_files(path, files)
+            elseif name:match("%.desktop$") then
+                table.insert(files, path)
+            end
+        end
+    end
+end
+
+---Parses a desktop file into its groups, keeping the best localized value of every key.
+---
+---@param contents string
+---@param locale_ranks table<string, integer>
+---@param unlocalized_rank integer
+---@return table<string, table<string, { rank: integer, value: string }>>
+local function parse_groups(contents, locale_ranks, unlocalized_rank)
+    local groups = {}
+    local current = nil
+
+    for line in contents:gmatch("[^\n]+") do
+        line = line:match("^%s*(.-)%s*$")
+
+        if line == "" or line:sub(1, 1) == "#" then
+            -- Skipped
+        elseif line:match("^%[.*%]$") then
+            current = line:sub(2, -2)
+            groups[current] = groups[current] or {}
+        elseif current then
+            local key, value = line:match("^(.-)%s*=%s*(.*)$")
+            if key then
+                local rank = unlocalized_rank
+                local base, locale = key:match("^(.-)%[(.*)%]$")
+                if base then
+                    key = base
+                    rank = locale_ranks[locale]
+                end
+
+                local existing = groups[current][key]
+                if rank and (not existing or rank < existing.rank) then
+                    groups[current][key] = { rank = rank, value = value }
+                end
+            end
+        end
+    end
+
+    return groups
+end
+
+---@param id string
+---@param path string
+---@param groups table<string, table<string, { rank: integer, value: string }>>
+---@param current_desktops table<string, boolean>
+---@param entries pinnacle.snowcap.integration.launcher.Entry[]
+local function add_launch_entries(id, path, groups, current_desktops, entries)
+    local main = groups["Desktop Entry"]
+    if not main then
+        return
+    end
+
+    local function get(group, key)
+        return group[key] and group[key].value
+    end
+
+    if
+        get(main, "Type") ~= "Application"
+        or get(main, "NoDisplay") == "true"
+        or get(main, "Hidden") == "true"
+    then
+        return
+    end
+
+    local try_exec = get(main, "TryExec")
+    if try_exec and not is_executable(unescape(try_exec)) then
+        return
+    end
+
+    local function shown_in(key)
+        local desktops = get(main, key)
+        if not desktops then
+            return nil
+        end
+        for _, desktop in ipairs(split_list(desktops)) do
+            if current_desktops[desktop:lower()] then
+                return true
+            end
+        end
+        return false
+    end
+
+    if shown_in("OnlyShowIn") == false or shown_in("NotShowIn") == true then
+        return
+    end
+
+    local name = get(main, "Name")
+    if not name then
+        return
+    end
+    name = unescape(name)
+
+    local icon = get(main, "Icon") and unescape(get(main, "Icon"))
+    local description = get(main, "GenericName") or get(main, "Comment")
+    description = description and unescape(description)
+    local keywords = get(main, "Keywords") and split_list(get(main, "Keywords")) or {}
+    local cwd = get(main, "Path") and unescape(get(main, "Path"))
+    local terminal = get(main, "Terminal") == "true"
+
+    local function exec(group)
+        local value = get(group, "Exec")
+        if not value then
+            return nil
+        end
+        local args = parse_exec(unescape(value), name, icon, path)
+        if #args == 0 then
+            return nil
+        end
+        return args
+    end
+
+    local main_exec = exec(main)
+    if main_exec then
+        table.insert(entries, {
+            id = id,
+            name = name,
+            description = description,
+            keywords = keywords,
+            exec = main_exec,
+            cwd = cwd,
+            terminal = terminal,
+        })
+    end
+
+    for _, action in ipairs(get(main, "Actions") and split_list(get(main, "Actions")) or {}) do
+        local group = groups["Desktop Action " .. action]
+        local action_name = group and get(group, "Name")
+        local action_exec = group and exec(group)
+
+        if action_name and action_exec then
+            table.insert(entries, {
+                id = id .. ":" .. action,
+                name = name .. ": " .. unescape(action_name),
+                description = description,
+                keywords = keywords,
+                exec = action_exec,
+                cwd = cwd,
+                terminal = terminal,
+            })
+        end
+    end
+end
+
+---Collects all launchable entries from the applications directories in the XDG data dirs.
+---
+---Entries earlier in the data dirs shadow ones with the same desktop file ID later on.
+---
+---@return pinnacle.snowcap.integration.launcher.Entry[]
+function desktop_entry.index()
+    local locale_keys = locales()
+    local locale_ranks = {}
+    for i, locale in ipairs(locale_keys) do
+        locale_ranks[locale] = i
+    end
+
+    local current_desktops = {}
+    for desktop in (os.getenv("XDG_CURRENT_DESKTOP") or ""):gmatch("[^:]+") do
+        current_desktops[desktop:lower()] = true
+    end
+
+    local seen = {}
+    local entries = {}
+
+    for _, dir in ipairs(data_dirs()) do
+        local apps_dir = dir .. "/applications"
+        local files = {}
+        collect_desktop_files(apps_dir, files)
+        table.sort(files)
+
+        for _, path in ipairs(files) do
+            local id = path:sub(#apps_dir + 2):gsub("/", "-")
+
+            -- Hidden or undisplayed entries still shadow later ones.
+            if not seen[id] then
+                seen[id] = true
+
+                local file = io.open(path, "r")
+                if file then
+                    local contents = file:read("*a")
+                    file:close()
+
+                    local groups = parse_groups(contents, locale_ranks, #locale_keys + 1)
+                    add_launch_entries(id, path, groups, current_desktops, entries)
+                end
+            end
+        end
+    end
+
+    return entries
+end
+
+---Scores how well `query` fuzzy-matches `candidate`, case-insensitively.
+---
+---Returns `nil` unless every non-whitespace character of the query appears in the
+---candidate in order. Consecutive matches and matches at the start of words score higher.
+---
+---@param query string
+---@param candidate string
+---@return integer?
+function desktop_entry.fuzzy_score(query, candidate)
+    local lower = candidate:lower()
+    local score = 0
+    local next_index = 1
+    local last_match = nil
+
+    for q in query:lower():gmatch("%S") do
+        local index = lower:find(q, next_index, true)
+        if not index then
+            return nil
+        end
+
+        score = score + 16
+
+        local prev = candidate:sub(index - 1, index - 1)
+        local c = candidate:sub(index, index)
+        if index == 1 or not prev:match("%w") or (prev:match("%l") and c:match("%u")) then
+            score = score + 24
+        end
+
+        if last_match and last_match + 1 == index then
+            score = score + 16
+        elseif last_match then
+            score = score - math.min(index - next_index, 8)
+        else
+            score = score - math.min(index - 1, 16)
+        end
+
+        last_match = index
+        next_index = index + 1
+    end
+
+    -- Prefer shorter candidates when everything else is equal.
+    return score - math.floor(#candidate / 8)
+end
+
+---@param entry pinnacle.snowcap.integration.launcher.Entry
+---@param query string
+---@return integer?
+local function entry_score(entry, query)
+    local best = desktop_entry.fuzzy_score(query, entry.name)
+
+    local function consider(score)
+        if score and (not best or score > best) then
+            best = score
+        end
+    end
+
+    local description = entry.description and desktop_entry.fuzzy_score(query, entry.description)
+    consider(description and math.floor(description / 2))
+    for _, keyword in ipairs(entry.keywords) do
+        local score = desktop_entry.fuzzy_score(query, keyword)
+        consider(score and math.floor(score / 2))
+    end
+
+    return best
+end
+
+---Ranks `entries` against `query`, returning the indices of matching entries,
+---best match first.
+---
+---Entries launched more often rank higher. With an empty query, every entry matches
+---and they're ordered by launch count.
+---
+---@param entries pinnacle.snowcap.integration.launcher.Entry[]
+---@param history table<string, integer>
+---@param query string
+---@return integer[]
+function desktop_entry.rank(entries, history, query)
+    local ranked = {}
+    local empty = not query:match("%S")
+
+    for i, entry in ipairs(entries) do
+        local score = empty and 0 or entry_score(entry, query)
+        if score then
+            local launches = (history[entry.id] or 0) + 1
+            local doublings = 0
+            while launches >= 2 do
+                launches = math.floor(launches / 2)
+                doublings = doublings + 1
+            end
+
+            table.insert(ranked, { index = i, score = score + FREQUENCY_WEIGHT * doublings })
+        end
+    end
+
+    table.sort(ranked, function(a, b)
+        if a.score ~= b.score then
+            return a.score > b.score
+        end
+        local a_name = entries[a.index].name:lower()
+        local b_name = entries[b.index].name:lower()
+        if a_name ~= b_name then
+            return a_name < b_name
+        end
+        return a.index < b.index
+    end)
+
+    local indices = {}
+    for _, entry in ipairs(ranked) do
+        table.insert(indices, entry.index)
+    end
+    return indices
+end
+
+---@return string?
+local function history_path()
+    local state_home = os.getenv("XDG_STATE_HOME")
+    if not state_home or state_home == "" then
+        local home = home_dir()
+        if not home then
+            return nil
+        end
+        state_home = home .. "/.local/state"
+    end
+
+    return state_home .. "/pinnacle/launcher_history"
+end
+
+---Loads how many times each entry has been launched, starting fresh if the history
+---doesn't exist or can't be read.
+---
+---@return table<string, integer>
+function desktop_entry.load_history()
+    local history = {}
+
+    local path = history_path()
+    local file = path and io.open(path, "r")
+    if not file then
+        return history
+    end
+
+    for line in file:lines() do
+        local count, id = line:match("^(%d+)\t(.+)$")
+        if count then
+            history[id] = tonumber(count)
+        end
+    end
+    file:close()
+
+    return history
+end
+
+---Records a launch of the entry with the given ID and saves the history.
+---
+---@param history table<string, integer>
+---@param id string
+function desktop_entry.record_launch(history, id)
+    history[id] = (history[id] or 0) + 1
+
+    local path = history_path()
+    if not path then
+        return
+    end
+
+    local stat = require("posix.sys.stat")
+    local dir = ""
+    for component in path:match("^(.*)/"):gmatch("[^/]+") do
+        dir = dir .. "/" .. component
+        stat.mkdir(dir)
+    end
+
+    local file, err = io.open(path, "w")
+    if not file then
+        require("pinnacle.log").error("Failed to save launcher history: " .. tostring(err))
+        return
+    end
+
+    for entry_id, count in pairs(history) do
+        file:write(count, "\t", entry_id, "\n")
+    end
+    file:close()
+end
+
+desktop_entry.unescape = unescape
+desktop_entry.split_list = split_list
+desktop_entry.parse_exec = parse_exec
+desktop_entry.parse_groups = parse_groups
+desktop_entry.add_launch_entries = add_launch_entries
+
+return desktop_entry
@@ -569,6 +569,15 @@ local pinnacle_input_v1_AccelProfile = {
     ACCEL_PROFILE_UNSPECIFIED = 0,
     ACCEL_PROFILE_FLAT = 1,
     ACCEL_PROFILE_ADAPTIVE = 2,
+    ACCEL_PROFILE_CUSTOM = 3,
+}
+
+---@enum pinnacle.input.v1.AccelType
+local pinnacle_input_v1_AccelType = {
+    ACCEL_TYPE_UNSPECIFIED = 0,
+    ACCEL_TYPE_FALLBACK = 1,
+    ACCEL_TYPE_MOTION = 2,
+    ACCEL_TYPE_SCROLL = 3,
 }
 
 ---@enum pinnacle.input.v1.ScrollMethod
@@ -635,6 +644,14 @@ local pinnacle_output_v1_Vrr = {
     VRR_ON_DEMAND = 3,
 }
 
+---@enum pinnacle.process.v1.RestartPolicy
+local pinnacle_process_v1_RestartPolicy = {
+    RESTART_POLICY_UNSPECIFIED = 0,
+    RESTART_POLICY_NEVER = 1,
+    RESTART_POLICY_ON_FAILURE = 2,
+    RESTART_POLICY_ALWAYS = 3,
+}
+
 ---@enum pinnacle.render.v1.Filter
 local pinnacle_render_v1_Filter = {
     FILTER_UNSPECIFIED = 0,
@@ -699,6 +716,19 @@ local pinnacle_v1_Backend = {
 ---@class pinnacle.debug.v1.SetProcessPipingRequest
 ---@field set_or_toggle pinnacle.util.v1.SetOrToggle?
 
+---@class pinnacle.debug.v1.SetTraceRecordingRequest
+---@field set_or_toggle pinnacle.util.v1.SetOrToggle?
+---@field path string?
+
+---@class pinnacle.debug.v1.SetTraceRecordingResponse
+---@field recording boolean?
+---@field path string?
+
+---@class pinnacle.debug.v1.DumpStateRequest
+
+---@class pinnacle.debug.v1.DumpStateResponse
+---@field json string?
+
 ---@class pinnacle.input.v1.Bind
 ---@field mods pinnacle.input.v1.Modifier[]?
 ---@field ignore_mods pinnacle.input.v1.Modifier[]?
@@ -772,23 +802,47 @@ local pinnacle_v1_Backend = {
 ---@field layout string?
 ---@field model string?
 ---@field options string?
+---@field device_sysname string?
 
 ---@class pinnacle.input.v1.SetRepeatRateRequest
 ---@field rate integer?
 ---@field delay integer?
+---@field device_sysname string?
 
 ---@class pinnacle.input.v1.SetXkbKeymapRequest
 ---@field keymap string?
+---@field device_sysname string?
 
 ---@class pinnacle.input.v1.SwitchXkbLayoutRequest
 ---@field next google.protobuf.Empty?
 ---@field prev google.protobuf.Empty?
 ---@field index integer?
 
+---@class pinnacle.input.v1.SetXkbLayoutPerWindowRequest
+---@field per_window boolean?
+
+---@class pinnacle.input.v1.GetXkbLayoutRequest
+
+---@class pinnacle.input.v1.GetXkbLayoutResponse
+---@field layout_index integer?
+---@field layout_name string?
+
 ---@class pinnacle.input.v1.SetXcursorRequest
 ---@field theme string?
 ---@field size integer?
 
+---@class pinnacle.input.v1.SetCursorHideRequest
+---@field hide_when_typing boolean?
+---@field timeout_ms integer?
+
+---@class pinnacle.input.v1.CustomAccelCurve
+---@field accel_type pinnacle.input.v1.AccelType?
+---@field step number?
+---@field points number[]?
+
+---@class pinnacle.input.v1.CustomAccel
+---@field curves pinnacle.input.v1.CustomAccelCurve[]?
+
 ---@class pinnacle.input.v1.CalibrationMatrix
 ---@field matrix number[]?
 
@@ -816,6 +870,7 @@ local pinnacle_v1_Backend = {
 ---@field name string?
 ---@field product_id integer?
 ---@field vendor_id integer?
+---@field accel_profiles pinnacle.input.v1.AccelProfile[]?
 
 ---@class pinnacle.input.v1.GetDeviceTypeRequest
 ---@field device_sysname string?
@@ -842,6 +897,30 @@ local pinnacle_v1_Backend = {
 ---@field tap_drag_lock boolean?
 ---@field tap boolean?
 ---@field send_events_mode pinnacle.input.v1.SendEventsMode?
+---@field custom_accel pinnacle.input.v1.CustomAccel?
+
+---@class pinnacle.input.v1.GetDeviceLibinputSettingsRequest
+---@field device_sysname string?
+
+---@class pinnacle.input.v1.GetDeviceLibinputSettingsResponse
+---@field accel_profile pinnacle.input.v1.AccelProfile?
+---@field accel_speed number?
+---@field calibration_matrix pinnacle.input.v1.CalibrationMatrix?
+---@field click_method pinnacle.input.v1.ClickMethod?
+---@field disable_while_typing boolean?
+---@field left_handed boolean?
+---@field middle_emulation boolean?
+---@field rotation_angle integer?
+---@field scroll_button integer?
+---@field scroll_button_lock boolean?
+---@field scroll_method pinnacle.input.v1.ScrollMethod?
+---@field natural_scroll boolean?
+---@field tap_button_map pinnacle.input.v1.TapButtonMap?
+---@field tap_drag boolean?
+---@field tap_drag_lock boolean?
+---@field tap boolean?
+---@field send_events_mode pinnacle.input.v1.SendEventsMode?
+---@field custom_accel pinnacle.input.v1.CustomAccel?
 
 ---@class pinnacle.input.v1.SetDeviceMapTargetRequest
 ---@field device_sysname string?
@@ -1045,11 +1124,23 @@ local pinnacle_v1_Backend = {
 ---@field pipe_stdin boolean?
 ---@field pipe_stdout boolean?
 ---@field pipe_stderr boolean?
+---@field supervision pinnacle.process.v1.Supervision?
+---@field cwd string?
+---@field target_tag_ids integer[]?
+---@field target_output_name string?
+---@field systemd_scope boolean?
+---@field activation_token boolean?
 
 ---@class pinnacle.process.v1.SpawnRequest.EnvsEntry
 ---@field key string?
 ---@field value string?
 
+---@class pinnacle.process.v1.Supervision
+---@field name string?
+---@field restart_policy pinnacle.process.v1.RestartPolicy?
+---@field backoff_ms integer?
+---@field max_retries integer?
+
 ---@class pinnacle.process.v1.SpawnData
 ---@field pid integer?
 ---@field fd_socket_path string?
@@ -1071,6 +1162,24 @@ local pinnacle_v1_Backend = {
 ---@field key string?
 ---@field value string?
 
+---@class pinnacle.process.v1.SupervisedProcess
+---@field name string?
+---@field pid integer?
+---@field cmd string[]?
+---@field restart_policy pinnacle.process.v1.RestartPolicy?
+---@field restart_count integer?
+
+---@class pinnacle.process.v1.GetSupervisedRequest
+
+---@class pinnacle.process.v1.GetSupervisedResponse
+---@field processes pinnacle.process.v1.SupervisedProcess[]?
+
+---@class pinnacle.process.v1.StopSupervisedRequest
+---@field name string?
+
+---@class pinnacle.process.v1.RestartSupervisedRequest
+---@field name string?
+
 ---@class pinnacle.render.v1.SetUpscaleFilterRequest
 ---@field filter pinnacle.render.v1.Filter?
 
@@ -1354,6 +1463,30 @@ local pinnacle_v1_Backend = {
 ---@class pinnacle.signal.v1.InputDeviceAddedResponse
 ---@field device_sysname string?
 
+---@class pinnacle.signal.v1.InputLayoutChangedRequest
+---@field control pinnacle.signal.v1.StreamControl?
+
+---@class pinnacle.signal.v1.InputLayoutChangedResponse
+---@field layout_index integer?
+---@field layout_name string?
+
+---@class pinnacle.signal.v1.ProcessExitedRequest
+---@field control pinnacle.signal.v1.StreamControl?
+
+---@class pinnacle.signal.v1.ProcessExitedResponse
+---@field pid integer?
+---@field name string?
+---@field exit_code integer?
+---@field exit_msg string?
+---@field restarting boolean?
+
+---@class pinnacle.signal.v1.ScreenCaptureRequestedRequest
+---@field control pinnacle.signal.v1.StreamControl?
+
+---@class pinnacle.signal.v1.ScreenCaptureRequestedResponse
+---@field executable string?
+---@field pid integer?
+
 ---@class pinnacle.tag.v1.GetRequest
 
 ---@class pinnacle.tag.v1.GetResponse
@@ -1432,6 +1565,10 @@ local pinnacle_v1_Backend = {
 ---@class pinnacle.v1.TakeLastErrorResponse
 ---@field error string?
 
+---@class pinnacle.v1.SetScreenCapturePermissionRequest
+---@field executable string?
+---@field allow boolean?
+
 local google = {}
 google.protobuf = {}
 google.protobuf.Empty = {}
@@ -1447,6 +1584,10 @@ pinnacle.debug.v1.SetDamageVisualizationRequest = {}
 pinnacle.debug.v1.SetOpaqueRegionVisualizationRequest = {}
 pinnacle.debug.v1.SetCursorPlaneScanoutRequest = {}
 pinnacle.debug.v1.SetProcessPipingRequest = {}
+pinnacle.debug.v1.SetTraceRecordingRequest = {}
+pinnacle.debug.v1.SetTraceRecordingResponse = {}
+pinnacle.debug.v1.DumpStateRequest = {}
+pinnacle.debug.v1.DumpStateResponse = {}
 pinnacle.input = {}
 pinnacle.input.v1 = {}
 pinnacle.input.v1.Bind = {}
@@ -1472,7 +1613,13 @@ pinnacle.input.v1.SetXkbConfigRequest = {}
 pinnacle.input.v1.SetRepeatRateRequest = {}
 pinnacle.input.v1.SetXkbKeymapRequest = {}
 pinnacle.input.v1.SwitchXkbLayoutRequest = {}
+pinnacle.input.v1.SetXkbLayoutPerWindowRequest = {}
+pinnacle.input.v1.GetXkbLayoutRequest = {}
+pinnacle.input.v1.GetXkbLayoutResponse = {}
 pinnacle.input.v1.SetXcursorRequest = {}
+pinnacle.input.v1.SetCursorHideRequest = {}
+pinnacle.input.v1.CustomAccelCurve = {}
+pinnacle.input.v1.CustomAccel = {}
 pinnacle.input.v1.CalibrationMatrix = {}
 pinnacle.input.v1.GetDevicesRequest = {}
 pinnacle.input.v1.GetDevicesResponse = {}
@@ -1483,6 +1630,8 @@ pinnacle.input.v1.GetDeviceInfoResponse = {}
 pinnacle.input.v1.GetDeviceTypeRequest = {}
 pinnacle.input.v1.GetDeviceTypeResponse = {}
 pinnacle.input.v1.SetDeviceLibinputSettingRequest = {}
+pinnacle.input.v1.GetDeviceLibinputSettingsRequest = {}
+pinnacle.input.v1.GetDeviceLibinputSettingsResponse = {}
 pinnacle.input.v1.SetDeviceMapTargetRequest = {}
 pinnacle.layout = {}
 pinnacle.layout.v1 = {}
@@ -1541,11 +1690,17 @@ pinnacle.process = {}
 pinnacle.process.v1 = {}
 pinnacle.process.v1.SpawnRequest = {}
 pinnacle.process.v1.SpawnRequest.EnvsEntry = {}
+pinnacle.process.v1.Supervision = {}
 pinnacle.process.v1.SpawnData = {}
 pinnacle.process.v1.SpawnResponse = {}
 pinnacle.process.v1.WaitOnSpawnRequest = {}
 pinnacle.process.v1.WaitOnSpawnResponse = {}
 pinnacle.process.v1.SetEnvRequest = {}
+pinnacle.process.v1.SupervisedProcess = {}
+pinnacle.process.v1.GetSupervisedRequest = {}
+pinnacle.process.v1.GetSupervisedResponse = {}
+pinnacle.process.v1.StopSupervisedRequest = {}
+pinnacle.process.v1.RestartSupervisedRequest = {}
 pinnacle.render = {}
 pinnacle.render.v1 = {}
 pinnacle.render.v1.SetUpscaleFilterRequest = {}
@@ -1638,6 +1793,12 @@ pinnacle.signal.v1.TagRemovedRequest = {}
 pinnacle.signal.v1.TagRemovedResponse = {}
 pinnacle.signal.v1.InputDeviceAddedRequest = {}
 pinnacle.signal.v1.InputDeviceAddedResponse = {}
+pinnacle.signal.v1.InputLayoutChangedRequest = {}
+pinnacle.signal.v1.InputLayoutChangedResponse = {}
+pinnacle.signal.v1.ProcessExitedRequest = {}
+pinnacle.signal.v1.ProcessExitedResponse = {}
+pinnacle.signal.v1.ScreenCaptureRequestedRequest = {}
+pinnacle.signal.v1.ScreenCaptureRequestedResponse = {}
 pinnacle.tag = {}
 pinnacle.tag.v1 = {}
 pinnacle.tag.v1.GetRequest = {}
@@ -1668,6 +1829,7 @@ pinnacle.v1.SetXwaylandClientSelfScaleRequest = {}
 pinnacle.v1.SetLastErrorRequest = {}
 pinnacle.v1.TakeLastErrorRequest = {}
 pinnacle.v1.TakeLastErrorResponse = {}
+pinnacle.v1.SetScreenCapturePermissionRequest = {}
 pinnacle.util.v1.SetOrToggle = pinnacle_util_v1_SetOrToggle
 pinnacle.util.v1.AbsOrRel = pinnacle_util_v1_AbsOrRel
 pinnacle.util.v1.Dir = pinnacle_util_v1_Dir
@@ -1675,6 +1837,7 @@ pinnacle.input.v1.Modifier = pinnacle_input_v1_Modifier
 pinnacle.input.v1.Edge = pinnacle_input_v1_Edge
 pinnacle.input.v1.ClickMethod = pinnacle_input_v1_ClickMethod
 pinnacle.input.v1.AccelProfile = pinnacle_input_v1_AccelProfile
+pinnacle.input.v1.AccelType = pinnacle_input_v1_AccelType
 pinnacle.input.v1.ScrollMethod = pinnacle_input_v1_ScrollMethod
 pinnacle.input.v1.TapButtonMap = pinnacle_input_v1_TapButtonMap
 pinnacle.input.v1.SendEventsMode = pinnacle_input_v1_SendEventsMode
@@ -1682,6 +1845,7 @@ pinnacle.input.v1.DeviceType = pinnacle_input_v1_DeviceType
 pinnacle.layout.v1.FlexDir = pinnacle_layout_v1_FlexDir
 pinnacle.output.v1.Transform = pinnacle_output_v1_Transform
 pinnacle.output.v1.Vrr = pinnacle_output_v1_Vrr
+pinnacle.process.v1.RestartPolicy = pinnacle_process_v1_RestartPolicy
 pinnacle.render.v1.Filter = pinnacle_render_v1_Filter
 pinnacle.window.v1.LayoutMode = pinnacle_window_v1_LayoutMode
 pinnacle.window.v1.DecorationMode = pinnacle_window_v1_DecorationMode
@@ -1757,6 +1921,40 @@ pinnacle.debug.v1.DebugService.SetProcessPiping.response = ".google.protobuf.Emp
 function Client:pinnacle_debug_v1_DebugService_SetProcessPiping(data)
     return self:unary_request(pinnacle.debug.v1.DebugService.SetProcessPiping, data)
 end
+pinnacle.debug.v1.DebugService.SetTraceRecording = {}
+pinnacle.debug.v1.DebugService.SetTraceRecording.service = "pinnacle.debug.v1.DebugService"
+pinnacle.debug.v1.DebugService.SetTraceRecording.method = "SetTraceRecording"
+pinnacle.debug.v1.DebugService.SetTraceRecording.request = ".pinnacle.debug.v1.SetTraceRecordingRequest"
+pinnacle.debug.v1.DebugService.SetTraceRecording.response = ".pinnacle.debug.v1.SetTraceRecordingResponse"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data pinnacle.debug.v1.SetTraceRecordingRequest
+---
+---@return pinnacle.debug.v1.SetTraceRecordingResponse | nil response
+---@return string | nil error An error string, if any
+function Client:pinnacle_debug_v1_DebugService_SetTraceRecording(data)
+    return self:unary_request(pinnacle.debug.v1.DebugService.SetTraceRecording, data)
+end
+pinnacle.debug.v1.DebugService.DumpState = {}
+pinnacle.debug.v1.DebugService.DumpState.service = "pinnacle.debug.v1.DebugService"
+pinnacle.debug.v1.DebugService.DumpState.method = "DumpState"
+pinnacle.debug.v1.DebugService.DumpState.request = ".pinnacle.debug.v1.DumpStateRequest"
+pinnacle.debug.v1.DebugService.DumpState.response = ".pinnacle.debug.v1.DumpStateResponse"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data pinnacle.debug.v1.DumpStateRequest
+---
+---@return pinnacle.debug.v1.DumpStateResponse | nil response
+---@return string | nil error An error string, if any
+function Client:pinnacle_debug_v1_DebugService_DumpState(data)
+    return self:unary_request(pinnacle.debug.v1.DebugService.DumpState, data)
+end
 pinnacle.input.v1.InputService = {}
 pinnacle.input.v1.InputService.Bind = {}
 pinnacle.input.v1.InputService.Bind.service = "pinnacle.input.v1.InputService"
@@ -1985,6 +2183,40 @@ pinnacle.input.v1.InputService.SwitchXkbLayout.response = ".google.protobuf.Empt
 function Client:pinnacle_input_v1_InputService_SwitchXkbLayout(data)
     return self:unary_request(pinnacle.input.v1.InputService.SwitchXkbLayout, data)
 end
+pinnacle.input.v1.InputService.SetXkbLayoutPerWindow = {}
+pinnacle.input.v1.InputService.SetXkbLayoutPerWindow.service = "pinnacle.input.v1.InputService"
+pinnacle.input.v1.InputService.SetXkbLayoutPerWindow.method = "SetXkbLayoutPerWindow"
+pinnacle.input.v1.InputService.SetXkbLayoutPerWindow.request = ".pinnacle.input.v1.SetXkbLayoutPerWindowRequest"
+pinnacle.input.v1.InputService.SetXkbLayoutPerWindow.response = ".google.protobuf.Empty"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data pinnacle.input.v1.SetXkbLayoutPerWindowRequest
+---
+---@return google.protobuf.Empty | nil response
+---@return string | nil error An error string, if any
+function Client:pinnacle_input_v1_InputService_SetXkbLayoutPerWindow(data)
+    return self:unary_request(pinnacle.input.v1.InputService.SetXkbLayoutPerWindow, data)
+end
+pinnacle.input.v1.InputService.GetXkbLayout = {}
+pinnacle.input.v1.InputService.GetXkbLayout.service = "pinnacle.input.v1.InputService"
+pinnacle.input.v1.InputService.GetXkbLayout.method = "GetXkbLayout"
+pinnacle.input.v1.InputService.GetXkbLayout.request = ".pinnacle.input.v1.GetXkbLayoutRequest"
+pinnacle.input.v1.InputService.GetXkbLayout.response = ".pinnacle.input.v1.GetXkbLayoutResponse"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data pinnacle.input.v1.GetXkbLayoutRequest
+---
+---@return pinnacle.input.v1.GetXkbLayoutResponse | nil response
+---@return string | nil error An error string, if any
+function Client:pinnacle_input_v1_InputService_GetXkbLayout(data)
+    return self:unary_request(pinnacle.input.v1.InputService.GetXkbLayout, data)
+end
 pinnacle.input.v1.InputService.SetXcursor = {}
 pinnacle.input.v1.InputService.SetXcursor.service = "pinnacle.input.v1.InputService"
 pinnacle.input.v1.InputService.SetXcursor.method = "SetXcursor"
@@ -2002,6 +2234,23 @@ pinnacle.input.v1.InputService.SetXcursor.response = ".google.protobuf.Empty"
 function Client:pinnacle_input_v1_InputService_SetXcursor(data)
     return self:unary_request(pinnacle.input.v1.InputService.SetXcursor, data)
 end
+pinnacle.input.v1.InputService.SetCursorHide = {}
+pinnacle.input.v1.InputService.SetCursorHide.service = "pinnacle.input.v1.InputService"
+pinnacle.input.v1.InputService.SetCursorHide.method = "SetCursorHide"
+pinnacle.input.v1.InputService.SetCursorHide.request = ".pinnacle.input.v1.SetCursorHideRequest"
+pinnacle.input.v1.InputService.SetCursorHide.response = ".google.protobuf.Empty"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data pinnacle.input.v1.SetCursorHideRequest
+---
+---@return google.protobuf.Empty | nil response
+---@return string | nil error An error string, if any
+function Client:pinnacle_input_v1_InputService_SetCursorHide(data)
+    return self:unary_request(pinnacle.input.v1.InputService.SetCursorHide, data)
+end
 pinnacle.input.v1.InputService.GetDevices = {}
 pinnacle.input.v1.InputService.GetDevices.service = "pinnacle.input.v1.InputService"
 pinnacle.input.v1.InputService.GetDevices.method = "GetDevices"
@@ -2087,6 +2336,23 @@ pinnacle.input.v1.InputService.SetDeviceLibinputSetting.response = ".google.prot
 function Client:pinnacle_input_v1_InputService_SetDeviceLibinputSetting(data)
     return self:unary_request(pinnacle.input.v1.InputService.SetDeviceLibinputSetting, data)
 end
+pinnacle.input.v1.InputService.GetDeviceLibinputSettings = {}
+pinnacle.input.v1.InputService.GetDeviceLibinputSettings.service = "pinnacle.input.v1.InputService"
+pinnacle.input.v1.InputService.GetDeviceLibinputSettings.method = "GetDeviceLibinputSettings"
+pinnacle.input.v1.InputService.GetDeviceLibinputSettings.request = ".pinnacle.input.v1.GetDeviceLibinputSettingsRequest"
+pinnacle.input.v1.InputService.GetDeviceLibinputSettings.response = ".pinnacle.input.v1.GetDeviceLibinputSettingsResponse"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data pinnacle.input.v1.GetDeviceLibinputSettingsRequest
+---
+---@return pinnacle.input.v1.GetDeviceLibinputSettingsResponse | nil response
+---@return string | nil error An error string, if any
+function Client:pinnacle_input_v1_InputService_GetDeviceLibinputSettings(data)
+    return self:unary_request(pinnacle.input.v1.InputService.GetDeviceLibinputSettings, data)
+end
 pinnacle.input.v1.InputService.SetDeviceMapTarget = {}
 pinnacle.input.v1.InputService.SetDeviceMapTarget.service = "pinnacle.input.v1.InputService"
 pinnacle.input.v1.InputService.SetDeviceMapTarget.method = "SetDeviceMapTarget"
@@ -2557,6 +2823,57 @@ pinnacle.process.v1.ProcessService.SetEnv.response = ".google.protobuf.Empty"
 function Client:pinnacle_process_v1_ProcessService_SetEnv(data)
     return self:unary_request(pinnacle.process.v1.ProcessService.SetEnv, data)
 end
+pinnacle.process.v1.ProcessService.GetSupervised = {}
+pinnacle.process.v1.ProcessService.GetSupervised.service = "pinnacle.process.v1.ProcessService"
+pinnacle.process.v1.ProcessService.GetSupervised.method = "GetSupervised"
+pinnacle.process.v1.ProcessService.GetSupervised.request = ".pinnacle.process.v1.GetSupervisedRequest"
+pinnacle.process.v1.ProcessService.GetSupervised.response = ".pinnacle.process.v1.GetSupervisedResponse"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data pinnacle.process.v1.GetSupervisedRequest
+---
+---@return pinnacle.process.v1.GetSupervisedResponse | nil response
+---@return string | nil error An error string, if any
+function Client:pinnacle_process_v1_ProcessService_GetSupervised(data)
+    return self:unary_request(pinnacle.process.v1.ProcessService.GetSupervised, data)
+end
+pinnacle.process.v1.ProcessService.StopSupervised = {}
+pinnacle.process.v1.ProcessService.StopSupervised.service = "pinnacle.process.v1.ProcessService"
+pinnacle.process.v1.ProcessService.StopSupervised.method = "StopSupervised"
+pinnacle.process.v1.ProcessService.StopSupervised.request = ".pinnacle.process.v1.StopSupervisedRequest"
+pinnacle.process.v1.ProcessService.StopSupervised.response = ".google.protobuf.Empty"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data pinnacle.process.v1.StopSupervisedRequest
+---
+---@return google.protobuf.Empty | nil response
+---@return string | nil error An error string, if any
+function Client:pinnacle_process_v1_ProcessService_StopSupervised(data)
+    return self:unary_request(pinnacle.process.v1.ProcessService.StopSupervised, data)
+end
+pinnacle.process.v1.ProcessService.RestartSupervised = {}
+pinnacle.process.v1.ProcessService.RestartSupervised.service = "pinnacle.process.v1.ProcessService"
+pinnacle.process.v1.ProcessService.RestartSupervised.method = "RestartSupervised"
+pinnacle.process.v1.ProcessService.RestartSupervised.request = ".pinnacle.process.v1.RestartSupervisedRequest"
+pinnacle.process.v1.ProcessService.RestartSupervised.response = ".google.protobuf.Empty"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data pinnacle.process.v1.RestartSupervisedRequest
+---
+---@return google.protobuf.Empty | nil response
+---@return string | nil error An error string, if any
+function Client:pinnacle_process_v1_ProcessService_RestartSupervised(data)
+    return self:unary_request(pinnacle.process.v1.ProcessService.RestartSupervised, data)
+end
 pinnacle.render.v1.RenderService = {}
 pinnacle.render.v1.RenderService.SetUpscaleFilter = {}
 pinnacle.render.v1.RenderService.SetUpscaleFilter.service = "pinnacle.render.v1.RenderService"
@@ -3488,6 +3805,72 @@ pinnacle.signal.v1.SignalService.InputDeviceAdded.response = ".pinnacle.signal.v
 function Client:pinnacle_signal_v1_SignalService_InputDeviceAdded(callback, done)
     return self:bidirectional_streaming_request(pinnacle.signal.v1.SignalService.InputDeviceAdded, callback, done)
 end
+pinnacle.signal.v1.SignalService.InputLayoutChanged = {}
+pinnacle.signal.v1.SignalService.InputLayoutChanged.service = "pinnacle.signal.v1.SignalService"
+pinnacle.signal.v1.SignalService.InputLayoutChanged.method = "InputLayoutChanged"
+pinnacle.signal.v1.SignalService.InputLayoutChanged.request = ".pinnacle.signal.v1.InputLayoutChangedRequest"
+pinnacle.signal.v1.SignalService.InputLayoutChanged.response = ".pinnacle.signal.v1.InputLayoutChangedResponse"
+
+---Performs a bidirectional-streaming request.
+---
+---`callback` will be called with every streamed response.
+---
+---The raw client-to-server stream is returned to allow you to send encoded messages.
+---
+---@nodiscard
+---
+---@param callback fun(response: pinnacle.signal.v1.InputLayoutChangedResponse, stream: grpc_client.h2.Stream)
+---@param done? fun()
+---
+---@return grpc_client.h2.Stream | nil
+---@return string | nil An error string, if any
+function Client:pinnacle_signal_v1_SignalService_InputLayoutChanged(callback, done)
+    return self:bidirectional_streaming_request(pinnacle.signal.v1.SignalService.InputLayoutChanged, callback, done)
+end
+pinnacle.signal.v1.SignalService.ProcessExited = {}
+pinnacle.signal.v1.SignalService.ProcessExited.service = "pinnacle.signal.v1.SignalService"
+pinnacle.signal.v1.SignalService.ProcessExited.method = "ProcessExited"
+pinnacle.signal.v1.SignalService.ProcessExited.request = ".pinnacle.signal.v1.ProcessExitedRequest"
+pinnacle.signal.v1.SignalService.ProcessExited.response = ".pinnacle.signal.v1.ProcessExitedResponse"
+
+---Performs a bidirectional-streaming request.
+---
+---`callback` will be called with every streamed response.
+---
+---The raw client-to-server stream is returned to allow you to send encoded messages.
+---
+---@nodiscard
+---
+---@param callback fun(response: pinnacle.signal.v1.ProcessExitedResponse, stream: grpc_client.h2.Stream)
+---@param done? fun()
+---
+---@return grpc_client.h2.Stream | nil
+---@return string | nil An error string, if any
+function Client:pinnacle_signal_v1_SignalService_ProcessExited(callback, done)
+    return self:bidirectional_streaming_request(pinnacle.signal.v1.SignalService.ProcessExited, callback, done)
+end
+pinnacle.signal.v1.SignalService.ScreenCaptureRequested = {}
+pinnacle.signal.v1.SignalService.ScreenCaptureRequested.service = "pinnacle.signal.v1.SignalService"
+pinnacle.signal.v1.SignalService.ScreenCaptureRequested.method = "ScreenCaptureRequested"
+pinnacle.signal.v1.SignalService.ScreenCaptureRequested.request = ".pinnacle.signal.v1.ScreenCaptureRequestedRequest"
+pinnacle.signal.v1.SignalService.ScreenCaptureRequested.response = ".pinnacle.signal.v1.ScreenCaptureRequestedResponse"
+
+---Performs a bidirectional-streaming request.
+---
+---`callback` will be called with every streamed response.
+---
+---The raw client-to-server stream is returned to allow you to send encoded messages.
+---
+---@nodiscard
+---
+---@param callback fun(response: pinnacle.signal.v1.ScreenCaptureRequestedResponse, stream: grpc_client.h2.Stream)
+---@param done? fun()
+---
+---@return grpc_client.h2.Stream | nil
+---@return string | nil An error string, if any
+function Client:pinnacle_signal_v1_SignalService_ScreenCaptureRequested(callback, done)
+    return self:bidirectional_streaming_request(pinnacle.signal.v1.SignalService.ScreenCaptureRequested, callback, done)
+end
 pinnacle.tag.v1.TagService = {}
 pinnacle.tag.v1.TagService.Get = {}
 pinnacle.tag.v1.TagService.Get.service = "pinnacle.tag.v1.TagService"
@@ -3767,6 +4150,23 @@ pinnacle.v1.PinnacleService.TakeLastError.response = ".pinnacle.v1.TakeLastError
 function Client:pinnacle_v1_PinnacleService_TakeLastError(data)
     return self:unary_request(pinnacle.v1.PinnacleService.TakeLastError, data)
 end
+pinnacle.v1.PinnacleService.SetScreenCapturePermission = {}
+pinnacle.v1.PinnacleService.SetScreenCapturePermission.service = "pinnacle.v1.PinnacleService"
+pinnacle.v1.PinnacleService.SetScreenCapturePermission.method = "SetScreenCapturePermission"
+pinnacle.v1.PinnacleService.SetScreenCapturePermission.request = ".pinnacle.v1.SetScreenCapturePermissionRequest"
+pinnacle.v1.PinnacleService.SetScreenCapturePermission.response = ".google.protobuf.Empty"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data pinnacle.v1.SetScreenCapturePermissionRequest
+---
+---@return google.protobuf.Empty | nil response
+---@return string | nil error An error string, if any
+function Client:pinnacle_v1_PinnacleService_SetScreenCapturePermission(data)
+    return self:unary_request(pinnacle.v1.PinnacleService.SetScreenCapturePermission, data)
+end
 return {
     google = google,
     pinnacle = pinnacle,
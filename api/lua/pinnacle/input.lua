@@ -660,6 +660,58 @@ function input.switch_xkb_layout(index)
     end
 end
 
+---Sets whether the current XKB layout is remembered per window.
+---
+---When enabled, the active layout is saved when a window loses keyboard focus
+---and restored when it regains it. Windows that have never been focused
+---keep whatever layout is currently active.
+---
+---#### Example
+---```lua
+---Input.set_xkb_layout_per_window(true)
+---```
+---
+---@param per_window boolean
+function input.set_xkb_layout_per_window(per_window)
+    local _, err = client:pinnacle_input_v1_InputService_SetXkbLayoutPerWindow({
+        per_window = per_window,
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
+---An XKB layout.
+---@class pinnacle.input.XkbLayout
+---@field index integer The index of this layout in the keymap.
+---@field name string The name of this layout, e.g. "English (US)".
+
+---Gets the currently active XKB layout.
+---
+---#### Example
+---```lua
+---local layout = Input.xkb_layout()
+---print("Current layout: " .. layout.name)
+---```
+---
+---@return pinnacle.input.XkbLayout
+function input.xkb_layout()
+    local response, err = client:pinnacle_input_v1_InputService_GetXkbLayout({})
+
+    if err then
+        log.error(err)
+    end
+
+    ---@type pinnacle.input.XkbLayout
+    local layout = {
+        index = response and response.layout_index or 0,
+        name = response and response.layout_name or "",
+    }
+
+    return layout
+end
+
 ---Sets the current xcursor theme.
 ---
 ---Pinnacle reads `$XCURSOR_THEME` on startup to set the theme.
@@ -692,11 +744,51 @@ function input.set_xcursor_size(size)
     end
 end
 
+---Sets whether the cursor is hidden when typing.
+---
+---When enabled, pressing a key that goes to a focused window hides the cursor
+---until the pointer, a touch, or a tablet tool moves again. Keys that trigger keybinds
+---or go to layer surfaces, like launchers, don't hide the cursor.
+---
+---@param hide_when_typing boolean
+function input.set_cursor_hide_when_typing(hide_when_typing)
+    local _, err = client:pinnacle_input_v1_InputService_SetCursorHide({
+        hide_when_typing = hide_when_typing,
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
+---Sets how long the pointer must not move before the cursor is hidden.
+---
+---The cursor reappears when the pointer moves again.
+---
+---#### Example
+---```lua
+---Input.set_cursor_hide_timeout(5000) -- Hide after 5 seconds
+---Input.set_cursor_hide_timeout(nil) -- Never hide after inactivity
+---```
+---
+---@param timeout_ms integer? The timeout in milliseconds, or nil to disable hiding after inactivity.
+function input.set_cursor_hide_timeout(timeout_ms)
+    local _, err = client:pinnacle_input_v1_InputService_SetCursorHide({
+        timeout_ms = timeout_ms or 0,
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
 ---@class pinnacle.input.InputSignal Signals related to input events.
 ---@field device_added fun(device: pinnacle.input.libinput.DeviceHandle)? A new input device was connected.
+---@field layout_changed fun(layout: pinnacle.input.XkbLayout)? The active XKB layout changed.
 
 local signal_name_to_SignalName = {
     device_added = "InputDeviceAdded",
+    layout_changed = "InputLayoutChanged",
 }
 
 ---Connects to an input signal.
@@ -18,7 +18,35 @@ local accel_profile_values = {
     flat = input_v1.AccelProfile.ACCEL_PROFILE_FLAT,
     ---Pointer acceleration
     adaptive = input_v1.AccelProfile.ACCEL_PROFILE_ADAPTIVE,
+    ---User-defined pointer acceleration, see `DeviceHandle:set_custom_accel`
+    custom = input_v1.AccelProfile.ACCEL_PROFILE_CUSTOM,
 }
+require("pinnacle.util").make_bijective(accel_profile_values)
+
+---The type of movement a custom acceleration curve applies to.
+---@enum (key) pinnacle.input.libinput.AccelType
+local accel_type_values = {
+    ---Movement that doesn't have a more specific curve set
+    fallback = input_v1.AccelType.ACCEL_TYPE_FALLBACK,
+    ---Pointer motion
+    motion = input_v1.AccelType.ACCEL_TYPE_MOTION,
+    ---Scrolling
+    scroll = input_v1.AccelType.ACCEL_TYPE_SCROLL,
+}
+require("pinnacle.util").make_bijective(accel_type_values)
+
+---A custom pointer acceleration function.
+---
+---The function is defined by `points` spaced uniformly along the x-axis,
+---starting at 0 and increasing by `step`. The x-axis is the device speed
+---in device units per millisecond, and the y-axis is the resulting pointer speed.
+---@class pinnacle.input.libinput.CustomAccelCurve
+---The type of movement this curve applies to.
+---@field accel_type pinnacle.input.libinput.AccelType
+---The distance between points on the x-axis. Must be greater than 0.
+---@field step number
+---Between 2 and 64 non-negative y-values.
+---@field points number[]
 
 ---The click method defines when to generate software-emulated buttons, usually on a device
 ---that does not have a specific physical button available.
@@ -29,6 +57,7 @@ local click_method_values = {
     ---Button presses are generated according to the number of fingers used
     click_finger = input_v1.ClickMethod.CLICK_METHOD_CLICK_FINGER,
 }
+require("pinnacle.util").make_bijective(click_method_values)
 
 ---The scroll method of a device selects when to generate scroll axis events instead of pointer motion events.
 ---@enum (key) pinnacle.input.libinput.ScrollMethod
@@ -42,6 +71,7 @@ local scroll_method_values = {
     ---Send scroll events when a button is down and the device moves along a scroll-capable axis
     on_button_down = input_v1.ScrollMethod.SCROLL_METHOD_ON_BUTTON_DOWN,
 }
+require("pinnacle.util").make_bijective(scroll_method_values)
 
 ---Map 1/2/3 finger taps to buttons.
 ---@enum (key) pinnacle.input.libinput.TapButtonMap
@@ -51,6 +81,7 @@ local tap_button_map_values = {
     ---1/2/3 finger tap maps to left/middle/right
     left_middle_right = input_v1.TapButtonMap.TAP_BUTTON_MAP_LEFT_MIDDLE_RIGHT,
 }
+require("pinnacle.util").make_bijective(tap_button_map_values)
 
 ---A libinput send events mode.
 ---@enum (key) pinnacle.input.libinput.SendEventsMode
@@ -62,6 +93,7 @@ local send_events_mode_values = {
     ---Disables this device only when an external mouse is connected.
     disabled_on_external_mouse = input_v1.SendEventsMode.SEND_EVENTS_MODE_DISABLED_ON_EXTERNAL_MOUSE,
 }
+require("pinnacle.util").make_bijective(send_events_mode_values)
 
 ---A handle to an input device.
 ---@class pinnacle.input.libinput.DeviceHandle
@@ -234,6 +266,106 @@ function DeviceHandle:device_type()
     return type
 end
 
+---Gets the acceleration profiles this device supports.
+---
+---@return pinnacle.input.libinput.AccelProfile[]
+function DeviceHandle:accel_profiles()
+    local response, err = client:pinnacle_input_v1_InputService_GetDeviceInfo({
+        device_sysname = self.sysname,
+    })
+
+    if err then
+        log.warn(err)
+        return {}
+    end
+
+    assert(response)
+
+    ---@type pinnacle.input.libinput.AccelProfile[]
+    local profiles = {}
+
+    for _, profile in ipairs(response.accel_profiles or {}) do
+        table.insert(profiles, accel_profile_values[profile])
+    end
+
+    return profiles
+end
+
+---The current libinput settings of a device.
+---
+---Settings the device doesn't support are nil.
+---@class pinnacle.input.libinput.LibinputSettings
+---@field accel_profile pinnacle.input.libinput.AccelProfile?
+---@field accel_speed number?
+---@field calibration_matrix number[]?
+---@field click_method pinnacle.input.libinput.ClickMethod?
+---@field disable_while_typing boolean?
+---@field left_handed boolean?
+---@field middle_emulation boolean?
+---@field rotation_angle integer?
+---@field scroll_button integer?
+---@field scroll_button_lock boolean?
+---@field scroll_method pinnacle.input.libinput.ScrollMethod?
+---@field natural_scroll boolean?
+---@field tap_button_map pinnacle.input.libinput.TapButtonMap?
+---@field tap_drag boolean?
+---@field tap_drag_lock boolean?
+---@field tap boolean?
+---@field send_events_mode pinnacle.input.libinput.SendEventsMode?
+---The custom acceleration curves, if the custom acceleration profile is active.
+---@field custom_accel pinnacle.input.libinput.CustomAccelCurve[]?
+
+---Gets this device's current libinput settings.
+---
+---@return pinnacle.input.libinput.LibinputSettings
+function DeviceHandle:libinput_settings()
+    local response, err = client:pinnacle_input_v1_InputService_GetDeviceLibinputSettings({
+        device_sysname = self.sysname,
+    })
+
+    if err then
+        log.warn(err)
+        return {}
+    end
+
+    assert(response)
+
+    ---@type pinnacle.input.libinput.CustomAccelCurve[]?
+    local custom_accel = nil
+    if response.custom_accel then
+        custom_accel = {}
+        for _, curve in ipairs(response.custom_accel.curves or {}) do
+            table.insert(custom_accel, {
+                accel_type = accel_type_values[curve.accel_type],
+                step = curve.step or 0,
+                points = curve.points or {},
+            })
+        end
+    end
+
+    ---@type pinnacle.input.libinput.LibinputSettings
+    return {
+        accel_profile = accel_profile_values[response.accel_profile],
+        accel_speed = response.accel_speed,
+        calibration_matrix = response.calibration_matrix and response.calibration_matrix.matrix,
+        click_method = click_method_values[response.click_method],
+        disable_while_typing = response.disable_while_typing,
+        left_handed = response.left_handed,
+        middle_emulation = response.middle_emulation,
+        rotation_angle = response.rotation_angle,
+        scroll_button = response.scroll_button,
+        scroll_button_lock = response.scroll_button_lock,
+        scroll_method = scroll_method_values[response.scroll_method],
+        natural_scroll = response.natural_scroll,
+        tap_button_map = tap_button_map_values[response.tap_button_map],
+        tap_drag = response.tap_drag,
+        tap_drag_lock = response.tap_drag_lock,
+        tap = response.tap,
+        send_events_mode = send_events_mode_values[response.send_events_mode],
+        custom_accel = custom_accel,
+    }
+end
+
 ---Maps the absolute input from this device to the corresponding output.
 ---
 ---This will cause touch input from this device to map proportionally
@@ -301,6 +433,46 @@ function DeviceHandle:set_accel_speed(accel_speed)
     })
 end
 
+---Sets this device's acceleration profile to `"custom"` with the given acceleration curves.
+---
+---At most one curve can be given per acceleration type. Movement types without
+---a curve use the `"fallback"` curve, or a flat profile if that isn't set either.
+---
+---This only has an effect on devices that support the custom profile;
+---see `DeviceHandle:accel_profiles`.
+---
+---#### Example
+---```lua
+---device:set_custom_accel({
+---    { accel_type = "motion", step = 1.0, points = { 0.0, 1.0, 2.5, 4.0, 6.0 } },
+---})
+---```
+---
+---@param curves pinnacle.input.libinput.CustomAccelCurve[]
+function DeviceHandle:set_custom_accel(curves)
+    ---@type pinnacle.input.v1.CustomAccelCurve[]
+    local custom_curves = {}
+
+    for _, curve in ipairs(curves) do
+        table.insert(custom_curves, {
+            accel_type = accel_type_values[curve.accel_type],
+            step = curve.step,
+            points = curve.points,
+        })
+    end
+
+    local _, err = client:pinnacle_input_v1_InputService_SetDeviceLibinputSetting({
+        device_sysname = self.sysname,
+        custom_accel = {
+            curves = custom_curves,
+        },
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
 ---Sets this device's calibration matrix.
 ---
 ---@param calibration_matrix number[] The calibration matrix as an array of 6 floats.
@@ -453,6 +625,70 @@ function DeviceHandle:set_send_events_mode(send_events_mode)
     })
 end
 
+---Sets the xkeyboard config for this device.
+---
+---This overrides the seat-wide config set with `Input.set_xkb_config`
+---for key presses on this device.
+---
+---#### Example
+---```lua
+---Libinput.for_each_device(function(device)
+---    if device:name() == "My USB Keyboard" then
+---        device:set_xkb_config({ layout = "us", variant = "colemak" })
+---    end
+---end)
+---```
+---
+---@param xkb_config pinnacle.input.XkbConfig
+function DeviceHandle:set_xkb_config(xkb_config)
+    local _, err = client:pinnacle_input_v1_InputService_SetXkbConfig({
+        rules = xkb_config.rules,
+        model = xkb_config.model,
+        layout = xkb_config.layout,
+        variant = xkb_config.variant,
+        options = xkb_config.options,
+        device_sysname = self.sysname,
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
+---Sets the XKB keymap for this device.
+---
+---This overrides the seat-wide keymap for key presses on this device.
+---
+---@param keymap string
+function DeviceHandle:set_xkb_keymap(keymap)
+    local _, err = client:pinnacle_input_v1_InputService_SetXkbKeymap({
+        keymap = keymap,
+        device_sysname = self.sysname,
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
+---Sets the repeat rate and delay for this device.
+---
+---This overrides the seat-wide repeat rate for key presses on this device.
+---
+---@param rate integer The time between repeats in milliseconds
+---@param delay integer The duration a key needs to be held down before repeating starts in milliseconds
+function DeviceHandle:set_repeat_rate(rate, delay)
+    local _, err = client:pinnacle_input_v1_InputService_SetRepeatRate({
+        rate = rate,
+        delay = delay,
+        device_sysname = self.sysname,
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
 ---Gets all connected input devices.
 ---
 ---@return pinnacle.input.libinput.DeviceHandle[]
@@ -5,6 +5,19 @@
 local log = require("pinnacle.log")
 local client = require("pinnacle.grpc.client").client
 local condition = require("cqueues.condition")
+local process_v1 = require("pinnacle.grpc.defs").pinnacle.process.v1
+
+---When a supervised process should be restarted.
+---@enum (key) pinnacle.process.RestartPolicy
+local restart_policy_values = {
+    ---Never restart the process
+    never = process_v1.RestartPolicy.RESTART_POLICY_NEVER,
+    ---Restart the process if it exits unsuccessfully
+    on_failure = process_v1.RestartPolicy.RESTART_POLICY_ON_FAILURE,
+    ---Always restart the process when it exits
+    always = process_v1.RestartPolicy.RESTART_POLICY_ALWAYS,
+}
+require("pinnacle.util").make_bijective(restart_policy_values)
 
 ---The standard input of a spawned process.
 ---@class pinnacle.process.ChildStdin
@@ -74,6 +87,15 @@ end
 ---@field private pipe_stdin boolean?
 ---@field private pipe_stdout boolean?
 ---@field private pipe_stderr boolean?
+---@field private supervise string?
+---@field private restart_policy pinnacle.process.RestartPolicy?
+---@field private restart_backoff_ms integer?
+---@field private max_retries integer?
+---@field private cwd string?
+---@field private target_tags pinnacle.tag.TagHandle[]?
+---@field private target_output pinnacle.output.OutputHandle?
+---@field private systemd_scope boolean?
+---@field private activation_token boolean?
 local Command = {}
 
 ---Options for a command.
@@ -101,11 +123,44 @@ local Command = {}
 ---
 ---The pipe will be available through the spawned child's `stderr`.
 ---@field pipe_stderr boolean?
+---Supervises the spawned process under this name, restarting it when it exits.
+---
+---Spawning a supervised process while one with the same name is running does nothing,
+---so supervised processes survive config reloads.
+---Restarted processes will not have their standard IO piped.
+---@field supervise string?
+---When the supervised process should be restarted. Defaults to `"on_failure"`.
+---@field restart_policy pinnacle.process.RestartPolicy?
+---The delay in milliseconds before the supervised process is first restarted.
+---The delay doubles with every consecutive restart. Defaults to 1000.
+---@field restart_backoff_ms integer?
+---The maximum number of consecutive restarts before the supervisor gives up.
+---@field max_retries integer?
+---The working directory of the process.
+---@field cwd string?
+---Places the first window mapped by the process or its descendants on these tags.
+---Window rules take precedence over this.
+---@field target_tags pinnacle.tag.TagHandle[]?
+---Places the first window mapped by the process or its descendants on this output's active tags.
+---Ignored if `target_tags` is set. Window rules take precedence over this.
+---@field target_output pinnacle.output.OutputHandle?
+---Spawns the process in its own transient systemd scope so it isn't killed along with the
+---compositor's unit and its resource usage can be attributed separately.
+---If `systemd-run` isn't available, the process is spawned normally.
+---@field systemd_scope boolean?
+---Passes a fresh xdg-activation token to the process in `XDG_ACTIVATION_TOKEN` and
+---`DESKTOP_STARTUP_ID`, letting its first window take focus.
+---@field activation_token boolean?
 
 ---Spawns this process, returning a `Child` that contains the process's standard IO if successful.
 ---
 ---@return pinnacle.process.Child? # A child with the process's standard IO, or `nil` if the process failed to spawn or doesn't exist.
 function Command:spawn()
+    local target_tag_ids = {}
+    for _, tag in ipairs(self.target_tags or {}) do
+        table.insert(target_tag_ids, tag.id)
+    end
+
     local response, err = client:pinnacle_process_v1_ProcessService_Spawn({
         cmd = type(self.cmd) == "string" and { self.cmd } or self.cmd,
         shell_cmd = self.shell_cmd,
@@ -115,6 +170,17 @@ function Command:spawn()
         pipe_stdin = self.pipe_stdin,
         pipe_stdout = self.pipe_stdout,
         pipe_stderr = self.pipe_stderr,
+        supervision = self.supervise and {
+            name = self.supervise,
+            restart_policy = restart_policy_values[self.restart_policy or "on_failure"],
+            backoff_ms = self.restart_backoff_ms,
+            max_retries = self.max_retries,
+        } or nil,
+        cwd = self.cwd,
+        target_tag_ids = target_tag_ids,
+        target_output_name = self.target_output and self.target_output.name,
+        systemd_scope = self.systemd_scope,
+        activation_token = self.activation_token,
     })
 
     if err then
@@ -234,6 +300,22 @@ function Child:on_line_stderr(on_line)
     return self
 end
 
+---A process spawned with the `supervise` option.
+---@class pinnacle.process.SupervisedProcess
+---@field name string The name of the process.
+---@field pid integer? The pid of the process, or `nil` if it isn't running.
+---@field cmd string[] The command the process was spawned with.
+---@field restart_policy pinnacle.process.RestartPolicy? When the process is restarted.
+---@field restart_count integer The number of consecutive restarts.
+
+---A process spawned by the compositor that exited.
+---@class pinnacle.process.ExitedProcess
+---@field pid integer The pid of the process.
+---@field name string? The name of the process if it is supervised.
+---@field exit_code integer? The process's exit code.
+---@field exit_msg string? The process's exit message.
+---@field restarting boolean Whether the supervisor will restart the process.
+
 ---Process management.
 ---
 ---This module provides utilities to spawn processes and capture their output.
@@ -350,4 +432,107 @@ function process.set_env(key, value)
     end
 end
 
+---Returns all processes spawned with the `supervise` option.
+---
+---This includes processes that have exited and were not restarted.
+---
+---@return pinnacle.process.SupervisedProcess[]
+function process.supervised()
+    local response, err = client:pinnacle_process_v1_ProcessService_GetSupervised({})
+
+    if err then
+        log.error(err)
+        return {}
+    end
+
+    ---@cast response pinnacle.process.v1.GetSupervisedResponse
+
+    ---@type pinnacle.process.SupervisedProcess[]
+    local processes = {}
+
+    for _, proc in ipairs(response.processes or {}) do
+        table.insert(processes, {
+            name = proc.name or "",
+            pid = proc.pid,
+            cmd = proc.cmd or {},
+            restart_policy = restart_policy_values[proc.restart_policy],
+            restart_count = proc.restart_count or 0,
+        })
+    end
+
+    return processes
+end
+
+---Stops the supervised process with the given name.
+---
+---The process will not be restarted until `process.restart_supervised` is called
+---or it is spawned again.
+---
+---@param name string The name of the supervised process.
+function process.stop_supervised(name)
+    local _, err = client:pinnacle_process_v1_ProcessService_StopSupervised({
+        name = name,
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
+---Restarts the supervised process with the given name.
+---
+---If the process isn't running, it is started.
+---
+---@param name string The name of the supervised process.
+function process.restart_supervised(name)
+    local _, err = client:pinnacle_process_v1_ProcessService_RestartSupervised({
+        name = name,
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
+---@class pinnacle.process.ProcessSignal Signals related to processes.
+---@field exited fun(exited: pinnacle.process.ExitedProcess)? A process spawned by the compositor exited.
+
+local signal_name_to_SignalName = {
+    exited = "ProcessExited",
+}
+
+---Connects to a process signal.
+---
+---`signals` is a table containing the signal(s) you want to connect to along with
+---a corresponding callback that will be called when the signal is signalled.
+---
+---This function returns a table of signal handles with each handle stored at the same key used
+---to connect to the signal. See `SignalHandles` for more information.
+---
+---# Example
+---```lua
+---Process.connect_signal({
+---    exited = function(exited)
+---        print("Process exited", exited.pid, exited.exit_code)
+---    end
+---})
+---```
+---@param signals pinnacle.process.ProcessSignal The signal you want to connect to
+---
+---@return pinnacle.signal.SignalHandles signal_handles Handles to every signal you connected to wrapped in a table, with keys being the same as the connected signal.
+---
+---@see pinnacle.signal.SignalHandles.disconnect_all - To disconnect from these signals
+function process.connect_signal(signals)
+    ---@diagnostic disable-next-line: invisible
+    local handles = require("pinnacle.signal").handles.new()
+
+    for signal, callback in pairs(signals) do
+        local handle =
+            require("pinnacle.signal").add_callback(signal_name_to_SignalName[signal], callback)
+        handles[signal] = handle
+    end
+
+    return handles
+end
+
 return process
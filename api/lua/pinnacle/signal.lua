@@ -157,6 +157,30 @@ local signals = {
         ---@type fun(response: table)
         on_response = nil,
     },
+    InputLayoutChanged = {
+        ---@type grpc_client.h2.Stream?
+        sender = nil,
+        ---@type { callback_id: integer, callback: fun(layout: pinnacle.input.XkbLayout) }[]
+        callbacks = {},
+        ---@type fun(response: table)
+        on_response = nil,
+    },
+    ProcessExited = {
+        ---@type grpc_client.h2.Stream?
+        sender = nil,
+        ---@type { callback_id: integer, callback: fun(exited: pinnacle.process.ExitedProcess) }[]
+        callbacks = {},
+        ---@type fun(response: table)
+        on_response = nil,
+    },
+    ScreenCaptureRequested = {
+        ---@type grpc_client.h2.Stream?
+        sender = nil,
+        ---@type { callback_id: integer, callback: fun(executable: string, pid: integer) }[]
+        callbacks = {},
+        ---@type fun(response: table)
+        on_response = nil,
+    },
 }
 
 ---Call a signal callback in protected mode
@@ -394,6 +418,45 @@ signals.InputDeviceAdded.on_response = function(response)
     end
 end
 
+signals.InputLayoutChanged.on_response = function(response)
+    ---@type pinnacle.input.XkbLayout
+    local layout = {
+        index = response.layout_index or 0,
+        name = response.layout_name or "",
+    }
+    local callbacks = require("pinnacle.util").deep_copy(signals.InputLayoutChanged.callbacks)
+
+    for _, callback in ipairs(callbacks) do
+        protected_callback("InputLayoutChanged", callback.callback, nil, layout)
+    end
+end
+
+signals.ProcessExited.on_response = function(response)
+    ---@type pinnacle.process.ExitedProcess
+    local exited = {
+        pid = response.pid or 0,
+        name = response.name,
+        exit_code = response.exit_code,
+        exit_msg = response.exit_msg,
+        restarting = response.restarting or false,
+    }
+    local callbacks = require("pinnacle.util").deep_copy(signals.ProcessExited.callbacks)
+
+    for _, callback in ipairs(callbacks) do
+        protected_callback("ProcessExited", callback.callback, nil, exited)
+    end
+end
+
+signals.ScreenCaptureRequested.on_response = function(response)
+    local executable = response.executable or ""
+    local pid = response.pid or 0
+    local callbacks = require("pinnacle.util").deep_copy(signals.ScreenCaptureRequested.callbacks)
+
+    for _, callback in ipairs(callbacks) do
+        protected_callback("ScreenCaptureRequested", callback.callback, nil, executable, pid)
+    end
+end
+
 -----------------------------------------------------------------------------
 
 ---@class pinnacle.signal.SignalHandleModule
@@ -32,16 +32,16 @@ local snowcap = {
 ---
 ---When opened, pressing ENTER will quit the compositor.
 ---@class pinnacle.snowcap.integration.QuitPrompt : snowcap.widget.Program
----The radius of the prompt's corners.
----@field border_radius number
+---The radius of the prompt's corners. Defaults to the theme's radius.
+---@field border_radius number?
 ---THe thickness of the prompt border.
 ---@field border_thickness number
----The color of the prompt background.
----@field background_color snowcap.widget.Color
----The color of the prompt border.
----@field border_color snowcap.widget.Color
----The font of the prompt.
----@field font snowcap.widget.Font
+---The color of the prompt background. Defaults to the theme's background color.
+---@field background_color snowcap.widget.Color?
+---The color of the prompt border. Defaults to the theme's danger color.
+---@field border_color snowcap.widget.Color?
+---The font of the prompt. Defaults to the theme's font.
+---@field font snowcap.widget.Font?
 ---The width of the prompt.
 ---@field width integer
 ---The height of the prompt.
@@ -49,18 +49,44 @@ local snowcap = {
 local QuitPrompt = {}
 setmetatable(QuitPrompt, { __index = require("snowcap.widget.base").Base })
 
+---A prompt asking whether a client may capture the screen.
+---
+---When opened, pressing Y allows the capture and pressing N or ESCAPE denies it.
+---Show this in response to the `screen_capture_requested` signal.
+---@class pinnacle.snowcap.integration.ScreenCapturePrompt : snowcap.widget.Program
+---The radius of the prompt's corners. Defaults to the theme's radius.
+---@field border_radius number?
+---The thickness of the prompt border.
+---@field border_thickness number
+---The color of the prompt background. Defaults to the theme's background color.
+---@field background_color snowcap.widget.Color?
+---The color of the prompt border. Defaults to the theme's danger color.
+---@field border_color snowcap.widget.Color?
+---The font of the prompt. Defaults to the theme's font.
+---@field font snowcap.widget.Font?
+---The width of the prompt.
+---@field width integer
+---The height of the prompt.
+---@field height integer
+---The path to the executable requesting the capture.
+---@field executable string
+---The pid of the process requesting the capture.
+---@field pid integer
+local ScreenCapturePrompt = {}
+setmetatable(ScreenCapturePrompt, { __index = require("snowcap.widget.base").Base })
+
 ---An overlay that shows various input binds.
 ---@class pinnacle.snowcap.integration.BindOverlay : snowcap.widget.Program
----The radius of the overlay's corners.
----@field border_radius number
+---The radius of the overlay's corners. Defaults to the theme's radius.
+---@field border_radius number?
 ---The thickness of the overlay border.
 ---@field border_thickness number
----The color of the overlay background.
----@field background_color snowcap.widget.Color
----The color of the overlay border.
----@field border_color snowcap.widget.Color
----The font of the overlay.
----@field font snowcap.widget.Font
+---The color of the overlay background. Defaults to the theme's background color.
+---@field background_color snowcap.widget.Color?
+---The color of the overlay border. Defaults to the theme's primary color.
+---@field border_color snowcap.widget.Color?
+---The font of the overlay. Defaults to the theme's font.
+---@field font snowcap.widget.Font?
 ---The width of the overlay.
 ---@field width integer
 ---The height of the overlay.
@@ -74,10 +100,10 @@ setmetatable(BindOverlay, { __index = require("snowcap.widget.base").Base })
 ---@field window pinnacle.window.WindowHandle
 ---The thickness of the border, in pixels.
 ---@field thickness integer
----The color of the border when it's focused.
----@field focused_color snowcap.widget.Color
----The color of the border when it's unfocused.
----@field unfocused_color snowcap.widget.Color
+---The color of the border when it's focused. Defaults to the theme's primary color.
+---@field focused_color snowcap.widget.Color?
+---The color of the border when it's unfocused. Defaults to the theme's background color.
+---@field unfocused_color snowcap.widget.Color?
 ---Whether the window this border surrounds is focused.
 ---@field focused boolean
 ---Whether to draw a titlebar
@@ -89,10 +115,156 @@ setmetatable(BindOverlay, { __index = require("snowcap.widget.base").Base })
 local FocusBorder = {}
 setmetatable(FocusBorder, { __index = require("snowcap.widget.base").Base })
 
+---An `Alt + Tab`-style window switcher with live thumbnails.
+---
+---The switcher lists the windows on the focused output across all tags,
+---most recently focused first, and starts on the previously focused window.
+---Showing it again while it's open moves the selection instead, so it's meant to be
+---bound with its `modifier` held:
+---
+---```lua
+---Input.keybind({ "alt" }, "Tab", function()
+---    Snowcap.integration.window_switcher():show()
+---end)
+---Input.keybind({ "alt", "shift" }, "Tab", function()
+---    Snowcap.integration.window_switcher():show_reverse()
+---end)
+---```
+---
+---Releasing the modifier focuses the selected window, switching to one of its tags
+---if it's hidden. Pressing ESCAPE closes the switcher without changing focus.
+---@class pinnacle.snowcap.integration.WindowSwitcher : snowcap.widget.Program
+---The radius of the switcher's corners. Defaults to the theme's radius.
+---@field border_radius number?
+---The thickness of the switcher border.
+---@field border_thickness number
+---The color of the switcher background. Defaults to the theme's background color.
+---@field background_color snowcap.widget.Color?
+---The color of the switcher border. Defaults to the theme's primary color.
+---@field border_color snowcap.widget.Color?
+---The color of the border around the selected window.
+---@field selection_color snowcap.widget.Color
+---The font of window titles. Defaults to the theme's font.
+---@field font snowcap.widget.Font?
+---The width of each thumbnail.
+---@field thumbnail_width integer
+---The height of each thumbnail.
+---@field thumbnail_height integer
+---The modifier that keeps the switcher open while held.
+---@field modifier "alt" | "super" | "ctrl" | "shift"
+---@field private entries { window: pinnacle.window.WindowHandle, title: string, identifier: string? }[]
+---@field private selected integer
+local WindowSwitcher = {}
+setmetatable(WindowSwitcher, { __index = require("snowcap.widget.base").Base })
+
+---The layer of the open window switcher, if any.
+---@type { handle: snowcap.layer.LayerHandle }?
+local open_switcher = nil
+
+---An application launcher.
+---
+---Lists the applications and desktop actions from installed `.desktop` files, fuzzy-matching
+---them against what's typed. Pressing ENTER launches the selected entry, UP and DOWN change
+---the selection, and ESCAPE closes the launcher. Entries that are launched often rank higher.
+---@class pinnacle.snowcap.integration.Launcher : snowcap.widget.Program
+---The radius of the launcher's corners. Defaults to the theme's radius.
+---@field border_radius number?
+---The thickness of the launcher border.
+---@field border_thickness number
+---The color of the launcher background. Defaults to the theme's background color.
+---@field background_color snowcap.widget.Color?
+---The color of the launcher border. Defaults to the theme's primary color.
+---@field border_color snowcap.widget.Color?
+---The background color of the selected entry.
+---@field selection_color snowcap.widget.Color
+---The color of entry descriptions.
+---@field description_color snowcap.widget.Color
+---The font of the launcher. Defaults to the theme's font.
+---@field font snowcap.widget.Font?
+---The width of the launcher.
+---@field width integer
+---The maximum number of entries shown at once.
+---@field max_results integer
+---The command that entries with `Terminal=true` are run with, followed by the entry's command.
+---@field terminal string[]
+---@field private entries pinnacle.snowcap.integration.launcher.Entry[]
+---@field private history table<string, integer>
+---@field private query string
+---@field private matches integer[]
+---@field private selected integer
+local Launcher = {}
+setmetatable(Launcher, { __index = require("snowcap.widget.base").Base })
+
+local LAUNCHER_INPUT_ID = "launcher_input"
+
+local desktop_entry = require("pinnacle.desktop_entry")
+
+---The colors, font, and corner radius of a built-in widget.
+---@class pinnacle.snowcap.integration.Look
+---@field background snowcap.widget.Color
+---@field border snowcap.widget.Color
+---@field font snowcap.widget.Font
+---@field radius number
+
+---Resolves the look of a built-in widget.
+---
+---Fields set on the widget win over the snowcap theme, which wins over the
+---widget's own defaults.
+---
+---@param program { background_color: snowcap.widget.Color?, border_color: snowcap.widget.Color?, font: snowcap.widget.Font?, border_radius: number? }
+---@param kind "alert" | "panel" Alerts are bordered with the theme's danger color, panels with its primary color.
+---
+---@return pinnacle.snowcap.integration.Look
+local function resolve_look(program, kind)
+    local Widget = require("snowcap.widget")
+    local Theme = require("snowcap.theme")
+
+    local theme, variant = Theme.current()
+    local palette = theme.palette or {}
+    local light = variant == Theme.variant.LIGHT
+
+    local default_background, default_border, theme_border
+    if kind == "alert" then
+        default_background = light and Widget.color.from_rgba(0.98, 0.9, 0.94, 0.85)
+            or Widget.color.from_rgba(0.15, 0.03, 0.1, 0.65)
+        default_border = Widget.color.from_rgba(0.8, 0.2, 0.4)
+        theme_border = palette.danger
+    else
+        default_background = light and Widget.color.from_rgba(0.9, 0.9, 0.96, 0.85)
+            or Widget.color.from_rgba(0.15, 0.15, 0.225, 0.8)
+        default_border = Widget.color.from_rgba(0.4, 0.4, 0.7)
+        theme_border = palette.primary
+    end
+
+    ---@type pinnacle.snowcap.integration.Look
+    return {
+        background = program.background_color or palette.background or default_background,
+        border = program.border_color or theme_border or default_border,
+        font = program.font or theme.font or { family = Widget.font.family.Name("Ubuntu") },
+        radius = program.border_radius or theme.radius or 12.0,
+    }
+end
+
+---Redraws `program` whenever the snowcap theme changes, until `is_closed` returns true.
+---
+---@param program snowcap.widget.base.Base
+---@param is_closed fun(): boolean
+local function follow_theme(program, is_closed)
+    require("snowcap.theme").connect(function()
+        if is_closed() then
+            return require("snowcap.signal").HandlerPolicy.Discard
+        end
+
+        program:emit(require("snowcap.widget.signal").redraw_needed)
+    end)
+end
+
 function QuitPrompt:view()
+    local look = resolve_look(self, "alert")
+
     local Widget = require("snowcap.widget")
 
-    local quit_font = require("pinnacle.util").deep_copy(self.font)
+    local quit_font = require("pinnacle.util").deep_copy(look.font)
     quit_font.weight = Widget.font.weight.BOLD
 
     local prompt = Widget.container({
@@ -101,15 +273,15 @@ function QuitPrompt:view()
         valign = Widget.alignment.CENTER,
         halign = Widget.alignment.CENTER,
         style = {
-            background = Widget.background.Color(self.background_color),
+            background = Widget.background.Color(look.background),
             border = {
                 width = self.border_thickness,
-                color = self.border_color,
+                color = look.border,
                 radius = {
-                    top_left = self.border_radius,
-                    top_right = self.border_radius,
-                    bottom_left = self.border_radius,
-                    bottom_right = self.border_radius,
+                    top_left = look.radius,
+                    top_right = look.radius,
+                    bottom_left = look.radius,
+                    bottom_right = look.radius,
                 },
             },
         },
@@ -126,7 +298,7 @@ function QuitPrompt:view()
                 Widget.text({
                     text = "Press ENTER to confirm, or\nany other key to close this",
                     style = {
-                        font = self.font,
+                        font = look.font,
                         pixels = 14.0,
                     },
                 }),
@@ -154,16 +326,129 @@ function QuitPrompt:show()
         return
     end
 
+    local closed = false
+    follow_theme(self, function()
+        return closed
+    end)
+
     prompt:on_key_press(function(_, key)
         if key == require("snowcap.input.keys").Return then
             require("pinnacle").quit()
         else
+            closed = true
             prompt:close()
         end
     end)
 end
 
+function ScreenCapturePrompt:view()
+    local look = resolve_look(self, "alert")
+
+    local Widget = require("snowcap.widget")
+
+    local title_font = require("pinnacle.util").deep_copy(look.font)
+    title_font.weight = Widget.font.weight.BOLD
+
+    local prompt = Widget.container({
+        width = Widget.length.Fixed(self.width),
+        height = Widget.length.Fixed(self.height),
+        valign = Widget.alignment.CENTER,
+        halign = Widget.alignment.CENTER,
+        style = {
+            background = Widget.background.Color(look.background),
+            border = {
+                width = self.border_thickness,
+                color = look.border,
+                radius = {
+                    top_left = look.radius,
+                    top_right = look.radius,
+                    bottom_left = look.radius,
+                    bottom_right = look.radius,
+                },
+            },
+        },
+        child = Widget.column({
+            children = {
+                Widget.text({
+                    text = "Allow screen capture?",
+                    style = {
+                        font = title_font,
+                        pixels = 20.0,
+                    },
+                }),
+                Widget.text({ text = "", style = { pixels = 8.0 } }),
+                Widget.text({
+                    text = self.executable .. " (pid " .. tostring(self.pid) .. ")",
+                    style = {
+                        font = look.font,
+                        pixels = 14.0,
+                    },
+                }),
+                Widget.text({
+                    text = "wants to capture the contents of your screen.",
+                    style = {
+                        font = look.font,
+                        pixels = 14.0,
+                    },
+                }),
+                Widget.text({ text = "", style = { pixels = 8.0 } }),
+                Widget.text({
+                    text = "Press Y to allow, or N/ESCAPE to deny",
+                    style = {
+                        font = look.font,
+                        pixels = 14.0,
+                    },
+                }),
+            },
+        }),
+    })
+
+    return prompt
+end
+
+function ScreenCapturePrompt:update(_) end
+
+---Shows this screen capture prompt.
+function ScreenCapturePrompt:show()
+    local Layer = require("snowcap.layer")
+    local prompt = Layer.new_widget({
+        program = self,
+        anchor = nil,
+        keyboard_interactivity = Layer.keyboard_interactivity.EXCLUSIVE,
+        exclusive_zone = "respect",
+        layer = Layer.zlayer.OVERLAY,
+    })
+
+    if not prompt then
+        return
+    end
+
+    local closed = false
+    follow_theme(self, function()
+        return closed
+    end)
+
+    local keys = require("snowcap.input.keys")
+
+    prompt:on_key_press(function(_, key)
+        local allow
+        if key == keys.y or key == keys.Y then
+            allow = true
+        elseif key == keys.n or key == keys.N or key == keys.Escape then
+            allow = false
+        else
+            return
+        end
+
+        require("pinnacle").set_screen_capture_permission(self.executable, allow)
+        closed = true
+        prompt:close()
+    end)
+end
+
 function BindOverlay:view()
+    local look = resolve_look(self, "panel")
+
     ---@param mods pinnacle.input.Mod[]
     ---@return string?
     local function mods_to_string(mods)
@@ -308,7 +593,7 @@ function BindOverlay:view()
 
     local Widget = require("snowcap.widget")
 
-    local bold_font = require("pinnacle.util").deep_copy(self.font)
+    local bold_font = require("pinnacle.util").deep_copy(look.font)
     bold_font.weight = Widget.font.weight.BOLD
 
     for _, group in ipairs(groups) do
@@ -338,7 +623,7 @@ function BindOverlay:view()
                     Widget.text({
                         text = repr,
                         style = {
-                            font = self.font,
+                            font = look.font,
                         },
                     })
                 )
@@ -351,14 +636,14 @@ function BindOverlay:view()
                                 text = repr,
                                 width = Widget.length.FillPortion(1),
                                 style = {
-                                    font = self.font,
+                                    font = look.font,
                                 },
                             }),
                             Widget.text({
                                 text = descs[1],
                                 width = Widget.length.FillPortion(2),
                                 style = {
-                                    font = self.font,
+                                    font = look.font,
                                 },
                             }),
                         },
@@ -372,7 +657,7 @@ function BindOverlay:view()
                     Widget.text({
                         text = repr .. ":",
                         style = {
-                            font = self.font,
+                            font = look.font,
                         },
                     })
                 )
@@ -383,7 +668,7 @@ function BindOverlay:view()
                         Widget.text({
                             text = "\t" .. desc,
                             style = {
-                                font = self.font,
+                                font = look.font,
                             },
                         })
                     )
@@ -408,7 +693,7 @@ function BindOverlay:view()
                     Widget.text({
                         text = repr,
                         style = {
-                            font = self.font,
+                            font = look.font,
                         },
                     })
                 )
@@ -421,14 +706,14 @@ function BindOverlay:view()
                                 text = repr,
                                 width = Widget.length.FillPortion(1),
                                 style = {
-                                    font = self.font,
+                                    font = look.font,
                                 },
                             }),
                             Widget.text({
                                 text = descs[1],
                                 width = Widget.length.FillPortion(2),
                                 style = {
-                                    font = self.font,
+                                    font = look.font,
                                 },
                             }),
                         },
@@ -442,7 +727,7 @@ function BindOverlay:view()
                     Widget.text({
                         text = repr .. ":",
                         style = {
-                            font = self.font,
+                            font = look.font,
                         },
                     })
                 )
@@ -453,7 +738,7 @@ function BindOverlay:view()
                         Widget.text({
                             text = "\t" .. desc,
                             style = {
-                                font = self.font,
+                                font = look.font,
                             },
                         })
                     )
@@ -516,15 +801,15 @@ function BindOverlay:view()
         valign = Widget.alignment.CENTER,
         halign = Widget.alignment.CENTER,
         style = {
-            background = Widget.background.Color(self.background_color),
+            background = Widget.background.Color(look.background),
             border = {
                 width = self.border_thickness,
-                color = self.border_color,
+                color = look.border,
                 radius = {
-                    top_left = self.border_radius,
-                    top_right = self.border_radius,
-                    bottom_left = self.border_radius,
-                    bottom_right = self.border_radius,
+                    top_left = look.radius,
+                    top_right = look.radius,
+                    bottom_left = look.radius,
+                    bottom_right = look.radius,
                 },
             },
         },
@@ -550,7 +835,13 @@ function BindOverlay:show()
         return
     end
 
+    local closed = false
+    follow_theme(self, function()
+        return closed
+    end)
+
     overlay:on_key_press(function(_, _)
+        closed = true
         overlay:close()
     end)
 end
@@ -632,10 +923,27 @@ local maximize_icon = table.concat({
 
 function FocusBorder:view()
     local Widget = require("snowcap.widget")
+    local Theme = require("snowcap.theme")
+
+    local theme, variant = Theme.current()
+    local palette = theme.palette or {}
+
+    local color
+    if self.focused then
+        color = self.focused_color
+            or palette.primary
+            or Widget.color.from_rgba(0.4, 0.15, 0.7)
+    else
+        color = self.unfocused_color
+            or palette.background
+            or (
+                variant == Theme.variant.LIGHT and Widget.color.from_rgba(0.8, 0.8, 0.8)
+                or Widget.color.from_rgba(0.15, 0.15, 0.15)
+            )
+    end
 
     local function brighten(amt)
-        local color = self.focused and self.focused_color or self.unfocused_color
-        color = require("pinnacle.util").deep_copy(color)
+        local color = require("pinnacle.util").deep_copy(color)
         color.red = color.red + amt
         color.green = color.green + amt
         color.blue = color.blue + amt
@@ -647,9 +955,7 @@ function FocusBorder:view()
     if self.include_titlebar then
         local titlebar = Widget.container({
             style = {
-                background = Widget.background.Color(
-                    self.focused and self.focused_color or self.unfocused_color
-                ),
+                background = Widget.background.Color(color),
             },
             padding = {
                 top = self.thickness,
@@ -810,7 +1116,7 @@ function FocusBorder:view()
                     alpha = 0,
                 }),
                 border = {
-                    color = self.focused and self.focused_color or self.unfocused_color,
+                    color = color,
                     width = self.thickness,
                     radius = {
                         top_right = 0,
@@ -921,29 +1227,454 @@ function FocusBorder:decorate()
 
     signal_holder[2] = signal
 
+    follow_theme(self, function()
+        return self.window:foreign_toplevel_list_identifier() == nil
+    end)
+
     return border
 end
 
+function WindowSwitcher:view()
+    local look = resolve_look(self, "panel")
+
+    local Widget = require("snowcap.widget")
+
+    local function radius(r)
+        return {
+            top_left = r,
+            top_right = r,
+            bottom_left = r,
+            bottom_right = r,
+        }
+    end
+
+    local entries = {}
+
+    for i, entry in ipairs(self.entries) do
+        local thumbnail
+        if entry.identifier then
+            thumbnail = Widget.Image({
+                handle = { toplevel_identifier = entry.identifier },
+                width = Widget.length.Fixed(self.thumbnail_width),
+                height = Widget.length.Fixed(self.thumbnail_height),
+                content_fit = Widget.image.content_fit.CONTAIN,
+            })
+        else
+            thumbnail = Widget.space({
+                width = Widget.length.Fixed(self.thumbnail_width),
+                height = Widget.length.Fixed(self.thumbnail_height),
+            })
+        end
+
+        table.insert(
+            entries,
+            Widget.container({
+                padding = { top = 8, right = 8, bottom = 8, left = 8 },
+                clip = true,
+                style = {
+                    border = {
+                        width = 3,
+                        color = i == self.selected and self.selection_color
+                            or Widget.color.from_rgba(0, 0, 0, 0),
+                        radius = radius(look.radius / 2),
+                    },
+                },
+                child = Widget.column({
+                    spacing = 8,
+                    item_alignment = Widget.alignment.CENTER,
+                    children = {
+                        thumbnail,
+                        Widget.text({
+                            text = entry.title,
+                            width = Widget.length.Fixed(self.thumbnail_width),
+                            halign = Widget.alignment.CENTER,
+                            wrapping = Widget.wrapping.NONE,
+                            style = {
+                                font = look.font,
+                                pixels = 14.0,
+                            },
+                        }),
+                    },
+                }),
+            })
+        )
+    end
+
+    return Widget.container({
+        padding = { top = 12, right = 12, bottom = 12, left = 12 },
+        style = {
+            background = Widget.background.Color(look.background),
+            border = {
+                width = self.border_thickness,
+                color = look.border,
+                radius = radius(look.radius),
+            },
+        },
+        child = Widget.row({
+            spacing = 8,
+            children = entries,
+        }),
+    })
+end
+
+function WindowSwitcher:update(msg)
+    local len = #self.entries
+    if len == 0 then
+        return
+    end
+
+    if msg.next then
+        self.selected = self.selected % len + 1
+    end
+    if msg.previous then
+        self.selected = (self.selected - 2) % len + 1
+    end
+end
+
+---Shows this window switcher, or selects the next window if one is already open.
+function WindowSwitcher:show()
+    self:show_with({ next = true })
+end
+
+---Shows this window switcher starting from the least recently focused window,
+---or selects the previous window if one is already open.
+function WindowSwitcher:show_reverse()
+    self:show_with({ previous = true })
+end
+
+---Returns whether a key release means `modifier` was let go.
+---
+---The modifiers sent with the release of a modifier key still include it,
+---so releasing one of its keys counts. Otherwise, any release without the modifier
+---held means it was let go, even if that happened before the switcher got keyboard focus.
+---
+---@param event snowcap.input.KeyEvent
+---@param modifier "alt" | "super" | "ctrl" | "shift"
+---@return boolean
+local function modifier_released(event, modifier)
+    local Keys = require("snowcap.input.keys")
+
+    local modifier_keys = {
+        alt = { Keys.Alt_L, Keys.Alt_R, Keys.Meta_L, Keys.Meta_R },
+        super = { Keys.Super_L, Keys.Super_R },
+        ctrl = { Keys.Control_L, Keys.Control_R },
+        shift = { Keys.Shift_L, Keys.Shift_R },
+    }
+
+    for _, key in ipairs(modifier_keys[modifier] or {}) do
+        if event.key == key then
+            return true
+        end
+    end
+
+    return not event.mods[modifier]
+end
+
+---@private
+function WindowSwitcher:show_with(msg)
+    if open_switcher then
+        open_switcher.handle:send_message(msg)
+        return
+    end
+
+    local output = require("pinnacle.output").get_focused()
+    if not output then
+        return
+    end
+
+    -- The focus stack has the most recently focused window last.
+    local stack = output:keyboard_focus_stack()
+    self.entries = {}
+    for i = #stack, 1, -1 do
+        local window = stack[i]
+        local title = window:title()
+        if title == "" then
+            title = window:app_id()
+        end
+        table.insert(self.entries, {
+            window = window,
+            title = title,
+            identifier = window:foreign_toplevel_list_identifier(),
+        })
+    end
+
+    if #self.entries == 0 then
+        return
+    end
+
+    if msg.next then
+        self.selected = math.min(2, #self.entries)
+    else
+        self.selected = #self.entries
+    end
+
+    local Layer = require("snowcap.layer")
+    local Keys = require("snowcap.input.keys")
+
+    local switcher = Layer.new_widget({
+        program = self,
+        anchor = nil,
+        keyboard_interactivity = Layer.keyboard_interactivity.EXCLUSIVE,
+        exclusive_zone = "respect",
+        layer = Layer.zlayer.OVERLAY,
+    })
+
+    if not switcher then
+        return
+    end
+
+    local this = { handle = switcher }
+    open_switcher = this
+
+    follow_theme(self, function()
+        return open_switcher ~= this
+    end)
+
+    switcher:on_key_event(function(handle, event)
+        if event.pressed then
+            if event.key == Keys.Tab and event.mods.shift then
+                handle:send_message({ previous = true })
+            elseif event.key == Keys.Tab or event.key == Keys.Right then
+                handle:send_message({ next = true })
+            elseif event.key == Keys.ISO_Left_Tab or event.key == Keys.Left then
+                handle:send_message({ previous = true })
+            elseif event.key == Keys.Escape then
+                open_switcher = nil
+                handle:close()
+            end
+            return
+        end
+
+        if not modifier_released(event, self.modifier) then
+            return
+        end
+
+        if open_switcher ~= this then
+            return
+        end
+        open_switcher = nil
+        handle:close()
+
+        local entry = self.entries[self.selected]
+        if not entry then
+            return
+        end
+
+        local window = entry.window
+        if not window:is_on_active_tag() then
+            local tag = window:tags()[1]
+            if tag then
+                tag:switch_to()
+            end
+        end
+        window:set_focused(true)
+        window:raise()
+    end)
+end
+
+function Launcher:view()
+    local look = resolve_look(self, "panel")
+
+    local Widget = require("snowcap.widget")
+
+    local function radius(r)
+        return {
+            top_left = r,
+            top_right = r,
+            bottom_left = r,
+            bottom_right = r,
+        }
+    end
+
+    local children = {
+        Widget.text_input({
+            placeholder = "Search applications",
+            value = self.query,
+            id = LAUNCHER_INPUT_ID,
+            on_input = function(query)
+                return { query_changed = query }
+            end,
+            font = look.font,
+            padding = { top = 8, right = 8, bottom = 8, left = 8 },
+            width = Widget.length.Fill,
+        }),
+    }
+
+    for i = 1, math.min(#self.matches, self.max_results) do
+        local entry = self.entries[self.matches[i]]
+
+        local lines = {
+            Widget.text({
+                text = entry.name,
+                wrapping = Widget.wrapping.NONE,
+                style = {
+                    font = look.font,
+                    pixels = 16.0,
+                },
+            }),
+        }
+        if entry.description then
+            table.insert(
+                lines,
+                Widget.text({
+                    text = entry.description,
+                    wrapping = Widget.wrapping.NONE,
+                    style = {
+                        font = look.font,
+                        pixels = 12.0,
+                        color = self.description_color,
+                    },
+                })
+            )
+        end
+
+        table.insert(
+            children,
+            Widget.container({
+                width = Widget.length.Fill,
+                padding = { top = 8, right = 8, bottom = 8, left = 8 },
+                clip = true,
+                style = {
+                    background = i == self.selected
+                            and Widget.background.Color(self.selection_color)
+                        or nil,
+                    border = {
+                        radius = radius(look.radius / 2),
+                    },
+                },
+                child = Widget.column({
+                    children = lines,
+                }),
+            })
+        )
+    end
+
+    return Widget.container({
+        width = Widget.length.Fixed(self.width),
+        padding = { top = 12, right = 12, bottom = 12, left = 12 },
+        style = {
+            background = Widget.background.Color(look.background),
+            border = {
+                width = self.border_thickness,
+                color = look.border,
+                radius = radius(look.radius),
+            },
+        },
+        child = Widget.column({
+            spacing = 8,
+            children = children,
+        }),
+    })
+end
+
+function Launcher:update(msg)
+    local shown = math.min(#self.matches, self.max_results)
+
+    if msg.query_changed then
+        self.query = msg.query_changed
+        self.matches = desktop_entry.rank(self.entries, self.history, self.query)
+        self.selected = 1
+    elseif msg.next and shown > 0 then
+        self.selected = self.selected % shown + 1
+    elseif msg.previous and shown > 0 then
+        self.selected = (self.selected - 2) % shown + 1
+    end
+end
+
+---Indexes installed applications and shows this launcher.
+function Launcher:show()
+    self.entries = desktop_entry.index()
+    self.history = desktop_entry.load_history()
+    self.query = ""
+    self.matches = desktop_entry.rank(self.entries, self.history, self.query)
+    self.selected = 1
+
+    local Layer = require("snowcap.layer")
+    local Keys = require("snowcap.input.keys")
+    local Operation = require("snowcap.widget.operation")
+
+    local launcher = Layer.new_widget({
+        program = self,
+        anchor = nil,
+        keyboard_interactivity = Layer.keyboard_interactivity.EXCLUSIVE,
+        exclusive_zone = "respect",
+        layer = Layer.zlayer.OVERLAY,
+    })
+
+    if not launcher then
+        return
+    end
+
+    launcher:operate(Operation.focusable.Focus(LAUNCHER_INPUT_ID))
+
+    local closed = false
+    follow_theme(self, function()
+        return closed
+    end)
+
+    -- The text input captures most keys, so look at every press.
+    launcher:on_key_event(function(handle, event)
+        if not event.pressed then
+            return
+        end
+
+        local key = event.key
+        if key == Keys.Down or key == Keys.Tab or (key == Keys.n and event.mods.ctrl) then
+            handle:send_message({ next = true })
+        elseif
+            key == Keys.Up
+            or key == Keys.ISO_Left_Tab
+            or (key == Keys.p and event.mods.ctrl)
+        then
+            handle:send_message({ previous = true })
+        elseif key == Keys.Escape then
+            closed = true
+            handle:close()
+        elseif key == Keys.Return or key == Keys.KP_Enter then
+            local index = self.matches[self.selected]
+            local entry = index and self.entries[index]
+            if not entry then
+                return
+            end
+
+            closed = true
+            handle:close()
+
+            local cmd = {}
+            if entry.terminal then
+                for _, arg in ipairs(self.terminal) do
+                    table.insert(cmd, arg)
+                end
+            end
+            for _, arg in ipairs(entry.exec) do
+                table.insert(cmd, arg)
+            end
+
+            require("pinnacle.process")
+                .command({
+                    cmd = cmd,
+                    cwd = entry.cwd,
+                    activation_token = true,
+                })
+                :spawn()
+
+            desktop_entry.record_launch(self.history, entry.id)
+        end
+    end)
+end
+
 ---Creates the default quit prompt.
 ---
 ---Some of its characteristics can be changed by altering its fields.
 ---
 ---@return pinnacle.snowcap.integration.QuitPrompt
 function integration.quit_prompt()
-    local Widget = require("snowcap.widget")
-
     local base = require("snowcap.widget.base").Base.new()
     setmetatable(base, { __index = QuitPrompt })
 
     ---@type pinnacle.snowcap.integration.QuitPrompt
     local prompt = {
-        border_radius = 12.0,
         border_thickness = 6.0,
-        background_color = Widget.color.from_rgba(0.15, 0.03, 0.1, 0.65),
-        border_color = Widget.color.from_rgba(0.8, 0.2, 0.4),
-        font = {
-            family = Widget.font.family.Name("Ubuntu"),
-        },
         width = 220,
         height = 120,
     }
@@ -957,26 +1688,48 @@ function integration.quit_prompt()
     return base
 end
 
+---Creates the default screen capture prompt for the given executable.
+---
+---Some of its characteristics can be changed by altering its fields.
+---
+---@param executable string The path to the executable requesting the capture.
+---@param pid integer The pid of the process requesting the capture.
+---
+---@return pinnacle.snowcap.integration.ScreenCapturePrompt
+function integration.screen_capture_prompt(executable, pid)
+    local base = require("snowcap.widget.base").Base.new()
+    setmetatable(base, { __index = ScreenCapturePrompt })
+
+    ---@type pinnacle.snowcap.integration.ScreenCapturePrompt
+    local prompt = {
+        border_thickness = 6.0,
+        width = 500,
+        height = 160,
+        executable = executable,
+        pid = pid,
+    }
+
+    for k, v in pairs(prompt) do
+        base[k] = v
+    end
+
+    ---@cast base pinnacle.snowcap.integration.ScreenCapturePrompt
+
+    return base
+end
+
 ---Creates the default bind overlay.
 ---
 ---Some of its characteristics can be changed by altering its fields.
 ---
 ---@return pinnacle.snowcap.integration.BindOverlay
 function integration.bind_overlay()
-    local Widget = require("snowcap.widget")
-
     local base = require("snowcap.widget.base").Base.new()
     setmetatable(base, { __index = BindOverlay })
 
     ---@type pinnacle.snowcap.integration.BindOverlay
     local overlay = {
-        border_radius = 12.0,
         border_thickness = 6.0,
-        background_color = Widget.color.from_rgba(0.15, 0.15, 0.225, 0.8),
-        border_color = Widget.color.from_rgba(0.4, 0.4, 0.7),
-        font = {
-            family = Widget.font.family.Name("Ubuntu"),
-        },
         width = 700,
         height = 500,
     }
@@ -996,8 +1749,6 @@ end
 ---
 ---@return pinnacle.snowcap.integration.FocusBorder
 function integration.focus_border(window)
-    local Widget = require("snowcap.widget")
-
     local base = require("snowcap.widget.base").Base.new()
     setmetatable(base, { __index = FocusBorder })
 
@@ -1005,8 +1756,6 @@ function integration.focus_border(window)
     local border = {
         window = window,
         thickness = 4,
-        focused_color = Widget.color.from_rgba(0.4, 0.15, 0.7),
-        unfocused_color = Widget.color.from_rgba(0.15, 0.15, 0.15),
         focused = window:focused(),
         include_titlebar = false,
         title = "",
@@ -1028,8 +1777,6 @@ end
 ---
 ---@return pinnacle.snowcap.integration.FocusBorder
 function integration.focus_border_with_titlebar(window)
-    local Widget = require("snowcap.widget")
-
     local base = require("snowcap.widget.base").Base.new()
     setmetatable(base, { __index = FocusBorder })
 
@@ -1037,8 +1784,6 @@ function integration.focus_border_with_titlebar(window)
     local border = {
         window = window,
         thickness = 4,
-        focused_color = Widget.color.from_rgba(0.4, 0.15, 0.7),
-        unfocused_color = Widget.color.from_rgba(0.15, 0.15, 0.15),
         focused = window:focused(),
         include_titlebar = true,
         title = window:title(),
@@ -1054,4 +1799,70 @@ function integration.focus_border_with_titlebar(window)
     return base
 end
 
+---Creates the default window switcher.
+---
+---Some of its characteristics can be changed by altering its fields.
+---
+---@return pinnacle.snowcap.integration.WindowSwitcher
+function integration.window_switcher()
+    local Widget = require("snowcap.widget")
+
+    local base = require("snowcap.widget.base").Base.new()
+    setmetatable(base, { __index = WindowSwitcher })
+
+    ---@type pinnacle.snowcap.integration.WindowSwitcher
+    local switcher = {
+        border_thickness = 6.0,
+        selection_color = Widget.color.from_rgba(0.8, 0.8, 1.0),
+        thumbnail_width = 240,
+        thumbnail_height = 150,
+        modifier = "alt",
+        entries = {},
+        selected = 1,
+    }
+
+    for k, v in pairs(switcher) do
+        base[k] = v
+    end
+
+    ---@cast base pinnacle.snowcap.integration.WindowSwitcher
+
+    return base
+end
+
+---Creates the default launcher.
+---
+---Some of its characteristics can be changed by altering its fields.
+---
+---@return pinnacle.snowcap.integration.Launcher
+function integration.launcher()
+    local Widget = require("snowcap.widget")
+
+    local base = require("snowcap.widget.base").Base.new()
+    setmetatable(base, { __index = Launcher })
+
+    ---@type pinnacle.snowcap.integration.Launcher
+    local launcher = {
+        border_thickness = 6.0,
+        selection_color = Widget.color.from_rgba(0.3, 0.3, 0.5, 0.8),
+        description_color = Widget.color.from_rgba(0.7, 0.7, 0.8),
+        width = 600,
+        max_results = 8,
+        terminal = { "alacritty", "-e" },
+        entries = {},
+        history = {},
+        query = "",
+        matches = {},
+        selected = 1,
+    }
+
+    for k, v in pairs(launcher) do
+        base[k] = v
+    end
+
+    ---@cast base pinnacle.snowcap.integration.Launcher
+
+    return base
+end
+
 return snowcap
@@ -70,6 +70,32 @@ async fn config() {
         .group("Compositor")
         .description("Show the bindings overlay");
 
+    // `mod_key + Tab` and `mod_key + shift + Tab` cycle through windows
+    #[cfg(feature = "snowcap")]
+    {
+        input::keybind(mod_key, Keysym::Tab)
+            .on_press(move || {
+                pinnacle_api::snowcap::WindowSwitcher {
+                    modifier: mod_key,
+                    ..pinnacle_api::snowcap::WindowSwitcher::new()
+                }
+                .show();
+            })
+            .group("Window")
+            .description("Switch to the next window");
+
+        input::keybind(mod_key | Mod::SHIFT, Keysym::Tab)
+            .on_press(move || {
+                pinnacle_api::snowcap::WindowSwitcher {
+                    modifier: mod_key,
+                    ..pinnacle_api::snowcap::WindowSwitcher::new()
+                }
+                .show_reverse();
+            })
+            .group("Window")
+            .description("Switch to the previous window");
+    }
+
     // `mod_key + shift + q` quits Pinnacle
     #[cfg(not(feature = "snowcap"))]
     input::keybind(mod_key | Mod::SHIFT, 'q')
@@ -118,6 +144,19 @@ async fn config() {
         .group("Process")
         .description("Spawn a terminal");
 
+    // `mod_key + d` shows the application launcher
+    #[cfg(feature = "snowcap")]
+    input::keybind(mod_key, 'd')
+        .on_press(move || {
+            pinnacle_api::snowcap::Launcher {
+                terminal: vec![terminal.into(), "-e".into()],
+                ..pinnacle_api::snowcap::Launcher::new()
+            }
+            .show();
+        })
+        .group("Process")
+        .description("Show the application launcher");
+
     // `mod_key + ctrl + space` toggles floating
     input::keybind(mod_key | Mod::CTRL, Keysym::space)
         .on_press(|| {
@@ -470,6 +509,17 @@ async fn config() {
         output.focus();
     })));
 
+    // Ask before unknown clients capture the screen.
+    // This only fires when `prompt_screen_capture` is set in `pinnacle.toml`.
+    #[cfg(feature = "snowcap")]
+    pinnacle_api::pinnacle::connect_signal(
+        pinnacle_api::signal::PinnacleSignal::ScreenCaptureRequested(Box::new(
+            |executable, pid| {
+                pinnacle_api::snowcap::ScreenCapturePrompt::new(executable, pid).show();
+            },
+        )),
+    );
+
     #[cfg(feature = "snowcap")]
     if let Some(error) = pinnacle_api::pinnacle::take_last_error() {
         // Show previous crash messages
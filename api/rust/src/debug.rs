@@ -2,10 +2,12 @@
 //!
 //! WARNING: This module is not governed by the API stability guarantees.
 
+use std::path::{Path, PathBuf};
+
 use pinnacle_api_defs::pinnacle::{
     debug::v1::{
-        SetCursorPlaneScanoutRequest, SetDamageVisualizationRequest,
-        SetOpaqueRegionVisualizationRequest, SetProcessPipingRequest,
+        DumpStateRequest, SetCursorPlaneScanoutRequest, SetDamageVisualizationRequest,
+        SetOpaqueRegionVisualizationRequest, SetProcessPipingRequest, SetTraceRecordingRequest,
     },
     util::v1::SetOrToggle,
 };
@@ -121,3 +123,54 @@ pub fn toggle_process_piping() {
         .block_on_tokio()
         .unwrap();
 }
+
+/// Starts recording gRPC requests, responses, and signals to a file.
+///
+/// If `path` is `None`, a new file is created in Pinnacle's state directory.
+/// If a recording is already in progress, it continues unless a different `path` is given.
+///
+/// The recording can be replayed with `pinnacle debug replay <FILE>`.
+///
+/// Returns the path being recorded to, or `None` if the file couldn't be created.
+pub fn start_trace_recording(path: Option<&Path>) -> Option<PathBuf> {
+    Client::debug()
+        .set_trace_recording(SetTraceRecordingRequest {
+            set_or_toggle: SetOrToggle::Set.into(),
+            path: path.map(|path| path.display().to_string()),
+        })
+        .block_on_tokio()
+        .ok()?
+        .into_inner()
+        .path
+        .map(PathBuf::from)
+}
+
+/// Stops recording gRPC traffic.
+///
+/// Returns the path of the finished recording, if one was in progress.
+pub fn stop_trace_recording() -> Option<PathBuf> {
+    Client::debug()
+        .set_trace_recording(SetTraceRecordingRequest {
+            set_or_toggle: SetOrToggle::Unset.into(),
+            path: None,
+        })
+        .block_on_tokio()
+        .unwrap()
+        .into_inner()
+        .path
+        .map(PathBuf::from)
+}
+
+/// Returns a JSON snapshot of the compositor's state.
+///
+/// This includes outputs, tags, windows, layout trees, pending transactions,
+/// focus stacks, and binds. The format is meant for bug reports and tests,
+/// and may change between releases.
+pub fn dump_state() -> String {
+    Client::debug()
+        .dump_state(DumpStateRequest {})
+        .block_on_tokio()
+        .unwrap()
+        .into_inner()
+        .json
+}
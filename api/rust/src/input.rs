@@ -6,15 +6,17 @@
 //!
 //! This module provides ways to manage bindings, input devices, and other input settings.
 
+use std::time::Duration;
+
 use num_enum::{FromPrimitive, IntoPrimitive};
 use pinnacle_api_defs::pinnacle::input::{
     self,
     v1::{
         BindProperties, BindRequest, EnterBindLayerRequest, GetBindInfosRequest,
-        KeybindOnPressRequest, KeybindStreamRequest, MousebindOnPressRequest,
-        MousebindStreamRequest, SetBindPropertiesRequest, SetRepeatRateRequest, SetXcursorRequest,
-        SetXkbConfigRequest, SetXkbKeymapRequest, SwitchXkbLayoutRequest,
-        switch_xkb_layout_request,
+        GetXkbLayoutRequest, KeybindOnPressRequest, KeybindStreamRequest, MousebindOnPressRequest,
+        MousebindStreamRequest, SetBindPropertiesRequest, SetCursorHideRequest,
+        SetRepeatRateRequest, SetXcursorRequest, SetXkbConfigRequest, SetXkbKeymapRequest,
+        SetXkbLayoutPerWindowRequest, SwitchXkbLayoutRequest, switch_xkb_layout_request,
     },
 };
 use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
@@ -601,6 +603,7 @@ pub fn set_xkb_config(xkb_config: XkbConfig) {
             layout: xkb_config.layout,
             model: xkb_config.model,
             options: xkb_config.options,
+            device_sysname: None,
         })
         .block_on_tokio()
         .unwrap();
@@ -624,6 +627,7 @@ pub fn set_xkb_keymap(keymap: impl ToString) {
     Client::input()
         .set_xkb_keymap(SetXkbKeymapRequest {
             keymap: keymap.to_string(),
+            device_sysname: None,
         })
         .block_on_tokio()
         .unwrap();
@@ -661,6 +665,56 @@ pub fn switch_xkb_layout(index: u32) {
         .unwrap();
 }
 
+/// Sets whether the current XKB layout is remembered per window.
+///
+/// When enabled, the active layout is saved when a window loses keyboard focus
+/// and restored when it regains it. Windows that have never been focused
+/// keep whatever layout is currently active.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pinnacle_api::input;
+/// input::set_xkb_layout_per_window(true);
+/// ```
+pub fn set_xkb_layout_per_window(per_window: bool) {
+    Client::input()
+        .set_xkb_layout_per_window(SetXkbLayoutPerWindowRequest { per_window })
+        .block_on_tokio()
+        .unwrap();
+}
+
+/// An XKB layout.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct XkbLayout {
+    /// The index of this layout in the keymap.
+    pub index: u32,
+    /// The name of this layout, e.g. "English (US)".
+    pub name: String,
+}
+
+/// Gets the currently active XKB layout.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pinnacle_api::input;
+/// let layout = input::xkb_layout();
+/// println!("Current layout: {}", layout.name);
+/// ```
+pub fn xkb_layout() -> XkbLayout {
+    let response = Client::input()
+        .get_xkb_layout(GetXkbLayoutRequest {})
+        .block_on_tokio()
+        .unwrap()
+        .into_inner();
+
+    XkbLayout {
+        index: response.layout_index,
+        name: response.layout_name,
+    }
+}
+
 /// Bind information.
 ///
 /// Mainly used for the bind overlay.
@@ -721,6 +775,7 @@ pub fn set_repeat_rate(rate: i32, delay: i32) {
         .set_repeat_rate(SetRepeatRateRequest {
             rate: Some(rate),
             delay: Some(delay),
+            device_sysname: None,
         })
         .block_on_tokio()
         .unwrap();
@@ -768,6 +823,54 @@ pub fn set_xcursor_size(size: u32) {
         .unwrap();
 }
 
+/// Sets whether the cursor is hidden when typing.
+///
+/// When enabled, pressing a key that goes to a focused window hides the cursor
+/// until the pointer, a touch, or a tablet tool moves again. Keys that trigger keybinds
+/// or go to layer surfaces, like launchers, don't hide the cursor.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pinnacle_api::input;
+/// input::set_cursor_hide_when_typing(true);
+/// ```
+pub fn set_cursor_hide_when_typing(hide_when_typing: bool) {
+    Client::input()
+        .set_cursor_hide(SetCursorHideRequest {
+            hide_when_typing: Some(hide_when_typing),
+            timeout_ms: None,
+        })
+        .block_on_tokio()
+        .unwrap();
+}
+
+/// Sets how long the pointer must not move before the cursor is hidden.
+///
+/// The cursor reappears when the pointer moves again.
+/// Pass `None` to disable hiding after inactivity.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pinnacle_api::input;
+/// # use std::time::Duration;
+/// input::set_cursor_hide_timeout(Some(Duration::from_secs(5)));
+/// ```
+pub fn set_cursor_hide_timeout(timeout: Option<Duration>) {
+    let timeout_ms = timeout
+        .map(|timeout| timeout.as_millis().clamp(1, u32::MAX as u128) as u32)
+        .unwrap_or(0);
+
+    Client::input()
+        .set_cursor_hide(SetCursorHideRequest {
+            hide_when_typing: None,
+            timeout_ms: Some(timeout_ms),
+        })
+        .block_on_tokio()
+        .unwrap();
+}
+
 /// A trait that designates anything that can be converted into a [`Keysym`].
 pub trait ToKeysym {
     /// Converts this into a [`Keysym`].
@@ -905,5 +1008,6 @@ pub fn connect_signal(signal: InputSignal) -> SignalHandle {
 
     match signal {
         InputSignal::DeviceAdded(f) => signal_state.input_device_added.add_callback(f),
+        InputSignal::LayoutChanged(f) => signal_state.input_layout_changed.add_callback(f),
     }
 }
@@ -7,13 +7,17 @@
 use pinnacle_api_defs::pinnacle::input::{
     self,
     v1::{
-        GetDeviceCapabilitiesRequest, GetDeviceInfoRequest, GetDeviceTypeRequest,
-        GetDevicesRequest, SetDeviceLibinputSettingRequest, SetDeviceMapTargetRequest,
+        GetDeviceCapabilitiesRequest, GetDeviceInfoRequest, GetDeviceLibinputSettingsRequest,
+        GetDeviceTypeRequest, GetDevicesRequest, SetDeviceLibinputSettingRequest,
+        SetDeviceMapTargetRequest, SetRepeatRateRequest, SetXkbConfigRequest, SetXkbKeymapRequest,
         set_device_libinput_setting_request::Setting, set_device_map_target_request::Target,
     },
 };
 
-use crate::{BlockOnTokio, client::Client, output::OutputHandle, signal::InputSignal, util::Rect};
+use crate::{
+    BlockOnTokio, client::Client, input::XkbConfig, output::OutputHandle, signal::InputSignal,
+    util::Rect,
+};
 
 /// A pointer acceleration profile.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -26,6 +30,11 @@ pub enum AccelProfile {
     ///
     /// Pointer acceleration depends on the input speed. This is the default profile for most devices.
     Adaptive,
+    /// A custom acceleration profile.
+    ///
+    /// Pointer acceleration is defined by user-provided [`CustomAccelCurve`]s.
+    /// Set the curves with [`DeviceHandle::set_custom_accel`].
+    Custom,
 }
 
 impl From<AccelProfile> for input::v1::AccelProfile {
@@ -33,6 +42,82 @@ impl From<AccelProfile> for input::v1::AccelProfile {
         match value {
             AccelProfile::Flat => input::v1::AccelProfile::Flat,
             AccelProfile::Adaptive => input::v1::AccelProfile::Adaptive,
+            AccelProfile::Custom => input::v1::AccelProfile::Custom,
+        }
+    }
+}
+
+impl TryFrom<input::v1::AccelProfile> for AccelProfile {
+    type Error = ();
+
+    fn try_from(value: input::v1::AccelProfile) -> Result<Self, Self::Error> {
+        match value {
+            input::v1::AccelProfile::Unspecified => Err(()),
+            input::v1::AccelProfile::Flat => Ok(AccelProfile::Flat),
+            input::v1::AccelProfile::Adaptive => Ok(AccelProfile::Adaptive),
+            input::v1::AccelProfile::Custom => Ok(AccelProfile::Custom),
+        }
+    }
+}
+
+/// The type of movement a [`CustomAccelCurve`] applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccelType {
+    /// Movement that doesn't have a more specific curve set.
+    Fallback,
+    /// Pointer motion.
+    Motion,
+    /// Scrolling.
+    Scroll,
+}
+
+impl From<AccelType> for input::v1::AccelType {
+    fn from(value: AccelType) -> Self {
+        match value {
+            AccelType::Fallback => input::v1::AccelType::Fallback,
+            AccelType::Motion => input::v1::AccelType::Motion,
+            AccelType::Scroll => input::v1::AccelType::Scroll,
+        }
+    }
+}
+
+impl TryFrom<input::v1::AccelType> for AccelType {
+    type Error = ();
+
+    fn try_from(value: input::v1::AccelType) -> Result<Self, Self::Error> {
+        match value {
+            input::v1::AccelType::Unspecified => Err(()),
+            input::v1::AccelType::Fallback => Ok(AccelType::Fallback),
+            input::v1::AccelType::Motion => Ok(AccelType::Motion),
+            input::v1::AccelType::Scroll => Ok(AccelType::Scroll),
+        }
+    }
+}
+
+/// A custom pointer acceleration function.
+///
+/// The function is defined by `points` spaced uniformly along the x-axis,
+/// starting at 0 and increasing by `step`. The x-axis is the device speed
+/// in device units per millisecond, and the y-axis is the resulting pointer speed.
+///
+/// The compositor rejects curves with a non-positive `step` or with
+/// fewer than 2 or more than 64 `points`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomAccelCurve {
+    /// The type of movement this curve applies to.
+    pub accel_type: AccelType,
+    /// The distance between points on the x-axis.
+    pub step: f64,
+    /// The y-values of the function.
+    pub points: Vec<f64>,
+}
+
+impl From<CustomAccelCurve> for input::v1::CustomAccelCurve {
+    fn from(value: CustomAccelCurve) -> Self {
+        input::v1::CustomAccelCurve {
+            accel_type: input::v1::AccelType::from(value.accel_type).into(),
+            step: value.step,
+            points: value.points,
         }
     }
 }
@@ -56,6 +141,18 @@ impl From<ClickMethod> for input::v1::ClickMethod {
     }
 }
 
+impl TryFrom<input::v1::ClickMethod> for ClickMethod {
+    type Error = ();
+
+    fn try_from(value: input::v1::ClickMethod) -> Result<Self, Self::Error> {
+        match value {
+            input::v1::ClickMethod::Unspecified => Err(()),
+            input::v1::ClickMethod::ButtonAreas => Ok(ClickMethod::ButtonAreas),
+            input::v1::ClickMethod::ClickFinger => Ok(ClickMethod::Clickfinger),
+        }
+    }
+}
+
 /// The scroll method of a device selects when to generate scroll axis events instead of pointer motion events.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ScrollMethod {
@@ -82,6 +179,20 @@ impl From<ScrollMethod> for input::v1::ScrollMethod {
     }
 }
 
+impl TryFrom<input::v1::ScrollMethod> for ScrollMethod {
+    type Error = ();
+
+    fn try_from(value: input::v1::ScrollMethod) -> Result<Self, Self::Error> {
+        match value {
+            input::v1::ScrollMethod::Unspecified => Err(()),
+            input::v1::ScrollMethod::NoScroll => Ok(ScrollMethod::NoScroll),
+            input::v1::ScrollMethod::TwoFinger => Ok(ScrollMethod::TwoFinger),
+            input::v1::ScrollMethod::Edge => Ok(ScrollMethod::Edge),
+            input::v1::ScrollMethod::OnButtonDown => Ok(ScrollMethod::OnButtonDown),
+        }
+    }
+}
+
 /// Map 1/2/3 finger taps to buttons.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TapButtonMap {
@@ -100,6 +211,18 @@ impl From<TapButtonMap> for input::v1::TapButtonMap {
     }
 }
 
+impl TryFrom<input::v1::TapButtonMap> for TapButtonMap {
+    type Error = ();
+
+    fn try_from(value: input::v1::TapButtonMap) -> Result<Self, Self::Error> {
+        match value {
+            input::v1::TapButtonMap::Unspecified => Err(()),
+            input::v1::TapButtonMap::LeftRightMiddle => Ok(TapButtonMap::LeftRightMiddle),
+            input::v1::TapButtonMap::LeftMiddleRight => Ok(TapButtonMap::LeftMiddleRight),
+        }
+    }
+}
+
 /// A libinput send events mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SendEventsMode {
@@ -123,6 +246,115 @@ impl From<SendEventsMode> for input::v1::SendEventsMode {
     }
 }
 
+impl TryFrom<input::v1::SendEventsMode> for SendEventsMode {
+    type Error = ();
+
+    fn try_from(value: input::v1::SendEventsMode) -> Result<Self, Self::Error> {
+        match value {
+            input::v1::SendEventsMode::Unspecified => Err(()),
+            input::v1::SendEventsMode::Enabled => Ok(SendEventsMode::Enabled),
+            input::v1::SendEventsMode::Disabled => Ok(SendEventsMode::Disabled),
+            input::v1::SendEventsMode::DisabledOnExternalMouse => {
+                Ok(SendEventsMode::DisabledOnExternalMouse)
+            }
+        }
+    }
+}
+
+/// The current libinput settings of a device.
+///
+/// Settings the device doesn't support are `None`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LibinputSettings {
+    /// The acceleration profile.
+    pub accel_profile: Option<AccelProfile>,
+    /// The acceleration speed.
+    pub accel_speed: Option<f64>,
+    /// The calibration matrix.
+    pub calibration_matrix: Option<[f32; 6]>,
+    /// The click method.
+    pub click_method: Option<ClickMethod>,
+    /// Whether the device is disabled while typing.
+    pub disable_while_typing: Option<bool>,
+    /// Whether the device is left-handed.
+    pub left_handed: Option<bool>,
+    /// Whether middle emulation is enabled.
+    pub middle_emulation: Option<bool>,
+    /// The rotation angle.
+    pub rotation_angle: Option<u32>,
+    /// The scroll button.
+    pub scroll_button: Option<u32>,
+    /// Whether the scroll button locks.
+    pub scroll_button_lock: Option<bool>,
+    /// The scroll method.
+    pub scroll_method: Option<ScrollMethod>,
+    /// Whether natural scroll is enabled.
+    pub natural_scroll: Option<bool>,
+    /// The tap button map.
+    pub tap_button_map: Option<TapButtonMap>,
+    /// Whether tap dragging is enabled.
+    pub tap_drag: Option<bool>,
+    /// Whether tap dragging locks.
+    pub tap_drag_lock: Option<bool>,
+    /// Whether tap-to-click is enabled.
+    pub tap: Option<bool>,
+    /// The send events mode.
+    pub send_events_mode: Option<SendEventsMode>,
+    /// The custom acceleration curves, if the custom acceleration profile is active.
+    pub custom_accel: Option<Vec<CustomAccelCurve>>,
+}
+
+impl From<input::v1::GetDeviceLibinputSettingsResponse> for LibinputSettings {
+    fn from(value: input::v1::GetDeviceLibinputSettingsResponse) -> Self {
+        Self {
+            accel_profile: value
+                .accel_profile
+                .and_then(|profile| input::v1::AccelProfile::try_from(profile).ok())
+                .and_then(|profile| profile.try_into().ok()),
+            accel_speed: value.accel_speed,
+            calibration_matrix: value
+                .calibration_matrix
+                .and_then(|matrix| matrix.matrix.try_into().ok()),
+            click_method: value
+                .click_method
+                .and_then(|method| input::v1::ClickMethod::try_from(method).ok())
+                .and_then(|method| method.try_into().ok()),
+            disable_while_typing: value.disable_while_typing,
+            left_handed: value.left_handed,
+            middle_emulation: value.middle_emulation,
+            rotation_angle: value.rotation_angle,
+            scroll_button: value.scroll_button,
+            scroll_button_lock: value.scroll_button_lock,
+            scroll_method: value
+                .scroll_method
+                .and_then(|method| input::v1::ScrollMethod::try_from(method).ok())
+                .and_then(|method| method.try_into().ok()),
+            natural_scroll: value.natural_scroll,
+            tap_button_map: value
+                .tap_button_map
+                .and_then(|map| input::v1::TapButtonMap::try_from(map).ok())
+                .and_then(|map| map.try_into().ok()),
+            tap_drag: value.tap_drag,
+            tap_drag_lock: value.tap_drag_lock,
+            tap: value.tap,
+            send_events_mode: value.send_events_mode().try_into().ok(),
+            custom_accel: value.custom_accel.map(|custom_accel| {
+                custom_accel
+                    .curves
+                    .into_iter()
+                    .filter_map(|curve| {
+                        Some(CustomAccelCurve {
+                            accel_type: curve.accel_type().try_into().ok()?,
+                            step: curve.step,
+                            points: curve.points,
+                        })
+                    })
+                    .collect()
+            }),
+        }
+    }
+}
+
 bitflags::bitflags! {
     /// A device's libinput capabilities.
     #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
@@ -352,6 +584,43 @@ impl DeviceHandle {
             .vendor_id
     }
 
+    /// Gets the [`AccelProfile`]s this device supports.
+    pub fn accel_profiles(&self) -> Vec<AccelProfile> {
+        self.accel_profiles_async().block_on_tokio()
+    }
+
+    /// Async impl for [`Self::accel_profiles`].
+    pub async fn accel_profiles_async(&self) -> Vec<AccelProfile> {
+        Client::input()
+            .get_device_info(GetDeviceInfoRequest {
+                device_sysname: self.sysname.clone(),
+            })
+            .await
+            .unwrap()
+            .into_inner()
+            .accel_profiles()
+            .filter_map(|profile| profile.try_into().ok())
+            .collect()
+    }
+
+    /// Gets this device's current [`LibinputSettings`].
+    ///
+    /// Returns the default settings if this device no longer exists.
+    pub fn libinput_settings(&self) -> LibinputSettings {
+        self.libinput_settings_async().block_on_tokio()
+    }
+
+    /// Async impl for [`Self::libinput_settings`].
+    pub async fn libinput_settings_async(&self) -> LibinputSettings {
+        Client::input()
+            .get_device_libinput_settings(GetDeviceLibinputSettingsRequest {
+                device_sysname: self.sysname.clone(),
+            })
+            .await
+            .map(|response| response.into_inner().into())
+            .unwrap_or_default()
+    }
+
     /// Gets this device's [`DeviceType`].
     pub fn device_type(&self) -> DeviceType {
         self.device_type_async().block_on_tokio()
@@ -423,6 +692,41 @@ impl DeviceHandle {
             .unwrap();
     }
 
+    /// Sets this device's acceleration profile to [`AccelProfile::Custom`]
+    /// with the given acceleration curves.
+    ///
+    /// At most one curve can be given per [`AccelType`]. Movement types without
+    /// a curve use the [`AccelType::Fallback`] curve, or a flat profile if that
+    /// isn't set either.
+    ///
+    /// This only has an effect on devices that support the custom profile;
+    /// see [`Self::accel_profiles`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use pinnacle_api::input::libinput;
+    /// # use pinnacle_api::input::libinput::{AccelType, CustomAccelCurve};
+    /// libinput::for_each_device(|device| {
+    ///     device.set_custom_accel([CustomAccelCurve {
+    ///         accel_type: AccelType::Motion,
+    ///         step: 1.0,
+    ///         points: vec![0.0, 1.0, 2.5, 4.0, 6.0],
+    ///     }]);
+    /// });
+    /// ```
+    pub fn set_custom_accel(&self, curves: impl IntoIterator<Item = CustomAccelCurve>) {
+        Client::input()
+            .set_device_libinput_setting(SetDeviceLibinputSettingRequest {
+                device_sysname: self.sysname.clone(),
+                setting: Some(Setting::CustomAccel(input::v1::CustomAccel {
+                    curves: curves.into_iter().map(From::from).collect(),
+                })),
+            })
+            .block_on_tokio()
+            .unwrap();
+    }
+
     /// Sets this device's acceleration speed.
     pub fn set_accel_speed(&self, accel_speed: f64) {
         Client::input()
@@ -608,6 +912,64 @@ impl DeviceHandle {
             .block_on_tokio()
             .unwrap();
     }
+
+    /// Sets the xkeyboard config for this device.
+    ///
+    /// This overrides the seat-wide config set with [`input::set_xkb_config`][crate::input::set_xkb_config]
+    /// for key presses on this device. It only affects devices with keyboard capability.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use pinnacle_api::input::libinput;
+    /// # use pinnacle_api::input::XkbConfig;
+    /// libinput::for_each_device(|device| {
+    ///     if device.name() == "My USB Keyboard" {
+    ///         device.set_xkb_config(XkbConfig::new().with_layout("us").with_variant("colemak"));
+    ///     }
+    /// });
+    /// ```
+    pub fn set_xkb_config(&self, xkb_config: XkbConfig) {
+        Client::input()
+            .set_xkb_config(SetXkbConfigRequest {
+                rules: xkb_config.rules,
+                variant: xkb_config.variant,
+                layout: xkb_config.layout,
+                model: xkb_config.model,
+                options: xkb_config.options,
+                device_sysname: Some(self.sysname.clone()),
+            })
+            .block_on_tokio()
+            .unwrap();
+    }
+
+    /// Sets the XKB keymap for this device.
+    ///
+    /// This overrides the seat-wide keymap for key presses on this device.
+    pub fn set_xkb_keymap(&self, keymap: impl ToString) {
+        Client::input()
+            .set_xkb_keymap(SetXkbKeymapRequest {
+                keymap: keymap.to_string(),
+                device_sysname: Some(self.sysname.clone()),
+            })
+            .block_on_tokio()
+            .unwrap();
+    }
+
+    /// Sets the repeat rate and delay for this device.
+    ///
+    /// This overrides the seat-wide repeat rate for key presses on this device.
+    /// Units are in milliseconds.
+    pub fn set_repeat_rate(&self, rate: i32, delay: i32) {
+        Client::input()
+            .set_repeat_rate(SetRepeatRateRequest {
+                rate: Some(rate),
+                delay: Some(delay),
+                device_sysname: Some(self.sysname.clone()),
+            })
+            .block_on_tokio()
+            .unwrap();
+    }
 }
 
 /// Gets handles to all connected input devices.
@@ -10,12 +10,17 @@ use pinnacle_api_defs::pinnacle::{
     self,
     v1::{
         BackendRequest, KeepaliveRequest, KeepaliveResponse, QuitRequest, ReloadConfigRequest,
-        SetLastErrorRequest, SetXwaylandClientSelfScaleRequest, TakeLastErrorRequest,
+        SetLastErrorRequest, SetScreenCapturePermissionRequest, SetXwaylandClientSelfScaleRequest,
+        TakeLastErrorRequest,
     },
 };
 use tonic::Streaming;
 
-use crate::{BlockOnTokio, client::Client};
+use crate::{
+    BlockOnTokio,
+    client::Client,
+    signal::{PinnacleSignal, SignalHandle},
+};
 
 /// A backend that Pinnacle runs with.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -97,6 +102,42 @@ pub fn take_last_error() -> Option<String> {
         .error
 }
 
+/// Allows or denies an executable to capture the screen.
+///
+/// This is used to respond to [`PinnacleSignal::ScreenCaptureRequested`] when
+/// `prompt_screen_capture` is enabled in the `[privileges]` section of `pinnacle.toml`.
+/// The decision lasts until the compositor exits or the privilege policy changes.
+pub fn set_screen_capture_permission(executable: impl ToString, allow: bool) {
+    Client::pinnacle()
+        .set_screen_capture_permission(SetScreenCapturePermissionRequest {
+            executable: executable.to_string(),
+            allow,
+        })
+        .block_on_tokio()
+        .unwrap();
+}
+
+/// Connects to a [`PinnacleSignal`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pinnacle_api::pinnacle;
+/// # use pinnacle_api::signal::PinnacleSignal;
+/// pinnacle::connect_signal(PinnacleSignal::ScreenCaptureRequested(Box::new(|exe, _pid| {
+///     pinnacle::set_screen_capture_permission(exe, exe == "/usr/bin/obs");
+/// })));
+/// ```
+pub fn connect_signal(signal: PinnacleSignal) -> SignalHandle {
+    let mut signal_state = Client::signal_state();
+
+    match signal {
+        PinnacleSignal::ScreenCaptureRequested(f) => {
+            signal_state.screen_capture_requested.add_callback(f)
+        }
+    }
+}
+
 pub(crate) async fn keepalive() -> (
     tokio::sync::mpsc::Sender<KeepaliveRequest>,
     Streaming<KeepaliveResponse>,
@@ -7,15 +7,30 @@
 //! This module provides ways to spawn processes and handle their output.
 
 use std::{
+    borrow::Borrow,
     collections::HashMap,
     os::fd::{FromRawFd, OwnedFd},
+    path::Path,
+    time::Duration,
 };
 
 use passfd::FdPassingExt;
-use pinnacle_api_defs::pinnacle::process::v1::{SetEnvRequest, SpawnRequest, WaitOnSpawnRequest};
+use pinnacle_api_defs::pinnacle::process::{
+    self,
+    v1::{
+        GetSupervisedRequest, RestartSupervisedRequest, SetEnvRequest, SpawnRequest,
+        StopSupervisedRequest, WaitOnSpawnRequest,
+    },
+};
 use tokio_stream::StreamExt;
 
-use crate::{BlockOnTokio, client::Client};
+use crate::{
+    BlockOnTokio,
+    client::Client,
+    output::OutputHandle,
+    signal::{ProcessSignal, SignalHandle},
+    tag::TagHandle,
+};
 
 /// Adds an environment variable that all newly spawned [`Command`]s will inherit.
 pub fn set_env(key: impl ToString, value: impl ToString) {
@@ -28,6 +43,76 @@ pub fn set_env(key: impl ToString, value: impl ToString) {
         .unwrap();
 }
 
+/// Connects to a [`ProcessSignal`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pinnacle_api::process;
+/// # use pinnacle_api::signal::ProcessSignal;
+/// process::connect_signal(ProcessSignal::Exited(Box::new(|exited| {
+///     println!("Process {} exited: {:?}", exited.pid, exited.exit_info);
+/// })));
+/// ```
+pub fn connect_signal(signal: ProcessSignal) -> SignalHandle {
+    let mut signal_state = Client::signal_state();
+
+    match signal {
+        ProcessSignal::Exited(f) => signal_state.process_exited.add_callback(f),
+    }
+}
+
+/// Returns all processes spawned with [`Command::supervise`].
+///
+/// This includes processes that have exited and were not restarted.
+pub fn supervised() -> Vec<SupervisedProcess> {
+    supervised_async().block_on_tokio()
+}
+
+/// Async impl for [`supervised`].
+pub async fn supervised_async() -> Vec<SupervisedProcess> {
+    Client::process()
+        .get_supervised(GetSupervisedRequest {})
+        .await
+        .unwrap()
+        .into_inner()
+        .processes
+        .into_iter()
+        .map(|proc| SupervisedProcess {
+            restart_policy: proc.restart_policy().try_into().unwrap_or_default(),
+            name: proc.name,
+            pid: proc.pid,
+            cmd: proc.cmd,
+            restart_count: proc.restart_count,
+        })
+        .collect()
+}
+
+/// Stops the supervised process with the given name.
+///
+/// The process will not be restarted until [`restart_supervised`] is called
+/// or it is spawned again.
+pub fn stop_supervised(name: impl ToString) {
+    Client::process()
+        .stop_supervised(StopSupervisedRequest {
+            name: name.to_string(),
+        })
+        .block_on_tokio()
+        .unwrap();
+}
+
+/// Restarts the supervised process with the given name.
+///
+/// If the process isn't running, it is started.
+pub fn restart_supervised(name: impl ToString) {
+    Client::process()
+        .restart_supervised(RestartSupervisedRequest {
+            name: name.to_string(),
+        })
+        .block_on_tokio()
+        .unwrap();
+}
+
 /// A process builder that allows you to spawn programs.
 pub struct Command {
     cmd: Vec<String>,
@@ -38,6 +123,75 @@ pub struct Command {
     pipe_stdin: bool,
     pipe_stdout: bool,
     pipe_stderr: bool,
+    supervision: Option<process::v1::Supervision>,
+    cwd: Option<String>,
+    target_tag_ids: Vec<u32>,
+    target_output_name: Option<String>,
+    systemd_scope: bool,
+    activation_token: bool,
+}
+
+/// When a supervised process should be restarted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RestartPolicy {
+    /// Never restart the process.
+    Never,
+    /// Restart the process if it exits unsuccessfully.
+    #[default]
+    OnFailure,
+    /// Always restart the process when it exits.
+    Always,
+}
+
+impl From<RestartPolicy> for process::v1::RestartPolicy {
+    fn from(value: RestartPolicy) -> Self {
+        match value {
+            RestartPolicy::Never => process::v1::RestartPolicy::Never,
+            RestartPolicy::OnFailure => process::v1::RestartPolicy::OnFailure,
+            RestartPolicy::Always => process::v1::RestartPolicy::Always,
+        }
+    }
+}
+
+impl TryFrom<process::v1::RestartPolicy> for RestartPolicy {
+    type Error = ();
+
+    fn try_from(value: process::v1::RestartPolicy) -> Result<Self, Self::Error> {
+        match value {
+            process::v1::RestartPolicy::Unspecified => Err(()),
+            process::v1::RestartPolicy::Never => Ok(RestartPolicy::Never),
+            process::v1::RestartPolicy::OnFailure => Ok(RestartPolicy::OnFailure),
+            process::v1::RestartPolicy::Always => Ok(RestartPolicy::Always),
+        }
+    }
+}
+
+/// A process spawned with [`Command::supervise`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupervisedProcess {
+    /// The name of the process.
+    pub name: String,
+    /// The pid of the process, or `None` if it isn't running.
+    pub pid: Option<u32>,
+    /// The command the process was spawned with.
+    pub cmd: Vec<String>,
+    /// When the process is restarted.
+    pub restart_policy: RestartPolicy,
+    /// The number of consecutive restarts.
+    pub restart_count: u32,
+}
+
+/// A process spawned by the compositor that exited.
+#[derive(Debug, Clone)]
+pub struct ExitedProcess {
+    /// The pid of the process.
+    pub pid: u32,
+    /// The name of the process if it is supervised.
+    pub name: Option<String>,
+    /// The process's exit info.
+    pub exit_info: ExitInfo,
+    /// Whether the supervisor will restart the process.
+    pub restarting: bool,
 }
 
 /// The result of spawning a [`Command`].
@@ -59,7 +213,7 @@ pub struct Child {
 }
 
 /// Information from an exited process.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct ExitInfo {
     /// The process's exit code.
     pub exit_code: Option<i32>,
@@ -120,6 +274,12 @@ impl Command {
             pipe_stdin: false,
             pipe_stdout: false,
             pipe_stderr: false,
+            supervision: None,
+            cwd: None,
+            target_tag_ids: Vec::new(),
+            target_output_name: None,
+            systemd_scope: false,
+            activation_token: false,
         }
     }
 
@@ -148,6 +308,12 @@ impl Command {
             pipe_stdin: false,
             pipe_stdout: false,
             pipe_stderr: false,
+            supervision: None,
+            cwd: None,
+            target_tag_ids: Vec::new(),
+            target_output_name: None,
+            systemd_scope: false,
+            activation_token: false,
         }
     }
 
@@ -219,6 +385,122 @@ impl Command {
         self
     }
 
+    /// Sets the working directory of the process.
+    pub fn current_dir(&mut self, dir: impl AsRef<Path>) -> &mut Self {
+        self.cwd = Some(dir.as_ref().to_string_lossy().into_owned());
+        self
+    }
+
+    /// Places the first window mapped by the process or its descendants on the given tags.
+    ///
+    /// Window rules take precedence over this.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use pinnacle_api::process::Command;
+    /// # use pinnacle_api::tag;
+    /// if let Some(tag) = tag::get("2") {
+    ///     Command::new("firefox").target_tags([tag]).spawn();
+    /// }
+    /// ```
+    pub fn target_tags<T: Borrow<TagHandle>>(
+        &mut self,
+        tags: impl IntoIterator<Item = T>,
+    ) -> &mut Self {
+        self.target_tag_ids = tags.into_iter().map(|tag| tag.borrow().id).collect();
+        self
+    }
+
+    /// Places the first window mapped by the process or its descendants on the given output's
+    /// active tags.
+    ///
+    /// This is ignored if [`Command::target_tags`] was called. Window rules take precedence
+    /// over this.
+    pub fn target_output(&mut self, output: &OutputHandle) -> &mut Self {
+        self.target_output_name = Some(output.name());
+        self
+    }
+
+    /// Spawns the process in its own transient systemd scope.
+    ///
+    /// This prevents the process from being killed along with the compositor's unit and
+    /// lets its resource usage be attributed separately. If `systemd-run` isn't available,
+    /// the process is spawned normally.
+    pub fn systemd_scope(&mut self) -> &mut Self {
+        self.systemd_scope = true;
+        self
+    }
+
+    /// Passes a fresh xdg-activation token to the process.
+    ///
+    /// The token is set in `XDG_ACTIVATION_TOKEN` and `DESKTOP_STARTUP_ID`, letting the
+    /// process's first window take focus even if something else was focused in the meantime.
+    pub fn activation_token(&mut self) -> &mut Self {
+        self.activation_token = true;
+        self
+    }
+
+    /// Supervises the spawned process under the given name, restarting it when it exits.
+    ///
+    /// By default, the process is only restarted if it exits unsuccessfully.
+    /// Use [`Command::restart_policy`] to change this.
+    ///
+    /// Spawning a supervised process while one with the same name is running does nothing,
+    /// so supervised processes survive config reloads.
+    /// Restarted processes will not have their standard io piped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use pinnacle_api::process::{Command, RestartPolicy};
+    /// Command::new("waybar")
+    ///     .supervise("bar")
+    ///     .restart_policy(RestartPolicy::Always)
+    ///     .max_retries(5)
+    ///     .spawn();
+    /// ```
+    pub fn supervise(&mut self, name: impl ToString) -> &mut Self {
+        self.supervision_mut().name = name.to_string();
+        self
+    }
+
+    /// Sets when the supervised process should be restarted.
+    ///
+    /// This has no effect unless [`Command::supervise`] is called.
+    pub fn restart_policy(&mut self, policy: RestartPolicy) -> &mut Self {
+        self.supervision_mut()
+            .set_restart_policy(process::v1::RestartPolicy::from(policy));
+        self
+    }
+
+    /// Sets the delay before the supervised process is first restarted.
+    ///
+    /// The delay doubles with every consecutive restart. Defaults to one second.
+    ///
+    /// This has no effect unless [`Command::supervise`] is called.
+    pub fn restart_backoff(&mut self, backoff: Duration) -> &mut Self {
+        self.supervision_mut().backoff_ms =
+            Some(backoff.as_millis().try_into().unwrap_or(u32::MAX));
+        self
+    }
+
+    /// Sets the maximum number of consecutive restarts before the supervisor gives up.
+    ///
+    /// This has no effect unless [`Command::supervise`] is called.
+    pub fn max_retries(&mut self, max_retries: u32) -> &mut Self {
+        self.supervision_mut().max_retries = Some(max_retries);
+        self
+    }
+
+    fn supervision_mut(&mut self) -> &mut process::v1::Supervision {
+        self.supervision
+            .get_or_insert_with(|| process::v1::Supervision {
+                restart_policy: process::v1::RestartPolicy::OnFailure.into(),
+                ..Default::default()
+            })
+    }
+
     /// Spawns this command, returning the spawned process's standard io, if any.
     pub fn spawn(&mut self) -> Option<Child> {
         let data = Client::process()
@@ -231,6 +513,15 @@ impl Command {
                 pipe_stdin: self.pipe_stdin,
                 pipe_stdout: self.pipe_stdout,
                 pipe_stderr: self.pipe_stderr,
+                supervision: self
+                    .supervision
+                    .clone()
+                    .filter(|supervision| !supervision.name.is_empty()),
+                cwd: self.cwd.clone(),
+                target_tag_ids: self.target_tag_ids.clone(),
+                target_output_name: self.target_output_name.clone(),
+                systemd_scope: self.systemd_scope,
+                activation_token: self.activation_token,
             })
             .block_on_tokio()
             .unwrap()
@@ -27,8 +27,9 @@ use tonic::Streaming;
 
 use crate::{
     BlockOnTokio,
-    input::libinput::DeviceHandle,
+    input::{XkbLayout, libinput::DeviceHandle},
     output::OutputHandle,
+    process::ExitedProcess,
     tag::TagHandle,
     window::{LayoutMode, WindowHandle},
 };
@@ -407,6 +408,67 @@ signals! {
                 }
             },
         }
+        /// The active XKB layout changed.
+        ///
+        /// Callbacks receive the new layout.
+        InputLayoutChanged = {
+            enum_name = LayoutChanged,
+            callback_type = Box<dyn FnMut(&XkbLayout) + Send + 'static>,
+            client_request = input_layout_changed,
+            on_response = |response, callbacks| {
+                let layout = XkbLayout {
+                    index: response.layout_index,
+                    name: response.layout_name,
+                };
+
+                for callback in callbacks {
+                    callback(&layout);
+                }
+            },
+        }
+    }
+    /// Signals relating to process events.
+    ProcessSignal => {
+        /// A process spawned by the compositor exited.
+        ///
+        /// Callbacks receive information about the exited process.
+        ProcessExited = {
+            enum_name = Exited,
+            callback_type = Box<dyn FnMut(&ExitedProcess) + Send + 'static>,
+            client_request = process_exited,
+            on_response = |response, callbacks| {
+                let exited = ExitedProcess {
+                    pid: response.pid,
+                    name: response.name,
+                    exit_info: crate::process::ExitInfo {
+                        exit_code: response.exit_code,
+                        exit_msg: response.exit_msg,
+                    },
+                    restarting: response.restarting,
+                };
+
+                for callback in callbacks {
+                    callback(&exited);
+                }
+            },
+        }
+    }
+    /// Signals relating to compositor-wide events.
+    PinnacleSignal => {
+        /// A client not on the privilege allowlist tried to capture the screen.
+        ///
+        /// Callbacks receive the client's executable path and pid.
+        /// Respond with [`set_screen_capture_permission`][crate::pinnacle::set_screen_capture_permission].
+        ScreenCaptureRequested = {
+            enum_name = ScreenCaptureRequested,
+            callback_type = Box<dyn FnMut(&str, u32) + Send + 'static>,
+            client_request = screen_capture_requested,
+            on_response = |response, callbacks| {
+                for callback in callbacks {
+                    callback(&response.executable, response.pid);
+                }
+            },
+        }
     }
 }
 
@@ -435,6 +497,11 @@ pub(crate) struct SignalState {
     pub(crate) tag_removed: SignalData<TagRemoved>,
 
     pub(crate) input_device_added: SignalData<InputDeviceAdded>,
+    pub(crate) input_layout_changed: SignalData<InputLayoutChanged>,
+
+    pub(crate) process_exited: SignalData<ProcessExited>,
+
+    pub(crate) screen_capture_requested: SignalData<ScreenCaptureRequested>,
 }
 
 impl std::fmt::Debug for SignalState {
@@ -467,6 +534,11 @@ impl SignalState {
             tag_removed: SignalData::new(),
 
             input_device_added: SignalData::new(),
+            input_layout_changed: SignalData::new(),
+
+            process_exited: SignalData::new(),
+
+            screen_capture_requested: SignalData::new(),
         }
     }
 
@@ -492,6 +564,11 @@ impl SignalState {
         self.tag_removed.reset();
 
         self.input_device_added.reset();
+        self.input_layout_changed.reset();
+
+        self.process_exited.reset();
+
+        self.screen_capture_requested.reset();
     }
 }
 
@@ -4,47 +4,178 @@
 //! Snowcap is a really-early-in-development widget system, designed for Pinnacle.
 //! This module contains preliminary widgets made with the system.
 
-use std::sync::{Arc, OnceLock};
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex, OnceLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
 
 use indexmap::IndexMap;
 use snowcap_api::{
     decoration::{DecorationHandle, NewDecorationError},
-    layer::{ExclusiveZone, KeyboardInteractivity, ZLayer},
+    layer::{
+        Anchor, ExclusiveZone, KeyboardInteractivity, LayerHandle, LayerOptions, Margin, ZLayer,
+    },
+    subscription::Subscription,
+    theme::{Theme, Variant},
     widget::{
-        Alignment, Background, Border, Color, Length, Padding, Program, Radius, WidgetDef,
+        Alignment, Background, Border, Length, Padding, Program, Radius, WidgetDef, Wrapping,
         button::{self, Button, Styles},
         column::Column,
         container::Container,
         font::{Family, Font, Weight},
-        image::{Handle, Image},
+        image::{ContentFit, Handle, Image},
         input_region::InputRegion,
         row::Row,
         scrollable::Scrollable,
+        space::Space,
         text::{self, Text},
+        text_input::TextInput,
     },
 };
 use xkbcommon::xkb::Keysym;
 
 use crate::{
     input::{BindInfoKind, Mod},
-    signal::SignalHandle,
+    output::OutputHandle,
+    signal::{OutputSignal, SignalHandle},
     window::WindowHandle,
 };
 
+mod desktop_entry;
+
+pub use snowcap_api::{
+    theme,
+    widget::{Color, font},
+};
+
+/// Which theme colors a built-in widget takes after.
+#[derive(Debug, Clone, Copy)]
+enum LookKind {
+    /// Prompts and error messages, bordered with the theme's danger color.
+    Alert,
+    /// Overlays, switchers, and launchers, bordered with the theme's primary color.
+    Panel,
+}
+
+/// The default colors, font, and corner radius of a built-in widget.
+///
+/// These come from the snowcap theme, falling back to the widget's own defaults.
+#[derive(Debug, Clone)]
+struct Look {
+    background: Color,
+    border: Color,
+    font: Font,
+    radius: f32,
+}
+
+impl Look {
+    fn current(kind: LookKind) -> Self {
+        let (variant, theme) = snowcap_api::theme::current();
+        Self::new(kind, variant, &theme)
+    }
+
+    fn new(kind: LookKind, variant: Variant, theme: &Theme) -> Self {
+        let light = variant == Variant::Light;
+
+        let (default_background, default_border, theme_border) = match kind {
+            LookKind::Alert => (
+                if light {
+                    Color::rgba(0.98, 0.9, 0.94, 0.85)
+                } else {
+                    Color::rgba(0.15, 0.03, 0.1, 0.65)
+                },
+                Color::rgb(0.8, 0.2, 0.4),
+                theme.palette.danger,
+            ),
+            LookKind::Panel => (
+                if light {
+                    Color::rgba(0.9, 0.9, 0.96, 0.85)
+                } else {
+                    Color::rgba(0.15, 0.15, 0.225, 0.8)
+                },
+                Color::rgb(0.4, 0.4, 0.7),
+                theme.palette.primary,
+            ),
+        };
+
+        Self {
+            background: theme.palette.background.unwrap_or(default_background),
+            border: theme_border.unwrap_or(default_border),
+            font: theme
+                .font
+                .clone()
+                .unwrap_or_else(|| Font::new_with_family(Family::Name("Ubuntu".into()))),
+            radius: theme.radius.unwrap_or(12.0),
+        }
+    }
+
+    /// Moves the fields of a built-in widget that still have the look from
+    /// before a theme change over to the look after it.
+    ///
+    /// Fields that were set to something else are left alone.
+    fn restyle(
+        kind: LookKind,
+        change: &theme::Change,
+        radius: &mut f32,
+        background: &mut Color,
+        border: &mut Color,
+        font: &mut Font,
+    ) {
+        let (variant, theme) = &change.previous;
+        let previous = Self::new(kind, *variant, theme);
+        let (variant, theme) = &change.current;
+        let current = Self::new(kind, *variant, theme);
+
+        follow_theme(radius, previous.radius, current.radius);
+        follow_theme(background, previous.background, current.background);
+        follow_theme(border, previous.border, current.border);
+        follow_theme(font, previous.font, current.font);
+    }
+
+    /// Returns the focused and unfocused colors of a [`FocusBorder`].
+    fn focus_border_colors(variant: Variant, theme: &Theme) -> (Color, Color) {
+        let focused = theme.palette.primary.unwrap_or(Color::rgb(0.4, 0.15, 0.7));
+        let unfocused = theme.palette.background.unwrap_or(match variant {
+            Variant::Dark => Color::rgb(0.15, 0.15, 0.15),
+            Variant::Light => Color::rgb(0.8, 0.8, 0.8),
+        });
+
+        (focused, unfocused)
+    }
+}
+
+/// Sets `field` to `current` if it still has the `previous` value from the theme.
+fn follow_theme<T: PartialEq>(field: &mut T, previous: T, current: T) {
+    if *field == previous {
+        *field = current;
+    }
+}
+
 /// A quit prompt.
 ///
 /// When opened, pressing ENTER will quit the compositor.
 #[derive(Default, Clone, Debug)]
 pub struct QuitPrompt {
     /// The radius of the prompt's corners.
+    ///
+    /// Defaults to the snowcap theme's radius.
     pub border_radius: f32,
     /// The thickness of the prompt border.
     pub border_thickness: f32,
     /// The color of the prompt background.
+    ///
+    /// Defaults to the snowcap theme's background color.
     pub background_color: Color,
     /// The color of the prompt border.
+    ///
+    /// Defaults to the snowcap theme's danger color.
     pub border_color: Color,
     /// The font of the prompt.
+    ///
+    /// Defaults to the snowcap theme's font.
     pub font: Font,
     /// The width of the prompt.
     pub width: u32,
@@ -53,9 +184,22 @@ pub struct QuitPrompt {
 }
 
 impl Program for QuitPrompt {
-    type Message = ();
+    type Message = theme::Change;
+
+    fn update(&mut self, change: Self::Message) {
+        Look::restyle(
+            LookKind::Alert,
+            &change,
+            &mut self.border_radius,
+            &mut self.background_color,
+            &mut self.border_color,
+            &mut self.font,
+        );
+    }
 
-    fn update(&mut self, _msg: Self::Message) {}
+    fn subscription(&self) -> Subscription<Self::Message> {
+        theme::changes()
+    }
 
     fn view(&self) -> Option<WidgetDef<Self::Message>> {
         let widget = Container::new(Column::new_with_children([
@@ -92,12 +236,14 @@ impl Program for QuitPrompt {
 impl QuitPrompt {
     /// Creates a quit prompt with sane defaults.
     pub fn new() -> Self {
+        let look = Look::current(LookKind::Alert);
+
         QuitPrompt {
-            border_radius: 12.0,
+            border_radius: look.radius,
             border_thickness: 6.0,
-            background_color: [0.15, 0.03, 0.1, 0.65].into(),
-            border_color: [0.8, 0.2, 0.4].into(),
-            font: Font::new_with_family(Family::Name("Ubuntu".into())),
+            background_color: look.background,
+            border_color: look.border,
+            font: look.font,
             width: 220,
             height: 120,
         }
@@ -123,18 +269,160 @@ impl QuitPrompt {
     }
 }
 
+/// A prompt asking whether a client may capture the screen.
+///
+/// When opened, pressing Y allows the capture and pressing N or ESCAPE denies it.
+/// Show this in response to
+/// [`PinnacleSignal::ScreenCaptureRequested`][crate::signal::PinnacleSignal::ScreenCaptureRequested].
+#[derive(Default, Clone, Debug)]
+pub struct ScreenCapturePrompt {
+    /// The radius of the prompt's corners.
+    ///
+    /// Defaults to the snowcap theme's radius.
+    pub border_radius: f32,
+    /// The thickness of the prompt border.
+    pub border_thickness: f32,
+    /// The color of the prompt background.
+    ///
+    /// Defaults to the snowcap theme's background color.
+    pub background_color: Color,
+    /// The color of the prompt border.
+    ///
+    /// Defaults to the snowcap theme's danger color.
+    pub border_color: Color,
+    /// The font of the prompt.
+    ///
+    /// Defaults to the snowcap theme's font.
+    pub font: Font,
+    /// The width of the prompt.
+    pub width: u32,
+    /// The height of the prompt.
+    pub height: u32,
+    /// The path to the executable requesting the capture.
+    pub executable: String,
+    /// The pid of the process requesting the capture.
+    pub pid: u32,
+}
+
+impl Program for ScreenCapturePrompt {
+    type Message = theme::Change;
+
+    fn update(&mut self, change: Self::Message) {
+        Look::restyle(
+            LookKind::Alert,
+            &change,
+            &mut self.border_radius,
+            &mut self.background_color,
+            &mut self.border_color,
+            &mut self.font,
+        );
+    }
+
+    fn subscription(&self) -> Subscription<Self::Message> {
+        theme::changes()
+    }
+
+    fn view(&self) -> Option<WidgetDef<Self::Message>> {
+        let widget = Container::new(Column::new_with_children([
+            Text::new("Allow screen capture?")
+                .style(
+                    text::Style::new()
+                        .font(self.font.clone().weight(Weight::Bold))
+                        .pixels(20.0),
+                )
+                .into(),
+            Text::new("").style(text::Style::new().pixels(8.0)).into(), // Spacing
+            Text::new(format!("{} (pid {})", self.executable, self.pid))
+                .style(text::Style::new().font(self.font.clone()).pixels(14.0))
+                .into(),
+            Text::new("wants to capture the contents of your screen.")
+                .style(text::Style::new().font(self.font.clone()).pixels(14.0))
+                .into(),
+            Text::new("").style(text::Style::new().pixels(8.0)).into(), // Spacing
+            Text::new("Press Y to allow, or N/ESCAPE to deny")
+                .style(text::Style::new().font(self.font.clone()).pixels(14.0))
+                .into(),
+        ]))
+        .width(Length::Fixed(self.width as f32))
+        .height(Length::Fixed(self.height as f32))
+        .vertical_alignment(Alignment::Center)
+        .horizontal_alignment(Alignment::Center)
+        .style(snowcap_api::widget::container::Style {
+            text_color: None,
+            background: Some(Background::Color(self.background_color)),
+            border: Some(snowcap_api::widget::Border {
+                color: Some(self.border_color),
+                width: Some(self.border_thickness),
+                radius: Some(self.border_radius.into()),
+            }),
+        });
+
+        Some(widget.into())
+    }
+}
+
+impl ScreenCapturePrompt {
+    /// Creates a screen capture prompt for the given executable with sane defaults.
+    pub fn new(executable: impl ToString, pid: u32) -> Self {
+        let look = Look::current(LookKind::Alert);
+
+        ScreenCapturePrompt {
+            border_radius: look.radius,
+            border_thickness: 6.0,
+            background_color: look.background,
+            border_color: look.border,
+            font: look.font,
+            width: 500,
+            height: 160,
+            executable: executable.to_string(),
+            pid,
+        }
+    }
+
+    /// Shows this screen capture prompt.
+    pub fn show(self) {
+        let executable = self.executable.clone();
+
+        snowcap_api::layer::new_widget(
+            self,
+            None,
+            KeyboardInteractivity::Exclusive,
+            ExclusiveZone::Respect,
+            ZLayer::Overlay,
+        )
+        .unwrap()
+        .on_key_press(move |handle, key, _mods| {
+            let allow = match key {
+                Keysym::y | Keysym::Y => true,
+                Keysym::n | Keysym::N | Keysym::Escape => false,
+                _ => return,
+            };
+            crate::pinnacle::set_screen_capture_permission(&executable, allow);
+            handle.close();
+        });
+    }
+}
+
 /// A bindings overlay.
 #[derive(Default, Clone, Debug)]
 pub struct BindOverlay {
     /// The radius of the overlay's corners.
+    ///
+    /// Defaults to the snowcap theme's radius.
     pub border_radius: f32,
     /// The thickness of the overlay border.
     pub border_thickness: f32,
     /// The color of the overlay background.
+    ///
+    /// Defaults to the snowcap theme's background color.
     pub background_color: Color,
     /// The color of the overlay border.
+    ///
+    /// Defaults to the snowcap theme's primary color.
     pub border_color: Color,
     /// The font of the overlay.
+    ///
+    /// Defaults to the snowcap theme's font.
     pub font: Font,
     /// The width of the overlay.
     pub width: u32,
@@ -143,9 +431,22 @@ pub struct BindOverlay {
 }
 
 impl Program for BindOverlay {
-    type Message = ();
+    type Message = theme::Change;
+
+    fn update(&mut self, change: Self::Message) {
+        Look::restyle(
+            LookKind::Panel,
+            &change,
+            &mut self.border_radius,
+            &mut self.background_color,
+            &mut self.border_color,
+            &mut self.font,
+        );
+    }
 
-    fn update(&mut self, _msg: Self::Message) {}
+    fn subscription(&self) -> Subscription<Self::Message> {
+        theme::changes()
+    }
 
     fn view(&self) -> Option<WidgetDef<Self::Message>> {
         #[derive(PartialEq, Eq, Hash)]
@@ -404,12 +705,14 @@ impl BindOverlay {
     ///
     /// Some of its characteristics can be changed by setting its fields.
     pub fn new() -> Self {
+        let look = Look::current(LookKind::Panel);
+
         BindOverlay {
-            border_radius: 12.0,
+            border_radius: look.radius,
             border_thickness: 6.0,
-            background_color: [0.15, 0.15, 0.225, 0.8].into(),
-            border_color: [0.4, 0.4, 0.7].into(),
-            font: Font::new_with_family(Family::Name("Ubuntu".into())),
+            background_color: look.background,
+            border_color: look.border,
+            font: look.font,
             width: 700,
             height: 500,
         }
@@ -463,14 +766,22 @@ fn format_mods(mods: Mod) -> Option<String> {
 #[derive(Default, Clone, Debug)]
 pub struct ConfigCrashedMessage {
     /// The radius of the prompt's corners.
+    ///
+    /// Defaults to the snowcap theme's radius.
     pub border_radius: f32,
     /// The thickness of the prompt border.
     pub border_thickness: f32,
     /// The color of the prompt background.
+    ///
+    /// Defaults to the snowcap theme's background color.
     pub background_color: Color,
     /// The color of the prompt border.
+    ///
+    /// Defaults to the snowcap theme's danger color.
     pub border_color: Color,
     /// The font of the prompt.
+    ///
+    /// Defaults to the snowcap theme's font.
     pub font: Font,
     /// The width of the prompt.
     pub width: u32,
@@ -481,9 +792,22 @@ pub struct ConfigCrashedMessage {
 }
 
 impl Program for ConfigCrashedMessage {
-    type Message = ();
+    type Message = theme::Change;
+
+    fn update(&mut self, change: Self::Message) {
+        Look::restyle(
+            LookKind::Alert,
+            &change,
+            &mut self.border_radius,
+            &mut self.background_color,
+            &mut self.border_color,
+            &mut self.font,
+        );
+    }
 
-    fn update(&mut self, _msg: Self::Message) {}
+    fn subscription(&self) -> Subscription<Self::Message> {
+        theme::changes()
+    }
 
     fn view(&self) -> Option<WidgetDef<Self::Message>> {
         let widget = Container::new(Column::new_with_children([
@@ -541,12 +865,14 @@ impl Program for ConfigCrashedMessage {
 impl ConfigCrashedMessage {
     /// Creates an error message.
     pub fn new(message: impl std::fmt::Display) -> Self {
+        let look = Look::current(LookKind::Alert);
+
         ConfigCrashedMessage {
-            border_radius: 12.0,
+            border_radius: look.radius,
             border_thickness: 6.0,
-            background_color: [0.15, 0.03, 0.1, 0.65].into(),
-            border_color: [0.8, 0.2, 0.4].into(),
-            font: Font::new_with_family(Family::Name("Ubuntu".into())),
+            background_color: look.background,
+            border_color: look.border,
+            font: look.font,
             width: 700,
             height: 400,
             message: message.to_string(),
@@ -579,8 +905,12 @@ pub struct FocusBorder {
     /// The thickness of the border, in pixels.
     pub thickness: u32,
     /// The color of the border when it's focused.
+    ///
+    /// Defaults to the snowcap theme's primary color.
     pub focused_color: Color,
     /// The color of the border when it's unfocused.
+    ///
+    /// Defaults to the snowcap theme's background color.
     pub unfocused_color: Color,
     /// Whether the window this border surrounds is focused.
     pub focused: bool,
@@ -603,16 +933,21 @@ pub enum FocusBorderMessage {
     Close,
     /// The title changed.
     TitleChanged(String),
+    /// The snowcap theme changed.
+    ThemeChanged(theme::Change),
 }
 
 impl FocusBorder {
     /// Creates a new focus border without a titlebar.
     pub fn new(window: &WindowHandle) -> Self {
+        let (variant, theme) = theme::current();
+        let (focused_color, unfocused_color) = Look::focus_border_colors(variant, &theme);
+
         Self {
             window: window.clone(),
             thickness: 4,
-            focused_color: Color::rgb(0.4, 0.15, 0.7),
-            unfocused_color: Color::rgb(0.15, 0.15, 0.15),
+            focused_color,
+            unfocused_color,
             focused: window.focused(),
             include_titlebar: false,
             title: String::new(),
@@ -622,11 +957,14 @@ impl FocusBorder {
 
     /// Creates a new focus border with a titlebar.
     pub fn new_with_titlebar(window: &WindowHandle) -> Self {
+        let (variant, theme) = theme::current();
+        let (focused_color, unfocused_color) = Look::focus_border_colors(variant, &theme);
+
         Self {
             window: window.clone(),
             thickness: 4,
-            focused_color: Color::rgb(0.4, 0.15, 0.7),
-            unfocused_color: Color::rgb(0.15, 0.15, 0.15),
+            focused_color,
+            unfocused_color,
             focused: window.focused(),
             include_titlebar: true,
             title: window.title(),
@@ -807,9 +1145,23 @@ impl Program for FocusBorder {
             FocusBorderMessage::TitleChanged(title) => {
                 self.title = title;
             }
+            FocusBorderMessage::ThemeChanged(change) => {
+                let (variant, theme) = &change.previous;
+                let (previous_focused, previous_unfocused) =
+                    Look::focus_border_colors(*variant, theme);
+                let (variant, theme) = &change.current;
+                let (focused, unfocused) = Look::focus_border_colors(*variant, theme);
+
+                follow_theme(&mut self.focused_color, previous_focused, focused);
+                follow_theme(&mut self.unfocused_color, previous_unfocused, unfocused);
+            }
         }
     }
 
+    fn subscription(&self) -> Subscription<Self::Message> {
+        theme::changes().map(FocusBorderMessage::ThemeChanged)
+    }
+
     fn view(&self) -> Option<WidgetDef<Self::Message>> {
         let mut row = Column::new();
 
@@ -996,3 +1348,877 @@ impl Program for FocusBorder {
         Some(row.into())
     }
 }
+
+/// An `Alt + Tab`-style window switcher with live thumbnails.
+///
+/// The switcher lists the windows on the focused output across all tags,
+/// most recently focused first, and starts on the previously focused window.
+/// Showing it again while it's open moves the selection instead, so it's meant to be
+/// bound with its [`modifier`][WindowSwitcher::modifier] held:
+///
+/// ```no_run
+/// # use pinnacle_api::input::{self, Keysym, Mod};
+/// # use pinnacle_api::snowcap::WindowSwitcher;
+/// input::keybind(Mod::ALT, Keysym::Tab).on_press(|| WindowSwitcher::new().show());
+/// input::keybind(Mod::ALT | Mod::SHIFT, Keysym::Tab)
+///     .on_press(|| WindowSwitcher::new().show_reverse());
+/// ```
+///
+/// Releasing the modifier focuses the selected window, switching to one of its tags
+/// if it's hidden. Pressing ESCAPE closes the switcher without changing focus.
+#[derive(Clone, Debug)]
+pub struct WindowSwitcher {
+    /// The radius of the switcher's corners.
+    ///
+    /// Defaults to the snowcap theme's radius.
+    pub border_radius: f32,
+    /// The thickness of the switcher border.
+    pub border_thickness: f32,
+    /// The color of the switcher background.
+    ///
+    /// Defaults to the snowcap theme's background color.
+    pub background_color: Color,
+    /// The color of the switcher border.
+    ///
+    /// Defaults to the snowcap theme's primary color.
+    pub border_color: Color,
+    /// The color of the border around the selected window.
+    pub selection_color: Color,
+    /// The font of window titles.
+    ///
+    /// Defaults to the snowcap theme's font.
+    pub font: Font,
+    /// The width of each thumbnail.
+    pub thumbnail_width: u32,
+    /// The height of each thumbnail.
+    pub thumbnail_height: u32,
+    /// The modifier that keeps the switcher open while held.
+    pub modifier: Mod,
+    entries: Vec<SwitcherEntry>,
+    selected: Arc<AtomicUsize>,
+}
+
+#[derive(Clone, Debug)]
+struct SwitcherEntry {
+    window: WindowHandle,
+    title: String,
+    identifier: Option<String>,
+}
+
+/// A message that changes a [`WindowSwitcher`].
+#[derive(Clone, Debug)]
+pub enum WindowSwitcherMessage {
+    /// Select the next window.
+    Next,
+    /// Select the previous window.
+    Previous,
+    /// The snowcap theme changed.
+    ThemeChanged(theme::Change),
+}
+
+static OPEN_SWITCHER: Mutex<Option<LayerHandle<WindowSwitcherMessage>>> = Mutex::new(None);
+
+impl Program for WindowSwitcher {
+    type Message = WindowSwitcherMessage;
+
+    fn update(&mut self, msg: Self::Message) {
+        if let WindowSwitcherMessage::ThemeChanged(change) = msg {
+            Look::restyle(
+                LookKind::Panel,
+                &change,
+                &mut self.border_radius,
+                &mut self.background_color,
+                &mut self.border_color,
+                &mut self.font,
+            );
+            return;
+        }
+
+        let len = self.entries.len();
+        if len == 0 {
+            return;
+        }
+
+        let _ = self
+            .selected
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |selected| {
+                Some(match msg {
+                    WindowSwitcherMessage::Next => (selected + 1) % len,
+                    WindowSwitcherMessage::Previous => (selected + len - 1) % len,
+                    WindowSwitcherMessage::ThemeChanged(_) => selected,
+                })
+            });
+    }
+
+    fn subscription(&self) -> Subscription<Self::Message> {
+        theme::changes().map(WindowSwitcherMessage::ThemeChanged)
+    }
+
+    fn view(&self) -> Option<WidgetDef<Self::Message>> {
+        let selected = self.selected.load(Ordering::Relaxed);
+
+        let entries =
+            self.entries
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| -> WidgetDef<Self::Message> {
+                    let thumbnail: WidgetDef<Self::Message> = match entry.identifier.clone() {
+                        Some(identifier) => Image::new(Handle::Toplevel(identifier))
+                            .width(Length::Fixed(self.thumbnail_width as f32))
+                            .height(Length::Fixed(self.thumbnail_height as f32))
+                            .content_fit(ContentFit::Contain)
+                            .into(),
+                        None => Space::new()
+                            .width(Length::Fixed(self.thumbnail_width as f32))
+                            .height(Length::Fixed(self.thumbnail_height as f32))
+                            .into(),
+                    };
+
+                    let title = Text::new(&entry.title)
+                        .style(text::Style::new().font(self.font.clone()).pixels(14.0))
+                        .wrapping(Wrapping::None)
+                        .width(Length::Fixed(self.thumbnail_width as f32))
+                        .horizontal_alignment(Alignment::Center);
+
+                    Container::new(
+                        Column::new_with_children([thumbnail, title.into()])
+                            .spacing(8.0)
+                            .item_alignment(Alignment::Center),
+                    )
+                    .padding(Padding::from(8.0))
+                    .clip(true)
+                    .style(snowcap_api::widget::container::Style {
+                        text_color: None,
+                        background: None,
+                        border: Some(Border {
+                            color: Some(if i == selected {
+                                self.selection_color
+                            } else {
+                                Color::rgba(0.0, 0.0, 0.0, 0.0)
+                            }),
+                            width: Some(3.0),
+                            radius: Some((self.border_radius / 2.0).into()),
+                        }),
+                    })
+                    .into()
+                });
+
+        let widget = Container::new(Row::new_with_children(entries).spacing(8.0))
+            .padding(Padding::from(12.0))
+            .style(snowcap_api::widget::container::Style {
+                text_color: None,
+                background: Some(Background::Color(self.background_color)),
+                border: Some(Border {
+                    color: Some(self.border_color),
+                    width: Some(self.border_thickness),
+                    radius: Some(self.border_radius.into()),
+                }),
+            });
+
+        Some(widget.into())
+    }
+}
+
+impl Default for WindowSwitcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WindowSwitcher {
+    /// Creates a window switcher with sane defaults.
+    pub fn new() -> Self {
+        let look = Look::current(LookKind::Panel);
+
+        WindowSwitcher {
+            border_radius: look.radius,
+            border_thickness: 6.0,
+            background_color: look.background,
+            border_color: look.border,
+            selection_color: [0.8, 0.8, 1.0].into(),
+            font: look.font,
+            thumbnail_width: 240,
+            thumbnail_height: 150,
+            modifier: Mod::ALT,
+            entries: Vec::new(),
+            selected: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Shows this window switcher, or selects the next window if one is already open.
+    pub fn show(self) {
+        self.show_with(WindowSwitcherMessage::Next);
+    }
+
+    /// Shows this window switcher starting from the least recently focused window,
+    /// or selects the previous window if one is already open.
+    pub fn show_reverse(self) {
+        self.show_with(WindowSwitcherMessage::Previous);
+    }
+
+    fn show_with(mut self, msg: WindowSwitcherMessage) {
+        let mut open_switcher = OPEN_SWITCHER.lock().unwrap();
+
+        if let Some(handle) = open_switcher.as_ref() {
+            handle.send_message(msg);
+            return;
+        }
+
+        let Some(output) = crate::output::get_focused() else {
+            return;
+        };
+
+        // The focus stack has the most recently focused window last.
+        let mut windows = output.keyboard_focus_stack().collect::<Vec<_>>();
+        windows.reverse();
+
+        self.entries = windows
+            .into_iter()
+            .map(|window| {
+                let mut title = window.title();
+                if title.is_empty() {
+                    title = window.app_id();
+                }
+                SwitcherEntry {
+                    title,
+                    identifier: window.foreign_toplevel_list_identifier(),
+                    window,
+                }
+            })
+            .collect();
+
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let start = match msg {
+            WindowSwitcherMessage::Next => 1.min(self.entries.len() - 1),
+            WindowSwitcherMessage::Previous => self.entries.len() - 1,
+            WindowSwitcherMessage::ThemeChanged(_) => 0,
+        };
+        self.selected.store(start, Ordering::Relaxed);
+
+        let windows = self
+            .entries
+            .iter()
+            .map(|entry| entry.window.clone())
+            .collect::<Vec<_>>();
+        let selected = self.selected.clone();
+        let modifier = self.modifier;
+
+        let handle = match snowcap_api::layer::new_widget(
+            self,
+            None,
+            KeyboardInteractivity::Exclusive,
+            ExclusiveZone::Respect,
+            ZLayer::Overlay,
+        ) {
+            Ok(handle) => handle,
+            Err(err) => {
+                println!("ERROR: Failed to show window switcher: {err}");
+                return;
+            }
+        };
+
+        handle.on_key_event(move |handle, event| {
+            if event.pressed {
+                match event.key {
+                    Keysym::Tab if event.mods.shift => {
+                        handle.send_message(WindowSwitcherMessage::Previous)
+                    }
+                    Keysym::Tab | Keysym::Right => handle.send_message(WindowSwitcherMessage::Next),
+                    Keysym::ISO_Left_Tab | Keysym::Left => {
+                        handle.send_message(WindowSwitcherMessage::Previous)
+                    }
+                    Keysym::Escape => {
+                        OPEN_SWITCHER.lock().unwrap().take();
+                        handle.close();
+                    }
+                    _ => (),
+                }
+                return;
+            }
+
+            if !modifier_released(&event, modifier) {
+                return;
+            }
+
+            if OPEN_SWITCHER.lock().unwrap().take().is_none() {
+                return;
+            }
+            handle.close();
+
+            let Some(window) = windows.get(selected.load(Ordering::Relaxed)) else {
+                return;
+            };
+
+            if !window.is_on_active_tag()
+                && let Some(tag) = window.tags().next()
+            {
+                tag.switch_to();
+            }
+            window.set_focused(true);
+            window.raise();
+        });
+
+        *open_switcher = Some(handle);
+    }
+}
+
+/// Returns whether a key release means `modifier` was let go.
+///
+/// The modifiers sent with the release of a modifier key still include it, so releasing
+/// one of its keys counts. Otherwise, any release without the modifier held means it was
+/// let go, even if that happened before the switcher got keyboard focus.
+fn modifier_released(event: &snowcap_api::input::KeyEvent, modifier: Mod) -> bool {
+    if event.pressed {
+        return false;
+    }
+
+    let modifier_keys = [
+        (Mod::ALT, [Keysym::Alt_L, Keysym::Alt_R]),
+        (Mod::ALT, [Keysym::Meta_L, Keysym::Meta_R]),
+        (Mod::SUPER, [Keysym::Super_L, Keysym::Super_R]),
+        (Mod::CTRL, [Keysym::Control_L, Keysym::Control_R]),
+        (Mod::SHIFT, [Keysym::Shift_L, Keysym::Shift_R]),
+    ];
+
+    if modifier_keys
+        .iter()
+        .any(|(m, keys)| modifier.contains(*m) && keys.contains(&event.key))
+    {
+        return true;
+    }
+
+    let mods = &event.mods;
+    let held = (modifier.contains(Mod::ALT) && mods.alt)
+        || (modifier.contains(Mod::SUPER) && mods.logo)
+        || (modifier.contains(Mod::CTRL) && mods.ctrl)
+        || (modifier.contains(Mod::SHIFT) && mods.shift);
+
+    !held
+}
+
+/// An application launcher.
+///
+/// Lists the applications and desktop actions from installed `.desktop` files, fuzzy-matching
+/// them against what's typed. Pressing ENTER launches the selected entry, UP and DOWN change
+/// the selection, and ESCAPE closes the launcher. Entries that are launched often rank higher.
+#[derive(Clone, Debug)]
+pub struct Launcher {
+    /// The radius of the launcher's corners.
+    ///
+    /// Defaults to the snowcap theme's radius.
+    pub border_radius: f32,
+    /// The thickness of the launcher border.
+    pub border_thickness: f32,
+    /// The color of the launcher background.
+    ///
+    /// Defaults to the snowcap theme's background color.
+    pub background_color: Color,
+    /// The color of the launcher border.
+    ///
+    /// Defaults to the snowcap theme's primary color.
+    pub border_color: Color,
+    /// The background color of the selected entry.
+    pub selection_color: Color,
+    /// The color of entry descriptions.
+    pub description_color: Color,
+    /// The font of the launcher.
+    ///
+    /// Defaults to the snowcap theme's font.
+    pub font: Font,
+    /// The width of the launcher.
+    pub width: u32,
+    /// The maximum number of entries shown at once.
+    pub max_results: usize,
+    /// The command that entries with `Terminal=true` are run with, followed by the entry's command.
+    pub terminal: Vec<String>,
+    search: Arc<Mutex<LauncherSearch>>,
+}
+
+#[derive(Debug, Default)]
+struct LauncherSearch {
+    entries: Vec<desktop_entry::LaunchEntry>,
+    history: desktop_entry::History,
+    query: String,
+    matches: Vec<usize>,
+    selected: usize,
+}
+
+impl LauncherSearch {
+    fn refresh(&mut self) {
+        self.matches = desktop_entry::rank(&self.entries, &self.history, &self.query);
+        self.selected = 0;
+    }
+}
+
+/// A message that changes a [`Launcher`].
+#[derive(Clone, Debug)]
+pub enum LauncherMessage {
+    /// The search query changed.
+    QueryChanged(String),
+    /// Select the next entry.
+    Next,
+    /// Select the previous entry.
+    Previous,
+    /// The snowcap theme changed.
+    ThemeChanged(theme::Change),
+}
+
+impl Program for Launcher {
+    type Message = LauncherMessage;
+
+    fn update(&mut self, msg: Self::Message) {
+        if let LauncherMessage::ThemeChanged(change) = msg {
+            Look::restyle(
+                LookKind::Panel,
+                &change,
+                &mut self.border_radius,
+                &mut self.background_color,
+                &mut self.border_color,
+                &mut self.font,
+            );
+            return;
+        }
+
+        let mut search = self.search.lock().unwrap();
+        let shown = search.matches.len().min(self.max_results);
+
+        match msg {
+            LauncherMessage::QueryChanged(query) => {
+                search.query = query;
+                search.refresh();
+            }
+            LauncherMessage::Next if shown > 0 => search.selected = (search.selected + 1) % shown,
+            LauncherMessage::Previous if shown > 0 => {
+                search.selected = (search.selected + shown - 1) % shown
+            }
+            LauncherMessage::Next
+            | LauncherMessage::Previous
+            | LauncherMessage::ThemeChanged(_) => (),
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Self::Message> {
+        theme::changes().map(LauncherMessage::ThemeChanged)
+    }
+
+    fn view(&self) -> Option<WidgetDef<Self::Message>> {
+        let search = self.search.lock().unwrap();
+
+        let input = TextInput::new("Search applications", &search.query)
+            .id(Self::INPUT_ID)
+            .on_input(LauncherMessage::QueryChanged)
+            .font(self.font.clone())
+            .padding(Padding::from(8.0))
+            .width(Length::Fill);
+
+        let results = search
+            .matches
+            .iter()
+            .take(self.max_results)
+            .enumerate()
+            .map(|(i, &index)| -> WidgetDef<Self::Message> {
+                let entry = &search.entries[index];
+
+                let mut column = Column::new().push(
+                    Text::new(&entry.name)
+                        .style(text::Style::new().font(self.font.clone()).pixels(16.0))
+                        .wrapping(Wrapping::None),
+                );
+                if let Some(description) = entry.description.as_ref() {
+                    column = column.push(
+                        Text::new(description)
+                            .style(
+                                text::Style::new()
+                                    .font(self.font.clone())
+                                    .pixels(12.0)
+                                    .color(self.description_color),
+                            )
+                            .wrapping(Wrapping::None),
+                    );
+                }
+
+                Container::new(column)
+                    .width(Length::Fill)
+                    .padding(Padding::from(8.0))
+                    .clip(true)
+                    .style(snowcap_api::widget::container::Style {
+                        text_color: None,
+                        background: (i == search.selected)
+                            .then_some(Background::Color(self.selection_color)),
+                        border: Some(Border {
+                            color: None,
+                            width: None,
+                            radius: Some((self.border_radius / 2.0).into()),
+                        }),
+                    })
+                    .into()
+            });
+
+        let widget = Container::new(
+            Column::new_with_children(std::iter::once(input.into()).chain(results)).spacing(8.0),
+        )
+        .width(Length::Fixed(self.width as f32))
+        .padding(Padding::from(12.0))
+        .style(snowcap_api::widget::container::Style {
+            text_color: None,
+            background: Some(Background::Color(self.background_color)),
+            border: Some(Border {
+                color: Some(self.border_color),
+                width: Some(self.border_thickness),
+                radius: Some(self.border_radius.into()),
+            }),
+        });
+
+        Some(widget.into())
+    }
+}
+
+impl Default for Launcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Launcher {
+    const INPUT_ID: &str = "launcher_input";
+
+    /// Creates a launcher with sane defaults.
+    pub fn new() -> Self {
+        let look = Look::current(LookKind::Panel);
+
+        Launcher {
+            border_radius: look.radius,
+            border_thickness: 6.0,
+            background_color: look.background,
+            border_color: look.border,
+            selection_color: [0.3, 0.3, 0.5, 0.8].into(),
+            description_color: [0.7, 0.7, 0.8].into(),
+            font: look.font,
+            width: 600,
+            max_results: 8,
+            terminal: vec!["alacritty".into(), "-e".into()],
+            search: Default::default(),
+        }
+    }
+
+    /// Indexes installed applications and shows this launcher.
+    pub fn show(self) {
+        {
+            let mut search = self.search.lock().unwrap();
+            search.entries = desktop_entry::index();
+            search.history = desktop_entry::History::load();
+            search.query.clear();
+            search.refresh();
+        }
+
+        let search = self.search.clone();
+        let terminal = self.terminal.clone();
+
+        let handle = match snowcap_api::layer::new_widget(
+            self,
+            None,
+            KeyboardInteractivity::Exclusive,
+            ExclusiveZone::Respect,
+            ZLayer::Overlay,
+        ) {
+            Ok(handle) => handle,
+            Err(err) => {
+                println!("ERROR: Failed to show launcher: {err}");
+                return;
+            }
+        };
+
+        handle.operate(snowcap_api::widget::operation::focusable::focus(
+            Self::INPUT_ID,
+        ));
+
+        // The text input captures most keys, so look at every press.
+        handle.on_key_event(move |handle, event| {
+            if !event.pressed {
+                return;
+            }
+
+            match event.key {
+                Keysym::Down | Keysym::Tab => handle.send_message(LauncherMessage::Next),
+                Keysym::n if event.mods.ctrl => handle.send_message(LauncherMessage::Next),
+                Keysym::Up | Keysym::ISO_Left_Tab => handle.send_message(LauncherMessage::Previous),
+                Keysym::p if event.mods.ctrl => handle.send_message(LauncherMessage::Previous),
+                Keysym::Escape => handle.close(),
+                Keysym::Return | Keysym::KP_Enter => {
+                    let entry = {
+                        let search = search.lock().unwrap();
+                        search
+                            .matches
+                            .get(search.selected)
+                            .map(|&index| search.entries[index].clone())
+                    };
+                    let Some(entry) = entry else {
+                        return;
+                    };
+
+                    handle.close();
+
+                    if let Some(mut command) = entry.command(&terminal) {
+                        command.spawn();
+                    }
+                    search.lock().unwrap().history.record(&entry.id);
+                }
+                _ => (),
+            }
+        });
+    }
+}
+
+/// A layer surface on every connected output.
+///
+/// A new layer is opened whenever an output connects and closed when it disconnects,
+/// which makes this a good fit for things like bars.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use pinnacle_api::snowcap::LayerPerOutput;
+/// # use pinnacle_api::experimental::snowcap_api::layer::{
+/// #     Anchor, ExclusiveZone, KeyboardInteractivity, Margin, ZLayer,
+/// # };
+/// # use pinnacle_api::experimental::snowcap_api::widget::Program;
+/// # fn example<P: Program<Message = ()> + Send + 'static>(new_bar: fn(String) -> P) {
+/// let bars = LayerPerOutput::new(
+///     Some(Anchor::Top),
+///     KeyboardInteractivity::None,
+///     ExclusiveZone::Respect,
+///     ZLayer::Top,
+///     Margin::from(4),
+///     move |output| new_bar(output.name()),
+/// );
+/// # }
+/// ```
+pub struct LayerPerOutput<Msg> {
+    layers: Arc<Mutex<HashMap<String, LayerHandle<Msg>>>>,
+    connect: SignalHandle,
+    disconnect: SignalHandle,
+}
+
+impl<Msg> std::fmt::Debug for LayerPerOutput<Msg> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LayerPerOutput")
+            .field("layers", &self.layers)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Msg> LayerPerOutput<Msg>
+where
+    Msg: Clone + Send + 'static,
+{
+    /// Opens a layer created by `new_program` on every enabled output,
+    /// as well as on outputs that connect later.
+    pub fn new<P>(
+        anchor: Option<Anchor>,
+        keyboard_interactivity: KeyboardInteractivity,
+        exclusive_zone: ExclusiveZone,
+        layer: ZLayer,
+        margin: Margin,
+        new_program: impl FnMut(&OutputHandle) -> P + Send + 'static,
+    ) -> Self
+    where
+        P: Program<Message = Msg> + Send + 'static,
+    {
+        let layers = Arc::new(Mutex::new(HashMap::<String, LayerHandle<Msg>>::new()));
+        let new_program = Arc::new(Mutex::new(new_program));
+
+        let open = {
+            let layers = layers.clone();
+            move |output: &OutputHandle| {
+                let program = (new_program.lock().unwrap())(output);
+
+                let handle = snowcap_api::layer::new_widget_with_options(
+                    program,
+                    anchor,
+                    keyboard_interactivity,
+                    exclusive_zone,
+                    layer,
+                    LayerOptions {
+                        output: Some(output.name()),
+                        margin,
+                    },
+                );
+
+                match handle {
+                    Ok(handle) => {
+                        if let Some(old) = layers.lock().unwrap().insert(output.name(), handle) {
+                            old.close();
+                        }
+                    }
+                    Err(err) => {
+                        println!("ERROR: failed to open layer on {}: {err}", output.name());
+                    }
+                }
+            }
+        };
+
+        for output in crate::output::get_all_enabled() {
+            open(&output);
+        }
+
+        let connect = crate::output::connect_signal(OutputSignal::Connect(Box::new(open)));
+
+        let disconnect = crate::output::connect_signal(OutputSignal::Disconnect(Box::new({
+            let layers = layers.clone();
+            move |output| {
+                if let Some(handle) = layers.lock().unwrap().remove(&output.name()) {
+                    handle.close();
+                }
+            }
+        })));
+
+        Self {
+            layers,
+            connect,
+            disconnect,
+        }
+    }
+}
+
+impl<Msg> LayerPerOutput<Msg> {
+    /// Returns the layer on the given output, if there is one.
+    pub fn layer(&self, output: &OutputHandle) -> Option<LayerHandle<Msg>> {
+        self.layers.lock().unwrap().get(&output.name()).cloned()
+    }
+
+    /// Returns all currently open layers.
+    pub fn layers(&self) -> Vec<LayerHandle<Msg>> {
+        self.layers.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Closes all layers and stops following output connections.
+    pub fn close(self) {
+        self.connect.disconnect();
+        self.disconnect.disconnect();
+
+        for (_, handle) in self.layers.lock().unwrap().drain() {
+            handle.close();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use snowcap_api::input::{KeyEvent, Modifiers};
+
+    use super::*;
+
+    fn release(key: Keysym, mods: Modifiers) -> KeyEvent {
+        KeyEvent {
+            key,
+            mods,
+            pressed: false,
+            captured: false,
+            text: None,
+        }
+    }
+
+    const ALT: Modifiers = Modifiers {
+        shift: false,
+        ctrl: false,
+        alt: true,
+        logo: false,
+    };
+
+    #[test]
+    fn releasing_the_modifier_key_releases_the_modifier() {
+        // The modifiers are only updated after the key event
+        assert!(modifier_released(&release(Keysym::Alt_L, ALT), Mod::ALT));
+        assert!(modifier_released(&release(Keysym::Alt_R, ALT), Mod::ALT));
+        assert!(!modifier_released(&release(Keysym::Super_L, ALT), Mod::ALT));
+    }
+
+    #[test]
+    fn releasing_other_keys_with_the_modifier_held_does_not_release_it() {
+        assert!(!modifier_released(&release(Keysym::Tab, ALT), Mod::ALT));
+
+        let mut press = release(Keysym::Alt_L, ALT);
+        press.pressed = true;
+        assert!(!modifier_released(&press, Mod::ALT));
+    }
+
+    #[test]
+    fn modifier_changes_without_the_modifier_release_it() {
+        // Sent when the modifier was let go before the switcher got keyboard focus
+        let event = release(Keysym::NoSymbol, Modifiers::default());
+        assert!(modifier_released(&event, Mod::ALT));
+        assert!(!modifier_released(
+            &release(Keysym::NoSymbol, ALT),
+            Mod::ALT
+        ));
+    }
+
+    fn quit_prompt(variant: Variant, theme: &Theme) -> QuitPrompt {
+        let look = Look::new(LookKind::Alert, variant, theme);
+
+        QuitPrompt {
+            border_radius: look.radius,
+            border_thickness: 6.0,
+            background_color: look.background,
+            border_color: look.border,
+            font: look.font,
+            width: 450,
+            height: 140,
+        }
+    }
+
+    #[test]
+    fn switching_variants_restyles_fields_that_follow_the_theme() {
+        let theme = Theme::new();
+        let mut prompt = quit_prompt(Variant::Dark, &theme);
+        let border_color = Color::rgb(0.0, 1.0, 0.0);
+        prompt.border_color = border_color;
+
+        prompt.update(theme::Change {
+            previous: (Variant::Dark, theme.clone()),
+            current: (Variant::Light, theme.clone()),
+        });
+
+        let light = Look::new(LookKind::Alert, Variant::Light, &theme);
+        assert_eq!(prompt.background_color, light.background);
+        assert_ne!(
+            prompt.background_color,
+            Look::new(LookKind::Alert, Variant::Dark, &theme).background
+        );
+        assert_eq!(prompt.border_color, border_color);
+        assert_eq!(prompt.border_radius, light.radius);
+    }
+
+    #[test]
+    fn theme_changes_restyle_fields_that_follow_the_theme() {
+        let dark = Theme::new()
+            .palette(theme::Palette {
+                danger: Some(Color::rgb(1.0, 0.0, 0.0)),
+                ..Default::default()
+            })
+            .radius(4.0);
+        let mut prompt = quit_prompt(Variant::Dark, &dark);
+        prompt.border_radius = 0.0;
+
+        let changed = dark
+            .clone()
+            .palette(theme::Palette {
+                danger: Some(Color::rgb(0.5, 0.0, 0.0)),
+                ..Default::default()
+            })
+            .radius(8.0)
+            .font(Font::new_with_family(Family::Monospace));
+
+        prompt.update(theme::Change {
+            previous: (Variant::Dark, dark),
+            current: (Variant::Dark, changed),
+        });
+
+        assert_eq!(prompt.border_color, Color::rgb(0.5, 0.0, 0.0));
+        assert_eq!(prompt.font, Font::new_with_family(Family::Monospace));
+        assert_eq!(prompt.border_radius, 0.0);
+    }
+}
@@ -0,0 +1,837 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Indexing of XDG desktop entries for the [`Launcher`](super::Launcher).
+
+use std::{
+    collections::{HashMap, HashSet},
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use crate::process::Command;
+
+/// Field codes that expand to files or URLs. The launcher never passes any.
+const FILE_FIELD_CODES: [&str; 10] = ["%f", "%F", "%u", "%U", "%d", "%D", "%n", "%N", "%v", "%m"];
+
+/// How much each doubling of an entry's launch count adds to its score.
+const FREQUENCY_WEIGHT: i64 = 16;
+
+/// An application or desktop action that can be launched.
+#[derive(Clone, Debug)]
+pub(super) struct LaunchEntry {
+    /// The desktop file ID, followed by `:<action>` for desktop actions.
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    keywords: Vec<String>,
+    exec: Vec<String>,
+    cwd: Option<String>,
+    terminal: bool,
+}
+
+impl LaunchEntry {
+    /// Scores how well this entry matches `query`, or returns `None` if it doesn't.
+    fn score(&self, query: &str) -> Option<i64> {
+        let name = fuzzy_score(query, &self.name);
+        let description = self
+            .description
+            .as_deref()
+            .and_then(|description| fuzzy_score(query, description))
+            .map(|score| score / 2);
+        let keywords = self
+            .keywords
+            .iter()
+            .filter_map(|keyword| fuzzy_score(query, keyword))
+            .max()
+            .map(|score| score / 2);
+
+        [name, description, keywords].into_iter().flatten().max()
+    }
+
+    /// Builds the command that launches this entry.
+    ///
+    /// Entries that need a terminal are run with `terminal` prepended.
+    pub fn command(&self, terminal: &[String]) -> Option<Command> {
+        let argv = if self.terminal {
+            terminal.iter().chain(self.exec.iter()).collect::<Vec<_>>()
+        } else {
+            self.exec.iter().collect()
+        };
+
+        let (program, args) = argv.split_first()?;
+
+        let mut command = Command::new(program);
+        command.args(args).activation_token();
+        if let Some(cwd) = self.cwd.as_ref() {
+            command.current_dir(cwd);
+        }
+
+        Some(command)
+    }
+}
+
+/// Collects all launchable entries from the applications directories in the XDG data dirs.
+///
+/// Entries earlier in the data dirs shadow ones with the same desktop file ID later on.
+pub(super) fn index() -> Vec<LaunchEntry> {
+    let locales = locales();
+    let current_desktops = std::env::var("XDG_CURRENT_DESKTOP")
+        .map(|desktops| {
+            desktops
+                .split(':')
+                .map(str::to_ascii_lowercase)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+
+    for dir in data_dirs() {
+        let apps_dir = dir.join("applications");
+        let mut files = Vec::new();
+        collect_desktop_files(&apps_dir, &mut files);
+        files.sort();
+
+        for path in files {
+            let Ok(relative) = path.strip_prefix(&apps_dir) else {
+                continue;
+            };
+            let id = relative.to_string_lossy().replace('/', "-");
+
+            // Hidden or undisplayed entries still shadow later ones.
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+
+            let Ok(contents) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+
+            let groups = parse_groups(&contents, &locales);
+            entries.extend(launch_entries(id, &path, &groups, &current_desktops));
+        }
+    }
+
+    entries
+}
+
+fn collect_desktop_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_desktop_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "desktop") {
+            files.push(path);
+        }
+    }
+}
+
+/// A group's keys, each with its value and how good a locale match it was.
+type Group = HashMap<String, (usize, String)>;
+
+fn get<'a>(group: &'a Group, key: &str) -> Option<&'a str> {
+    group.get(key).map(|(_, value)| value.as_str())
+}
+
+fn is_true(group: &Group, key: &str) -> bool {
+    get(group, key) == Some("true")
+}
+
+fn launch_entries(
+    id: String,
+    path: &Path,
+    groups: &HashMap<String, Group>,
+    current_desktops: &[String],
+) -> Vec<LaunchEntry> {
+    let Some(main) = groups.get("Desktop Entry") else {
+        return Vec::new();
+    };
+    if get(main, "Type") != Some("Application")
+        || is_true(main, "NoDisplay")
+        || is_true(main, "Hidden")
+    {
+        return Vec::new();
+    }
+
+    if let Some(try_exec) = get(main, "TryExec")
+        && !is_executable(&unescape(try_exec))
+    {
+        return Vec::new();
+    }
+
+    let shown_in = |key: &str| {
+        get(main, key).map(|desktops| {
+            split_list(desktops)
+                .iter()
+                .any(|desktop| current_desktops.contains(&desktop.to_ascii_lowercase()))
+        })
+    };
+    if shown_in("OnlyShowIn") == Some(false) || shown_in("NotShowIn") == Some(true) {
+        return Vec::new();
+    }
+
+    let Some(name) = get(main, "Name").map(unescape) else {
+        return Vec::new();
+    };
+    let icon = get(main, "Icon").map(unescape);
+    let description = get(main, "GenericName")
+        .or_else(|| get(main, "Comment"))
+        .map(unescape);
+    let keywords = get(main, "Keywords").map(split_list).unwrap_or_default();
+    let cwd = get(main, "Path").map(unescape);
+    let terminal = is_true(main, "Terminal");
+
+    let exec = |group: &Group, name: &str| {
+        get(group, "Exec")
+            .map(|exec| parse_exec(&unescape(exec), name, icon.as_deref(), path))
+            .filter(|exec| !exec.is_empty())
+    };
+
+    let mut entries = Vec::new();
+
+    if let Some(exec) = exec(main, &name) {
+        entries.push(LaunchEntry {
+            id: id.clone(),
+            name: name.clone(),
+            description: description.clone(),
+            keywords: keywords.clone(),
+            exec,
+            cwd: cwd.clone(),
+            terminal,
+        });
+    }
+
+    for action in get(main, "Actions").map(split_list).unwrap_or_default() {
+        let Some(group) = groups.get(&format!("Desktop Action {action}")) else {
+            continue;
+        };
+        let Some(action_name) = get(group, "Name").map(unescape) else {
+            continue;
+        };
+        let Some(exec) = exec(group, &name) else {
+            continue;
+        };
+
+        entries.push(LaunchEntry {
+            id: format!("{id}:{action}"),
+            name: format!("{name}: {action_name}"),
+            description: description.clone(),
+            keywords: keywords.clone(),
+            exec,
+            cwd: cwd.clone(),
+            terminal,
+        });
+    }
+
+    entries
+}
+
+/// Parses a desktop file into its groups, keeping the best localized value of every key.
+fn parse_groups(contents: &str, locales: &[String]) -> HashMap<String, Group> {
+    let mut groups = HashMap::<String, Group>::new();
+    let mut current = None;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            current = Some(header.to_string());
+            groups.entry(header.to_string()).or_default();
+            continue;
+        }
+
+        let (Some(group), Some((key, value))) = (current.as_ref(), line.split_once('=')) else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+
+        let (key, rank) = match key.split_once('[') {
+            Some((key, locale)) => {
+                let locale = locale.trim_end_matches(']');
+                let Some(rank) = locales.iter().position(|l| l == locale) else {
+                    continue;
+                };
+                (key, rank)
+            }
+            None => (key, locales.len()),
+        };
+
+        let group = groups.entry(group.clone()).or_default();
+        if group
+            .get(key)
+            .is_none_or(|(existing_rank, _)| rank < *existing_rank)
+        {
+            group.insert(key.to_string(), (rank, value.to_string()));
+        }
+    }
+
+    groups
+}
+
+/// Returns the locale keys to look for, best match first.
+///
+/// For `lang_COUNTRY.ENCODING@MODIFIER`, these are `lang_COUNTRY@MODIFIER`, `lang_COUNTRY`,
+/// `lang@MODIFIER`, and `lang`.
+fn locales() -> Vec<String> {
+    let Some(locale) = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|locale| !locale.is_empty())
+    else {
+        return Vec::new();
+    };
+
+    let (locale, modifier) = match locale.split_once('@') {
+        Some((locale, modifier)) => (locale, Some(modifier)),
+        None => (locale.as_str(), None),
+    };
+    let locale = locale.split('.').next().unwrap_or_default();
+    let (lang, country) = match locale.split_once('_') {
+        Some((lang, country)) => (lang, Some(country)),
+        None => (locale, None),
+    };
+
+    let mut locales = Vec::new();
+    if let (Some(country), Some(modifier)) = (country, modifier) {
+        locales.push(format!("{lang}_{country}@{modifier}"));
+    }
+    if let Some(country) = country {
+        locales.push(format!("{lang}_{country}"));
+    }
+    if let Some(modifier) = modifier {
+        locales.push(format!("{lang}@{modifier}"));
+    }
+    locales.push(lang.to_string());
+
+    locales
+}
+
+/// Unescapes a `string` or `localestring` value.
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('s') => unescaped.push(' '),
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('r') => unescaped.push('\r'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+/// Splits a `;`-separated list value.
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.as_str().starts_with(';') => {
+                item.push(';');
+                chars.next();
+            }
+            ';' => items.push(unescape(&std::mem::take(&mut item))),
+            c => item.push(c),
+        }
+    }
+    items.push(unescape(&item));
+
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+/// Splits an `Exec` value into arguments, expanding or dropping field codes.
+fn parse_exec(exec: &str, name: &str, icon: Option<&str>, path: &Path) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut arg = None::<String>;
+    let mut quoted = false;
+    let mut chars = exec.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                arg.get_or_insert_default();
+            }
+            '\\' if quoted => {
+                if let Some(c) = chars.next() {
+                    arg.get_or_insert_default().push(c);
+                }
+            }
+            c if c.is_whitespace() && !quoted => args.extend(arg.take()),
+            c => arg.get_or_insert_default().push(c),
+        }
+    }
+    args.extend(arg);
+
+    let mut expanded = Vec::new();
+
+    for arg in args {
+        if FILE_FIELD_CODES.contains(&arg.as_str()) {
+            continue;
+        }
+
+        if arg == "%i" {
+            if let Some(icon) = icon {
+                expanded.extend(["--icon".to_string(), icon.to_string()]);
+            }
+            continue;
+        }
+
+        let mut expanded_arg = String::with_capacity(arg.len());
+        let mut chars = arg.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                expanded_arg.push(c);
+                continue;
+            }
+
+            match chars.next() {
+                Some('%') => expanded_arg.push('%'),
+                Some('c') => expanded_arg.push_str(name),
+                Some('k') => expanded_arg.push_str(&path.to_string_lossy()),
+                _ => (),
+            }
+        }
+
+        expanded.push(expanded_arg);
+    }
+
+    expanded
+}
+
+fn is_executable(program: &str) -> bool {
+    let is_executable_file = |path: &Path| {
+        path.metadata()
+            .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+    };
+
+    let path = Path::new(program);
+    if path.is_absolute() {
+        return is_executable_file(path);
+    }
+
+    std::env::var_os("PATH").is_some_and(|paths| {
+        std::env::split_paths(&paths).any(|dir| is_executable_file(&dir.join(program)))
+    })
+}
+
+fn data_dirs() -> Vec<PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".local/share")));
+
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+
+    data_home
+        .into_iter()
+        .chain(data_dirs.split(':').map(PathBuf::from))
+        .collect()
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Scores how well `query` fuzzy-matches `candidate`, case-insensitively.
+///
+/// Returns `None` unless every non-whitespace character of the query appears in the
+/// candidate in order. Consecutive matches and matches at the start of words score higher.
+pub(super) fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let mut chars = Vec::with_capacity(candidate.len());
+    let mut prev = None::<char>;
+    for c in candidate.chars() {
+        let word_start = prev.is_none_or(|prev| {
+            !prev.is_alphanumeric() || (prev.is_lowercase() && c.is_uppercase())
+        });
+        for (i, lower) in c.to_lowercase().enumerate() {
+            chars.push((lower, word_start && i == 0));
+        }
+        prev = Some(c);
+    }
+
+    let mut score = 0;
+    let mut next = 0;
+    let mut last_match = None::<usize>;
+
+    for q in query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+    {
+        let offset = chars[next..].iter().position(|(c, _)| *c == q)?;
+        let index = next + offset;
+
+        score += 16;
+        if chars[index].1 {
+            score += 24;
+        }
+        match last_match {
+            Some(last) if last + 1 == index => score += 16,
+            Some(_) => score -= (offset as i64).min(8),
+            None => score -= (index as i64).min(16),
+        }
+
+        last_match = Some(index);
+        next = index + 1;
+    }
+
+    // Prefer shorter candidates when everything else is equal.
+    Some(score - (chars.len() as i64) / 8)
+}
+
+/// Ranks `entries` against `query`, returning the indices of matching entries,
+/// best match first.
+///
+/// Entries launched more often rank higher. With an empty query, every entry matches
+/// and they're ordered by launch count.
+pub(super) fn rank(entries: &[LaunchEntry], history: &History, query: &str) -> Vec<usize> {
+    let mut ranked = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let score = if query.trim().is_empty() {
+                0
+            } else {
+                entry.score(query)?
+            };
+            let launches = i64::from(history.launches(&entry.id));
+            Some((
+                i,
+                score + FREQUENCY_WEIGHT * i64::from((launches + 1).ilog2()),
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    ranked.sort_by(|(a, a_score), (b, b_score)| {
+        b_score.cmp(a_score).then_with(|| {
+            entries[*a]
+                .name
+                .to_lowercase()
+                .cmp(&entries[*b].name.to_lowercase())
+        })
+    });
+
+    ranked.into_iter().map(|(i, _)| i).collect()
+}
+
+/// How many times each entry has been launched, persisted in
+/// `$XDG_STATE_HOME/pinnacle/launcher_history`.
+#[derive(Clone, Debug, Default)]
+pub(super) struct History {
+    launches: HashMap<String, u32>,
+}
+
+impl History {
+    fn path() -> Option<PathBuf> {
+        let state_home = std::env::var_os("XDG_STATE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home_dir().map(|home| home.join(".local/state")))?;
+
+        Some(state_home.join("pinnacle").join("launcher_history"))
+    }
+
+    /// Loads the history, starting fresh if it doesn't exist or can't be read.
+    pub fn load() -> Self {
+        let launches = Self::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|contents| {
+                contents
+                    .lines()
+                    .filter_map(|line| {
+                        let (count, id) = line.split_once('\t')?;
+                        Some((id.to_string(), count.parse().ok()?))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { launches }
+    }
+
+    pub fn launches(&self, id: &str) -> u32 {
+        self.launches.get(id).copied().unwrap_or_default()
+    }
+
+    /// Records a launch of the entry with the given ID and saves the history.
+    pub fn record(&mut self, id: &str) {
+        let count = self.launches.entry(id.to_string()).or_default();
+        *count = count.saturating_add(1);
+
+        let Some(path) = Self::path() else {
+            return;
+        };
+
+        let contents = self
+            .launches
+            .iter()
+            .map(|(id, count)| format!("{count}\t{id}\n"))
+            .collect::<String>();
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, contents));
+
+        if let Err(err) = result {
+            println!("ERROR: Failed to save launcher history: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATH: &str = "/usr/share/applications/app.desktop";
+
+    fn exec(exec: &str) -> Vec<String> {
+        parse_exec(exec, "App", Some("app-icon"), Path::new(PATH))
+    }
+
+    fn entry(id: &str, name: &str) -> LaunchEntry {
+        LaunchEntry {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: None,
+            keywords: Vec::new(),
+            exec: vec![id.to_string()],
+            cwd: None,
+            terminal: false,
+        }
+    }
+
+    fn history(launches: &[(&str, u32)]) -> History {
+        History {
+            launches: launches
+                .iter()
+                .map(|(id, count)| (id.to_string(), *count))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn file_field_codes_are_dropped() {
+        assert_eq!(exec("app %f"), ["app"]);
+        assert_eq!(exec("app %U --new-window"), ["app", "--new-window"]);
+    }
+
+    #[test]
+    fn field_codes_are_expanded() {
+        assert_eq!(exec("app --name=%c"), ["app", "--name=App"]);
+        assert_eq!(exec("app %k"), ["app", PATH]);
+        assert_eq!(exec("app 100%%"), ["app", "100%"]);
+        assert_eq!(exec("app %i"), ["app", "--icon", "app-icon"]);
+        assert_eq!(parse_exec("app %i", "App", None, Path::new(PATH)), ["app"]);
+        // Unknown and deprecated field codes are removed
+        assert_eq!(exec("app a%zb"), ["app", "ab"]);
+    }
+
+    #[test]
+    fn quoted_arguments_keep_their_whitespace() {
+        assert_eq!(
+            exec(r#""/opt/my app/app" --title "Hello world""#),
+            ["/opt/my app/app", "--title", "Hello world"]
+        );
+        assert_eq!(exec(r#"app """#), ["app", ""]);
+        assert_eq!(exec("app   --flag\t"), ["app", "--flag"]);
+    }
+
+    #[test]
+    fn quoted_arguments_unescape_backslashes() {
+        assert_eq!(
+            exec(r#"sh -c "echo \"a b\" \$HOME \\""#),
+            ["sh", "-c", r#"echo "a b" $HOME \"#]
+        );
+    }
+
+    #[test]
+    fn exec_values_are_unescaped_before_being_split() {
+        // As written in a desktop file, where backslashes are escaped once more
+        let value = r#"sh -c "echo \\"hi\\"""#;
+        assert_eq!(exec(&unescape(value)), ["sh", "-c", r#"echo "hi""#]);
+    }
+
+    #[test]
+    fn string_values_are_unescaped() {
+        assert_eq!(unescape(r"a\sb\tc\nd\re"), "a b\tc\nd\re");
+        assert_eq!(unescape(r"back\\slash"), r"back\slash");
+        assert_eq!(unescape(r"trailing\"), r"trailing\");
+    }
+
+    #[test]
+    fn lists_split_on_unescaped_semicolons() {
+        assert_eq!(split_list("a;b;c;"), ["a", "b", "c"]);
+        assert_eq!(split_list(r"a\;b;c"), ["a;b", "c"]);
+        assert_eq!(split_list(r";;a\sb;;"), ["a b"]);
+        assert!(split_list("").is_empty());
+    }
+
+    #[test]
+    fn the_best_localized_value_is_kept() {
+        let locales = ["de_DE".to_string(), "de".to_string()];
+        let contents = "\
+# A comment
+[Desktop Entry]
+Name[fr]=Bonjour
+Name[de]=Hallo
+Name=Hello
+Name[de_DE]=Guten Tag
+Comment = Says hello
+Comment[de]=Sagt Hallo
+
+[Desktop Action new]
+Name=New
+";
+
+        let groups = parse_groups(contents, &locales);
+        let main = &groups["Desktop Entry"];
+        assert_eq!(get(main, "Name"), Some("Guten Tag"));
+        assert_eq!(get(main, "Comment"), Some("Sagt Hallo"));
+        assert_eq!(get(&groups["Desktop Action new"], "Name"), Some("New"));
+
+        let groups = parse_groups(contents, &[]);
+        assert_eq!(get(&groups["Desktop Entry"], "Name"), Some("Hello"));
+        assert_eq!(get(&groups["Desktop Entry"], "Comment"), Some("Says hello"));
+    }
+
+    #[test]
+    fn desktop_actions_become_entries() {
+        let contents = "\
+[Desktop Entry]
+Type=Application
+Name=Browser
+Exec=browser %u
+Keywords=web;internet;
+Actions=private;missing;
+
+[Desktop Action private]
+Name=Private Window
+Exec=browser --private %u
+";
+
+        let groups = parse_groups(contents, &[]);
+        let entries = launch_entries("browser.desktop".into(), Path::new(PATH), &groups, &[]);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, "browser.desktop");
+        assert_eq!(entries[0].exec, ["browser"]);
+        assert_eq!(entries[0].keywords, ["web", "internet"]);
+        assert_eq!(entries[1].id, "browser.desktop:private");
+        assert_eq!(entries[1].name, "Browser: Private Window");
+        assert_eq!(entries[1].exec, ["browser", "--private"]);
+    }
+
+    #[test]
+    fn hidden_entries_are_skipped() {
+        let entries = |extra: &str| {
+            let contents =
+                format!("[Desktop Entry]\nType=Application\nName=App\nExec=app\n{extra}\n");
+            let groups = parse_groups(&contents, &[]);
+            launch_entries(
+                "app.desktop".into(),
+                Path::new(PATH),
+                &groups,
+                &["gnome".to_string()],
+            )
+        };
+
+        assert_eq!(entries("").len(), 1);
+        assert!(entries("NoDisplay=true").is_empty());
+        assert!(entries("Hidden=true").is_empty());
+        assert!(entries("OnlyShowIn=KDE;").is_empty());
+        assert_eq!(entries("OnlyShowIn=KDE;GNOME;").len(), 1);
+        assert!(entries("NotShowIn=GNOME;").is_empty());
+    }
+
+    #[test]
+    fn fuzzy_matches_need_every_character_in_order() {
+        assert!(fuzzy_score("fox", "Firefox").is_some());
+        assert!(fuzzy_score("FIRE", "firefox").is_some());
+        assert!(fuzzy_score("fire fox", "Firefox").is_some());
+        assert!(fuzzy_score("xf", "Firefox").is_none());
+        assert!(fuzzy_score("foxes", "Firefox").is_none());
+    }
+
+    #[test]
+    fn word_starts_and_consecutive_matches_score_higher() {
+        let score = |query, candidate| fuzzy_score(query, candidate).unwrap();
+
+        assert!(score("fm", "File Manager") > score("fm", "Fireman"));
+        assert!(score("fm", "FileManager") > score("fm", "Fireman"));
+        assert!(score("fire", "Firefox") > score("fire", "Fixture Editor"));
+        // Shorter candidates win ties
+        assert!(score("term", "Terminal") > score("term", "Terminal Emulator Deluxe"));
+    }
+
+    #[test]
+    fn entries_are_ranked_by_score_then_name() {
+        let entries = [
+            entry("manager", "Fireman"),
+            entry("terminal", "Terminal"),
+            entry("files", "File Manager"),
+        ];
+
+        assert_eq!(rank(&entries, &History::default(), "fm"), [2, 0]);
+        assert_eq!(rank(&entries, &History::default(), "  "), [2, 0, 1]);
+    }
+
+    #[test]
+    fn descriptions_and_keywords_match_at_half_weight() {
+        let mut by_keyword = entry("browser", "Browser");
+        by_keyword.keywords = vec!["internet".into()];
+        let by_name = entry("internet", "Internet");
+
+        assert_eq!(
+            rank(&[by_keyword, by_name], &History::default(), "internet"),
+            [1, 0]
+        );
+    }
+
+    #[test]
+    fn frequently_launched_entries_rank_higher() {
+        let entries = [entry("one", "Alpha One"), entry("two", "Alpha Two")];
+
+        assert_eq!(rank(&entries, &History::default(), "alpha"), [0, 1]);
+        assert_eq!(rank(&entries, &history(&[("two", 3)]), "alpha"), [1, 0]);
+        // Only each doubling counts
+        assert_eq!(
+            rank(&entries, &history(&[("one", 1), ("two", 2)]), "alpha"),
+            [0, 1]
+        );
+        assert_eq!(rank(&entries, &history(&[("two", 1)]), ""), [1, 0]);
+    }
+}
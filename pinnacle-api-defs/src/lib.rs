@@ -79,7 +79,10 @@ pub mod pinnacle {
                 TagActiveRequest,
                 TagCreatedRequest,
                 TagRemovedRequest,
-                InputDeviceAddedRequest
+                InputDeviceAddedRequest,
+                InputLayoutChangedRequest,
+                ProcessExitedRequest,
+                ScreenCaptureRequestedRequest
             );
         }
     }
@@ -7,6 +7,7 @@ local client = require("snowcap.grpc.client").client
 ---@class snowcap.Snowcap
 local snowcap = {
     layer = require("snowcap.layer"),
+    theme = require("snowcap.theme"),
     widget = require("snowcap.widget"),
 }
 
@@ -600,6 +600,48 @@ local snowcap_widget_v1_MouseArea_Interaction = {
     INTERACTION_HELP = 17,
 }
 
+---@enum snowcap.widget.v1.Canvas.FillRule
+local snowcap_widget_v1_Canvas_FillRule = {
+    FILL_RULE_UNSPECIFIED = 0,
+    FILL_RULE_NON_ZERO = 1,
+    FILL_RULE_EVEN_ODD = 2,
+}
+
+---@enum snowcap.widget.v1.Canvas.LineCap
+local snowcap_widget_v1_Canvas_LineCap = {
+    LINE_CAP_UNSPECIFIED = 0,
+    LINE_CAP_BUTT = 1,
+    LINE_CAP_SQUARE = 2,
+    LINE_CAP_ROUND = 3,
+}
+
+---@enum snowcap.widget.v1.Canvas.LineJoin
+local snowcap_widget_v1_Canvas_LineJoin = {
+    LINE_JOIN_UNSPECIFIED = 0,
+    LINE_JOIN_MITER = 1,
+    LINE_JOIN_ROUND = 2,
+    LINE_JOIN_BEVEL = 3,
+}
+
+---@enum snowcap.widget.v1.Tooltip.Position
+local snowcap_widget_v1_Tooltip_Position = {
+    POSITION_UNSPECIFIED = 0,
+    POSITION_TOP = 1,
+    POSITION_BOTTOM = 2,
+    POSITION_LEFT = 3,
+    POSITION_RIGHT = 4,
+    POSITION_FOLLOW_CURSOR = 5,
+}
+
+---@enum snowcap.widget.v1.Transition.Easing
+local snowcap_widget_v1_Transition_Easing = {
+    EASING_UNSPECIFIED = 0,
+    EASING_LINEAR = 1,
+    EASING_EASE_IN = 2,
+    EASING_EASE_OUT = 3,
+    EASING_EASE_IN_OUT = 4,
+}
+
 ---@enum snowcap.widget.v0alpha1.Alignment
 local snowcap_widget_v0alpha1_Alignment = {
     ALIGNMENT_UNSPECIFIED = 0,
@@ -754,6 +796,20 @@ local snowcap_popup_v1_PopupEvent_Focus = {
     FOCUS_LOST = 2,
 }
 
+---@enum snowcap.theme.v1.Variant
+local snowcap_theme_v1_Variant = {
+    VARIANT_UNSPECIFIED = 0,
+    VARIANT_DARK = 1,
+    VARIANT_LIGHT = 2,
+}
+
+---@enum snowcap.clipboard.v1.ClipboardKind
+local snowcap_clipboard_v1_ClipboardKind = {
+    CLIPBOARD_KIND_UNSPECIFIED = 0,
+    CLIPBOARD_KIND_STANDARD = 1,
+    CLIPBOARD_KIND_PRIMARY = 2,
+}
+
 
 ---@alias google.protobuf.Empty nil
 
@@ -846,6 +902,21 @@ local snowcap_popup_v1_PopupEvent_Focus = {
 ---@field input_region snowcap.widget.v1.InputRegion?
 ---@field mouse_area snowcap.widget.v1.MouseArea?
 ---@field text_input snowcap.widget.v1.TextInput?
+---@field slider snowcap.widget.v1.Slider?
+---@field vertical_slider snowcap.widget.v1.VerticalSlider?
+---@field checkbox snowcap.widget.v1.Checkbox?
+---@field toggler snowcap.widget.v1.Toggler?
+---@field progress_bar snowcap.widget.v1.ProgressBar?
+---@field pick_list snowcap.widget.v1.PickList?
+---@field radio snowcap.widget.v1.Radio?
+---@field svg snowcap.widget.v1.Svg?
+---@field canvas snowcap.widget.v1.Canvas?
+---@field stack snowcap.widget.v1.Stack?
+---@field tooltip snowcap.widget.v1.Tooltip?
+---@field grid snowcap.widget.v1.Grid?
+---@field space snowcap.widget.v1.Space?
+---@field rule snowcap.widget.v1.Rule?
+---@field transition snowcap.widget.v1.Transition?
 
 ---@class snowcap.widget.v1.Text
 ---@field text string?
@@ -963,6 +1034,7 @@ local snowcap_popup_v1_PopupEvent_Focus = {
 ---@field path string?
 ---@field bytes string?
 ---@field rgba snowcap.widget.v1.Image.Rgba?
+---@field toplevel_identifier string?
 ---@field width snowcap.widget.v1.Length?
 ---@field height snowcap.widget.v1.Length?
 ---@field expand boolean?
@@ -1066,6 +1138,203 @@ local snowcap_popup_v1_PopupEvent_Focus = {
 ---@field submit google.protobuf.Empty?
 ---@field paste string?
 
+---@class snowcap.widget.v1.Slider
+---@field min number?
+---@field max number?
+---@field value number?
+---@field step number?
+---@field shift_step number?
+---@field default_value number?
+---@field width snowcap.widget.v1.Length?
+---@field height number?
+---@field on_change boolean?
+---@field on_release boolean?
+---@field widget_id integer?
+
+---@class snowcap.widget.v1.Slider.Event
+---@field change number?
+---@field release google.protobuf.Empty?
+
+---@class snowcap.widget.v1.VerticalSlider
+---@field min number?
+---@field max number?
+---@field value number?
+---@field step number?
+---@field shift_step number?
+---@field default_value number?
+---@field width number?
+---@field height snowcap.widget.v1.Length?
+---@field on_change boolean?
+---@field on_release boolean?
+---@field widget_id integer?
+
+---@class snowcap.widget.v1.Checkbox
+---@field label string?
+---@field checked boolean?
+---@field on_toggle boolean?
+---@field size number?
+---@field spacing number?
+---@field text_size number?
+---@field width snowcap.widget.v1.Length?
+---@field widget_id integer?
+
+---@class snowcap.widget.v1.Checkbox.Event
+---@field checked boolean?
+
+---@class snowcap.widget.v1.Toggler
+---@field label string?
+---@field toggled boolean?
+---@field on_toggle boolean?
+---@field size number?
+---@field spacing number?
+---@field text_size number?
+---@field width snowcap.widget.v1.Length?
+---@field widget_id integer?
+
+---@class snowcap.widget.v1.Toggler.Event
+---@field toggled boolean?
+
+---@class snowcap.widget.v1.ProgressBar
+---@field min number?
+---@field max number?
+---@field value number?
+---@field length snowcap.widget.v1.Length?
+---@field girth snowcap.widget.v1.Length?
+---@field vertical boolean?
+---@field style snowcap.widget.v1.ProgressBar.Style?
+
+---@class snowcap.widget.v1.ProgressBar.Style
+---@field background snowcap.widget.v1.Background?
+---@field bar snowcap.widget.v1.Background?
+---@field border snowcap.widget.v1.Border?
+
+---@class snowcap.widget.v1.PickList
+---@field options string[]?
+---@field selected string?
+---@field placeholder string?
+---@field on_select boolean?
+---@field width snowcap.widget.v1.Length?
+---@field padding snowcap.widget.v1.Padding?
+---@field text_size number?
+---@field widget_id integer?
+
+---@class snowcap.widget.v1.PickList.Event
+---@field selected string?
+
+---@class snowcap.widget.v1.Radio
+---@field label string?
+---@field selected boolean?
+---@field on_select boolean?
+---@field size number?
+---@field spacing number?
+---@field text_size number?
+---@field width snowcap.widget.v1.Length?
+---@field widget_id integer?
+
+---@class snowcap.widget.v1.Radio.Event
+
+---@class snowcap.widget.v1.Svg
+---@field path string?
+---@field bytes string?
+---@field width snowcap.widget.v1.Length?
+---@field height snowcap.widget.v1.Length?
+---@field content_fit snowcap.widget.v1.Image.ContentFit?
+---@field color snowcap.widget.v1.Color?
+---@field rotation_degrees number?
+---@field opacity number?
+
+---@class snowcap.widget.v1.Canvas
+---@field width snowcap.widget.v1.Length?
+---@field height snowcap.widget.v1.Length?
+---@field commands snowcap.widget.v1.Canvas.Command[]?
+
+---@class snowcap.widget.v1.Canvas.Point
+---@field x number?
+---@field y number?
+
+---@class snowcap.widget.v1.Canvas.Size
+---@field width number?
+---@field height number?
+
+---@class snowcap.widget.v1.Canvas.Path
+---@field segments snowcap.widget.v1.Canvas.Path.Segment[]?
+
+---@class snowcap.widget.v1.Canvas.Path.Segment
+---@field move_to snowcap.widget.v1.Canvas.Point?
+---@field line_to snowcap.widget.v1.Canvas.Point?
+---@field quadratic_curve_to snowcap.widget.v1.Canvas.Path.QuadraticCurveTo?
+---@field bezier_curve_to snowcap.widget.v1.Canvas.Path.BezierCurveTo?
+---@field arc snowcap.widget.v1.Canvas.Path.Arc?
+---@field rectangle snowcap.widget.v1.Canvas.Path.Rectangle?
+---@field circle snowcap.widget.v1.Canvas.Path.Circle?
+---@field close google.protobuf.Empty?
+
+---@class snowcap.widget.v1.Canvas.Path.QuadraticCurveTo
+---@field control snowcap.widget.v1.Canvas.Point?
+---@field to snowcap.widget.v1.Canvas.Point?
+
+---@class snowcap.widget.v1.Canvas.Path.BezierCurveTo
+---@field control_a snowcap.widget.v1.Canvas.Point?
+---@field control_b snowcap.widget.v1.Canvas.Point?
+---@field to snowcap.widget.v1.Canvas.Point?
+
+---@class snowcap.widget.v1.Canvas.Path.Arc
+---@field center snowcap.widget.v1.Canvas.Point?
+---@field radius number?
+---@field start_radians number?
+---@field end_radians number?
+
+---@class snowcap.widget.v1.Canvas.Path.Rectangle
+---@field top_left snowcap.widget.v1.Canvas.Point?
+---@field size snowcap.widget.v1.Canvas.Size?
+---@field radius snowcap.widget.v1.Radius?
+
+---@class snowcap.widget.v1.Canvas.Path.Circle
+---@field center snowcap.widget.v1.Canvas.Point?
+---@field radius number?
+
+---@class snowcap.widget.v1.Canvas.LinearGradient
+---@field start snowcap.widget.v1.Canvas.Point?
+---@field end snowcap.widget.v1.Canvas.Point?
+---@field stops snowcap.widget.v1.Gradient.ColorStop[]?
+
+---@class snowcap.widget.v1.Canvas.Style
+---@field color snowcap.widget.v1.Color?
+---@field gradient snowcap.widget.v1.Canvas.LinearGradient?
+
+---@class snowcap.widget.v1.Canvas.Fill
+---@field path snowcap.widget.v1.Canvas.Path?
+---@field style snowcap.widget.v1.Canvas.Style?
+---@field rule snowcap.widget.v1.Canvas.FillRule?
+
+---@class snowcap.widget.v1.Canvas.Stroke
+---@field path snowcap.widget.v1.Canvas.Path?
+---@field style snowcap.widget.v1.Canvas.Style?
+---@field width number?
+---@field line_cap snowcap.widget.v1.Canvas.LineCap?
+---@field line_join snowcap.widget.v1.Canvas.LineJoin?
+---@field dash_segments number[]?
+---@field dash_offset integer?
+
+---@class snowcap.widget.v1.Canvas.Text
+---@field content string?
+---@field position snowcap.widget.v1.Canvas.Point?
+---@field color snowcap.widget.v1.Color?
+---@field size number?
+---@field font snowcap.widget.v1.Font?
+---@field horizontal_alignment snowcap.widget.v1.Alignment?
+---@field vertical_alignment snowcap.widget.v1.Alignment?
+
+---@class snowcap.widget.v1.Canvas.Command
+---@field fill snowcap.widget.v1.Canvas.Fill?
+---@field stroke snowcap.widget.v1.Canvas.Stroke?
+---@field text snowcap.widget.v1.Canvas.Text?
+---@field push_transform google.protobuf.Empty?
+---@field pop_transform google.protobuf.Empty?
+---@field translate snowcap.widget.v1.Canvas.Point?
+---@field rotate_radians number?
+---@field scale number?
+
 ---@class snowcap.widget.v1.GetWidgetEventsRequest
 ---@field layer_id integer?
 ---@field decoration_id integer?
@@ -1076,10 +1345,79 @@ local snowcap_popup_v1_PopupEvent_Focus = {
 ---@field button snowcap.widget.v1.Button.Event?
 ---@field mouse_area snowcap.widget.v1.MouseArea.Event?
 ---@field text_input snowcap.widget.v1.TextInput.Event?
+---@field slider snowcap.widget.v1.Slider.Event?
+---@field checkbox snowcap.widget.v1.Checkbox.Event?
+---@field toggler snowcap.widget.v1.Toggler.Event?
+---@field pick_list snowcap.widget.v1.PickList.Event?
+---@field radio snowcap.widget.v1.Radio.Event?
 
 ---@class snowcap.widget.v1.GetWidgetEventsResponse
 ---@field widget_events snowcap.widget.v1.WidgetEvent[]?
 
+---@class snowcap.widget.v1.Stack
+---@field width snowcap.widget.v1.Length?
+---@field height snowcap.widget.v1.Length?
+---@field clip boolean?
+---@field children snowcap.widget.v1.WidgetDef[]?
+
+---@class snowcap.widget.v1.Tooltip
+---@field content snowcap.widget.v1.WidgetDef?
+---@field tooltip snowcap.widget.v1.WidgetDef?
+---@field position snowcap.widget.v1.Tooltip.Position?
+---@field gap number?
+---@field padding number?
+---@field delay_millis integer?
+---@field snap_within_viewport boolean?
+---@field style snowcap.widget.v1.Container.Style?
+
+---@class snowcap.widget.v1.Grid
+---@field amount integer?
+---@field max_cell_width number?
+---@field spacing number?
+---@field width number?
+---@field aspect_ratio number?
+---@field evenly_distribute snowcap.widget.v1.Length?
+---@field children snowcap.widget.v1.WidgetDef[]?
+
+---@class snowcap.widget.v1.Space
+---@field width snowcap.widget.v1.Length?
+---@field height snowcap.widget.v1.Length?
+
+---@class snowcap.widget.v1.Rule
+---@field vertical boolean?
+---@field thickness number?
+---@field style snowcap.widget.v1.Rule.Style?
+
+---@class snowcap.widget.v1.Rule.Style
+---@field color snowcap.widget.v1.Color?
+---@field radius snowcap.widget.v1.Radius?
+---@field fill_mode snowcap.widget.v1.Rule.FillMode?
+---@field snap boolean?
+
+---@class snowcap.widget.v1.Rule.FillMode
+---@field full google.protobuf.Empty?
+---@field percent number?
+---@field padded integer?
+---@field asymmetric_padding snowcap.widget.v1.Rule.AsymmetricPadding?
+
+---@class snowcap.widget.v1.Rule.AsymmetricPadding
+---@field start integer?
+---@field end integer?
+
+---@class snowcap.widget.v1.Transition
+---@field child snowcap.widget.v1.WidgetDef?
+---@field key string?
+---@field duration_millis integer?
+---@field delay_millis integer?
+---@field easing snowcap.widget.v1.Transition.Easing?
+---@field from_opacity number?
+---@field from_offset_x number?
+---@field from_offset_y number?
+---@field from_scale number?
+---@field reverse boolean?
+---@field repeat_forever boolean?
+---@field auto_reverse boolean?
+
 ---@class snowcap.operation.v1.Focusable
 ---@field focus snowcap.operation.v1.Focusable.Focus?
 ---@field unfocus snowcap.operation.v1.Focusable.Unfocus?
@@ -1319,12 +1657,20 @@ local snowcap_popup_v1_PopupEvent_Focus = {
 ---@class snowcap.layer.v0alpha1.CloseRequest
 ---@field layer_id integer?
 
+---@class snowcap.layer.v1.Margin
+---@field top integer?
+---@field right integer?
+---@field bottom integer?
+---@field left integer?
+
 ---@class snowcap.layer.v1.NewLayerRequest
 ---@field widget_def snowcap.widget.v1.WidgetDef?
 ---@field anchor snowcap.layer.v1.Anchor?
 ---@field keyboard_interactivity snowcap.layer.v1.KeyboardInteractivity?
 ---@field exclusive_zone integer?
 ---@field layer snowcap.layer.v1.Layer?
+---@field output_name string?
+---@field margin snowcap.layer.v1.Margin?
 
 ---@class snowcap.layer.v1.NewLayerResponse
 ---@field layer_id integer?
@@ -1345,6 +1691,8 @@ local snowcap_popup_v1_PopupEvent_Focus = {
 ---@field keyboard_interactivity snowcap.layer.v1.KeyboardInteractivity?
 ---@field exclusive_zone integer?
 ---@field layer snowcap.layer.v1.Layer?
+---@field margin snowcap.layer.v1.Margin?
+---@field output_name string?
 
 ---@class snowcap.layer.v1.UpdateLayerResponse
 
@@ -1362,6 +1710,20 @@ local snowcap_popup_v1_PopupEvent_Focus = {
 ---@class snowcap.layer.v1.GetLayerEventsResponse
 ---@field layer_events snowcap.layer.v1.LayerEvent[]?
 
+---@class snowcap.layer.v1.GetClipboardRequest
+---@field layer_id integer?
+---@field kind snowcap.clipboard.v1.ClipboardKind?
+
+---@class snowcap.layer.v1.GetClipboardResponse
+---@field text string?
+
+---@class snowcap.layer.v1.SetClipboardRequest
+---@field layer_id integer?
+---@field kind snowcap.clipboard.v1.ClipboardKind?
+---@field text string?
+
+---@class snowcap.layer.v1.SetClipboardResponse
+
 ---@class snowcap.popup.v1.Offset
 ---@field x number?
 ---@field y number?
@@ -1436,6 +1798,58 @@ local snowcap_popup_v1_PopupEvent_Focus = {
 ---@class snowcap.popup.v1.GetPopupEventsResponse
 ---@field popup_events snowcap.popup.v1.PopupEvent[]?
 
+---@class snowcap.popup.v1.GetClipboardRequest
+---@field popup_id integer?
+---@field kind snowcap.clipboard.v1.ClipboardKind?
+
+---@class snowcap.popup.v1.GetClipboardResponse
+---@field text string?
+
+---@class snowcap.popup.v1.SetClipboardRequest
+---@field popup_id integer?
+---@field kind snowcap.clipboard.v1.ClipboardKind?
+---@field text string?
+
+---@class snowcap.popup.v1.SetClipboardResponse
+
+---@class snowcap.theme.v1.Theme
+---@field palette snowcap.theme.v1.Palette?
+---@field font snowcap.widget.v1.Font?
+---@field text_size number?
+---@field radius number?
+---@field spacing number?
+
+---@class snowcap.theme.v1.Palette
+---@field background snowcap.widget.v1.Color?
+---@field text snowcap.widget.v1.Color?
+---@field primary snowcap.widget.v1.Color?
+---@field success snowcap.widget.v1.Color?
+---@field warning snowcap.widget.v1.Color?
+---@field danger snowcap.widget.v1.Color?
+
+---@class snowcap.theme.v1.SetThemeRequest
+---@field variant snowcap.theme.v1.Variant?
+---@field theme snowcap.theme.v1.Theme?
+
+---@class snowcap.theme.v1.SetVariantRequest
+---@field variant snowcap.theme.v1.Variant?
+
+---@class snowcap.theme.v1.LoadThemeRequest
+---@field path string?
+---@field watch boolean?
+
+---@class snowcap.theme.v1.GetThemeRequest
+
+---@class snowcap.theme.v1.GetThemeResponse
+---@field variant snowcap.theme.v1.Variant?
+---@field theme snowcap.theme.v1.Theme?
+
+---@class snowcap.theme.v1.WatchThemeRequest
+
+---@class snowcap.theme.v1.WatchThemeResponse
+---@field variant snowcap.theme.v1.Variant?
+---@field theme snowcap.theme.v1.Theme?
+
 ---@class snowcap.v0alpha1.Nothing
 
 ---@class snowcap.v1.Nothing
@@ -1490,9 +1904,48 @@ snowcap.widget.v1.TextInput.Icon = {}
 snowcap.widget.v1.TextInput.Style = {}
 snowcap.widget.v1.TextInput.Style.Inner = {}
 snowcap.widget.v1.TextInput.Event = {}
+snowcap.widget.v1.Slider = {}
+snowcap.widget.v1.Slider.Event = {}
+snowcap.widget.v1.VerticalSlider = {}
+snowcap.widget.v1.Checkbox = {}
+snowcap.widget.v1.Checkbox.Event = {}
+snowcap.widget.v1.Toggler = {}
+snowcap.widget.v1.Toggler.Event = {}
+snowcap.widget.v1.ProgressBar = {}
+snowcap.widget.v1.ProgressBar.Style = {}
+snowcap.widget.v1.PickList = {}
+snowcap.widget.v1.PickList.Event = {}
+snowcap.widget.v1.Radio = {}
+snowcap.widget.v1.Radio.Event = {}
+snowcap.widget.v1.Svg = {}
+snowcap.widget.v1.Canvas = {}
+snowcap.widget.v1.Canvas.Point = {}
+snowcap.widget.v1.Canvas.Size = {}
+snowcap.widget.v1.Canvas.Path = {}
+snowcap.widget.v1.Canvas.Path.Segment = {}
+snowcap.widget.v1.Canvas.Path.QuadraticCurveTo = {}
+snowcap.widget.v1.Canvas.Path.BezierCurveTo = {}
+snowcap.widget.v1.Canvas.Path.Arc = {}
+snowcap.widget.v1.Canvas.Path.Rectangle = {}
+snowcap.widget.v1.Canvas.Path.Circle = {}
+snowcap.widget.v1.Canvas.LinearGradient = {}
+snowcap.widget.v1.Canvas.Style = {}
+snowcap.widget.v1.Canvas.Fill = {}
+snowcap.widget.v1.Canvas.Stroke = {}
+snowcap.widget.v1.Canvas.Text = {}
+snowcap.widget.v1.Canvas.Command = {}
 snowcap.widget.v1.GetWidgetEventsRequest = {}
 snowcap.widget.v1.WidgetEvent = {}
 snowcap.widget.v1.GetWidgetEventsResponse = {}
+snowcap.widget.v1.Stack = {}
+snowcap.widget.v1.Tooltip = {}
+snowcap.widget.v1.Grid = {}
+snowcap.widget.v1.Space = {}
+snowcap.widget.v1.Rule = {}
+snowcap.widget.v1.Rule.Style = {}
+snowcap.widget.v1.Rule.FillMode = {}
+snowcap.widget.v1.Rule.AsymmetricPadding = {}
+snowcap.widget.v1.Transition = {}
 snowcap.operation = {}
 snowcap.operation.v1 = {}
 snowcap.operation.v1.Focusable = {}
@@ -1552,6 +2005,7 @@ snowcap.layer.v0alpha1.NewLayerRequest = {}
 snowcap.layer.v0alpha1.NewLayerResponse = {}
 snowcap.layer.v0alpha1.CloseRequest = {}
 snowcap.layer.v1 = {}
+snowcap.layer.v1.Margin = {}
 snowcap.layer.v1.NewLayerRequest = {}
 snowcap.layer.v1.NewLayerResponse = {}
 snowcap.layer.v1.CloseRequest = {}
@@ -1564,6 +2018,10 @@ snowcap.layer.v1.ViewResponse = {}
 snowcap.layer.v1.GetLayerEventsRequest = {}
 snowcap.layer.v1.LayerEvent = {}
 snowcap.layer.v1.GetLayerEventsResponse = {}
+snowcap.layer.v1.GetClipboardRequest = {}
+snowcap.layer.v1.GetClipboardResponse = {}
+snowcap.layer.v1.SetClipboardRequest = {}
+snowcap.layer.v1.SetClipboardResponse = {}
 snowcap.popup = {}
 snowcap.popup.v1 = {}
 snowcap.popup.v1.Offset = {}
@@ -1582,6 +2040,23 @@ snowcap.popup.v1.ViewResponse = {}
 snowcap.popup.v1.GetPopupEventsRequest = {}
 snowcap.popup.v1.PopupEvent = {}
 snowcap.popup.v1.GetPopupEventsResponse = {}
+snowcap.popup.v1.GetClipboardRequest = {}
+snowcap.popup.v1.GetClipboardResponse = {}
+snowcap.popup.v1.SetClipboardRequest = {}
+snowcap.popup.v1.SetClipboardResponse = {}
+snowcap.theme = {}
+snowcap.theme.v1 = {}
+snowcap.theme.v1.Theme = {}
+snowcap.theme.v1.Palette = {}
+snowcap.theme.v1.SetThemeRequest = {}
+snowcap.theme.v1.SetVariantRequest = {}
+snowcap.theme.v1.LoadThemeRequest = {}
+snowcap.theme.v1.GetThemeRequest = {}
+snowcap.theme.v1.GetThemeResponse = {}
+snowcap.theme.v1.WatchThemeRequest = {}
+snowcap.theme.v1.WatchThemeResponse = {}
+snowcap.clipboard = {}
+snowcap.clipboard.v1 = {}
 snowcap.v0alpha1 = {}
 snowcap.v0alpha1.Nothing = {}
 snowcap.v1 = {}
@@ -1593,6 +2068,11 @@ snowcap.widget.v1.Font.Stretch = snowcap_widget_v1_Font_Stretch
 snowcap.widget.v1.Font.Style = snowcap_widget_v1_Font_Style
 snowcap.widget.v1.Image.ContentFit = snowcap_widget_v1_Image_ContentFit
 snowcap.widget.v1.MouseArea.Interaction = snowcap_widget_v1_MouseArea_Interaction
+snowcap.widget.v1.Canvas.FillRule = snowcap_widget_v1_Canvas_FillRule
+snowcap.widget.v1.Canvas.LineCap = snowcap_widget_v1_Canvas_LineCap
+snowcap.widget.v1.Canvas.LineJoin = snowcap_widget_v1_Canvas_LineJoin
+snowcap.widget.v1.Tooltip.Position = snowcap_widget_v1_Tooltip_Position
+snowcap.widget.v1.Transition.Easing = snowcap_widget_v1_Transition_Easing
 snowcap.widget.v0alpha1.Alignment = snowcap_widget_v0alpha1_Alignment
 snowcap.widget.v0alpha1.ScrollableAlignment = snowcap_widget_v0alpha1_ScrollableAlignment
 snowcap.widget.v0alpha1.Font.Weight = snowcap_widget_v0alpha1_Font_Weight
@@ -1608,6 +2088,8 @@ snowcap.layer.v1.LayerEvent.Focus = snowcap_layer_v1_LayerEvent_Focus
 snowcap.popup.v1.Anchor = snowcap_popup_v1_Anchor
 snowcap.popup.v1.Gravity = snowcap_popup_v1_Gravity
 snowcap.popup.v1.PopupEvent.Focus = snowcap_popup_v1_PopupEvent_Focus
+snowcap.theme.v1.Variant = snowcap_theme_v1_Variant
+snowcap.clipboard.v1.ClipboardKind = snowcap_clipboard_v1_ClipboardKind
 
 snowcap.widget.v1.WidgetService = {}
 snowcap.widget.v1.WidgetService.GetWidgetEvents = {}
@@ -1939,6 +2421,40 @@ snowcap.layer.v1.LayerService.GetLayerEvents.response = ".snowcap.layer.v1.GetLa
 function Client:snowcap_layer_v1_LayerService_GetLayerEvents(data, callback, done)
     return self:server_streaming_request(snowcap.layer.v1.LayerService.GetLayerEvents, data, callback, done)
 end
+snowcap.layer.v1.LayerService.GetClipboard = {}
+snowcap.layer.v1.LayerService.GetClipboard.service = "snowcap.layer.v1.LayerService"
+snowcap.layer.v1.LayerService.GetClipboard.method = "GetClipboard"
+snowcap.layer.v1.LayerService.GetClipboard.request = ".snowcap.layer.v1.GetClipboardRequest"
+snowcap.layer.v1.LayerService.GetClipboard.response = ".snowcap.layer.v1.GetClipboardResponse"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data snowcap.layer.v1.GetClipboardRequest
+---
+---@return snowcap.layer.v1.GetClipboardResponse | nil response
+---@return string | nil error An error string, if any
+function Client:snowcap_layer_v1_LayerService_GetClipboard(data)
+    return self:unary_request(snowcap.layer.v1.LayerService.GetClipboard, data)
+end
+snowcap.layer.v1.LayerService.SetClipboard = {}
+snowcap.layer.v1.LayerService.SetClipboard.service = "snowcap.layer.v1.LayerService"
+snowcap.layer.v1.LayerService.SetClipboard.method = "SetClipboard"
+snowcap.layer.v1.LayerService.SetClipboard.request = ".snowcap.layer.v1.SetClipboardRequest"
+snowcap.layer.v1.LayerService.SetClipboard.response = ".snowcap.layer.v1.SetClipboardResponse"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data snowcap.layer.v1.SetClipboardRequest
+---
+---@return snowcap.layer.v1.SetClipboardResponse | nil response
+---@return string | nil error An error string, if any
+function Client:snowcap_layer_v1_LayerService_SetClipboard(data)
+    return self:unary_request(snowcap.layer.v1.LayerService.SetClipboard, data)
+end
 snowcap.popup.v1.PopupService = {}
 snowcap.popup.v1.PopupService.NewPopup = {}
 snowcap.popup.v1.PopupService.NewPopup.service = "snowcap.popup.v1.PopupService"
@@ -2045,6 +2561,129 @@ snowcap.popup.v1.PopupService.GetPopupEvents.response = ".snowcap.popup.v1.GetPo
 function Client:snowcap_popup_v1_PopupService_GetPopupEvents(data, callback, done)
     return self:server_streaming_request(snowcap.popup.v1.PopupService.GetPopupEvents, data, callback, done)
 end
+snowcap.popup.v1.PopupService.GetClipboard = {}
+snowcap.popup.v1.PopupService.GetClipboard.service = "snowcap.popup.v1.PopupService"
+snowcap.popup.v1.PopupService.GetClipboard.method = "GetClipboard"
+snowcap.popup.v1.PopupService.GetClipboard.request = ".snowcap.popup.v1.GetClipboardRequest"
+snowcap.popup.v1.PopupService.GetClipboard.response = ".snowcap.popup.v1.GetClipboardResponse"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data snowcap.popup.v1.GetClipboardRequest
+---
+---@return snowcap.popup.v1.GetClipboardResponse | nil response
+---@return string | nil error An error string, if any
+function Client:snowcap_popup_v1_PopupService_GetClipboard(data)
+    return self:unary_request(snowcap.popup.v1.PopupService.GetClipboard, data)
+end
+snowcap.popup.v1.PopupService.SetClipboard = {}
+snowcap.popup.v1.PopupService.SetClipboard.service = "snowcap.popup.v1.PopupService"
+snowcap.popup.v1.PopupService.SetClipboard.method = "SetClipboard"
+snowcap.popup.v1.PopupService.SetClipboard.request = ".snowcap.popup.v1.SetClipboardRequest"
+snowcap.popup.v1.PopupService.SetClipboard.response = ".snowcap.popup.v1.SetClipboardResponse"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data snowcap.popup.v1.SetClipboardRequest
+---
+---@return snowcap.popup.v1.SetClipboardResponse | nil response
+---@return string | nil error An error string, if any
+function Client:snowcap_popup_v1_PopupService_SetClipboard(data)
+    return self:unary_request(snowcap.popup.v1.PopupService.SetClipboard, data)
+end
+snowcap.theme.v1.ThemeService = {}
+snowcap.theme.v1.ThemeService.SetTheme = {}
+snowcap.theme.v1.ThemeService.SetTheme.service = "snowcap.theme.v1.ThemeService"
+snowcap.theme.v1.ThemeService.SetTheme.method = "SetTheme"
+snowcap.theme.v1.ThemeService.SetTheme.request = ".snowcap.theme.v1.SetThemeRequest"
+snowcap.theme.v1.ThemeService.SetTheme.response = ".google.protobuf.Empty"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data snowcap.theme.v1.SetThemeRequest
+---
+---@return google.protobuf.Empty | nil response
+---@return string | nil error An error string, if any
+function Client:snowcap_theme_v1_ThemeService_SetTheme(data)
+    return self:unary_request(snowcap.theme.v1.ThemeService.SetTheme, data)
+end
+snowcap.theme.v1.ThemeService.SetVariant = {}
+snowcap.theme.v1.ThemeService.SetVariant.service = "snowcap.theme.v1.ThemeService"
+snowcap.theme.v1.ThemeService.SetVariant.method = "SetVariant"
+snowcap.theme.v1.ThemeService.SetVariant.request = ".snowcap.theme.v1.SetVariantRequest"
+snowcap.theme.v1.ThemeService.SetVariant.response = ".google.protobuf.Empty"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data snowcap.theme.v1.SetVariantRequest
+---
+---@return google.protobuf.Empty | nil response
+---@return string | nil error An error string, if any
+function Client:snowcap_theme_v1_ThemeService_SetVariant(data)
+    return self:unary_request(snowcap.theme.v1.ThemeService.SetVariant, data)
+end
+snowcap.theme.v1.ThemeService.LoadTheme = {}
+snowcap.theme.v1.ThemeService.LoadTheme.service = "snowcap.theme.v1.ThemeService"
+snowcap.theme.v1.ThemeService.LoadTheme.method = "LoadTheme"
+snowcap.theme.v1.ThemeService.LoadTheme.request = ".snowcap.theme.v1.LoadThemeRequest"
+snowcap.theme.v1.ThemeService.LoadTheme.response = ".google.protobuf.Empty"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data snowcap.theme.v1.LoadThemeRequest
+---
+---@return google.protobuf.Empty | nil response
+---@return string | nil error An error string, if any
+function Client:snowcap_theme_v1_ThemeService_LoadTheme(data)
+    return self:unary_request(snowcap.theme.v1.ThemeService.LoadTheme, data)
+end
+snowcap.theme.v1.ThemeService.GetTheme = {}
+snowcap.theme.v1.ThemeService.GetTheme.service = "snowcap.theme.v1.ThemeService"
+snowcap.theme.v1.ThemeService.GetTheme.method = "GetTheme"
+snowcap.theme.v1.ThemeService.GetTheme.request = ".snowcap.theme.v1.GetThemeRequest"
+snowcap.theme.v1.ThemeService.GetTheme.response = ".snowcap.theme.v1.GetThemeResponse"
+
+---Performs a unary request.
+---
+---@nodiscard
+---
+---@param data snowcap.theme.v1.GetThemeRequest
+---
+---@return snowcap.theme.v1.GetThemeResponse | nil response
+---@return string | nil error An error string, if any
+function Client:snowcap_theme_v1_ThemeService_GetTheme(data)
+    return self:unary_request(snowcap.theme.v1.ThemeService.GetTheme, data)
+end
+snowcap.theme.v1.ThemeService.WatchTheme = {}
+snowcap.theme.v1.ThemeService.WatchTheme.service = "snowcap.theme.v1.ThemeService"
+snowcap.theme.v1.ThemeService.WatchTheme.method = "WatchTheme"
+snowcap.theme.v1.ThemeService.WatchTheme.request = ".snowcap.theme.v1.WatchThemeRequest"
+snowcap.theme.v1.ThemeService.WatchTheme.response = ".snowcap.theme.v1.WatchThemeResponse"
+
+---Performs a server-streaming request.
+---
+---`callback` will be called with every streamed response.
+---
+---@nodiscard
+---
+---@param data snowcap.theme.v1.WatchThemeRequest
+---@param callback fun(response: snowcap.theme.v1.WatchThemeResponse)
+---@param done? fun()
+---
+---@return string | nil An error string, if any
+function Client:snowcap_theme_v1_ThemeService_WatchTheme(data, callback, done)
+    return self:server_streaming_request(snowcap.theme.v1.ThemeService.WatchTheme, data, callback, done)
+end
 return {
     google = google,
     snowcap = snowcap,
@@ -18,6 +18,8 @@ function protobuf.build_protos()
         "snowcap/operation/" .. version .. "/operation.proto",
         "snowcap/decoration/" .. version .. "/decoration.proto",
         "snowcap/popup/" .. version .. "/popup.proto",
+        "snowcap/theme/" .. version .. "/theme.proto",
+        "snowcap/clipboard/" .. version .. "/clipboard.proto",
         "google/protobuf/empty.proto",
     }
 
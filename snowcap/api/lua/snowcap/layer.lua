@@ -64,6 +64,15 @@ local zlayer = {
     OVERLAY = 4,
 }
 
+---Which clipboard to read from or write to.
+---@enum snowcap.layer.ClipboardKind
+local clipboard_kind = {
+    ---The standard clipboard, used for copy and paste.
+    STANDARD = 1,
+    ---The primary selection, used for middle-click paste.
+    PRIMARY = 2,
+}
+
 ---@package
 ---@enum snowcap.layer.FocusEvent
 local focus_event = {
@@ -90,12 +99,24 @@ local function exclusive_zone_to_api(zone)
     return -1
 end
 
+---The distance between a layer surface and the edges it is anchored to.
+---
+---Negative values let the surface extend past the edge.
+---@class snowcap.layer.Margin
+---@field top integer?
+---@field right integer?
+---@field bottom integer?
+---@field left integer?
+
 ---@class snowcap.layer.LayerArgs
 ---@field program snowcap.widget.Program
 ---@field anchor snowcap.layer.Anchor?
 ---@field keyboard_interactivity snowcap.layer.KeyboardInteractivity
 ---@field exclusive_zone snowcap.layer.ExclusiveZone
 ---@field layer snowcap.layer.ZLayer
+---The name of the output to open the layer on. If nil, the compositor chooses.
+---@field output string?
+---@field margin snowcap.layer.Margin?
 
 ---@param args snowcap.layer.LayerArgs
 ---@return snowcap.layer.LayerHandle|nil handle A handle to the layer surface, or nil if an error occurred.
@@ -114,6 +135,8 @@ function layer.new_widget(args)
         anchor = args.anchor,
         keyboard_interactivity = args.keyboard_interactivity,
         widget_def = widget.widget_def_into_api(widget_def),
+        output_name = args.output,
+        margin = args.margin,
     }
 
     local response, err = client:snowcap_layer_v1_LayerService_NewLayer(request)
@@ -248,6 +271,9 @@ function layer.new_widget(args)
 end
 
 ---Do something when a key event is received.
+---
+---Changes to the modifiers without a key event, like when this surface gains
+---keyboard focus, are sent as a release of `Keys.NoSymbol`.
 ---@param on_event fun(handle: snowcap.layer.LayerHandle, event: snowcap.input.KeyEvent)
 function LayerHandle:on_key_event(on_event)
     local err = client:snowcap_input_v1_InputService_KeyboardKey(
@@ -297,6 +323,7 @@ end
 ---@field keyboard_interactivity? snowcap.layer.KeyboardInteractivity
 ---@field exclusive_zone? snowcap.layer.ExclusiveZone
 ---@field layer? snowcap.layer.ZLayer
+---@field margin? snowcap.layer.Margin
 
 ---Update this layer's attributes.
 ---@param args snowcap.layer.LayerUpdateArgs
@@ -310,6 +337,25 @@ function LayerHandle:update(args)
         keyboard_interactivity = args.keyboard_interactivity,
         exclusive_zone = exclusive_zone,
         layer = args.layer,
+        margin = args.margin,
+    })
+
+    if err then
+        log.error(err)
+    end
+
+    return err == nil
+end
+
+---Moves this layer to the output with the given name.
+---
+---The layer is recreated on the new output, so any open popups are closed.
+---@param output_name string? The name of the output. If nil, the compositor chooses.
+---@return boolean True if the operation succeed.
+function LayerHandle:set_output(output_name)
+    local _, err = client:snowcap_layer_v1_LayerService_UpdateLayer({
+        layer_id = self.id,
+        output_name = output_name or "",
     })
 
     if err then
@@ -344,8 +390,46 @@ function LayerHandle:operate(operation)
     end
 end
 
+---Gets the text contents of the clipboard.
+---@param kind snowcap.layer.ClipboardKind? The clipboard to read from. Defaults to `STANDARD`.
+---@return string|nil
+function LayerHandle:clipboard(kind)
+    local response, err = client:snowcap_layer_v1_LayerService_GetClipboard({
+        layer_id = self.id,
+        kind = kind or clipboard_kind.STANDARD,
+    })
+
+    if err then
+        log.error(err)
+        return nil
+    end
+
+    assert(response)
+
+    return response.text
+end
+
+---Sets the text contents of the clipboard.
+---
+---Compositors only allow this while the layer has keyboard focus
+---or has recently been clicked.
+---@param text string
+---@param kind snowcap.layer.ClipboardKind? The clipboard to write to. Defaults to `STANDARD`.
+function LayerHandle:set_clipboard(text, kind)
+    local _, err = client:snowcap_layer_v1_LayerService_SetClipboard({
+        layer_id = self.id,
+        kind = kind or clipboard_kind.STANDARD,
+        text = text,
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
 layer.anchor = anchor
 layer.keyboard_interactivity = keyboard_interactivity
 layer.zlayer = zlayer
+layer.clipboard_kind = clipboard_kind
 
 return layer
@@ -175,6 +175,17 @@ local gravity = {
 }
 popup.gravity = gravity
 
+---Which clipboard to read from or write to.
+---@enum snowcap.popup.ClipboardKind
+local clipboard_kind = {
+    ---The standard clipboard, used for copy and paste.
+    STANDARD = 1,
+    ---The primary selection, used for middle-click paste.
+    PRIMARY = 2,
+}
+
+popup.clipboard_kind = clipboard_kind
+
 ---@package
 ---@enum snowcap.popup.FocusEvent
 local focus_event = {
@@ -381,6 +392,9 @@ function popup.new_widget(args)
 end
 
 ---Do something when a key event is received.
+---
+---Changes to the modifiers without a key event, like when this surface gains
+---keyboard focus, are sent as a release of `Keys.NoSymbol`.
 ---@param on_event fun(handle: snowcap.popup.PopupHandle, event: snowcap.input.KeyEvent)
 function PopupHandle:on_key_event(on_event)
     local err = client:snowcap_input_v1_InputService_KeyboardKey(
@@ -483,4 +497,41 @@ function PopupHandle:send_message(message)
     self._update(message)
 end
 
+---Gets the text contents of the clipboard.
+---@param kind snowcap.popup.ClipboardKind? The clipboard to read from. Defaults to `STANDARD`.
+---@return string|nil
+function PopupHandle:clipboard(kind)
+    local response, err = client:snowcap_popup_v1_PopupService_GetClipboard({
+        popup_id = self.id,
+        kind = kind or clipboard_kind.STANDARD,
+    })
+
+    if err then
+        log.error(err)
+        return nil
+    end
+
+    assert(response)
+
+    return response.text
+end
+
+---Sets the text contents of the clipboard.
+---
+---Compositors only allow this while the popup has keyboard focus
+---or has recently been clicked.
+---@param text string
+---@param kind snowcap.popup.ClipboardKind? The clipboard to write to. Defaults to `STANDARD`.
+function PopupHandle:set_clipboard(text, kind)
+    local _, err = client:snowcap_popup_v1_PopupService_SetClipboard({
+        popup_id = self.id,
+        kind = kind or clipboard_kind.STANDARD,
+        text = text,
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
 return popup
@@ -132,7 +132,7 @@ function SignalEntry:emit(...)
         local ok, ret = pcall(callback.callback, ...)
 
         if ok and ret == signal.HandlerPolicy.Discard then
-            to_remove = callback
+            table.insert(to_remove, callback)
         elseif not ok then
             Log.error("While handling '" .. self.signal .. "': " .. ret)
         end
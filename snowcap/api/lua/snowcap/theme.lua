@@ -0,0 +1,161 @@
+-- This Source Code Form is subject to the terms of the Mozilla Public
+-- License, v. 2.0. If a copy of the MPL was not distributed with this
+-- file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+local log = require("snowcap.log")
+local client = require("snowcap.grpc.client").client
+local signal = require("snowcap.signal")
+
+---Snowcap's global theme.
+---
+---The theme holds the palette, font, text size, corner radius, and spacing that widgets use
+---when they don't set their own. Every program drawn by snowcap inherits it.
+---
+---There is a dark and a light variant of the theme. Each can be set separately, and which one
+---is used can be switched at any time. Anything left unset in a variant falls back to
+---snowcap's defaults for that variant.
+---@class snowcap.theme
+local theme = {}
+
+---A variant of the theme.
+---@enum snowcap.theme.Variant
+local variant = {
+    ---Light text on a dark background.
+    DARK = 1,
+    ---Dark text on a light background.
+    LIGHT = 2,
+}
+theme.variant = variant
+
+---The colors of a theme.
+---
+---Unset colors fall back to snowcap's defaults.
+---@class snowcap.theme.Palette
+---@field background snowcap.widget.Color?
+---@field text snowcap.widget.Color?
+---@field primary snowcap.widget.Color?
+---@field success snowcap.widget.Color?
+---@field warning snowcap.widget.Color?
+---@field danger snowcap.widget.Color?
+
+---A theme.
+---@class snowcap.theme.Theme
+---The colors of the theme.
+---@field palette snowcap.theme.Palette?
+---The default font.
+---@field font snowcap.widget.Font?
+---The default text size, in pixels.
+---@field text_size number?
+---The default corner radius of buttons, text inputs, pick lists, and styled containers.
+---@field radius number?
+---The default spacing of columns, rows, and grids.
+---@field spacing number?
+
+---@type { theme: snowcap.theme.Theme, variant: snowcap.theme.Variant }?
+local current = nil
+
+local changed = signal.Signaler.new()
+
+---Sets the theme of one or both variants.
+---
+---@param thm snowcap.theme.Theme
+---@param var snowcap.theme.Variant? The variant to set the theme of. Sets both if nil.
+function theme.set(thm, var)
+    local _, err = client:snowcap_theme_v1_ThemeService_SetTheme({
+        variant = var,
+        theme = thm --[[@as snowcap.theme.v1.Theme]],
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
+---Switches to a variant of the theme.
+---
+---@param var snowcap.theme.Variant
+function theme.set_variant(var)
+    local _, err = client:snowcap_theme_v1_ThemeService_SetVariant({
+        variant = var,
+    })
+
+    if err then
+        log.error(err)
+    end
+end
+
+---Loads both variants of the theme, and the variant to use, from a TOML file.
+---
+---A file that fails to load again while watched leaves the theme as it was.
+---
+---@param path string
+---@param watch boolean? Whether to load the file again whenever it changes.
+---
+---@return boolean success
+function theme.load(path, watch)
+    local _, err = client:snowcap_theme_v1_ThemeService_LoadTheme({
+        path = path,
+        watch = watch or false,
+    })
+
+    if err then
+        log.error(err)
+    end
+
+    return err == nil
+end
+
+---Starts keeping `current` up to date.
+local function watch_theme()
+    local err = client:snowcap_theme_v1_ThemeService_WatchTheme({}, function(response)
+        current = {
+            theme = response.theme or {},
+            variant = response.variant or variant.DARK,
+        }
+        changed:emit("changed", current.theme, current.variant)
+    end)
+
+    if err then
+        log.error(err)
+    end
+end
+
+---Returns the theme of the variant in use, and the variant.
+---
+---The first call asks snowcap for the theme. After that, it is kept up to date
+---in the background, so this is cheap to call from a program's `view`.
+---
+---@return snowcap.theme.Theme
+---@return snowcap.theme.Variant
+function theme.current()
+    if not current then
+        local response, err = client:snowcap_theme_v1_ThemeService_GetTheme({})
+
+        if err then
+            log.error(err)
+        end
+
+        current = {
+            theme = response and response.theme or {},
+            variant = response and response.variant or variant.DARK,
+        }
+
+        watch_theme()
+    end
+
+    return current.theme, current.variant
+end
+
+---Connects to changes of the theme or the variant in use.
+---
+---Return `HandlerPolicy.Discard` from `callback` to stop receiving changes.
+---
+---@param callback fun(thm: snowcap.theme.Theme, var: snowcap.theme.Variant): snowcap.signal.HandlerPolicy?
+---
+---@return snowcap.signal.SignalHandle
+function theme.connect(callback)
+    theme.current()
+    return changed:connect("changed", callback)
+end
+
+return theme
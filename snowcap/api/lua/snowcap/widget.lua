@@ -2,6 +2,10 @@
 -- License, v. 2.0. If a copy of the MPL was not distributed with this
 -- file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+---A widget program.
+---
+---Subscriptions are only available in Rust. To update a program from outside events,
+---send messages to it with its surface's `send_message`, e.g. from a timer or signal callback.
 ---@class snowcap.widget.Program : snowcap.widget.base.Base
 ---@field update fun(self: self, message: any)
 ---@field view fun(self: self): snowcap.widget.WidgetDef?
@@ -38,6 +42,21 @@
 ---@field input_region snowcap.widget.InputRegion?
 ---@field mouse_area snowcap.widget.MouseArea?
 ---@field text_input snowcap.widget.TextInput?
+---@field slider snowcap.widget.Slider?
+---@field vertical_slider snowcap.widget.VerticalSlider?
+---@field checkbox snowcap.widget.Checkbox?
+---@field toggler snowcap.widget.Toggler?
+---@field progress_bar snowcap.widget.ProgressBar?
+---@field pick_list snowcap.widget.PickList?
+---@field radio snowcap.widget.Radio?
+---@field svg snowcap.widget.Svg?
+---@field canvas snowcap.widget.Canvas?
+---@field stack snowcap.widget.Stack?
+---@field tooltip snowcap.widget.Tooltip?
+---@field grid snowcap.widget.Grid?
+---@field space snowcap.widget.Space?
+---@field rule snowcap.widget.Rule?
+---@field transition snowcap.widget.Transition?
 
 ---@class snowcap.widget.Border
 ---@field color snowcap.widget.Color?
@@ -175,6 +194,145 @@ local content_fit = {
 ---@field path string?
 ---@field bytes string?
 ---@field rgba { width: integer, height: integer, rgba: string }?
+---The live contents of the toplevel with this ext-foreign-toplevel-list identifier.
+---@field toplevel_identifier string?
+
+---@class snowcap.widget.Svg
+---@field handle snowcap.widget.svg.Handle
+---@field width snowcap.widget.Length?
+---@field height snowcap.widget.Length?
+---@field content_fit snowcap.widget.image.ContentFit?
+---Draws the svg in this color, for symbolic icons.
+---@field color snowcap.widget.Color?
+---@field rotation_degrees number?
+---@field opacity number?
+
+---@class snowcap.widget.svg.Handle
+---@field path string?
+---@field bytes string?
+
+---A widget that draws a list of commands.
+---
+---The commands are only re-tessellated when they change.
+---See `snowcap.widget.canvas` for helpers to build them.
+---@class snowcap.widget.Canvas
+---@field width snowcap.widget.Length?
+---@field height snowcap.widget.Length?
+---@field commands snowcap.widget.canvas.Command[]
+
+---Layers children on top of each other. Later children are drawn on top.
+---@class snowcap.widget.Stack
+---@field width snowcap.widget.Length?
+---@field height snowcap.widget.Length?
+---@field clip boolean?
+---@field children snowcap.widget.WidgetDef[]
+
+---Shows a hint next to its content while the content is hovered.
+---
+---The hint is drawn inside the surface, so it may be cut off on small surfaces.
+---@class snowcap.widget.Tooltip
+---@field content snowcap.widget.WidgetDef
+---@field tooltip snowcap.widget.WidgetDef
+---@field position snowcap.widget.tooltip.Position?
+---The space between the content and the hint.
+---@field gap number?
+---@field padding number?
+---How long the content needs to be hovered before the hint shows, in milliseconds.
+---@field delay integer?
+---@field snap_within_viewport boolean?
+---@field style snowcap.widget.container.Style?
+
+---@enum snowcap.widget.tooltip.Position
+local tooltip_position = {
+    TOP = 1,
+    BOTTOM = 2,
+    LEFT = 3,
+    RIGHT = 4,
+    FOLLOW_CURSOR = 5,
+}
+
+---Lays out children in rows of equally sized cells.
+---
+---Set one of `columns` or `max_cell_width`, and one of `aspect_ratio` or `evenly_distribute`.
+---@class snowcap.widget.Grid
+---Use this many columns.
+---@field columns integer?
+---Use as many columns as fit without cells getting wider than this.
+---@field max_cell_width number?
+---@field spacing number?
+---@field width number?
+---Cells keep this width to height ratio.
+---@field aspect_ratio number?
+---Rows evenly split this height.
+---@field evenly_distribute snowcap.widget.Length?
+---@field children snowcap.widget.WidgetDef[]
+
+---Empty space.
+---@class snowcap.widget.Space
+---@field width snowcap.widget.Length?
+---@field height snowcap.widget.Length?
+
+---A horizontal or vertical line for dividing content.
+---@class snowcap.widget.Rule
+---@field vertical boolean?
+---@field thickness number
+---@field style snowcap.widget.rule.Style?
+
+---Unset fields use the theme's defaults.
+---@class snowcap.widget.rule.Style
+---@field color snowcap.widget.Color?
+---@field radius snowcap.widget.Radius?
+---@field fill_mode snowcap.widget.rule.FillMode?
+---Whether to snap the line to the pixel grid.
+---@field snap boolean?
+
+---How much of the available length a rule covers. Only one field should be set.
+---@class snowcap.widget.rule.FillMode
+---@field full {}?
+---From 0 to 100. The line is centered.
+---@field percent number?
+---The same offset from both ends.
+---@field padded integer?
+---Offsets from the top or left end and the bottom or right end.
+---@field asymmetric_padding { start: integer, ["end"]: integer }?
+
+---Animates its child into place.
+---
+---The child starts out faded, offset, and scaled by the `from_` fields and eases
+---into its normal state over `duration`. The animation runs in snowcap, so the program
+---doesn't need to send a new view every frame.
+---
+---Layout is not affected; the child is only drawn elsewhere. Canvases are not faded.
+---@class snowcap.widget.Transition
+---@field child snowcap.widget.WidgetDef
+---Transitions with the same key keep running across view updates instead
+---of starting over. Changing anything else about the transition restarts it.
+---@field key string?
+---The duration in milliseconds.
+---@field duration integer
+---The delay before starting in milliseconds.
+---@field delay integer?
+---@field easing snowcap.widget.transition.Easing?
+---The opacity to start from, from 0.0 to 1.0.
+---@field from_opacity number?
+---The offset to start from.
+---@field from_offset { x: number, y: number }?
+---The scale to start from.
+---@field from_scale number?
+---Animate from the normal state to the `from_` state instead, for example
+---to fade something out before closing it.
+---@field reverse boolean?
+---@field repeat_forever boolean?
+---Whether repeating goes back and forth instead of jumping to the start.
+---@field auto_reverse boolean?
+
+---@enum snowcap.widget.transition.Easing
+local transition_easing = {
+    LINEAR = 1,
+    EASE_IN = 2,
+    EASE_OUT = 3,
+    EASE_IN_OUT = 4,
+}
 
 ---@class snowcap.widget.InputRegion
 ---@field add boolean
@@ -444,6 +602,144 @@ local text_input_event_type = {
     PASTE = "press",
 }
 
+---A horizontal bar with a handle that selects a value from a range.
+---@class snowcap.widget.Slider
+---The lowest value.
+---@field min number
+---The highest value.
+---@field max number
+---The current value.
+---@field value number
+---The step size.
+---@field step number?
+---The step size used while shift is held.
+---@field shift_step number?
+---The value the slider resets to when ctrl-clicked.
+---@field default number?
+---@field width snowcap.widget.Length?
+---The height in pixels.
+---@field height number?
+---Sets the message that should be produced when the handle is dragged.
+---@field on_change (fun(value: number): any)?
+---Sets the message that should be produced when the handle is released.
+---@field on_release any?
+---@field package widget_id integer?
+
+---A vertical bar with a handle that selects a value from a range.
+---@class snowcap.widget.VerticalSlider
+---The lowest value.
+---@field min number
+---The highest value.
+---@field max number
+---The current value.
+---@field value number
+---The step size.
+---@field step number?
+---The step size used while shift is held.
+---@field shift_step number?
+---The value the slider resets to when ctrl-clicked.
+---@field default number?
+---The width in pixels.
+---@field width number?
+---@field height snowcap.widget.Length?
+---Sets the message that should be produced when the handle is dragged.
+---@field on_change (fun(value: number): any)?
+---Sets the message that should be produced when the handle is released.
+---@field on_release any?
+---@field package widget_id integer?
+
+---The `Slider` and `VerticalSlider` callbacks.
+---@class snowcap.widget.slider.Callbacks
+---@field on_change (fun(value: number): any)?
+---@field on_release any?
+
+---A box that can be checked, with a label next to it.
+---@class snowcap.widget.Checkbox
+---@field label string
+---@field checked boolean
+---Sets the message that should be produced when the `Checkbox` is toggled.
+---
+---If the field is not set, the `Checkbox` will be disabled.
+---@field on_toggle (fun(checked: boolean): any)?
+---The size of the box.
+---@field size number?
+---The spacing between the box and the label.
+---@field spacing number?
+---The text size of the label.
+---@field text_size number?
+---@field width snowcap.widget.Length?
+---@field package widget_id integer?
+
+---A switch that can be toggled, with an optional label next to it.
+---@class snowcap.widget.Toggler
+---@field label string?
+---@field toggled boolean
+---Sets the message that should be produced when the `Toggler` is toggled.
+---
+---If the field is not set, the `Toggler` will be disabled.
+---@field on_toggle (fun(toggled: boolean): any)?
+---The height of the switch.
+---@field size number?
+---The spacing between the switch and the label.
+---@field spacing number?
+---The text size of the label.
+---@field text_size number?
+---@field width snowcap.widget.Length?
+---@field package widget_id integer?
+
+---A bar that fills up as `value` goes from `min` to `max`.
+---@class snowcap.widget.ProgressBar
+---@field min number
+---@field max number
+---@field value number
+---The length of the bar along the direction it fills.
+---@field length snowcap.widget.Length?
+---The thickness of the bar.
+---@field girth snowcap.widget.Length?
+---Whether the bar fills from bottom to top.
+---@field vertical boolean?
+---@field style snowcap.widget.progress_bar.Style?
+
+---Appearance of a `ProgressBar`.
+---@class snowcap.widget.progress_bar.Style
+---The `Background` of the unfilled part.
+---@field background snowcap.widget.Background?
+---The `Background` of the filled part.
+---@field bar snowcap.widget.Background?
+---@field border snowcap.widget.Border?
+
+---A dropdown that lets the user pick one of several options.
+---@class snowcap.widget.PickList
+---@field options string[]
+---The currently selected option.
+---@field selected string?
+---Text to display when nothing is selected.
+---@field placeholder string?
+---Sets the message that should be produced when an option is picked.
+---@field on_select (fun(selected: string): any)?
+---@field width snowcap.widget.Length?
+---@field padding snowcap.widget.Padding?
+---@field text_size number?
+---@field package widget_id integer?
+
+---A radio button with a label next to it.
+---
+---Each radio button stands on its own. To make a group, create one radio button per choice
+---and mark the one matching your program's state as selected.
+---@class snowcap.widget.Radio
+---@field label string
+---@field selected boolean
+---Sets the message that should be produced when the `Radio` is clicked.
+---@field on_select any?
+---The size of the button.
+---@field size number?
+---The spacing between the button and the label.
+---@field spacing number?
+---The text size of the label.
+---@field text_size number?
+---@field width snowcap.widget.Length?
+---@field package widget_id integer?
+
 ---@class snowcap.widget.Length
 ---@field fill {}?
 ---@field fill_portion integer?
@@ -656,6 +952,12 @@ local font = {
 ---@field button fun(widget: snowcap.widget.WidgetDef)?
 ---@field mouse_area fun(widget: snowcap.widget.WidgetDef)?
 ---@field text_input fun(widget: snowcap.widget.WidgetDef)?
+---@field slider fun(widget: snowcap.widget.WidgetDef)?
+---@field vertical_slider fun(widget: snowcap.widget.WidgetDef)?
+---@field checkbox fun(widget: snowcap.widget.WidgetDef)?
+---@field toggler fun(widget: snowcap.widget.WidgetDef)?
+---@field pick_list fun(widget: snowcap.widget.WidgetDef)?
+---@field radio fun(widget: snowcap.widget.WidgetDef)?
 
 local widget = {
     length = length,
@@ -666,6 +968,12 @@ local widget = {
     image = {
         content_fit = content_fit,
     },
+    tooltip = {
+        position = tooltip_position,
+    },
+    transition = {
+        easing = transition_easing,
+    },
     line_height = line_height,
     wrapping = wrapping,
     mouse = mouse,
@@ -783,6 +1091,7 @@ local function image_into_api(def)
         path = def.handle.path,
         bytes = def.handle.bytes,
         rgba = def.handle.rgba,
+        toplevel_identifier = def.handle.toplevel_identifier,
         width = def.width --[[@as snowcap.widget.v1.Length]],
         height = def.height --[[@as snowcap.widget.v1.Length]],
         expand = def.expand,
@@ -851,6 +1160,244 @@ local function text_input_into_api(def)
     }
 end
 
+---@param def snowcap.widget.Slider
+---@return snowcap.widget.v1.Slider
+local function slider_into_api(def)
+    ---@type snowcap.widget.v1.Slider
+    return {
+        min = def.min,
+        max = def.max,
+        value = def.value,
+        step = def.step,
+        shift_step = def.shift_step,
+        default_value = def.default,
+        width = def.width --[[@as snowcap.widget.v1.Length]],
+        height = def.height,
+        on_change = def.on_change ~= nil,
+        on_release = def.on_release ~= nil,
+        widget_id = def.widget_id,
+    }
+end
+
+---@param def snowcap.widget.VerticalSlider
+---@return snowcap.widget.v1.VerticalSlider
+local function vertical_slider_into_api(def)
+    ---@type snowcap.widget.v1.VerticalSlider
+    return {
+        min = def.min,
+        max = def.max,
+        value = def.value,
+        step = def.step,
+        shift_step = def.shift_step,
+        default_value = def.default,
+        width = def.width,
+        height = def.height --[[@as snowcap.widget.v1.Length]],
+        on_change = def.on_change ~= nil,
+        on_release = def.on_release ~= nil,
+        widget_id = def.widget_id,
+    }
+end
+
+---@param def snowcap.widget.Checkbox
+---@return snowcap.widget.v1.Checkbox
+local function checkbox_into_api(def)
+    ---@type snowcap.widget.v1.Checkbox
+    return {
+        label = def.label,
+        checked = def.checked,
+        on_toggle = def.on_toggle ~= nil,
+        size = def.size,
+        spacing = def.spacing,
+        text_size = def.text_size,
+        width = def.width --[[@as snowcap.widget.v1.Length]],
+        widget_id = def.widget_id,
+    }
+end
+
+---@param def snowcap.widget.Toggler
+---@return snowcap.widget.v1.Toggler
+local function toggler_into_api(def)
+    ---@type snowcap.widget.v1.Toggler
+    return {
+        label = def.label,
+        toggled = def.toggled,
+        on_toggle = def.on_toggle ~= nil,
+        size = def.size,
+        spacing = def.spacing,
+        text_size = def.text_size,
+        width = def.width --[[@as snowcap.widget.v1.Length]],
+        widget_id = def.widget_id,
+    }
+end
+
+---@param def snowcap.widget.ProgressBar
+---@return snowcap.widget.v1.ProgressBar
+local function progress_bar_into_api(def)
+    ---@type snowcap.widget.v1.ProgressBar
+    return {
+        min = def.min,
+        max = def.max,
+        value = def.value,
+        length = def.length --[[@as snowcap.widget.v1.Length]],
+        girth = def.girth --[[@as snowcap.widget.v1.Length]],
+        vertical = def.vertical,
+        style = def.style --[[@as snowcap.widget.v1.ProgressBar.Style]],
+    }
+end
+
+---@param def snowcap.widget.PickList
+---@return snowcap.widget.v1.PickList
+local function pick_list_into_api(def)
+    ---@type snowcap.widget.v1.PickList
+    return {
+        options = def.options,
+        selected = def.selected,
+        placeholder = def.placeholder,
+        on_select = def.on_select ~= nil,
+        width = def.width --[[@as snowcap.widget.v1.Length]],
+        padding = def.padding --[[@as snowcap.widget.v1.Padding]],
+        text_size = def.text_size,
+        widget_id = def.widget_id,
+    }
+end
+
+---@param def snowcap.widget.Radio
+---@return snowcap.widget.v1.Radio
+local function radio_into_api(def)
+    ---@type snowcap.widget.v1.Radio
+    return {
+        label = def.label,
+        selected = def.selected,
+        on_select = def.on_select ~= nil,
+        size = def.size,
+        spacing = def.spacing,
+        text_size = def.text_size,
+        width = def.width --[[@as snowcap.widget.v1.Length]],
+        widget_id = def.widget_id,
+    }
+end
+
+---@param def snowcap.widget.Svg
+---@return snowcap.widget.v1.Svg
+local function svg_into_api(def)
+    ---@type snowcap.widget.v1.Svg
+    return {
+        path = def.handle.path,
+        bytes = def.handle.bytes,
+        width = def.width --[[@as snowcap.widget.v1.Length]],
+        height = def.height --[[@as snowcap.widget.v1.Length]],
+        content_fit = def.content_fit,
+        color = def.color --[[@as snowcap.widget.v1.Color]],
+        rotation_degrees = def.rotation_degrees,
+        opacity = def.opacity,
+    }
+end
+
+---@param def snowcap.widget.Canvas
+---@return snowcap.widget.v1.Canvas
+local function canvas_into_api(def)
+    ---@type snowcap.widget.v1.Canvas
+    return {
+        width = def.width --[[@as snowcap.widget.v1.Length]],
+        height = def.height --[[@as snowcap.widget.v1.Length]],
+        commands = def.commands --[[@as snowcap.widget.v1.Canvas.Command[] ]],
+    }
+end
+
+---@param def snowcap.widget.Stack
+---@return snowcap.widget.v1.Stack
+local function stack_into_api(def)
+    local children = {}
+    for _, child in ipairs(def.children) do
+        table.insert(children, widget.widget_def_into_api(child))
+    end
+
+    ---@type snowcap.widget.v1.Stack
+    return {
+        width = def.width --[[@as snowcap.widget.v1.Length]],
+        height = def.height --[[@as snowcap.widget.v1.Length]],
+        clip = def.clip,
+        children = children,
+    }
+end
+
+---@param def snowcap.widget.Tooltip
+---@return snowcap.widget.v1.Tooltip
+local function tooltip_into_api(def)
+    ---@type snowcap.widget.v1.Tooltip
+    return {
+        content = widget.widget_def_into_api(def.content),
+        tooltip = widget.widget_def_into_api(def.tooltip),
+        position = def.position,
+        gap = def.gap,
+        padding = def.padding,
+        delay_millis = def.delay,
+        snap_within_viewport = def.snap_within_viewport,
+        style = def.style --[[@as snowcap.widget.v1.Container.Style]],
+    }
+end
+
+---@param def snowcap.widget.Grid
+---@return snowcap.widget.v1.Grid
+local function grid_into_api(def)
+    local children = {}
+    for _, child in ipairs(def.children) do
+        table.insert(children, widget.widget_def_into_api(child))
+    end
+
+    ---@type snowcap.widget.v1.Grid
+    return {
+        amount = def.columns,
+        max_cell_width = def.max_cell_width,
+        spacing = def.spacing,
+        width = def.width,
+        aspect_ratio = def.aspect_ratio,
+        evenly_distribute = def.evenly_distribute --[[@as snowcap.widget.v1.Length]],
+        children = children,
+    }
+end
+
+---@param def snowcap.widget.Space
+---@return snowcap.widget.v1.Space
+local function space_into_api(def)
+    ---@type snowcap.widget.v1.Space
+    return {
+        width = def.width --[[@as snowcap.widget.v1.Length]],
+        height = def.height --[[@as snowcap.widget.v1.Length]],
+    }
+end
+
+---@param def snowcap.widget.Rule
+---@return snowcap.widget.v1.Rule
+local function rule_into_api(def)
+    ---@type snowcap.widget.v1.Rule
+    return {
+        vertical = def.vertical or false,
+        thickness = def.thickness,
+        style = def.style --[[@as snowcap.widget.v1.Rule.Style]],
+    }
+end
+
+---@param def snowcap.widget.Transition
+---@return snowcap.widget.v1.Transition
+local function transition_into_api(def)
+    ---@type snowcap.widget.v1.Transition
+    return {
+        child = widget.widget_def_into_api(def.child),
+        key = def.key,
+        duration_millis = def.duration,
+        delay_millis = def.delay,
+        easing = def.easing,
+        from_opacity = def.from_opacity,
+        from_offset_x = def.from_offset and def.from_offset.x,
+        from_offset_y = def.from_offset and def.from_offset.y,
+        from_scale = def.from_scale,
+        reverse = def.reverse or false,
+        repeat_forever = def.repeat_forever or false,
+        auto_reverse = def.auto_reverse or false,
+    }
+end
+
 ---@param def snowcap.widget.WidgetDef
 ---@return snowcap.widget.v1.WidgetDef
 function widget.widget_def_into_api(def)
@@ -884,6 +1431,51 @@ function widget.widget_def_into_api(def)
     if def.text_input then
         def.text_input = text_input_into_api(def.text_input)
     end
+    if def.slider then
+        def.slider = slider_into_api(def.slider)
+    end
+    if def.vertical_slider then
+        def.vertical_slider = vertical_slider_into_api(def.vertical_slider)
+    end
+    if def.checkbox then
+        def.checkbox = checkbox_into_api(def.checkbox)
+    end
+    if def.toggler then
+        def.toggler = toggler_into_api(def.toggler)
+    end
+    if def.progress_bar then
+        def.progress_bar = progress_bar_into_api(def.progress_bar)
+    end
+    if def.pick_list then
+        def.pick_list = pick_list_into_api(def.pick_list)
+    end
+    if def.radio then
+        def.radio = radio_into_api(def.radio)
+    end
+    if def.svg then
+        def.svg = svg_into_api(def.svg)
+    end
+    if def.canvas then
+        def.canvas = canvas_into_api(def.canvas)
+    end
+    if def.stack then
+        def.stack = stack_into_api(def.stack)
+    end
+    if def.tooltip then
+        def.tooltip = tooltip_into_api(def.tooltip)
+    end
+    if def.grid then
+        def.grid = grid_into_api(def.grid)
+    end
+    if def.space then
+        def.space = space_into_api(def.space)
+    end
+    if def.rule then
+        def.rule = rule_into_api(def.rule)
+    end
+    if def.transition then
+        def.transition = transition_into_api(def.transition)
+    end
 
     return def --[[@as snowcap.widget.v1.WidgetDef]]
 end
@@ -1020,6 +1612,203 @@ function widget.text_input(text_input)
     }
 end
 
+---Create a new Slider widget.
+---@param slider snowcap.widget.Slider
+---
+---@return snowcap.widget.WidgetDef
+function widget.slider(slider)
+    if slider.on_change ~= nil or slider.on_release ~= nil then
+        slider.widget_id = widget_id_counter
+        widget_id_counter = widget_id_counter + 1
+    end
+
+    ---@type snowcap.widget.WidgetDef
+    return {
+        slider = slider,
+    }
+end
+
+---Create a new VerticalSlider widget.
+---@param vertical_slider snowcap.widget.VerticalSlider
+---
+---@return snowcap.widget.WidgetDef
+function widget.vertical_slider(vertical_slider)
+    if vertical_slider.on_change ~= nil or vertical_slider.on_release ~= nil then
+        vertical_slider.widget_id = widget_id_counter
+        widget_id_counter = widget_id_counter + 1
+    end
+
+    ---@type snowcap.widget.WidgetDef
+    return {
+        vertical_slider = vertical_slider,
+    }
+end
+
+---Create a new Checkbox widget.
+---@param checkbox snowcap.widget.Checkbox
+---
+---@return snowcap.widget.WidgetDef
+function widget.checkbox(checkbox)
+    if checkbox.on_toggle ~= nil then
+        checkbox.widget_id = widget_id_counter
+        widget_id_counter = widget_id_counter + 1
+    end
+
+    ---@type snowcap.widget.WidgetDef
+    return {
+        checkbox = checkbox,
+    }
+end
+
+---Create a new Toggler widget.
+---@param toggler snowcap.widget.Toggler
+---
+---@return snowcap.widget.WidgetDef
+function widget.toggler(toggler)
+    if toggler.on_toggle ~= nil then
+        toggler.widget_id = widget_id_counter
+        widget_id_counter = widget_id_counter + 1
+    end
+
+    ---@type snowcap.widget.WidgetDef
+    return {
+        toggler = toggler,
+    }
+end
+
+---Create a new ProgressBar widget.
+---@param progress_bar snowcap.widget.ProgressBar
+---
+---@return snowcap.widget.WidgetDef
+function widget.progress_bar(progress_bar)
+    ---@type snowcap.widget.WidgetDef
+    return {
+        progress_bar = progress_bar,
+    }
+end
+
+---Create a new PickList widget.
+---@param pick_list snowcap.widget.PickList
+---
+---@return snowcap.widget.WidgetDef
+function widget.pick_list(pick_list)
+    if pick_list.on_select ~= nil then
+        pick_list.widget_id = widget_id_counter
+        widget_id_counter = widget_id_counter + 1
+    end
+
+    ---@type snowcap.widget.WidgetDef
+    return {
+        pick_list = pick_list,
+    }
+end
+
+---Create a new Radio widget.
+---@param radio snowcap.widget.Radio
+---
+---@return snowcap.widget.WidgetDef
+function widget.radio(radio)
+    if radio.on_select ~= nil then
+        radio.widget_id = widget_id_counter
+        widget_id_counter = widget_id_counter + 1
+    end
+
+    ---@type snowcap.widget.WidgetDef
+    return {
+        radio = radio,
+    }
+end
+
+---Create a new Svg widget.
+---@param svg snowcap.widget.Svg
+---
+---@return snowcap.widget.WidgetDef
+function widget.svg(svg)
+    ---@type snowcap.widget.WidgetDef
+    return {
+        svg = svg,
+    }
+end
+
+---Create a new Canvas widget.
+---
+---Use `require("snowcap.widget.canvas")` to build its commands.
+---@param canvas snowcap.widget.Canvas
+---
+---@return snowcap.widget.WidgetDef
+function widget.canvas(canvas)
+    ---@type snowcap.widget.WidgetDef
+    return {
+        canvas = canvas,
+    }
+end
+
+---Create a new Stack widget.
+---@param stack snowcap.widget.Stack
+---
+---@return snowcap.widget.WidgetDef
+function widget.stack(stack)
+    ---@type snowcap.widget.WidgetDef
+    return {
+        stack = stack,
+    }
+end
+
+---Create a new Tooltip widget.
+---@param tooltip snowcap.widget.Tooltip
+---
+---@return snowcap.widget.WidgetDef
+function widget.tooltip(tooltip)
+    ---@type snowcap.widget.WidgetDef
+    return {
+        tooltip = tooltip,
+    }
+end
+
+---Create a new Grid widget.
+---@param grid snowcap.widget.Grid
+---
+---@return snowcap.widget.WidgetDef
+function widget.grid(grid)
+    ---@type snowcap.widget.WidgetDef
+    return {
+        grid = grid,
+    }
+end
+
+---Create some empty space.
+---@param space snowcap.widget.Space?
+---
+---@return snowcap.widget.WidgetDef
+function widget.space(space)
+    ---@type snowcap.widget.WidgetDef
+    return {
+        space = space or {},
+    }
+end
+
+---Create a new Rule widget.
+---@param rule snowcap.widget.Rule
+---
+---@return snowcap.widget.WidgetDef
+function widget.rule(rule)
+    ---@type snowcap.widget.WidgetDef
+    return {
+        rule = rule,
+    }
+end
+
+---Create a new Transition widget.
+---@param transition snowcap.widget.Transition
+---
+---@return snowcap.widget.WidgetDef
+function widget.transition(transition)
+    ---@type snowcap.widget.WidgetDef
+    return {
+        transition = transition,
+    }
+end
+
 ---@private
 ---@lcat nodoc
 ---@param wgt snowcap.widget.WidgetDef
@@ -1045,6 +1834,19 @@ function widget._traverse_widget_tree(wgt, callbacks, with_widget)
         widget._traverse_widget_tree(wgt.input_region.child, callbacks, with_widget)
     elseif wgt.mouse_area then
         widget._traverse_widget_tree(wgt.mouse_area.child, callbacks, with_widget)
+    elseif wgt.stack then
+        for _, w in ipairs(wgt.stack.children or {}) do
+            widget._traverse_widget_tree(w, callbacks, with_widget)
+        end
+    elseif wgt.tooltip then
+        widget._traverse_widget_tree(wgt.tooltip.content, callbacks, with_widget)
+        widget._traverse_widget_tree(wgt.tooltip.tooltip, callbacks, with_widget)
+    elseif wgt.grid then
+        for _, w in ipairs(wgt.grid.children or {}) do
+            widget._traverse_widget_tree(w, callbacks, with_widget)
+        end
+    elseif wgt.transition then
+        widget._traverse_widget_tree(wgt.transition.child, callbacks, with_widget)
     end
 end
 
@@ -1100,6 +1902,31 @@ function widget._collect_callbacks(callbacks, wgt)
     if wgt.text_input and wgt.text_input.widget_id then
         callbacks[wgt.text_input.widget_id] = collect_text_input_callbacks(wgt.text_input)
     end
+
+    local slider = wgt.slider or wgt.vertical_slider
+    if slider and slider.widget_id then
+        ---@type snowcap.widget.slider.Callbacks
+        callbacks[slider.widget_id] = {
+            on_change = slider.on_change,
+            on_release = slider.on_release,
+        }
+    end
+
+    if wgt.checkbox and wgt.checkbox.on_toggle then
+        callbacks[wgt.checkbox.widget_id] = wgt.checkbox.on_toggle
+    end
+
+    if wgt.toggler and wgt.toggler.on_toggle then
+        callbacks[wgt.toggler.widget_id] = wgt.toggler.on_toggle
+    end
+
+    if wgt.pick_list and wgt.pick_list.on_select then
+        callbacks[wgt.pick_list.widget_id] = wgt.pick_list.on_select
+    end
+
+    if wgt.radio and wgt.radio.on_select then
+        callbacks[wgt.radio.widget_id] = wgt.radio.on_select
+    end
 end
 
 ---@private
@@ -1211,6 +2038,46 @@ function widget._text_input_process_event(callbacks, event)
     return msg
 end
 
+---@private
+---@lcat nodoc
+---@param callbacks snowcap.widget.slider.Callbacks
+---@param event snowcap.widget.v1.Slider.Event
+---@return any?
+function widget._slider_process_event(callbacks, event)
+    callbacks = callbacks or {}
+
+    if event.release ~= nil then
+        return callbacks.on_release
+    end
+
+    if event.change ~= nil and callbacks.on_change ~= nil then
+        local ok, val = pcall(callbacks.on_change, event.change)
+
+        if not ok then
+            require("snowcap.log").error(val)
+        else
+            return val
+        end
+    end
+
+    return nil
+end
+
+---Calls a widget callback with an argument, logging any errors.
+---@param cb fun(arg: any): any
+---@param arg any
+---@return any?
+local function call_callback(cb, arg)
+    local ok, val = pcall(cb, arg)
+
+    if not ok then
+        require("snowcap.log").error(val)
+        return nil
+    end
+
+    return val
+end
+
 ---@private
 ---@lcat nodoc
 ---@param callbacks any[]
@@ -1231,6 +2098,25 @@ function widget._message_from_event(callbacks, event)
             ---@diagnostic disable-next-line:param-type-mismatch
             msg = widget._text_input_process_event(callbacks[widget_id], event.text_input)
         end
+    elseif event.slider then
+        if callbacks[widget_id] ~= nil then
+            ---@diagnostic disable-next-line:param-type-mismatch
+            msg = widget._slider_process_event(callbacks[widget_id], event.slider)
+        end
+    elseif event.checkbox then
+        if callbacks[widget_id] ~= nil then
+            msg = call_callback(callbacks[widget_id], event.checkbox.checked == true)
+        end
+    elseif event.toggler then
+        if callbacks[widget_id] ~= nil then
+            msg = call_callback(callbacks[widget_id], event.toggler.toggled == true)
+        end
+    elseif event.pick_list then
+        if callbacks[widget_id] ~= nil then
+            msg = call_callback(callbacks[widget_id], event.pick_list.selected or "")
+        end
+    elseif event.radio then
+        msg = callbacks[widget_id]
     end
 
     return msg
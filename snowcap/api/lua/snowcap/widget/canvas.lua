@@ -0,0 +1,314 @@
+-- This Source Code Form is subject to the terms of the Mozilla Public
+-- License, v. 2.0. If a copy of the MPL was not distributed with this
+-- file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+---@class snowcap.widget.canvas.Point
+---@field x number
+---@field y number
+
+---A part of a `Path`. Only one field should be set.
+---@class snowcap.widget.canvas.Segment
+---@field move_to snowcap.widget.canvas.Point?
+---@field line_to snowcap.widget.canvas.Point?
+---@field quadratic_curve_to { control: snowcap.widget.canvas.Point, to: snowcap.widget.canvas.Point }?
+---@field bezier_curve_to { control_a: snowcap.widget.canvas.Point, control_b: snowcap.widget.canvas.Point, to: snowcap.widget.canvas.Point }?
+---@field arc { center: snowcap.widget.canvas.Point, radius: number, start_radians: number, end_radians: number }?
+---@field rectangle { top_left: snowcap.widget.canvas.Point, size: { width: number, height: number }, radius: snowcap.widget.Radius? }?
+---@field circle { center: snowcap.widget.canvas.Point, radius: number }?
+---@field close {}?
+
+---A shape made out of segments.
+---@class snowcap.widget.canvas.Path
+---@field segments snowcap.widget.canvas.Segment[]
+
+---How a fill or stroke is colored. Only one field should be set.
+---@class snowcap.widget.canvas.Style
+---@field color snowcap.widget.Color?
+---A linear gradient between two points.
+---@field gradient { start: snowcap.widget.canvas.Point, ["end"]: snowcap.widget.canvas.Point, stops: { offset: number, color: snowcap.widget.Color }[] }?
+
+---@class snowcap.widget.canvas.Fill
+---@field path snowcap.widget.canvas.Path
+---@field style snowcap.widget.canvas.Style
+---@field rule snowcap.widget.canvas.FillRule?
+
+---@class snowcap.widget.canvas.Stroke
+---@field path snowcap.widget.canvas.Path
+---@field style snowcap.widget.canvas.Style
+---@field width number?
+---@field line_cap snowcap.widget.canvas.LineCap?
+---@field line_join snowcap.widget.canvas.LineJoin?
+---Alternating lengths of dashes and gaps.
+---@field dash_segments number[]?
+---@field dash_offset integer?
+
+---@class snowcap.widget.canvas.Text
+---@field content string
+---@field position snowcap.widget.canvas.Point
+---@field color snowcap.widget.Color?
+---@field size number?
+---@field font snowcap.widget.Font?
+---@field horizontal_alignment snowcap.widget.Alignment?
+---@field vertical_alignment snowcap.widget.Alignment?
+
+---A drawing command. Only one field should be set.
+---@class snowcap.widget.canvas.Command
+---@field fill snowcap.widget.canvas.Fill?
+---@field stroke snowcap.widget.canvas.Stroke?
+---@field text snowcap.widget.canvas.Text?
+---@field push_transform {}?
+---@field pop_transform {}?
+---@field translate snowcap.widget.canvas.Point?
+---@field rotate_radians number?
+---@field scale number?
+
+---Helpers to build drawing commands for `Widget.canvas`.
+---
+---## Example
+---Draw a line graph:
+---```lua
+---local Widget = require("snowcap.widget")
+---local Canvas = require("snowcap.widget.canvas")
+---
+---local history = { 0.2, 0.5, 0.4, 0.9, 0.6 }
+---
+---local line = Canvas.path()
+---for i, value in ipairs(history) do
+---    local x, y = (i - 1) * 25, 50 - value * 50
+---    if i == 1 then
+---        line:move_to(x, y)
+---    else
+---        line:line_to(x, y)
+---    end
+---end
+---
+---local graph = Widget.canvas({
+---    width = Widget.length.Fixed(100),
+---    height = Widget.length.Fixed(50),
+---    commands = {
+---        Canvas.stroke(line, Widget.color.from_rgba(0.4, 0.8, 0.4), { width = 2 }),
+---    },
+---})
+---```
+---@class snowcap.widget.canvas
+local canvas = {
+    ---@enum snowcap.widget.canvas.FillRule
+    fill_rule = {
+        NON_ZERO = 1,
+        EVEN_ODD = 2,
+    },
+    ---@enum snowcap.widget.canvas.LineCap
+    line_cap = {
+        BUTT = 1,
+        SQUARE = 2,
+        ROUND = 3,
+    },
+    ---@enum snowcap.widget.canvas.LineJoin
+    line_join = {
+        MITER = 1,
+        ROUND = 2,
+        BEVEL = 3,
+    },
+}
+
+---@class snowcap.widget.canvas.PathBuilder : snowcap.widget.canvas.Path
+local PathBuilder = {}
+
+---Starts a new subpath.
+---@param x number
+---@param y number
+---@return snowcap.widget.canvas.PathBuilder self
+function PathBuilder:move_to(x, y)
+    table.insert(self.segments, { move_to = { x = x, y = y } })
+    return self
+end
+
+---Draws a line to a point.
+---@param x number
+---@param y number
+---@return snowcap.widget.canvas.PathBuilder self
+function PathBuilder:line_to(x, y)
+    table.insert(self.segments, { line_to = { x = x, y = y } })
+    return self
+end
+
+---Draws a quadratic Bézier curve.
+---@param control snowcap.widget.canvas.Point
+---@param to snowcap.widget.canvas.Point
+---@return snowcap.widget.canvas.PathBuilder self
+function PathBuilder:quadratic_curve_to(control, to)
+    table.insert(self.segments, { quadratic_curve_to = { control = control, to = to } })
+    return self
+end
+
+---Draws a cubic Bézier curve.
+---@param control_a snowcap.widget.canvas.Point
+---@param control_b snowcap.widget.canvas.Point
+---@param to snowcap.widget.canvas.Point
+---@return snowcap.widget.canvas.PathBuilder self
+function PathBuilder:bezier_curve_to(control_a, control_b, to)
+    table.insert(self.segments, {
+        bezier_curve_to = { control_a = control_a, control_b = control_b, to = to },
+    })
+    return self
+end
+
+---Draws an arc of a circle.
+---@param center snowcap.widget.canvas.Point
+---@param radius number
+---@param start_radians number
+---@param end_radians number
+---@return snowcap.widget.canvas.PathBuilder self
+function PathBuilder:arc(center, radius, start_radians, end_radians)
+    table.insert(self.segments, {
+        arc = {
+            center = center,
+            radius = radius,
+            start_radians = start_radians,
+            end_radians = end_radians,
+        },
+    })
+    return self
+end
+
+---Adds a rectangle, optionally with rounded corners.
+---@param x number
+---@param y number
+---@param width number
+---@param height number
+---@param radius snowcap.widget.Radius?
+---@return snowcap.widget.canvas.PathBuilder self
+function PathBuilder:rectangle(x, y, width, height, radius)
+    table.insert(self.segments, {
+        rectangle = {
+            top_left = { x = x, y = y },
+            size = { width = width, height = height },
+            radius = radius,
+        },
+    })
+    return self
+end
+
+---Adds a circle.
+---@param x number
+---@param y number
+---@param radius number
+---@return snowcap.widget.canvas.PathBuilder self
+function PathBuilder:circle(x, y, radius)
+    table.insert(self.segments, { circle = { center = { x = x, y = y }, radius = radius } })
+    return self
+end
+
+---Closes the current subpath.
+---@return snowcap.widget.canvas.PathBuilder self
+function PathBuilder:close()
+    table.insert(self.segments, { close = {} })
+    return self
+end
+
+---Creates an empty path.
+---@return snowcap.widget.canvas.PathBuilder
+function canvas.path()
+    ---@type snowcap.widget.canvas.PathBuilder
+    local path = { segments = {} }
+    setmetatable(path, { __index = PathBuilder })
+    return path
+end
+
+---Turns a color or gradient into a style.
+---@param style snowcap.widget.Color | snowcap.widget.canvas.Style
+---@return snowcap.widget.canvas.Style
+local function into_style(style)
+    if style.color or style.gradient then
+        return style --[[@as snowcap.widget.canvas.Style]]
+    end
+    return { color = style --[[@as snowcap.widget.Color]] }
+end
+
+---Creates a linear gradient style between two points.
+---@param start snowcap.widget.canvas.Point
+---@param end_ snowcap.widget.canvas.Point
+---@param stops { offset: number, color: snowcap.widget.Color }[]
+---@return snowcap.widget.canvas.Style
+function canvas.linear_gradient(start, end_, stops)
+    return { gradient = { start = start, ["end"] = end_, stops = stops } }
+end
+
+---Fills the inside of a path.
+---@param path snowcap.widget.canvas.Path
+---@param style snowcap.widget.Color | snowcap.widget.canvas.Style
+---@param rule snowcap.widget.canvas.FillRule?
+---@return snowcap.widget.canvas.Command
+function canvas.fill(path, style, rule)
+    return {
+        fill = {
+            path = { segments = path.segments },
+            style = into_style(style),
+            rule = rule,
+        },
+    }
+end
+
+---Draws the outline of a path.
+---@param path snowcap.widget.canvas.Path
+---@param style snowcap.widget.Color | snowcap.widget.canvas.Style
+---@param opts { width: number?, line_cap: snowcap.widget.canvas.LineCap?, line_join: snowcap.widget.canvas.LineJoin?, dash_segments: number[]?, dash_offset: integer? }?
+---@return snowcap.widget.canvas.Command
+function canvas.stroke(path, style, opts)
+    opts = opts or {}
+
+    return {
+        stroke = {
+            path = { segments = path.segments },
+            style = into_style(style),
+            width = opts.width or 1,
+            line_cap = opts.line_cap,
+            line_join = opts.line_join,
+            dash_segments = opts.dash_segments,
+            dash_offset = opts.dash_offset,
+        },
+    }
+end
+
+---Draws text.
+---@param text snowcap.widget.canvas.Text
+---@return snowcap.widget.canvas.Command
+function canvas.text(text)
+    return { text = text }
+end
+
+---Saves the current transform.
+---@return snowcap.widget.canvas.Command
+function canvas.push_transform()
+    return { push_transform = {} }
+end
+
+---Restores the last saved transform.
+---@return snowcap.widget.canvas.Command
+function canvas.pop_transform()
+    return { pop_transform = {} }
+end
+
+---Moves the origin of later commands.
+---@param x number
+---@param y number
+---@return snowcap.widget.canvas.Command
+function canvas.translate(x, y)
+    return { translate = { x = x, y = y } }
+end
+
+---Rotates later commands around the origin.
+---@param radians number
+---@return snowcap.widget.canvas.Command
+function canvas.rotate(radians)
+    return { rotate_radians = radians }
+end
+
+---Scales later commands.
+---@param scale number
+---@return snowcap.widget.canvas.Command
+function canvas.scale(scale)
+    return { scale = scale }
+end
+
+return canvas
@@ -7,6 +7,7 @@ use snowcap_api_defs::snowcap::{
     input::v1::input_service_client::InputServiceClient,
     layer::v1::layer_service_client::LayerServiceClient,
     popup::v1::popup_service_client::PopupServiceClient,
+    theme::v1::theme_service_client::ThemeServiceClient,
     widget::v1::widget_service_client::WidgetServiceClient,
 };
 use tokio::sync::{RwLock, RwLockReadGuard};
@@ -22,6 +23,7 @@ pub struct Client {
     widget: WidgetServiceClient<Channel>,
     decoration: DecorationServiceClient<Channel>,
     popup: PopupServiceClient<Channel>,
+    theme: ThemeServiceClient<Channel>,
 }
 
 impl Client {
@@ -57,6 +59,10 @@ impl Client {
         Self::get().decoration.clone()
     }
 
+    pub fn theme() -> ThemeServiceClient<Channel> {
+        Self::get().theme.clone()
+    }
+
     fn new(channel: Channel) -> Self {
         Self {
             layer: LayerServiceClient::new(channel.clone()),
@@ -64,6 +70,7 @@ impl Client {
             widget: WidgetServiceClient::new(channel.clone()),
             decoration: DecorationServiceClient::new(channel.clone()),
             popup: PopupServiceClient::new(channel.clone()),
+            theme: ThemeServiceClient::new(channel.clone()),
         }
     }
 }
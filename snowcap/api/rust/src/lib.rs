@@ -14,7 +14,9 @@
 mod client;
 pub mod input;
 pub mod signal;
+pub mod subscription;
 pub mod surface;
+pub mod theme;
 pub mod widget;
 
 pub use surface::{decoration, layer, popup};
@@ -0,0 +1,495 @@
+//! Subscriptions to outside events.
+//!
+//! A [`Program`] returns the [`Subscription`]s it wants from [`Program::subscription`].
+//! Each subscription runs in the background for as long as the program keeps returning it
+//! and turns what it listens to into messages for the program, so widgets like clocks and
+//! battery indicators can update themselves.
+//!
+//! Subscriptions are told apart by an id. After every update, subscriptions with new ids are
+//! started and ones that are no longer returned are stopped. Returning the same subscription
+//! again keeps it running without restarting it.
+//!
+//! Subscriptions are only available in the Rust API.
+//!
+//! [`Program`]: crate::widget::Program
+//! [`Program::subscription`]: crate::widget::Program::subscription
+
+use std::{
+    any::TypeId,
+    collections::{HashMap, HashSet, VecDeque, hash_map::DefaultHasher},
+    ffi::OsStr,
+    hash::{Hash, Hasher},
+    mem::MaybeUninit,
+    os::{fd::OwnedFd, unix::ffi::OsStrExt},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use futures::{Stream, StreamExt, stream::BoxStream};
+use rustix::{
+    fs::inotify::{self, CreateFlags, ReadFlags, WatchFlags},
+    io::Errno,
+};
+use tokio::{io::unix::AsyncFd, sync::mpsc::UnboundedSender, task::AbortHandle};
+use tracing::error;
+
+const WATCH_FLAGS: WatchFlags = WatchFlags::CLOSE_WRITE
+    .union(WatchFlags::CREATE)
+    .union(WatchFlags::DELETE)
+    .union(WatchFlags::MOVED_FROM)
+    .union(WatchFlags::MOVED_TO)
+    .union(WatchFlags::ONLYDIR);
+
+/// A set of background event sources that produce messages.
+pub struct Subscription<Msg> {
+    recipes: Vec<Recipe<Msg>>,
+}
+
+struct Recipe<Msg> {
+    id: u64,
+    stream: Box<dyn FnOnce() -> BoxStream<'static, Msg> + Send>,
+}
+
+impl<Msg> std::fmt::Debug for Subscription<Msg> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscription")
+            .field(
+                "ids",
+                &self
+                    .recipes
+                    .iter()
+                    .map(|recipe| recipe.id)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<Msg> Default for Subscription<Msg> {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl<Msg> Subscription<Msg> {
+    /// Creates a subscription that doesn't produce anything.
+    pub fn none() -> Self {
+        Self {
+            recipes: Vec::new(),
+        }
+    }
+
+    /// Combines several subscriptions into one.
+    pub fn batch(subscriptions: impl IntoIterator<Item = Subscription<Msg>>) -> Self {
+        Self {
+            recipes: subscriptions
+                .into_iter()
+                .flat_map(|subscription| subscription.recipes)
+                .collect(),
+        }
+    }
+}
+
+impl<Msg: Send + 'static> Subscription<Msg> {
+    /// Creates a subscription from a stream.
+    ///
+    /// `stream` is only called when a subscription with this `id` isn't already running.
+    /// The subscription stops when the stream ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use snowcap_api::subscription::Subscription;
+    /// # use futures::StreamExt;
+    /// enum Msg {
+    ///     Number(u32),
+    /// }
+    ///
+    /// let numbers = Subscription::run("numbers", || futures::stream::iter(0..3).map(Msg::Number));
+    /// ```
+    pub fn run<I, S>(id: I, stream: impl FnOnce() -> S + Send + 'static) -> Self
+    where
+        I: Hash + 'static,
+        S: Stream<Item = Msg> + Send + 'static,
+    {
+        let mut hasher = DefaultHasher::new();
+        TypeId::of::<I>().hash(&mut hasher);
+        id.hash(&mut hasher);
+
+        Self {
+            recipes: vec![Recipe {
+                id: hasher.finish(),
+                stream: Box::new(move || stream().boxed()),
+            }],
+        }
+    }
+
+    /// Transforms the messages of this subscription.
+    ///
+    /// The type of `f` becomes part of the ids of the subscription, but anything it
+    /// captures does not. Capture-free functions like enum variants work best.
+    pub fn map<F, T>(self, f: F) -> Subscription<T>
+    where
+        F: Fn(Msg) -> T + Clone + Send + 'static,
+        T: Send + 'static,
+    {
+        Subscription {
+            recipes: self
+                .recipes
+                .into_iter()
+                .map(|recipe| {
+                    let f = f.clone();
+
+                    let mut hasher = DefaultHasher::new();
+                    recipe.id.hash(&mut hasher);
+                    TypeId::of::<F>().hash(&mut hasher);
+
+                    Recipe {
+                        id: hasher.finish(),
+                        stream: Box::new(move || (recipe.stream)().map(f).boxed()),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Creates a subscription that produces the current time every `interval`.
+///
+/// The first message arrives one `interval` after the subscription starts.
+pub fn every(interval: Duration) -> Subscription<Instant> {
+    Subscription::run(("every", interval), move || {
+        let start = tokio::time::Instant::now() + interval;
+        let mut interval = tokio::time::interval_at(start, interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        futures::stream::unfold(interval, |mut interval| async move {
+            let tick = interval.tick().await;
+            Some((tick.into_std(), interval))
+        })
+    })
+}
+
+/// Creates a subscription that produces the path of a file whenever it changes.
+///
+/// If `path` is a directory, the paths of files changing inside of it are produced instead.
+/// A change is a file being written and closed, created, deleted, or moved.
+///
+/// Files in `/sys` and `/proc` don't report changes; use [`every`] to poll them instead.
+pub fn watch(path: impl Into<PathBuf>) -> Subscription<PathBuf> {
+    let path = path.into();
+
+    Subscription::run(("watch", path.clone()), move || {
+        let watcher = Watcher::new(&path)
+            .inspect_err(|err| error!("Failed to watch {}: {err}", path.display()))
+            .ok();
+
+        futures::stream::unfold(watcher, |watcher| async move {
+            let mut watcher = watcher?;
+            let path = watcher.next().await?;
+            Some((path, Some(watcher)))
+        })
+    })
+}
+
+struct Watcher {
+    fd: AsyncFd<OwnedFd>,
+    dir: PathBuf,
+    /// The file in `dir` to report changes for, or `None` to report all of them.
+    file_name: Option<PathBuf>,
+    pending: VecDeque<PathBuf>,
+}
+
+impl Watcher {
+    fn new(path: &Path) -> std::io::Result<Self> {
+        // Editors often replace files instead of writing to them, so files
+        // are watched through their directory.
+        let (dir, file_name) = if path.is_dir() {
+            (path.to_path_buf(), None)
+        } else {
+            let dir = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+                _ => PathBuf::from("."),
+            };
+            (dir, path.file_name().map(PathBuf::from))
+        };
+
+        let fd = inotify::init(CreateFlags::CLOEXEC | CreateFlags::NONBLOCK)?;
+        inotify::add_watch(&fd, &dir, WATCH_FLAGS)?;
+
+        Ok(Self {
+            fd: AsyncFd::new(fd)?,
+            dir,
+            file_name,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Waits for the next change, returning `None` if the watch broke.
+    async fn next(&mut self) -> Option<PathBuf> {
+        loop {
+            if let Some(path) = self.pending.pop_front() {
+                return Some(path);
+            }
+
+            let mut guard = self
+                .fd
+                .readable()
+                .await
+                .inspect_err(|err| error!("Failed to wait for inotify events: {err}"))
+                .ok()?;
+
+            let mut buf = [MaybeUninit::uninit(); 4096];
+            let mut reader = inotify::Reader::new(guard.get_inner(), &mut buf);
+
+            loop {
+                let event = match reader.next() {
+                    Ok(event) => event,
+                    Err(Errno::AGAIN) => {
+                        guard.clear_ready();
+                        break;
+                    }
+                    Err(Errno::INTR) => continue,
+                    Err(err) => {
+                        error!("Failed to read inotify events: {err}");
+                        return None;
+                    }
+                };
+
+                if event.events().contains(ReadFlags::IGNORED) {
+                    // The watched directory is gone
+                    return None;
+                }
+
+                let Some(name) = event.file_name() else {
+                    continue;
+                };
+                let name = OsStr::from_bytes(name.to_bytes());
+
+                if self
+                    .file_name
+                    .as_ref()
+                    .is_none_or(|file_name| file_name.as_os_str() == name)
+                {
+                    let path = self.dir.join(name);
+                    if !self.pending.contains(&path) {
+                        self.pending.push_back(path);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Keeps the subscriptions of a program running.
+pub(crate) struct Tracker {
+    running: HashMap<u64, AbortHandle>,
+}
+
+impl Tracker {
+    pub(crate) fn new() -> Self {
+        Self {
+            running: HashMap::new(),
+        }
+    }
+
+    /// Starts new subscriptions and stops ones that are no longer in `subscription`.
+    ///
+    /// Messages are sent to `msg_sender` like emitted messages are.
+    pub(crate) fn update<Msg: Send + 'static>(
+        &mut self,
+        subscription: Subscription<Msg>,
+        msg_sender: &UnboundedSender<Option<Msg>>,
+    ) {
+        let mut ids = HashSet::new();
+
+        for recipe in subscription.recipes {
+            if !ids.insert(recipe.id) || self.running.contains_key(&recipe.id) {
+                continue;
+            }
+
+            let mut stream = (recipe.stream)();
+            let msg_sender = msg_sender.clone();
+
+            let task = tokio::spawn(async move {
+                while let Some(msg) = stream.next().await {
+                    if msg_sender.send(Some(msg)).is_err() {
+                        break;
+                    }
+                }
+            });
+
+            self.running.insert(recipe.id, task.abort_handle());
+        }
+
+        self.running.retain(|id, task| {
+            let keep = ids.contains(id);
+            if !keep {
+                task.abort();
+            }
+            keep
+        });
+    }
+}
+
+impl Drop for Tracker {
+    fn drop(&mut self) {
+        for task in self.running.values() {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::*;
+
+    /// A subscription that produces its id once, then stays running.
+    struct Counted {
+        id: u32,
+        /// How many times the stream was started.
+        starts: Arc<AtomicUsize>,
+        /// Held by the stream while it's running.
+        alive: Arc<()>,
+    }
+
+    impl Counted {
+        fn new(id: u32) -> Self {
+            Self {
+                id,
+                starts: Arc::default(),
+                alive: Arc::default(),
+            }
+        }
+
+        fn subscription(&self) -> Subscription<u32> {
+            let id = self.id;
+            let starts = self.starts.clone();
+            let alive = self.alive.clone();
+
+            Subscription::run(id, move || {
+                starts.fetch_add(1, Ordering::SeqCst);
+                futures::stream::once(async move { id })
+                    .chain(futures::stream::pending())
+                    .map(move |id| {
+                        let _alive = &alive;
+                        id
+                    })
+            })
+        }
+
+        fn starts(&self) -> usize {
+            self.starts.load(Ordering::SeqCst)
+        }
+
+        async fn wait_until_stopped(&self) {
+            tokio::time::timeout(Duration::from_secs(1), async {
+                while Arc::strong_count(&self.alive) > 1 {
+                    tokio::task::yield_now().await;
+                }
+            })
+            .await
+            .expect("subscription was not stopped");
+        }
+    }
+
+    #[tokio::test]
+    async fn update_starts_new_subscriptions_once() {
+        let (sender, mut receiver) = unbounded_channel();
+        let mut tracker = Tracker::new();
+        let one = Counted::new(1);
+
+        tracker.update(one.subscription(), &sender);
+        tracker.update(one.subscription(), &sender);
+
+        assert_eq!(one.starts(), 1);
+        assert_eq!(tracker.running.len(), 1);
+        assert_eq!(receiver.recv().await, Some(Some(1)));
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn update_starts_duplicate_ids_once() {
+        let (sender, _receiver) = unbounded_channel();
+        let mut tracker = Tracker::new();
+        let one = Counted::new(1);
+
+        tracker.update(
+            Subscription::batch([one.subscription(), one.subscription()]),
+            &sender,
+        );
+
+        assert_eq!(one.starts(), 1);
+        assert_eq!(tracker.running.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn update_stops_subscriptions_that_are_no_longer_returned() {
+        let (sender, _receiver) = unbounded_channel();
+        let mut tracker = Tracker::new();
+        let one = Counted::new(1);
+        let two = Counted::new(2);
+
+        tracker.update(
+            Subscription::batch([one.subscription(), two.subscription()]),
+            &sender,
+        );
+        assert_eq!(tracker.running.len(), 2);
+
+        tracker.update(two.subscription(), &sender);
+        one.wait_until_stopped().await;
+
+        assert_eq!(tracker.running.len(), 1);
+        assert_eq!(Arc::strong_count(&two.alive), 2);
+        assert_eq!((one.starts(), two.starts()), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn stopped_subscriptions_restart_when_returned_again() {
+        let (sender, _receiver) = unbounded_channel();
+        let mut tracker = Tracker::new();
+        let one = Counted::new(1);
+
+        tracker.update(one.subscription(), &sender);
+        tracker.update(Subscription::none(), &sender);
+        one.wait_until_stopped().await;
+        tracker.update(one.subscription(), &sender);
+
+        assert_eq!(one.starts(), 2);
+        assert_eq!(tracker.running.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn dropping_the_tracker_stops_everything() {
+        let (sender, _receiver) = unbounded_channel();
+        let mut tracker = Tracker::new();
+        let one = Counted::new(1);
+
+        tracker.update(one.subscription(), &sender);
+        drop(tracker);
+
+        one.wait_until_stopped().await;
+    }
+
+    #[test]
+    fn ids_depend_on_the_id_and_mapping() {
+        let id = |subscription: Subscription<u32>| subscription.recipes[0].id;
+        let run = |n: u32| Subscription::run(n, futures::stream::empty);
+
+        assert_eq!(id(run(1)), id(run(1)));
+        assert_ne!(id(run(1)), id(run(2)));
+        assert_ne!(id(run(1)), id(run(1).map(|n| n + 1)));
+        // Ids of different types don't collide
+        assert_ne!(
+            id(run(1)),
+            id(Subscription::run(1u64, futures::stream::empty))
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! Surfaces that widgets can be created on.
 
+use snowcap_api_defs::snowcap as defs;
+
 use crate::{
     decoration::DecorationHandle,
     layer::LayerHandle,
@@ -49,6 +51,25 @@ impl<Msg> Clone for SurfaceEvent<Msg> {
     }
 }
 
+/// Which clipboard to read from or write to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ClipboardKind {
+    /// The standard clipboard, used for copy and paste.
+    #[default]
+    Standard,
+    /// The primary selection, used for middle-click paste.
+    Primary,
+}
+
+impl From<ClipboardKind> for defs::clipboard::v1::ClipboardKind {
+    fn from(value: ClipboardKind) -> Self {
+        match value {
+            ClipboardKind::Standard => Self::Standard,
+            ClipboardKind::Primary => Self::Primary,
+        }
+    }
+}
+
 /// Implementation detail for [`SurfaceHandle`]
 enum Inner<Msg> {
     /// A handle to a layer surface.
@@ -20,6 +20,7 @@ use crate::{
     BlockOnTokio,
     client::Client,
     popup::{self, AsParent},
+    subscription,
     surface::SurfaceEvent,
     widget::{self, Program, WidgetDef, WidgetId, WidgetMessage, operation, signal},
 };
@@ -165,9 +166,21 @@ where
     });
 
     tokio::spawn(async move {
+        let mut subscriptions = subscription::Tracker::new();
+
         loop {
+            subscriptions.update(program.subscription(), &msg_send);
+
             tokio::select! {
-                Some(Ok(response)) = event_stream.next() => {
+                response = event_stream.next() => {
+                    // The surface was closed
+                    let Some(response) = response else {
+                        break;
+                    };
+                    let Ok(response) = response else {
+                        continue;
+                    };
+
                     for widget_event in response.widget_events {
                         let Some(msg) = widget::message_from_event(&callbacks, widget_event) else {
                             continue;
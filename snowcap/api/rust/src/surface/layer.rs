@@ -3,12 +3,13 @@
 use std::{collections::HashMap, num::NonZeroU32};
 
 use snowcap_api_defs::snowcap::{
+    clipboard,
     input::v1::{KeyboardKeyRequest, keyboard_key_request::Target},
     layer::{
         self,
         v1::{
-            CloseRequest, GetLayerEventsRequest, NewLayerRequest, OperateLayerRequest,
-            UpdateLayerRequest, ViewRequest,
+            CloseRequest, GetClipboardRequest, GetLayerEventsRequest, NewLayerRequest,
+            OperateLayerRequest, SetClipboardRequest, UpdateLayerRequest, ViewRequest,
         },
     },
     widget::v1::{GetWidgetEventsRequest, get_widget_events_request},
@@ -23,7 +24,8 @@ use crate::{
     client::Client,
     input::{KeyEvent, Modifiers},
     popup::{self, AsParent},
-    surface::SurfaceEvent,
+    subscription,
+    surface::{ClipboardKind, SurfaceEvent},
     widget::{self, Program, WidgetDef, WidgetId, WidgetMessage, operation, signal},
 };
 
@@ -122,6 +124,51 @@ impl From<ZLayer> for layer::v1::Layer {
     }
 }
 
+/// The distance between a layer surface and the edges it is anchored to.
+///
+/// Negative values let the surface extend past the edge.
+#[allow(missing_docs)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Margin {
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+    pub left: i32,
+}
+
+impl From<i32> for Margin {
+    fn from(value: i32) -> Self {
+        Self {
+            top: value,
+            right: value,
+            bottom: value,
+            left: value,
+        }
+    }
+}
+
+impl From<Margin> for layer::v1::Margin {
+    fn from(value: Margin) -> Self {
+        Self {
+            top: value.top,
+            right: value.right,
+            bottom: value.bottom,
+            left: value.left,
+        }
+    }
+}
+
+/// Extra options for [`new_widget_with_options`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct LayerOptions {
+    /// The name of the output to open the layer on.
+    ///
+    /// If `None`, the compositor chooses.
+    pub output: Option<String>,
+    /// The layer's margin.
+    pub margin: Margin,
+}
+
 /// The error type for layer event conversion.
 #[allow(missing_docs)]
 #[derive(Debug)]
@@ -165,11 +212,59 @@ pub enum UpdateLayerError {
 
 /// Create a new widget.
 pub fn new_widget<Msg, P>(
+    program: P,
+    anchor: Option<Anchor>,
+    keyboard_interactivity: KeyboardInteractivity,
+    exclusive_zone: ExclusiveZone,
+    layer: ZLayer,
+) -> Result<LayerHandle<Msg>, NewLayerError>
+where
+    Msg: Clone + Send + 'static,
+    P: Program<Message = Msg> + Send + 'static,
+{
+    new_widget_with_options(
+        program,
+        anchor,
+        keyboard_interactivity,
+        exclusive_zone,
+        layer,
+        LayerOptions::default(),
+    )
+}
+
+/// Create a new widget with the given [`LayerOptions`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use snowcap_api::layer::{self, *};
+/// # use snowcap_api::widget::Program;
+/// # fn example(bar: impl Program<Message = ()> + Send + 'static) -> Result<(), NewLayerError> {
+/// // Open a bar on `DP-1` that floats 8 pixels away from the top edge.
+/// let bar = layer::new_widget_with_options(
+///     bar,
+///     Some(Anchor::Top),
+///     KeyboardInteractivity::None,
+///     ExclusiveZone::Respect,
+///     ZLayer::Top,
+///     LayerOptions {
+///         output: Some("DP-1".into()),
+///         margin: Margin {
+///             top: 8,
+///             ..Default::default()
+///         },
+///     },
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn new_widget_with_options<Msg, P>(
     mut program: P,
     anchor: Option<Anchor>,
     keyboard_interactivity: KeyboardInteractivity,
     exclusive_zone: ExclusiveZone,
     layer: ZLayer,
+    options: LayerOptions,
 ) -> Result<LayerHandle<Msg>, NewLayerError>
 where
     Msg: Clone + Send + 'static,
@@ -193,6 +288,8 @@ where
                 as i32,
             exclusive_zone: exclusive_zone.into(),
             layer: layer::v1::Layer::from(layer) as i32,
+            output_name: options.output,
+            margin: Some(options.margin.into()),
         })
         .block_on_tokio()?;
 
@@ -268,9 +365,21 @@ where
     });
 
     tokio::spawn(async move {
+        let mut subscriptions = subscription::Tracker::new();
+
         loop {
+            subscriptions.update(program.subscription(), &msg_send);
+
             tokio::select! {
-                Some(Ok(response)) = widget_event_stream.next() => {
+                response = widget_event_stream.next() => {
+                    // The surface was closed
+                    let Some(response) = response else {
+                        break;
+                    };
+                    let Ok(response) = response else {
+                        continue;
+                    };
+
                     for widget_event in response.widget_events {
                         let Some(msg) = widget::message_from_event(&callbacks, widget_event) else {
                             continue;
@@ -332,6 +441,8 @@ where
                     keyboard_interactivity: None,
                     exclusive_zone: None,
                     layer: None,
+                    margin: None,
+                    output_name: None,
                 })
                 .await
                 .unwrap();
@@ -398,6 +509,8 @@ impl<Msg> LayerHandle<Msg> {
                 keyboard_interactivity,
                 exclusive_zone,
                 layer,
+                margin: None,
+                output_name: None,
             })
             .block_on_tokio()?;
 
@@ -430,6 +543,36 @@ impl<Msg> LayerHandle<Msg> {
         self.update(None, None, None, Some(layer))
     }
 
+    /// Update this layer's margin.
+    pub fn set_margin(&self, margin: impl Into<Margin>) -> Result<(), UpdateLayerError> {
+        Client::layer()
+            .update_layer(UpdateLayerRequest {
+                layer_id: self.id.to_inner(),
+                margin: Some(margin.into().into()),
+                ..Default::default()
+            })
+            .block_on_tokio()?;
+
+        Ok(())
+    }
+
+    /// Moves this layer to the output with the given name.
+    ///
+    /// If `None`, the compositor chooses.
+    ///
+    /// The layer is recreated on the new output, so any open popups are closed.
+    pub fn set_output(&self, output_name: Option<&str>) -> Result<(), UpdateLayerError> {
+        Client::layer()
+            .update_layer(UpdateLayerRequest {
+                layer_id: self.id.to_inner(),
+                output_name: Some(output_name.unwrap_or_default().to_string()),
+                ..Default::default()
+            })
+            .block_on_tokio()?;
+
+        Ok(())
+    }
+
     /// Close this layer widget.
     pub fn close(&self) {
         if let Err(status) = Client::layer()
@@ -466,6 +609,40 @@ impl<Msg> LayerHandle<Msg> {
             error!("Failed to send operation to {self:?}: {status}");
         }
     }
+
+    /// Returns the text contents of the clipboard.
+    pub fn clipboard(&self, kind: ClipboardKind) -> Option<String> {
+        match Client::layer()
+            .get_clipboard(GetClipboardRequest {
+                layer_id: self.id.to_inner(),
+                kind: clipboard::v1::ClipboardKind::from(kind).into(),
+            })
+            .block_on_tokio()
+        {
+            Ok(response) => response.into_inner().text,
+            Err(status) => {
+                error!("Failed to get clipboard for {self:?}: {status}");
+                None
+            }
+        }
+    }
+
+    /// Sets the text contents of the clipboard.
+    ///
+    /// Compositors only allow this while the layer has keyboard focus
+    /// or has recently been clicked.
+    pub fn set_clipboard(&self, kind: ClipboardKind, text: impl Into<String>) {
+        if let Err(status) = Client::layer()
+            .set_clipboard(SetClipboardRequest {
+                layer_id: self.id.to_inner(),
+                kind: clipboard::v1::ClipboardKind::from(kind).into(),
+                text: text.into(),
+            })
+            .block_on_tokio()
+        {
+            error!("Failed to set clipboard for {self:?}: {status}");
+        }
+    }
 }
 
 impl<Msg> LayerHandle<Msg>
@@ -473,6 +650,9 @@ where
     Msg: Clone + Send + 'static,
 {
     /// Do something when a key event is received
+    ///
+    /// Changes to the modifiers without a key event, like when this surface gains
+    /// keyboard focus, are sent as a release of [`Keysym::NoSymbol`].
     pub fn on_key_event(
         &self,
         mut on_event: impl FnMut(LayerHandle<Msg>, KeyEvent) + Send + 'static,
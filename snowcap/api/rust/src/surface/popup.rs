@@ -4,12 +4,13 @@ use std::collections::HashMap;
 
 use bitflags::bitflags;
 use snowcap_api_defs::snowcap::{
+    clipboard,
     input::v1::{KeyboardKeyRequest, keyboard_key_request::Target},
     popup::{
         self,
         v1::{
-            CloseRequest, GetPopupEventsRequest, NewPopupRequest, OperatePopupRequest,
-            UpdatePopupRequest, ViewRequest,
+            CloseRequest, GetClipboardRequest, GetPopupEventsRequest, NewPopupRequest,
+            OperatePopupRequest, SetClipboardRequest, UpdatePopupRequest, ViewRequest,
         },
     },
     widget::v1::{GetWidgetEventsRequest, get_widget_events_request},
@@ -23,7 +24,8 @@ use crate::{
     BlockOnTokio,
     client::Client,
     input::{KeyEvent, Modifiers},
-    surface::SurfaceEvent,
+    subscription,
+    surface::{ClipboardKind, SurfaceEvent},
     widget::{
         self, Program, WidgetDef, WidgetId, WidgetMessage,
         operation::{self, Operation},
@@ -305,9 +307,21 @@ where
     });
 
     tokio::spawn(async move {
+        let mut subscriptions = subscription::Tracker::new();
+
         loop {
+            subscriptions.update(program.subscription(), &msg_send);
+
             tokio::select! {
-                Some(Ok(response)) = widget_event_stream.next() => {
+                response = widget_event_stream.next() => {
+                    // The surface was closed
+                    let Some(response) = response else {
+                        break;
+                    };
+                    let Ok(response) = response else {
+                        continue;
+                    };
+
                     for widget_event in response.widget_events {
                         let Some(msg) = widget::message_from_event(&callbacks, widget_event) else {
                             continue;
@@ -486,6 +500,40 @@ impl<Msg> PopupHandle<Msg> {
     pub fn force_redraw(&self) {
         let _ = self.msg_sender.send(None);
     }
+
+    /// Returns the text contents of the clipboard.
+    pub fn clipboard(&self, kind: ClipboardKind) -> Option<String> {
+        match Client::popup()
+            .get_clipboard(GetClipboardRequest {
+                popup_id: self.id.to_inner(),
+                kind: clipboard::v1::ClipboardKind::from(kind).into(),
+            })
+            .block_on_tokio()
+        {
+            Ok(response) => response.into_inner().text,
+            Err(status) => {
+                error!("Failed to get clipboard for {self:?}: {status}");
+                None
+            }
+        }
+    }
+
+    /// Sets the text contents of the clipboard.
+    ///
+    /// Compositors only allow this while the popup has keyboard focus
+    /// or has recently been clicked.
+    pub fn set_clipboard(&self, kind: ClipboardKind, text: impl Into<String>) {
+        if let Err(status) = Client::popup()
+            .set_clipboard(SetClipboardRequest {
+                popup_id: self.id.to_inner(),
+                kind: clipboard::v1::ClipboardKind::from(kind).into(),
+                text: text.into(),
+            })
+            .block_on_tokio()
+        {
+            error!("Failed to set clipboard for {self:?}: {status}");
+        }
+    }
 }
 
 impl<Msg> PopupHandle<Msg>
@@ -493,6 +541,9 @@ where
     Msg: Clone + Send + 'static,
 {
     /// Do something when a key event is received.
+    ///
+    /// Changes to the modifiers without a key event, like when this surface gains
+    /// keyboard focus, are sent as a release of [`Keysym::NoSymbol`].
     pub fn on_key_event(
         &self,
         mut on_event: impl FnMut(PopupHandle<Msg>, KeyEvent) + Send + 'static,
@@ -0,0 +1,354 @@
+//! Snowcap's global theme.
+//!
+//! The theme holds the palette, font, text size, corner radius, and spacing that widgets use
+//! when they don't set their own. Every program drawn by snowcap inherits it.
+//!
+//! There is a dark and a light variant of the theme. Each can be set separately, and which one
+//! is used can be switched at any time. Anything left unset in a variant falls back to
+//! snowcap's defaults for that variant.
+//!
+//! Themes can also be loaded from a TOML file with [`load`]:
+//!
+//! ```toml
+//! variant = "dark"
+//!
+//! [dark]
+//! font = "Ubuntu"
+//! text_size = 14
+//! radius = 8
+//! spacing = 4
+//!
+//! [dark.palette]
+//! background = "#1e1e2e"
+//! text = "#cdd6f4"
+//! primary = "#cba6f7"
+//!
+//! [light.palette]
+//! background = "#eff1f5"
+//! text = "#4c4f69"
+//! primary = "#8839ef"
+//! ```
+
+use std::{path::Path, sync::OnceLock};
+
+use futures::StreamExt;
+use snowcap_api_defs::snowcap::theme::{
+    self,
+    v1::{
+        GetThemeRequest, LoadThemeRequest, SetThemeRequest, SetVariantRequest, WatchThemeRequest,
+    },
+};
+use tokio::sync::watch;
+use tracing::error;
+
+use crate::{
+    BlockOnTokio,
+    client::Client,
+    subscription::Subscription,
+    widget::{Color, font::Font},
+};
+
+/// A variant of the theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum Variant {
+    /// Light text on a dark background.
+    #[default]
+    Dark,
+    /// Dark text on a light background.
+    Light,
+}
+
+impl From<Variant> for theme::v1::Variant {
+    fn from(value: Variant) -> Self {
+        match value {
+            Variant::Dark => Self::Dark,
+            Variant::Light => Self::Light,
+        }
+    }
+}
+
+impl From<theme::v1::Variant> for Variant {
+    fn from(value: theme::v1::Variant) -> Self {
+        match value {
+            theme::v1::Variant::Unspecified | theme::v1::Variant::Dark => Variant::Dark,
+            theme::v1::Variant::Light => Variant::Light,
+        }
+    }
+}
+
+/// The colors of a theme.
+///
+/// Unset colors fall back to snowcap's defaults.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Palette {
+    pub background: Option<Color>,
+    pub text: Option<Color>,
+    pub primary: Option<Color>,
+    pub success: Option<Color>,
+    pub warning: Option<Color>,
+    pub danger: Option<Color>,
+}
+
+impl From<Palette> for theme::v1::Palette {
+    fn from(value: Palette) -> Self {
+        Self {
+            background: value.background.map(From::from),
+            text: value.text.map(From::from),
+            primary: value.primary.map(From::from),
+            success: value.success.map(From::from),
+            warning: value.warning.map(From::from),
+            danger: value.danger.map(From::from),
+        }
+    }
+}
+
+impl From<theme::v1::Palette> for Palette {
+    fn from(value: theme::v1::Palette) -> Self {
+        Self {
+            background: value.background.map(From::from),
+            text: value.text.map(From::from),
+            primary: value.primary.map(From::from),
+            success: value.success.map(From::from),
+            warning: value.warning.map(From::from),
+            danger: value.danger.map(From::from),
+        }
+    }
+}
+
+/// A theme.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Theme {
+    /// The colors of the theme.
+    pub palette: Palette,
+    /// The default font.
+    pub font: Option<Font>,
+    /// The default text size, in pixels.
+    pub text_size: Option<f32>,
+    /// The default corner radius of buttons, text inputs, pick lists, and styled containers.
+    pub radius: Option<f32>,
+    /// The default spacing of columns, rows, and grids.
+    pub spacing: Option<f32>,
+}
+
+impl Theme {
+    /// Creates a theme that leaves everything to snowcap's defaults.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the colors of this theme.
+    pub fn palette(self, palette: Palette) -> Self {
+        Self { palette, ..self }
+    }
+
+    /// Sets the default font of this theme.
+    pub fn font(self, font: Font) -> Self {
+        Self {
+            font: Some(font),
+            ..self
+        }
+    }
+
+    /// Sets the default text size of this theme.
+    pub fn text_size(self, text_size: f32) -> Self {
+        Self {
+            text_size: Some(text_size),
+            ..self
+        }
+    }
+
+    /// Sets the default corner radius of this theme.
+    pub fn radius(self, radius: f32) -> Self {
+        Self {
+            radius: Some(radius),
+            ..self
+        }
+    }
+
+    /// Sets the default spacing of this theme.
+    pub fn spacing(self, spacing: f32) -> Self {
+        Self {
+            spacing: Some(spacing),
+            ..self
+        }
+    }
+}
+
+impl From<Theme> for theme::v1::Theme {
+    fn from(value: Theme) -> Self {
+        Self {
+            palette: Some(value.palette.into()),
+            font: value.font.map(From::from),
+            text_size: value.text_size,
+            radius: value.radius,
+            spacing: value.spacing,
+        }
+    }
+}
+
+impl From<theme::v1::Theme> for Theme {
+    fn from(value: theme::v1::Theme) -> Self {
+        Self {
+            palette: value.palette.map(From::from).unwrap_or_default(),
+            font: value.font.map(From::from),
+            text_size: value.text_size,
+            radius: value.radius,
+            spacing: value.spacing,
+        }
+    }
+}
+
+/// The error type for [`load`].
+#[derive(thiserror::Error, Debug)]
+pub enum LoadThemeError {
+    /// Snowcap returned a gRPC error status.
+    #[error("gRPC error: `{0}`")]
+    GrpcStatus(#[from] tonic::Status),
+}
+
+/// Sets the theme of both variants.
+pub fn set(theme: Theme) {
+    set_theme(None, theme);
+}
+
+/// Sets the theme of one variant.
+pub fn set_variant_theme(variant: Variant, theme: Theme) {
+    set_theme(Some(variant), theme);
+}
+
+fn set_theme(variant: Option<Variant>, theme: Theme) {
+    let variant = variant.map(theme::v1::Variant::from).unwrap_or_default();
+
+    if let Err(status) = Client::theme()
+        .set_theme(SetThemeRequest {
+            variant: variant.into(),
+            theme: Some(theme.into()),
+        })
+        .block_on_tokio()
+    {
+        error!("Failed to set theme: {status}");
+    }
+}
+
+/// Switches to a variant of the theme.
+pub fn set_variant(variant: Variant) {
+    if let Err(status) = Client::theme()
+        .set_variant(SetVariantRequest {
+            variant: theme::v1::Variant::from(variant).into(),
+        })
+        .block_on_tokio()
+    {
+        error!("Failed to set theme variant: {status}");
+    }
+}
+
+/// Loads both variants of the theme, and the variant to use, from a TOML file.
+///
+/// If `watch` is true, the file is loaded again whenever it changes.
+/// A file that fails to load again leaves the theme as it was.
+pub fn load(path: impl AsRef<Path>, watch: bool) -> Result<(), LoadThemeError> {
+    Client::theme()
+        .load_theme(LoadThemeRequest {
+            path: path.as_ref().to_string_lossy().into_owned(),
+            watch,
+        })
+        .block_on_tokio()?;
+
+    Ok(())
+}
+
+/// Returns the variant in use and its theme.
+///
+/// The first call asks snowcap for the theme. After that, it is kept up to date
+/// in the background, so this is cheap to call from [`Program::view`].
+///
+/// [`Program::view`]: crate::widget::Program::view
+pub fn current() -> (Variant, Theme) {
+    receiver().borrow().clone()
+}
+
+/// A change of the theme or of the variant in use.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// The variant and theme before the change.
+    pub previous: (Variant, Theme),
+    /// The variant and theme after the change.
+    pub current: (Variant, Theme),
+}
+
+/// Creates a subscription that produces a [`Change`] whenever the theme
+/// or the variant in use changes.
+///
+/// Programs that read the theme with [`current`] can return this from
+/// [`Program::subscription`] to be redrawn when it changes.
+///
+/// [`Program::subscription`]: crate::widget::Program::subscription
+pub fn changes() -> Subscription<Change> {
+    Subscription::run("theme", || {
+        let mut receiver = receiver().clone();
+        let previous = receiver.borrow_and_update().clone();
+
+        futures::stream::unfold(
+            (receiver, previous),
+            |(mut receiver, previous)| async move {
+                receiver.changed().await.ok()?;
+                let current = receiver.borrow_and_update().clone();
+                let change = Change {
+                    previous,
+                    current: current.clone(),
+                };
+                Some((change, (receiver, current)))
+            },
+        )
+    })
+}
+
+fn receiver() -> &'static watch::Receiver<(Variant, Theme)> {
+    static CURRENT: OnceLock<watch::Receiver<(Variant, Theme)>> = OnceLock::new();
+
+    CURRENT.get_or_init(|| {
+        let current = match Client::theme()
+            .get_theme(GetThemeRequest {})
+            .block_on_tokio()
+        {
+            Ok(response) => {
+                let response = response.into_inner();
+                (
+                    response.variant().into(),
+                    response.theme.map(From::from).unwrap_or_default(),
+                )
+            }
+            Err(status) => {
+                error!("Failed to get theme: {status}");
+                Default::default()
+            }
+        };
+
+        let (sender, receiver) = watch::channel(current);
+
+        tokio::spawn(async move {
+            let mut stream = match Client::theme().watch_theme(WatchThemeRequest {}).await {
+                Ok(stream) => stream.into_inner(),
+                Err(status) => {
+                    error!("Failed to watch theme: {status}");
+                    return;
+                }
+            };
+
+            while let Some(Ok(response)) = stream.next().await {
+                let current = (
+                    response.variant().into(),
+                    response.theme.map(From::from).unwrap_or_default(),
+                );
+                sender.send_if_modified(|old| {
+                    let modified = *old != current;
+                    *old = current;
+                    modified
+                });
+            }
+        });
+
+        receiver
+    })
+}
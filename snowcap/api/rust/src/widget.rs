@@ -4,19 +4,33 @@
 
 pub mod base;
 pub mod button;
+pub mod canvas;
+pub mod checkbox;
 pub mod column;
 pub mod container;
 pub mod font;
+pub mod grid;
 pub mod image;
 pub mod input_region;
 pub mod message;
 pub mod mouse_area;
 pub mod operation;
+pub mod pick_list;
+pub mod progress_bar;
+pub mod radio;
 pub mod row;
+pub mod rule;
 pub mod scrollable;
 pub mod signal;
+pub mod slider;
+pub mod space;
+pub mod stack;
+pub mod svg;
 pub mod text;
 pub mod text_input;
+pub mod toggler;
+pub mod tooltip;
+pub mod transition;
 pub mod utils;
 
 use std::{
@@ -25,18 +39,33 @@ use std::{
 };
 
 use button::Button;
+use canvas::Canvas;
+use checkbox::Checkbox;
 use column::Column;
 use container::Container;
+use grid::Grid;
 use image::Image;
 use mouse_area::MouseArea;
+use pick_list::PickList;
+use progress_bar::ProgressBar;
+use radio::Radio;
 use row::Row;
+use rule::Rule;
 use scrollable::Scrollable;
+use slider::{Slider, VerticalSlider};
 use snowcap_api_defs::snowcap::widget;
+use space::Space;
+use stack::Stack;
+use svg::Svg;
 use text::Text;
 use text_input::TextInput;
+use toggler::Toggler;
+use tooltip::Tooltip;
+use transition::Transition;
 
 use crate::{
     signal::{HandlerPolicy, Signaler},
+    subscription::Subscription,
     surface::SurfaceEvent,
     widget::{input_region::InputRegion, utils::Radians},
 };
@@ -122,6 +151,11 @@ pub enum WidgetMessage<Msg> {
     Button(Msg),
     MouseArea(mouse_area::Callbacks<Msg>),
     TextInput(text_input::Callbacks<Msg>),
+    Slider(slider::Callbacks<Msg>),
+    Checkbox(checkbox::Callbacks<Msg>),
+    Toggler(toggler::Callbacks<Msg>),
+    PickList(pick_list::Callbacks<Msg>),
+    Radio(Msg),
 }
 
 pub fn message_from_event<Msg>(
@@ -149,6 +183,26 @@ where
             WidgetMessage::TextInput(callbacks) => callbacks.process_event(event.into()),
             _ => unreachable!(),
         }),
+        Event::Slider(event) => callbacks.get(&id).cloned().and_then(|f| match f {
+            WidgetMessage::Slider(callbacks) => callbacks.process_event(event.into()),
+            _ => unreachable!(),
+        }),
+        Event::Checkbox(event) => callbacks.get(&id).cloned().and_then(|f| match f {
+            WidgetMessage::Checkbox(callbacks) => callbacks.process_event(event),
+            _ => unreachable!(),
+        }),
+        Event::Toggler(event) => callbacks.get(&id).cloned().and_then(|f| match f {
+            WidgetMessage::Toggler(callbacks) => callbacks.process_event(event),
+            _ => unreachable!(),
+        }),
+        Event::PickList(event) => callbacks.get(&id).cloned().and_then(|f| match f {
+            WidgetMessage::PickList(callbacks) => callbacks.process_event(event),
+            _ => unreachable!(),
+        }),
+        Event::Radio(_event) => callbacks.get(&id).cloned().map(|f| match f {
+            WidgetMessage::Radio(msg) => msg,
+            _ => unreachable!(),
+        }),
     }
 }
 
@@ -188,6 +242,34 @@ impl<Msg> WidgetDef<Msg> {
                 mouse_area.child.collect_messages(callbacks, with_widget);
             }
             Widget::TextInput(_) => (),
+            Widget::Slider(_) => (),
+            Widget::VerticalSlider(_) => (),
+            Widget::Checkbox(_) => (),
+            Widget::Toggler(_) => (),
+            Widget::ProgressBar(_) => (),
+            Widget::PickList(_) => (),
+            Widget::Radio(_) => (),
+            Widget::Svg(_) => (),
+            Widget::Canvas(_) => (),
+            Widget::Stack(stack) => {
+                for widget in stack.children.iter() {
+                    widget.collect_messages(callbacks, with_widget);
+                }
+            }
+            Widget::Tooltip(tooltip) => {
+                tooltip.content.collect_messages(callbacks, with_widget);
+                tooltip.tooltip.collect_messages(callbacks, with_widget);
+            }
+            Widget::Grid(grid) => {
+                for widget in grid.children.iter() {
+                    widget.collect_messages(callbacks, with_widget);
+                }
+            }
+            Widget::Space(_) => (),
+            Widget::Rule(_) => (),
+            Widget::Transition(transition) => {
+                transition.child.collect_messages(callbacks, with_widget);
+            }
         }
     }
 }
@@ -218,6 +300,55 @@ impl<Msg: Clone> WidgetDef<Msg> {
                     .map(|id| (id, WidgetMessage::TextInput(text_input.callbacks.clone()))),
             );
         }
+
+        if let Widget::Slider(slider) = &self.widget {
+            callbacks.extend(
+                slider
+                    .widget_id
+                    .map(|id| (id, WidgetMessage::Slider(slider.callbacks.clone()))),
+            );
+        }
+
+        if let Widget::VerticalSlider(slider) = &self.widget {
+            callbacks.extend(
+                slider
+                    .widget_id
+                    .map(|id| (id, WidgetMessage::Slider(slider.callbacks.clone()))),
+            );
+        }
+
+        if let Widget::Checkbox(checkbox) = &self.widget {
+            callbacks.extend(
+                checkbox
+                    .widget_id
+                    .map(|id| (id, WidgetMessage::Checkbox(checkbox.callbacks.clone()))),
+            );
+        }
+
+        if let Widget::Toggler(toggler) = &self.widget {
+            callbacks.extend(
+                toggler
+                    .widget_id
+                    .map(|id| (id, WidgetMessage::Toggler(toggler.callbacks.clone()))),
+            );
+        }
+
+        if let Widget::PickList(pick_list) = &self.widget {
+            callbacks.extend(
+                pick_list
+                    .widget_id
+                    .map(|id| (id, WidgetMessage::PickList(pick_list.callbacks.clone()))),
+            );
+        }
+
+        if let Widget::Radio(radio) = &self.widget {
+            callbacks.extend(
+                radio
+                    .on_select
+                    .clone()
+                    .map(|(id, msg)| (id, WidgetMessage::Radio(msg))),
+            );
+        }
     }
 }
 
@@ -244,6 +375,21 @@ pub enum Widget<Msg> {
     InputRegion(Box<InputRegion<Msg>>),
     MouseArea(Box<MouseArea<Msg>>),
     TextInput(Box<TextInput<Msg>>),
+    Slider(Box<Slider<Msg>>),
+    VerticalSlider(Box<VerticalSlider<Msg>>),
+    Checkbox(Box<Checkbox<Msg>>),
+    Toggler(Box<Toggler<Msg>>),
+    ProgressBar(ProgressBar),
+    PickList(Box<PickList<Msg>>),
+    Radio(Box<Radio<Msg>>),
+    Svg(Svg),
+    Canvas(Canvas),
+    Stack(Stack<Msg>),
+    Tooltip(Box<Tooltip<Msg>>),
+    Grid(Grid<Msg>),
+    Space(Space),
+    Rule(Rule),
+    Transition(Box<Transition<Msg>>),
 }
 
 impl<Msg, T: Into<Widget<Msg>>> From<T> for WidgetDef<Msg> {
@@ -281,6 +427,33 @@ impl<Msg> From<Widget<Msg>> for widget::v1::widget_def::Widget {
             Widget::TextInput(text_input) => {
                 widget::v1::widget_def::Widget::TextInput(Box::new((*text_input).into()))
             }
+            Widget::Slider(slider) => widget::v1::widget_def::Widget::Slider((*slider).into()),
+            Widget::VerticalSlider(slider) => {
+                widget::v1::widget_def::Widget::VerticalSlider((*slider).into())
+            }
+            Widget::Checkbox(checkbox) => {
+                widget::v1::widget_def::Widget::Checkbox((*checkbox).into())
+            }
+            Widget::Toggler(toggler) => widget::v1::widget_def::Widget::Toggler((*toggler).into()),
+            Widget::ProgressBar(progress_bar) => {
+                widget::v1::widget_def::Widget::ProgressBar(progress_bar.into())
+            }
+            Widget::PickList(pick_list) => {
+                widget::v1::widget_def::Widget::PickList((*pick_list).into())
+            }
+            Widget::Radio(radio) => widget::v1::widget_def::Widget::Radio((*radio).into()),
+            Widget::Svg(svg) => widget::v1::widget_def::Widget::Svg(svg.into()),
+            Widget::Canvas(canvas) => widget::v1::widget_def::Widget::Canvas(canvas.into()),
+            Widget::Stack(stack) => widget::v1::widget_def::Widget::Stack(stack.into()),
+            Widget::Tooltip(tooltip) => {
+                widget::v1::widget_def::Widget::Tooltip(Box::new((*tooltip).into()))
+            }
+            Widget::Grid(grid) => widget::v1::widget_def::Widget::Grid(grid.into()),
+            Widget::Space(space) => widget::v1::widget_def::Widget::Space(space.into()),
+            Widget::Rule(rule) => widget::v1::widget_def::Widget::Rule(rule.into()),
+            Widget::Transition(transition) => {
+                widget::v1::widget_def::Widget::Transition(Box::new((*transition).into()))
+            }
         }
     }
 }
@@ -342,6 +515,12 @@ impl From<Color> for widget::v1::Color {
     }
 }
 
+impl From<widget::v1::Color> for Color {
+    fn from(value: widget::v1::Color) -> Self {
+        Color::rgba(value.red, value.green, value.blue, value.alpha)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Padding {
     pub top: f32,
@@ -668,6 +847,17 @@ pub trait Program {
         let _ = event;
     }
 
+    /// Returns the [`Subscription`]s this program wants to receive messages from.
+    ///
+    /// This is called after every update. Subscriptions that are returned keep running,
+    /// and ones that stop being returned are stopped. Programs with child programs
+    /// should [batch] the subscriptions of their children with their own.
+    ///
+    /// [batch]: Subscription::batch
+    fn subscription(&self) -> Subscription<Self::Message> {
+        Subscription::none()
+    }
+
     /// Returns a possibly held [`Signaler`].
     ///
     /// Usually this is from a [`WidgetBase`] stored in the
@@ -743,6 +933,10 @@ impl<Msg> Program for Box<dyn Program<Message = Msg>> {
         (**self).event(event);
     }
 
+    fn subscription(&self) -> Subscription<Self::Message> {
+        (**self).subscription()
+    }
+
     fn register_child(&self, child: &dyn Program<Message = Self::Message>)
     where
         Self::Message: Clone + 'static,
@@ -770,6 +964,10 @@ impl<Msg> Program for Box<dyn Program<Message = Msg> + Send> {
         (**self).event(event);
     }
 
+    fn subscription(&self) -> Subscription<Self::Message> {
+        (**self).subscription()
+    }
+
     fn register_child(&self, child: &dyn Program<Message = Self::Message>)
     where
         Self::Message: Clone + 'static,
@@ -797,6 +995,10 @@ impl<Msg> Program for Box<dyn Program<Message = Msg> + Send + Sync> {
         (**self).event(event);
     }
 
+    fn subscription(&self) -> Subscription<Self::Message> {
+        (**self).subscription()
+    }
+
     fn register_child(&self, child: &dyn Program<Message = Self::Message>)
     where
         Self::Message: Clone + 'static,
@@ -0,0 +1,669 @@
+//! Custom drawing.
+//!
+//! A [`Canvas`] holds a list of drawing commands that Snowcap replays in order.
+//! Snowcap caches the resulting geometry and only redraws it when the commands change,
+//! so a canvas that is rebuilt with the same commands on every view is cheap.
+//!
+//! # Example
+//!
+//! Draw a line graph:
+//!
+//! ```
+//! use snowcap_api::widget::{
+//!     Color, Length,
+//!     canvas::{Canvas, Path, Stroke},
+//! };
+//!
+//! let history = [0.2, 0.5, 0.4, 0.9, 0.6];
+//!
+//! let mut line = Path::new();
+//! for (i, value) in history.iter().enumerate() {
+//!     let point = (i as f32 * 25.0, 50.0 - value * 50.0);
+//!     line = if i == 0 { line.move_to(point) } else { line.line_to(point) };
+//! }
+//!
+//! let canvas = Canvas::new()
+//!     .width(Length::Fixed(100.0))
+//!     .height(Length::Fixed(50.0))
+//!     .stroke(line, Stroke::new(Color::rgb(0.4, 0.8, 0.4)).width(2.0));
+//! ```
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Alignment, Color, ColorStop, Length, Radius, font::Font, utils::Radians};
+
+/// A widget that draws a list of [`Command`]s.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Canvas {
+    pub width: Option<Length>,
+    pub height: Option<Length>,
+    pub commands: Vec<Command>,
+}
+
+impl Canvas {
+    /// Creates an empty [`Canvas`].
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn width(self, width: Length) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    pub fn height(self, height: Length) -> Self {
+        Self {
+            height: Some(height),
+            ..self
+        }
+    }
+
+    /// Adds a [`Command`].
+    pub fn push(mut self, command: Command) -> Self {
+        self.commands.push(command);
+        self
+    }
+
+    /// Fills a [`Path`] using the non-zero fill rule.
+    pub fn fill(self, path: Path, style: impl Into<Style>) -> Self {
+        self.push(Command::Fill {
+            path,
+            style: style.into(),
+            rule: FillRule::NonZero,
+        })
+    }
+
+    /// Draws the outline of a [`Path`].
+    pub fn stroke(self, path: Path, stroke: Stroke) -> Self {
+        self.push(Command::Stroke { path, stroke })
+    }
+
+    /// Draws [`Text`].
+    pub fn fill_text(self, text: Text) -> Self {
+        self.push(Command::Text(text))
+    }
+
+    /// Saves the current transform.
+    pub fn push_transform(self) -> Self {
+        self.push(Command::PushTransform)
+    }
+
+    /// Restores the last saved transform.
+    pub fn pop_transform(self) -> Self {
+        self.push(Command::PopTransform)
+    }
+
+    /// Moves the origin of later commands.
+    pub fn translate(self, x: f32, y: f32) -> Self {
+        self.push(Command::Translate(Point { x, y }))
+    }
+
+    /// Rotates later commands around the origin.
+    pub fn rotate(self, angle: impl Into<Radians>) -> Self {
+        self.push(Command::Rotate(angle.into()))
+    }
+
+    /// Scales later commands.
+    pub fn scale(self, scale: f32) -> Self {
+        self.push(Command::Scale(scale))
+    }
+}
+
+impl From<Canvas> for widget::v1::Canvas {
+    fn from(value: Canvas) -> Self {
+        Self {
+            width: value.width.map(From::from),
+            height: value.height.map(From::from),
+            commands: value.commands.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+/// A drawing command.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Fills the inside of a [`Path`].
+    Fill {
+        path: Path,
+        style: Style,
+        rule: FillRule,
+    },
+    /// Draws the outline of a [`Path`].
+    Stroke { path: Path, stroke: Stroke },
+    /// Draws [`Text`].
+    Text(Text),
+    /// Saves the current transform.
+    PushTransform,
+    /// Restores the last saved transform.
+    PopTransform,
+    /// Moves the origin of later commands.
+    Translate(Point),
+    /// Rotates later commands around the origin.
+    Rotate(Radians),
+    /// Scales later commands.
+    Scale(f32),
+}
+
+impl From<Command> for widget::v1::canvas::Command {
+    fn from(value: Command) -> Self {
+        use widget::v1::canvas::command::Command as ApiCommand;
+
+        let command = match value {
+            Command::Fill { path, style, rule } => ApiCommand::Fill(widget::v1::canvas::Fill {
+                path: Some(path.into()),
+                style: Some(style.into()),
+                rule: widget::v1::canvas::FillRule::from(rule) as i32,
+            }),
+            Command::Stroke { path, stroke } => ApiCommand::Stroke(widget::v1::canvas::Stroke {
+                path: Some(path.into()),
+                style: Some(stroke.style.into()),
+                width: stroke.width,
+                line_cap: widget::v1::canvas::LineCap::from(stroke.line_cap) as i32,
+                line_join: widget::v1::canvas::LineJoin::from(stroke.line_join) as i32,
+                dash_segments: stroke.dash_segments,
+                dash_offset: stroke.dash_offset,
+            }),
+            Command::Text(text) => ApiCommand::Text(text.into()),
+            Command::PushTransform => ApiCommand::PushTransform(()),
+            Command::PopTransform => ApiCommand::PopTransform(()),
+            Command::Translate(point) => ApiCommand::Translate(point.into()),
+            Command::Rotate(radians) => ApiCommand::RotateRadians(radians.0),
+            Command::Scale(scale) => ApiCommand::Scale(scale),
+        };
+
+        Self {
+            command: Some(command),
+        }
+    }
+}
+
+/// A point in the canvas' coordinate space.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl From<(f32, f32)> for Point {
+    fn from((x, y): (f32, f32)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<Point> for widget::v1::canvas::Point {
+    fn from(value: Point) -> Self {
+        Self {
+            x: value.x,
+            y: value.y,
+        }
+    }
+}
+
+/// A shape made out of [`Segment`]s.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path {
+    pub segments: Vec<Segment>,
+}
+
+impl Path {
+    /// Creates an empty [`Path`].
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a [`Path`] with a single line.
+    pub fn line(from: impl Into<Point>, to: impl Into<Point>) -> Self {
+        Self::new().move_to(from).line_to(to)
+    }
+
+    /// Creates a [`Path`] with a single rectangle.
+    pub fn rectangle(top_left: impl Into<Point>, width: f32, height: f32) -> Self {
+        Self::new().push(Segment::Rectangle {
+            top_left: top_left.into(),
+            width,
+            height,
+            radius: None,
+        })
+    }
+
+    /// Creates a [`Path`] with a single rectangle with rounded corners.
+    pub fn rounded_rectangle(
+        top_left: impl Into<Point>,
+        width: f32,
+        height: f32,
+        radius: impl Into<Radius>,
+    ) -> Self {
+        Self::new().push(Segment::Rectangle {
+            top_left: top_left.into(),
+            width,
+            height,
+            radius: Some(radius.into()),
+        })
+    }
+
+    /// Creates a [`Path`] with a single circle.
+    pub fn circle(center: impl Into<Point>, radius: f32) -> Self {
+        Self::new().push(Segment::Circle {
+            center: center.into(),
+            radius,
+        })
+    }
+
+    /// Adds a [`Segment`].
+    pub fn push(mut self, segment: Segment) -> Self {
+        self.segments.push(segment);
+        self
+    }
+
+    /// Starts a new subpath at `to`.
+    pub fn move_to(self, to: impl Into<Point>) -> Self {
+        self.push(Segment::MoveTo(to.into()))
+    }
+
+    /// Draws a line to `to`.
+    pub fn line_to(self, to: impl Into<Point>) -> Self {
+        self.push(Segment::LineTo(to.into()))
+    }
+
+    /// Draws a quadratic Bézier curve to `to`.
+    pub fn quadratic_curve_to(self, control: impl Into<Point>, to: impl Into<Point>) -> Self {
+        self.push(Segment::QuadraticCurveTo {
+            control: control.into(),
+            to: to.into(),
+        })
+    }
+
+    /// Draws a cubic Bézier curve to `to`.
+    pub fn bezier_curve_to(
+        self,
+        control_a: impl Into<Point>,
+        control_b: impl Into<Point>,
+        to: impl Into<Point>,
+    ) -> Self {
+        self.push(Segment::BezierCurveTo {
+            control_a: control_a.into(),
+            control_b: control_b.into(),
+            to: to.into(),
+        })
+    }
+
+    /// Draws an arc of a circle.
+    pub fn arc(
+        self,
+        center: impl Into<Point>,
+        radius: f32,
+        start_angle: impl Into<Radians>,
+        end_angle: impl Into<Radians>,
+    ) -> Self {
+        self.push(Segment::Arc {
+            center: center.into(),
+            radius,
+            start_angle: start_angle.into(),
+            end_angle: end_angle.into(),
+        })
+    }
+
+    /// Closes the current subpath.
+    pub fn close(self) -> Self {
+        self.push(Segment::Close)
+    }
+}
+
+impl From<Path> for widget::v1::canvas::Path {
+    fn from(value: Path) -> Self {
+        Self {
+            segments: value.segments.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+/// A part of a [`Path`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+    MoveTo(Point),
+    LineTo(Point),
+    QuadraticCurveTo {
+        control: Point,
+        to: Point,
+    },
+    BezierCurveTo {
+        control_a: Point,
+        control_b: Point,
+        to: Point,
+    },
+    Arc {
+        center: Point,
+        radius: f32,
+        start_angle: Radians,
+        end_angle: Radians,
+    },
+    Rectangle {
+        top_left: Point,
+        width: f32,
+        height: f32,
+        radius: Option<Radius>,
+    },
+    Circle {
+        center: Point,
+        radius: f32,
+    },
+    Close,
+}
+
+impl From<Segment> for widget::v1::canvas::path::Segment {
+    fn from(value: Segment) -> Self {
+        use widget::v1::canvas::path::{self, segment::Segment as ApiSegment};
+
+        let segment = match value {
+            Segment::MoveTo(to) => ApiSegment::MoveTo(to.into()),
+            Segment::LineTo(to) => ApiSegment::LineTo(to.into()),
+            Segment::QuadraticCurveTo { control, to } => {
+                ApiSegment::QuadraticCurveTo(path::QuadraticCurveTo {
+                    control: Some(control.into()),
+                    to: Some(to.into()),
+                })
+            }
+            Segment::BezierCurveTo {
+                control_a,
+                control_b,
+                to,
+            } => ApiSegment::BezierCurveTo(path::BezierCurveTo {
+                control_a: Some(control_a.into()),
+                control_b: Some(control_b.into()),
+                to: Some(to.into()),
+            }),
+            Segment::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+            } => ApiSegment::Arc(path::Arc {
+                center: Some(center.into()),
+                radius,
+                start_radians: start_angle.0,
+                end_radians: end_angle.0,
+            }),
+            Segment::Rectangle {
+                top_left,
+                width,
+                height,
+                radius,
+            } => ApiSegment::Rectangle(path::Rectangle {
+                top_left: Some(top_left.into()),
+                size: Some(widget::v1::canvas::Size { width, height }),
+                radius: radius.map(From::from),
+            }),
+            Segment::Circle { center, radius } => ApiSegment::Circle(path::Circle {
+                center: Some(center.into()),
+                radius,
+            }),
+            Segment::Close => ApiSegment::Close(()),
+        };
+
+        Self {
+            segment: Some(segment),
+        }
+    }
+}
+
+/// How a fill or stroke is colored.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Style {
+    /// A solid color.
+    Color(Color),
+    /// A linear gradient between two points.
+    Gradient(LinearGradient),
+}
+
+impl From<Color> for Style {
+    fn from(color: Color) -> Self {
+        Self::Color(color)
+    }
+}
+
+impl From<LinearGradient> for Style {
+    fn from(gradient: LinearGradient) -> Self {
+        Self::Gradient(gradient)
+    }
+}
+
+impl From<Style> for widget::v1::canvas::Style {
+    fn from(value: Style) -> Self {
+        use widget::v1::canvas::style::Style as ApiStyle;
+
+        let style = match value {
+            Style::Color(color) => ApiStyle::Color(color.into()),
+            Style::Gradient(gradient) => ApiStyle::Gradient(gradient.into()),
+        };
+
+        Self { style: Some(style) }
+    }
+}
+
+/// A gradient that interpolates colors from `start` to `end`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearGradient {
+    pub start: Point,
+    pub end: Point,
+    pub stops: Vec<ColorStop>,
+}
+
+impl LinearGradient {
+    /// Creates a new [`LinearGradient`] without any color stops.
+    pub fn new(start: impl Into<Point>, end: impl Into<Point>) -> Self {
+        Self {
+            start: start.into(),
+            end: end.into(),
+            stops: Vec::new(),
+        }
+    }
+
+    /// Adds a [`ColorStop`].
+    ///
+    /// `offset`s not within the 0.0..=1.0 range and stops after the 8th are ignored.
+    pub fn add_stop(mut self, offset: f32, color: Color) -> Self {
+        self.stops.push(ColorStop { offset, color });
+        self
+    }
+}
+
+impl From<LinearGradient> for widget::v1::canvas::LinearGradient {
+    fn from(value: LinearGradient) -> Self {
+        Self {
+            start: Some(value.start.into()),
+            end: Some(value.end.into()),
+            stops: value.stops.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+/// How overlapping parts of a path are filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum FillRule {
+    #[default]
+    NonZero,
+    EvenOdd,
+}
+
+impl From<FillRule> for widget::v1::canvas::FillRule {
+    fn from(value: FillRule) -> Self {
+        match value {
+            FillRule::NonZero => widget::v1::canvas::FillRule::NonZero,
+            FillRule::EvenOdd => widget::v1::canvas::FillRule::EvenOdd,
+        }
+    }
+}
+
+/// How the ends of a stroke are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Square,
+    Round,
+}
+
+impl From<LineCap> for widget::v1::canvas::LineCap {
+    fn from(value: LineCap) -> Self {
+        match value {
+            LineCap::Butt => widget::v1::canvas::LineCap::Butt,
+            LineCap::Square => widget::v1::canvas::LineCap::Square,
+            LineCap::Round => widget::v1::canvas::LineCap::Round,
+        }
+    }
+}
+
+/// How the corners of a stroke are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum LineJoin {
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+impl From<LineJoin> for widget::v1::canvas::LineJoin {
+    fn from(value: LineJoin) -> Self {
+        match value {
+            LineJoin::Miter => widget::v1::canvas::LineJoin::Miter,
+            LineJoin::Round => widget::v1::canvas::LineJoin::Round,
+            LineJoin::Bevel => widget::v1::canvas::LineJoin::Bevel,
+        }
+    }
+}
+
+/// How the outline of a [`Path`] is drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+    pub style: Style,
+    pub width: f32,
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+    /// Alternating lengths of dashes and gaps. Empty for a solid line.
+    pub dash_segments: Vec<f32>,
+    pub dash_offset: u32,
+}
+
+impl Stroke {
+    /// Creates a solid, one pixel wide [`Stroke`].
+    pub fn new(style: impl Into<Style>) -> Self {
+        Self {
+            style: style.into(),
+            width: 1.0,
+            line_cap: LineCap::default(),
+            line_join: LineJoin::default(),
+            dash_segments: Vec::new(),
+            dash_offset: 0,
+        }
+    }
+
+    pub fn width(self, width: f32) -> Self {
+        Self { width, ..self }
+    }
+
+    pub fn line_cap(self, line_cap: LineCap) -> Self {
+        Self { line_cap, ..self }
+    }
+
+    pub fn line_join(self, line_join: LineJoin) -> Self {
+        Self { line_join, ..self }
+    }
+
+    /// Makes the stroke dashed.
+    pub fn dashed(self, segments: impl IntoIterator<Item = f32>, offset: u32) -> Self {
+        Self {
+            dash_segments: segments.into_iter().collect(),
+            dash_offset: offset,
+            ..self
+        }
+    }
+}
+
+/// Text drawn on a [`Canvas`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Text {
+    pub content: String,
+    pub position: Point,
+    pub color: Option<Color>,
+    pub size: Option<f32>,
+    pub font: Option<Font>,
+    pub horizontal_alignment: Option<Alignment>,
+    pub vertical_alignment: Option<Alignment>,
+}
+
+impl Text {
+    /// Creates new [`Text`] at the given position.
+    pub fn new(content: impl Into<String>, position: impl Into<Point>) -> Self {
+        Self {
+            content: content.into(),
+            position: position.into(),
+            color: None,
+            size: None,
+            font: None,
+            horizontal_alignment: None,
+            vertical_alignment: None,
+        }
+    }
+
+    pub fn color(self, color: Color) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    pub fn size(self, size: f32) -> Self {
+        Self {
+            size: Some(size),
+            ..self
+        }
+    }
+
+    pub fn font(self, font: Font) -> Self {
+        Self {
+            font: Some(font),
+            ..self
+        }
+    }
+
+    /// Sets how the text is aligned horizontally relative to its position.
+    pub fn horizontal_alignment(self, alignment: Alignment) -> Self {
+        Self {
+            horizontal_alignment: Some(alignment),
+            ..self
+        }
+    }
+
+    /// Sets how the text is aligned vertically relative to its position.
+    pub fn vertical_alignment(self, alignment: Alignment) -> Self {
+        Self {
+            vertical_alignment: Some(alignment),
+            ..self
+        }
+    }
+}
+
+impl From<Text> for widget::v1::canvas::Text {
+    fn from(value: Text) -> Self {
+        let mut text = Self {
+            content: value.content,
+            position: Some(value.position.into()),
+            color: value.color.map(From::from),
+            size: value.size,
+            font: value.font.map(From::from),
+            horizontal_alignment: None,
+            vertical_alignment: None,
+        };
+
+        if let Some(alignment) = value.horizontal_alignment {
+            text.set_horizontal_alignment(alignment.into());
+        }
+        if let Some(alignment) = value.vertical_alignment {
+            text.set_vertical_alignment(alignment.into());
+        }
+
+        text
+    }
+}
@@ -0,0 +1,155 @@
+//! Boxes that can be checked.
+
+use std::sync::Arc;
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Length, Widget, WidgetId};
+
+/// A box that can be checked, with a label next to it.
+///
+/// If [`Checkbox::on_toggle`] isn't called, the checkbox is disabled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkbox<Msg> {
+    pub label: String,
+    pub checked: bool,
+    pub size: Option<f32>,
+    pub spacing: Option<f32>,
+    pub text_size: Option<f32>,
+    pub width: Option<Length>,
+    pub(crate) callbacks: Callbacks<Msg>,
+    pub(crate) widget_id: Option<WidgetId>,
+}
+
+impl<Msg> Checkbox<Msg> {
+    /// Creates a new [`Checkbox`].
+    pub fn new(label: impl Into<String>, checked: bool) -> Self {
+        Self {
+            label: label.into(),
+            checked,
+            size: None,
+            spacing: None,
+            text_size: None,
+            width: None,
+            callbacks: Callbacks { on_toggle: None },
+            widget_id: None,
+        }
+    }
+
+    /// Sets the message produced when the [`Checkbox`] is toggled.
+    ///
+    /// The closure receives the new checked state.
+    pub fn on_toggle<F>(self, on_toggle: F) -> Self
+    where
+        F: Fn(bool) -> Msg + Sync + Send + 'static,
+    {
+        Self {
+            widget_id: self.widget_id.or_else(|| Some(WidgetId::next())),
+            callbacks: Callbacks {
+                on_toggle: Some(Arc::new(on_toggle)),
+            },
+            ..self
+        }
+    }
+
+    /// Sets the size of the box.
+    pub fn size(self, size: f32) -> Self {
+        Self {
+            size: Some(size),
+            ..self
+        }
+    }
+
+    /// Sets the spacing between the box and the label.
+    pub fn spacing(self, spacing: f32) -> Self {
+        Self {
+            spacing: Some(spacing),
+            ..self
+        }
+    }
+
+    /// Sets the text size of the label.
+    pub fn text_size(self, text_size: f32) -> Self {
+        Self {
+            text_size: Some(text_size),
+            ..self
+        }
+    }
+
+    /// Sets the width of the [`Checkbox`].
+    pub fn width(self, width: Length) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+}
+
+impl<Msg> From<Checkbox<Msg>> for Widget<Msg> {
+    fn from(value: Checkbox<Msg>) -> Self {
+        Widget::Checkbox(Box::new(value))
+    }
+}
+
+impl<Msg> From<Checkbox<Msg>> for widget::v1::Checkbox {
+    fn from(value: Checkbox<Msg>) -> Self {
+        let Checkbox {
+            label,
+            checked,
+            size,
+            spacing,
+            text_size,
+            width,
+            callbacks,
+            widget_id,
+        } = value;
+
+        Self {
+            label,
+            checked,
+            on_toggle: callbacks.on_toggle.is_some(),
+            size,
+            spacing,
+            text_size,
+            width: width.map(From::from),
+            widget_id: widget_id.map(WidgetId::to_inner),
+        }
+    }
+}
+
+/// The [`Checkbox`] callbacks.
+#[derive(Clone)]
+pub struct Callbacks<Msg> {
+    /// Message to be sent when the [`Checkbox`] is toggled.
+    pub(crate) on_toggle: Option<Arc<dyn Fn(bool) -> Msg + Sync + Send>>,
+}
+
+impl<Msg> Callbacks<Msg> {
+    pub(crate) fn process_event(self, evt: widget::v1::checkbox::Event) -> Option<Msg> {
+        self.on_toggle.map(|handler| handler(evt.checked))
+    }
+}
+
+impl<Msg> std::fmt::Debug for Callbacks<Msg> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Callbacks")
+            .field(
+                "on_toggle",
+                &self
+                    .on_toggle
+                    .as_ref()
+                    .map_or("None", |_| "Some(OnToggleHandler)"),
+            )
+            .finish()
+    }
+}
+
+impl<Msg> PartialEq for Callbacks<Msg> {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.on_toggle, &other.on_toggle) {
+            (Some(lhs), Some(rhs)) => Arc::ptr_eq(lhs, rhs),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
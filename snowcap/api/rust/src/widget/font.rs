@@ -61,6 +61,17 @@ impl From<Font> for widget::v1::Font {
     }
 }
 
+impl From<widget::v1::Font> for Font {
+    fn from(value: widget::v1::Font) -> Self {
+        Self {
+            weight: value.weight().into(),
+            stretch: value.stretch().into(),
+            style: value.style().into(),
+            family: value.family.map(Family::from).unwrap_or_default(),
+        }
+    }
+}
+
 /// A font family.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
@@ -90,6 +101,19 @@ impl From<Family> for widget::v1::font::Family {
     }
 }
 
+impl From<widget::v1::font::Family> for Family {
+    fn from(value: widget::v1::font::Family) -> Self {
+        match value.family {
+            Some(widget::v1::font::family::Family::Name(name)) => Family::Name(name),
+            Some(widget::v1::font::family::Family::Serif(())) => Family::Serif,
+            Some(widget::v1::font::family::Family::SansSerif(())) | None => Family::SansSerif,
+            Some(widget::v1::font::family::Family::Cursive(())) => Family::Cursive,
+            Some(widget::v1::font::family::Family::Fantasy(())) => Family::Fantasy,
+            Some(widget::v1::font::family::Family::Monospace(())) => Family::Monospace,
+        }
+    }
+}
+
 /// A font weight.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
@@ -122,6 +146,23 @@ impl From<Weight> for widget::v1::font::Weight {
     }
 }
 
+impl From<widget::v1::font::Weight> for Weight {
+    fn from(value: widget::v1::font::Weight) -> Self {
+        match value {
+            widget::v1::font::Weight::Unspecified => Weight::default(),
+            widget::v1::font::Weight::Thin => Weight::Thin,
+            widget::v1::font::Weight::ExtraLight => Weight::ExtraLight,
+            widget::v1::font::Weight::Light => Weight::Light,
+            widget::v1::font::Weight::Normal => Weight::Normal,
+            widget::v1::font::Weight::Medium => Weight::Medium,
+            widget::v1::font::Weight::Semibold => Weight::Semibold,
+            widget::v1::font::Weight::Bold => Weight::Bold,
+            widget::v1::font::Weight::ExtraBold => Weight::ExtraBold,
+            widget::v1::font::Weight::Black => Weight::Black,
+        }
+    }
+}
+
 /// A font stretch.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
@@ -154,6 +195,23 @@ impl From<Stretch> for widget::v1::font::Stretch {
     }
 }
 
+impl From<widget::v1::font::Stretch> for Stretch {
+    fn from(value: widget::v1::font::Stretch) -> Self {
+        match value {
+            widget::v1::font::Stretch::Unspecified => Stretch::default(),
+            widget::v1::font::Stretch::UltraCondensed => Stretch::UltraCondensed,
+            widget::v1::font::Stretch::ExtraCondensed => Stretch::ExtraCondensed,
+            widget::v1::font::Stretch::Condensed => Stretch::Condensed,
+            widget::v1::font::Stretch::SemiCondensed => Stretch::SemiCondensed,
+            widget::v1::font::Stretch::Normal => Stretch::Normal,
+            widget::v1::font::Stretch::SemiExpanded => Stretch::SemiExpanded,
+            widget::v1::font::Stretch::Expanded => Stretch::Expanded,
+            widget::v1::font::Stretch::ExtraExpanded => Stretch::ExtraExpanded,
+            widget::v1::font::Stretch::UltraExpanded => Stretch::UltraExpanded,
+        }
+    }
+}
+
 /// A font style.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
@@ -173,3 +231,14 @@ impl From<Style> for widget::v1::font::Style {
         }
     }
 }
+
+impl From<widget::v1::font::Style> for Style {
+    fn from(value: widget::v1::font::Style) -> Self {
+        match value {
+            widget::v1::font::Style::Unspecified => Style::default(),
+            widget::v1::font::Style::Normal => Style::Normal,
+            widget::v1::font::Style::Italic => Style::Italic,
+            widget::v1::font::Style::Oblique => Style::Oblique,
+        }
+    }
+}
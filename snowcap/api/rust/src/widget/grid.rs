@@ -0,0 +1,132 @@
+//! Widgets laid out in rows of equally sized cells.
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Length, WidgetDef};
+
+/// A widget that lays out its children in rows of equally sized cells,
+/// filling each row from left to right.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<Msg> {
+    pub columns: Option<Columns>,
+    pub spacing: Option<f32>,
+    pub width: Option<f32>,
+    pub sizing: Option<Sizing>,
+    pub children: Vec<WidgetDef<Msg>>,
+}
+
+impl<Msg> Default for Grid<Msg> {
+    fn default() -> Self {
+        Self {
+            columns: Default::default(),
+            spacing: Default::default(),
+            width: Default::default(),
+            sizing: Default::default(),
+            children: Default::default(),
+        }
+    }
+}
+
+impl<Msg> Grid<Msg> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn new_with_children(children: impl IntoIterator<Item = WidgetDef<Msg>>) -> Self {
+        Self {
+            children: children.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Uses this many columns.
+    pub fn columns(self, amount: u32) -> Self {
+        Self {
+            columns: Some(Columns::Amount(amount)),
+            ..self
+        }
+    }
+
+    /// Uses as many columns as fit without cells getting wider than `max_cell_width`.
+    pub fn fluid(self, max_cell_width: f32) -> Self {
+        Self {
+            columns: Some(Columns::MaxCellWidth(max_cell_width)),
+            ..self
+        }
+    }
+
+    pub fn spacing(self, spacing: f32) -> Self {
+        Self {
+            spacing: Some(spacing),
+            ..self
+        }
+    }
+
+    pub fn width(self, width: f32) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    /// Sets how tall rows are.
+    pub fn sizing(self, sizing: Sizing) -> Self {
+        Self {
+            sizing: Some(sizing),
+            ..self
+        }
+    }
+
+    pub fn push(mut self, child: impl Into<WidgetDef<Msg>>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+}
+
+impl<Msg> From<Grid<Msg>> for widget::v1::Grid {
+    fn from(value: Grid<Msg>) -> Self {
+        widget::v1::Grid {
+            columns: value.columns.map(From::from),
+            spacing: value.spacing,
+            width: value.width,
+            sizing: value.sizing.map(From::from),
+            children: value.children.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+/// How a [`Grid`] picks its number of columns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Columns {
+    /// A fixed number of columns.
+    Amount(u32),
+    /// As many columns as fit without cells getting wider than this.
+    MaxCellWidth(f32),
+}
+
+impl From<Columns> for widget::v1::grid::Columns {
+    fn from(value: Columns) -> Self {
+        match value {
+            Columns::Amount(amount) => Self::Amount(amount),
+            Columns::MaxCellWidth(width) => Self::MaxCellWidth(width),
+        }
+    }
+}
+
+/// How tall the rows of a [`Grid`] are.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sizing {
+    /// Cells keep this width to height ratio.
+    AspectRatio(f32),
+    /// Rows evenly split this height.
+    EvenlyDistribute(Length),
+}
+
+impl From<Sizing> for widget::v1::grid::Sizing {
+    fn from(value: Sizing) -> Self {
+        match value {
+            Sizing::AspectRatio(ratio) => Self::AspectRatio(ratio),
+            Sizing::EvenlyDistribute(height) => Self::EvenlyDistribute(height.into()),
+        }
+    }
+}
@@ -117,6 +117,9 @@ impl From<Image> for widget::v1::Image {
                     height,
                     rgba: bytes,
                 }),
+                Handle::Toplevel(identifier) => {
+                    widget::v1::image::Handle::ToplevelIdentifier(identifier)
+                }
             }),
         }
     }
@@ -131,6 +134,10 @@ pub enum Handle {
         height: u32,
         bytes: Vec<u8>,
     },
+    /// The live contents of the toplevel with this ext-foreign-toplevel-list identifier.
+    ///
+    /// Nothing is drawn until the first frame is captured.
+    Toplevel(String),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
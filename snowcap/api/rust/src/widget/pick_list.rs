@@ -0,0 +1,154 @@
+//! Dropdowns for picking one of several options.
+
+use std::sync::Arc;
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Length, Padding, Widget, WidgetId};
+
+/// A dropdown that lets the user pick one of several options.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PickList<Msg> {
+    pub options: Vec<String>,
+    pub selected: Option<String>,
+    pub placeholder: Option<String>,
+    pub width: Option<Length>,
+    pub padding: Option<Padding>,
+    pub text_size: Option<f32>,
+    pub(crate) callbacks: Callbacks<Msg>,
+    pub(crate) widget_id: Option<WidgetId>,
+}
+
+impl<Msg> PickList<Msg> {
+    /// Creates a new [`PickList`] with the given options and the currently selected option.
+    pub fn new(
+        options: impl IntoIterator<Item = impl Into<String>>,
+        selected: Option<impl Into<String>>,
+    ) -> Self {
+        Self {
+            options: options.into_iter().map(Into::into).collect(),
+            selected: selected.map(Into::into),
+            placeholder: None,
+            width: None,
+            padding: None,
+            text_size: None,
+            callbacks: Callbacks { on_select: None },
+            widget_id: None,
+        }
+    }
+
+    /// Sets the message produced when an option is picked.
+    pub fn on_select<F>(self, on_select: F) -> Self
+    where
+        F: Fn(String) -> Msg + Sync + Send + 'static,
+    {
+        Self {
+            widget_id: self.widget_id.or_else(|| Some(WidgetId::next())),
+            callbacks: Callbacks {
+                on_select: Some(Arc::new(on_select)),
+            },
+            ..self
+        }
+    }
+
+    /// Sets the text shown when nothing is selected.
+    pub fn placeholder(self, placeholder: impl Into<String>) -> Self {
+        Self {
+            placeholder: Some(placeholder.into()),
+            ..self
+        }
+    }
+
+    /// Sets the width of the [`PickList`].
+    pub fn width(self, width: Length) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    /// Sets the [`Padding`] of the [`PickList`].
+    pub fn padding(self, padding: Padding) -> Self {
+        Self {
+            padding: Some(padding),
+            ..self
+        }
+    }
+
+    /// Sets the text size of the [`PickList`].
+    pub fn text_size(self, text_size: f32) -> Self {
+        Self {
+            text_size: Some(text_size),
+            ..self
+        }
+    }
+}
+
+impl<Msg> From<PickList<Msg>> for Widget<Msg> {
+    fn from(value: PickList<Msg>) -> Self {
+        Widget::PickList(Box::new(value))
+    }
+}
+
+impl<Msg> From<PickList<Msg>> for widget::v1::PickList {
+    fn from(value: PickList<Msg>) -> Self {
+        let PickList {
+            options,
+            selected,
+            placeholder,
+            width,
+            padding,
+            text_size,
+            callbacks,
+            widget_id,
+        } = value;
+
+        Self {
+            options,
+            selected,
+            placeholder,
+            on_select: callbacks.on_select.is_some(),
+            width: width.map(From::from),
+            padding: padding.map(From::from),
+            text_size,
+            widget_id: widget_id.map(WidgetId::to_inner),
+        }
+    }
+}
+
+/// The [`PickList`] callbacks.
+#[derive(Clone)]
+pub struct Callbacks<Msg> {
+    /// Message to be sent when an option is picked.
+    pub(crate) on_select: Option<Arc<dyn Fn(String) -> Msg + Sync + Send>>,
+}
+
+impl<Msg> Callbacks<Msg> {
+    pub(crate) fn process_event(self, evt: widget::v1::pick_list::Event) -> Option<Msg> {
+        self.on_select.map(|handler| handler(evt.selected))
+    }
+}
+
+impl<Msg> std::fmt::Debug for Callbacks<Msg> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Callbacks")
+            .field(
+                "on_select",
+                &self
+                    .on_select
+                    .as_ref()
+                    .map_or("None", |_| "Some(OnSelectHandler)"),
+            )
+            .finish()
+    }
+}
+
+impl<Msg> PartialEq for Callbacks<Msg> {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.on_select, &other.on_select) {
+            (Some(lhs), Some(rhs)) => Arc::ptr_eq(lhs, rhs),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
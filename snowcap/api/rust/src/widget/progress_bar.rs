@@ -0,0 +1,122 @@
+//! Bars that show progress.
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Background, Border, Length};
+
+/// A bar that fills up as `value` goes from `min` to `max`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressBar {
+    pub min: f32,
+    pub max: f32,
+    pub value: f32,
+    pub length: Option<Length>,
+    pub girth: Option<Length>,
+    pub vertical: bool,
+    pub style: Option<Style>,
+}
+
+impl ProgressBar {
+    /// Creates a new [`ProgressBar`] over `min..=max` filled up to `value`.
+    pub fn new(min: f32, max: f32, value: f32) -> Self {
+        Self {
+            min,
+            max,
+            value,
+            length: None,
+            girth: None,
+            vertical: false,
+            style: None,
+        }
+    }
+
+    /// Sets the length of the [`ProgressBar`] along the direction it fills.
+    pub fn length(self, length: Length) -> Self {
+        Self {
+            length: Some(length),
+            ..self
+        }
+    }
+
+    /// Sets the thickness of the [`ProgressBar`].
+    pub fn girth(self, girth: Length) -> Self {
+        Self {
+            girth: Some(girth),
+            ..self
+        }
+    }
+
+    /// Makes the [`ProgressBar`] fill from bottom to top.
+    pub fn vertical(self) -> Self {
+        Self {
+            vertical: true,
+            ..self
+        }
+    }
+
+    /// Sets the style of the [`ProgressBar`].
+    pub fn style(self, style: Style) -> Self {
+        Self {
+            style: Some(style),
+            ..self
+        }
+    }
+}
+
+impl From<ProgressBar> for widget::v1::ProgressBar {
+    fn from(value: ProgressBar) -> Self {
+        Self {
+            min: value.min,
+            max: value.max,
+            value: value.value,
+            length: value.length.map(From::from),
+            girth: value.girth.map(From::from),
+            vertical: value.vertical,
+            style: value.style.map(From::from),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Style {
+    pub background: Option<Background>,
+    pub bar: Option<Background>,
+    pub border: Option<Border>,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn background(self, background: impl Into<Background>) -> Self {
+        Self {
+            background: Some(background.into()),
+            ..self
+        }
+    }
+
+    pub fn bar(self, bar: impl Into<Background>) -> Self {
+        Self {
+            bar: Some(bar.into()),
+            ..self
+        }
+    }
+
+    pub fn border(self, border: Border) -> Self {
+        Self {
+            border: Some(border),
+            ..self
+        }
+    }
+}
+
+impl From<Style> for widget::v1::progress_bar::Style {
+    fn from(value: Style) -> Self {
+        Self {
+            background: value.background.map(From::from),
+            bar: value.bar.map(From::from),
+            border: value.border.map(From::from),
+        }
+    }
+}
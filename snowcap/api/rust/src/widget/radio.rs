@@ -0,0 +1,96 @@
+//! Radio buttons.
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Length, Widget, WidgetId};
+
+/// A radio button with a label next to it.
+///
+/// Each radio button stands on its own. To make a group, create one radio button per choice
+/// and mark the one matching your program's state as selected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Radio<Msg> {
+    pub label: String,
+    pub selected: bool,
+    pub size: Option<f32>,
+    pub spacing: Option<f32>,
+    pub text_size: Option<f32>,
+    pub width: Option<Length>,
+    pub(crate) on_select: Option<(WidgetId, Msg)>,
+}
+
+impl<Msg> Radio<Msg> {
+    /// Creates a new [`Radio`].
+    pub fn new(label: impl Into<String>, selected: bool) -> Self {
+        Self {
+            label: label.into(),
+            selected,
+            size: None,
+            spacing: None,
+            text_size: None,
+            width: None,
+            on_select: None,
+        }
+    }
+
+    /// Sets the message produced when the [`Radio`] is clicked.
+    pub fn on_select(self, on_select: Msg) -> Self {
+        Self {
+            on_select: Some((WidgetId::next(), on_select)),
+            ..self
+        }
+    }
+
+    /// Sets the size of the button.
+    pub fn size(self, size: f32) -> Self {
+        Self {
+            size: Some(size),
+            ..self
+        }
+    }
+
+    /// Sets the spacing between the button and the label.
+    pub fn spacing(self, spacing: f32) -> Self {
+        Self {
+            spacing: Some(spacing),
+            ..self
+        }
+    }
+
+    /// Sets the text size of the label.
+    pub fn text_size(self, text_size: f32) -> Self {
+        Self {
+            text_size: Some(text_size),
+            ..self
+        }
+    }
+
+    /// Sets the width of the [`Radio`].
+    pub fn width(self, width: Length) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+}
+
+impl<Msg> From<Radio<Msg>> for Widget<Msg> {
+    fn from(value: Radio<Msg>) -> Self {
+        Widget::Radio(Box::new(value))
+    }
+}
+
+impl<Msg> From<Radio<Msg>> for widget::v1::Radio {
+    fn from(value: Radio<Msg>) -> Self {
+        Self {
+            label: value.label,
+            selected: value.selected,
+            on_select: value.on_select.is_some(),
+            size: value.size,
+            spacing: value.spacing,
+            text_size: value.text_size,
+            width: value.width.map(From::from),
+            widget_id: value.on_select.map(|(id, _)| id.to_inner()),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Lines for dividing content.
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Color, Radius};
+
+/// A horizontal or vertical line for dividing content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rule {
+    pub vertical: bool,
+    pub thickness: f32,
+    pub style: Option<Style>,
+}
+
+impl Rule {
+    /// Creates a horizontal rule with the given height.
+    pub fn horizontal(thickness: f32) -> Self {
+        Self {
+            vertical: false,
+            thickness,
+            style: None,
+        }
+    }
+
+    /// Creates a vertical rule with the given width.
+    pub fn vertical(thickness: f32) -> Self {
+        Self {
+            vertical: true,
+            thickness,
+            style: None,
+        }
+    }
+
+    pub fn style(self, style: Style) -> Self {
+        Self {
+            style: Some(style),
+            ..self
+        }
+    }
+}
+
+impl From<Rule> for widget::v1::Rule {
+    fn from(value: Rule) -> Self {
+        widget::v1::Rule {
+            vertical: value.vertical,
+            thickness: value.thickness,
+            style: value.style.map(From::from),
+        }
+    }
+}
+
+/// The appearance of a [`Rule`].
+///
+/// Unset fields use the theme's defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Style {
+    pub color: Option<Color>,
+    pub radius: Option<Radius>,
+    pub fill_mode: Option<FillMode>,
+    /// Whether to snap the line to the pixel grid.
+    pub snap: Option<bool>,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn color(self, color: Color) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    pub fn radius(self, radius: Radius) -> Self {
+        Self {
+            radius: Some(radius),
+            ..self
+        }
+    }
+
+    pub fn fill_mode(self, fill_mode: FillMode) -> Self {
+        Self {
+            fill_mode: Some(fill_mode),
+            ..self
+        }
+    }
+
+    pub fn snap(self, snap: bool) -> Self {
+        Self {
+            snap: Some(snap),
+            ..self
+        }
+    }
+}
+
+impl From<Style> for widget::v1::rule::Style {
+    fn from(value: Style) -> Self {
+        widget::v1::rule::Style {
+            color: value.color.map(From::from),
+            radius: value.radius.map(From::from),
+            fill_mode: value.fill_mode.map(From::from),
+            snap: value.snap,
+        }
+    }
+}
+
+/// How much of the available length a [`Rule`] covers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FillMode {
+    /// The whole length.
+    Full,
+    /// A percentage of the length, from 0 to 100. The line is centered.
+    Percent(f32),
+    /// The length minus the same offset from both ends.
+    Padded(u32),
+    /// The length minus different offsets from the top or left end
+    /// and the bottom or right end.
+    AsymmetricPadding(u32, u32),
+}
+
+impl From<FillMode> for widget::v1::rule::FillMode {
+    fn from(value: FillMode) -> Self {
+        use widget::v1::rule::fill_mode;
+
+        let fill_mode = match value {
+            FillMode::Full => fill_mode::FillMode::Full(()),
+            FillMode::Percent(percent) => fill_mode::FillMode::Percent(percent),
+            FillMode::Padded(padding) => fill_mode::FillMode::Padded(padding),
+            FillMode::AsymmetricPadding(start, end) => {
+                fill_mode::FillMode::AsymmetricPadding(widget::v1::rule::AsymmetricPadding {
+                    start,
+                    end,
+                })
+            }
+        };
+
+        Self {
+            fill_mode: Some(fill_mode),
+        }
+    }
+}
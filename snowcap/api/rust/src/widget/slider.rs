@@ -0,0 +1,353 @@
+//! Sliders that let the user pick a value from a range.
+
+use std::sync::Arc;
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Length, Widget, WidgetId};
+
+/// A horizontal bar with a handle that selects a value from a range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slider<Msg> {
+    pub min: f32,
+    pub max: f32,
+    pub value: f32,
+    pub step: Option<f32>,
+    pub shift_step: Option<f32>,
+    pub default: Option<f32>,
+    pub width: Option<Length>,
+    pub height: Option<f32>,
+    pub(crate) callbacks: Callbacks<Msg>,
+    pub(crate) widget_id: Option<WidgetId>,
+}
+
+impl<Msg> Slider<Msg> {
+    /// Creates a new [`Slider`] over `min..=max` with the handle at `value`.
+    pub fn new(min: f32, max: f32, value: f32) -> Self {
+        Self {
+            min,
+            max,
+            value,
+            step: None,
+            shift_step: None,
+            default: None,
+            width: None,
+            height: None,
+            callbacks: Callbacks::default(),
+            widget_id: None,
+        }
+    }
+
+    /// Sets the step size of the [`Slider`].
+    pub fn step(self, step: f32) -> Self {
+        Self {
+            step: Some(step),
+            ..self
+        }
+    }
+
+    /// Sets the step size used while shift is held.
+    pub fn shift_step(self, shift_step: f32) -> Self {
+        Self {
+            shift_step: Some(shift_step),
+            ..self
+        }
+    }
+
+    /// Sets the value the [`Slider`] resets to when ctrl-clicked.
+    pub fn default(self, default: f32) -> Self {
+        Self {
+            default: Some(default),
+            ..self
+        }
+    }
+
+    /// Sets the width of the [`Slider`].
+    pub fn width(self, width: Length) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    /// Sets the height of the [`Slider`] in pixels.
+    pub fn height(self, height: f32) -> Self {
+        Self {
+            height: Some(height),
+            ..self
+        }
+    }
+
+    /// Sets the message produced when the handle is dragged.
+    pub fn on_change<F>(self, on_change: F) -> Self
+    where
+        F: Fn(f32) -> Msg + Sync + Send + 'static,
+    {
+        Self {
+            widget_id: self.widget_id.or_else(|| Some(WidgetId::next())),
+            callbacks: Callbacks {
+                on_change: Some(Arc::new(on_change)),
+                ..self.callbacks
+            },
+            ..self
+        }
+    }
+
+    /// Sets the message produced when the handle is released.
+    pub fn on_release(self, on_release: Msg) -> Self {
+        Self {
+            widget_id: self.widget_id.or_else(|| Some(WidgetId::next())),
+            callbacks: Callbacks {
+                on_release: Some(on_release),
+                ..self.callbacks
+            },
+            ..self
+        }
+    }
+}
+
+impl<Msg> From<Slider<Msg>> for Widget<Msg> {
+    fn from(value: Slider<Msg>) -> Self {
+        Widget::Slider(Box::new(value))
+    }
+}
+
+impl<Msg> From<Slider<Msg>> for widget::v1::Slider {
+    fn from(value: Slider<Msg>) -> Self {
+        let Slider {
+            min,
+            max,
+            value,
+            step,
+            shift_step,
+            default,
+            width,
+            height,
+            callbacks,
+            widget_id,
+        } = value;
+
+        Self {
+            min,
+            max,
+            value,
+            step,
+            shift_step,
+            default_value: default,
+            width: width.map(From::from),
+            height,
+            on_change: callbacks.on_change.is_some(),
+            on_release: callbacks.on_release.is_some(),
+            widget_id: widget_id.map(WidgetId::to_inner),
+        }
+    }
+}
+
+/// A vertical bar with a handle that selects a value from a range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerticalSlider<Msg> {
+    pub min: f32,
+    pub max: f32,
+    pub value: f32,
+    pub step: Option<f32>,
+    pub shift_step: Option<f32>,
+    pub default: Option<f32>,
+    pub width: Option<f32>,
+    pub height: Option<Length>,
+    pub(crate) callbacks: Callbacks<Msg>,
+    pub(crate) widget_id: Option<WidgetId>,
+}
+
+impl<Msg> VerticalSlider<Msg> {
+    /// Creates a new [`VerticalSlider`] over `min..=max` with the handle at `value`.
+    pub fn new(min: f32, max: f32, value: f32) -> Self {
+        Self {
+            min,
+            max,
+            value,
+            step: None,
+            shift_step: None,
+            default: None,
+            width: None,
+            height: None,
+            callbacks: Callbacks::default(),
+            widget_id: None,
+        }
+    }
+
+    /// Sets the step size of the [`VerticalSlider`].
+    pub fn step(self, step: f32) -> Self {
+        Self {
+            step: Some(step),
+            ..self
+        }
+    }
+
+    /// Sets the step size used while shift is held.
+    pub fn shift_step(self, shift_step: f32) -> Self {
+        Self {
+            shift_step: Some(shift_step),
+            ..self
+        }
+    }
+
+    /// Sets the value the [`VerticalSlider`] resets to when ctrl-clicked.
+    pub fn default(self, default: f32) -> Self {
+        Self {
+            default: Some(default),
+            ..self
+        }
+    }
+
+    /// Sets the width of the [`VerticalSlider`] in pixels.
+    pub fn width(self, width: f32) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    /// Sets the height of the [`VerticalSlider`].
+    pub fn height(self, height: Length) -> Self {
+        Self {
+            height: Some(height),
+            ..self
+        }
+    }
+
+    /// Sets the message produced when the handle is dragged.
+    pub fn on_change<F>(self, on_change: F) -> Self
+    where
+        F: Fn(f32) -> Msg + Sync + Send + 'static,
+    {
+        Self {
+            widget_id: self.widget_id.or_else(|| Some(WidgetId::next())),
+            callbacks: Callbacks {
+                on_change: Some(Arc::new(on_change)),
+                ..self.callbacks
+            },
+            ..self
+        }
+    }
+
+    /// Sets the message produced when the handle is released.
+    pub fn on_release(self, on_release: Msg) -> Self {
+        Self {
+            widget_id: self.widget_id.or_else(|| Some(WidgetId::next())),
+            callbacks: Callbacks {
+                on_release: Some(on_release),
+                ..self.callbacks
+            },
+            ..self
+        }
+    }
+}
+
+impl<Msg> From<VerticalSlider<Msg>> for Widget<Msg> {
+    fn from(value: VerticalSlider<Msg>) -> Self {
+        Widget::VerticalSlider(Box::new(value))
+    }
+}
+
+impl<Msg> From<VerticalSlider<Msg>> for widget::v1::VerticalSlider {
+    fn from(value: VerticalSlider<Msg>) -> Self {
+        let VerticalSlider {
+            min,
+            max,
+            value,
+            step,
+            shift_step,
+            default,
+            width,
+            height,
+            callbacks,
+            widget_id,
+        } = value;
+
+        Self {
+            min,
+            max,
+            value,
+            step,
+            shift_step,
+            default_value: default,
+            width,
+            height: height.map(From::from),
+            on_change: callbacks.on_change.is_some(),
+            on_release: callbacks.on_release.is_some(),
+            widget_id: widget_id.map(WidgetId::to_inner),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Event {
+    Change(f32),
+    Release,
+}
+
+impl From<widget::v1::slider::Event> for Event {
+    fn from(value: widget::v1::slider::Event) -> Self {
+        use widget::v1::slider::event::Data;
+
+        let data = value.data.expect("Invalid EventType");
+        match data {
+            Data::Change(value) => Self::Change(value),
+            Data::Release(()) => Self::Release,
+        }
+    }
+}
+
+/// The [`Slider`] and [`VerticalSlider`] callbacks.
+#[derive(Clone)]
+pub struct Callbacks<Msg> {
+    /// Message to be sent when the handle is dragged.
+    pub(crate) on_change: Option<Arc<dyn Fn(f32) -> Msg + Sync + Send>>,
+    /// Message to be sent when the handle is released.
+    pub(crate) on_release: Option<Msg>,
+}
+
+impl<Msg> Default for Callbacks<Msg> {
+    fn default() -> Self {
+        Self {
+            on_change: None,
+            on_release: None,
+        }
+    }
+}
+
+impl<Msg> Callbacks<Msg> {
+    pub(crate) fn process_event(self, evt: Event) -> Option<Msg> {
+        match evt {
+            Event::Change(value) => self.on_change.map(|handler| handler(value)),
+            Event::Release => self.on_release,
+        }
+    }
+}
+
+impl<Msg: std::fmt::Debug> std::fmt::Debug for Callbacks<Msg> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Callbacks")
+            .field(
+                "on_change",
+                &self
+                    .on_change
+                    .as_ref()
+                    .map_or("None", |_| "Some(OnChangeHandler)"),
+            )
+            .field("on_release", &self.on_release)
+            .finish()
+    }
+}
+
+impl<Msg: PartialEq> PartialEq for Callbacks<Msg> {
+    fn eq(&self, other: &Self) -> bool {
+        let on_change_eq = match (&self.on_change, &other.on_change) {
+            (Some(lhs), Some(rhs)) => Arc::ptr_eq(lhs, rhs),
+            (None, None) => true,
+            _ => false,
+        };
+
+        on_change_eq && self.on_release == other.on_release
+    }
+}
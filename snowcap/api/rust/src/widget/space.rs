@@ -0,0 +1,51 @@
+//! Empty space.
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::Length;
+
+/// An empty widget that takes up space.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Space {
+    pub width: Option<Length>,
+    pub height: Option<Length>,
+}
+
+impl Space {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates space that fills the available width.
+    pub fn horizontal() -> Self {
+        Self::new().width(Length::Fill)
+    }
+
+    /// Creates space that fills the available height.
+    pub fn vertical() -> Self {
+        Self::new().height(Length::Fill)
+    }
+
+    pub fn width(self, width: Length) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    pub fn height(self, height: Length) -> Self {
+        Self {
+            height: Some(height),
+            ..self
+        }
+    }
+}
+
+impl From<Space> for widget::v1::Space {
+    fn from(value: Space) -> Self {
+        widget::v1::Space {
+            width: value.width.map(From::from),
+            height: value.height.map(From::from),
+        }
+    }
+}
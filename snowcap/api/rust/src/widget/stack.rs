@@ -0,0 +1,78 @@
+//! Widgets layered on top of each other.
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Length, WidgetDef};
+
+/// A widget that layers its children on top of each other.
+///
+/// Later children are drawn on top of earlier ones.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stack<Msg> {
+    pub width: Option<Length>,
+    pub height: Option<Length>,
+    pub clip: Option<bool>,
+    pub children: Vec<WidgetDef<Msg>>,
+}
+
+impl<Msg> Default for Stack<Msg> {
+    fn default() -> Self {
+        Self {
+            width: Default::default(),
+            height: Default::default(),
+            clip: Default::default(),
+            children: Default::default(),
+        }
+    }
+}
+
+impl<Msg> Stack<Msg> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn new_with_children(children: impl IntoIterator<Item = WidgetDef<Msg>>) -> Self {
+        Self {
+            children: children.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn width(self, width: Length) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    pub fn height(self, height: Length) -> Self {
+        Self {
+            height: Some(height),
+            ..self
+        }
+    }
+
+    pub fn clip(self, clip: bool) -> Self {
+        Self {
+            clip: Some(clip),
+            ..self
+        }
+    }
+
+    /// Adds a child on top of the others.
+    pub fn push(mut self, child: impl Into<WidgetDef<Msg>>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+}
+
+impl<Msg> From<Stack<Msg>> for widget::v1::Stack {
+    fn from(value: Stack<Msg>) -> Self {
+        widget::v1::Stack {
+            width: value.width.map(From::from),
+            height: value.height.map(From::from),
+            clip: value.clip,
+            children: value.children.into_iter().map(From::from).collect(),
+        }
+    }
+}
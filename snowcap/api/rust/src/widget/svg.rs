@@ -0,0 +1,107 @@
+//! Vector images.
+
+use std::path::PathBuf;
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Color, Length, image::ContentFit};
+
+/// An svg image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Svg {
+    pub handle: Handle,
+    pub width: Option<Length>,
+    pub height: Option<Length>,
+    pub content_fit: Option<ContentFit>,
+    /// Draws the svg in this color, for symbolic icons.
+    pub color: Option<Color>,
+    /// Rotation in degrees.
+    pub rotation: Option<f32>,
+    pub opacity: Option<f32>,
+}
+
+impl Svg {
+    pub fn new(handle: Handle) -> Self {
+        Self {
+            handle,
+            width: None,
+            height: None,
+            content_fit: None,
+            color: None,
+            rotation: None,
+            opacity: None,
+        }
+    }
+
+    pub fn width(self, width: Length) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    pub fn height(self, height: Length) -> Self {
+        Self {
+            height: Some(height),
+            ..self
+        }
+    }
+
+    pub fn content_fit(self, content_fit: ContentFit) -> Self {
+        Self {
+            content_fit: Some(content_fit),
+            ..self
+        }
+    }
+
+    /// Draws every part of the svg in `color`.
+    ///
+    /// This is meant for symbolic icons that should follow the text color.
+    pub fn color(self, color: Color) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    pub fn rotation(self, degrees: f32) -> Self {
+        Self {
+            rotation: Some(degrees),
+            ..self
+        }
+    }
+
+    pub fn opacity(self, opacity: f32) -> Self {
+        Self {
+            opacity: Some(opacity),
+            ..self
+        }
+    }
+}
+
+impl From<Svg> for widget::v1::Svg {
+    fn from(value: Svg) -> Self {
+        Self {
+            width: value.width.map(From::from),
+            height: value.height.map(From::from),
+            content_fit: value
+                .content_fit
+                .map(|c| widget::v1::image::ContentFit::from(c) as i32),
+            color: value.color.map(From::from),
+            rotation_degrees: value.rotation,
+            opacity: value.opacity,
+            handle: Some(match value.handle {
+                Handle::Path(path_buf) => {
+                    widget::v1::svg::Handle::Path(path_buf.to_string_lossy().to_string())
+                }
+                Handle::Bytes(bytes) => widget::v1::svg::Handle::Bytes(bytes),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Handle {
+    Path(PathBuf),
+    Bytes(Vec<u8>),
+}
@@ -0,0 +1,163 @@
+//! Switches that can be toggled on and off.
+
+use std::sync::Arc;
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Length, Widget, WidgetId};
+
+/// A switch that can be toggled, with an optional label next to it.
+///
+/// If [`Toggler::on_toggle`] isn't called, the toggler is disabled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toggler<Msg> {
+    pub label: Option<String>,
+    pub toggled: bool,
+    pub size: Option<f32>,
+    pub spacing: Option<f32>,
+    pub text_size: Option<f32>,
+    pub width: Option<Length>,
+    pub(crate) callbacks: Callbacks<Msg>,
+    pub(crate) widget_id: Option<WidgetId>,
+}
+
+impl<Msg> Toggler<Msg> {
+    /// Creates a new [`Toggler`].
+    pub fn new(toggled: bool) -> Self {
+        Self {
+            label: None,
+            toggled,
+            size: None,
+            spacing: None,
+            text_size: None,
+            width: None,
+            callbacks: Callbacks { on_toggle: None },
+            widget_id: None,
+        }
+    }
+
+    /// Sets the label of the [`Toggler`].
+    pub fn label(self, label: impl Into<String>) -> Self {
+        Self {
+            label: Some(label.into()),
+            ..self
+        }
+    }
+
+    /// Sets the message produced when the [`Toggler`] is toggled.
+    ///
+    /// The closure receives the new toggled state.
+    pub fn on_toggle<F>(self, on_toggle: F) -> Self
+    where
+        F: Fn(bool) -> Msg + Sync + Send + 'static,
+    {
+        Self {
+            widget_id: self.widget_id.or_else(|| Some(WidgetId::next())),
+            callbacks: Callbacks {
+                on_toggle: Some(Arc::new(on_toggle)),
+            },
+            ..self
+        }
+    }
+
+    /// Sets the height of the switch.
+    pub fn size(self, size: f32) -> Self {
+        Self {
+            size: Some(size),
+            ..self
+        }
+    }
+
+    /// Sets the spacing between the switch and the label.
+    pub fn spacing(self, spacing: f32) -> Self {
+        Self {
+            spacing: Some(spacing),
+            ..self
+        }
+    }
+
+    /// Sets the text size of the label.
+    pub fn text_size(self, text_size: f32) -> Self {
+        Self {
+            text_size: Some(text_size),
+            ..self
+        }
+    }
+
+    /// Sets the width of the [`Toggler`].
+    pub fn width(self, width: Length) -> Self {
+        Self {
+            width: Some(width),
+            ..self
+        }
+    }
+}
+
+impl<Msg> From<Toggler<Msg>> for Widget<Msg> {
+    fn from(value: Toggler<Msg>) -> Self {
+        Widget::Toggler(Box::new(value))
+    }
+}
+
+impl<Msg> From<Toggler<Msg>> for widget::v1::Toggler {
+    fn from(value: Toggler<Msg>) -> Self {
+        let Toggler {
+            label,
+            toggled,
+            size,
+            spacing,
+            text_size,
+            width,
+            callbacks,
+            widget_id,
+        } = value;
+
+        Self {
+            label,
+            toggled,
+            on_toggle: callbacks.on_toggle.is_some(),
+            size,
+            spacing,
+            text_size,
+            width: width.map(From::from),
+            widget_id: widget_id.map(WidgetId::to_inner),
+        }
+    }
+}
+
+/// The [`Toggler`] callbacks.
+#[derive(Clone)]
+pub struct Callbacks<Msg> {
+    /// Message to be sent when the [`Toggler`] is toggled.
+    pub(crate) on_toggle: Option<Arc<dyn Fn(bool) -> Msg + Sync + Send>>,
+}
+
+impl<Msg> Callbacks<Msg> {
+    pub(crate) fn process_event(self, evt: widget::v1::toggler::Event) -> Option<Msg> {
+        self.on_toggle.map(|handler| handler(evt.toggled))
+    }
+}
+
+impl<Msg> std::fmt::Debug for Callbacks<Msg> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Callbacks")
+            .field(
+                "on_toggle",
+                &self
+                    .on_toggle
+                    .as_ref()
+                    .map_or("None", |_| "Some(OnToggleHandler)"),
+            )
+            .finish()
+    }
+}
+
+impl<Msg> PartialEq for Callbacks<Msg> {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.on_toggle, &other.on_toggle) {
+            (Some(lhs), Some(rhs)) => Arc::ptr_eq(lhs, rhs),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
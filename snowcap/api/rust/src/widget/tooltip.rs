@@ -0,0 +1,126 @@
+//! Hints shown while hovering a widget.
+
+use std::time::Duration;
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Widget, WidgetDef, container};
+
+/// A widget that shows a hint next to its content while the content is hovered.
+///
+/// The hint is drawn inside the surface, so it may be cut off on small surfaces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tooltip<Msg> {
+    pub content: WidgetDef<Msg>,
+    pub tooltip: WidgetDef<Msg>,
+    pub position: Position,
+    /// The space between the content and the hint.
+    pub gap: Option<f32>,
+    pub padding: Option<f32>,
+    /// How long the content needs to be hovered before the hint shows.
+    pub delay: Option<Duration>,
+    /// Whether to keep the hint inside the surface.
+    pub snap_within_viewport: Option<bool>,
+    pub style: Option<container::Style>,
+}
+
+impl<Msg> Tooltip<Msg> {
+    pub fn new(
+        content: impl Into<WidgetDef<Msg>>,
+        tooltip: impl Into<WidgetDef<Msg>>,
+        position: Position,
+    ) -> Self {
+        Self {
+            content: content.into(),
+            tooltip: tooltip.into(),
+            position,
+            gap: None,
+            padding: None,
+            delay: None,
+            snap_within_viewport: None,
+            style: None,
+        }
+    }
+
+    pub fn gap(self, gap: f32) -> Self {
+        Self {
+            gap: Some(gap),
+            ..self
+        }
+    }
+
+    pub fn padding(self, padding: f32) -> Self {
+        Self {
+            padding: Some(padding),
+            ..self
+        }
+    }
+
+    pub fn delay(self, delay: Duration) -> Self {
+        Self {
+            delay: Some(delay),
+            ..self
+        }
+    }
+
+    pub fn snap_within_viewport(self, snap: bool) -> Self {
+        Self {
+            snap_within_viewport: Some(snap),
+            ..self
+        }
+    }
+
+    pub fn style(self, style: container::Style) -> Self {
+        Self {
+            style: Some(style),
+            ..self
+        }
+    }
+}
+
+impl<Msg> From<Tooltip<Msg>> for Widget<Msg> {
+    fn from(value: Tooltip<Msg>) -> Self {
+        Self::Tooltip(Box::new(value))
+    }
+}
+
+impl<Msg> From<Tooltip<Msg>> for widget::v1::Tooltip {
+    fn from(value: Tooltip<Msg>) -> Self {
+        widget::v1::Tooltip {
+            content: Some(Box::new(value.content.into())),
+            tooltip: Some(Box::new(value.tooltip.into())),
+            position: widget::v1::tooltip::Position::from(value.position) as i32,
+            gap: value.gap,
+            padding: value.padding,
+            delay_millis: value
+                .delay
+                .map(|delay| delay.as_millis().try_into().unwrap_or(u32::MAX)),
+            snap_within_viewport: value.snap_within_viewport,
+            style: value.style.map(From::from),
+        }
+    }
+}
+
+/// Where a [`Tooltip`]'s hint appears.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum Position {
+    #[default]
+    Top,
+    Bottom,
+    Left,
+    Right,
+    /// The hint follows the cursor.
+    FollowCursor,
+}
+
+impl From<Position> for widget::v1::tooltip::Position {
+    fn from(value: Position) -> Self {
+        match value {
+            Position::Top => Self::Top,
+            Position::Bottom => Self::Bottom,
+            Position::Left => Self::Left,
+            Position::Right => Self::Right,
+            Position::FollowCursor => Self::FollowCursor,
+        }
+    }
+}
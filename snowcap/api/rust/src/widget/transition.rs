@@ -0,0 +1,173 @@
+//! Animations that run in snowcap.
+
+use std::time::Duration;
+
+use snowcap_api_defs::snowcap::widget;
+
+use super::{Widget, WidgetDef};
+
+/// A widget that animates its child into place.
+///
+/// The child starts out faded, offset, and scaled by the `from_` fields
+/// and eases into its normal state over `duration`. The animation runs in snowcap,
+/// so the program doesn't need to send a new view every frame.
+///
+/// Layout is not affected; the child is only drawn elsewhere.
+/// Canvases are not faded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition<Msg> {
+    pub child: WidgetDef<Msg>,
+    /// Transitions with the same key keep running across view updates instead
+    /// of starting over. Changing anything else about the transition restarts it.
+    pub key: Option<String>,
+    pub duration: Duration,
+    pub delay: Option<Duration>,
+    pub easing: Easing,
+    /// The opacity to start from, from 0.0 to 1.0.
+    pub from_opacity: Option<f32>,
+    /// The offset to start from.
+    pub from_offset: Option<(f32, f32)>,
+    /// The scale to start from.
+    pub from_scale: Option<f32>,
+    /// Whether to animate from the normal state to the `from_` state instead.
+    pub reverse: bool,
+    pub repeat_forever: bool,
+    /// Whether repeating goes back and forth instead of jumping to the start.
+    pub auto_reverse: bool,
+}
+
+impl<Msg> Transition<Msg> {
+    pub fn new(child: impl Into<WidgetDef<Msg>>, duration: Duration) -> Self {
+        Self {
+            child: child.into(),
+            key: None,
+            duration,
+            delay: None,
+            easing: Easing::default(),
+            from_opacity: None,
+            from_offset: None,
+            from_scale: None,
+            reverse: false,
+            repeat_forever: false,
+            auto_reverse: false,
+        }
+    }
+
+    /// Creates a transition that fades its child in.
+    pub fn fade_in(child: impl Into<WidgetDef<Msg>>, duration: Duration) -> Self {
+        Self::new(child, duration).from_opacity(0.0)
+    }
+
+    /// Creates a transition that fades its child out.
+    pub fn fade_out(child: impl Into<WidgetDef<Msg>>, duration: Duration) -> Self {
+        Self::fade_in(child, duration).reverse()
+    }
+
+    pub fn key(self, key: impl Into<String>) -> Self {
+        Self {
+            key: Some(key.into()),
+            ..self
+        }
+    }
+
+    pub fn delay(self, delay: Duration) -> Self {
+        Self {
+            delay: Some(delay),
+            ..self
+        }
+    }
+
+    pub fn easing(self, easing: Easing) -> Self {
+        Self { easing, ..self }
+    }
+
+    pub fn from_opacity(self, opacity: f32) -> Self {
+        Self {
+            from_opacity: Some(opacity),
+            ..self
+        }
+    }
+
+    pub fn from_offset(self, x: f32, y: f32) -> Self {
+        Self {
+            from_offset: Some((x, y)),
+            ..self
+        }
+    }
+
+    pub fn from_scale(self, scale: f32) -> Self {
+        Self {
+            from_scale: Some(scale),
+            ..self
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        Self {
+            reverse: true,
+            ..self
+        }
+    }
+
+    pub fn repeat_forever(self) -> Self {
+        Self {
+            repeat_forever: true,
+            ..self
+        }
+    }
+
+    pub fn auto_reverse(self) -> Self {
+        Self {
+            auto_reverse: true,
+            ..self
+        }
+    }
+}
+
+impl<Msg> From<Transition<Msg>> for Widget<Msg> {
+    fn from(value: Transition<Msg>) -> Self {
+        Self::Transition(Box::new(value))
+    }
+}
+
+impl<Msg> From<Transition<Msg>> for widget::v1::Transition {
+    fn from(value: Transition<Msg>) -> Self {
+        widget::v1::Transition {
+            child: Some(Box::new(value.child.into())),
+            key: value.key,
+            duration_millis: value.duration.as_millis().try_into().unwrap_or(u32::MAX),
+            delay_millis: value
+                .delay
+                .map(|delay| delay.as_millis().try_into().unwrap_or(u32::MAX)),
+            easing: widget::v1::transition::Easing::from(value.easing) as i32,
+            from_opacity: value.from_opacity,
+            from_offset_x: value.from_offset.map(|(x, _)| x),
+            from_offset_y: value.from_offset.map(|(_, y)| y),
+            from_scale: value.from_scale,
+            reverse: value.reverse,
+            repeat_forever: value.repeat_forever,
+            auto_reverse: value.auto_reverse,
+        }
+    }
+}
+
+/// How a [`Transition`] speeds up and slows down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    #[default]
+    EaseInOut,
+}
+
+impl From<Easing> for widget::v1::transition::Easing {
+    fn from(value: Easing) -> Self {
+        match value {
+            Easing::Linear => Self::Linear,
+            Easing::EaseIn => Self::EaseIn,
+            Easing::EaseOut => Self::EaseOut,
+            Easing::EaseInOut => Self::EaseInOut,
+        }
+    }
+}
@@ -41,6 +41,18 @@ pub mod snowcap {
         }
     }
 
+    pub mod theme {
+        pub mod v1 {
+            tonic::include_proto!("snowcap.theme.v1");
+        }
+    }
+
+    pub mod clipboard {
+        pub mod v1 {
+            tonic::include_proto!("snowcap.clipboard.v1");
+        }
+    }
+
     pub mod operation {
         pub mod v1 {
             tonic::include_proto!("snowcap.operation.v1");
@@ -1,8 +1,10 @@
+pub mod clipboard;
 pub mod decoration;
 pub mod input;
 pub mod layer;
 pub mod operation;
 pub mod popup;
+pub mod theme;
 pub mod widget;
 
 use std::pin::Pin;
@@ -35,14 +37,28 @@ where
     F: FnOnce(&mut State) -> Result<T, Status> + Send + 'static,
     T: Send + 'static,
 {
-    let (sender, receiver) = tokio::sync::oneshot::channel::<Result<T, Status>>();
-
-    let f = Box::new(|state: &mut State| {
+    run_unary_deferred(fn_sender, |state, sender| {
         // TODO: find a way to handle this error
         if sender.send(with_state(state)).is_err() {
             warn!("failed to send result of API call to config; receiver already dropped");
         }
-    });
+    })
+    .await
+}
+
+/// Like [`run_unary`], but the response is sent through the given sender,
+/// so it can wait on something else in the event loop.
+async fn run_unary_deferred<F, T>(
+    fn_sender: &StateFnSender,
+    with_state: F,
+) -> Result<Response<T>, Status>
+where
+    F: FnOnce(&mut State, tokio::sync::oneshot::Sender<Result<T, Status>>) + Send + 'static,
+    T: Send + 'static,
+{
+    let (sender, receiver) = tokio::sync::oneshot::channel::<Result<T, Status>>();
+
+    let f = Box::new(|state: &mut State| with_state(state, sender));
 
     fn_sender
         .send(f)
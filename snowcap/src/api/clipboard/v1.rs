@@ -0,0 +1,13 @@
+use iced_wgpu::core::clipboard::Kind;
+use snowcap_api_defs::snowcap::clipboard::v1::ClipboardKind;
+
+use crate::util::convert::FromApi;
+
+impl FromApi<ClipboardKind> for Kind {
+    fn from_api(api_type: ClipboardKind) -> Self {
+        match api_type {
+            ClipboardKind::Unspecified | ClipboardKind::Standard => Kind::Standard,
+            ClipboardKind::Primary => Kind::Primary,
+        }
+    }
+}
@@ -15,6 +15,8 @@ use crate::api::run_unary_no_response;
 use crate::api::widget::v0alpha1::widget_def_to_fn;
 use crate::layer::ExclusiveZone;
 use crate::layer::LayerId;
+use crate::layer::LayerProperties;
+use crate::layer::Margin;
 use crate::layer::SnowcapLayer;
 
 #[tonic::async_trait]
@@ -87,10 +89,14 @@ impl layer_service_server::LayerService for super::LayerService {
             let layer = SnowcapLayer::new(
                 state,
                 Some((width, height)),
-                layer,
-                anchor,
-                exclusive_zone,
-                keyboard_interactivity,
+                None,
+                LayerProperties {
+                    layer,
+                    anchor,
+                    exclusive_zone,
+                    keyboard_interactivity,
+                    margin: Margin::default(),
+                },
                 f,
             );
 
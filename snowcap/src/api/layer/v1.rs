@@ -1,12 +1,14 @@
 use std::num::NonZeroU32;
 
 use anyhow::Context;
+use iced_wgpu::core::clipboard::Kind;
 use smithay_client_toolkit::shell::wlr_layer;
 use snowcap_api_defs::snowcap::layer::{
     self,
     v1::{
-        CloseRequest, GetLayerEventsRequest, GetLayerEventsResponse, NewLayerRequest,
-        NewLayerResponse, OperateLayerRequest, OperateLayerResponse, UpdateLayerRequest,
+        CloseRequest, GetClipboardRequest, GetClipboardResponse, GetLayerEventsRequest,
+        GetLayerEventsResponse, NewLayerRequest, NewLayerResponse, OperateLayerRequest,
+        OperateLayerResponse, SetClipboardRequest, SetClipboardResponse, UpdateLayerRequest,
         UpdateLayerResponse, ViewRequest, ViewResponse, layer_service_server,
     },
 };
@@ -14,11 +16,11 @@ use tonic::{Request, Response, Status};
 
 use crate::{
     api::{
-        ResponseStream, run_server_streaming_mapped, run_unary, run_unary_no_response,
-        widget::v1::widget_def_to_fn,
+        ResponseStream, run_server_streaming_mapped, run_unary, run_unary_deferred,
+        run_unary_no_response, widget::v1::widget_def_to_fn,
     },
-    layer::{ExclusiveZone, LayerEvent, LayerId, SnowcapLayer},
-    util::convert::TryFromApi,
+    layer::{ExclusiveZone, LayerEvent, LayerId, LayerProperties, Margin, SnowcapLayer},
+    util::convert::{FromApi, TryFromApi},
 };
 
 #[tonic::async_trait]
@@ -35,6 +37,8 @@ impl layer_service_server::LayerService for super::LayerService {
         let exclusive_zone = request.exclusive_zone;
         let keyboard_interactivity = request.keyboard_interactivity();
         let layer = request.layer();
+        let margin = request.margin.map(Margin::from_api).unwrap_or_default();
+        let output_name = request.output_name;
 
         let Some(widget_def) = request.widget_def else {
             return Err(Status::invalid_argument("no widget def"));
@@ -81,13 +85,26 @@ impl layer_service_server::LayerService for super::LayerService {
                 return Err(Status::invalid_argument("widget def was null"));
             };
 
+            let output = match output_name {
+                Some(name) => Some(
+                    state
+                        .output_for_name(&name)
+                        .ok_or_else(|| Status::not_found(format!("no output named `{name}`")))?,
+                ),
+                None => None,
+            };
+
             let layer = SnowcapLayer::new(
                 state,
                 None,
-                layer,
-                anchor,
-                exclusive_zone,
-                keyboard_interactivity,
+                output,
+                LayerProperties {
+                    layer,
+                    anchor,
+                    exclusive_zone,
+                    keyboard_interactivity,
+                    margin,
+                },
                 f,
             );
 
@@ -202,9 +219,25 @@ impl layer_service_server::LayerService for super::LayerService {
             layer::v1::Layer::Overlay => Some(wlr_layer::Layer::Overlay),
         };
 
+        let margin = request.margin.map(Margin::from_api);
+        let output_name = request.output_name;
+
         let widget_def = request.widget_def;
 
         run_unary(&self.sender, move |state| {
+            if let Some(name) = output_name {
+                let output =
+                    if name.is_empty() {
+                        None
+                    } else {
+                        Some(state.output_for_name(&name).ok_or_else(|| {
+                            Status::not_found(format!("no output named `{name}`"))
+                        })?)
+                    };
+
+                state.layer_set_output(id, output);
+            }
+
             let Some(layer) = state.layers.iter_mut().find(|layer| layer.layer_id == id) else {
                 return Ok(UpdateLayerResponse {});
             };
@@ -214,6 +247,7 @@ impl layer_service_server::LayerService for super::LayerService {
                 anchor,
                 exclusive_zone,
                 keyboard_interactivity,
+                margin,
                 widget_def.and_then(widget_def_to_fn),
             );
 
@@ -266,6 +300,63 @@ impl layer_service_server::LayerService for super::LayerService {
         })
         .await
     }
+
+    async fn get_clipboard(
+        &self,
+        request: Request<GetClipboardRequest>,
+    ) -> Result<Response<GetClipboardResponse>, Status> {
+        let request = request.into_inner();
+
+        let id = LayerId(request.layer_id);
+        let kind = Kind::from_api(request.kind());
+
+        run_unary_deferred(&self.sender, move |state, sender| {
+            if !state.layers.iter().any(|layer| layer.layer_id == id) {
+                let _ = sender.send(Err(Status::not_found(format!("no layer with id {}", id.0))));
+                return;
+            }
+
+            // Another client's clipboard may still be on its way
+            state.clipboard.borrow_mut().read_with(kind, move |text| {
+                let _ = sender.send(Ok(GetClipboardResponse { text }));
+            });
+        })
+        .await
+    }
+
+    async fn set_clipboard(
+        &self,
+        request: Request<SetClipboardRequest>,
+    ) -> Result<Response<SetClipboardResponse>, Status> {
+        let request = request.into_inner();
+
+        let id = LayerId(request.layer_id);
+        let kind = Kind::from_api(request.kind());
+        let text = request.text;
+
+        run_unary(&self.sender, move |state| {
+            let Some(layer) = state.layers.iter().find(|layer| layer.layer_id == id) else {
+                return Err(Status::not_found(format!("no layer with id {}", id.0)));
+            };
+
+            // The compositor requires the serial of an input event on the requesting surface
+            let Some(serial) = layer.surface.focus_serial else {
+                return Err(Status::failed_precondition(format!(
+                    "layer {} has not received any input",
+                    id.0
+                )));
+            };
+
+            if !state.clipboard.borrow_mut().write(kind, text, serial) {
+                return Err(Status::unavailable(
+                    "the compositor doesn't support this clipboard",
+                ));
+            }
+
+            Ok(SetClipboardResponse {})
+        })
+        .await
+    }
 }
 
 impl From<LayerEvent> for snowcap_api_defs::snowcap::layer::v1::LayerEvent {
@@ -285,3 +376,21 @@ impl From<LayerEvent> for snowcap_api_defs::snowcap::layer::v1::LayerEvent {
         }
     }
 }
+
+impl FromApi<layer::v1::Margin> for Margin {
+    fn from_api(api_type: layer::v1::Margin) -> Self {
+        let layer::v1::Margin {
+            top,
+            right,
+            bottom,
+            left,
+        } = api_type;
+
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+}
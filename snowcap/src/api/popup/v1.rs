@@ -1,16 +1,18 @@
 use anyhow::Context;
+use iced_wgpu::core::clipboard::Kind;
 use smithay_client_toolkit::reexports::protocols::xdg::shell::client::xdg_positioner;
 use snowcap_api_defs::snowcap::popup::v1::{
-    self, CloseRequest, GetPopupEventsRequest, GetPopupEventsResponse, NewPopupRequest,
-    NewPopupResponse, OperatePopupRequest, OperatePopupResponse, UpdatePopupRequest,
+    self, CloseRequest, GetClipboardRequest, GetClipboardResponse, GetPopupEventsRequest,
+    GetPopupEventsResponse, NewPopupRequest, NewPopupResponse, OperatePopupRequest,
+    OperatePopupResponse, SetClipboardRequest, SetClipboardResponse, UpdatePopupRequest,
     UpdatePopupResponse, ViewRequest, ViewResponse, new_popup_request, popup_service_server,
 };
 use tonic::{Request, Response, Status};
 
 use crate::{
     api::{
-        ResponseStream, run_server_streaming_mapped, run_unary, run_unary_no_response,
-        widget::v1::widget_def_to_fn,
+        ResponseStream, run_server_streaming_mapped, run_unary, run_unary_deferred,
+        run_unary_no_response, widget::v1::widget_def_to_fn,
     },
     decoration::DecorationId,
     layer::LayerId,
@@ -312,6 +314,63 @@ impl popup_service_server::PopupService for super::PopupService {
         })
         .await
     }
+
+    async fn get_clipboard(
+        &self,
+        request: Request<GetClipboardRequest>,
+    ) -> Result<Response<GetClipboardResponse>, Status> {
+        let request = request.into_inner();
+
+        let id = PopupId(request.popup_id);
+        let kind = Kind::from_api(request.kind());
+
+        run_unary_deferred(&self.sender, move |state, sender| {
+            if !state.popups.iter().any(|popup| popup.popup_id == id) {
+                let _ = sender.send(Err(Status::not_found(format!("no popup with id {}", id.0))));
+                return;
+            }
+
+            // Another client's clipboard may still be on its way
+            state.clipboard.borrow_mut().read_with(kind, move |text| {
+                let _ = sender.send(Ok(GetClipboardResponse { text }));
+            });
+        })
+        .await
+    }
+
+    async fn set_clipboard(
+        &self,
+        request: Request<SetClipboardRequest>,
+    ) -> Result<Response<SetClipboardResponse>, Status> {
+        let request = request.into_inner();
+
+        let id = PopupId(request.popup_id);
+        let kind = Kind::from_api(request.kind());
+        let text = request.text;
+
+        run_unary(&self.sender, move |state| {
+            let Some(popup) = state.popups.iter().find(|popup| popup.popup_id == id) else {
+                return Err(Status::not_found(format!("no popup with id {}", id.0)));
+            };
+
+            // The compositor requires the serial of an input event on the requesting surface
+            let Some(serial) = popup.surface.focus_serial else {
+                return Err(Status::failed_precondition(format!(
+                    "popup {} has not received any input",
+                    id.0
+                )));
+            };
+
+            if !state.clipboard.borrow_mut().write(kind, text, serial) {
+                return Err(Status::unavailable(
+                    "the compositor doesn't support this clipboard",
+                ));
+            }
+
+            Ok(SetClipboardResponse {})
+        })
+        .await
+    }
 }
 
 impl From<new_popup_request::ParentId> for popup::ParentId {
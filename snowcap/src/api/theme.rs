@@ -0,0 +1,14 @@
+mod v1;
+
+use super::StateFnSender;
+
+#[derive(Clone)]
+pub struct ThemeService {
+    sender: StateFnSender,
+}
+
+impl ThemeService {
+    pub fn new(sender: StateFnSender) -> Self {
+        Self { sender }
+    }
+}
@@ -0,0 +1,91 @@
+use std::path::PathBuf;
+
+use snowcap_api_defs::snowcap::theme::v1::{
+    self, GetThemeRequest, GetThemeResponse, LoadThemeRequest, SetThemeRequest, SetVariantRequest,
+    WatchThemeRequest, WatchThemeResponse, theme_service_server,
+};
+use tonic::{Request, Response, Status};
+
+use crate::{
+    api::{ResponseStream, run_server_streaming_mapped, run_unary, run_unary_no_response},
+    theme::Variant,
+    util::convert::FromApi,
+};
+
+#[tonic::async_trait]
+impl theme_service_server::ThemeService for super::ThemeService {
+    type WatchThemeStream = ResponseStream<WatchThemeResponse>;
+
+    async fn set_theme(&self, request: Request<SetThemeRequest>) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+
+        let variant = Option::<Variant>::from_api(request.variant());
+        let theme = request.theme.unwrap_or_default();
+
+        run_unary_no_response(&self.sender, move |state| {
+            state.set_theme(variant, theme);
+        })
+        .await
+    }
+
+    async fn set_variant(
+        &self,
+        request: Request<SetVariantRequest>,
+    ) -> Result<Response<()>, Status> {
+        let Some(variant) = Option::<Variant>::from_api(request.into_inner().variant()) else {
+            return Err(Status::invalid_argument("no variant specified"));
+        };
+
+        run_unary_no_response(&self.sender, move |state| {
+            state.set_theme_variant(variant);
+        })
+        .await
+    }
+
+    async fn load_theme(&self, request: Request<LoadThemeRequest>) -> Result<Response<()>, Status> {
+        let LoadThemeRequest { path, watch } = request.into_inner();
+
+        if path.is_empty() {
+            return Err(Status::invalid_argument("no path specified"));
+        }
+
+        run_unary(&self.sender, move |state| {
+            state
+                .load_theme(PathBuf::from(path), watch)
+                .map_err(|err| Status::invalid_argument(format!("{err:#}")))
+        })
+        .await
+    }
+
+    async fn get_theme(
+        &self,
+        _request: Request<GetThemeRequest>,
+    ) -> Result<Response<GetThemeResponse>, Status> {
+        run_unary(&self.sender, move |state| {
+            Ok(GetThemeResponse {
+                variant: v1::Variant::from(state.theme.variant).into(),
+                theme: Some(state.theme.current().clone()),
+            })
+        })
+        .await
+    }
+
+    async fn watch_theme(
+        &self,
+        _request: Request<WatchThemeRequest>,
+    ) -> Result<Response<Self::WatchThemeStream>, Status> {
+        run_server_streaming_mapped(
+            &self.sender,
+            move |state, sender| {
+                let _ = sender.send((state.theme.variant, state.theme.current().clone()));
+                state.theme.watchers.push(sender);
+            },
+            |(variant, theme)| {
+                Ok(WatchThemeResponse {
+                    variant: v1::Variant::from(variant).into(),
+                    theme: Some(theme),
+                })
+            },
+        )
+    }
+}
@@ -1,7 +1,15 @@
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
 use anyhow::Context;
 use iced::widget::{
-    Column, Container, Row, Scrollable, button, image::FilterMethod, scrollable::Scrollbar,
+    Column, Container, Grid, Row, Scrollable, Space, Stack, Themer, Tooltip, button, grid,
+    image::FilterMethod, rule, scrollable::Scrollbar, tooltip,
 };
+use prost::Message as _;
 use snowcap_api_defs::snowcap::widget::{
     self,
     v1::{
@@ -16,8 +24,14 @@ use crate::{
     decoration::DecorationId,
     layer::LayerId,
     popup::PopupId,
+    theme::{self, Rounded},
+    toplevel_capture,
     util::convert::{FromApi, TryFromApi},
-    widget::{MouseAreaEvent, TextInputEvent, ViewFn, WidgetEvent, WidgetId},
+    widget::{
+        MouseAreaEvent, SliderEvent, SnowcapMessage, TextInputEvent, ViewFn, WidgetEvent, WidgetId,
+        canvas::{DisplayList, DisplayListProgram},
+        transition::{self, Clock, Fade, Transition},
+    },
 };
 
 #[tonic::async_trait]
@@ -67,6 +81,25 @@ impl widget_service_server::WidgetService for super::WidgetService {
                                 WidgetEvent::TextInput(evt) => {
                                     widget_event::Event::TextInput(evt.into())
                                 }
+                                WidgetEvent::Slider(evt) => widget_event::Event::Slider(evt.into()),
+                                WidgetEvent::Checkbox(checked) => {
+                                    widget_event::Event::Checkbox(widget::v1::checkbox::Event {
+                                        checked,
+                                    })
+                                }
+                                WidgetEvent::Toggler(toggled) => {
+                                    widget_event::Event::Toggler(widget::v1::toggler::Event {
+                                        toggled,
+                                    })
+                                }
+                                WidgetEvent::PickList(selected) => {
+                                    widget_event::Event::PickList(widget::v1::pick_list::Event {
+                                        selected,
+                                    })
+                                }
+                                WidgetEvent::Radio => {
+                                    widget_event::Event::Radio(widget::v1::radio::Event {})
+                                }
                             }),
                         })
                         .collect(),
@@ -76,8 +109,43 @@ impl widget_service_server::WidgetService for super::WidgetService {
     }
 }
 
+/// Makes a progress bar's range and value safe to pass to iced, which panics on
+/// NaN and inverted ranges.
+///
+/// Inverted ranges are swapped, non-finite ranges fall back to `0.0..=1.0`,
+/// and a NaN value is treated as the start of the range.
+fn progress_bar_range(min: f32, max: f32, value: f32) -> (RangeInclusive<f32>, f32) {
+    let (min, max) = if !min.is_finite() || !max.is_finite() {
+        tracing::warn!("progress bar range {min}..={max} is not finite, using 0..=1");
+        (0.0, 1.0)
+    } else if min > max {
+        (max, min)
+    } else {
+        (min, max)
+    };
+
+    let value = if value.is_nan() { min } else { value.clamp(min, max) };
+
+    (min..=max, value)
+}
+
 pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
-    let def = def.widget?;
+    let palette = def.theme.and_then(|theme| theme.palette);
+    let f = widget_to_fn(def.widget?)?;
+
+    let Some(palette) = palette else {
+        return Some(f);
+    };
+
+    Some(Box::new(move || {
+        let theme = theme::current().iced_with_palette(palette);
+        Themer::new(Some(theme), f())
+            .text_color(|theme: &iced::Theme| theme.palette().text)
+            .into()
+    }))
+}
+
+fn widget_to_fn(def: widget_def::Widget) -> Option<ViewFn> {
     match def {
         widget_def::Widget::Text(text_def) => {
             let horizontal_alignment = text_def.horizontal_alignment();
@@ -95,8 +163,14 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
             } = text_def;
 
             let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
+                let defaults = theme::current();
                 let mut text = iced::widget::Text::new(text.clone());
-                if let Some(pixels) = style.as_ref().and_then(|style| style.pixels) {
+                if let Some(pixels) = style
+                    .as_ref()
+                    .and_then(|style| style.pixels)
+                    .or(defaults.text_size)
+                {
                     text = text.size(pixels);
                 }
                 if let Some(width) = width {
@@ -106,8 +180,11 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
                     text = text.height(iced::Length::from_api(height));
                 }
                 if let Some(color) = style.as_ref().and_then(|style| style.color) {
-                    text = text.style(move |_| iced::widget::text::Style {
-                        color: Some(iced::Color::from_api(color)),
+                    text = text.style(move |_| {
+                        iced::widget::text::Style {
+                            color: Some(iced::Color::from_api(color)),
+                        }
+                        .fade(alpha)
                     });
                 }
 
@@ -151,8 +228,13 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
                     text = text.wrapping(wrapping);
                 }
 
-                if let Some(font) = style.as_ref().and_then(|s| s.font.clone()) {
-                    text = text.font(iced::Font::from_api(font));
+                if let Some(font) = style
+                    .as_ref()
+                    .and_then(|s| s.font.clone())
+                    .map(iced::Font::from_api)
+                    .or(defaults.font)
+                {
+                    text = text.font(font);
                 }
 
                 text.into()
@@ -177,7 +259,7 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
             let f: ViewFn = Box::new(move || {
                 let mut column = Column::new();
 
-                if let Some(spacing) = spacing {
+                if let Some(spacing) = spacing.or(theme::current().spacing) {
                     column = column.spacing(spacing);
                 }
 
@@ -234,7 +316,7 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
             let f: ViewFn = Box::new(move || {
                 let mut row = Row::new();
 
-                if let Some(spacing) = spacing {
+                if let Some(spacing) = spacing.or(theme::current().spacing) {
                     row = row.spacing(spacing);
                 }
 
@@ -294,6 +376,7 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
             let child_widget_fn = child.and_then(|def| widget_def_to_fn(*def));
 
             let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
                 let mut scrollable = Scrollable::new(
                     child_widget_fn
                         .as_ref()
@@ -392,7 +475,7 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
                         }
                     }
 
-                    s
+                    s.fade(alpha)
                 });
 
                 scrollable.into()
@@ -421,6 +504,7 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
             let child_widget_fn = child.and_then(|def| widget_def_to_fn(*def));
 
             let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
                 let mut container = Container::new(
                     child_widget_fn
                         .as_ref()
@@ -467,10 +551,12 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
                 let background_color_clone = style.as_ref().and_then(|s| s.background_color);
                 let border_color_clone = style.as_ref().and_then(|s| s.border);
                 let background_clone = style.as_ref().and_then(|s| s.background.clone());
+                let radius = theme::current().radius;
 
                 let style = move |theme: &iced::Theme| {
                     let mut style =
-                        <iced::Theme as iced::widget::container::Catalog>::default()(theme);
+                        <iced::Theme as iced::widget::container::Catalog>::default()(theme)
+                            .rounded(radius);
 
                     if let Some(text_color) = text_color_clone {
                         style.text_color = Some(iced::Color::from_api(text_color));
@@ -501,7 +587,7 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
                             .ok();
                     }
 
-                    style
+                    style.fade(alpha)
                 };
 
                 container = container.style(style);
@@ -529,6 +615,7 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
             let child_widget_fn = child.and_then(|def| widget_def_to_fn(*def));
 
             let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
                 let mut button = iced::widget::Button::new(
                     child_widget_fn
                         .as_ref()
@@ -557,8 +644,10 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
 
                 let style = {
                     let style = style.clone();
+                    let radius = theme::current().radius;
                     move |theme: &iced::Theme, status| {
-                        let mut s = <iced::Theme as button::Catalog>::default()(theme, status);
+                        let mut s = <iced::Theme as button::Catalog>::default()(theme, status)
+                            .rounded(radius);
                         let style = style.clone();
 
                         let inner = match status {
@@ -595,7 +684,7 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
                             }
                         }
 
-                        s
+                        s.fade(alpha)
                     }
                 };
 
@@ -623,7 +712,15 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
 
             let handle = handle?;
 
+            let toplevel = match &handle {
+                widget::v1::image::Handle::ToplevelIdentifier(identifier) => Some(
+                    toplevel_capture::CaptureTarget::get_or_create(identifier.clone()),
+                ),
+                _ => None,
+            };
+
             let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
                 // FIXME: don't clone the entire image
                 let mut image = match handle.clone() {
                     widget::v1::image::Handle::Path(path) => {
@@ -639,6 +736,12 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
                     }) => iced::widget::Image::new(iced::widget::image::Handle::from_rgba(
                         width, height, rgba,
                     )),
+                    widget::v1::image::Handle::ToplevelIdentifier(_) => iced::widget::Image::new(
+                        toplevel
+                            .as_ref()
+                            .map(|toplevel| toplevel.frame())
+                            .unwrap_or_else(toplevel_capture::empty_frame),
+                    ),
                 };
 
                 if let Some(true) = nearest_neighbor {
@@ -647,8 +750,8 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
                 if let Some(degrees) = rotation_degrees {
                     image = image.rotation(iced::Radians::from(iced::Degrees::from(degrees)));
                 }
-                if let Some(opacity) = opacity {
-                    image = image.opacity(opacity.clamp(0.0, 1.0));
+                if opacity.is_some() || alpha < 1.0 {
+                    image = image.opacity(opacity.unwrap_or(1.0).clamp(0.0, 1.0) * alpha);
                 }
                 if let Some(width) = width {
                     image = image.width(iced::Length::from_api(width));
@@ -868,6 +971,9 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
             } = *text_input;
 
             let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
+                let defaults = theme::current();
+                let radius = defaults.radius;
                 let mut text_input = iced::widget::TextInput::new(&placeholder, &value);
 
                 if let Some(id) = id.clone() {
@@ -904,8 +1010,11 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
                     }
                 }
 
-                if let Some(font) = font.clone() {
-                    text_input = text_input.font(iced::Font::from_api(font));
+                if let Some(font) = font.clone().map(iced::Font::from_api).or(defaults.font) {
+                    text_input = text_input.font(font);
+                }
+                if let Some(text_size) = defaults.text_size {
+                    text_input = text_input.size(text_size);
                 }
 
                 if let Some(icon) = icon.clone() {
@@ -944,7 +1053,8 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
                     let style = Styles::from_api(style);
                     let style = move |theme: &iced::Theme, status| {
                         use iced::widget::text_input;
-                        let s = <iced::Theme as text_input::Catalog>::default()(theme, status);
+                        let s = <iced::Theme as text_input::Catalog>::default()(theme, status)
+                            .rounded(radius);
 
                         let crate::widget::text_input::Styles {
                             active,
@@ -983,22 +1093,832 @@ pub fn widget_def_to_fn(def: WidgetDef) -> Option<ViewFn> {
                                 value: value.unwrap_or(s.value),
                                 selection: selection.unwrap_or(s.selection),
                             }
+                            .fade(alpha)
                         } else {
-                            s
+                            s.fade(alpha)
                         }
                     };
 
                     text_input = text_input.style(style);
+                } else if alpha < 1.0 || radius.is_some() {
+                    text_input = text_input.style(move |theme, status| {
+                        <iced::Theme as iced::widget::text_input::Catalog>::default()(theme, status)
+                            .rounded(radius)
+                            .fade(alpha)
+                    });
                 }
 
                 text_input.into()
             });
 
+            Some(f)
+        }
+        widget_def::Widget::Slider(slider) => {
+            let widget::v1::Slider {
+                min,
+                max,
+                value,
+                step,
+                shift_step,
+                default_value,
+                width,
+                height,
+                on_change,
+                on_release,
+                widget_id,
+            } = slider;
+
+            let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
+                let mut slider = iced::widget::Slider::new(min..=max, value, move |value| {
+                    widget_message(
+                        widget_id.filter(|_| on_change),
+                        WidgetEvent::Slider(SliderEvent::Change(value)),
+                    )
+                });
+
+                if on_release {
+                    slider = slider.on_release(widget_message(
+                        widget_id,
+                        WidgetEvent::Slider(SliderEvent::Release),
+                    ));
+                }
+                if let Some(step) = step {
+                    slider = slider.step(step);
+                }
+                if let Some(shift_step) = shift_step {
+                    slider = slider.shift_step(shift_step);
+                }
+                if let Some(default_value) = default_value {
+                    slider = slider.default(default_value);
+                }
+                if let Some(width) = width {
+                    slider = slider.width(iced::Length::from_api(width));
+                }
+                if let Some(height) = height {
+                    slider = slider.height(height);
+                }
+
+                if alpha < 1.0 {
+                    slider = slider.style(move |theme, status| {
+                        <iced::Theme as iced::widget::slider::Catalog>::default()(theme, status)
+                            .fade(alpha)
+                    });
+                }
+
+                slider.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::VerticalSlider(slider) => {
+            let widget::v1::VerticalSlider {
+                min,
+                max,
+                value,
+                step,
+                shift_step,
+                default_value,
+                width,
+                height,
+                on_change,
+                on_release,
+                widget_id,
+            } = slider;
+
+            let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
+                let mut slider =
+                    iced::widget::VerticalSlider::new(min..=max, value, move |value| {
+                        widget_message(
+                            widget_id.filter(|_| on_change),
+                            WidgetEvent::Slider(SliderEvent::Change(value)),
+                        )
+                    });
+
+                if on_release {
+                    slider = slider.on_release(widget_message(
+                        widget_id,
+                        WidgetEvent::Slider(SliderEvent::Release),
+                    ));
+                }
+                if let Some(step) = step {
+                    slider = slider.step(step);
+                }
+                if let Some(shift_step) = shift_step {
+                    slider = slider.shift_step(shift_step);
+                }
+                if let Some(default_value) = default_value {
+                    slider = slider.default(default_value);
+                }
+                if let Some(width) = width {
+                    slider = slider.width(width);
+                }
+                if let Some(height) = height {
+                    slider = slider.height(iced::Length::from_api(height));
+                }
+
+                if alpha < 1.0 {
+                    slider = slider.style(move |theme, status| {
+                        <iced::Theme as iced::widget::slider::Catalog>::default()(theme, status)
+                            .fade(alpha)
+                    });
+                }
+
+                slider.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::Checkbox(checkbox) => {
+            let widget::v1::Checkbox {
+                label,
+                checked,
+                on_toggle,
+                size,
+                spacing,
+                text_size,
+                width,
+                widget_id,
+            } = checkbox;
+
+            let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
+                let defaults = theme::current();
+                let mut checkbox = iced::widget::Checkbox::new(checked).label(label.clone());
+
+                if let Some(widget_id) = widget_id
+                    && on_toggle
+                {
+                    checkbox = checkbox.on_toggle(move |checked| {
+                        SnowcapMessage::WidgetEvent(
+                            WidgetId(widget_id),
+                            WidgetEvent::Checkbox(checked),
+                        )
+                    });
+                }
+                if let Some(size) = size {
+                    checkbox = checkbox.size(size);
+                }
+                if let Some(spacing) = spacing {
+                    checkbox = checkbox.spacing(spacing);
+                }
+                if let Some(text_size) = text_size.or(defaults.text_size) {
+                    checkbox = checkbox.text_size(text_size);
+                }
+                if let Some(font) = defaults.font {
+                    checkbox = checkbox.font(font);
+                }
+                if let Some(width) = width {
+                    checkbox = checkbox.width(iced::Length::from_api(width));
+                }
+
+                if alpha < 1.0 {
+                    checkbox = checkbox.style(move |theme, status| {
+                        <iced::Theme as iced::widget::checkbox::Catalog>::default()(theme, status)
+                            .fade(alpha)
+                    });
+                }
+
+                checkbox.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::Toggler(toggler) => {
+            let widget::v1::Toggler {
+                label,
+                toggled,
+                on_toggle,
+                size,
+                spacing,
+                text_size,
+                width,
+                widget_id,
+            } = toggler;
+
+            let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
+                let defaults = theme::current();
+                let mut toggler = iced::widget::Toggler::new(toggled);
+
+                if let Some(label) = label.clone() {
+                    toggler = toggler.label(label);
+                }
+                if let Some(widget_id) = widget_id
+                    && on_toggle
+                {
+                    toggler = toggler.on_toggle(move |toggled| {
+                        SnowcapMessage::WidgetEvent(
+                            WidgetId(widget_id),
+                            WidgetEvent::Toggler(toggled),
+                        )
+                    });
+                }
+                if let Some(size) = size {
+                    toggler = toggler.size(size);
+                }
+                if let Some(spacing) = spacing {
+                    toggler = toggler.spacing(spacing);
+                }
+                if let Some(text_size) = text_size.or(defaults.text_size) {
+                    toggler = toggler.text_size(text_size);
+                }
+                if let Some(font) = defaults.font {
+                    toggler = toggler.font(font);
+                }
+                if let Some(width) = width {
+                    toggler = toggler.width(iced::Length::from_api(width));
+                }
+
+                if alpha < 1.0 {
+                    toggler = toggler.style(move |theme, status| {
+                        <iced::Theme as iced::widget::toggler::Catalog>::default()(theme, status)
+                            .fade(alpha)
+                    });
+                }
+
+                toggler.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::ProgressBar(progress_bar) => {
+            let widget::v1::ProgressBar {
+                min,
+                max,
+                value,
+                length,
+                girth,
+                vertical,
+                style,
+            } = progress_bar;
+
+            let (range, value) = progress_bar_range(min, max, value);
+
+            let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
+                let mut progress_bar = iced::widget::ProgressBar::new(range.clone(), value);
+
+                if let Some(length) = length {
+                    progress_bar = progress_bar.length(iced::Length::from_api(length));
+                }
+                if let Some(girth) = girth {
+                    progress_bar = progress_bar.girth(iced::Length::from_api(girth));
+                }
+                if vertical {
+                    progress_bar = progress_bar.vertical();
+                }
+                if let Some(style) = style.clone() {
+                    progress_bar = progress_bar.style(move |theme| {
+                        use iced::widget::progress_bar;
+
+                        let mut s = <iced::Theme as progress_bar::Catalog>::default()(theme);
+
+                        let widget::v1::progress_bar::Style {
+                            background,
+                            bar,
+                            border,
+                        } = style.clone();
+
+                        if let Some(background) = background.and_then(|bg| {
+                            TryFromApi::try_from_api(bg)
+                                .inspect_err(|e| tracing::error!("{e}"))
+                                .ok()
+                        }) {
+                            s.background = background;
+                        }
+                        if let Some(bar) = bar.and_then(|bar| {
+                            TryFromApi::try_from_api(bar)
+                                .inspect_err(|e| tracing::error!("{e}"))
+                                .ok()
+                        }) {
+                            s.bar = bar;
+                        }
+                        if let Some(border) = border {
+                            s.border = FromApi::from_api(border);
+                        }
+
+                        s.fade(alpha)
+                    });
+                } else if alpha < 1.0 {
+                    progress_bar = progress_bar.style(move |theme| {
+                        <iced::Theme as iced::widget::progress_bar::Catalog>::default()(theme)
+                            .fade(alpha)
+                    });
+                }
+
+                progress_bar.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::PickList(pick_list) => {
+            let widget::v1::PickList {
+                options,
+                selected,
+                placeholder,
+                on_select,
+                width,
+                padding,
+                text_size,
+                widget_id,
+            } = pick_list;
+
+            let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
+                let defaults = theme::current();
+                let radius = defaults.radius;
+                let mut pick_list = iced::widget::PickList::new(
+                    options.clone(),
+                    selected.clone(),
+                    move |selected| {
+                        widget_message(
+                            widget_id.filter(|_| on_select),
+                            WidgetEvent::PickList(selected),
+                        )
+                    },
+                );
+
+                if let Some(placeholder) = placeholder.as_deref() {
+                    pick_list = pick_list.placeholder(placeholder);
+                }
+                if let Some(width) = width {
+                    pick_list = pick_list.width(iced::Length::from_api(width));
+                }
+                if let Some(padding) = padding {
+                    pick_list = pick_list.padding(iced::Padding::from_api(padding));
+                }
+                if let Some(text_size) = text_size.or(defaults.text_size) {
+                    pick_list = pick_list.text_size(text_size);
+                }
+                if let Some(font) = defaults.font {
+                    pick_list = pick_list.font(font);
+                }
+
+                if alpha < 1.0 || radius.is_some() {
+                    pick_list = pick_list.style(move |theme, status| {
+                        <iced::Theme as iced::widget::pick_list::Catalog>::default()(theme, status)
+                            .rounded(radius)
+                            .fade(alpha)
+                    });
+                }
+
+                pick_list.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::Radio(radio) => {
+            let widget::v1::Radio {
+                label,
+                selected,
+                on_select,
+                size,
+                spacing,
+                text_size,
+                width,
+                widget_id,
+            } = radio;
+
+            let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
+                let defaults = theme::current();
+                // Each radio is its own group; the program keeps track of which one is selected.
+                let mut radio =
+                    iced::widget::Radio::new(label.clone(), (), selected.then_some(()), |()| {
+                        widget_message(widget_id.filter(|_| on_select), WidgetEvent::Radio)
+                    });
+
+                if let Some(size) = size {
+                    radio = radio.size(size);
+                }
+                if let Some(spacing) = spacing {
+                    radio = radio.spacing(spacing);
+                }
+                if let Some(text_size) = text_size.or(defaults.text_size) {
+                    radio = radio.text_size(text_size);
+                }
+                if let Some(font) = defaults.font {
+                    radio = radio.font(font);
+                }
+                if let Some(width) = width {
+                    radio = radio.width(iced::Length::from_api(width));
+                }
+
+                if alpha < 1.0 {
+                    radio = radio.style(move |theme, status| {
+                        <iced::Theme as iced::widget::radio::Catalog>::default()(theme, status)
+                            .fade(alpha)
+                    });
+                }
+
+                radio.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::Svg(svg) => {
+            let content_fit = svg.content_fit();
+
+            let widget::v1::Svg {
+                handle,
+                width,
+                height,
+                content_fit: _,
+                color,
+                rotation_degrees,
+                opacity,
+            } = svg;
+
+            let handle = match handle? {
+                widget::v1::svg::Handle::Path(path) => iced::widget::svg::Handle::from_path(path),
+                widget::v1::svg::Handle::Bytes(bytes) => {
+                    iced::widget::svg::Handle::from_memory(bytes)
+                }
+            };
+
+            let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
+                let mut svg = iced::widget::Svg::new(handle.clone());
+
+                if let Some(width) = width {
+                    svg = svg.width(iced::Length::from_api(width));
+                }
+                if let Some(height) = height {
+                    svg = svg.height(iced::Length::from_api(height));
+                }
+                if let Some(color) = color {
+                    let color = iced::Color::from_api(color);
+                    svg = svg.style(move |_theme, _status| {
+                        iced::widget::svg::Style { color: Some(color) }.fade(alpha)
+                    });
+                }
+                if let Some(degrees) = rotation_degrees {
+                    svg = svg.rotation(iced::Radians::from(iced::Degrees::from(degrees)));
+                }
+                if opacity.is_some() || alpha < 1.0 {
+                    svg = svg.opacity(opacity.unwrap_or(1.0).clamp(0.0, 1.0) * alpha);
+                }
+
+                let content_fit = match content_fit {
+                    widget::v1::image::ContentFit::Unspecified => None,
+                    widget::v1::image::ContentFit::Contain => Some(iced::ContentFit::Contain),
+                    widget::v1::image::ContentFit::Cover => Some(iced::ContentFit::Cover),
+                    widget::v1::image::ContentFit::Fill => Some(iced::ContentFit::Fill),
+                    widget::v1::image::ContentFit::None => Some(iced::ContentFit::None),
+                    widget::v1::image::ContentFit::ScaleDown => Some(iced::ContentFit::ScaleDown),
+                };
+
+                if let Some(content_fit) = content_fit {
+                    svg = svg.content_fit(content_fit);
+                }
+
+                svg.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::Canvas(canvas) => {
+            let widget::v1::Canvas {
+                width,
+                height,
+                commands,
+            } = canvas;
+
+            let display_list = DisplayList::get_or_create(commands);
+
+            let f: ViewFn = Box::new(move || {
+                let mut canvas =
+                    iced::widget::Canvas::new(DisplayListProgram(display_list.clone()));
+
+                if let Some(width) = width {
+                    canvas = canvas.width(iced::Length::from_api(width));
+                }
+                if let Some(height) = height {
+                    canvas = canvas.height(iced::Length::from_api(height));
+                }
+
+                canvas.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::Stack(widget::v1::Stack {
+            width,
+            height,
+            clip,
+            children,
+        }) => {
+            let children_widget_fns = children
+                .into_iter()
+                .flat_map(widget_def_to_fn)
+                .collect::<Vec<_>>();
+
+            let f: ViewFn = Box::new(move || {
+                let mut stack = Stack::new();
+
+                if let Some(width) = width {
+                    stack = stack.width(iced::Length::from_api(width));
+                }
+                if let Some(height) = height {
+                    stack = stack.height(iced::Length::from_api(height));
+                }
+                if let Some(clip) = clip {
+                    stack = stack.clip(clip);
+                }
+
+                for child in children_widget_fns.iter() {
+                    stack = stack.push(child());
+                }
+
+                stack.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::Tooltip(tooltip_def) => {
+            let position = tooltip_def.position();
+
+            let widget::v1::Tooltip {
+                content,
+                tooltip: hint,
+                position: _,
+                gap,
+                padding,
+                delay_millis,
+                snap_within_viewport,
+                style,
+            } = *tooltip_def;
+
+            let content_widget_fn = content.and_then(|def| widget_def_to_fn(*def));
+            let hint_widget_fn = hint.and_then(|def| widget_def_to_fn(*def));
+
+            let position = match position {
+                widget::v1::tooltip::Position::Unspecified | widget::v1::tooltip::Position::Top => {
+                    tooltip::Position::Top
+                }
+                widget::v1::tooltip::Position::Bottom => tooltip::Position::Bottom,
+                widget::v1::tooltip::Position::Left => tooltip::Position::Left,
+                widget::v1::tooltip::Position::Right => tooltip::Position::Right,
+                widget::v1::tooltip::Position::FollowCursor => tooltip::Position::FollowCursor,
+            };
+            let style = style.map(iced::widget::container::Style::from_api);
+
+            let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
+                let mut tooltip = Tooltip::new(
+                    content_widget_fn
+                        .as_ref()
+                        .map(|content| content())
+                        .unwrap_or_else(|| iced::widget::Text::new("NULL").into()),
+                    hint_widget_fn
+                        .as_ref()
+                        .map(|hint| hint())
+                        .unwrap_or_else(|| iced::widget::Text::new("NULL").into()),
+                    position,
+                );
+
+                if let Some(gap) = gap {
+                    tooltip = tooltip.gap(gap);
+                }
+                if let Some(padding) = padding {
+                    tooltip = tooltip.padding(padding);
+                }
+                if let Some(delay_millis) = delay_millis {
+                    tooltip = tooltip.delay(Duration::from_millis(delay_millis as u64));
+                }
+                if let Some(snap) = snap_within_viewport {
+                    tooltip = tooltip.snap_within_viewport(snap);
+                }
+                if let Some(style) = style {
+                    tooltip = tooltip.style(move |_theme| style.fade(alpha));
+                }
+
+                tooltip.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::Grid(widget::v1::Grid {
+            columns,
+            spacing,
+            width,
+            sizing,
+            children,
+        }) => {
+            let children_widget_fns = children
+                .into_iter()
+                .flat_map(widget_def_to_fn)
+                .collect::<Vec<_>>();
+
+            let sizing = sizing.map(|sizing| match sizing {
+                widget::v1::grid::Sizing::AspectRatio(ratio) => grid::Sizing::AspectRatio(ratio),
+                widget::v1::grid::Sizing::EvenlyDistribute(height) => {
+                    grid::Sizing::EvenlyDistribute(iced::Length::from_api(height))
+                }
+            });
+
+            let f: ViewFn = Box::new(move || {
+                let mut grid = Grid::new();
+
+                match columns {
+                    Some(widget::v1::grid::Columns::Amount(amount)) => {
+                        grid = grid.columns(amount as usize);
+                    }
+                    Some(widget::v1::grid::Columns::MaxCellWidth(max_width)) => {
+                        grid = grid.fluid(max_width);
+                    }
+                    None => (),
+                }
+
+                if let Some(spacing) = spacing.or(theme::current().spacing) {
+                    grid = grid.spacing(spacing);
+                }
+                if let Some(width) = width {
+                    grid = grid.width(width);
+                }
+                if let Some(sizing) = sizing {
+                    grid = grid.height(sizing);
+                }
+
+                for child in children_widget_fns.iter() {
+                    grid = grid.push(child());
+                }
+
+                grid.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::Space(widget::v1::Space { width, height }) => {
+            let f: ViewFn = Box::new(move || {
+                let mut space = Space::new();
+
+                if let Some(width) = width {
+                    space = space.width(iced::Length::from_api(width));
+                }
+                if let Some(height) = height {
+                    space = space.height(iced::Length::from_api(height));
+                }
+
+                space.into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::Rule(widget::v1::Rule {
+            vertical,
+            thickness,
+            style,
+        }) => {
+            let color = style
+                .as_ref()
+                .and_then(|style| style.color)
+                .map(iced::Color::from_api);
+            let radius = style
+                .as_ref()
+                .and_then(|style| style.radius)
+                .map(iced::border::Radius::from_api);
+            let fill_mode = style
+                .as_ref()
+                .and_then(|style| style.fill_mode.clone())
+                .and_then(|fill_mode| fill_mode.fill_mode)
+                .map(rule::FillMode::from_api);
+            let snap = style.as_ref().and_then(|style| style.snap);
+
+            let f: ViewFn = Box::new(move || {
+                let alpha = transition::opacity();
+                let rule = if vertical {
+                    rule::vertical(thickness)
+                } else {
+                    rule::horizontal(thickness)
+                };
+
+                rule.style(move |theme| {
+                    let mut style = rule::default(theme);
+
+                    if let Some(color) = color {
+                        style.color = color;
+                    }
+                    if let Some(radius) = radius {
+                        style.radius = radius;
+                    }
+                    if let Some(fill_mode) = fill_mode {
+                        style.fill_mode = fill_mode;
+                    }
+                    if let Some(snap) = snap {
+                        style.snap = snap;
+                    }
+
+                    style.fade(alpha)
+                })
+                .into()
+            });
+
+            Some(f)
+        }
+        widget_def::Widget::Transition(transition_def) => {
+            let easing = transition_def.easing();
+
+            let widget::v1::Transition {
+                child,
+                key,
+                duration_millis,
+                delay_millis,
+                easing: _,
+                from_opacity,
+                from_offset_x,
+                from_offset_y,
+                from_scale,
+                reverse,
+                repeat_forever,
+                auto_reverse,
+            } = *transition_def;
+
+            let child_widget_fn = child.and_then(|def| widget_def_to_fn(*def));
+
+            let timing = transition::Timing {
+                duration: Duration::from_millis(duration_millis as u64),
+                delay: Duration::from_millis(delay_millis.unwrap_or_default() as u64),
+                easing: match easing {
+                    widget::v1::transition::Easing::Unspecified => Default::default(),
+                    widget::v1::transition::Easing::Linear => transition::Easing::Linear,
+                    widget::v1::transition::Easing::EaseIn => transition::Easing::EaseIn,
+                    widget::v1::transition::Easing::EaseOut => transition::Easing::EaseOut,
+                    widget::v1::transition::Easing::EaseInOut => transition::Easing::EaseInOut,
+                },
+                reverse,
+                repeat_forever,
+                auto_reverse,
+            };
+
+            // Everything but the child and key decides whether a keyed transition restarts.
+            let fingerprint = {
+                let mut hasher = DefaultHasher::new();
+                widget::v1::Transition {
+                    child: None,
+                    key: None,
+                    duration_millis,
+                    delay_millis,
+                    easing: easing as i32,
+                    from_opacity,
+                    from_offset_x,
+                    from_offset_y,
+                    from_scale,
+                    reverse,
+                    repeat_forever,
+                    auto_reverse,
+                }
+                .encode_to_vec()
+                .hash(&mut hasher);
+                hasher.finish()
+            };
+
+            let clock = Clock::get_or_create(key, timing, fingerprint);
+
+            let from_opacity = from_opacity.unwrap_or(1.0).clamp(0.0, 1.0);
+            let from_offset = iced::Vector::new(
+                from_offset_x.unwrap_or_default(),
+                from_offset_y.unwrap_or_default(),
+            );
+            let from_scale = from_scale.unwrap_or(1.0);
+
+            let f: ViewFn = Box::new(move || {
+                let (progress, animating) = clock.progress(Instant::now());
+
+                let opacity = from_opacity + (1.0 - from_opacity) * progress;
+                let offset = from_offset * (1.0 - progress);
+                let scale = from_scale + (1.0 - from_scale) * progress;
+
+                let child = transition::with_opacity(opacity, || {
+                    child_widget_fn
+                        .as_ref()
+                        .map(|child| child())
+                        .unwrap_or_else(|| iced::widget::Text::new("NULL").into())
+                });
+
+                Transition::new(child)
+                    .opacity(opacity)
+                    .offset(offset)
+                    .scale(scale)
+                    .animating(animating)
+                    .into()
+            });
+
             Some(f)
         }
     }
 }
 
+/// Creates a message for a widget event, or a no-op if the widget isn't listening for it.
+fn widget_message(widget_id: Option<u32>, event: WidgetEvent) -> SnowcapMessage {
+    match widget_id {
+        Some(widget_id) => SnowcapMessage::WidgetEvent(WidgetId(widget_id), event),
+        None => SnowcapMessage::Noop,
+    }
+}
+
 impl FromApi<widget::v1::Length> for iced::Length {
     fn from_api(length: widget::v1::Length) -> Self {
         use widget::v1::length::Strategy;
@@ -1362,6 +2282,19 @@ impl FromApi<iced::Point> for snowcap_api_defs::snowcap::widget::v1::mouse_area:
     }
 }
 
+impl From<SliderEvent> for snowcap_api_defs::snowcap::widget::v1::slider::Event {
+    fn from(value: SliderEvent) -> Self {
+        use snowcap_api_defs::snowcap::widget::v1::slider::event::Data;
+
+        let data = match value {
+            SliderEvent::Change(value) => Data::Change(value),
+            SliderEvent::Release => Data::Release(()),
+        };
+
+        Self { data: Some(data) }
+    }
+}
+
 impl From<TextInputEvent> for snowcap_api_defs::snowcap::widget::v1::text_input::Event {
     fn from(value: TextInputEvent) -> Self {
         use snowcap_api_defs::snowcap::widget::v1::text_input::event::Data;
@@ -1469,3 +2402,21 @@ impl FromApi<widget::v1::text_input::Style> for crate::widget::text_input::Style
         }
     }
 }
+
+impl FromApi<widget::v1::rule::fill_mode::FillMode> for rule::FillMode {
+    fn from_api(api_type: widget::v1::rule::fill_mode::FillMode) -> Self {
+        use widget::v1::rule::fill_mode::FillMode;
+
+        match api_type {
+            FillMode::Full(()) => Self::Full,
+            FillMode::Percent(percent) => Self::Percent(percent.clamp(0.0, 100.0)),
+            FillMode::Padded(padding) => Self::Padded(padding.min(u16::MAX as u32) as u16),
+            FillMode::AsymmetricPadding(widget::v1::rule::AsymmetricPadding { start, end }) => {
+                Self::AsymmetricPadding(
+                    start.min(u16::MAX as u32) as u16,
+                    end.min(u16::MAX as u32) as u16,
+                )
+            }
+        }
+    }
+}
@@ -1,10 +1,631 @@
-/// A no-op clipboard.
-pub struct WaylandClipboard;
+//! Clipboard support using `wl_data_device` and `zwp_primary_selection_v1`.
+
+use std::{
+    cell::{Cell, RefCell},
+    fs::File,
+    io::{ErrorKind, Read, Write},
+    os::fd::{AsFd, OwnedFd},
+    rc::Rc,
+    time::Duration,
+};
+
+use iced_wgpu::core::clipboard::Kind;
+use rustix::fs::OFlags;
+use smithay_client_toolkit::{
+    data_device_manager::{
+        DataDeviceManagerState, WritePipe, data_device::DataDevice, data_source::CopyPasteSource,
+    },
+    primary_selection::{
+        PrimarySelectionManagerState, device::PrimarySelectionDevice,
+        selection::PrimarySelectionSource,
+    },
+    reexports::{
+        calloop::{
+            Interest, LoopHandle, Mode, PostAction, RegistrationToken,
+            generic::Generic,
+            timer::{TimeoutAction, Timer},
+        },
+        client::{
+            Connection, QueueHandle,
+            globals::GlobalList,
+            protocol::{wl_data_source::WlDataSource, wl_seat::WlSeat},
+        },
+        protocols::wp::primary_selection::zv1::client::zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
+    },
+};
+use tracing::warn;
+
+use crate::state::State;
+
+/// Text mime types, in order of preference.
+const TEXT_MIME_TYPES: [&str; 5] = [
+    "text/plain;charset=utf-8",
+    "UTF8_STRING",
+    "text/plain",
+    "STRING",
+    "TEXT",
+];
+
+/// How long to wait for the selection owner to send its contents.
+const READ_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Text offered by another client's selection.
+#[derive(Default)]
+enum OfferedText {
+    /// Nothing is offered, or it isn't text.
+    #[default]
+    None,
+    /// The text is being read.
+    Reading {
+        read: PipeRead,
+        /// Called with the text once it's read.
+        waiters: Vec<Box<dyn FnOnce(Option<String>)>>,
+    },
+    /// The text was read, or `None` if reading it failed.
+    Read(Option<String>),
+}
+
+/// The clipboard and primary selection shared by all surfaces.
+pub struct ClipboardState {
+    conn: Connection,
+    queue_handle: QueueHandle<State>,
+
+    data_device_manager: Option<DataDeviceManagerState>,
+    primary_selection_manager: Option<PrimarySelectionManagerState>,
+
+    data_device: Option<DataDevice>,
+    primary_selection_device: Option<PrimarySelectionDevice>,
+
+    /// The selection we currently own, if any.
+    selection: Option<(CopyPasteSource, String)>,
+    /// The primary selection we currently own, if any.
+    primary_selection: Option<(PrimarySelectionSource, String)>,
+
+    /// The clipboard offered by other clients, read as soon as it's offered
+    /// so that widgets can paste it without waiting.
+    offered_selection: OfferedText,
+    /// The primary selection offered by other clients.
+    offered_primary_selection: OfferedText,
+}
+
+impl ClipboardState {
+    pub fn new(globals: &GlobalList, queue_handle: &QueueHandle<State>, conn: Connection) -> Self {
+        let data_device_manager = DataDeviceManagerState::bind(globals, queue_handle)
+            .inspect_err(|err| warn!("Clipboard is unavailable: {err}"))
+            .ok();
+        let primary_selection_manager = PrimarySelectionManagerState::bind(globals, queue_handle)
+            .inspect_err(|err| warn!("Primary selection is unavailable: {err}"))
+            .ok();
+
+        Self {
+            conn,
+            queue_handle: queue_handle.clone(),
+            data_device_manager,
+            primary_selection_manager,
+            data_device: None,
+            primary_selection_device: None,
+            selection: None,
+            primary_selection: None,
+            offered_selection: OfferedText::None,
+            offered_primary_selection: OfferedText::None,
+        }
+    }
+
+    /// Creates data devices for the given seat.
+    pub fn set_seat(&mut self, seat: &WlSeat) {
+        self.data_device = self
+            .data_device_manager
+            .as_ref()
+            .map(|manager| manager.get_data_device(&self.queue_handle, seat));
+        self.primary_selection_device = self
+            .primary_selection_manager
+            .as_ref()
+            .map(|manager| manager.get_selection_device(&self.queue_handle, seat));
+    }
+
+    /// Returns the text contents of the clipboard or primary selection.
+    ///
+    /// If another client's contents are still being read, this returns `None`.
+    /// Use [`ClipboardState::read_with`] to wait for them.
+    pub fn read(&self, kind: Kind) -> Option<String> {
+        if let Some(contents) = self.owned(kind) {
+            return Some(contents.to_string());
+        }
+
+        match self.offered(kind) {
+            OfferedText::Read(text) => text.clone(),
+            OfferedText::None | OfferedText::Reading { .. } => None,
+        }
+    }
+
+    /// Calls `callback` with the text contents of the clipboard or primary selection,
+    /// once another client's contents have been read.
+    pub fn read_with(&mut self, kind: Kind, callback: impl FnOnce(Option<String>) + 'static) {
+        if let Some(contents) = self.owned(kind) {
+            callback(Some(contents.to_string()));
+            return;
+        }
+
+        match self.offered_mut(kind) {
+            OfferedText::None => callback(None),
+            OfferedText::Reading { waiters, .. } => waiters.push(Box::new(callback)),
+            OfferedText::Read(text) => callback(text.clone()),
+        }
+    }
+
+    /// Starts reading the clipboard or primary selection another client just offered.
+    pub(crate) fn selection_offered(
+        &mut self,
+        kind: Kind,
+        loop_handle: &LoopHandle<'static, State>,
+    ) {
+        // Whoever was waiting for the previous offer gets this one instead
+        let waiters = match std::mem::take(self.offered_mut(kind)) {
+            OfferedText::Reading { read, waiters } => {
+                read.cancel(loop_handle);
+                waiters
+            }
+            OfferedText::None | OfferedText::Read(_) => Vec::new(),
+        };
+
+        let read = self.receive(kind).and_then(|pipe| {
+            // The selection owner only sees the request once it's flushed.
+            if let Err(err) = self.conn.flush() {
+                warn!("Failed to flush wayland connection: {err}");
+            }
+
+            read_pipe(loop_handle, pipe, move |state: &mut State, text| {
+                let waiters = match std::mem::replace(
+                    state.clipboard.borrow_mut().offered_mut(kind),
+                    OfferedText::Read(text.clone()),
+                ) {
+                    OfferedText::Reading { waiters, .. } => waiters,
+                    OfferedText::None | OfferedText::Read(_) => Vec::new(),
+                };
+
+                for waiter in waiters {
+                    waiter(text.clone());
+                }
+            })
+        });
+
+        match read {
+            Some(read) => *self.offered_mut(kind) = OfferedText::Reading { read, waiters },
+            None => {
+                for waiter in waiters {
+                    waiter(None);
+                }
+            }
+        }
+    }
+
+    fn owned(&self, kind: Kind) -> Option<&str> {
+        match kind {
+            Kind::Standard => self
+                .selection
+                .as_ref()
+                .map(|(_, contents)| contents.as_str()),
+            Kind::Primary => self
+                .primary_selection
+                .as_ref()
+                .map(|(_, contents)| contents.as_str()),
+        }
+    }
+
+    fn offered(&self, kind: Kind) -> &OfferedText {
+        match kind {
+            Kind::Standard => &self.offered_selection,
+            Kind::Primary => &self.offered_primary_selection,
+        }
+    }
+
+    fn offered_mut(&mut self, kind: Kind) -> &mut OfferedText {
+        match kind {
+            Kind::Standard => &mut self.offered_selection,
+            Kind::Primary => &mut self.offered_primary_selection,
+        }
+    }
+
+    /// Asks the owner of the clipboard or primary selection to send its text.
+    fn receive(&self, kind: Kind) -> Option<File> {
+        let pipe = match kind {
+            Kind::Standard => {
+                let offer = self.data_device.as_ref()?.data().selection_offer()?;
+                let mime_type = offer.with_mime_types(preferred_mime_type)?;
+                offer
+                    .receive(mime_type)
+                    .inspect_err(|err| warn!("Failed to receive clipboard contents: {err}"))
+                    .ok()?
+            }
+            Kind::Primary => {
+                let offer = self
+                    .primary_selection_device
+                    .as_ref()?
+                    .data()
+                    .selection_offer()?;
+                let mime_type = offer.with_mime_types(preferred_mime_type)?;
+                offer
+                    .receive(mime_type)
+                    .inspect_err(|err| warn!("Failed to receive primary selection: {err}"))
+                    .ok()?
+            }
+        };
+
+        Some(File::from(OwnedFd::from(pipe)))
+    }
+
+    /// Takes ownership of the clipboard or primary selection with the given text.
+    ///
+    /// `serial` must be the serial of a recent input event on one of our surfaces,
+    /// otherwise the compositor will ignore the request.
+    ///
+    /// Returns `false` if the compositor doesn't support this kind of clipboard.
+    pub fn write(&mut self, kind: Kind, contents: String, serial: u32) -> bool {
+        match kind {
+            Kind::Standard => {
+                let (Some(manager), Some(device)) =
+                    (self.data_device_manager.as_ref(), self.data_device.as_ref())
+                else {
+                    return false;
+                };
+
+                let source = manager.create_copy_paste_source(&self.queue_handle, TEXT_MIME_TYPES);
+                source.set_selection(device, serial);
+                self.selection = Some((source, contents));
+            }
+            Kind::Primary => {
+                let (Some(manager), Some(device)) = (
+                    self.primary_selection_manager.as_ref(),
+                    self.primary_selection_device.as_ref(),
+                ) else {
+                    return false;
+                };
+
+                let source = manager.create_selection_source(&self.queue_handle, TEXT_MIME_TYPES);
+                source.set_selection(device, serial);
+                self.primary_selection = Some((source, contents));
+            }
+        }
+
+        true
+    }
+
+    /// Sends the clipboard contents to another client.
+    pub(crate) fn send_selection(
+        &self,
+        loop_handle: &LoopHandle<'static, State>,
+        source: &WlDataSource,
+        mime_type: &str,
+        pipe: WritePipe,
+    ) {
+        if let Some((_, contents)) = self
+            .selection
+            .as_ref()
+            .filter(|(selection, _)| selection.inner() == source)
+        {
+            write_pipe(loop_handle, contents, mime_type, pipe);
+        }
+    }
+
+    /// Sends the primary selection contents to another client.
+    pub(crate) fn send_primary_selection(
+        &self,
+        loop_handle: &LoopHandle<'static, State>,
+        source: &ZwpPrimarySelectionSourceV1,
+        mime_type: &str,
+        pipe: WritePipe,
+    ) {
+        if let Some((_, contents)) = self
+            .primary_selection
+            .as_ref()
+            .filter(|(selection, _)| selection.inner() == source)
+        {
+            write_pipe(loop_handle, contents, mime_type, pipe);
+        }
+    }
+
+    /// Called when another client took the clipboard from us.
+    pub(crate) fn selection_cancelled(&mut self, source: &WlDataSource) {
+        if self
+            .selection
+            .as_ref()
+            .is_some_and(|(selection, _)| selection.inner() == source)
+        {
+            self.selection = None;
+        }
+    }
+
+    /// Called when another client took the primary selection from us.
+    pub(crate) fn primary_selection_cancelled(&mut self, source: &ZwpPrimarySelectionSourceV1) {
+        if self
+            .primary_selection
+            .as_ref()
+            .is_some_and(|(selection, _)| selection.inner() == source)
+        {
+            self.primary_selection = None;
+        }
+    }
+}
+
+fn preferred_mime_type(offered: &[String]) -> Option<String> {
+    TEXT_MIME_TYPES
+        .into_iter()
+        .find(|mime_type| offered.iter().any(|offered| offered == mime_type))
+        .map(ToString::to_string)
+}
+
+/// Sends `contents` through `pipe` as the event loop finds it writable,
+/// so a client that doesn't read the pipe can't block us.
+fn write_pipe(
+    loop_handle: &LoopHandle<'static, State>,
+    contents: &str,
+    mime_type: &str,
+    pipe: WritePipe,
+) {
+    if !TEXT_MIME_TYPES.contains(&mime_type) {
+        return;
+    }
+
+    if let Err(err) = set_nonblocking(&pipe) {
+        warn!("Failed to send clipboard contents: {err}");
+        return;
+    }
+
+    let contents = contents.as_bytes().to_vec();
+    let mut written = 0;
+
+    let res = loop_handle.insert_source(pipe, move |(), file, _state| {
+        loop {
+            match (&**file).write(&contents[written..]) {
+                Ok(len) => {
+                    written += len;
+                    if written == contents.len() {
+                        return PostAction::Remove;
+                    }
+                }
+                Err(err) if err.kind() == ErrorKind::Interrupted => (),
+                Err(err) if err.kind() == ErrorKind::WouldBlock => return PostAction::Continue,
+                Err(err) => {
+                    warn!("Failed to send clipboard contents: {err}");
+                    return PostAction::Remove;
+                }
+            }
+        }
+    });
+
+    if let Err(err) = res {
+        warn!("Failed to send clipboard contents: {}", err.error);
+    }
+}
+
+/// A pipe being read by [`read_pipe`].
+struct PipeRead {
+    pipe: RegistrationToken,
+    timeout: Rc<Cell<Option<RegistrationToken>>>,
+}
+
+impl PipeRead {
+    /// Stops reading without calling the callback. Dropping the pipe closes our end.
+    fn cancel<D>(self, loop_handle: &LoopHandle<'static, D>) {
+        loop_handle.remove(self.pipe);
+        if let Some(timeout) = self.timeout.take() {
+            loop_handle.remove(timeout);
+        }
+    }
+}
+
+/// Reads `pipe` as the event loop finds it readable, then calls `on_done` with its
+/// contents.
+///
+/// A misbehaving selection owner may never close its end of the pipe, so this gives up
+/// and calls `on_done` with `None` after [`READ_TIMEOUT`].
+fn read_pipe<D: 'static>(
+    loop_handle: &LoopHandle<'static, D>,
+    pipe: File,
+    on_done: impl FnOnce(&mut D, Option<String>) + 'static,
+) -> Option<PipeRead> {
+    if let Err(err) = set_nonblocking(&pipe) {
+        warn!("Failed to read clipboard contents: {err}");
+        return None;
+    }
+
+    // Whichever of the pipe and the timeout finishes first removes the other
+    let on_done: Rc<Cell<Option<Box<dyn FnOnce(&mut D, Option<String>)>>>> =
+        Rc::new(Cell::new(Some(Box::new(on_done))));
+    let timeout = Rc::new(Cell::new(None));
+
+    let mut contents = Vec::new();
+
+    let pipe_token = loop_handle.insert_source(Generic::new(pipe, Interest::READ, Mode::Level), {
+        let loop_handle = loop_handle.clone();
+        let on_done = on_done.clone();
+        let timeout = timeout.clone();
+
+        move |_, file, data| {
+            let mut buf = [0; 4096];
+
+            let text = loop {
+                match (&**file).read(&mut buf) {
+                    Ok(0) => break Some(String::from_utf8_lossy(&contents).into_owned()),
+                    Ok(len) => contents.extend_from_slice(&buf[..len]),
+                    Err(err) if err.kind() == ErrorKind::Interrupted => (),
+                    Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                        return Ok(PostAction::Continue);
+                    }
+                    Err(err) => {
+                        warn!("Failed to read clipboard contents: {err}");
+                        break None;
+                    }
+                }
+            };
+
+            if let Some(timeout) = timeout.take() {
+                loop_handle.remove(timeout);
+            }
+            if let Some(on_done) = on_done.take() {
+                on_done(data, text);
+            }
+
+            Ok(PostAction::Remove)
+        }
+    });
+
+    let pipe_token = match pipe_token {
+        Ok(token) => token,
+        Err(err) => {
+            warn!("Failed to read clipboard contents: {}", err.error);
+            return None;
+        }
+    };
+
+    let timeout_token = loop_handle.insert_source(Timer::from_duration(READ_TIMEOUT), {
+        let loop_handle = loop_handle.clone();
+
+        move |_, _, data| {
+            warn!("Timed out reading clipboard contents");
+
+            loop_handle.remove(pipe_token);
+            if let Some(on_done) = on_done.take() {
+                on_done(data, None);
+            }
+
+            TimeoutAction::Drop
+        }
+    });
+
+    match timeout_token {
+        Ok(token) => timeout.set(Some(token)),
+        Err(err) => {
+            warn!("Failed to read clipboard contents: {}", err.error);
+            loop_handle.remove(pipe_token);
+            return None;
+        }
+    }
+
+    Some(PipeRead {
+        pipe: pipe_token,
+        timeout,
+    })
+}
+
+fn set_nonblocking(fd: impl AsFd) -> rustix::io::Result<()> {
+    let flags = rustix::fs::fcntl_getfl(&fd)?;
+    rustix::fs::fcntl_setfl(&fd, flags | OFlags::NONBLOCK)
+}
+
+/// A surface's view of the clipboard.
+///
+/// Writes use the serial of the last input event the surface received.
+pub struct WaylandClipboard {
+    state: Rc<RefCell<ClipboardState>>,
+    serial: Option<u32>,
+}
+
+impl WaylandClipboard {
+    pub fn new(state: Rc<RefCell<ClipboardState>>) -> Self {
+        Self {
+            state,
+            serial: None,
+        }
+    }
+
+    pub fn set_serial(&mut self, serial: Option<u32>) {
+        self.serial = serial;
+    }
+}
 
 impl iced_wgpu::core::Clipboard for WaylandClipboard {
-    fn read(&self, _kind: iced_wgpu::core::clipboard::Kind) -> Option<String> {
-        None
+    fn read(&self, kind: Kind) -> Option<String> {
+        self.state.borrow().read(kind)
+    }
+
+    fn write(&mut self, kind: Kind, contents: String) {
+        let Some(serial) = self.serial else {
+            warn!("Cannot set the clipboard without a recent input event");
+            return;
+        };
+
+        self.state.borrow_mut().write(kind, contents, serial);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use smithay_client_toolkit::reexports::calloop::EventLoop;
+
+    use super::*;
+
+    /// What the read finished with, if it finished.
+    type Finished = Option<Option<String>>;
+
+    fn start_read(event_loop: &EventLoop<'static, Finished>) -> (PipeRead, std::io::PipeWriter) {
+        let (reader, writer) = std::io::pipe().unwrap();
+        let read = read_pipe(
+            &event_loop.handle(),
+            File::from(OwnedFd::from(reader)),
+            |finished: &mut Finished, text| *finished = Some(text),
+        )
+        .unwrap();
+
+        (read, writer)
+    }
+
+    fn dispatch_for(
+        event_loop: &mut EventLoop<'static, Finished>,
+        finished: &mut Finished,
+        duration: Duration,
+    ) {
+        let end = std::time::Instant::now() + duration;
+        while std::time::Instant::now() < end && finished.is_none() {
+            event_loop
+                .dispatch(Duration::from_millis(5), finished)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn pipes_are_read_until_closed() {
+        let mut event_loop = EventLoop::try_new().unwrap();
+        let mut finished = None;
+
+        let (_read, mut writer) = start_read(&event_loop);
+
+        writer.write_all(b"hello ").unwrap();
+        dispatch_for(&mut event_loop, &mut finished, Duration::from_millis(50));
+        assert_eq!(finished, None);
+
+        writer.write_all(b"world").unwrap();
+        drop(writer);
+        dispatch_for(&mut event_loop, &mut finished, READ_TIMEOUT);
+
+        assert_eq!(finished, Some(Some("hello world".to_string())));
     }
 
-    fn write(&mut self, _kind: iced_wgpu::core::clipboard::Kind, _contents: String) {}
+    #[test]
+    fn pipes_that_stay_open_time_out() {
+        let mut event_loop = EventLoop::try_new().unwrap();
+        let mut finished = None;
+
+        let (_read, mut writer) = start_read(&event_loop);
+        writer.write_all(b"never finished").unwrap();
+
+        dispatch_for(&mut event_loop, &mut finished, READ_TIMEOUT * 2);
+        assert_eq!(finished, Some(None));
+
+        // Our end was closed
+        assert!(writer.write_all(b"more").is_err());
+    }
+
+    #[test]
+    fn cancelled_reads_never_finish() {
+        let mut event_loop = EventLoop::try_new().unwrap();
+        let mut finished = None;
+
+        let (read, writer) = start_read(&event_loop);
+        read.cancel(&event_loop.handle());
+        drop(writer);
+
+        dispatch_for(&mut event_loop, &mut finished, READ_TIMEOUT * 2);
+        assert_eq!(finished, None);
+    }
 }
@@ -1,3 +1,12 @@
+use std::sync::Once;
+
+use tracing::warn;
+
+const UBUNTU_REGULAR: &[u8] = include_bytes!("../resources/fonts/Ubuntu-Regular.ttf");
+const UBUNTU_BOLD: &[u8] = include_bytes!("../resources/fonts/Ubuntu-Bold.ttf");
+const UBUNTU_ITALIC: &[u8] = include_bytes!("../resources/fonts/Ubuntu-Italic.ttf");
+const UBUNTU_BOLD_ITALIC: &[u8] = include_bytes!("../resources/fonts/Ubuntu-BoldItalic.ttf");
+
 pub type Compositor = iced_renderer::fallback::Compositor<
     crate::wgpu::Compositor,
     iced_tiny_skia::window::Compositor,
@@ -8,3 +17,64 @@ pub type Surface = iced_renderer::fallback::Surface<
     iced_wgpu::window::Surface<'static>,
     iced_tiny_skia::window::Surface,
 >;
+
+/// Environment variable used to pick a renderer, either `wgpu` or `tiny-skia`.
+pub const RENDERER_ENV: &str = "SNOWCAP_RENDERER";
+
+/// Which renderer surfaces should use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RendererKind {
+    /// Use wgpu, falling back to tiny-skia if it fails to initialize.
+    #[default]
+    Auto,
+    /// Always use tiny-skia.
+    TinySkia,
+}
+
+impl RendererKind {
+    /// Reads the renderer from [`RENDERER_ENV`], also honoring iced's `ICED_BACKEND`.
+    pub fn from_env() -> Self {
+        let backend = std::env::var(RENDERER_ENV).or_else(|_| std::env::var("ICED_BACKEND"));
+
+        match backend.as_deref() {
+            Ok("tiny-skia") => Self::TinySkia,
+            Ok("wgpu") | Err(_) => Self::Auto,
+            Ok(other) => {
+                warn!("Unknown renderer `{other}`, expected `wgpu` or `tiny-skia`");
+                Self::Auto
+            }
+        }
+    }
+}
+
+/// Creates a software compositor.
+pub fn new_tiny_skia(window: impl iced_graphics::compositor::Window + Clone) -> Compositor {
+    let tiny_skia =
+        iced_tiny_skia::window::compositor::new(iced_graphics::Settings::default().into(), window);
+
+    load_fonts();
+
+    Compositor::Secondary(tiny_skia)
+}
+
+/// Loads the bundled fonts into the global font system.
+///
+/// Both renderers share the font system, so this only does anything the first time.
+pub fn load_fonts() {
+    static LOAD_FONTS: Once = Once::new();
+
+    LOAD_FONTS.call_once(|| {
+        let mut font_system = iced_graphics::text::font_system()
+            .write()
+            .expect("font system lock was poisoned");
+
+        for font in [
+            UBUNTU_REGULAR,
+            UBUNTU_BOLD,
+            UBUNTU_ITALIC,
+            UBUNTU_BOLD_ITALIC,
+        ] {
+            font_system.load_font(font.into());
+        }
+    });
+}
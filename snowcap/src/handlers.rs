@@ -1,18 +1,33 @@
 pub mod decoration;
 pub mod foreign_toplevel_list;
 pub mod foreign_toplevel_management;
+pub mod image_copy_capture;
 pub mod keyboard;
 pub mod pointer;
 
+use iced_wgpu::core::clipboard::Kind;
 use smithay_client_toolkit::{
     compositor::CompositorHandler,
-    delegate_compositor, delegate_layer, delegate_output, delegate_registry, delegate_seat,
-    delegate_xdg_popup, delegate_xdg_shell,
+    data_device_manager::{
+        WritePipe,
+        data_device::DataDeviceHandler,
+        data_offer::{DataOfferHandler, DragOffer},
+        data_source::DataSourceHandler,
+    },
+    delegate_compositor, delegate_data_device, delegate_layer, delegate_output,
+    delegate_primary_selection, delegate_registry, delegate_seat, delegate_xdg_popup,
+    delegate_xdg_shell,
     output::{OutputHandler, OutputState},
+    primary_selection::{
+        device::PrimarySelectionDeviceHandler, selection::PrimarySelectionSourceHandler,
+    },
     reexports::{
         client::{
             Connection, Dispatch, QueueHandle, delegate_noop,
             protocol::{
+                wl_data_device::WlDataDevice,
+                wl_data_device_manager::DndAction,
+                wl_data_source::WlDataSource,
                 wl_output::{self, WlOutput},
                 wl_region::WlRegion,
                 wl_seat::WlSeat,
@@ -24,6 +39,10 @@ use smithay_client_toolkit::{
                 wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1,
                 wp_fractional_scale_v1::{self, WpFractionalScaleV1},
             },
+            primary_selection::zv1::client::{
+                zwp_primary_selection_device_v1::ZwpPrimarySelectionDeviceV1,
+                zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
+            },
             viewporter::client::{wp_viewport::WpViewport, wp_viewporter::WpViewporter},
         },
     },
@@ -61,6 +80,7 @@ impl SeatHandler for State {
         // support one seat as well, but could be improved either by picking the best seat (the one
         // with the most desirable capabilities), or having the user pick a seat by name.
         if self.seat.is_none() {
+            self.clipboard.borrow_mut().set_seat(&seat);
             self.seat = Some(seat);
         }
     }
@@ -458,3 +478,159 @@ impl PopupHandler for State {
     }
 }
 delegate_xdg_popup!(State);
+
+impl DataDeviceHandler for State {
+    fn enter(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _data_device: &WlDataDevice,
+        _x: f64,
+        _y: f64,
+        _wl_surface: &WlSurface,
+    ) {
+    }
+
+    fn leave(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _data_device: &WlDataDevice) {}
+
+    fn motion(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _data_device: &WlDataDevice,
+        _x: f64,
+        _y: f64,
+    ) {
+    }
+
+    fn selection(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _data_device: &WlDataDevice,
+    ) {
+        self.clipboard
+            .borrow_mut()
+            .selection_offered(Kind::Standard, &self.loop_handle);
+    }
+
+    fn drop_performed(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _data_device: &WlDataDevice,
+    ) {
+    }
+}
+
+impl DataOfferHandler for State {
+    fn source_actions(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _offer: &mut DragOffer,
+        _actions: DndAction,
+    ) {
+    }
+
+    fn selected_action(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _offer: &mut DragOffer,
+        _actions: DndAction,
+    ) {
+    }
+}
+
+impl DataSourceHandler for State {
+    fn accept_mime(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _source: &WlDataSource,
+        _mime: Option<String>,
+    ) {
+    }
+
+    fn send_request(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        source: &WlDataSource,
+        mime: String,
+        fd: WritePipe,
+    ) {
+        self.clipboard
+            .borrow()
+            .send_selection(&self.loop_handle, source, &mime, fd);
+    }
+
+    fn cancelled(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, source: &WlDataSource) {
+        self.clipboard.borrow_mut().selection_cancelled(source);
+    }
+
+    fn dnd_dropped(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _source: &WlDataSource) {
+    }
+
+    fn dnd_finished(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _source: &WlDataSource,
+    ) {
+    }
+
+    fn action(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _source: &WlDataSource,
+        _action: DndAction,
+    ) {
+    }
+}
+delegate_data_device!(State);
+
+impl PrimarySelectionDeviceHandler for State {
+    fn selection(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _primary_selection_device: &ZwpPrimarySelectionDeviceV1,
+    ) {
+        self.clipboard
+            .borrow_mut()
+            .selection_offered(Kind::Primary, &self.loop_handle);
+    }
+}
+
+impl PrimarySelectionSourceHandler for State {
+    fn send_request(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        source: &ZwpPrimarySelectionSourceV1,
+        mime: String,
+        write_pipe: WritePipe,
+    ) {
+        self.clipboard.borrow().send_primary_selection(
+            &self.loop_handle,
+            source,
+            &mime,
+            write_pipe,
+        );
+    }
+
+    fn cancelled(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        source: &ZwpPrimarySelectionSourceV1,
+    ) {
+        self.clipboard
+            .borrow_mut()
+            .primary_selection_cancelled(source);
+    }
+}
+delegate_primary_selection!(State);
@@ -0,0 +1,124 @@
+use smithay_client_toolkit::{
+    delegate_shm,
+    reexports::{
+        client::{Dispatch, WEnum, delegate_noop, protocol::wl_buffer::WlBuffer},
+        protocols::ext::{
+            image_capture_source::v1::client::{
+                ext_foreign_toplevel_image_capture_source_manager_v1::ExtForeignToplevelImageCaptureSourceManagerV1,
+                ext_image_capture_source_v1::ExtImageCaptureSourceV1,
+            },
+            image_copy_capture::v1::client::{
+                ext_image_copy_capture_frame_v1::{
+                    self, ExtImageCopyCaptureFrameV1, FailureReason,
+                },
+                ext_image_copy_capture_manager_v1::ExtImageCopyCaptureManagerV1,
+                ext_image_copy_capture_session_v1::{self, ExtImageCopyCaptureSessionV1},
+            },
+        },
+    },
+    shm::{Shm, ShmHandler},
+};
+use tracing::warn;
+
+use crate::state::State;
+
+impl ShmHandler for State {
+    fn shm_state(&mut self) -> &mut Shm {
+        &mut self.shm
+    }
+}
+delegate_shm!(State);
+
+delegate_noop!(State: ExtForeignToplevelImageCaptureSourceManagerV1);
+delegate_noop!(State: ExtImageCaptureSourceV1);
+delegate_noop!(State: ExtImageCopyCaptureManagerV1);
+delegate_noop!(State: ignore WlBuffer);
+
+impl Dispatch<ExtImageCopyCaptureSessionV1, ()> for State {
+    fn event(
+        state: &mut Self,
+        proxy: &ExtImageCopyCaptureSessionV1,
+        event: <ExtImageCopyCaptureSessionV1 as smithay_client_toolkit::reexports::client::Proxy>::Event,
+        _data: &(),
+        _conn: &smithay_client_toolkit::reexports::client::Connection,
+        qhandle: &smithay_client_toolkit::reexports::client::QueueHandle<Self>,
+    ) {
+        let Some(capture) = state
+            .toplevel_capture_state
+            .as_mut()
+            .and_then(|capture_state| {
+                capture_state
+                    .captures
+                    .iter_mut()
+                    .find(|capture| &capture.session == proxy)
+            })
+        else {
+            return;
+        };
+
+        match event {
+            ext_image_copy_capture_session_v1::Event::BufferSize { width, height } => {
+                capture.set_buffer_size(width, height);
+            }
+            ext_image_copy_capture_session_v1::Event::ShmFormat { format } => {
+                if let WEnum::Value(format) = format {
+                    capture.add_shm_format(format);
+                }
+            }
+            ext_image_copy_capture_session_v1::Event::DmabufDevice { .. } => (),
+            ext_image_copy_capture_session_v1::Event::DmabufFormat { .. } => (),
+            ext_image_copy_capture_session_v1::Event::Done => {
+                capture.constraints_done(&state.shm, qhandle);
+            }
+            ext_image_copy_capture_session_v1::Event::Stopped => capture.stop(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Dispatch<ExtImageCopyCaptureFrameV1, ()> for State {
+    fn event(
+        state: &mut Self,
+        proxy: &ExtImageCopyCaptureFrameV1,
+        event: <ExtImageCopyCaptureFrameV1 as smithay_client_toolkit::reexports::client::Proxy>::Event,
+        _data: &(),
+        _conn: &smithay_client_toolkit::reexports::client::Connection,
+        _qhandle: &smithay_client_toolkit::reexports::client::QueueHandle<Self>,
+    ) {
+        let Some(capture) = state
+            .toplevel_capture_state
+            .as_mut()
+            .and_then(|capture_state| {
+                capture_state
+                    .captures
+                    .iter_mut()
+                    .find(|capture| capture.frame.as_ref() == Some(proxy))
+            })
+        else {
+            return;
+        };
+
+        match event {
+            ext_image_copy_capture_frame_v1::Event::Ready => {
+                if let Some(identifier) = capture.frame_ready() {
+                    let session = capture.session.clone();
+                    state.toplevel_frame_captured(&identifier, &session);
+                }
+            }
+            ext_image_copy_capture_frame_v1::Event::Failed { reason } => {
+                capture.frame_failed();
+
+                match reason {
+                    // New constraints will follow, and `done` will capture again.
+                    WEnum::Value(FailureReason::BufferConstraints) => (),
+                    WEnum::Value(FailureReason::Stopped) => capture.stop(),
+                    _ => warn!("Toplevel capture failed: {reason:?}"),
+                }
+            }
+            ext_image_copy_capture_frame_v1::Event::Transform { .. } => (),
+            ext_image_copy_capture_frame_v1::Event::Damage { .. } => (),
+            ext_image_copy_capture_frame_v1::Event::PresentationTime { .. } => (),
+            _ => unreachable!(),
+        }
+    }
+}
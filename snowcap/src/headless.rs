@@ -0,0 +1,61 @@
+//! Rendering widgets without a Wayland connection.
+//!
+//! Views are rendered with tiny-skia into an RGBA buffer, so widget trees can be
+//! snapshot-tested on machines without a GPU or a running compositor.
+
+use iced::{Color, Font, Pixels, Size, mouse::Cursor};
+
+use crate::{compositor::Renderer, widget::SnowcapWidgetProgram};
+
+/// A rendered view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The size of the snapshot in physical pixels.
+    pub size: Size<u32>,
+    /// Unpremultiplied RGBA pixels, row by row.
+    pub rgba: Vec<u8>,
+}
+
+impl Snapshot {
+    /// Returns the RGBA color of the pixel at the given physical coordinates.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.size.width || y >= self.size.height {
+            return None;
+        }
+
+        let index = ((y * self.size.width + x) * 4) as usize;
+        self.rgba.get(index..index + 4)?.try_into().ok()
+    }
+}
+
+/// Renders a program's view at the given scale.
+///
+/// Like a layer surface, the snapshot is sized to fit the view's layout within `max_size`.
+///
+/// Text uses the bundled Ubuntu font rather than the system default font
+/// so snapshots don't depend on the fonts installed on the machine.
+pub fn render(program: &mut SnowcapWidgetProgram, max_size: Size<u32>, scale: f32) -> Snapshot {
+    crate::compositor::load_fonts();
+
+    let mut renderer = Renderer::Secondary(iced_tiny_skia::Renderer::new(
+        Font::with_name("Ubuntu"),
+        Pixels(16.0),
+    ));
+
+    let _ = program.rebuild_ui(max_size, &mut renderer, None);
+    program.draw(&mut renderer, Cursor::Unavailable);
+
+    let viewport = program.viewport(scale);
+
+    let Renderer::Secondary(tiny_skia) = &mut renderer else {
+        unreachable!("headless rendering always uses tiny-skia");
+    };
+
+    let rgba =
+        iced_tiny_skia::window::compositor::screenshot(tiny_skia, &viewport, Color::TRANSPARENT);
+
+    Snapshot {
+        size: viewport.physical_size(),
+        rgba,
+    }
+}
@@ -1,4 +1,4 @@
-use std::num::NonZeroU32;
+use std::{mem, num::NonZeroU32};
 
 use iced_runtime::core::widget;
 use smithay_client_toolkit::{
@@ -45,6 +45,16 @@ impl State {
         self.layers.iter_mut().find(|layer| layer.layer_id == id)
     }
 
+    /// Returns the output with the given name, if snowcap knows about it.
+    pub fn output_for_name(&self, name: &str) -> Option<WlOutput> {
+        self.output_state.outputs().find(|output| {
+            self.output_state
+                .info(output)
+                .and_then(|info| info.name)
+                .is_some_and(|output_name| output_name == name)
+        })
+    }
+
     pub fn layer_destroy(&mut self, id: LayerId) {
         let to_destroy: Vec<_> = self
             .popups
@@ -64,6 +74,64 @@ impl State {
 
         self.layers.retain(|p| p.layer_id != id);
     }
+
+    /// Moves a layer to another output.
+    ///
+    /// Layer surfaces can't change outputs once created, so this recreates the
+    /// underlying surface and carries the widgets over.
+    pub fn layer_set_output(&mut self, id: LayerId, output: Option<WlOutput>) {
+        let Some(index) = self.layers.iter().position(|layer| layer.layer_id == id) else {
+            return;
+        };
+
+        if self.layers[index].wl_output == output && output.is_some() {
+            return;
+        }
+
+        let to_destroy: Vec<_> = self
+            .popups
+            .iter()
+            .filter(|p| p.parent_id == ParentId::Layer(id))
+            .map(|p| p.popup_id)
+            .collect();
+
+        for popup_id in to_destroy {
+            self.popup_destroy(popup_id);
+        }
+
+        let mut surface =
+            SnowcapSurface::new(self, Box::new(|| iced::widget::Column::new().into()), false);
+
+        let initial_size = output
+            .as_ref()
+            .and_then(|output| self.output_state.info(output))
+            .and_then(|info| info.logical_size)
+            .map(|(w, h)| iced::Size::new(w as u32, h as u32));
+
+        let layer = &mut self.layers[index];
+
+        mem::swap(&mut surface.widgets, &mut layer.surface.widgets);
+        surface.widget_event_sender = layer.surface.widget_event_sender.take();
+
+        let layer_surface = self.layer_shell_state.create_layer_surface(
+            &self.queue_handle,
+            surface.wl_surface.clone(),
+            layer.properties.layer,
+            Some("snowcap"),
+            output.as_ref(),
+        );
+
+        layer_surface.set_size(1, 1);
+        layer.properties.apply(&layer_surface);
+        layer_surface.commit();
+
+        // The old surface needs to be dropped before the old layer surface.
+        layer.surface = surface;
+        layer.layer = layer_surface;
+
+        layer.wl_output = output;
+        layer.initial_configure = InitialConfigureState::PreConfigure(initial_size);
+    }
 }
 
 pub struct SnowcapLayer {
@@ -82,6 +150,8 @@ pub struct SnowcapLayer {
 
     pub wl_output: Option<WlOutput>,
 
+    properties: LayerProperties,
+
     pub keyboard_key_sender: Option<UnboundedSender<KeyboardKey>>,
     pub pointer_button_sender: Option<UnboundedSender<Result<PointerButtonResponse, Status>>>,
     pub layer_event_sender: Option<UnboundedSender<Vec<LayerEvent>>>,
@@ -106,15 +176,57 @@ pub enum ExclusiveZone {
     Ignore,
 }
 
+impl From<ExclusiveZone> for i32 {
+    fn from(value: ExclusiveZone) -> Self {
+        match value {
+            ExclusiveZone::Exclusive(size) => size.get() as i32,
+            ExclusiveZone::Respect => 0,
+            ExclusiveZone::Ignore => -1,
+        }
+    }
+}
+
+/// The distance between a layer surface and the edges it is anchored to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Margin {
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+    pub left: i32,
+}
+
+/// Layer surface state that has to be reapplied when the surface is recreated.
+#[derive(Debug, Copy, Clone)]
+pub struct LayerProperties {
+    pub layer: wlr_layer::Layer,
+    pub anchor: Anchor,
+    pub exclusive_zone: ExclusiveZone,
+    pub keyboard_interactivity: wlr_layer::KeyboardInteractivity,
+    pub margin: Margin,
+}
+
+impl LayerProperties {
+    /// Applies everything except the z layer, which is given at creation.
+    fn apply(&self, layer: &LayerSurface) {
+        layer.set_anchor(self.anchor);
+        layer.set_keyboard_interactivity(self.keyboard_interactivity);
+        layer.set_exclusive_zone(self.exclusive_zone.into());
+        layer.set_margin(
+            self.margin.top,
+            self.margin.right,
+            self.margin.bottom,
+            self.margin.left,
+        );
+    }
+}
+
 impl SnowcapLayer {
     pub fn new(
         state: &mut State,
         // COMPAT: 0.1
         max_size: Option<(u32, u32)>,
-        layer: wlr_layer::Layer,
-        anchor: Anchor,
-        exclusive_zone: ExclusiveZone,
-        keyboard_interactivity: wlr_layer::KeyboardInteractivity,
+        output: Option<WlOutput>,
+        properties: LayerProperties,
         widgets: ViewFn,
     ) -> Self {
         let surface = SnowcapSurface::new(state, widgets, false);
@@ -122,22 +234,24 @@ impl SnowcapLayer {
         let layer = state.layer_shell_state.create_layer_surface(
             &state.queue_handle,
             surface.wl_surface.clone(),
-            layer,
+            properties.layer,
             Some("snowcap"),
-            None,
+            output.as_ref(),
         );
 
         layer.set_size(1, 1);
-        layer.set_anchor(anchor);
-        layer.set_keyboard_interactivity(keyboard_interactivity);
-        layer.set_exclusive_zone(match exclusive_zone {
-            ExclusiveZone::Exclusive(size) => size.get() as i32,
-            ExclusiveZone::Respect => 0,
-            ExclusiveZone::Ignore => -1,
-        });
+        properties.apply(&layer);
 
         layer.commit();
 
+        // If we already know the output, we don't have to wait for the
+        // surface to enter it to get its size.
+        let initial_size = output
+            .as_ref()
+            .and_then(|output| state.output_state.info(output))
+            .and_then(|info| info.logical_size)
+            .map(|(w, h)| iced::Size::new(w as u32, h as u32));
+
         let next_id = state.layer_id_counter.next();
 
         Self {
@@ -146,12 +260,13 @@ impl SnowcapLayer {
             max_size: max_size.map(|(w, h)| iced::Size::new(w, h)),
             output_size: iced::Size::new(1, 1),
             pending_output_size: None,
-            wl_output: None,
+            wl_output: output,
+            properties,
             layer_id: next_id,
             keyboard_key_sender: None,
             pointer_button_sender: None,
             layer_event_sender: None,
-            initial_configure: InitialConfigureState::PreConfigure(None),
+            initial_configure: InitialConfigureState::PreConfigure(initial_size),
         }
     }
 
@@ -169,6 +284,7 @@ impl SnowcapLayer {
         anchor: Option<Anchor>,
         exclusive_zone: Option<ExclusiveZone>,
         keyboard_interactivity: Option<wlr_layer::KeyboardInteractivity>,
+        margin: Option<Margin>,
         widgets: Option<ViewFn>,
     ) {
         if let Some(widgets) = widgets {
@@ -176,26 +292,33 @@ impl SnowcapLayer {
         }
 
         if let Some(layer) = layer {
+            self.properties.layer = layer;
             self.layer.set_layer(layer);
         }
 
         if let Some(anchor) = anchor {
+            self.properties.anchor = anchor;
             self.layer.set_anchor(anchor);
         }
 
         if let Some(zone) = exclusive_zone {
-            self.layer.set_exclusive_zone(match zone {
-                ExclusiveZone::Exclusive(size) => size.get() as i32,
-                ExclusiveZone::Respect => 0,
-                ExclusiveZone::Ignore => -1,
-            });
+            self.properties.exclusive_zone = zone;
+            self.layer.set_exclusive_zone(zone.into());
         }
 
         if let Some(keyboard_interactivity) = keyboard_interactivity {
+            self.properties.keyboard_interactivity = keyboard_interactivity;
             self.layer
                 .set_keyboard_interactivity(keyboard_interactivity);
         }
 
+        if let Some(margin) = margin {
+            self.properties.margin = margin;
+            self.layer
+                .set_margin(margin.top, margin.right, margin.bottom, margin.left);
+            self.surface.invalidate_layout();
+        }
+
         self.surface.request_frame();
     }
 
@@ -233,13 +356,30 @@ impl SnowcapLayer {
     }
 
     pub fn widget_bounds(&self) -> iced::Size<u32> {
+        let Margin {
+            top,
+            right,
+            bottom,
+            left,
+        } = self.properties.margin;
+
+        // Negative margins let the surface go past the output's edges,
+        // so only positive ones shrink the available space.
+        let horizontal = (left.max(0) + right.max(0)) as u32;
+        let vertical = (top.max(0) + bottom.max(0)) as u32;
+
+        let available = iced::Size::new(
+            self.output_size.width.saturating_sub(horizontal).max(1),
+            self.output_size.height.saturating_sub(vertical).max(1),
+        );
+
         if let Some(max_size) = self.max_size {
             iced::Size::new(
-                self.output_size.width.min(max_size.width),
-                self.output_size.height.min(max_size.height),
+                available.width.min(max_size.width),
+                available.height.min(max_size.height),
             )
         } else {
-            self.output_size
+            available
         }
     }
 
@@ -3,6 +3,7 @@ pub mod clipboard;
 pub mod compositor;
 pub mod decoration;
 pub mod handlers;
+pub mod headless;
 pub mod input;
 pub mod layer;
 pub mod popup;
@@ -10,6 +11,8 @@ pub mod runtime;
 pub mod server;
 pub mod state;
 pub mod surface;
+pub mod theme;
+pub mod toplevel_capture;
 pub mod util;
 pub mod wgpu;
 pub mod widget;
@@ -44,12 +47,16 @@ impl SnowcapHandle {
     }
 }
 
-pub fn start(stop_signal_sender: Option<tokio::sync::oneshot::Sender<SnowcapHandle>>) {
+pub fn start(
+    stop_signal_sender: Option<tokio::sync::oneshot::Sender<SnowcapHandle>>,
+    renderer_kind: compositor::RendererKind,
+) {
     info!("Snowcap starting up");
 
     let mut event_loop = EventLoop::<State>::try_new().unwrap();
 
-    let mut state = State::new(event_loop.handle(), event_loop.get_signal()).unwrap();
+    let mut state =
+        State::new(event_loop.handle(), event_loop.get_signal(), renderer_kind).unwrap();
 
     state.start_grpc_server(socket_dir()).unwrap();
 
@@ -104,6 +111,7 @@ pub fn start(stop_signal_sender: Option<tokio::sync::oneshot::Sender<SnowcapHand
                 state.keyboard_focus = None;
             }
 
+            state.update_toplevel_captures();
             state.update_surfaces();
         })
         .unwrap();
@@ -1,3 +1,4 @@
+use snowcap::compositor::RendererKind;
 use tracing_subscriber::EnvFilter;
 
 #[tokio::main]
@@ -9,7 +10,7 @@ async fn main() -> anyhow::Result<()> {
         .with_env_filter(env_filter)
         .init();
 
-    tokio::task::spawn_blocking(|| snowcap::start(None))
+    tokio::task::spawn_blocking(|| snowcap::start(None, RendererKind::from_env()))
         .await
         .unwrap();
 
@@ -2,14 +2,14 @@ use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 use smithay_client_toolkit::reexports::calloop;
-use snowcap_api_defs::snowcap::{decoration, input, layer, popup, widget};
+use snowcap_api_defs::snowcap::{decoration, input, layer, popup, theme, widget};
 use tokio::task::JoinHandle;
 use tracing::{error, info};
 
 use crate::{
     api::{
         decoration::DecorationService, input::InputService, layer::LayerService,
-        popup::PopupService, widget::WidgetService,
+        popup::PopupService, theme::ThemeService, widget::WidgetService,
     },
     state::State,
 };
@@ -65,6 +65,7 @@ impl State {
         let widget_service = WidgetService::new(grpc_sender.clone());
         let decoration_service = DecorationService::new(grpc_sender.clone());
         let popup_service = PopupService::new(grpc_sender.clone());
+        let theme_service = ThemeService::new(grpc_sender.clone());
 
         let refl_service = tonic_reflection::server::Builder::configure()
             .register_encoded_file_descriptor_set(snowcap_api_defs::FILE_DESCRIPTOR_SET)
@@ -101,6 +102,9 @@ impl State {
             )
             .add_service(popup::v1::popup_service_server::PopupServiceServer::new(
                 popup_service,
+            ))
+            .add_service(theme::v1::theme_service_server::ThemeServiceServer::new(
+                theme_service,
             ));
 
         let join_handle = tokio::spawn(async move {
@@ -1,3 +1,5 @@
+use std::{cell::RefCell, rc::Rc};
+
 use anyhow::Context;
 use iced::keyboard::key::{NativeCode, Physical};
 use iced_futures::Runtime;
@@ -30,11 +32,15 @@ use smithay_client_toolkit::{
     registry::RegistryState,
     seat::{SeatState, keyboard::Modifiers, pointer::cursor_shape::CursorShapeManager},
     shell::{WaylandSurface, wlr_layer::LayerShell, xdg::XdgShell},
+    shm::Shm,
 };
 use snowcap_protocols::snowcap_decoration_v1::client::snowcap_decoration_manager_v1::SnowcapDecorationManagerV1;
+use tracing::warn;
 use xkbcommon::xkb::Keysym;
 
 use crate::{
+    clipboard::ClipboardState,
+    compositor::RendererKind,
     decoration::{DecorationIdCounter, SnowcapDecoration},
     handlers::{foreign_toplevel_list::ForeignToplevelListHandleData, keyboard::KeyboardFocus},
     layer::{LayerIdCounter, SnowcapLayer},
@@ -42,6 +48,8 @@ use crate::{
     runtime::{CalloopSenderSink, CurrentTokioExecutor},
     server::GrpcServerState,
     surface::{self, CalloopNotifier},
+    theme::ThemeState,
+    toplevel_capture::ToplevelCaptureState,
     widget::SnowcapMessage,
 };
 
@@ -65,11 +73,14 @@ pub struct State {
     pub xdg_shell: XdgShell,
     pub cursor_shape_manager: CursorShapeManager,
     pub cursor_shape_device: Option<WpCursorShapeDeviceV1>,
+    pub shm: Shm,
+    pub toplevel_capture_state: Option<ToplevelCaptureState>,
 
     pub grpc_server_state: Option<GrpcServerState>,
 
     pub queue_handle: QueueHandle<State>,
 
+    pub renderer_kind: RendererKind,
     pub compositor: Option<crate::compositor::Compositor>,
     pub tiny_skia: Option<crate::compositor::Compositor>,
 
@@ -78,6 +89,7 @@ pub struct State {
     pub popups: Vec<SnowcapPopup>,
 
     pub seat: Option<WlSeat>,
+    pub clipboard: Rc<RefCell<ClipboardState>>,
     // TODO: per wl_keyboard
     pub keyboard_focus: Option<KeyboardFocus>,
     pub keyboard_modifiers: Modifiers,
@@ -93,12 +105,15 @@ pub struct State {
 
     pub foreign_toplevel_list_handles:
         Vec<(ExtForeignToplevelHandleV1, ForeignToplevelListHandleData)>,
+
+    pub theme: ThemeState,
 }
 
 impl State {
     pub fn new(
         loop_handle: LoopHandle<'static, State>,
         loop_signal: LoopSignal,
+        mut renderer_kind: RendererKind,
     ) -> anyhow::Result<Self> {
         let conn =
             Connection::connect_to_env().context("failed to establish wayland connection")?;
@@ -120,6 +135,20 @@ impl State {
         let foreign_toplevel_list: ExtForeignToplevelListV1 =
             globals.bind(&queue_handle, 1..=1, ()).unwrap();
         let cursor_shape_manager = CursorShapeManager::bind(&globals, &queue_handle).unwrap();
+        let shm = Shm::bind(&globals, &queue_handle).unwrap();
+
+        // Toplevel capture is optional; images of toplevels stay empty without it.
+        let toplevel_capture_state = globals
+            .bind(&queue_handle, 1..=1, ())
+            .and_then(|source_manager| {
+                Ok(ToplevelCaptureState {
+                    source_manager,
+                    copy_capture_manager: globals.bind(&queue_handle, 1..=1, ())?,
+                    captures: Vec::new(),
+                })
+            })
+            .inspect_err(|err| warn!("Toplevel capture is unavailable: {err}"))
+            .ok();
 
         let xdg_shell = XdgShell::bind(&globals, &queue_handle).unwrap();
 
@@ -167,9 +196,17 @@ impl State {
         let shell = iced_graphics::Shell::new(notifier);
 
         // Attempt to create a wgpu renderer upfront; this takes a non-trivial amount of time to do
-        let compositor = crate::wgpu::Compositor::new(shell.clone())
-            .ok()
-            .map(crate::compositor::Compositor::Primary);
+        let compositor = match renderer_kind {
+            RendererKind::Auto => match crate::wgpu::Compositor::new(shell.clone()) {
+                Ok(compositor) => Some(crate::compositor::Compositor::Primary(compositor)),
+                Err(err) => {
+                    warn!("Failed to initialize wgpu, falling back to tiny-skia: {err:#}");
+                    renderer_kind = RendererKind::TinySkia;
+                    None
+                }
+            },
+            RendererKind::TinySkia => None,
+        };
 
         let (sender, recv) = calloop::channel::channel::<(iced::window::Id, SnowcapMessage)>();
         let mut runtime = Runtime::new(CurrentTokioExecutor, CalloopSenderSink::new(sender));
@@ -253,6 +290,28 @@ impl State {
                             text: None,
                         }),
                     )),
+                    // Programs can't tell when modifiers are released before they get
+                    // keyboard focus otherwise, and the modifiers sent with the release
+                    // of a modifier key still include it.
+                    iced::Event::Keyboard(iced::keyboard::Event::ModifiersChanged(modifiers)) => {
+                        Some((
+                            id,
+                            SnowcapMessage::KeyboardKey(crate::handlers::keyboard::KeyboardKey {
+                                key: Keysym::NoSymbol,
+                                modifiers: Modifiers {
+                                    ctrl: modifiers.control(),
+                                    alt: modifiers.alt(),
+                                    shift: modifiers.shift(),
+                                    caps_lock: false,
+                                    logo: modifiers.logo(),
+                                    num_lock: false,
+                                },
+                                pressed: false,
+                                captured,
+                                text: None,
+                            }),
+                        ))
+                    }
                     _ => None,
                 }
             }),
@@ -260,6 +319,11 @@ impl State {
 
         let seat = seat_state.seats().next();
 
+        let mut clipboard = ClipboardState::new(&globals, &queue_handle, conn.clone());
+        if let Some(seat) = seat.as_ref() {
+            clipboard.set_seat(seat);
+        }
+
         let state = State {
             loop_handle,
             loop_signal,
@@ -280,15 +344,19 @@ impl State {
             xdg_shell,
             cursor_shape_manager,
             cursor_shape_device: None,
+            shm,
+            toplevel_capture_state,
 
             grpc_server_state: None,
             queue_handle,
+            renderer_kind,
             compositor,
             tiny_skia: None,
             layers: Vec::new(),
             decorations: Vec::new(),
             popups: Vec::new(),
             seat,
+            clipboard: Rc::new(RefCell::new(clipboard)),
             keyboard_focus: None,
             keyboard_modifiers: smithay_client_toolkit::seat::keyboard::Modifiers::default(),
             keyboard: None,
@@ -299,6 +367,7 @@ impl State {
             decoration_id_counter: DecorationIdCounter::default(),
             popup_id_counter: PopupIdCounter::default(),
             foreign_toplevel_list_handles: Vec::new(),
+            theme: ThemeState::default(),
         };
 
         Ok(state)
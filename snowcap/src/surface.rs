@@ -19,10 +19,11 @@ use smithay_client_toolkit::{
     },
 };
 use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
 
 use crate::{
     clipboard::WaylandClipboard,
-    compositor::{Renderer, Surface},
+    compositor::{Renderer, RendererKind, Surface},
     state::State,
     util::BlockOnTokio,
     widget::{SnowcapMessage, SnowcapWidgetProgram, ViewFn, WidgetEvent, WidgetId},
@@ -97,16 +98,15 @@ impl SnowcapSurface {
         let display_handle = DisplayHandle::new(&state.conn);
 
         let compositor = if force_tiny_skia {
-            state.tiny_skia.get_or_insert_with(|| {
-                let tiny_skia = iced_tiny_skia::window::compositor::new(
-                    iced_graphics::Settings::default().into(),
-                    window_handle,
-                );
-
-                crate::compositor::Compositor::Secondary(tiny_skia)
-            })
+            state
+                .tiny_skia
+                .get_or_insert_with(|| crate::compositor::new_tiny_skia(window_handle))
         } else {
             state.compositor.get_or_insert_with(|| {
+                if state.renderer_kind == RendererKind::TinySkia {
+                    return crate::compositor::new_tiny_skia(window_handle);
+                }
+
                 crate::compositor::Compositor::new(
                     iced_graphics::Settings::default(),
                     display_handle,
@@ -114,7 +114,10 @@ impl SnowcapSurface {
                     state.shell.clone(),
                 )
                 .block_on_tokio()
-                .unwrap()
+                .unwrap_or_else(|err| {
+                    warn!("Failed to initialize wgpu, falling back to tiny-skia: {err}");
+                    crate::compositor::new_tiny_skia(window_handle)
+                })
             })
         };
 
@@ -122,7 +125,7 @@ impl SnowcapSurface {
 
         let iced_surface = compositor.create_surface(window_handle, 1, 1);
 
-        let clipboard = WaylandClipboard;
+        let clipboard = WaylandClipboard::new(state.clipboard.clone());
 
         let widgets = SnowcapWidgetProgram::new(widgets);
 
@@ -303,6 +306,8 @@ impl SnowcapSurface {
             return update_status;
         }
 
+        self.clipboard.set_serial(self.focus_serial);
+
         let Some((state, statuses)) = self.widgets.update(
             cursor,
             &mut self.renderer,
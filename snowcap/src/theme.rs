@@ -0,0 +1,581 @@
+//! The global theme.
+//!
+//! Widgets that don't set a color, font, text size, corner radius, or spacing of their own
+//! fall back to the theme of the current variant. Changing the theme rebuilds every surface.
+//!
+//! Themes can be loaded from a TOML file:
+//!
+//! ```toml
+//! variant = "dark"
+//!
+//! [dark]
+//! font = "Ubuntu"
+//! text_size = 14.0
+//! radius = 8.0
+//! spacing = 4.0
+//!
+//! [dark.palette]
+//! background = "#303446"
+//! text = "#c6d0f5"
+//! primary = "#8caaee"
+//!
+//! [light.palette]
+//! background = "#eff1f5"
+//! ```
+
+use std::{
+    cell::RefCell,
+    ffi::OsStr,
+    mem::MaybeUninit,
+    os::{fd::OwnedFd, unix::ffi::OsStrExt},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use anyhow::Context;
+use rustix::{
+    fs::inotify::{self, CreateFlags, WatchFlags},
+    io::Errno,
+};
+use serde::Deserialize;
+use smithay_client_toolkit::reexports::calloop::{
+    Interest, Mode, PostAction, RegistrationToken, generic::Generic,
+};
+use snowcap_api_defs::snowcap::{theme, widget};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::{error, info, warn};
+
+use crate::{state::State, util::convert::FromApi};
+
+const WATCH_FLAGS: WatchFlags = WatchFlags::CLOSE_WRITE
+    .union(WatchFlags::CREATE)
+    .union(WatchFlags::MOVED_TO)
+    .union(WatchFlags::ONLYDIR);
+
+thread_local! {
+    static CURRENT: RefCell<Rc<Resolved>> =
+        RefCell::new(Rc::new(Resolved::new(Variant::Dark, &Default::default())));
+}
+
+/// Returns the theme that views are built and drawn with.
+pub fn current() -> Rc<Resolved> {
+    CURRENT.with_borrow(Rc::clone)
+}
+
+/// Replaces the theme that views are built and drawn with.
+///
+/// This does not rebuild existing views.
+pub fn set_current(variant: Variant, theme: &theme::v1::Theme) {
+    CURRENT.set(Rc::new(Resolved::new(variant, theme)));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl FromApi<theme::v1::Variant> for Option<Variant> {
+    fn from_api(api_type: theme::v1::Variant) -> Self {
+        match api_type {
+            theme::v1::Variant::Unspecified => None,
+            theme::v1::Variant::Dark => Some(Variant::Dark),
+            theme::v1::Variant::Light => Some(Variant::Light),
+        }
+    }
+}
+
+impl From<Variant> for theme::v1::Variant {
+    fn from(value: Variant) -> Self {
+        match value {
+            Variant::Dark => theme::v1::Variant::Dark,
+            Variant::Light => theme::v1::Variant::Light,
+        }
+    }
+}
+
+/// A theme with snowcap's defaults filled in.
+#[derive(Debug)]
+pub struct Resolved {
+    /// The theme iced widgets take their default styles from.
+    pub iced: iced::Theme,
+    /// The color of text without a color of its own.
+    pub text_color: iced::Color,
+    pub font: Option<iced::Font>,
+    pub text_size: Option<f32>,
+    pub radius: Option<f32>,
+    pub spacing: Option<f32>,
+}
+
+impl Resolved {
+    fn new(variant: Variant, theme: &theme::v1::Theme) -> Self {
+        let (base, base_text_color) = match variant {
+            Variant::Dark => (iced::Theme::CatppuccinFrappe, iced::Color::WHITE),
+            Variant::Light => (
+                iced::Theme::CatppuccinLatte,
+                iced::theme::Palette::CATPPUCCIN_LATTE.text,
+            ),
+        };
+
+        let (iced, text_color) = match theme.palette {
+            Some(palette) if palette != Default::default() => {
+                let text_color = palette.text.map(iced::Color::from_api);
+                let palette = palette_over(base.palette(), palette);
+                (
+                    iced::Theme::custom(format!("snowcap {variant:?}"), palette),
+                    text_color.unwrap_or(base_text_color),
+                )
+            }
+            _ => (base, base_text_color),
+        };
+
+        Self {
+            iced,
+            text_color,
+            font: theme.font.clone().map(iced::Font::from_api),
+            text_size: theme.text_size,
+            radius: theme.radius,
+            spacing: theme.spacing,
+        }
+    }
+}
+
+impl Resolved {
+    /// Returns the iced theme with the colors set in `palette` swapped in.
+    pub fn iced_with_palette(&self, palette: widget::v1::Palette) -> iced::Theme {
+        let widget::v1::Palette {
+            background,
+            text,
+            primary,
+            success,
+            warning,
+            danger,
+        } = palette;
+
+        let palette = palette_over(
+            self.iced.palette(),
+            theme::v1::Palette {
+                background,
+                text,
+                primary,
+                success,
+                warning,
+                danger,
+            },
+        );
+
+        iced::Theme::custom(format!("{} with palette", self.iced), palette)
+    }
+}
+
+/// Styles with corners that follow the theme's radius.
+pub trait Rounded {
+    /// Sets the corner radius of this style to `radius` if it is set.
+    fn rounded(self, radius: Option<f32>) -> Self;
+}
+
+impl Rounded for iced::widget::container::Style {
+    fn rounded(mut self, radius: Option<f32>) -> Self {
+        if let Some(radius) = radius {
+            self.border = self.border.rounded(radius);
+        }
+        self
+    }
+}
+
+impl Rounded for iced::widget::button::Style {
+    fn rounded(mut self, radius: Option<f32>) -> Self {
+        if let Some(radius) = radius {
+            self.border = self.border.rounded(radius);
+        }
+        self
+    }
+}
+
+impl Rounded for iced::widget::text_input::Style {
+    fn rounded(mut self, radius: Option<f32>) -> Self {
+        if let Some(radius) = radius {
+            self.border = self.border.rounded(radius);
+        }
+        self
+    }
+}
+
+impl Rounded for iced::widget::pick_list::Style {
+    fn rounded(mut self, radius: Option<f32>) -> Self {
+        if let Some(radius) = radius {
+            self.border = self.border.rounded(radius);
+        }
+        self
+    }
+}
+
+/// Replaces the colors of `base` with the ones set in `palette`.
+fn palette_over(base: iced::theme::Palette, palette: theme::v1::Palette) -> iced::theme::Palette {
+    let color = |color: Option<widget::v1::Color>, base| color.map_or(base, iced::Color::from_api);
+
+    iced::theme::Palette {
+        background: color(palette.background, base.background),
+        text: color(palette.text, base.text),
+        primary: color(palette.primary, base.primary),
+        success: color(palette.success, base.success),
+        warning: color(palette.warning, base.warning),
+        danger: color(palette.danger, base.danger),
+    }
+}
+
+/// The themes of both variants and the clients listening for changes.
+#[derive(Default)]
+pub struct ThemeState {
+    pub dark: theme::v1::Theme,
+    pub light: theme::v1::Theme,
+    pub variant: Variant,
+    pub watchers: Vec<UnboundedSender<(Variant, theme::v1::Theme)>>,
+    file_watch: Option<FileWatch>,
+}
+
+impl ThemeState {
+    /// Returns the theme of the current variant.
+    pub fn current(&self) -> &theme::v1::Theme {
+        match self.variant {
+            Variant::Dark => &self.dark,
+            Variant::Light => &self.light,
+        }
+    }
+}
+
+struct FileWatch {
+    path: PathBuf,
+    token: RegistrationToken,
+}
+
+impl State {
+    /// Sets the theme of `variant`, or of both variants if `None`.
+    pub fn set_theme(&mut self, variant: Option<Variant>, theme: theme::v1::Theme) {
+        match variant {
+            Some(Variant::Dark) => self.theme.dark = theme,
+            Some(Variant::Light) => self.theme.light = theme,
+            None => {
+                self.theme.dark = theme.clone();
+                self.theme.light = theme;
+            }
+        }
+
+        self.theme_changed();
+    }
+
+    pub fn set_theme_variant(&mut self, variant: Variant) {
+        if self.theme.variant == variant {
+            return;
+        }
+
+        self.theme.variant = variant;
+        self.theme_changed();
+    }
+
+    /// Loads both variants from the TOML file at `path`.
+    ///
+    /// If `watch` is set, the file is loaded again whenever it changes.
+    pub fn load_theme(&mut self, path: PathBuf, watch: bool) -> anyhow::Result<()> {
+        let file = ThemeFile::load(&path)?;
+
+        if let Some(file_watch) = self.theme.file_watch.take() {
+            self.loop_handle.remove(file_watch.token);
+        }
+
+        if watch {
+            match self.watch_theme_file(&path) {
+                Ok(token) => {
+                    info!("Watching {} for theme changes", path.display());
+                    self.theme.file_watch = Some(FileWatch { path, token });
+                }
+                Err(err) => warn!("Failed to watch {}: {err}", path.display()),
+            }
+        }
+
+        self.apply_theme_file(file);
+
+        Ok(())
+    }
+
+    fn apply_theme_file(&mut self, file: ThemeFile) {
+        self.theme.dark = file.dark.into();
+        self.theme.light = file.light.into();
+        if let Some(variant) = file.variant {
+            self.theme.variant = variant.into();
+        }
+
+        self.theme_changed();
+    }
+
+    fn watch_theme_file(&mut self, path: &Path) -> anyhow::Result<RegistrationToken> {
+        // Editors often replace files instead of writing to them, so the file
+        // is watched through its directory.
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let file_name = path.file_name().context("theme path has no file name")?;
+        let file_name = file_name.to_os_string();
+
+        let inotify_fd = inotify::init(CreateFlags::CLOEXEC | CreateFlags::NONBLOCK)?;
+        inotify::add_watch(&inotify_fd, dir, WATCH_FLAGS)?;
+
+        let token = self
+            .loop_handle
+            .insert_source(
+                Generic::new(inotify_fd, Interest::READ, Mode::Level),
+                move |_, inotify_fd, state| {
+                    if read_events(inotify_fd, &file_name) {
+                        state.reload_theme_file();
+                    }
+                    Ok(PostAction::Continue)
+                },
+            )
+            .map_err(|err| err.error)?;
+
+        Ok(token)
+    }
+
+    fn reload_theme_file(&mut self) {
+        let Some(file_watch) = self.theme.file_watch.as_ref() else {
+            return;
+        };
+
+        match ThemeFile::load(&file_watch.path) {
+            Ok(file) => {
+                info!("Reloaded theme from {}", file_watch.path.display());
+                self.apply_theme_file(file);
+            }
+            // Keep the last good theme around until the file is fixed
+            Err(err) => error!("{err:#}"),
+        }
+    }
+
+    /// Rebuilds all surfaces with the current theme and notifies watchers.
+    fn theme_changed(&mut self) {
+        let variant = self.theme.variant;
+        let theme = self.theme.current().clone();
+
+        set_current(variant, &theme);
+
+        for layer in self.layers.iter_mut() {
+            layer.surface.invalidate_layout();
+            layer.surface.request_frame();
+        }
+        for deco in self.decorations.iter_mut() {
+            deco.surface.invalidate_layout();
+            deco.surface.request_frame();
+        }
+        for popup in self.popups.iter_mut() {
+            popup.surface.invalidate_layout();
+            popup.surface.request_frame();
+        }
+
+        self.theme
+            .watchers
+            .retain(|watcher| watcher.send((variant, theme.clone())).is_ok());
+    }
+}
+
+/// Drains pending inotify events, returning whether `file_name` changed.
+fn read_events(inotify_fd: &OwnedFd, file_name: &OsStr) -> bool {
+    let mut buf = [MaybeUninit::uninit(); 4096];
+    let mut reader = inotify::Reader::new(inotify_fd, &mut buf);
+
+    let mut changed = false;
+
+    loop {
+        let event = match reader.next() {
+            Ok(event) => event,
+            Err(Errno::AGAIN) => break,
+            Err(Errno::INTR) => continue,
+            Err(err) => {
+                warn!("Failed to read theme watcher events: {err}");
+                break;
+            }
+        };
+
+        if event
+            .file_name()
+            .is_some_and(|name| OsStr::from_bytes(name.to_bytes()) == file_name)
+        {
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeFile {
+    variant: Option<FileVariant>,
+    dark: FileTheme,
+    light: FileTheme,
+}
+
+impl ThemeFile {
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read theme at {}", path.display()))?;
+        toml::from_str(&data)
+            .with_context(|| format!("failed to parse theme at {}", path.display()))
+    }
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum FileVariant {
+    Dark,
+    Light,
+}
+
+impl From<FileVariant> for Variant {
+    fn from(value: FileVariant) -> Self {
+        match value {
+            FileVariant::Dark => Variant::Dark,
+            FileVariant::Light => Variant::Light,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct FileTheme {
+    palette: Option<FilePalette>,
+    /// A font family name.
+    font: Option<String>,
+    text_size: Option<f32>,
+    radius: Option<f32>,
+    spacing: Option<f32>,
+}
+
+impl From<FileTheme> for theme::v1::Theme {
+    fn from(value: FileTheme) -> Self {
+        Self {
+            palette: value.palette.map(From::from),
+            font: value.font.map(|name| widget::v1::Font {
+                family: Some(widget::v1::font::Family {
+                    family: Some(widget::v1::font::family::Family::Name(name)),
+                }),
+                ..Default::default()
+            }),
+            text_size: value.text_size,
+            radius: value.radius,
+            spacing: value.spacing,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct FilePalette {
+    background: Option<HexColor>,
+    text: Option<HexColor>,
+    primary: Option<HexColor>,
+    success: Option<HexColor>,
+    warning: Option<HexColor>,
+    danger: Option<HexColor>,
+}
+
+impl From<FilePalette> for theme::v1::Palette {
+    fn from(value: FilePalette) -> Self {
+        Self {
+            background: value.background.map(|color| color.0),
+            text: value.text.map(|color| color.0),
+            primary: value.primary.map(|color| color.0),
+            success: value.success.map(|color| color.0),
+            warning: value.warning.map(|color| color.0),
+            danger: value.danger.map(|color| color.0),
+        }
+    }
+}
+
+/// A color written as `#rrggbb` or `#rrggbbaa`.
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(try_from = "String")]
+struct HexColor(widget::v1::Color);
+
+impl TryFrom<String> for HexColor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let invalid = || format!("invalid color `{value}`, expected `#rrggbb` or `#rrggbbaa`");
+
+        let hex = value.strip_prefix('#').ok_or_else(invalid)?;
+        if !matches!(hex.len(), 6 | 8) {
+            return Err(invalid());
+        }
+
+        let channel = |index: usize| {
+            hex.get(index..index + 2)
+                .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+                .map(|channel| channel as f32 / 255.0)
+        };
+
+        Ok(Self(widget::v1::Color {
+            red: channel(0).ok_or_else(invalid)?,
+            green: channel(2).ok_or_else(invalid)?,
+            blue: channel(4).ok_or_else(invalid)?,
+            alpha: if hex.len() == 8 {
+                channel(6).ok_or_else(invalid)?
+            } else {
+                1.0
+            },
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_colors_parse() {
+        assert_eq!(
+            HexColor::try_from("#ff0080".to_string()),
+            Ok(HexColor(widget::v1::Color {
+                red: 1.0,
+                green: 0.0,
+                blue: 128.0 / 255.0,
+                alpha: 1.0,
+            }))
+        );
+        assert_eq!(
+            HexColor::try_from("#00000000".to_string()).map(|color| color.0.alpha),
+            Ok(0.0)
+        );
+        assert!(HexColor::try_from("ff0080".to_string()).is_err());
+        assert!(HexColor::try_from("#ff00".to_string()).is_err());
+        assert!(HexColor::try_from("#gg0000".to_string()).is_err());
+    }
+
+    #[test]
+    fn theme_file_parses() {
+        let file: ThemeFile = toml::from_str(
+            r##"
+            variant = "light"
+
+            [dark]
+            font = "Ubuntu"
+            radius = 8.0
+
+            [light.palette]
+            primary = "#0000ff"
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(file.variant, Some(FileVariant::Light));
+        assert_eq!(file.dark.font.as_deref(), Some("Ubuntu"));
+        assert_eq!(file.dark.radius, Some(8.0));
+        assert_eq!(file.light.palette.unwrap().primary.unwrap().0.blue, 1.0);
+    }
+
+    #[test]
+    fn unknown_theme_keys_are_rejected() {
+        assert!(toml::from_str::<ThemeFile>("[dark]\nradious = 8.0").is_err());
+    }
+}
@@ -0,0 +1,415 @@
+//! Streaming of toplevel contents into images.
+//!
+//! Image widgets that show a toplevel hold a [`CaptureTarget`]. Once per event loop
+//! iteration, a capture session is started for every live target and stopped for every
+//! dropped one. Frames are copied out of a shm buffer and stored on the target, after which
+//! the surfaces showing the target are rebuilt to pick them up.
+//! Each toplevel is captured at most once every [`MIN_FRAME_INTERVAL`].
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+use iced::widget::image;
+use smithay_client_toolkit::{
+    reexports::{
+        calloop::timer::{TimeoutAction, Timer},
+        client::{
+            QueueHandle,
+            protocol::{wl_buffer::WlBuffer, wl_shm},
+        },
+        protocols::ext::{
+            foreign_toplevel_list::v1::client::ext_foreign_toplevel_handle_v1::ExtForeignToplevelHandleV1,
+            image_capture_source::v1::client::{
+                ext_foreign_toplevel_image_capture_source_manager_v1::ExtForeignToplevelImageCaptureSourceManagerV1,
+                ext_image_capture_source_v1::ExtImageCaptureSourceV1,
+            },
+            image_copy_capture::v1::client::{
+                ext_image_copy_capture_frame_v1::ExtImageCopyCaptureFrameV1,
+                ext_image_copy_capture_manager_v1::{self, ExtImageCopyCaptureManagerV1},
+                ext_image_copy_capture_session_v1::ExtImageCopyCaptureSessionV1,
+            },
+        },
+    },
+    shm::{Shm, raw::RawPool},
+};
+use tracing::warn;
+
+use crate::state::State;
+
+/// The shortest time between two captures of the same toplevel.
+///
+/// Toplevels are usually shown as thumbnails, which don't need every frame.
+pub const MIN_FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+thread_local! {
+    static TARGETS: RefCell<HashMap<String, Weak<CaptureTarget>>> = RefCell::default();
+    /// The identifiers of the targets shown by the view being built, see [`collect_shown`].
+    static SHOWN: RefCell<Option<HashSet<String>>> = const { RefCell::new(None) };
+}
+
+/// A toplevel whose contents are shown by at least one widget.
+pub struct CaptureTarget {
+    identifier: String,
+    frame: RefCell<Option<image::Handle>>,
+}
+
+impl CaptureTarget {
+    /// Returns the target for the given foreign toplevel identifier, reusing a live one
+    /// so its latest frame survives view updates.
+    pub fn get_or_create(identifier: String) -> Rc<Self> {
+        TARGETS.with_borrow_mut(|targets| {
+            if let Some(target) = targets.get(&identifier).and_then(Weak::upgrade) {
+                return target;
+            }
+
+            targets.retain(|_, target| target.strong_count() > 0);
+
+            let target = Rc::new(Self {
+                identifier: identifier.clone(),
+                frame: RefCell::default(),
+            });
+            targets.insert(identifier, Rc::downgrade(&target));
+            target
+        })
+    }
+
+    /// Returns the latest captured frame, or a transparent pixel if nothing
+    /// has been captured yet.
+    pub fn frame(&self) -> image::Handle {
+        SHOWN.with_borrow_mut(|shown| {
+            if let Some(shown) = shown.as_mut() {
+                shown.insert(self.identifier.clone());
+            }
+        });
+
+        self.frame.borrow().clone().unwrap_or_else(empty_frame)
+    }
+
+    fn live_targets() -> Vec<Rc<Self>> {
+        TARGETS.with_borrow(|targets| targets.values().filter_map(Weak::upgrade).collect())
+    }
+}
+
+/// Builds a view with `build`, returning it along with the identifiers of the
+/// toplevels it shows.
+pub fn collect_shown<T>(build: impl FnOnce() -> T) -> (T, HashSet<String>) {
+    let outer = SHOWN.replace(Some(HashSet::new()));
+    let ret = build();
+    let shown = SHOWN.replace(outer).unwrap_or_default();
+    (ret, shown)
+}
+
+/// A transparent pixel to show in place of a toplevel that hasn't been captured.
+pub fn empty_frame() -> image::Handle {
+    image::Handle::from_rgba(1, 1, vec![0; 4])
+}
+
+/// Globals needed to capture toplevels. Absent if the compositor doesn't support them.
+pub struct ToplevelCaptureState {
+    pub source_manager: ExtForeignToplevelImageCaptureSourceManagerV1,
+    pub copy_capture_manager: ExtImageCopyCaptureManagerV1,
+    pub captures: Vec<ToplevelCapture>,
+}
+
+/// An ongoing capture of one toplevel.
+pub struct ToplevelCapture {
+    target: Weak<CaptureTarget>,
+    source: ExtImageCaptureSourceV1,
+    pub session: ExtImageCopyCaptureSessionV1,
+    pub frame: Option<ExtImageCopyCaptureFrameV1>,
+    pending_constraints: Constraints,
+    constraints: Option<Constraints>,
+    buffer: Option<ShmBuffer>,
+    stopped: bool,
+}
+
+#[derive(Default)]
+struct Constraints {
+    size: (u32, u32),
+    formats: Vec<wl_shm::Format>,
+}
+
+struct ShmBuffer {
+    pool: RawPool,
+    buffer: WlBuffer,
+    width: u32,
+    height: u32,
+    format: wl_shm::Format,
+}
+
+impl ToplevelCapture {
+    fn new(
+        state: &ToplevelCaptureState,
+        target: &Rc<CaptureTarget>,
+        toplevel: &ExtForeignToplevelHandleV1,
+        queue_handle: &QueueHandle<State>,
+    ) -> Self {
+        let source = state
+            .source_manager
+            .create_source(toplevel, queue_handle, ());
+        let session = state.copy_capture_manager.create_session(
+            &source,
+            ext_image_copy_capture_manager_v1::Options::empty(),
+            queue_handle,
+            (),
+        );
+
+        Self {
+            target: Rc::downgrade(target),
+            source,
+            session,
+            frame: None,
+            pending_constraints: Constraints::default(),
+            constraints: None,
+            buffer: None,
+            stopped: false,
+        }
+    }
+
+    pub fn set_buffer_size(&mut self, width: u32, height: u32) {
+        self.pending_constraints.size = (width, height);
+    }
+
+    pub fn add_shm_format(&mut self, format: wl_shm::Format) {
+        self.pending_constraints.formats.push(format);
+    }
+
+    /// Applies the constraints sent since the last `done` and captures a frame
+    /// if none is in flight.
+    pub fn constraints_done(&mut self, shm: &Shm, queue_handle: &QueueHandle<State>) {
+        self.constraints = Some(std::mem::take(&mut self.pending_constraints));
+
+        if self.frame.is_none() {
+            self.capture_frame(shm, queue_handle);
+        }
+    }
+
+    /// Starts capturing the next frame into the shm buffer, reallocating it
+    /// if the constraints changed.
+    pub fn capture_frame(&mut self, shm: &Shm, queue_handle: &QueueHandle<State>) {
+        if self.stopped || self.frame.is_some() {
+            return;
+        }
+
+        let Some(constraints) = self.constraints.as_ref() else {
+            return;
+        };
+
+        let (width, height) = constraints.size;
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        // Formats that are trivial to turn into RGBA.
+        let Some(format) = [
+            wl_shm::Format::Abgr8888,
+            wl_shm::Format::Xbgr8888,
+            wl_shm::Format::Argb8888,
+            wl_shm::Format::Xrgb8888,
+        ]
+        .into_iter()
+        .find(|format| constraints.formats.contains(format)) else {
+            warn!("No supported shm format for toplevel capture");
+            return;
+        };
+
+        let needs_new_buffer = self.buffer.as_ref().is_none_or(|buffer| {
+            buffer.width != width || buffer.height != height || buffer.format != format
+        });
+
+        if needs_new_buffer {
+            if let Some(buffer) = self.buffer.take() {
+                buffer.buffer.destroy();
+            }
+
+            let len = width as usize * height as usize * 4;
+            let mut pool = match RawPool::new(len, shm) {
+                Ok(pool) => pool,
+                Err(err) => {
+                    warn!("Failed to create shm pool for toplevel capture: {err}");
+                    return;
+                }
+            };
+            let buffer = pool.create_buffer(
+                0,
+                width as i32,
+                height as i32,
+                width as i32 * 4,
+                format,
+                (),
+                queue_handle,
+            );
+
+            self.buffer = Some(ShmBuffer {
+                pool,
+                buffer,
+                width,
+                height,
+                format,
+            });
+        }
+
+        let Some(buffer) = self.buffer.as_ref() else {
+            return;
+        };
+
+        let frame = self.session.create_frame(queue_handle, ());
+        frame.attach_buffer(&buffer.buffer);
+        frame.damage_buffer(0, 0, width as i32, height as i32);
+        frame.capture();
+
+        self.frame = Some(frame);
+    }
+
+    /// Copies the finished frame into the target as RGBA.
+    ///
+    /// Returns the target's identifier, or `None` if the target was dropped.
+    pub fn frame_ready(&mut self) -> Option<String> {
+        if let Some(frame) = self.frame.take() {
+            frame.destroy();
+        }
+
+        let (Some(target), Some(buffer)) = (self.target.upgrade(), self.buffer.as_mut()) else {
+            return None;
+        };
+
+        let len = buffer.width as usize * buffer.height as usize * 4;
+        let mut pixels = buffer.pool.mmap()[..len].to_vec();
+
+        // Shm formats are little-endian, so ARGB is stored as BGRA and ABGR as RGBA.
+        for pixel in pixels.chunks_exact_mut(4) {
+            match buffer.format {
+                wl_shm::Format::Argb8888 => pixel.swap(0, 2),
+                wl_shm::Format::Xrgb8888 => {
+                    pixel.swap(0, 2);
+                    pixel[3] = u8::MAX;
+                }
+                wl_shm::Format::Xbgr8888 => pixel[3] = u8::MAX,
+                _ => (),
+            }
+        }
+
+        target.frame.replace(Some(image::Handle::from_rgba(
+            buffer.width,
+            buffer.height,
+            pixels,
+        )));
+
+        Some(target.identifier.clone())
+    }
+
+    pub fn frame_failed(&mut self) {
+        if let Some(frame) = self.frame.take() {
+            frame.destroy();
+        }
+    }
+
+    /// Stops capturing. The capture stays around until its target is dropped
+    /// so it isn't restarted every iteration.
+    pub fn stop(&mut self) {
+        if self.stopped {
+            return;
+        }
+        self.stopped = true;
+
+        if let Some(frame) = self.frame.take() {
+            frame.destroy();
+        }
+        if let Some(buffer) = self.buffer.take() {
+            buffer.buffer.destroy();
+        }
+        self.session.destroy();
+        self.source.destroy();
+    }
+}
+
+impl State {
+    /// Rebuilds and redraws the surfaces showing the toplevel with the given identifier,
+    /// then captures its next frame once [`MIN_FRAME_INTERVAL`] has passed.
+    pub fn toplevel_frame_captured(
+        &mut self,
+        identifier: &str,
+        session: &ExtImageCopyCaptureSessionV1,
+    ) {
+        let surfaces = self
+            .layers
+            .iter_mut()
+            .map(|layer| &mut layer.surface)
+            .chain(self.decorations.iter_mut().map(|deco| &mut deco.surface))
+            .chain(self.popups.iter_mut().map(|popup| &mut popup.surface));
+
+        for surface in surfaces {
+            if surface.widgets.shows_toplevel(identifier) {
+                surface.invalidate_layout();
+                surface.schedule_redraw();
+            }
+        }
+
+        let session = session.clone();
+        let res = self.loop_handle.insert_source(
+            Timer::from_duration(MIN_FRAME_INTERVAL),
+            move |_, _, state| {
+                let Some(capture_state) = state.toplevel_capture_state.as_mut() else {
+                    return TimeoutAction::Drop;
+                };
+
+                if let Some(capture) = capture_state
+                    .captures
+                    .iter_mut()
+                    .find(|capture| capture.session == session)
+                {
+                    capture.capture_frame(&state.shm, &state.queue_handle);
+                }
+
+                TimeoutAction::Drop
+            },
+        );
+
+        if let Err(err) = res {
+            warn!("Failed to schedule toplevel capture: {}", err.error);
+        }
+    }
+
+    /// Starts capturing toplevels that widgets want to show and stops capturing
+    /// ones that are no longer shown.
+    pub fn update_toplevel_captures(&mut self) {
+        let Some(capture_state) = self.toplevel_capture_state.as_mut() else {
+            return;
+        };
+
+        capture_state.captures.retain_mut(|capture| {
+            let alive = capture.target.strong_count() > 0;
+            if !alive {
+                capture.stop();
+            }
+            alive
+        });
+
+        for target in CaptureTarget::live_targets() {
+            if capture_state
+                .captures
+                .iter()
+                .any(|capture| capture.target.as_ptr() == Rc::as_ptr(&target))
+            {
+                continue;
+            }
+
+            let Some(toplevel) = self
+                .foreign_toplevel_list_handles
+                .iter()
+                .find(|(_, data)| data.identifier() == Some(&target.identifier))
+                .map(|(handle, _)| handle)
+            else {
+                continue;
+            };
+
+            let capture =
+                ToplevelCapture::new(capture_state, &target, toplevel, &self.queue_handle);
+            capture_state.captures.push(capture);
+        }
+    }
+}
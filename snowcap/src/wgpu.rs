@@ -1,15 +1,9 @@
 use anyhow::Context;
 use iced::wgpu::ExperimentalFeatures;
-use iced_graphics::Compositor as _;
 use iced_wgpu::wgpu;
 
 use crate::util::BlockOnTokio;
 
-const UBUNTU_REGULAR: &[u8] = include_bytes!("../resources/fonts/Ubuntu-Regular.ttf");
-const UBUNTU_BOLD: &[u8] = include_bytes!("../resources/fonts/Ubuntu-Bold.ttf");
-const UBUNTU_ITALIC: &[u8] = include_bytes!("../resources/fonts/Ubuntu-Italic.ttf");
-const UBUNTU_BOLD_ITALIC: &[u8] = include_bytes!("../resources/fonts/Ubuntu-BoldItalic.ttf");
-
 pub struct Compositor {
     instance: wgpu::Instance,
     device: wgpu::Device,
@@ -64,7 +58,7 @@ impl Compositor {
             shell,
         );
 
-        let mut compositor = Compositor {
+        let compositor = Compositor {
             instance,
             device,
             adapter,
@@ -73,10 +67,7 @@ impl Compositor {
             engine,
         };
 
-        compositor.load_font(UBUNTU_REGULAR.into());
-        compositor.load_font(UBUNTU_BOLD.into());
-        compositor.load_font(UBUNTU_ITALIC.into());
-        compositor.load_font(UBUNTU_BOLD_ITALIC.into());
+        crate::compositor::load_fonts();
 
         Ok(compositor)
     }
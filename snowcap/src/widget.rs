@@ -1,11 +1,18 @@
+pub mod canvas;
 pub mod input_region;
+pub mod transition;
 
-use iced::{Color, Theme, event::Status};
+use std::collections::HashSet;
+
+use iced::event::Status;
 use iced_graphics::Viewport;
 use iced_wgpu::core::{Clipboard, layout::Limits, widget};
 use smithay_client_toolkit::reexports::client::{QueueHandle, protocol::wl_surface::WlSurface};
 
-use crate::{handlers::keyboard::KeyboardKey, state::State, widget::input_region::Collect};
+use crate::{
+    handlers::keyboard::KeyboardKey, state::State, theme, toplevel_capture,
+    widget::input_region::Collect,
+};
 
 pub type Element = iced::Element<'static, SnowcapMessage, iced::Theme, crate::compositor::Renderer>;
 pub type UserInterface =
@@ -42,6 +49,8 @@ pub struct SnowcapWidgetProgram {
     user_interface: Option<UserInterface>,
     queued_events: Vec<iced::Event>,
     size: iced::Size<u32>,
+    /// The identifiers of the toplevels the view shows.
+    shown_toplevels: HashSet<String>,
 }
 
 impl SnowcapWidgetProgram {
@@ -52,6 +61,7 @@ impl SnowcapWidgetProgram {
             user_interface: None,
             queued_events: Vec::new(),
             size: iced::Size::default(),
+            shown_toplevels: HashSet::new(),
         }
     }
 
@@ -59,6 +69,11 @@ impl SnowcapWidgetProgram {
         self.size
     }
 
+    /// Returns whether the view shows the toplevel with the given identifier.
+    pub fn shows_toplevel(&self, identifier: &str) -> bool {
+        self.shown_toplevels.contains(identifier)
+    }
+
     #[must_use]
     pub fn rebuild_ui(
         &mut self,
@@ -75,7 +90,8 @@ impl SnowcapWidgetProgram {
             .take()
             .map(|ui| ui.into_cache())
             .unwrap_or_default();
-        let mut view = (self.view)();
+        let (mut view, shown_toplevels) = toplevel_capture::collect_shown(|| (self.view)());
+        self.shown_toplevels = shown_toplevels;
         let mut tree = iced_wgpu::core::widget::Tree::empty();
         tree.diff(&view);
 
@@ -116,11 +132,13 @@ impl SnowcapWidgetProgram {
     }
 
     pub fn draw(&mut self, renderer: &mut iced_renderer::Renderer, cursor: iced::mouse::Cursor) {
+        let theme = theme::current();
+
         self.user_interface.as_mut().unwrap().draw(
             renderer,
-            &Theme::CatppuccinFrappe,
+            &theme.iced,
             &iced_wgpu::core::renderer::Style {
-                text_color: Color::WHITE,
+                text_color: theme.text_color,
             },
             cursor,
         );
@@ -218,6 +236,11 @@ pub enum WidgetEvent {
     Button,
     MouseArea(MouseAreaEvent),
     TextInput(TextInputEvent),
+    Slider(SliderEvent),
+    Checkbox(bool),
+    Toggler(bool),
+    PickList(String),
+    Radio,
 }
 
 #[derive(Debug, Clone)]
@@ -242,6 +265,12 @@ pub enum TextInputEvent {
     Paste(String),
 }
 
+#[derive(Debug, Clone)]
+pub enum SliderEvent {
+    Change(f32),
+    Release,
+}
+
 pub(crate) mod text_input {
     #[derive(Debug, Default, Clone)]
     pub(crate) struct Styles {
@@ -0,0 +1,295 @@
+//! A canvas that draws a display list sent by the config.
+//!
+//! Configs usually resend their whole widget tree whenever something changes,
+//! so tessellated geometry is cached by display list and canvas size rather than by widget.
+//! A canvas whose commands and size didn't change reuses the previous geometry.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    rc::{Rc, Weak},
+};
+
+use iced::{
+    Point, Radians, Rectangle, Size, Vector, mouse,
+    widget::canvas::{self, Frame, Geometry, Path, path},
+};
+use prost::Message as _;
+use snowcap_api_defs::snowcap::widget::v1::{self as api, canvas::command::Command};
+
+use crate::{compositor::Renderer, util::convert::FromApi, widget::SnowcapMessage};
+
+/// How many canvas sizes a display list keeps geometry for.
+///
+/// Identical commands may be drawn by several canvases of different sizes at once.
+const MAX_CACHED_SIZES: usize = 4;
+
+thread_local! {
+    static DISPLAY_LISTS: RefCell<HashMap<u64, Weak<DisplayList>>> = RefCell::default();
+}
+
+/// Draw commands along with their tessellated geometry.
+pub struct DisplayList {
+    commands: Vec<api::canvas::Command>,
+    /// Geometry by canvas size, most recently drawn last.
+    caches: RefCell<Vec<(Size, canvas::Cache<Renderer>)>>,
+}
+
+impl DisplayList {
+    /// Returns the display list for the given commands, reusing a cached one if
+    /// an identical list is still alive.
+    pub fn get_or_create(commands: Vec<api::canvas::Command>) -> Rc<Self> {
+        let key = {
+            let mut hasher = DefaultHasher::new();
+            for command in commands.iter() {
+                command.encode_to_vec().hash(&mut hasher);
+            }
+            hasher.finish()
+        };
+
+        DISPLAY_LISTS.with_borrow_mut(|lists| {
+            if let Some(list) = lists.get(&key).and_then(Weak::upgrade)
+                && list.commands == commands
+            {
+                return list;
+            }
+
+            lists.retain(|_, list| list.strong_count() > 0);
+
+            let list = Rc::new(Self {
+                commands,
+                caches: RefCell::default(),
+            });
+            lists.insert(key, Rc::downgrade(&list));
+            list
+        })
+    }
+
+    /// Returns the geometry for a canvas of the given size, tessellating it if needed.
+    fn geometry(&self, renderer: &Renderer, size: Size) -> Geometry<Renderer> {
+        let mut caches = self.caches.borrow_mut();
+
+        match caches
+            .iter()
+            .position(|(cached_size, _)| *cached_size == size)
+        {
+            Some(index) => {
+                let cache = caches.remove(index);
+                caches.push(cache);
+            }
+            None => {
+                if caches.len() == MAX_CACHED_SIZES {
+                    caches.remove(0);
+                }
+                caches.push((size, canvas::Cache::new()));
+            }
+        }
+
+        let (_, cache) = caches.last().expect("a cache was just pushed");
+        cache.draw(renderer, size, |frame| self.draw(frame))
+    }
+
+    fn draw(&self, frame: &mut Frame<Renderer>) {
+        for command in self.commands.iter() {
+            let Some(command) = command.command.clone() else {
+                continue;
+            };
+
+            match command {
+                Command::Fill(fill) => {
+                    let rule = match fill.rule() {
+                        api::canvas::FillRule::Unspecified | api::canvas::FillRule::NonZero => {
+                            canvas::fill::Rule::NonZero
+                        }
+                        api::canvas::FillRule::EvenOdd => canvas::fill::Rule::EvenOdd,
+                    };
+                    let (Some(path), Some(style)) = (fill.path, fill.style.and_then(style)) else {
+                        continue;
+                    };
+
+                    frame.fill(&build_path(path), canvas::Fill { style, rule });
+                }
+                Command::Stroke(stroke) => {
+                    let line_cap = match stroke.line_cap() {
+                        api::canvas::LineCap::Unspecified | api::canvas::LineCap::Butt => {
+                            canvas::LineCap::Butt
+                        }
+                        api::canvas::LineCap::Square => canvas::LineCap::Square,
+                        api::canvas::LineCap::Round => canvas::LineCap::Round,
+                    };
+                    let line_join = match stroke.line_join() {
+                        api::canvas::LineJoin::Unspecified | api::canvas::LineJoin::Miter => {
+                            canvas::LineJoin::Miter
+                        }
+                        api::canvas::LineJoin::Round => canvas::LineJoin::Round,
+                        api::canvas::LineJoin::Bevel => canvas::LineJoin::Bevel,
+                    };
+                    let (Some(path), Some(style)) = (stroke.path, stroke.style.and_then(style))
+                    else {
+                        continue;
+                    };
+
+                    frame.stroke(
+                        &build_path(path),
+                        canvas::Stroke {
+                            style,
+                            width: stroke.width,
+                            line_cap,
+                            line_join,
+                            line_dash: canvas::LineDash {
+                                segments: &stroke.dash_segments,
+                                offset: stroke.dash_offset as usize,
+                            },
+                        },
+                    );
+                }
+                Command::Text(text) => {
+                    let mut fill_text = canvas::Text {
+                        content: text.content,
+                        position: text.position.map(point).unwrap_or_default(),
+                        ..Default::default()
+                    };
+
+                    if let Some(color) = text.color {
+                        fill_text.color = iced::Color::from_api(color);
+                    }
+                    if let Some(size) = text.size {
+                        fill_text.size = size.into();
+                    }
+                    if let Some(font) = text.font {
+                        fill_text.font = iced::Font::from_api(font);
+                    }
+                    if let Some(horizontal_alignment) = text.horizontal_alignment {
+                        fill_text.align_x = match api::Alignment::try_from(horizontal_alignment)
+                            .unwrap_or(api::Alignment::Unspecified)
+                        {
+                            api::Alignment::Unspecified | api::Alignment::Start => {
+                                iced::alignment::Horizontal::Left.into()
+                            }
+                            api::Alignment::Center => iced::alignment::Horizontal::Center.into(),
+                            api::Alignment::End => iced::alignment::Horizontal::Right.into(),
+                        };
+                    }
+                    if let Some(vertical_alignment) = text.vertical_alignment {
+                        fill_text.align_y = match api::Alignment::try_from(vertical_alignment)
+                            .unwrap_or(api::Alignment::Unspecified)
+                        {
+                            api::Alignment::Unspecified | api::Alignment::Start => {
+                                iced::alignment::Vertical::Top
+                            }
+                            api::Alignment::Center => iced::alignment::Vertical::Center,
+                            api::Alignment::End => iced::alignment::Vertical::Bottom,
+                        };
+                    }
+
+                    frame.fill_text(fill_text);
+                }
+                Command::PushTransform(()) => frame.push_transform(),
+                Command::PopTransform(()) => frame.pop_transform(),
+                Command::Translate(translation) => {
+                    frame.translate(Vector::new(translation.x, translation.y))
+                }
+                Command::RotateRadians(radians) => frame.rotate(Radians(radians)),
+                Command::Scale(scale) => frame.scale(scale),
+            }
+        }
+    }
+}
+
+/// A [`canvas::Program`] that draws a [`DisplayList`].
+pub struct DisplayListProgram(pub Rc<DisplayList>);
+
+impl canvas::Program<SnowcapMessage, iced::Theme, Renderer> for DisplayListProgram {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &iced::Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry<Renderer>> {
+        vec![self.0.geometry(renderer, bounds.size())]
+    }
+}
+
+fn point(point: api::canvas::Point) -> Point {
+    Point::new(point.x, point.y)
+}
+
+fn style(style: api::canvas::Style) -> Option<canvas::Style> {
+    use api::canvas::style::Style;
+
+    match style.style? {
+        Style::Color(color) => Some(canvas::Style::Solid(iced::Color::from_api(color))),
+        Style::Gradient(gradient) => {
+            let mut linear = canvas::gradient::Linear::new(
+                gradient.start.map(point).unwrap_or_default(),
+                gradient.end.map(point).unwrap_or_default(),
+            );
+
+            for stop in gradient.stops {
+                linear = linear.add_stop(
+                    stop.offset,
+                    stop.color.map(iced::Color::from_api).unwrap_or_default(),
+                );
+            }
+
+            Some(canvas::Style::Gradient(canvas::Gradient::Linear(linear)))
+        }
+    }
+}
+
+fn build_path(path: api::canvas::Path) -> Path {
+    use api::canvas::path::segment::Segment;
+
+    Path::new(|builder| {
+        for segment in path.segments {
+            let Some(segment) = segment.segment else {
+                continue;
+            };
+
+            match segment {
+                Segment::MoveTo(to) => builder.move_to(point(to)),
+                Segment::LineTo(to) => builder.line_to(point(to)),
+                Segment::QuadraticCurveTo(curve) => builder.quadratic_curve_to(
+                    curve.control.map(point).unwrap_or_default(),
+                    curve.to.map(point).unwrap_or_default(),
+                ),
+                Segment::BezierCurveTo(curve) => builder.bezier_curve_to(
+                    curve.control_a.map(point).unwrap_or_default(),
+                    curve.control_b.map(point).unwrap_or_default(),
+                    curve.to.map(point).unwrap_or_default(),
+                ),
+                Segment::Arc(arc) => builder.arc(path::Arc {
+                    center: arc.center.map(point).unwrap_or_default(),
+                    radius: arc.radius,
+                    start_angle: Radians(arc.start_radians),
+                    end_angle: Radians(arc.end_radians),
+                }),
+                Segment::Rectangle(rectangle) => {
+                    let top_left = rectangle.top_left.map(point).unwrap_or_default();
+                    let size = rectangle
+                        .size
+                        .map(|size| Size::new(size.width, size.height))
+                        .unwrap_or_default();
+
+                    match rectangle.radius {
+                        Some(radius) => builder.rounded_rectangle(
+                            top_left,
+                            size,
+                            iced::border::Radius::from_api(radius),
+                        ),
+                        None => builder.rectangle(top_left, size),
+                    }
+                }
+                Segment::Circle(circle) => {
+                    builder.circle(circle.center.map(point).unwrap_or_default(), circle.radius)
+                }
+                Segment::Close(()) => builder.close(),
+            }
+        }
+    })
+}
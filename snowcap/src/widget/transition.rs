@@ -0,0 +1,687 @@
+//! Animations that run in snowcap instead of in the program.
+//!
+//! A [`Transition`] is rebuilt every frame while it is running. Its offset and scale are
+//! applied as a transformation when drawing. Its opacity is handed to the widgets
+//! built inside of it through [`opacity`], which they [`Fade`] their styles by.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::{Rc, Weak},
+    time::{Duration, Instant},
+};
+
+use iced::{
+    Background, Border, Color, Element, Shadow, Transformation, Vector,
+    widget::{
+        button, checkbox, container, pick_list, progress_bar, radio, rule, scrollable, slider, svg,
+        text, text_input, toggler,
+    },
+};
+use iced_wgpu::core::{
+    Clipboard, Layout, Shell, Widget, layout, mouse, overlay, renderer,
+    widget::{Operation, Tree, tree},
+};
+
+thread_local! {
+    static OPACITY: Cell<f32> = const { Cell::new(1.0) };
+    static CLOCKS: RefCell<HashMap<String, Weak<Clock>>> = RefCell::default();
+}
+
+/// Returns the opacity that widgets currently being built should be drawn with.
+pub fn opacity() -> f32 {
+    OPACITY.get()
+}
+
+/// Builds widgets with `f` while they are faded by `opacity`, on top of any
+/// transitions they are already inside of.
+pub fn with_opacity<T>(opacity: f32, f: impl FnOnce() -> T) -> T {
+    let outer = OPACITY.get();
+    OPACITY.set(outer * opacity.clamp(0.0, 1.0));
+    let ret = f();
+    OPACITY.set(outer);
+    ret
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Timing {
+    pub duration: Duration,
+    pub delay: Duration,
+    pub easing: Easing,
+    pub reverse: bool,
+    pub repeat_forever: bool,
+    pub auto_reverse: bool,
+}
+
+/// Keeps track of when a transition started.
+pub struct Clock {
+    start: Instant,
+    timing: Timing,
+    fingerprint: u64,
+}
+
+impl Clock {
+    /// Returns the clock for the transition with the given key, starting a new one
+    /// if there is none or its transition changed.
+    ///
+    /// Transitions without a key always start a new clock.
+    pub fn get_or_create(key: Option<String>, timing: Timing, fingerprint: u64) -> Rc<Self> {
+        let new = || {
+            Rc::new(Self {
+                start: Instant::now(),
+                timing,
+                fingerprint,
+            })
+        };
+
+        let Some(key) = key else {
+            return new();
+        };
+
+        CLOCKS.with_borrow_mut(|clocks| {
+            if let Some(clock) = clocks.get(&key).and_then(Weak::upgrade)
+                && clock.fingerprint == fingerprint
+            {
+                return clock;
+            }
+
+            clocks.retain(|_, clock| clock.strong_count() > 0);
+
+            let clock = new();
+            clocks.insert(key, Rc::downgrade(&clock));
+            clock
+        })
+    }
+
+    /// Returns how far along the transition is at `now`, eased, from 0.0 at
+    /// the `from` state to 1.0 at the normal state, and whether it is still running.
+    pub fn progress(&self, now: Instant) -> (f32, bool) {
+        let Timing {
+            duration,
+            delay,
+            easing,
+            reverse,
+            repeat_forever,
+            auto_reverse,
+        } = self.timing;
+
+        let elapsed = now.saturating_duration_since(self.start);
+        let running = elapsed < delay + duration || repeat_forever;
+
+        let t = if duration.is_zero() {
+            1.0
+        } else {
+            let runs = elapsed.saturating_sub(delay).as_secs_f32() / duration.as_secs_f32();
+            if !running {
+                1.0
+            } else if auto_reverse && runs as u64 % 2 == 1 {
+                1.0 - runs.fract()
+            } else {
+                runs.fract()
+            }
+        };
+
+        let t = easing.apply(t);
+
+        (if reverse { 1.0 - t } else { t }, running)
+    }
+}
+
+/// Draws its content offset and scaled around its center.
+///
+/// While `animating`, the user interface is rebuilt every frame so the
+/// transition can move along.
+pub struct Transition<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    opacity: f32,
+    offset: Vector,
+    scale: f32,
+    animating: bool,
+}
+
+impl<'a, Message, Theme, Renderer> Transition<'a, Message, Theme, Renderer>
+where
+    Renderer: iced_renderer::core::Renderer,
+{
+    pub fn new(content: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        Self {
+            content: content.into(),
+            opacity: 1.0,
+            offset: Vector::ZERO,
+            scale: 1.0,
+            animating: false,
+        }
+    }
+
+    /// Sets the opacity of the default text color.
+    ///
+    /// Everything else fades through [`opacity`] when it is built.
+    pub fn opacity(self, opacity: f32) -> Self {
+        Self { opacity, ..self }
+    }
+
+    pub fn offset(self, offset: Vector) -> Self {
+        Self { offset, ..self }
+    }
+
+    pub fn scale(self, scale: f32) -> Self {
+        Self { scale, ..self }
+    }
+
+    pub fn animating(self, animating: bool) -> Self {
+        Self { animating, ..self }
+    }
+
+    fn transformation(&self, layout: Layout<'_>) -> Transformation {
+        let center = layout.bounds().center();
+
+        Transformation::translate(center.x + self.offset.x, center.y + self.offset.y)
+            * Transformation::scale(self.scale)
+            * Transformation::translate(-center.x, -center.y)
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Transition<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: iced_renderer::core::Renderer + 'a,
+{
+    fn from(value: Transition<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(value)
+    }
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Transition<'_, Message, Theme, Renderer>
+where
+    Renderer: iced_renderer::core::Renderer,
+{
+    fn size(&self) -> iced::Size<iced::Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> iced::Size<iced::Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content.as_widget_mut().layout(tree, renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &iced::Rectangle,
+    ) {
+        let transformation = self.transformation(layout);
+        let style = renderer::Style {
+            text_color: style.text_color.scale_alpha(self.opacity),
+        };
+
+        renderer.with_transformation(transformation, |renderer| {
+            self.content.as_widget().draw(
+                tree,
+                renderer,
+                theme,
+                &style,
+                layout,
+                cursor * transformation.inverse(),
+                &(*viewport * transformation.inverse()),
+            );
+        });
+    }
+
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree);
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        self.content
+            .as_widget_mut()
+            .operate(tree, layout, renderer, operation);
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &iced::Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &iced::Rectangle,
+    ) {
+        if self.animating
+            && let iced::Event::Window(iced::window::Event::RedrawRequested(_)) = event
+        {
+            shell.invalidate_widgets();
+            shell.request_redraw();
+        }
+
+        let transformation = self.transformation(layout);
+
+        self.content.as_widget_mut().update(
+            tree,
+            event,
+            layout,
+            cursor * transformation.inverse(),
+            renderer,
+            clipboard,
+            shell,
+            &(*viewport * transformation.inverse()),
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &iced::Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let transformation = self.transformation(layout);
+
+        self.content.as_widget().mouse_interaction(
+            tree,
+            layout,
+            cursor * transformation.inverse(),
+            &(*viewport * transformation.inverse()),
+            renderer,
+        )
+    }
+
+    fn overlay<'a>(
+        &'a mut self,
+        tree: &'a mut Tree,
+        layout: Layout<'a>,
+        renderer: &Renderer,
+        viewport: &iced::Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'a, Message, Theme, Renderer>> {
+        self.content.as_widget_mut().overlay(
+            tree,
+            layout,
+            renderer,
+            viewport,
+            translation + self.offset,
+        )
+    }
+}
+
+/// Scales the alpha of every color in a style.
+pub trait Fade {
+    fn fade(self, opacity: f32) -> Self;
+}
+
+impl Fade for Color {
+    fn fade(self, opacity: f32) -> Self {
+        self.scale_alpha(opacity)
+    }
+}
+
+impl Fade for Background {
+    fn fade(self, opacity: f32) -> Self {
+        self.scale_alpha(opacity)
+    }
+}
+
+impl<T: Fade> Fade for Option<T> {
+    fn fade(self, opacity: f32) -> Self {
+        self.map(|inner| inner.fade(opacity))
+    }
+}
+
+impl Fade for Border {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            color: self.color.fade(opacity),
+            ..self
+        }
+    }
+}
+
+impl Fade for Shadow {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            color: self.color.fade(opacity),
+            ..self
+        }
+    }
+}
+
+impl Fade for text::Style {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            color: self.color.fade(opacity),
+        }
+    }
+}
+
+impl Fade for container::Style {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            text_color: self.text_color.fade(opacity),
+            background: self.background.fade(opacity),
+            border: self.border.fade(opacity),
+            shadow: self.shadow.fade(opacity),
+            ..self
+        }
+    }
+}
+
+impl Fade for button::Style {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            background: self.background.fade(opacity),
+            text_color: self.text_color.fade(opacity),
+            border: self.border.fade(opacity),
+            shadow: self.shadow.fade(opacity),
+            ..self
+        }
+    }
+}
+
+impl Fade for scrollable::Rail {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            background: self.background.fade(opacity),
+            border: self.border.fade(opacity),
+            scroller: scrollable::Scroller {
+                background: self.scroller.background.fade(opacity),
+                border: self.scroller.border.fade(opacity),
+            },
+        }
+    }
+}
+
+impl Fade for scrollable::Style {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            container: self.container.fade(opacity),
+            vertical_rail: self.vertical_rail.fade(opacity),
+            horizontal_rail: self.horizontal_rail.fade(opacity),
+            gap: self.gap.fade(opacity),
+            auto_scroll: scrollable::AutoScroll {
+                background: self.auto_scroll.background.fade(opacity),
+                border: self.auto_scroll.border.fade(opacity),
+                shadow: self.auto_scroll.shadow.fade(opacity),
+                icon: self.auto_scroll.icon.fade(opacity),
+            },
+        }
+    }
+}
+
+impl Fade for text_input::Style {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            background: self.background.fade(opacity),
+            border: self.border.fade(opacity),
+            icon: self.icon.fade(opacity),
+            placeholder: self.placeholder.fade(opacity),
+            value: self.value.fade(opacity),
+            selection: self.selection.fade(opacity),
+        }
+    }
+}
+
+impl Fade for slider::Style {
+    fn fade(self, opacity: f32) -> Self {
+        let (left, right) = self.rail.backgrounds;
+
+        Self {
+            rail: slider::Rail {
+                backgrounds: (left.fade(opacity), right.fade(opacity)),
+                border: self.rail.border.fade(opacity),
+                ..self.rail
+            },
+            handle: slider::Handle {
+                background: self.handle.background.fade(opacity),
+                border_color: self.handle.border_color.fade(opacity),
+                ..self.handle
+            },
+        }
+    }
+}
+
+impl Fade for checkbox::Style {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            background: self.background.fade(opacity),
+            icon_color: self.icon_color.fade(opacity),
+            border: self.border.fade(opacity),
+            text_color: self.text_color.fade(opacity),
+        }
+    }
+}
+
+impl Fade for toggler::Style {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            background: self.background.fade(opacity),
+            background_border_color: self.background_border_color.fade(opacity),
+            foreground: self.foreground.fade(opacity),
+            foreground_border_color: self.foreground_border_color.fade(opacity),
+            text_color: self.text_color.fade(opacity),
+            ..self
+        }
+    }
+}
+
+impl Fade for progress_bar::Style {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            background: self.background.fade(opacity),
+            bar: self.bar.fade(opacity),
+            border: self.border.fade(opacity),
+        }
+    }
+}
+
+impl Fade for pick_list::Style {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            text_color: self.text_color.fade(opacity),
+            placeholder_color: self.placeholder_color.fade(opacity),
+            handle_color: self.handle_color.fade(opacity),
+            background: self.background.fade(opacity),
+            border: self.border.fade(opacity),
+        }
+    }
+}
+
+impl Fade for radio::Style {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            background: self.background.fade(opacity),
+            dot_color: self.dot_color.fade(opacity),
+            border_color: self.border_color.fade(opacity),
+            text_color: self.text_color.fade(opacity),
+            ..self
+        }
+    }
+}
+
+impl Fade for rule::Style {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            color: self.color.fade(opacity),
+            ..self
+        }
+    }
+}
+
+impl Fade for svg::Style {
+    fn fade(self, opacity: f32) -> Self {
+        Self {
+            color: self.color.fade(opacity),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(timing: Timing) -> (Clock, Instant) {
+        let start = Instant::now();
+        let clock = Clock {
+            start,
+            timing: Timing {
+                easing: Easing::Linear,
+                ..timing
+            },
+            fingerprint: 0,
+        };
+        (clock, start)
+    }
+
+    fn assert_progress(clock: &Clock, at: Instant, expected: (f32, bool)) {
+        let (t, running) = clock.progress(at);
+        assert!(
+            (t - expected.0).abs() < 1e-4 && running == expected.1,
+            "expected {expected:?}, got {:?}",
+            (t, running)
+        );
+    }
+
+    #[test]
+    fn progress_waits_for_the_delay_and_stops_at_the_end() {
+        let (clock, start) = clock(Timing {
+            duration: Duration::from_secs(1),
+            delay: Duration::from_millis(500),
+            ..Default::default()
+        });
+
+        assert_progress(&clock, start, (0.0, true));
+        assert_progress(&clock, start + Duration::from_millis(250), (0.0, true));
+        assert_progress(&clock, start + Duration::from_millis(1000), (0.5, true));
+        assert_progress(&clock, start + Duration::from_millis(1500), (1.0, false));
+        assert_progress(&clock, start + Duration::from_secs(5), (1.0, false));
+    }
+
+    #[test]
+    fn progress_repeats_forever() {
+        let (clock, start) = clock(Timing {
+            duration: Duration::from_secs(1),
+            delay: Duration::from_millis(500),
+            repeat_forever: true,
+            ..Default::default()
+        });
+
+        assert_progress(&clock, start + Duration::from_millis(750), (0.25, true));
+        assert_progress(&clock, start + Duration::from_millis(1750), (0.25, true));
+        assert_progress(&clock, start + Duration::from_millis(10_000), (0.5, true));
+    }
+
+    #[test]
+    fn progress_auto_reverses_every_other_run() {
+        let (clock, start) = clock(Timing {
+            duration: Duration::from_secs(1),
+            repeat_forever: true,
+            auto_reverse: true,
+            ..Default::default()
+        });
+
+        assert_progress(&clock, start + Duration::from_millis(250), (0.25, true));
+        assert_progress(&clock, start + Duration::from_millis(1250), (0.75, true));
+        assert_progress(&clock, start + Duration::from_millis(2250), (0.25, true));
+        assert_progress(&clock, start + Duration::from_millis(3750), (0.25, true));
+    }
+
+    #[test]
+    fn progress_runs_backwards_when_reversed() {
+        let (clock, start) = clock(Timing {
+            duration: Duration::from_secs(1),
+            reverse: true,
+            ..Default::default()
+        });
+
+        assert_progress(&clock, start + Duration::from_millis(250), (0.75, true));
+        assert_progress(&clock, start + Duration::from_secs(2), (0.0, false));
+    }
+
+    #[test]
+    fn progress_of_instant_transitions_is_complete() {
+        let (clock, start) = clock(Timing {
+            delay: Duration::from_millis(500),
+            ..Default::default()
+        });
+
+        assert_progress(&clock, start, (1.0, true));
+        assert_progress(&clock, start + Duration::from_secs(1), (1.0, false));
+    }
+
+    #[test]
+    fn progress_is_eased() {
+        let start = Instant::now();
+        let clock = Clock {
+            start,
+            timing: Timing {
+                duration: Duration::from_secs(1),
+                easing: Easing::EaseIn,
+                ..Default::default()
+            },
+            fingerprint: 0,
+        };
+
+        assert_progress(&clock, start + Duration::from_millis(500), (0.125, true));
+    }
+}
@@ -0,0 +1,383 @@
+use std::rc::Rc;
+
+use iced::Size;
+use snowcap::{
+    api::widget::v1::widget_def_to_fn,
+    headless::{self, Snapshot},
+    theme::Variant,
+    widget::{SnowcapWidgetProgram, canvas::DisplayList},
+};
+use snowcap_api_defs::snowcap::{
+    theme,
+    widget::v1::{
+        Background, Canvas, Checkbox, Color, Column, Container, Grid, Length, PickList,
+        ProgressBar, Radio, Slider, Stack, Text, Toggler, WidgetDef, background, canvas, container,
+        grid, length, progress_bar, widget_def,
+    },
+};
+
+fn fixed(pixels: f32) -> Length {
+    Length {
+        strategy: Some(length::Strategy::Fixed(pixels)),
+    }
+}
+
+fn colored_box(width: f32, height: f32, color: Color) -> WidgetDef {
+    WidgetDef {
+        theme: None,
+        widget: Some(widget_def::Widget::Container(Box::new(Container {
+            width: Some(fixed(width)),
+            height: Some(fixed(height)),
+            child: Some(Box::new(WidgetDef {
+                theme: None,
+                widget: Some(widget_def::Widget::Column(Column::default())),
+            })),
+            style: Some(container::Style {
+                background: Some(Background {
+                    background: Some(background::Background::Color(color)),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))),
+    }
+}
+
+fn filled_rectangle(x: f32, y: f32, width: f32, height: f32, color: Color) -> canvas::Command {
+    canvas::Command {
+        command: Some(canvas::command::Command::Fill(canvas::Fill {
+            path: Some(canvas::Path {
+                segments: vec![canvas::path::Segment {
+                    segment: Some(canvas::path::segment::Segment::Rectangle(
+                        canvas::path::Rectangle {
+                            top_left: Some(canvas::Point { x, y }),
+                            size: Some(canvas::Size { width, height }),
+                            radius: None,
+                        },
+                    )),
+                }],
+            }),
+            style: Some(canvas::Style {
+                style: Some(canvas::style::Style::Color(color)),
+            }),
+            rule: canvas::FillRule::Unspecified.into(),
+        })),
+    }
+}
+
+fn text(text: &str) -> WidgetDef {
+    WidgetDef {
+        theme: None,
+        widget: Some(widget_def::Widget::Text(Text {
+            text: text.to_string(),
+            ..Default::default()
+        })),
+    }
+}
+
+const RED: Color = Color {
+    red: 1.0,
+    green: 0.0,
+    blue: 0.0,
+    alpha: 1.0,
+};
+
+const BLUE: Color = Color {
+    red: 0.0,
+    green: 0.0,
+    blue: 1.0,
+    alpha: 1.0,
+};
+
+const MAX_SIZE: Size<u32> = Size::new(1024, 1024);
+
+fn render(widget_def: WidgetDef, scale: f32) -> Snapshot {
+    let mut program = SnowcapWidgetProgram::new(widget_def_to_fn(widget_def).unwrap());
+    headless::render(&mut program, MAX_SIZE, scale)
+}
+
+#[test]
+fn snapshot_is_sized_to_the_view() {
+    let snapshot = render(colored_box(40.0, 20.0, RED), 1.0);
+
+    assert_eq!(snapshot.size, Size::new(40, 20));
+    assert_eq!(snapshot.rgba.len(), 40 * 20 * 4);
+}
+
+#[test]
+fn snapshot_respects_scale() {
+    let snapshot = render(colored_box(40.0, 20.0, RED), 2.0);
+
+    assert_eq!(snapshot.size, Size::new(80, 40));
+}
+
+#[test]
+fn snapshot_renders_background() {
+    let snapshot = render(colored_box(40.0, 20.0, RED), 1.0);
+
+    assert_eq!(snapshot.pixel(20, 10), Some([255, 0, 0, 255]));
+    assert_eq!(snapshot.pixel(40, 10), None);
+}
+
+#[test]
+fn text_snapshots_are_deterministic() {
+    let first = render(text("Snowcap"), 1.0);
+    let second = render(text("Snowcap"), 1.0);
+
+    assert_eq!(first, second);
+    assert!(first.rgba.chunks_exact(4).any(|pixel| pixel[3] != 0));
+}
+
+#[test]
+fn canvas_draws_commands() {
+    let snapshot = render(
+        WidgetDef {
+            theme: None,
+            widget: Some(widget_def::Widget::Canvas(Canvas {
+                width: Some(fixed(40.0)),
+                height: Some(fixed(20.0)),
+                commands: vec![filled_rectangle(0.0, 0.0, 20.0, 20.0, RED)],
+            })),
+        },
+        1.0,
+    );
+
+    assert_eq!(snapshot.size, Size::new(40, 20));
+    assert_eq!(snapshot.pixel(10, 10), Some([255, 0, 0, 255]));
+    assert_eq!(snapshot.pixel(30, 10), Some([0, 0, 0, 0]));
+}
+
+#[test]
+fn identical_display_lists_are_reused() {
+    let first = DisplayList::get_or_create(vec![filled_rectangle(0.0, 0.0, 20.0, 20.0, RED)]);
+    let second = DisplayList::get_or_create(vec![filled_rectangle(0.0, 0.0, 20.0, 20.0, RED)]);
+    let changed = DisplayList::get_or_create(vec![filled_rectangle(0.0, 0.0, 10.0, 20.0, RED)]);
+
+    assert!(Rc::ptr_eq(&first, &second));
+    assert!(!Rc::ptr_eq(&first, &changed));
+}
+
+#[test]
+fn stack_draws_later_children_on_top() {
+    let snapshot = render(
+        WidgetDef {
+            theme: None,
+            widget: Some(widget_def::Widget::Stack(Stack {
+                children: vec![colored_box(40.0, 20.0, RED), colored_box(20.0, 20.0, BLUE)],
+                ..Default::default()
+            })),
+        },
+        1.0,
+    );
+
+    assert_eq!(snapshot.size, Size::new(40, 20));
+    assert_eq!(snapshot.pixel(10, 10), Some([0, 0, 255, 255]));
+    assert_eq!(snapshot.pixel(30, 10), Some([255, 0, 0, 255]));
+}
+
+#[test]
+fn grid_wraps_children_into_rows() {
+    let snapshot = render(
+        WidgetDef {
+            theme: None,
+            widget: Some(widget_def::Widget::Grid(Grid {
+                columns: Some(grid::Columns::Amount(2)),
+                width: Some(40.0),
+                sizing: Some(grid::Sizing::AspectRatio(1.0)),
+                children: vec![
+                    colored_box(20.0, 20.0, RED),
+                    colored_box(20.0, 20.0, BLUE),
+                    colored_box(20.0, 20.0, BLUE),
+                    colored_box(20.0, 20.0, RED),
+                ],
+                ..Default::default()
+            })),
+        },
+        1.0,
+    );
+
+    assert_eq!(snapshot.size, Size::new(40, 40));
+    assert_eq!(snapshot.pixel(10, 10), Some([255, 0, 0, 255]));
+    assert_eq!(snapshot.pixel(30, 10), Some([0, 0, 255, 255]));
+    assert_eq!(snapshot.pixel(10, 30), Some([0, 0, 255, 255]));
+    assert_eq!(snapshot.pixel(30, 30), Some([255, 0, 0, 255]));
+}
+
+fn column_of_boxes(spacing: Option<f32>) -> WidgetDef {
+    WidgetDef {
+        theme: None,
+        widget: Some(widget_def::Widget::Column(Column {
+            spacing,
+            children: vec![colored_box(10.0, 10.0, RED), colored_box(10.0, 10.0, BLUE)],
+            ..Default::default()
+        })),
+    }
+}
+
+#[test]
+fn columns_inherit_theme_spacing() {
+    snowcap::theme::set_current(
+        Variant::Dark,
+        &theme::v1::Theme {
+            spacing: Some(5.0),
+            ..Default::default()
+        },
+    );
+
+    let themed = render(column_of_boxes(None), 1.0);
+    let overridden = render(column_of_boxes(Some(0.0)), 1.0);
+
+    assert_eq!(themed.size, Size::new(10, 25));
+    assert_eq!(themed.pixel(5, 20), Some([0, 0, 255, 255]));
+    assert_eq!(overridden.size, Size::new(10, 20));
+}
+
+fn widget(widget: widget_def::Widget) -> WidgetDef {
+    WidgetDef {
+        theme: None,
+        widget: Some(widget),
+    }
+}
+
+fn is_drawn(snapshot: &Snapshot) -> bool {
+    snapshot.size.width > 0
+        && snapshot.size.height > 0
+        && snapshot.rgba.chunks_exact(4).any(|pixel| pixel[3] != 0)
+}
+
+fn progress_bar(min: f32, max: f32, value: f32) -> WidgetDef {
+    let color = |color| Background {
+        background: Some(background::Background::Color(color)),
+    };
+
+    widget(widget_def::Widget::ProgressBar(ProgressBar {
+        min,
+        max,
+        value,
+        length: Some(fixed(40.0)),
+        girth: Some(fixed(10.0)),
+        style: Some(progress_bar::Style {
+            background: Some(color(BLUE)),
+            bar: Some(color(RED)),
+            border: None,
+        }),
+        ..Default::default()
+    }))
+}
+
+#[test]
+fn progress_bar_fills_up_to_its_value() {
+    let snapshot = render(progress_bar(0.0, 1.0, 0.5), 1.0);
+
+    assert_eq!(snapshot.size, Size::new(40, 10));
+    assert_eq!(snapshot.pixel(10, 5), Some([255, 0, 0, 255]));
+    assert_eq!(snapshot.pixel(30, 5), Some([0, 0, 255, 255]));
+}
+
+#[test]
+fn progress_bar_handles_degenerate_ranges() {
+    let expected = render(progress_bar(0.0, 1.0, 0.5), 1.0);
+
+    assert_eq!(render(progress_bar(1.0, 0.0, 0.5), 1.0), expected);
+    assert_eq!(render(progress_bar(f32::NAN, 1.0, 0.5), 1.0), expected);
+    assert_eq!(render(progress_bar(0.0, f32::INFINITY, 0.5), 1.0), expected);
+
+    let empty = render(progress_bar(0.0, 1.0, f32::NAN), 1.0);
+    assert_eq!(empty.pixel(10, 5), Some([0, 0, 255, 255]));
+
+    let full = render(progress_bar(0.0, 1.0, 2.0), 1.0);
+    assert_eq!(full.pixel(30, 5), Some([255, 0, 0, 255]));
+
+    assert!(is_drawn(&render(progress_bar(1.0, 1.0, 1.0), 1.0)));
+}
+
+#[test]
+fn slider_renders() {
+    let snapshot = render(
+        widget(widget_def::Widget::Slider(Slider {
+            min: 0.0,
+            max: 10.0,
+            value: 5.0,
+            width: Some(fixed(100.0)),
+            ..Default::default()
+        })),
+        1.0,
+    );
+
+    assert_eq!(snapshot.size.width, 100);
+    assert!(is_drawn(&snapshot));
+}
+
+#[test]
+fn checkbox_renders_its_state() {
+    let checkbox = |checked| {
+        widget(widget_def::Widget::Checkbox(Checkbox {
+            label: "Check".to_string(),
+            checked,
+            ..Default::default()
+        }))
+    };
+
+    let checked = render(checkbox(true), 1.0);
+    let unchecked = render(checkbox(false), 1.0);
+
+    assert!(is_drawn(&checked));
+    assert_eq!(checked.size, unchecked.size);
+    assert_ne!(checked, unchecked);
+}
+
+#[test]
+fn toggler_renders_its_state() {
+    let toggler = |toggled| {
+        widget(widget_def::Widget::Toggler(Toggler {
+            label: Some("Toggle".to_string()),
+            toggled,
+            ..Default::default()
+        }))
+    };
+
+    let on = render(toggler(true), 1.0);
+    let off = render(toggler(false), 1.0);
+
+    assert!(is_drawn(&on));
+    assert_eq!(on.size, off.size);
+    assert_ne!(on, off);
+}
+
+#[test]
+fn radio_renders_its_state() {
+    let radio = |selected| {
+        widget(widget_def::Widget::Radio(Radio {
+            label: "Option".to_string(),
+            selected,
+            ..Default::default()
+        }))
+    };
+
+    let selected = render(radio(true), 1.0);
+    let unselected = render(radio(false), 1.0);
+
+    assert!(is_drawn(&selected));
+    assert_eq!(selected.size, unselected.size);
+    assert_ne!(selected, unselected);
+}
+
+#[test]
+fn pick_list_renders() {
+    let pick_list = |selected: Option<&str>| {
+        widget(widget_def::Widget::PickList(PickList {
+            options: vec!["One".to_string(), "Two".to_string()],
+            selected: selected.map(str::to_string),
+            placeholder: Some("Pick one".to_string()),
+            width: Some(fixed(120.0)),
+            ..Default::default()
+        }))
+    };
+
+    let selected = render(pick_list(Some("Two")), 1.0);
+    let placeholder = render(pick_list(None), 1.0);
+
+    assert_eq!(selected.size.width, 120);
+    assert!(is_drawn(&selected));
+    assert_ne!(selected, placeholder);
+}
@@ -1,3 +1,5 @@
+pub mod auth;
+pub mod connection;
 pub mod debug;
 pub mod input;
 pub mod layout;
@@ -7,6 +9,7 @@ pub mod process;
 pub mod render;
 pub mod signal;
 pub mod tag;
+pub mod trace;
 pub mod window;
 
 use std::pin::Pin;
@@ -17,16 +20,26 @@ use tokio::{
     task::JoinHandle,
 };
 use tokio_stream::{Stream, StreamExt};
-use tonic::{Response, Status, Streaming};
+use tonic::{Request, Response, Status, Streaming};
 use tracing::{debug, warn};
 
 use crate::state::State;
 
+use self::connection::{ApiClientId, ApiConnectInfo};
+
 pub type ResponseStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
 pub type StateFnSender = calloop::channel::Sender<Box<dyn FnOnce(&mut State) + Send>>;
 pub type TonicResult<T> = Result<Response<T>, Status>;
 pub type Sender<T> = async_channel::Sender<T>;
 
+/// Returns the id of the client that sent `request`.
+fn client_id<T>(request: &Request<T>) -> Option<ApiClientId> {
+    request
+        .extensions()
+        .get::<ApiConnectInfo>()
+        .map(|info| info.client_id)
+}
+
 async fn run_unary_no_response<F>(
     fn_sender: &StateFnSender,
     with_state: F,
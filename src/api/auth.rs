@@ -0,0 +1,557 @@
+//! Identifying gRPC clients and enforcing what they may call.
+//!
+//! Every connection is identified once from the peer credentials of its Unix socket,
+//! and every request is checked against that identity.
+//! The main config (and anything running inside the compositor) gets full access,
+//! while other clients get the access configured in the `[api]` section of `pinnacle.toml`.
+
+use std::{
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    sync::{Arc, RwLock},
+    task::{Context, Poll},
+};
+
+use smithay::reexports::rustix::process::geteuid;
+use tokio::net::unix::UCred;
+use tonic::{Status, codegen::http};
+use tracing::debug;
+
+use crate::process::parent_pid;
+
+use super::connection::ApiConnectInfo;
+
+/// What a gRPC client may do.
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiAccess {
+    /// The client may call anything.
+    Full,
+    /// The client may query state and subscribe to signals.
+    #[default]
+    ReadOnly,
+    /// The client may not call anything.
+    None,
+}
+
+/// A gRPC service that access can be scoped to.
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiService {
+    Pinnacle,
+    Window,
+    Tag,
+    Output,
+    Input,
+    Process,
+    Layout,
+    Render,
+    Debug,
+    Signal,
+}
+
+impl ApiService {
+    /// Gets the service from a fully qualified gRPC service name
+    /// like `pinnacle.window.v1.WindowService`.
+    fn from_grpc_name(name: &str) -> Option<Self> {
+        let mut parts = name.split('.');
+        if parts.next()? != "pinnacle" {
+            return None;
+        }
+
+        Some(match parts.next()? {
+            "v1" => Self::Pinnacle,
+            "window" => Self::Window,
+            "tag" => Self::Tag,
+            "output" => Self::Output,
+            "input" => Self::Input,
+            "process" => Self::Process,
+            "layout" => Self::Layout,
+            "render" => Self::Render,
+            "debug" => Self::Debug,
+            "signal" => Self::Signal,
+            _ => return None,
+        })
+    }
+}
+
+/// Access given to a specific executable.
+#[derive(serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ApiClientRule {
+    /// The path to the client's executable.
+    pub executable: PathBuf,
+    pub access: ApiAccess,
+    /// Limits `access` to these services. Other services are denied.
+    pub services: Option<Vec<ApiService>>,
+}
+
+/// The `[api]` section of `pinnacle.toml`.
+#[derive(serde::Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiPermissions {
+    /// Access for clients that aren't the config and don't match a rule in `clients`.
+    pub default_access: ApiAccess,
+    pub clients: Vec<ApiClientRule>,
+}
+
+/// State shared with the gRPC server to identify clients.
+#[derive(Debug, Default)]
+pub struct ApiAuthState {
+    pub permissions: ApiPermissions,
+    /// The pid of the currently running config process.
+    pub config_pid: Option<u32>,
+}
+
+/// The process on the other end of a gRPC connection.
+///
+/// This is resolved once when the client connects, so requests don't have to read `/proc`
+/// and a reused pid can't take over the identity of an exited client.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ApiPeer {
+    pub pid: Option<i32>,
+    /// Whether the client runs as the same user as the compositor.
+    pub same_user: bool,
+    /// The resolved path to the client's executable.
+    pub executable: Option<PathBuf>,
+    /// The pids of the client and its ancestors, nearest first.
+    ancestors: Vec<u32>,
+}
+
+impl ApiPeer {
+    /// Identifies a client from its peer credentials.
+    pub fn from_cred(cred: Option<&UCred>) -> Self {
+        // Clients without credentials can't be told apart, so they get the default access
+        let Some(cred) = cred else {
+            return Self {
+                same_user: true,
+                ..Default::default()
+            };
+        };
+
+        let pid = cred.pid();
+        let same_user = cred.uid() == geteuid().as_raw();
+
+        let Some(raw_pid) = pid.and_then(|pid| u32::try_from(pid).ok()) else {
+            return Self {
+                pid,
+                same_user,
+                ..Default::default()
+            };
+        };
+
+        Self {
+            pid,
+            same_user,
+            executable: std::fs::read_link(format!("/proc/{raw_pid}/exe")).ok(),
+            ancestors: ancestors(raw_pid),
+        }
+    }
+
+    /// Returns whether this client is `pid` or one of its descendants.
+    fn is_descendant_of(&self, pid: u32) -> bool {
+        self.ancestors.contains(&pid)
+    }
+}
+
+/// Returns `pid` followed by the pids of its ancestors up to init.
+fn ancestors(pid: u32) -> Vec<u32> {
+    let mut ancestors = vec![pid];
+    while let Some(ppid) = ancestors
+        .last()
+        .copied()
+        .filter(|&ancestor| ancestor > 1)
+        .and_then(parent_pid)
+    {
+        ancestors.push(ppid);
+    }
+    ancestors
+}
+
+/// The identity and access of the client that sent a request.
+///
+/// This is inserted into the extensions of every request that passes the permission check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiClient {
+    pub pid: Option<i32>,
+    pub executable: Option<PathBuf>,
+    /// Whether this client is the main config or runs inside the compositor.
+    pub is_config: bool,
+    pub access: ApiAccess,
+    pub services: Option<Vec<ApiService>>,
+}
+
+impl ApiClient {
+    /// Checks whether this client may call the method at the given request path,
+    /// e.g. `/pinnacle.window.v1.WindowService/Close`.
+    pub fn check(&self, path: &str) -> Result<(), Status> {
+        if self.access == ApiAccess::Full && self.services.is_none() {
+            return Ok(());
+        }
+
+        let denied = || {
+            let pid = self
+                .pid
+                .map(|pid| pid.to_string())
+                .unwrap_or_else(|| "?".to_string());
+
+            let hint = match self.executable.as_ref() {
+                Some(exe) => format!(
+                    "to allow it, add an `[[api.clients]]` rule with \
+                    `executable = \"{}\"` and `access = \"full\"` to the `[api]` \
+                    table in pinnacle.toml",
+                    exe.display()
+                ),
+                None => "its executable is unknown, so only `[api] default_access` \
+                    in pinnacle.toml applies to it"
+                    .to_string(),
+            };
+
+            Status::permission_denied(format!(
+                "client (pid {pid}) is not allowed to call {path}; {hint}"
+            ))
+        };
+
+        if self.access == ApiAccess::None {
+            return Err(denied());
+        }
+
+        let (service_name, method) = path
+            .trim_start_matches('/')
+            .split_once('/')
+            .unwrap_or_default();
+
+        if service_name.starts_with("grpc.reflection.") {
+            return Ok(());
+        }
+
+        let Some(service) = ApiService::from_grpc_name(service_name) else {
+            return Err(denied());
+        };
+
+        if self
+            .services
+            .as_ref()
+            .is_some_and(|services| !services.contains(&service))
+        {
+            return Err(denied());
+        }
+
+        if self.access == ApiAccess::ReadOnly && !is_read_only(service, method) {
+            return Err(denied());
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns whether a method only queries state.
+fn is_read_only(service: ApiService, method: &str) -> bool {
+    method.starts_with("Get")
+        || service == ApiService::Signal
+        || (service == ApiService::Pinnacle && matches!(method, "Backend" | "Keepalive"))
+        || (service == ApiService::Debug && method == "DumpState")
+}
+
+impl ApiAuthState {
+    /// Resolves the access of an identified client.
+    pub fn client(&self, peer: &ApiPeer) -> ApiClient {
+        let is_config = peer.pid == Some(std::process::id() as i32)
+            || self
+                .config_pid
+                .is_some_and(|config_pid| peer.is_descendant_of(config_pid));
+
+        if !peer.same_user {
+            return ApiClient {
+                pid: peer.pid,
+                executable: peer.executable.clone(),
+                is_config: false,
+                access: ApiAccess::None,
+                services: None,
+            };
+        }
+
+        if is_config {
+            return ApiClient {
+                pid: peer.pid,
+                executable: peer.executable.clone(),
+                is_config: true,
+                access: ApiAccess::Full,
+                services: None,
+            };
+        }
+
+        let rule = peer.executable.as_ref().and_then(|exe| {
+            self.permissions
+                .clients
+                .iter()
+                .find(|rule| &rule.executable == exe)
+        });
+
+        let (access, services) = match rule {
+            Some(rule) => (rule.access, rule.services.clone()),
+            None => (self.permissions.default_access, None),
+        };
+
+        ApiClient {
+            pid: peer.pid,
+            executable: peer.executable.clone(),
+            is_config: false,
+            access,
+            services,
+        }
+    }
+}
+
+/// A [`tower::Layer`] that rejects requests the calling client isn't allowed to make.
+#[derive(Debug, Clone)]
+pub struct ApiAuthLayer {
+    state: Arc<RwLock<ApiAuthState>>,
+}
+
+impl ApiAuthLayer {
+    pub fn new(state: Arc<RwLock<ApiAuthState>>) -> Self {
+        Self { state }
+    }
+}
+
+impl<S> tower::Layer<S> for ApiAuthLayer {
+    type Service = ApiAuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiAuthService {
+            inner,
+            state: self.state.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiAuthService<S> {
+    inner: S,
+    state: Arc<RwLock<ApiAuthState>>,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for ApiAuthService<S>
+where
+    S: tower::Service<http::Request<B>, Response = http::Response<tonic::body::Body>>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let unknown_peer = ApiPeer::from_cred(None);
+        let peer = req
+            .extensions()
+            .get::<ApiConnectInfo>()
+            .map_or(&unknown_peer, |info| &*info.peer);
+
+        let client = self
+            .state
+            .read()
+            .expect("api auth lock poisoned")
+            .client(peer);
+
+        if let Err(status) = client.check(req.uri().path()) {
+            debug!("Denied gRPC request: {}", status.message());
+            return Box::pin(async move { Ok(status.into_http()) });
+        }
+
+        req.extensions_mut().insert(client);
+
+        // The service that was polled ready must be the one that is called
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(inner.call(req))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(access: ApiAccess, services: Option<Vec<ApiService>>) -> ApiClient {
+        ApiClient {
+            pid: Some(1234),
+            executable: None,
+            is_config: false,
+            access,
+            services,
+        }
+    }
+
+    #[test]
+    fn read_only_clients_can_only_query_and_subscribe() {
+        let client = client(ApiAccess::ReadOnly, None);
+
+        assert!(
+            client
+                .check("/pinnacle.window.v1.WindowService/GetTitle")
+                .is_ok()
+        );
+        assert!(
+            client
+                .check("/pinnacle.signal.v1.SignalService/WindowFocused")
+                .is_ok()
+        );
+        assert!(client.check("/pinnacle.v1.PinnacleService/Backend").is_ok());
+        assert!(
+            client
+                .check("/pinnacle.debug.v1.DebugService/DumpState")
+                .is_ok()
+        );
+        assert!(
+            client
+                .check("/grpc.reflection.v1.ServerReflection/ServerReflectionInfo")
+                .is_ok()
+        );
+
+        assert!(client.check("/pinnacle.v1.PinnacleService/Quit").is_err());
+        assert!(
+            client
+                .check("/pinnacle.process.v1.ProcessService/Spawn")
+                .is_err()
+        );
+        assert!(
+            client
+                .check("/pinnacle.window.v1.WindowService/Close")
+                .is_err()
+        );
+        assert!(
+            client
+                .check("/pinnacle.debug.v1.DebugService/SetTraceRecording")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn scoped_clients_are_limited_to_their_services() {
+        let client = client(
+            ApiAccess::Full,
+            Some(vec![ApiService::Tag, ApiService::Signal]),
+        );
+
+        assert!(client.check("/pinnacle.tag.v1.TagService/SwitchTo").is_ok());
+        assert!(
+            client
+                .check("/pinnacle.signal.v1.SignalService/TagActive")
+                .is_ok()
+        );
+        assert!(
+            client
+                .check("/pinnacle.window.v1.WindowService/GetTitle")
+                .is_err()
+        );
+        assert!(client.check("/pinnacle.v1.PinnacleService/Quit").is_err());
+    }
+
+    #[test]
+    fn no_access_denies_everything() {
+        let client = client(ApiAccess::None, None);
+
+        assert!(
+            client
+                .check("/pinnacle.window.v1.WindowService/GetTitle")
+                .is_err()
+        );
+        assert!(
+            client
+                .check("/pinnacle.signal.v1.SignalService/WindowFocused")
+                .is_err()
+        );
+    }
+
+    fn peer(executable: Option<&str>) -> ApiPeer {
+        ApiPeer {
+            pid: Some(1234),
+            same_user: true,
+            executable: executable.map(PathBuf::from),
+            ancestors: vec![1234, 1000, 1],
+        }
+    }
+
+    #[test]
+    fn executable_rules_override_default_access() {
+        let state = ApiAuthState {
+            permissions: ApiPermissions {
+                default_access: ApiAccess::None,
+                clients: vec![ApiClientRule {
+                    executable: "/usr/bin/bar".into(),
+                    access: ApiAccess::ReadOnly,
+                    services: None,
+                }],
+            },
+            config_pid: None,
+        };
+
+        assert_eq!(
+            state.client(&peer(Some("/usr/bin/bar"))).access,
+            ApiAccess::ReadOnly
+        );
+        assert_eq!(
+            state.client(&peer(Some("/usr/bin/other"))).access,
+            ApiAccess::None
+        );
+        assert_eq!(state.client(&peer(None)).access, ApiAccess::None);
+        assert_eq!(
+            state
+                .client(&ApiPeer {
+                    same_user: false,
+                    ..peer(Some("/usr/bin/bar"))
+                })
+                .access,
+            ApiAccess::None
+        );
+    }
+
+    #[test]
+    fn descendants_of_the_config_have_full_access() {
+        let state = ApiAuthState {
+            permissions: Default::default(),
+            config_pid: Some(1000),
+        };
+
+        let client = state.client(&peer(Some("/usr/bin/lua")));
+        assert!(client.is_config);
+        assert_eq!(client.access, ApiAccess::Full);
+
+        let state = ApiAuthState {
+            config_pid: Some(999),
+            ..state
+        };
+        assert!(!state.client(&peer(Some("/usr/bin/lua"))).is_config);
+    }
+
+    #[test]
+    fn ancestors_lead_up_to_the_parent_process() {
+        let pid = std::process::id();
+        let ancestors = ancestors(pid);
+
+        assert_eq!(ancestors[0], pid);
+        assert_eq!(ancestors.get(1).copied(), parent_pid(pid));
+    }
+
+    #[test]
+    fn denials_name_the_missing_rule() {
+        let client = ApiClient {
+            executable: Some("/usr/bin/pinnacle".into()),
+            ..client(ApiAccess::ReadOnly, None)
+        };
+
+        let status = client
+            .check("/pinnacle.tag.v1.TagService/SwitchTo")
+            .unwrap_err();
+        assert!(status.message().contains("[[api.clients]]"));
+        assert!(status.message().contains("/usr/bin/pinnacle"));
+    }
+}
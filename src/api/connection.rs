@@ -0,0 +1,176 @@
+//! Tracking gRPC client connections.
+//!
+//! Each connection to the gRPC socket is given an [`ApiClientId`]. Binds, signal connections,
+//! window rules, and the layout manager remember the client that created them and are
+//! removed when that client disconnects, so several clients can configure the compositor
+//! at once without clobbering each other.
+
+use std::{
+    io,
+    pin::Pin,
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    task::{Context, Poll},
+};
+
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::{UnixListener, UnixStream},
+};
+use tokio_stream::{Stream, StreamExt, wrappers::UnixListenerStream};
+use tonic::transport::server::{Connected, UdsConnectInfo};
+use tracing::debug;
+
+use crate::state::Pinnacle;
+
+use super::{StateFnSender, auth::ApiPeer};
+
+static API_CLIENT_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// An id unique to each connection to the gRPC socket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct ApiClientId(u64);
+
+impl ApiClientId {
+    fn next() -> Self {
+        Self(API_CLIENT_ID_COUNTER.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub fn to_inner(self) -> u64 {
+        self.0
+    }
+}
+
+/// Connection info inserted into the extensions of every request.
+#[derive(Debug, Clone)]
+pub struct ApiConnectInfo {
+    pub uds: UdsConnectInfo,
+    pub client_id: ApiClientId,
+    /// The process on the other end of the connection.
+    pub peer: Arc<ApiPeer>,
+}
+
+/// A connection to the gRPC socket.
+///
+/// Dropping this tells the compositor to clean up everything the client owned.
+pub struct ApiConnection {
+    stream: UnixStream,
+    client_id: ApiClientId,
+    sender: StateFnSender,
+    peer: OnceLock<Arc<ApiPeer>>,
+}
+
+impl ApiConnection {
+    fn new(stream: UnixStream, sender: StateFnSender) -> Self {
+        let client_id = ApiClientId::next();
+        debug!(?client_id, "gRPC client connected");
+        Self {
+            stream,
+            client_id,
+            sender,
+            peer: OnceLock::new(),
+        }
+    }
+}
+
+impl Drop for ApiConnection {
+    fn drop(&mut self) {
+        let client_id = self.client_id;
+        // This fails if the compositor is shutting down, in which case there's nothing to clean up
+        let _ = self.sender.send(Box::new(move |state| {
+            state.pinnacle.remove_api_client(client_id);
+        }));
+    }
+}
+
+impl Connected for ApiConnection {
+    type ConnectInfo = ApiConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        let uds = self.stream.connect_info();
+
+        // Identifying a client reads from /proc, so only do it once per connection
+        let peer = self
+            .peer
+            .get_or_init(|| Arc::new(ApiPeer::from_cred(uds.peer_cred.as_ref())))
+            .clone();
+
+        ApiConnectInfo {
+            uds,
+            client_id: self.client_id,
+            peer,
+        }
+    }
+}
+
+impl AsyncRead for ApiConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for ApiConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.stream.is_write_vectored()
+    }
+}
+
+/// Accepts connections on `listener`, giving each one an [`ApiClientId`].
+pub fn incoming(
+    listener: UnixListener,
+    sender: StateFnSender,
+) -> impl Stream<Item = io::Result<ApiConnection>> {
+    UnixListenerStream::new(listener)
+        .map(move |stream| stream.map(|stream| ApiConnection::new(stream, sender.clone())))
+}
+
+impl Pinnacle {
+    /// Removes binds, signal connections, window rules, and the layout manager
+    /// owned by a client that disconnected.
+    pub fn remove_api_client(&mut self, client_id: ApiClientId) {
+        debug!(?client_id, "gRPC client disconnected, cleaning up");
+
+        self.input_state.bind_state.remove_owned_by(Some(client_id));
+
+        self.signal_state.remove_client(client_id);
+
+        if self.window_rule_state.remove_client(client_id) {
+            self.apply_finished_window_rules();
+        }
+
+        if self.layout_state.layout_manager_owner == Some(client_id) {
+            self.layout_state.layout_manager_owner = None;
+            self.layout_state.layout_request_sender = None;
+        }
+    }
+}
@@ -2,15 +2,25 @@ use pinnacle_api_defs::pinnacle::{
     debug::{
         self,
         v1::{
-            SetCursorPlaneScanoutRequest, SetDamageVisualizationRequest,
-            SetOpaqueRegionVisualizationRequest, SetProcessPipingRequest,
+            DumpStateRequest, DumpStateResponse, SetCursorPlaneScanoutRequest,
+            SetDamageVisualizationRequest, SetOpaqueRegionVisualizationRequest,
+            SetProcessPipingRequest, SetTraceRecordingRequest, SetTraceRecordingResponse,
         },
     },
     util::v1::SetOrToggle,
 };
+use std::path::PathBuf;
+
 use tonic::{Request, Status};
 
-use crate::api::{TonicResult, run_unary_no_response};
+use crate::{
+    api::{
+        TonicResult, run_unary, run_unary_no_response,
+        trace::{TraceStartState, default_trace_path},
+    },
+    tag::TagId,
+    window::window_state::WindowId,
+};
 
 #[tonic::async_trait]
 impl debug::v1::debug_service_server::DebugService for super::DebugService {
@@ -123,4 +133,82 @@ impl debug::v1::debug_service_server::DebugService for super::DebugService {
         })
         .await
     }
+
+    async fn set_trace_recording(
+        &self,
+        request: Request<SetTraceRecordingRequest>,
+    ) -> TonicResult<SetTraceRecordingResponse> {
+        let request = request.into_inner();
+        let set_or_toggle = request.set_or_toggle();
+        let path = request.path.map(PathBuf::from);
+
+        let set = match set_or_toggle {
+            SetOrToggle::Set => Some(true),
+            SetOrToggle::Unset => Some(false),
+            SetOrToggle::Toggle => None,
+            SetOrToggle::Unspecified => {
+                return Err(Status::invalid_argument("no set or toggle specified"));
+            }
+        };
+
+        run_unary(&self.sender, move |state| {
+            let recorder = &state.pinnacle.config.trace_recorder;
+            let current = recorder.path();
+
+            if !set.unwrap_or(current.is_none()) {
+                return Ok(SetTraceRecordingResponse {
+                    recording: false,
+                    path: recorder.stop().map(|path| path.display().to_string()),
+                });
+            }
+
+            // Keep the current recording unless a different file was requested
+            if let Some(current) = current
+                && path.as_ref().is_none_or(|path| *path == current)
+            {
+                return Ok(SetTraceRecordingResponse {
+                    recording: true,
+                    path: Some(current.display().to_string()),
+                });
+            }
+
+            let path = path
+                .or_else(|| {
+                    state
+                        .pinnacle
+                        .xdg_base_dirs
+                        .get_state_home()
+                        .map(|dir| default_trace_path(&dir))
+                })
+                .ok_or_else(|| Status::failed_precondition("no state directory to record to"))?;
+
+            let start_state = TraceStartState {
+                next_tag_id: TagId::peek_next().to_inner(),
+                next_window_id: WindowId::peek_next().0,
+                dump: state.pinnacle.dump_state(),
+            };
+
+            recorder.start(&path, Some(start_state)).map_err(|err| {
+                Status::internal(format!("failed to record to {}: {err}", path.display()))
+            })?;
+
+            Ok(SetTraceRecordingResponse {
+                recording: true,
+                path: Some(path.display().to_string()),
+            })
+        })
+        .await
+    }
+
+    async fn dump_state(
+        &self,
+        _request: Request<DumpStateRequest>,
+    ) -> TonicResult<DumpStateResponse> {
+        run_unary(&self.sender, |state| {
+            let json = serde_json::to_string(&state.pinnacle.dump_state())
+                .map_err(|err| Status::internal(format!("failed to serialize state: {err}")))?;
+            Ok(DumpStateResponse { json })
+        })
+        .await
+    }
 }
@@ -1,16 +1,21 @@
+use std::time::Duration;
+
 use pinnacle_api_defs::pinnacle::input::{
     self,
     v1::{
-        AccelProfile, BindInfo, BindRequest, BindResponse, ClickMethod, EnterBindLayerRequest,
-        GetBindInfosRequest, GetBindInfosResponse, GetBindLayerStackRequest,
-        GetBindLayerStackResponse, GetDeviceCapabilitiesRequest, GetDeviceCapabilitiesResponse,
-        GetDeviceInfoRequest, GetDeviceInfoResponse, GetDeviceTypeRequest, GetDeviceTypeResponse,
-        GetDevicesRequest, GetDevicesResponse, KeybindOnPressRequest, KeybindStreamRequest,
+        AccelProfile, AccelType, BindInfo, BindRequest, BindResponse, CalibrationMatrix,
+        ClickMethod, CustomAccel, CustomAccelCurve, EnterBindLayerRequest, GetBindInfosRequest,
+        GetBindInfosResponse, GetBindLayerStackRequest, GetBindLayerStackResponse,
+        GetDeviceCapabilitiesRequest, GetDeviceCapabilitiesResponse, GetDeviceInfoRequest,
+        GetDeviceInfoResponse, GetDeviceLibinputSettingsRequest, GetDeviceLibinputSettingsResponse,
+        GetDeviceTypeRequest, GetDeviceTypeResponse, GetDevicesRequest, GetDevicesResponse,
+        GetXkbLayoutRequest, GetXkbLayoutResponse, KeybindOnPressRequest, KeybindStreamRequest,
         KeybindStreamResponse, MousebindOnPressRequest, MousebindStreamRequest,
         MousebindStreamResponse, ScrollMethod, SendEventsMode, SetBindPropertiesRequest,
-        SetDeviceLibinputSettingRequest, SetDeviceMapTargetRequest, SetRepeatRateRequest,
-        SetXcursorRequest, SetXkbConfigRequest, SetXkbKeymapRequest, SwitchXkbLayoutRequest,
-        TapButtonMap, set_device_map_target_request::Target, switch_xkb_layout_request::Action,
+        SetCursorHideRequest, SetDeviceLibinputSettingRequest, SetDeviceMapTargetRequest,
+        SetRepeatRateRequest, SetXcursorRequest, SetXkbConfigRequest, SetXkbKeymapRequest,
+        SetXkbLayoutPerWindowRequest, SwitchXkbLayoutRequest, TapButtonMap,
+        set_device_map_target_request::Target, switch_xkb_layout_request::Action,
     },
 };
 use smithay::reexports::input as libinput;
@@ -20,15 +25,20 @@ use smithay::{
     utils::{Logical, Rectangle},
 };
 use tonic::{Request, Status};
-use tracing::{error, warn};
+use tracing::{debug, error, warn};
 
 use crate::{
-    api::{ResponseStream, TonicResult, run_server_streaming, run_unary, run_unary_no_response},
+    api::{
+        ResponseStream, TonicResult, client_id, run_server_streaming, run_unary,
+        run_unary_no_response,
+    },
     input::{
         bind::{Edge, ModMask},
-        libinput::device_type,
+        keyboard,
+        libinput::{self as device_config, DeviceState, device_type},
     },
     output::OutputName,
+    state::{State, WithState},
 };
 
 use super::InputService;
@@ -39,6 +49,7 @@ impl input::v1::input_service_server::InputService for InputService {
     type MousebindStreamStream = ResponseStream<MousebindStreamResponse>;
 
     async fn bind(&self, request: Request<BindRequest>) -> TonicResult<BindResponse> {
+        let owner = client_id(&request);
         let request = request.into_inner();
 
         let Some(bind) = request.bind else {
@@ -136,6 +147,7 @@ impl input::v1::input_service_server::InputService for InputService {
                         quit,
                         reload_config,
                         allow_when_locked,
+                        owner,
                     );
 
                     bind_id
@@ -156,6 +168,7 @@ impl input::v1::input_service_server::InputService for InputService {
                             quit,
                             reload_config,
                             allow_when_locked,
+                            owner,
                         );
 
                     bind_id
@@ -551,19 +564,50 @@ impl input::v1::input_service_server::InputService for InputService {
     async fn set_xkb_config(&self, request: Request<SetXkbConfigRequest>) -> TonicResult<()> {
         let request = request.into_inner();
 
+        let new_config = XkbConfig {
+            rules: request.rules(),
+            variant: request.variant(),
+            model: request.model(),
+            layout: request.layout(),
+            options: request.options.clone(),
+        };
+
+        let keymap = keyboard::compile_keymap(&new_config);
+        let device_sysname = request.device_sysname.clone();
+
         run_unary_no_response(&self.sender, move |state| {
-            let new_config = XkbConfig {
-                rules: request.rules(),
-                variant: request.variant(),
-                model: request.model(),
-                layout: request.layout(),
-                options: request.options.clone(),
+            let Some(keymap) = keymap else {
+                error!("Failed to set xkbconfig: invalid config");
+                return;
             };
+
+            if let Some(device_sysname) = device_sysname {
+                let Some((device, device_state)) = device_state_mut(state, &device_sysname) else {
+                    return;
+                };
+                device_state.keyboard_config.keymap = Some(keymap);
+                state
+                    .pinnacle
+                    .input_state
+                    .keyboard_config_state
+                    .invalidate_device(&device);
+                return;
+            }
+
+            state
+                .pinnacle
+                .input_state
+                .keyboard_config_state
+                .default_keymap = Some(keymap.clone());
+            state.pinnacle.input_state.keyboard_config_state.reset();
+
             if let Some(kb) = state.pinnacle.seat.get_keyboard()
-                && let Err(err) = kb.set_xkb_config(state, new_config)
+                && let Err(err) = kb.set_keymap_from_string(state, keymap)
             {
                 error!("Failed to set xkbconfig: {err}");
             }
+            state.reset_xkb_layout_changed();
+            state.signal_xkb_layout_changed();
         })
         .await
     }
@@ -577,9 +621,35 @@ impl input::v1::input_service_server::InputService for InputService {
         let delay = request
             .delay
             .ok_or_else(|| Status::invalid_argument("no rate specified"))?;
+        let device_sysname = request.device_sysname;
 
         run_unary_no_response(&self.sender, move |state| {
-            if let Some(kb) = state.pinnacle.seat.get_keyboard() {
+            if let Some(device_sysname) = device_sysname {
+                let Some((device, device_state)) = device_state_mut(state, &device_sysname) else {
+                    return;
+                };
+                device_state.keyboard_config.repeat_info = Some((rate, delay));
+                state
+                    .pinnacle
+                    .input_state
+                    .keyboard_config_state
+                    .invalidate_device(&device);
+                return;
+            }
+
+            let input_state = &mut state.pinnacle.input_state;
+            input_state.keyboard_config_state.default_repeat_info = (rate, delay);
+
+            // Don't clobber the repeat info of the keyboard currently in use
+            let device_repeat_info = input_state
+                .keyboard_config_state
+                .active_device()
+                .and_then(|device| input_state.libinput_state.devices.get(device))
+                .and_then(|device_state| device_state.keyboard_config.repeat_info);
+
+            if device_repeat_info.is_none()
+                && let Some(kb) = state.pinnacle.seat.get_keyboard()
+            {
                 kb.change_repeat_info(rate, delay);
             }
         })
@@ -587,15 +657,39 @@ impl input::v1::input_service_server::InputService for InputService {
     }
 
     async fn set_xkb_keymap(&self, request: Request<SetXkbKeymapRequest>) -> TonicResult<()> {
-        let keymap = request.into_inner().keymap;
+        let request = request.into_inner();
+        let keymap = request.keymap;
+        let device_sysname = request.device_sysname;
 
         run_unary_no_response(&self.sender, move |state| {
+            if let Some(device_sysname) = device_sysname {
+                let Some((device, device_state)) = device_state_mut(state, &device_sysname) else {
+                    return;
+                };
+                device_state.keyboard_config.keymap = Some(keymap);
+                state
+                    .pinnacle
+                    .input_state
+                    .keyboard_config_state
+                    .invalidate_device(&device);
+                return;
+            }
+
+            state
+                .pinnacle
+                .input_state
+                .keyboard_config_state
+                .default_keymap = Some(keymap.clone());
+            state.pinnacle.input_state.keyboard_config_state.reset();
+
             let Some(kb) = state.pinnacle.seat.get_keyboard() else {
                 return;
             };
             if let Err(err) = kb.set_keymap_from_string(state, keymap) {
                 warn!("Failed to set keymap: {err}");
             }
+            state.reset_xkb_layout_changed();
+            state.signal_xkb_layout_changed();
         })
         .await
     }
@@ -621,6 +715,51 @@ impl input::v1::input_service_server::InputService for InputService {
                     }
                 }
             });
+            state.signal_xkb_layout_changed();
+        })
+        .await
+    }
+
+    async fn set_xkb_layout_per_window(
+        &self,
+        request: Request<SetXkbLayoutPerWindowRequest>,
+    ) -> TonicResult<()> {
+        let per_window = request.into_inner().per_window;
+
+        run_unary_no_response(&self.sender, move |state| {
+            let layout_state = &mut state.pinnacle.input_state.xkb_layout_state;
+            if layout_state.per_window == per_window {
+                return;
+            }
+            layout_state.per_window = per_window;
+
+            // Don't restore stale layouts from a previous time this was enabled
+            for win in state.pinnacle.windows.iter() {
+                win.with_state_mut(|state| state.xkb_layout.take());
+            }
+        })
+        .await
+    }
+
+    async fn get_xkb_layout(
+        &self,
+        _request: Request<GetXkbLayoutRequest>,
+    ) -> TonicResult<GetXkbLayoutResponse> {
+        run_unary(&self.sender, move |state| {
+            let Some(kb) = state.pinnacle.seat.get_keyboard() else {
+                return Err(Status::internal("no keyboard"));
+            };
+
+            let (layout_index, layout_name) = kb.with_xkb_state(state, |context| {
+                let xkb = context.xkb().lock().unwrap();
+                let layout = xkb.active_layout();
+                (layout.0, xkb.layout_name(layout).to_string())
+            });
+
+            Ok(GetXkbLayoutResponse {
+                layout_index,
+                layout_name,
+            })
         })
         .await
     }
@@ -638,8 +777,60 @@ impl input::v1::input_service_server::InputService for InputService {
             .ok_or_else(|| Status::invalid_argument("no setting specified"))?;
 
         use pinnacle_api_defs::pinnacle::input::v1::set_device_libinput_setting_request::Setting;
+
+        if let Setting::CustomAccel(custom_accel) = setting {
+            let curves = custom_accel
+                .curves
+                .into_iter()
+                .map(device_config::CustomAccelCurve::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            for (i, curve) in curves.iter().enumerate() {
+                curve
+                    .validate()
+                    .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+                if curves[..i]
+                    .iter()
+                    .any(|other| other.accel_type == curve.accel_type)
+                {
+                    return Err(Status::invalid_argument(format!(
+                        "multiple curves for accel type {:?}",
+                        curve.accel_type
+                    )));
+                }
+            }
+
+            return run_unary(&self.sender, move |state| {
+                let Some((device, device_state)) = device_state_mut(state, &device_sysname) else {
+                    return Ok(());
+                };
+
+                if !device_config::accel_profiles(&device)
+                    .contains(&device_config::AccelProfile::Custom)
+                {
+                    debug!("{device_sysname} does not support custom acceleration");
+                    return Ok(());
+                }
+
+                device_config::apply_custom_accel(&device, &curves).map_err(|err| {
+                    Status::internal(format!(
+                        "failed to set custom accel for {device_sysname}: {err}"
+                    ))
+                })?;
+                device_state.custom_accel = Some(curves);
+
+                Ok(())
+            })
+            .await;
+        }
+
+        let is_accel_profile = matches!(setting, Setting::AccelProfile(_));
+
         // TODO: move into input/libinput.rs
-        let apply_setting: Box<dyn Fn(&mut libinput::Device) + Send> = match setting {
+        let apply_setting: Box<
+            dyn Fn(&mut libinput::Device) -> libinput::DeviceConfigResult + Send,
+        > = match setting {
             Setting::AccelProfile(profile) => {
                 let profile = AccelProfile::try_from(profile).unwrap_or(AccelProfile::Unspecified);
 
@@ -648,16 +839,20 @@ impl input::v1::input_service_server::InputService for InputService {
                         return Err(Status::invalid_argument("unspecified accel profile"));
                     }
                     AccelProfile::Flat => Box::new(|device| {
-                        let _ = device.config_accel_set_profile(libinput::AccelProfile::Flat);
+                        device.config_accel_set_profile(libinput::AccelProfile::Flat)
                     }),
                     AccelProfile::Adaptive => Box::new(|device| {
-                        let _ = device.config_accel_set_profile(libinput::AccelProfile::Adaptive);
+                        device.config_accel_set_profile(libinput::AccelProfile::Adaptive)
+                    }),
+                    AccelProfile::Custom => Box::new(|device| {
+                        device_config::apply_custom_accel(device, &[])
+                            .map_err(|_| libinput::DeviceConfigError::Unsupported)
                     }),
                 }
             }
-            Setting::AccelSpeed(speed) => Box::new(move |device| {
-                let _ = device.config_accel_set_speed(speed);
-            }),
+            Setting::AccelSpeed(speed) => {
+                Box::new(move |device| device.config_accel_set_speed(speed))
+            }
             Setting::CalibrationMatrix(matrix) => {
                 let matrix = <[f32; 6]>::try_from(matrix.matrix).map_err(|vec| {
                     Status::invalid_argument(format!(
@@ -666,9 +861,7 @@ impl input::v1::input_service_server::InputService for InputService {
                     ))
                 })?;
 
-                Box::new(move |device| {
-                    let _ = device.config_calibration_set_matrix(matrix);
-                })
+                Box::new(move |device| device.config_calibration_set_matrix(matrix))
             }
             Setting::ClickMethod(method) => {
                 let method = ClickMethod::try_from(method).unwrap_or(ClickMethod::Unspecified);
@@ -678,33 +871,33 @@ impl input::v1::input_service_server::InputService for InputService {
                         return Err(Status::invalid_argument("unspecified click method"));
                     }
                     ClickMethod::ButtonAreas => Box::new(|device| {
-                        let _ = device.config_click_set_method(libinput::ClickMethod::ButtonAreas);
+                        device.config_click_set_method(libinput::ClickMethod::ButtonAreas)
                     }),
                     ClickMethod::ClickFinger => Box::new(|device| {
-                        let _ = device.config_click_set_method(libinput::ClickMethod::Clickfinger);
+                        device.config_click_set_method(libinput::ClickMethod::Clickfinger)
                     }),
                 }
             }
-            Setting::DisableWhileTyping(disable) => Box::new(move |device| {
-                let _ = device.config_dwt_set_enabled(disable);
-            }),
-            Setting::LeftHanded(enable) => Box::new(move |device| {
-                let _ = device.config_left_handed_set(enable);
-            }),
-            Setting::MiddleEmulation(enable) => Box::new(move |device| {
-                let _ = device.config_middle_emulation_set_enabled(enable);
-            }),
-            Setting::RotationAngle(angle) => Box::new(move |device| {
-                let _ = device.config_rotation_set_angle(angle % 360);
-            }),
-            Setting::ScrollButton(button) => Box::new(move |device| {
-                let _ = device.config_scroll_set_button(button);
-            }),
+            Setting::DisableWhileTyping(disable) => {
+                Box::new(move |device| device.config_dwt_set_enabled(disable))
+            }
+            Setting::LeftHanded(enable) => {
+                Box::new(move |device| device.config_left_handed_set(enable))
+            }
+            Setting::MiddleEmulation(enable) => {
+                Box::new(move |device| device.config_middle_emulation_set_enabled(enable))
+            }
+            Setting::RotationAngle(angle) => {
+                Box::new(move |device| device.config_rotation_set_angle(angle % 360))
+            }
+            Setting::ScrollButton(button) => {
+                Box::new(move |device| device.config_scroll_set_button(button))
+            }
             Setting::ScrollButtonLock(enable) => Box::new(move |device| {
-                let _ = device.config_scroll_set_button_lock(match enable {
+                device.config_scroll_set_button_lock(match enable {
                     true => libinput::ScrollButtonLockState::Enabled,
                     false => libinput::ScrollButtonLockState::Disabled,
-                });
+                })
             }),
             Setting::ScrollMethod(method) => {
                 let method = ScrollMethod::try_from(method).unwrap_or(ScrollMethod::Unspecified);
@@ -714,23 +907,22 @@ impl input::v1::input_service_server::InputService for InputService {
                         return Err(Status::invalid_argument("unspecified scroll method"));
                     }
                     ScrollMethod::NoScroll => Box::new(|device| {
-                        let _ = device.config_scroll_set_method(libinput::ScrollMethod::NoScroll);
+                        device.config_scroll_set_method(libinput::ScrollMethod::NoScroll)
                     }),
                     ScrollMethod::TwoFinger => Box::new(|device| {
-                        let _ = device.config_scroll_set_method(libinput::ScrollMethod::TwoFinger);
+                        device.config_scroll_set_method(libinput::ScrollMethod::TwoFinger)
                     }),
                     ScrollMethod::Edge => Box::new(|device| {
-                        let _ = device.config_scroll_set_method(libinput::ScrollMethod::Edge);
+                        device.config_scroll_set_method(libinput::ScrollMethod::Edge)
                     }),
                     ScrollMethod::OnButtonDown => Box::new(|device| {
-                        let _ =
-                            device.config_scroll_set_method(libinput::ScrollMethod::OnButtonDown);
+                        device.config_scroll_set_method(libinput::ScrollMethod::OnButtonDown)
                     }),
                 }
             }
-            Setting::NaturalScroll(enable) => Box::new(move |device| {
-                let _ = device.config_scroll_set_natural_scroll_enabled(enable);
-            }),
+            Setting::NaturalScroll(enable) => {
+                Box::new(move |device| device.config_scroll_set_natural_scroll_enabled(enable))
+            }
             Setting::TapButtonMap(map) => {
                 let map = TapButtonMap::try_from(map).unwrap_or(TapButtonMap::Unspecified);
 
@@ -739,24 +931,20 @@ impl input::v1::input_service_server::InputService for InputService {
                         return Err(Status::invalid_argument("unspecified tap button map"));
                     }
                     TapButtonMap::LeftRightMiddle => Box::new(|device| {
-                        let _ = device
-                            .config_tap_set_button_map(libinput::TapButtonMap::LeftRightMiddle);
+                        device.config_tap_set_button_map(libinput::TapButtonMap::LeftRightMiddle)
                     }),
                     TapButtonMap::LeftMiddleRight => Box::new(|device| {
-                        let _ = device
-                            .config_tap_set_button_map(libinput::TapButtonMap::LeftMiddleRight);
+                        device.config_tap_set_button_map(libinput::TapButtonMap::LeftMiddleRight)
                     }),
                 }
             }
-            Setting::TapDrag(enable) => Box::new(move |device| {
-                let _ = device.config_tap_set_drag_enabled(enable);
-            }),
-            Setting::TapDragLock(enable) => Box::new(move |device| {
-                let _ = device.config_tap_set_drag_lock_enabled(enable);
-            }),
-            Setting::Tap(enable) => Box::new(move |device| {
-                let _ = device.config_tap_set_enabled(enable);
-            }),
+            Setting::TapDrag(enable) => {
+                Box::new(move |device| device.config_tap_set_drag_enabled(enable))
+            }
+            Setting::TapDragLock(enable) => {
+                Box::new(move |device| device.config_tap_set_drag_lock_enabled(enable))
+            }
+            Setting::Tap(enable) => Box::new(move |device| device.config_tap_set_enabled(enable)),
             Setting::SendEventsMode(mode) => {
                 let mode = SendEventsMode::try_from(mode).unwrap_or(SendEventsMode::Unspecified);
 
@@ -765,34 +953,170 @@ impl input::v1::input_service_server::InputService for InputService {
                         return Err(Status::invalid_argument("unspecified send events mode"));
                     }
                     SendEventsMode::Enabled => Box::new(|device| {
-                        let _ =
-                            device.config_send_events_set_mode(libinput::SendEventsMode::ENABLED);
+                        device.config_send_events_set_mode(libinput::SendEventsMode::ENABLED)
                     }),
                     SendEventsMode::Disabled => Box::new(|device| {
-                        let _ =
-                            device.config_send_events_set_mode(libinput::SendEventsMode::DISABLED);
+                        device.config_send_events_set_mode(libinput::SendEventsMode::DISABLED)
                     }),
                     SendEventsMode::DisabledOnExternalMouse => Box::new(|device| {
-                        let _ = device.config_send_events_set_mode(
+                        device.config_send_events_set_mode(
                             libinput::SendEventsMode::DISABLED_ON_EXTERNAL_MOUSE,
-                        );
+                        )
                     }),
                 }
             }
+            Setting::CustomAccel(_) => unreachable!("handled above"),
         };
 
-        run_unary_no_response(&self.sender, move |state| {
+        run_unary(&self.sender, move |state| {
             let device = state
                 .pinnacle
                 .input_state
                 .libinput_state
                 .devices
-                .keys()
-                .find(|device| device.sysname() == device_sysname);
+                .iter_mut()
+                .find(|(device, _)| device.sysname() == device_sysname);
 
-            if let Some(device) = device {
-                apply_setting(&mut device.clone());
+            if let Some((device, device_state)) = device {
+                match apply_setting(&mut device.clone()) {
+                    Ok(()) => (),
+                    // Configs usually apply settings to every device,
+                    // so ones that don't support a setting are skipped
+                    Err(libinput::DeviceConfigError::Unsupported) => {
+                        debug!("{device_sysname} does not support this setting");
+                        return Ok(());
+                    }
+                    Err(_) => {
+                        return Err(Status::invalid_argument(format!(
+                            "invalid value for this setting on {device_sysname}"
+                        )));
+                    }
+                }
+
+                // Any previous custom curves were replaced
+                if is_accel_profile {
+                    device_state.custom_accel = None;
+                }
             }
+
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get_device_libinput_settings(
+        &self,
+        request: Request<GetDeviceLibinputSettingsRequest>,
+    ) -> TonicResult<GetDeviceLibinputSettingsResponse> {
+        let device_sysname = request.into_inner().device_sysname;
+
+        run_unary(&self.sender, move |state| {
+            let Some((device, device_state)) = state
+                .pinnacle
+                .input_state
+                .libinput_state
+                .devices
+                .iter()
+                .find(|(device, _)| device.sysname() == device_sysname)
+            else {
+                return Err(Status::not_found(format!(
+                    "no device with sysname {device_sysname}"
+                )));
+            };
+
+            let accel_available = device.config_accel_is_available();
+            let accel_profile = accel_available
+                .then(|| device_config::accel_profile(device))
+                .flatten();
+
+            let has_tap = device.config_tap_finger_count() > 0;
+            let has_scroll_button = device
+                .config_scroll_methods()
+                .contains(&libinput::ScrollMethod::OnButtonDown);
+
+            let send_events_mode = device.config_send_events_mode();
+            let send_events_mode = if send_events_mode.contains(libinput::SendEventsMode::DISABLED)
+            {
+                SendEventsMode::Disabled
+            } else if send_events_mode
+                .contains(libinput::SendEventsMode::DISABLED_ON_EXTERNAL_MOUSE)
+            {
+                SendEventsMode::DisabledOnExternalMouse
+            } else {
+                SendEventsMode::Enabled
+            };
+
+            Ok(GetDeviceLibinputSettingsResponse {
+                accel_profile: accel_profile.map(|profile| AccelProfile::from(profile).into()),
+                accel_speed: accel_available.then(|| device.config_accel_speed()),
+                calibration_matrix: device
+                    .config_calibration_has_matrix()
+                    .then(|| device.config_calibration_matrix())
+                    .flatten()
+                    .map(|matrix| CalibrationMatrix {
+                        matrix: matrix.to_vec(),
+                    }),
+                click_method: device
+                    .config_click_method()
+                    .and_then(|method| match method {
+                        libinput::ClickMethod::ButtonAreas => Some(ClickMethod::ButtonAreas),
+                        libinput::ClickMethod::Clickfinger => Some(ClickMethod::ClickFinger),
+                        _ => None,
+                    })
+                    .map(Into::into),
+                disable_while_typing: device
+                    .config_dwt_is_available()
+                    .then(|| device.config_dwt_enabled()),
+                left_handed: device
+                    .config_left_handed_is_available()
+                    .then(|| device.config_left_handed()),
+                middle_emulation: device
+                    .config_middle_emulation_is_available()
+                    .then(|| device.config_middle_emulation_enabled()),
+                rotation_angle: device
+                    .config_rotation_is_available()
+                    .then(|| device.config_rotation_angle()),
+                scroll_button: has_scroll_button.then(|| device.config_scroll_button()),
+                scroll_button_lock: has_scroll_button.then(|| {
+                    device.config_scroll_button_lock() == libinput::ScrollButtonLockState::Enabled
+                }),
+                scroll_method: device
+                    .config_scroll_method()
+                    .and_then(|method| match method {
+                        libinput::ScrollMethod::NoScroll => Some(ScrollMethod::NoScroll),
+                        libinput::ScrollMethod::TwoFinger => Some(ScrollMethod::TwoFinger),
+                        libinput::ScrollMethod::Edge => Some(ScrollMethod::Edge),
+                        libinput::ScrollMethod::OnButtonDown => Some(ScrollMethod::OnButtonDown),
+                        _ => None,
+                    })
+                    .map(Into::into),
+                natural_scroll: device
+                    .config_scroll_has_natural_scroll()
+                    .then(|| device.config_scroll_natural_scroll_enabled()),
+                tap_button_map: device
+                    .config_tap_button_map()
+                    .and_then(|map| match map {
+                        libinput::TapButtonMap::LeftRightMiddle => {
+                            Some(TapButtonMap::LeftRightMiddle)
+                        }
+                        libinput::TapButtonMap::LeftMiddleRight => {
+                            Some(TapButtonMap::LeftMiddleRight)
+                        }
+                        _ => None,
+                    })
+                    .map(Into::into),
+                tap_drag: has_tap.then(|| device.config_tap_drag_enabled()),
+                tap_drag_lock: has_tap.then(|| device.config_tap_drag_lock_enabled()),
+                tap: has_tap.then(|| device.config_tap_enabled()),
+                send_events_mode: send_events_mode.into(),
+                custom_accel: device_state
+                    .custom_accel
+                    .as_ref()
+                    .filter(|_| accel_profile == Some(device_config::AccelProfile::Custom))
+                    .map(|curves| CustomAccel {
+                        curves: curves.iter().cloned().map(CustomAccelCurve::from).collect(),
+                    }),
+            })
         })
         .await
     }
@@ -819,6 +1143,28 @@ impl input::v1::input_service_server::InputService for InputService {
         .await
     }
 
+    async fn set_cursor_hide(&self, request: Request<SetCursorHideRequest>) -> TonicResult<()> {
+        let request = request.into_inner();
+
+        let hide_when_typing = request.hide_when_typing;
+        let timeout_ms = request.timeout_ms;
+
+        run_unary_no_response(&self.sender, move |state| {
+            if let Some(hide_when_typing) = hide_when_typing {
+                state
+                    .pinnacle
+                    .cursor_state
+                    .set_hide_when_typing(hide_when_typing);
+            }
+
+            if let Some(timeout_ms) = timeout_ms {
+                let timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms.into()));
+                state.set_cursor_hide_timeout(timeout);
+            }
+        })
+        .await
+    }
+
     async fn get_devices(
         &self,
         _request: Request<GetDevicesRequest>,
@@ -856,6 +1202,10 @@ impl input::v1::input_service_server::InputService for InputService {
                     name: device.name().to_string(),
                     product_id: device.id_product(),
                     vendor_id: device.id_vendor(),
+                    accel_profiles: device_config::accel_profiles(device)
+                        .into_iter()
+                        .map(|profile| AccelProfile::from(profile).into())
+                        .collect(),
                 })
                 .unwrap_or_default();
 
@@ -990,3 +1340,70 @@ impl input::v1::input_service_server::InputService for InputService {
         .await
     }
 }
+
+fn device_state_mut<'a>(
+    state: &'a mut State,
+    device_sysname: &str,
+) -> Option<(libinput::Device, &'a mut DeviceState)> {
+    let device_state = state
+        .pinnacle
+        .input_state
+        .libinput_state
+        .devices
+        .iter_mut()
+        .find(|(device, _)| device.sysname() == device_sysname)
+        .map(|(device, device_state)| (device.clone(), device_state));
+
+    if device_state.is_none() {
+        warn!("No device with sysname {device_sysname}");
+    }
+
+    device_state
+}
+
+impl From<device_config::AccelProfile> for AccelProfile {
+    fn from(value: device_config::AccelProfile) -> Self {
+        match value {
+            device_config::AccelProfile::Flat => AccelProfile::Flat,
+            device_config::AccelProfile::Adaptive => AccelProfile::Adaptive,
+            device_config::AccelProfile::Custom => AccelProfile::Custom,
+        }
+    }
+}
+
+impl TryFrom<CustomAccelCurve> for device_config::CustomAccelCurve {
+    type Error = Status;
+
+    fn try_from(value: CustomAccelCurve) -> Result<Self, Self::Error> {
+        let accel_type = match value.accel_type() {
+            AccelType::Unspecified => {
+                return Err(Status::invalid_argument("unspecified accel type"));
+            }
+            AccelType::Fallback => device_config::AccelType::Fallback,
+            AccelType::Motion => device_config::AccelType::Motion,
+            AccelType::Scroll => device_config::AccelType::Scroll,
+        };
+
+        Ok(Self {
+            accel_type,
+            step: value.step,
+            points: value.points,
+        })
+    }
+}
+
+impl From<device_config::CustomAccelCurve> for CustomAccelCurve {
+    fn from(value: device_config::CustomAccelCurve) -> Self {
+        let accel_type = match value.accel_type {
+            device_config::AccelType::Fallback => AccelType::Fallback,
+            device_config::AccelType::Motion => AccelType::Motion,
+            device_config::AccelType::Scroll => AccelType::Scroll,
+        };
+
+        Self {
+            accel_type: accel_type.into(),
+            step: value.step,
+            points: value.points,
+        }
+    }
+}
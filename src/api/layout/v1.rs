@@ -6,7 +6,7 @@ use tokio::sync::mpsc::unbounded_channel;
 use tonic::{Request, Streaming};
 
 use crate::{
-    api::{ResponseStream, TonicResult, run_bidirectional_streaming},
+    api::{ResponseStream, TonicResult, client_id, run_bidirectional_streaming},
     layout::LayoutInfo,
     output::OutputName,
 };
@@ -19,6 +19,7 @@ impl layout::v1::layout_service_server::LayoutService for super::LayoutService {
         &self,
         request: Request<Streaming<LayoutRequest>>,
     ) -> TonicResult<Self::LayoutStream> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
         run_bidirectional_streaming(
@@ -62,7 +63,7 @@ impl layout::v1::layout_service_server::LayoutService for super::LayoutService {
                     }
                 }
             },
-            |state, sender, _join_handle| {
+            move |state, sender, _join_handle| {
                 let (send, mut recv) = unbounded_channel::<LayoutInfo>();
                 tokio::spawn(async move {
                     while let Some(info) = recv.recv().await {
@@ -85,6 +86,7 @@ impl layout::v1::layout_service_server::LayoutService for super::LayoutService {
                     .layout_state
                     .layout_request_sender
                     .replace(send);
+                state.pinnacle.layout_state.layout_manager_owner = owner;
             },
         )
     }
@@ -641,12 +641,16 @@ impl output::v1::output_service_server::OutputService for super::OutputService {
         run_unary(&self.sender, move |state| {
             let output = output_name.output(&state.pinnacle);
 
+            // Windows on inactive tags aren't mapped, so go by the output
+            // of their tags instead of their geometry.
             let focus_stack_window_ids = output
                 .as_ref()
                 .map(|output| {
                     state
                         .pinnacle
-                        .focus_stack_for_output(output)
+                        .keyboard_focus_stack
+                        .windows()
+                        .filter(|win| win.output(&state.pinnacle).as_ref() == Some(output))
                         .map(|win| win.with_state(|state| state.id.0))
                         .collect()
                 })
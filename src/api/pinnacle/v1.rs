@@ -1,16 +1,19 @@
+use std::path::PathBuf;
+
 use pinnacle_api_defs::pinnacle::{
     self,
     v1::{
         self, BackendRequest, BackendResponse, KeepaliveRequest, KeepaliveResponse, QuitRequest,
-        ReloadConfigRequest, SetLastErrorRequest, SetXwaylandClientSelfScaleRequest,
-        TakeLastErrorRequest, TakeLastErrorResponse,
+        ReloadConfigRequest, SetLastErrorRequest, SetScreenCapturePermissionRequest,
+        SetXwaylandClientSelfScaleRequest, TakeLastErrorRequest, TakeLastErrorResponse,
     },
 };
 use tonic::{Request, Streaming};
 use tracing::{info, trace};
 
 use crate::api::{
-    ResponseStream, TonicResult, run_bidirectional_streaming, run_unary, run_unary_no_response,
+    ResponseStream, TonicResult, auth::ApiClient, client_id, run_bidirectional_streaming,
+    run_unary, run_unary_no_response,
 };
 
 #[tonic::async_trait]
@@ -39,15 +42,32 @@ impl v1::pinnacle_service_server::PinnacleService for super::PinnacleService {
 
     async fn keepalive(
         &self,
-        _request: Request<Streaming<KeepaliveRequest>>,
+        request: Request<Streaming<KeepaliveRequest>>,
     ) -> TonicResult<Self::KeepaliveStream> {
+        let is_config = request
+            .extensions()
+            .get::<ApiClient>()
+            .is_none_or(|client| client.is_config);
+        let owner = client_id(&request);
+
         run_bidirectional_streaming(
             self.sender.clone(),
-            _request.into_inner(),
+            request.into_inner(),
             |_, _| {},
-            |state, sender, _| {
+            move |state, sender, in_stream| {
+                if !is_config {
+                    // Other clients keep their stream until they disconnect
+                    // so they don't replace the config's keepalive.
+                    tokio::spawn(async move {
+                        let _sender = sender;
+                        let _ = in_stream.await;
+                    });
+                    return;
+                }
+
                 let (oneshot_tx, oneshot_rx) = tokio::sync::oneshot::channel::<()>();
                 state.pinnacle.config.keepalive_sender.replace(oneshot_tx);
+                state.pinnacle.config.keepalive_owner = owner;
                 tokio::spawn(async move {
                     let _sender = sender;
                     let _ = oneshot_rx.await;
@@ -62,7 +82,6 @@ impl v1::pinnacle_service_server::PinnacleService for super::PinnacleService {
             let backend = match &state.backend {
                 crate::backend::Backend::Winit(_) => pinnacle::v1::Backend::Window,
                 crate::backend::Backend::Udev(_) => pinnacle::v1::Backend::Tty,
-                #[cfg(feature = "testing")]
                 crate::backend::Backend::Dummy(_) => pinnacle::v1::Backend::Tty, // unused
             };
 
@@ -108,4 +127,20 @@ impl v1::pinnacle_service_server::PinnacleService for super::PinnacleService {
         })
         .await
     }
+
+    async fn set_screen_capture_permission(
+        &self,
+        request: Request<SetScreenCapturePermissionRequest>,
+    ) -> TonicResult<()> {
+        let request = request.into_inner();
+        let executable = PathBuf::from(request.executable);
+        let allow = request.allow;
+
+        run_unary_no_response(&self.sender, move |state| {
+            state
+                .pinnacle
+                .set_screen_capture_permission(executable, allow);
+        })
+        .await
+    }
 }
@@ -1,14 +1,33 @@
+use std::{path::PathBuf, time::Duration};
+
 use pinnacle_api_defs::pinnacle::process::{
     self,
-    v1::{SetEnvRequest, SpawnRequest, SpawnResponse, WaitOnSpawnRequest, WaitOnSpawnResponse},
+    v1::{
+        GetSupervisedRequest, GetSupervisedResponse, RestartSupervisedRequest, SetEnvRequest,
+        SpawnRequest, SpawnResponse, StopSupervisedRequest, SupervisedProcess, WaitOnSpawnRequest,
+        WaitOnSpawnResponse,
+    },
 };
-use tonic::Request;
+use tonic::{Request, Status};
 
 use crate::{
     api::{ResponseStream, TonicResult, run_server_streaming, run_unary, run_unary_no_response},
-    process::PipeProcesses,
+    handlers::xdg_activation::ActivationContext,
+    output::OutputName,
+    process::{ChildCommand, PipeProcesses, RestartPolicy, SpawnTarget, Supervision},
+    tag::TagId,
 };
 
+impl From<RestartPolicy> for process::v1::RestartPolicy {
+    fn from(value: RestartPolicy) -> Self {
+        match value {
+            RestartPolicy::Never => process::v1::RestartPolicy::Never,
+            RestartPolicy::OnFailure => process::v1::RestartPolicy::OnFailure,
+            RestartPolicy::Always => process::v1::RestartPolicy::Always,
+        }
+    }
+}
+
 #[tonic::async_trait]
 impl process::v1::process_service_server::ProcessService for super::ProcessService {
     type WaitOnSpawnStream = ResponseStream<WaitOnSpawnResponse>;
@@ -25,25 +44,85 @@ impl process::v1::process_service_server::ProcessService for super::ProcessServi
             pipe_stdin,
             pipe_stdout,
             pipe_stderr,
+            supervision,
+            cwd,
+            target_tag_ids,
+            target_output_name,
+            systemd_scope,
+            activation_token,
         } = request;
 
+        let target = if !target_tag_ids.is_empty() {
+            Some(SpawnTarget::Tags(
+                target_tag_ids.into_iter().map(TagId::new).collect(),
+            ))
+        } else {
+            target_output_name.map(|name| SpawnTarget::Output(OutputName(name)))
+        };
+
+        let supervision = supervision
+            .map(|supervision| {
+                if supervision.name.is_empty() {
+                    return Err(Status::invalid_argument("supervised process has no name"));
+                }
+
+                let restart_policy = match supervision.restart_policy() {
+                    process::v1::RestartPolicy::Never => RestartPolicy::Never,
+                    process::v1::RestartPolicy::Unspecified
+                    | process::v1::RestartPolicy::OnFailure => RestartPolicy::OnFailure,
+                    process::v1::RestartPolicy::Always => RestartPolicy::Always,
+                };
+
+                Ok(Supervision::new(
+                    supervision.name,
+                    restart_policy,
+                    supervision
+                        .backoff_ms
+                        .map(|ms| Duration::from_millis(ms.into())),
+                    supervision.max_retries,
+                ))
+            })
+            .transpose()?;
+
         run_unary(&self.sender, move |state| {
             let pipe_processes = !state.pinnacle.config.debug.disable_process_piping;
 
             envs.extend(state.pinnacle.config.process_envs.clone());
 
+            if activation_token {
+                // The config spawned this on behalf of the user, so let it take focus.
+                let (token, data) = state
+                    .pinnacle
+                    .xdg_activation_state
+                    .create_external_token(None);
+                data.user_data
+                    .insert_if_missing(|| ActivationContext::FocusIfPossible);
+                let token = token.as_str().to_string();
+
+                envs.insert("XDG_ACTIVATION_TOKEN".to_string(), token.clone());
+                envs.insert("DESKTOP_STARTUP_ID".to_string(), token);
+            }
+
+            let command = ChildCommand {
+                cmd,
+                shell_cmd,
+                envs,
+                cwd: cwd.map(PathBuf::from),
+                systemd_scope,
+            };
+
             let fds = state.pinnacle.process_state.spawn(
-                &cmd,
-                &shell_cmd,
+                command,
                 unique,
                 once,
-                envs,
                 &state.pinnacle.xdg_base_dirs,
                 PipeProcesses {
                     stdin: pipe_processes && pipe_stdin,
                     stdout: pipe_processes && pipe_stdout,
                     stderr: pipe_processes && pipe_stderr,
                 },
+                supervision,
+                target,
             );
 
             Ok(SpawnResponse {
@@ -99,4 +178,51 @@ impl process::v1::process_service_server::ProcessService for super::ProcessServi
         })
         .await
     }
+
+    async fn get_supervised(
+        &self,
+        _request: Request<GetSupervisedRequest>,
+    ) -> TonicResult<GetSupervisedResponse> {
+        run_unary(&self.sender, move |state| {
+            let processes = state
+                .pinnacle
+                .process_state
+                .supervised()
+                .map(|proc| SupervisedProcess {
+                    name: proc.supervision.name.clone(),
+                    pid: proc.pid,
+                    cmd: proc.command.cmd.clone(),
+                    restart_policy: process::v1::RestartPolicy::from(
+                        proc.supervision.restart_policy,
+                    )
+                    .into(),
+                    restart_count: proc.restart_count,
+                })
+                .collect();
+
+            Ok(GetSupervisedResponse { processes })
+        })
+        .await
+    }
+
+    async fn stop_supervised(&self, request: Request<StopSupervisedRequest>) -> TonicResult<()> {
+        let name = request.into_inner().name;
+
+        run_unary_no_response(&self.sender, move |state| {
+            state.pinnacle.stop_supervised(&name);
+        })
+        .await
+    }
+
+    async fn restart_supervised(
+        &self,
+        request: Request<RestartSupervisedRequest>,
+    ) -> TonicResult<()> {
+        let name = request.into_inner().name;
+
+        run_unary_no_response(&self.sender, move |state| {
+            state.pinnacle.restart_supervised(&name);
+        })
+        .await
+    }
 }
@@ -1,5 +1,6 @@
 use std::{
     collections::{HashMap, VecDeque},
+    path::Path,
     sync::atomic::{AtomicU32, Ordering},
 };
 
@@ -7,18 +8,20 @@ use pinnacle_api_defs::pinnacle::{
     signal::{
         self,
         v1::{
-            InputDeviceAddedRequest, InputDeviceAddedResponse, OutputConnectRequest,
-            OutputConnectResponse, OutputDisconnectRequest, OutputDisconnectResponse,
-            OutputFocusedRequest, OutputFocusedResponse, OutputMoveRequest, OutputMoveResponse,
+            InputDeviceAddedRequest, InputDeviceAddedResponse, InputLayoutChangedRequest,
+            InputLayoutChangedResponse, OutputConnectRequest, OutputConnectResponse,
+            OutputDisconnectRequest, OutputDisconnectResponse, OutputFocusedRequest,
+            OutputFocusedResponse, OutputMoveRequest, OutputMoveResponse,
             OutputPointerEnterRequest, OutputPointerEnterResponse, OutputPointerLeaveRequest,
-            OutputPointerLeaveResponse, OutputResizeRequest, OutputResizeResponse, SignalRequest,
-            StreamControl, TagActiveRequest, TagActiveResponse, TagCreatedRequest,
-            TagCreatedResponse, TagRemovedRequest, TagRemovedResponse, WindowCreatedRequest,
-            WindowCreatedResponse, WindowDestroyedRequest, WindowDestroyedResponse,
-            WindowFocusedRequest, WindowFocusedResponse, WindowLayoutModeChangedRequest,
-            WindowLayoutModeChangedResponse, WindowPointerEnterRequest, WindowPointerEnterResponse,
-            WindowPointerLeaveRequest, WindowPointerLeaveResponse, WindowTitleChangedRequest,
-            WindowTitleChangedResponse,
+            OutputPointerLeaveResponse, OutputResizeRequest, OutputResizeResponse,
+            ProcessExitedRequest, ProcessExitedResponse, ScreenCaptureRequestedRequest,
+            ScreenCaptureRequestedResponse, SignalRequest, StreamControl, TagActiveRequest,
+            TagActiveResponse, TagCreatedRequest, TagCreatedResponse, TagRemovedRequest,
+            TagRemovedResponse, WindowCreatedRequest, WindowCreatedResponse,
+            WindowDestroyedRequest, WindowDestroyedResponse, WindowFocusedRequest,
+            WindowFocusedResponse, WindowLayoutModeChangedRequest, WindowLayoutModeChangedResponse,
+            WindowPointerEnterRequest, WindowPointerEnterResponse, WindowPointerLeaveRequest,
+            WindowPointerLeaveResponse, WindowTitleChangedRequest, WindowTitleChangedResponse,
         },
     },
     window,
@@ -30,12 +33,15 @@ use tracing::warn;
 
 use crate::{
     api::Sender,
+    process::ExitInfo,
     state::{State, WithState},
     tag::Tag,
     window::{WindowElement, window_state::LayoutModeKind},
 };
 
-use super::{ResponseStream, StateFnSender, run_bidirectional_streaming};
+use super::{
+    ResponseStream, StateFnSender, client_id, connection::ApiClientId, run_bidirectional_streaming,
+};
 
 #[derive(Debug, Default)]
 pub struct SignalState {
@@ -64,31 +70,69 @@ pub struct SignalState {
 
     // Input
     pub input_device_added: InputDeviceAdded,
-}
+    pub input_layout_changed: InputLayoutChanged,
 
-impl SignalState {
-    pub fn clear(&mut self) {
-        self.output_connect.clear();
-        self.output_disconnect.clear();
-        self.output_resize.clear();
-        self.output_move.clear();
-        self.output_pointer_enter.clear();
-        self.output_pointer_leave.clear();
-        self.output_focused.clear();
+    // Process
+    pub process_exited: ProcessExited,
 
-        self.window_pointer_enter.clear();
-        self.window_pointer_leave.clear();
-        self.window_focused.clear();
-        self.window_title_changed.clear();
-        self.window_layout_changed.clear();
-        self.window_created.clear();
-        self.window_destroyed.clear();
+    // Privileges
+    pub screen_capture_requested: ScreenCaptureRequested,
+}
 
-        self.tag_active.clear();
-        self.tag_created.clear();
-        self.tag_removed.clear();
+impl SignalState {
+    /// Disconnects all signals owned by `client_id`.
+    pub fn remove_client(&mut self, client_id: ApiClientId) {
+        self.output_connect.remove_client(client_id);
+        self.output_disconnect.remove_client(client_id);
+        self.output_resize.remove_client(client_id);
+        self.output_move.remove_client(client_id);
+        self.output_pointer_enter.remove_client(client_id);
+        self.output_pointer_leave.remove_client(client_id);
+        self.output_focused.remove_client(client_id);
+
+        self.window_pointer_enter.remove_client(client_id);
+        self.window_pointer_leave.remove_client(client_id);
+        self.window_focused.remove_client(client_id);
+        self.window_title_changed.remove_client(client_id);
+        self.window_layout_changed.remove_client(client_id);
+        self.window_created.remove_client(client_id);
+        self.window_destroyed.remove_client(client_id);
+
+        self.tag_active.remove_client(client_id);
+        self.tag_created.remove_client(client_id);
+        self.tag_removed.remove_client(client_id);
+
+        self.input_device_added.remove_client(client_id);
+        self.input_layout_changed.remove_client(client_id);
+
+        self.process_exited.remove_client(client_id);
+
+        self.screen_capture_requested.remove_client(client_id);
+    }
 
-        self.input_device_added.clear();
+    /// Returns whether `client_id` is connected to any signal.
+    pub fn has_client(&self, client_id: ApiClientId) -> bool {
+        self.output_connect.v1.has_client(client_id)
+            || self.output_disconnect.v1.has_client(client_id)
+            || self.output_resize.v1.has_client(client_id)
+            || self.output_move.v1.has_client(client_id)
+            || self.output_pointer_enter.v1.has_client(client_id)
+            || self.output_pointer_leave.v1.has_client(client_id)
+            || self.output_focused.v1.has_client(client_id)
+            || self.window_pointer_enter.v1.has_client(client_id)
+            || self.window_pointer_leave.v1.has_client(client_id)
+            || self.window_focused.v1.has_client(client_id)
+            || self.window_title_changed.v1.has_client(client_id)
+            || self.window_layout_changed.v1.has_client(client_id)
+            || self.window_created.v1.has_client(client_id)
+            || self.window_destroyed.v1.has_client(client_id)
+            || self.tag_active.v1.has_client(client_id)
+            || self.tag_created.v1.has_client(client_id)
+            || self.tag_removed.v1.has_client(client_id)
+            || self.input_device_added.v1.has_client(client_id)
+            || self.input_layout_changed.v1.has_client(client_id)
+            || self.process_exited.v1.has_client(client_id)
+            || self.screen_capture_requested.v1.has_client(client_id)
     }
 }
 
@@ -100,6 +144,7 @@ pub struct SignalData<T> {
 #[derive(Debug)]
 struct SignalInstance<T> {
     sender: Sender<Result<T, Status>>,
+    owner: Option<ApiClientId>,
     ready: bool,
     buffer: VecDeque<T>,
 }
@@ -108,7 +153,8 @@ pub trait Signal {
     type Args<'a>;
 
     fn signal(&mut self, args: Self::Args<'_>);
-    fn clear(&mut self);
+    /// Disconnects all instances of this signal owned by `client_id`.
+    fn remove_client(&mut self, client_id: ApiClientId);
 }
 
 #[derive(Debug, Default)]
@@ -127,8 +173,8 @@ impl Signal for OutputConnect {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -148,8 +194,8 @@ impl Signal for OutputDisconnect {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -173,8 +219,8 @@ impl Signal for OutputResize {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -196,8 +242,8 @@ impl Signal for OutputMove {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -217,8 +263,8 @@ impl Signal for OutputPointerEnter {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -238,8 +284,8 @@ impl Signal for OutputPointerLeave {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -259,8 +305,8 @@ impl Signal for OutputFocused {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -280,8 +326,8 @@ impl Signal for WindowPointerEnter {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -301,8 +347,8 @@ impl Signal for WindowPointerLeave {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -322,8 +368,8 @@ impl Signal for WindowFocused {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -344,8 +390,8 @@ impl Signal for WindowTitleChanged {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -374,8 +420,8 @@ impl Signal for WindowLayoutChanged {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -395,8 +441,8 @@ impl Signal for WindowCreated {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -418,8 +464,8 @@ impl Signal for WindowDestroyed {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -440,8 +486,8 @@ impl Signal for TagActive {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -461,8 +507,8 @@ impl Signal for TagCreated {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -482,8 +528,8 @@ impl Signal for TagRemoved {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -503,8 +549,83 @@ impl Signal for InputDeviceAdded {
         });
     }
 
-    fn clear(&mut self) {
-        self.v1.instances.clear();
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct InputLayoutChanged {
+    v1: SignalData<signal::v1::InputLayoutChangedResponse>,
+}
+
+impl Signal for InputLayoutChanged {
+    type Args<'a> = (u32, &'a str);
+
+    /// Args: (layout index, layout name)
+    fn signal(&mut self, args: Self::Args<'_>) {
+        let (layout_index, layout_name) = args;
+        self.v1.signal(|buf| {
+            buf.push_back(signal::v1::InputLayoutChangedResponse {
+                layout_index,
+                layout_name: layout_name.to_string(),
+            });
+        });
+    }
+
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ProcessExited {
+    v1: SignalData<signal::v1::ProcessExitedResponse>,
+}
+
+impl Signal for ProcessExited {
+    type Args<'a> = (u32, Option<&'a str>, &'a ExitInfo, bool);
+
+    /// Args: (pid, supervised name, exit info, whether the process will be restarted)
+    fn signal(&mut self, args: Self::Args<'_>) {
+        let (pid, name, exit_info, restarting) = args;
+        self.v1.signal(|buf| {
+            buf.push_back(signal::v1::ProcessExitedResponse {
+                pid,
+                name: name.map(ToString::to_string),
+                exit_code: exit_info.exit_code,
+                exit_msg: exit_info.exit_msg.clone(),
+                restarting,
+            });
+        });
+    }
+
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ScreenCaptureRequested {
+    v1: SignalData<signal::v1::ScreenCaptureRequestedResponse>,
+}
+
+impl Signal for ScreenCaptureRequested {
+    type Args<'a> = (&'a Path, u32);
+
+    /// Args: (executable, pid)
+    fn signal(&mut self, args: Self::Args<'_>) {
+        let (executable, pid) = args;
+        self.v1.signal(|buf| {
+            buf.push_back(signal::v1::ScreenCaptureRequestedResponse {
+                executable: executable.to_string_lossy().into_owned(),
+                pid,
+            });
+        });
+    }
+
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.v1.remove_client(client_id);
     }
 }
 
@@ -536,11 +657,17 @@ impl<T> SignalData<T> {
         })
     }
 
-    fn connect(&mut self, id: ClientSignalId, sender: Sender<Result<T, Status>>) {
+    fn connect(
+        &mut self,
+        id: ClientSignalId,
+        sender: Sender<Result<T, Status>>,
+        owner: Option<ApiClientId>,
+    ) {
         self.instances.insert(
             id,
             SignalInstance {
                 sender,
+                owner,
                 ready: true,
                 buffer: Default::default(),
             },
@@ -551,6 +678,17 @@ impl<T> SignalData<T> {
         self.instances.remove(&id);
     }
 
+    fn remove_client(&mut self, client_id: ApiClientId) {
+        self.instances
+            .retain(|_, instance| instance.owner != Some(client_id));
+    }
+
+    fn has_client(&self, client_id: ApiClientId) -> bool {
+        self.instances
+            .values()
+            .any(|instance| instance.owner == Some(client_id))
+    }
+
     /// Mark this signal as ready to send.
     ///
     /// If there are signals already in the buffer, they will be sent.
@@ -572,6 +710,7 @@ impl<T> SignalData<T> {
 
 fn start_signal_stream<I, O, F>(
     sender: StateFnSender,
+    owner: Option<ApiClientId>,
     in_stream: Streaming<I>,
     signal_data_selector: F,
 ) -> Result<Response<ResponseStream<O>>, Status>
@@ -597,7 +736,7 @@ where
         },
         move |state, sender, _join_handle| {
             let signal = signal_data_selector_clone(state);
-            signal.connect(client_signal_id, sender);
+            signal.connect(client_signal_id, sender, owner);
         },
     )
 }
@@ -635,14 +774,20 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
     type TagRemovedStream = ResponseStream<TagRemovedResponse>;
 
     type InputDeviceAddedStream = ResponseStream<InputDeviceAddedResponse>;
+    type InputLayoutChangedStream = ResponseStream<InputLayoutChangedResponse>;
+
+    type ProcessExitedStream = ResponseStream<ProcessExitedResponse>;
+
+    type ScreenCaptureRequestedStream = ResponseStream<ScreenCaptureRequestedResponse>;
 
     async fn output_connect(
         &self,
         request: Request<Streaming<OutputConnectRequest>>,
     ) -> Result<Response<Self::OutputConnectStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.output_connect.v1
         })
     }
@@ -651,9 +796,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<OutputDisconnectRequest>>,
     ) -> Result<Response<Self::OutputDisconnectStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.output_disconnect.v1
         })
     }
@@ -662,9 +808,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<OutputResizeRequest>>,
     ) -> Result<Response<Self::OutputResizeStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.output_resize.v1
         })
     }
@@ -673,9 +820,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<OutputMoveRequest>>,
     ) -> Result<Response<Self::OutputMoveStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.output_move.v1
         })
     }
@@ -684,9 +832,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<OutputPointerEnterRequest>>,
     ) -> Result<Response<Self::OutputPointerEnterStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.output_pointer_enter.v1
         })
     }
@@ -695,9 +844,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<OutputPointerLeaveRequest>>,
     ) -> Result<Response<Self::OutputPointerLeaveStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.output_pointer_leave.v1
         })
     }
@@ -706,9 +856,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<OutputFocusedRequest>>,
     ) -> Result<Response<Self::OutputFocusedStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.output_focused.v1
         })
     }
@@ -717,9 +868,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<WindowPointerEnterRequest>>,
     ) -> Result<Response<Self::WindowPointerEnterStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.window_pointer_enter.v1
         })
     }
@@ -728,9 +880,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<WindowPointerLeaveRequest>>,
     ) -> Result<Response<Self::WindowPointerLeaveStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.window_pointer_leave.v1
         })
     }
@@ -739,9 +892,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<WindowFocusedRequest>>,
     ) -> Result<Response<Self::WindowFocusedStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.window_focused.v1
         })
     }
@@ -750,9 +904,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<WindowTitleChangedRequest>>,
     ) -> Result<Response<Self::WindowTitleChangedStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.window_title_changed.v1
         })
     }
@@ -761,9 +916,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<WindowLayoutModeChangedRequest>>,
     ) -> Result<Response<Self::WindowLayoutModeChangedStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.window_layout_changed.v1
         })
     }
@@ -772,9 +928,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<WindowCreatedRequest>>,
     ) -> Result<Response<Self::WindowCreatedStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.window_created.v1
         })
     }
@@ -783,9 +940,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<WindowDestroyedRequest>>,
     ) -> Result<Response<Self::WindowDestroyedStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.window_destroyed.v1
         })
     }
@@ -794,9 +952,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<TagActiveRequest>>,
     ) -> Result<Response<Self::TagActiveStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.tag_active.v1
         })
     }
@@ -805,9 +964,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<TagCreatedRequest>>,
     ) -> Result<Response<Self::TagCreatedStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.tag_created.v1
         })
     }
@@ -816,9 +976,10 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<TagRemovedRequest>>,
     ) -> Result<Response<Self::TagRemovedStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.tag_removed.v1
         })
     }
@@ -827,10 +988,47 @@ impl signal::v1::signal_service_server::SignalService for SignalService {
         &self,
         request: Request<Streaming<InputDeviceAddedRequest>>,
     ) -> Result<Response<Self::InputDeviceAddedStream>, Status> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
-        start_signal_stream(self.sender.clone(), in_stream, |state| {
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
             &mut state.pinnacle.signal_state.input_device_added.v1
         })
     }
+
+    async fn input_layout_changed(
+        &self,
+        request: Request<Streaming<InputLayoutChangedRequest>>,
+    ) -> Result<Response<Self::InputLayoutChangedStream>, Status> {
+        let owner = client_id(&request);
+        let in_stream = request.into_inner();
+
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
+            &mut state.pinnacle.signal_state.input_layout_changed.v1
+        })
+    }
+
+    async fn process_exited(
+        &self,
+        request: Request<Streaming<ProcessExitedRequest>>,
+    ) -> Result<Response<Self::ProcessExitedStream>, Status> {
+        let owner = client_id(&request);
+        let in_stream = request.into_inner();
+
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
+            &mut state.pinnacle.signal_state.process_exited.v1
+        })
+    }
+
+    async fn screen_capture_requested(
+        &self,
+        request: Request<Streaming<ScreenCaptureRequestedRequest>>,
+    ) -> Result<Response<Self::ScreenCaptureRequestedStream>, Status> {
+        let owner = client_id(&request);
+        let in_stream = request.into_inner();
+
+        start_signal_stream(self.sender.clone(), owner, in_stream, |state| {
+            &mut state.pinnacle.signal_state.screen_capture_requested.v1
+        })
+    }
 }
@@ -0,0 +1,556 @@
+//! Recording gRPC traffic to a file.
+//!
+//! When a recording is in progress, every message that passes through the gRPC server is
+//! written as a line of JSON along with the call it belongs to and a timestamp.
+//! Responses on the signal service are marked as signals.
+//!
+//! The first line holds the outputs, tags, and windows that existed when the recording started,
+//! so a replay can start from the same state.
+//!
+//! A recording can be replayed with `pinnacle debug replay`.
+
+use std::{
+    fs::File,
+    future::Future,
+    io::{LineWriter, Write},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    task::{Context, Poll},
+    time::{Instant, SystemTime},
+};
+
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use tonic::codegen::http;
+use tracing::{info, warn};
+
+use super::auth::ApiClient;
+use crate::dump::StateDump;
+
+/// What happened in a recorded call.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceEventKind {
+    /// A client started a call.
+    Call,
+    /// The client sent a message.
+    Request,
+    /// The client finished sending messages.
+    RequestEnd,
+    /// The server sent a message.
+    Response,
+    /// The server sent a signal.
+    Signal,
+    /// The server finished the call.
+    End,
+    /// The call was dropped before the server finished it.
+    Cancel,
+    /// The compositor's state when the recording started.
+    ///
+    /// This is written first and isn't part of any call.
+    State,
+}
+
+/// A line in a trace file.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct TraceEntry {
+    /// Milliseconds since the recording started.
+    pub time_ms: u64,
+    /// An id shared by all entries of the same call.
+    pub call: u64,
+    pub kind: TraceEventKind,
+    /// The method path, e.g. `/pinnacle.window.v1.WindowService/Close`.
+    ///
+    /// Only set for [`TraceEventKind::Call`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// The pid of the client.
+    ///
+    /// Only set for [`TraceEventKind::Call`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<i32>,
+    /// The encoded protobuf message, in hex.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// The gRPC status code the call ended with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<i32>,
+    /// Only set for [`TraceEventKind::State`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<Box<TraceStartState>>,
+}
+
+/// What a replay needs to start from the same state as the recording.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct TraceStartState {
+    /// The id the next created tag gets.
+    pub next_tag_id: u32,
+    /// The id the next created window gets.
+    pub next_window_id: u32,
+    pub dump: StateDump,
+}
+
+/// Records gRPC traffic while a recording is in progress.
+///
+/// This is shared between the compositor and the gRPC server.
+#[derive(Debug, Clone, Default)]
+pub struct TraceRecorder {
+    writer: Arc<Mutex<Option<TraceWriter>>>,
+    next_call: Arc<AtomicU64>,
+}
+
+#[derive(Debug)]
+struct TraceWriter {
+    file: LineWriter<File>,
+    path: PathBuf,
+    start: Instant,
+}
+
+impl TraceRecorder {
+    /// Starts recording to the file at `path`, replacing any in-progress recording.
+    ///
+    /// `state` is written first so replays can start from it.
+    pub fn start(&self, path: &Path, state: Option<TraceStartState>) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(path)?;
+
+        let prev = self
+            .writer
+            .lock()
+            .expect("trace lock poisoned")
+            .replace(TraceWriter {
+                file: LineWriter::new(file),
+                path: path.to_path_buf(),
+                start: Instant::now(),
+            });
+
+        if let Some(prev) = prev {
+            info!("Finished gRPC trace at {}", prev.path.display());
+        }
+        info!("Recording gRPC trace to {}", path.display());
+
+        if let Some(state) = state {
+            self.write(TraceEntry {
+                time_ms: 0,
+                call: 0,
+                kind: TraceEventKind::State,
+                method: None,
+                pid: None,
+                message: None,
+                status: None,
+                state: Some(Box::new(state)),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Stops recording, returning the path of the finished recording.
+    pub fn stop(&self) -> Option<PathBuf> {
+        let writer = self.writer.lock().expect("trace lock poisoned").take()?;
+        info!("Finished gRPC trace at {}", writer.path.display());
+        Some(writer.path)
+    }
+
+    /// Returns the path being recorded to, if a recording is in progress.
+    pub fn path(&self) -> Option<PathBuf> {
+        self.writer
+            .lock()
+            .expect("trace lock poisoned")
+            .as_ref()
+            .map(|writer| writer.path.clone())
+    }
+
+    /// Records the start of a call if a recording is in progress.
+    fn begin_call(&self, method: &str, pid: Option<i32>) -> Option<TraceCall> {
+        if self.path().is_none() {
+            return None;
+        }
+
+        let call = TraceCall {
+            recorder: self.clone(),
+            id: self.next_call.fetch_add(1, Ordering::Relaxed),
+            is_signal: method.starts_with("/pinnacle.signal."),
+        };
+
+        self.write(TraceEntry {
+            time_ms: 0,
+            call: call.id,
+            kind: TraceEventKind::Call,
+            method: Some(method.to_string()),
+            pid,
+            message: None,
+            status: None,
+            state: None,
+        });
+
+        Some(call)
+    }
+
+    fn write(&self, mut entry: TraceEntry) {
+        let mut writer = self.writer.lock().expect("trace lock poisoned");
+        let Some(trace) = writer.as_mut() else {
+            return;
+        };
+
+        entry.time_ms = trace.start.elapsed().as_millis() as u64;
+
+        let res = serde_json::to_writer(&mut trace.file, &entry)
+            .map_err(std::io::Error::from)
+            .and_then(|()| writeln!(trace.file));
+
+        if let Err(err) = res {
+            warn!(
+                "Failed to write to gRPC trace at {}, stopping recording: {err}",
+                trace.path.display()
+            );
+            *writer = None;
+        }
+    }
+}
+
+/// Returns the default path for a new recording in `state_dir`.
+pub fn default_trace_path(state_dir: &Path) -> PathBuf {
+    let secs = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    state_dir.join("traces").join(format!("trace-{secs}.jsonl"))
+}
+
+/// A call that is being recorded.
+#[derive(Debug, Clone)]
+struct TraceCall {
+    recorder: TraceRecorder,
+    id: u64,
+    is_signal: bool,
+}
+
+impl TraceCall {
+    fn record(&self, kind: TraceEventKind, message: Option<&[u8]>, status: Option<i32>) {
+        self.recorder.write(TraceEntry {
+            time_ms: 0,
+            call: self.id,
+            kind,
+            method: None,
+            pid: None,
+            message: message.map(encode_hex),
+            status,
+            state: None,
+        });
+    }
+}
+
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Splits a stream of bytes into length-prefixed gRPC messages.
+#[derive(Debug, Default)]
+struct MessageParser {
+    buf: Vec<u8>,
+}
+
+impl MessageParser {
+    /// Adds data to the buffer, returning any messages that are now complete.
+    fn push(&mut self, data: &[u8]) -> Vec<Vec<u8>> {
+        self.buf.extend_from_slice(data);
+
+        let mut messages = Vec::new();
+
+        // Each message is a compression flag, a big-endian u32 length, then the message
+        while let Some(len_bytes) = self.buf.get(1..5) {
+            let len = u32::from_be_bytes(len_bytes.try_into().expect("len is 4 bytes")) as usize;
+            if self.buf.len() < 5 + len {
+                break;
+            }
+            messages.push(self.buf[5..5 + len].to_vec());
+            self.buf.drain(..5 + len);
+        }
+
+        messages
+    }
+}
+
+fn grpc_status(headers: &http::HeaderMap) -> Option<i32> {
+    headers.get("grpc-status")?.to_str().ok()?.parse().ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Request,
+    Response,
+}
+
+/// A body that records the messages passing through it.
+pub struct TracedBody<B> {
+    inner: Pin<Box<B>>,
+    trace: Option<(TraceCall, MessageParser)>,
+    side: Side,
+    finished: bool,
+}
+
+impl<B> TracedBody<B> {
+    fn new(inner: B, call: Option<TraceCall>, side: Side) -> Self {
+        Self {
+            inner: Box::pin(inner),
+            trace: call.map(|call| (call, MessageParser::default())),
+            side,
+            finished: false,
+        }
+    }
+
+    fn finish(&mut self, status: Option<i32>) {
+        if self.finished {
+            return;
+        }
+        self.finished = true;
+
+        let Some((call, _)) = self.trace.as_ref() else {
+            return;
+        };
+
+        match self.side {
+            Side::Request => call.record(TraceEventKind::RequestEnd, None, None),
+            Side::Response => call.record(TraceEventKind::End, None, status),
+        }
+    }
+}
+
+impl<B> Drop for TracedBody<B> {
+    fn drop(&mut self) {
+        if self.side == Side::Response
+            && !self.finished
+            && let Some((call, _)) = self.trace.as_ref()
+        {
+            call.record(TraceEventKind::Cancel, None, None);
+        }
+    }
+}
+
+impl<B> Body for TracedBody<B>
+where
+    B: Body<Data = Bytes>,
+{
+    type Data = Bytes;
+    type Error = B::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = &mut *self;
+        let polled = this.inner.as_mut().poll_frame(cx);
+
+        let mut finished_with = None;
+
+        if let Some((call, parser)) = this.trace.as_mut() {
+            match &polled {
+                Poll::Ready(Some(Ok(frame))) => {
+                    if let Some(data) = frame.data_ref() {
+                        let kind = match (this.side, call.is_signal) {
+                            (Side::Request, _) => TraceEventKind::Request,
+                            (Side::Response, false) => TraceEventKind::Response,
+                            (Side::Response, true) => TraceEventKind::Signal,
+                        };
+                        for message in parser.push(data) {
+                            call.record(kind, Some(&message), None);
+                        }
+                    } else if let Some(trailers) = frame.trailers_ref() {
+                        finished_with = Some(grpc_status(trailers));
+                    }
+                }
+                Poll::Ready(None) => finished_with = Some(None),
+                Poll::Ready(Some(Err(_))) | Poll::Pending => (),
+            }
+        }
+
+        if let Some(status) = finished_with {
+            this.finish(status);
+        }
+
+        polled
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// A [`tower::Layer`] that records requests and responses while a recording is in progress.
+#[derive(Debug, Clone)]
+pub struct TraceLayer {
+    recorder: TraceRecorder,
+}
+
+impl TraceLayer {
+    pub fn new(recorder: TraceRecorder) -> Self {
+        Self { recorder }
+    }
+}
+
+impl<S> tower::Layer<S> for TraceLayer {
+    type Service = TraceService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceService {
+            inner,
+            recorder: self.recorder.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TraceService<S> {
+    inner: S,
+    recorder: TraceRecorder,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for TraceService<S>
+where
+    S: tower::Service<http::Request<TracedBody<B>>, Response = http::Response<tonic::body::Body>>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let pid = req
+            .extensions()
+            .get::<ApiClient>()
+            .and_then(|client| client.pid);
+
+        let call = self.recorder.begin_call(req.uri().path(), pid);
+
+        let req = req.map(|body| TracedBody::new(body, call.clone(), Side::Request));
+
+        // The service that was polled ready must be the one that is called
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let fut = inner.call(req);
+
+        Box::pin(async move {
+            let response = fut.await?;
+
+            let Some(call) = call else {
+                return Ok(response);
+            };
+
+            // Trailers-only responses, usually errors
+            if let Some(status) = grpc_status(response.headers()) {
+                call.record(TraceEventKind::End, None, Some(status));
+                return Ok(response);
+            }
+
+            Ok(response.map(|body| {
+                tonic::body::Body::new(TracedBody::new(body, Some(call), Side::Response))
+            }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(message: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0];
+        frame.extend((message.len() as u32).to_be_bytes());
+        frame.extend(message);
+        frame
+    }
+
+    #[test]
+    fn parser_splits_messages_across_chunks() {
+        let mut parser = MessageParser::default();
+
+        let mut data = frame(b"hello");
+        data.extend(frame(b""));
+        data.extend(frame(b"world"));
+
+        let (first, rest) = data.split_at(7);
+
+        assert_eq!(parser.push(first), Vec::<Vec<u8>>::new());
+        assert_eq!(
+            parser.push(rest),
+            vec![b"hello".to_vec(), Vec::new(), b"world".to_vec()]
+        );
+        assert!(parser.buf.is_empty());
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        let bytes = [0x00, 0x7f, 0x80, 0xff, 0x12];
+        let hex = encode_hex(&bytes);
+
+        assert_eq!(hex, "007f80ff12");
+        assert_eq!(decode_hex(&hex), Some(bytes.to_vec()));
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+    }
+
+    #[test]
+    fn recorder_writes_entries_while_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.jsonl");
+        let recorder = TraceRecorder::default();
+
+        assert!(
+            recorder
+                .begin_call("/pinnacle.v1.PinnacleService/Quit", None)
+                .is_none()
+        );
+
+        recorder.start(&path, None).unwrap();
+        let call = recorder
+            .begin_call("/pinnacle.signal.v1.SignalService/WindowFocused", Some(1))
+            .unwrap();
+        call.record(TraceEventKind::Request, Some(&[1, 2]), None);
+        assert_eq!(recorder.stop(), Some(path.clone()));
+        call.record(TraceEventKind::End, None, Some(0));
+
+        let entries = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<TraceEntry>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(call.is_signal);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].kind, TraceEventKind::Call);
+        assert_eq!(
+            entries[0].method.as_deref(),
+            Some("/pinnacle.signal.v1.SignalService/WindowFocused")
+        );
+        assert_eq!(entries[1].kind, TraceEventKind::Request);
+        assert_eq!(entries[1].message.as_deref(), Some("0102"));
+    }
+}
@@ -33,7 +33,7 @@ use tracing::warn;
 
 use crate::{
     api::{
-        ResponseStream, TonicResult, run_bidirectional_streaming_mapped, run_unary,
+        ResponseStream, TonicResult, client_id, run_bidirectional_streaming_mapped, run_unary,
         run_unary_no_response,
     },
     focus::keyboard::KeyboardFocusTarget,
@@ -834,6 +834,7 @@ impl v1::window_service_server::WindowService for super::WindowService {
         &self,
         request: Request<Streaming<WindowRuleRequest>>,
     ) -> TonicResult<Self::WindowRuleStream> {
+        let owner = client_id(&request);
         let in_stream = request.into_inner();
 
         let id_ctr = Arc::new(AtomicU32::default());
@@ -853,31 +854,16 @@ impl v1::window_service_server::WindowService for super::WindowService {
                             let id = finished.request_id;
                             id_ctr.store(id, Ordering::Release);
 
-                            for win in state.pinnacle.window_rule_state.finished_windows() {
-                                let Some(unmapped_idx) = state
-                                    .pinnacle
-                                    .unmapped_windows
-                                    .iter_mut()
-                                    .position(|unmapped| unmapped.window == win)
-                                else {
-                                    continue;
-                                };
-
-                                let mut unmapped =
-                                    state.pinnacle.unmapped_windows.swap_remove(unmapped_idx);
-
-                                state
-                                    .pinnacle
-                                    .apply_window_rules_and_send_initial_configure(&mut unmapped);
-
-                                state.pinnacle.unmapped_windows.push(unmapped);
-                            }
+                            state.pinnacle.apply_finished_window_rules();
                         }
                     }
                 }
             },
-            |state, sender, _join_handle| {
-                state.pinnacle.window_rule_state.new_sender(sender, id_ctr);
+            move |state, sender, _join_handle| {
+                state
+                    .pinnacle
+                    .window_rule_state
+                    .new_sender(sender, id_ctr, owner);
             },
             |request| {
                 Ok(WindowRuleResponse {
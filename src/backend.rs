@@ -22,11 +22,9 @@ use crate::{
     state::{Pinnacle, State, WithState},
 };
 
-#[cfg(feature = "testing")]
 use self::dummy::Dummy;
 use self::{udev::Udev, winit::Winit};
 
-#[cfg(feature = "testing")]
 pub mod dummy;
 pub mod udev;
 pub mod winit;
@@ -36,7 +34,6 @@ pub enum Backend {
     Winit(Winit),
     /// The compositor is running in a tty
     Udev(Udev),
-    #[cfg(feature = "testing")]
     Dummy(Dummy),
 }
 
@@ -55,7 +52,6 @@ impl Backend {
                 }
             }
             Backend::Udev(udev) => udev.upscale_filter = filter,
-            #[cfg(feature = "testing")]
             Backend::Dummy(_) => (),
         }
     }
@@ -68,7 +64,6 @@ impl Backend {
                 }
             }
             Backend::Udev(udev) => udev.downscale_filter = filter,
-            #[cfg(feature = "testing")]
             Backend::Dummy(_) => (),
         }
     }
@@ -77,7 +72,6 @@ impl Backend {
         match self {
             Backend::Winit(winit) => winit.seat_name(),
             Backend::Udev(udev) => udev.seat_name(),
-            #[cfg(feature = "testing")]
             Backend::Dummy(dummy) => dummy.seat_name(),
         }
     }
@@ -86,7 +80,6 @@ impl Backend {
         match self {
             Backend::Winit(winit) => winit.early_import(surface),
             Backend::Udev(udev) => udev.early_import(surface),
-            #[cfg(feature = "testing")]
             Backend::Dummy(dummy) => dummy.early_import(surface),
         }
     }
@@ -98,7 +91,6 @@ impl Backend {
         match self {
             Backend::Winit(winit) => Some(with_renderer(winit.backend.renderer())),
             Backend::Udev(udev) => Some(with_renderer(udev.renderer().ok()?.as_mut())),
-            #[cfg(feature = "testing")]
             Backend::Dummy(_) => None,
         }
     }
@@ -107,7 +99,6 @@ impl Backend {
         match self {
             Backend::Winit(_) => (),
             Backend::Udev(udev) => udev.set_output_vrr(output, vrr),
-            #[cfg(feature = "testing")]
             Backend::Dummy(dummy) => dummy.set_output_vrr(output, vrr),
         }
     }
@@ -121,7 +112,6 @@ impl Backend {
         match self {
             Backend::Winit(_) => (),
             Backend::Udev(udev) => udev.set_output_powered(output, loop_handle, powered),
-            #[cfg(feature = "testing")]
             Backend::Dummy(dummy) => dummy.set_output_powered(output, powered),
         }
     }
@@ -140,7 +130,6 @@ impl Backend {
                 .and_then(|mut renderer| renderer.import_dmabuf(&dmabuf, None))
                 .map(|_| ())
                 .context("udev dmabuf import failed"),
-            #[cfg(feature = "testing")]
             Backend::Dummy(dummy) => dummy
                 .renderer
                 .import_dmabuf(&dmabuf, None)
@@ -223,7 +212,6 @@ impl BackendData for Backend {
         match self {
             Backend::Winit(winit) => winit.seat_name(),
             Backend::Udev(udev) => udev.seat_name(),
-            #[cfg(feature = "testing")]
             Backend::Dummy(dummy) => dummy.seat_name(),
         }
     }
@@ -232,7 +220,6 @@ impl BackendData for Backend {
         match self {
             Backend::Winit(winit) => winit.reset_buffers(output),
             Backend::Udev(udev) => udev.reset_buffers(output),
-            #[cfg(feature = "testing")]
             Backend::Dummy(dummy) => dummy.reset_buffers(output),
         }
     }
@@ -241,7 +228,6 @@ impl BackendData for Backend {
         match self {
             Backend::Winit(winit) => winit.early_import(surface),
             Backend::Udev(udev) => udev.early_import(surface),
-            #[cfg(feature = "testing")]
             Backend::Dummy(dummy) => dummy.early_import(surface),
         }
     }
@@ -250,7 +236,6 @@ impl BackendData for Backend {
         match self {
             Backend::Winit(winit) => winit.set_output_mode(output, mode),
             Backend::Udev(udev) => udev.set_output_mode(output, mode),
-            #[cfg(feature = "testing")]
             Backend::Dummy(dummy) => dummy.set_output_mode(output, mode),
         }
     }
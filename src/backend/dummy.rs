@@ -128,6 +128,10 @@ impl Pinnacle {
 
         self.signal_state.output_connect.signal(&output);
 
+        let static_output = output.clone();
+        self.loop_handle
+            .insert_idle(move |state| state.apply_static_output_config(&static_output));
+
         self.focus_output(&output);
 
         output
@@ -290,6 +290,7 @@ impl Udev {
                                         .libinput_state
                                         .devices
                                         .insert(device.clone(), DeviceState::default());
+                                    state.pinnacle.apply_static_libinput_config(device);
                                     state
                                         .pinnacle
                                         .signal_state
@@ -1124,6 +1125,11 @@ impl Udev {
             }
         } else {
             pinnacle.signal_state.output_connect.signal(&output);
+
+            let output = output.clone();
+            pinnacle
+                .loop_handle
+                .insert_idle(move |state| state.apply_static_output_config(&output));
         }
 
         pinnacle.output_management_manager_state.update::<State>();
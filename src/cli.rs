@@ -7,6 +7,9 @@ use anyhow::Context;
 use clap::{Parser, ValueHint};
 use tracing::warn;
 
+pub mod msg;
+pub mod replay;
+
 /// Valid backends that Pinnacle can run.
 #[derive(clap::ValueEnum, Debug, Clone, Copy)]
 pub enum Backend {
@@ -16,8 +19,7 @@ pub enum Backend {
     Udev,
     /// Run the dummy backend
     ///
-    /// This does not open a window and is used only for testing.
-    #[cfg(feature = "testing")]
+    /// This does not open a window and is used for testing and replaying traces.
     Dummy,
 }
 
@@ -131,6 +133,9 @@ pub enum CliSubcommand {
         #[arg(short, long)]
         execute: Option<String>,
     },
+
+    /// Query and control a running Pinnacle instance without a config API
+    Msg(msg::MsgArgs),
 }
 
 /// Config subcommands
@@ -410,6 +415,19 @@ pub fn generate_config(args: ConfigGen) -> anyhow::Result<()> {
 pub enum DebugSubcommand {
     // Panic to check backtraces
     Panic,
+    /// Replay a gRPC trace recorded with the debug API
+    ///
+    /// Without `--socket`, this starts a headless instance with no config and the outputs
+    /// and tags the trace was recorded with, then replays against it.
+    Replay(replay::ReplayArgs),
+    /// Print the compositor's state as JSON
+    ///
+    /// This includes windows, tags, outputs, layout trees, focus stacks, and binds.
+    DumpState {
+        /// Connect to the gRPC socket at this path instead of `$PINNACLE_GRPC_SOCKET`
+        #[arg(long, value_name("PATH"))]
+        socket: Option<PathBuf>,
+    },
 }
 
 pub fn start_lua_repl(execute: Option<String>) {
@@ -580,4 +598,44 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn cli_msg_parses_correctly() -> anyhow::Result<()> {
+        let cli = Cli::parse_from(["pinnacle", "msg", "tag", "switch", "3", "--json"]);
+
+        let Some(CliSubcommand::Msg(msg_args)) = cli.subcommand else {
+            anyhow::bail!("cli.subcommand msg doesn't exist");
+        };
+
+        assert!(msg_args.json);
+        assert_matches!(
+            msg_args.command,
+            msg::MsgCommand::Tag(msg::TagCommand::Switch { name, output: None }) if name == "3"
+        );
+
+        let cli = Cli::parse_from(["pinnacle", "msg", "output", "set-scale", "DP-1", "1.5"]);
+
+        let Some(CliSubcommand::Msg(msg_args)) = cli.subcommand else {
+            anyhow::bail!("cli.subcommand msg doesn't exist");
+        };
+
+        assert_matches!(
+            msg_args.command,
+            msg::MsgCommand::Output(msg::OutputCommand::SetScale { name, scale })
+                if name == "DP-1" && scale == 1.5
+        );
+
+        let cli = Cli::parse_from(["pinnacle", "msg", "subscribe", "window-focused"]);
+
+        let Some(CliSubcommand::Msg(msg_args)) = cli.subcommand else {
+            anyhow::bail!("cli.subcommand msg doesn't exist");
+        };
+
+        assert_matches!(
+            msg_args.command,
+            msg::MsgCommand::Subscribe { signals } if signals == [msg::SignalName::WindowFocused]
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,842 @@
+//! `pinnacle msg`, a native client for querying and controlling a running Pinnacle.
+//!
+//! This talks to the gRPC socket directly and doesn't need a config API installed.
+//! JSON output is considered stable: fields may be added but will not be renamed or removed.
+
+use std::{
+    future::Future,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use hyper_util::rt::TokioIo;
+use pinnacle_api_defs::pinnacle::{
+    debug::v1::{DumpStateRequest, debug_service_client::DebugServiceClient},
+    output::{self, v1::output_service_client::OutputServiceClient},
+    signal::v1::{SignalRequest, StreamControl, signal_service_client::SignalServiceClient},
+    tag::{self, v1::tag_service_client::TagServiceClient},
+    util::v1::{AbsOrRel, SetOrToggle},
+    window::{
+        self,
+        v1::{LayoutMode, window_service_client::WindowServiceClient},
+    },
+};
+use serde::Serialize;
+use tokio::sync::mpsc::unbounded_channel;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tonic::{
+    Response, Status, Streaming,
+    transport::{Channel, Endpoint, Uri},
+};
+use tower::service_fn;
+
+use crate::{config::GRPC_SOCKET_ENV, dump::StateDump, window::window_state::LayoutModeKind};
+
+/// Query and control a running Pinnacle instance.
+#[derive(clap::Args, Debug)]
+pub struct MsgArgs {
+    /// Print output as JSON
+    ///
+    /// Commands that change something print an acknowledgement.
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Connect to the gRPC socket at this path instead of `$PINNACLE_GRPC_SOCKET`
+    #[arg(long, value_name("PATH"), global = true)]
+    pub socket: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: MsgCommand,
+}
+
+/// `pinnacle msg` subcommands.
+#[derive(clap::Subcommand, Debug)]
+pub enum MsgCommand {
+    /// List all windows
+    Windows,
+    /// List all outputs
+    Outputs,
+    /// List all tags
+    Tags,
+    /// Control a window
+    #[command(subcommand)]
+    Window(WindowCommand),
+    /// Control tags
+    #[command(subcommand)]
+    Tag(TagCommand),
+    /// Control an output
+    #[command(subcommand)]
+    Output(OutputCommand),
+    /// Print signals as JSON lines as they are emitted
+    Subscribe {
+        /// The signals to subscribe to
+        #[arg(required = true)]
+        signals: Vec<SignalName>,
+    },
+}
+
+/// `pinnacle msg window` subcommands.
+///
+/// Commands act on the focused window if no id is given.
+#[derive(clap::Subcommand, Debug)]
+pub enum WindowCommand {
+    /// Close a window
+    Close { id: Option<u32> },
+    /// Focus a window
+    Focus { id: u32 },
+    /// Toggle whether a window is floating
+    ToggleFloating { id: Option<u32> },
+    /// Toggle whether a window is fullscreen
+    ToggleFullscreen { id: Option<u32> },
+    /// Toggle whether a window is maximized
+    ToggleMaximized { id: Option<u32> },
+}
+
+/// `pinnacle msg tag` subcommands.
+///
+/// Tags are looked up by name on the given output, or the focused output if not given.
+#[derive(clap::Subcommand, Debug)]
+pub enum TagCommand {
+    /// Switch to a tag, deactivating all other tags on its output
+    Switch {
+        name: String,
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Toggle whether a tag is active
+    Toggle {
+        name: String,
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
+/// `pinnacle msg output` subcommands.
+#[derive(clap::Subcommand, Debug)]
+pub enum OutputCommand {
+    /// Focus an output
+    Focus { name: String },
+    /// Set an output's scale
+    SetScale { name: String, scale: f32 },
+    /// Power an output on or off
+    SetPowered {
+        name: String,
+        #[arg(action = clap::ArgAction::Set)]
+        powered: bool,
+    },
+}
+
+/// Signals that can be subscribed to.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalName {
+    OutputConnect,
+    OutputDisconnect,
+    OutputResize,
+    OutputMove,
+    OutputPointerEnter,
+    OutputPointerLeave,
+    OutputFocused,
+    WindowPointerEnter,
+    WindowPointerLeave,
+    WindowFocused,
+    WindowTitleChanged,
+    WindowLayoutModeChanged,
+    WindowCreated,
+    WindowDestroyed,
+    TagActive,
+    TagCreated,
+    TagRemoved,
+    InputDeviceAdded,
+    InputLayoutChanged,
+    ProcessExited,
+    ScreenCaptureRequested,
+}
+
+/// A window, as printed by `pinnacle msg windows`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct WindowInfo {
+    pub id: u32,
+    pub app_id: String,
+    pub title: String,
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub focused: bool,
+    pub layout_mode: Option<&'static str>,
+    pub tag_ids: Vec<u32>,
+}
+
+/// An output, as printed by `pinnacle msg outputs`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct OutputInfo {
+    pub name: String,
+    pub make: String,
+    pub model: String,
+    pub serial: String,
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub logical_width: Option<u32>,
+    pub logical_height: Option<u32>,
+    pub scale: f32,
+    pub focused: bool,
+    pub enabled: bool,
+    pub powered: bool,
+    pub tag_ids: Vec<u32>,
+}
+
+/// A tag, as printed by `pinnacle msg tags`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TagInfo {
+    pub id: u32,
+    pub name: String,
+    pub active: bool,
+    pub output: String,
+}
+
+/// What a command that changes something prints with `--json`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Ack {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_name: Option<String>,
+}
+
+impl Ack {
+    fn new() -> Self {
+        Self {
+            ok: true,
+            window_id: None,
+            tag_id: None,
+            output_name: None,
+        }
+    }
+}
+
+/// The result of a `pinnacle msg` command.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Reply {
+    Windows(Vec<WindowInfo>),
+    Outputs(Vec<OutputInfo>),
+    Tags(Vec<TagInfo>),
+    Ack(Ack),
+}
+
+/// Runs `pinnacle msg`.
+pub async fn run(args: MsgArgs) -> anyhow::Result<()> {
+    let channel = connect_to(args.socket).await?;
+
+    if let MsgCommand::Subscribe { signals } = args.command {
+        return subscribe(&channel, signals).await;
+    }
+
+    let reply = execute(&channel, args.command).await?;
+
+    if args.json {
+        return print_json(&reply);
+    }
+
+    match reply {
+        Reply::Windows(windows) => {
+            for win in windows {
+                let focused = if win.focused { "*" } else { " " };
+                println!(
+                    "{focused} {}\t{}\t{}\t{}",
+                    win.id,
+                    win.layout_mode.unwrap_or("unknown"),
+                    win.app_id,
+                    win.title
+                );
+            }
+        }
+        Reply::Outputs(outputs) => {
+            for op in outputs {
+                let focused = if op.focused { "*" } else { " " };
+                println!(
+                    "{focused} {}\t{}x{}+{}+{}\t{}x\t{} {}",
+                    op.name,
+                    op.logical_width.unwrap_or_default(),
+                    op.logical_height.unwrap_or_default(),
+                    op.x.unwrap_or_default(),
+                    op.y.unwrap_or_default(),
+                    op.scale,
+                    op.make,
+                    op.model,
+                );
+            }
+        }
+        Reply::Tags(tags) => {
+            for tag in tags {
+                let active = if tag.active { "*" } else { " " };
+                println!("{active} {}\t{}\t{}", tag.id, tag.output, tag.name);
+            }
+        }
+        Reply::Ack(_) => (),
+    }
+
+    Ok(())
+}
+
+/// Runs a `pinnacle msg` command other than [`MsgCommand::Subscribe`], which
+/// prints signals as they come in instead of replying once.
+pub async fn execute(channel: &Channel, command: MsgCommand) -> anyhow::Result<Reply> {
+    let reply = match command {
+        MsgCommand::Windows => Reply::Windows(windows(&dump(channel).await?)),
+        MsgCommand::Outputs => Reply::Outputs(outputs(channel, &dump(channel).await?).await?),
+        MsgCommand::Tags => Reply::Tags(tags(&dump(channel).await?)),
+        MsgCommand::Window(command) => Reply::Ack(window_command(channel, command).await?),
+        MsgCommand::Tag(command) => Reply::Ack(tag_command(channel, command).await?),
+        MsgCommand::Output(command) => Reply::Ack(output_command(channel, command).await?),
+        MsgCommand::Subscribe { .. } => {
+            anyhow::bail!("subscribing prints signals as they come in and has no reply")
+        }
+    };
+
+    Ok(reply)
+}
+
+/// Runs `pinnacle debug dump-state`.
+pub async fn dump_state(socket: Option<PathBuf>) -> anyhow::Result<()> {
+    let channel = connect_to(socket).await?;
+
+    let json = DebugServiceClient::new(channel)
+        .dump_state(DumpStateRequest {})
+        .await?
+        .into_inner()
+        .json;
+
+    print_json(&serde_json::from_str::<serde_json::Value>(&json)?)
+}
+
+/// Connects to `socket`, or the socket in `$PINNACLE_GRPC_SOCKET` if it's `None`.
+async fn connect_to(socket: Option<PathBuf>) -> anyhow::Result<Channel> {
+    let socket = match socket {
+        Some(socket) => socket,
+        None => std::env::var_os(GRPC_SOCKET_ENV)
+            .map(PathBuf::from)
+            .with_context(|| format!("${GRPC_SOCKET_ENV} is not set; is Pinnacle running?"))?,
+    };
+
+    connect(&socket)
+        .await
+        .with_context(|| format!("failed to connect to {}", socket.display()))
+}
+
+/// Connects to the gRPC socket at `socket`.
+pub async fn connect(socket: &Path) -> anyhow::Result<Channel> {
+    let socket = socket.to_path_buf();
+
+    // The uri is ignored, we use a unix socket
+    let channel = Endpoint::try_from("http://[::]:50051")?
+        .connect_with_connector(service_fn(move |_: Uri| {
+            let socket = socket.clone();
+            async move {
+                Ok::<_, std::io::Error>(TokioIo::new(
+                    tokio::net::UnixStream::connect(socket).await?,
+                ))
+            }
+        }))
+        .await?;
+
+    Ok(channel)
+}
+
+fn print_json(value: &impl Serialize) -> anyhow::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, value)?;
+    writeln!(stdout)?;
+    Ok(())
+}
+
+fn layout_mode_name(layout_mode: LayoutMode) -> Option<&'static str> {
+    match layout_mode {
+        LayoutMode::Unspecified => None,
+        LayoutMode::Tiled => Some("tiled"),
+        LayoutMode::Floating => Some("floating"),
+        LayoutMode::Fullscreen => Some("fullscreen"),
+        LayoutMode::Maximized => Some("maximized"),
+    }
+}
+
+/// Gets the compositor's state in one call.
+async fn dump(channel: &Channel) -> anyhow::Result<StateDump> {
+    let json = DebugServiceClient::new(channel.clone())
+        .dump_state(DumpStateRequest {})
+        .await?
+        .into_inner()
+        .json;
+
+    Ok(serde_json::from_str(&json)?)
+}
+
+fn layout_mode_kind_name(layout_mode: LayoutModeKind) -> &'static str {
+    match layout_mode {
+        LayoutModeKind::Tiled => "tiled",
+        // The API reports spilled windows as floating
+        LayoutModeKind::Floating | LayoutModeKind::Spilled => "floating",
+        LayoutModeKind::Fullscreen => "fullscreen",
+        LayoutModeKind::Maximized => "maximized",
+    }
+}
+
+fn windows(dump: &StateDump) -> Vec<WindowInfo> {
+    dump.windows
+        .iter()
+        .map(|win| WindowInfo {
+            id: win.id,
+            app_id: win.app_id.clone().unwrap_or_default(),
+            title: win.title.clone().unwrap_or_default(),
+            x: win.geometry.map(|geo| geo.x),
+            y: win.geometry.map(|geo| geo.y),
+            width: win
+                .geometry
+                .map(|geo| geo.width.try_into().unwrap_or_default()),
+            height: win
+                .geometry
+                .map(|geo| geo.height.try_into().unwrap_or_default()),
+            focused: dump.focus.focused_window == Some(win.id),
+            layout_mode: Some(layout_mode_kind_name(win.layout_mode)),
+            tag_ids: win.tags.clone(),
+        })
+        .collect()
+}
+
+/// Gets outputs from `dump`, asking for the make, model, and serial of all of them at once.
+async fn outputs(channel: &Channel, dump: &StateDump) -> anyhow::Result<Vec<OutputInfo>> {
+    let requests = dump
+        .outputs
+        .iter()
+        .map(|op| {
+            let mut client = OutputServiceClient::new(channel.clone());
+            let output_name = op.name.clone();
+            tokio::spawn(async move {
+                client
+                    .get_info(output::v1::GetInfoRequest { output_name })
+                    .await
+                    .map(|response| response.into_inner())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut infos = Vec::new();
+    for request in requests {
+        infos.push(request.await??);
+    }
+
+    let outputs = dump
+        .outputs
+        .iter()
+        .zip(infos)
+        .map(|(op, info)| OutputInfo {
+            name: op.name.clone(),
+            make: info.make,
+            model: info.model,
+            serial: info.serial,
+            x: op.geometry.map(|geo| geo.x),
+            y: op.geometry.map(|geo| geo.y),
+            logical_width: op
+                .geometry
+                .map(|geo| geo.width.try_into().unwrap_or_default()),
+            logical_height: op
+                .geometry
+                .map(|geo| geo.height.try_into().unwrap_or_default()),
+            scale: op.scale as f32,
+            focused: dump.focus.focused_output.as_ref() == Some(&op.name),
+            enabled: op.enabled,
+            powered: op.powered,
+            tag_ids: op
+                .tags
+                .iter()
+                .filter(|tag| !tag.defunct)
+                .map(|tag| tag.id)
+                .collect(),
+        })
+        .collect();
+
+    Ok(outputs)
+}
+
+fn tags(dump: &StateDump) -> Vec<TagInfo> {
+    dump.outputs
+        .iter()
+        .flat_map(|op| {
+            op.tags
+                .iter()
+                .filter(|tag| !tag.defunct)
+                .map(|tag| TagInfo {
+                    id: tag.id,
+                    name: tag.name.clone(),
+                    active: tag.active,
+                    output: op.name.clone(),
+                })
+        })
+        .collect()
+}
+
+async fn window_command(channel: &Channel, command: WindowCommand) -> anyhow::Result<Ack> {
+    let mut client = WindowServiceClient::new(channel.clone());
+
+    let window_id = match &command {
+        WindowCommand::Focus { id } => *id,
+        WindowCommand::Close { id }
+        | WindowCommand::ToggleFloating { id }
+        | WindowCommand::ToggleFullscreen { id }
+        | WindowCommand::ToggleMaximized { id } => match id {
+            Some(id) => *id,
+            None => dump(channel)
+                .await?
+                .focus
+                .focused_window
+                .context("no window is focused")?,
+        },
+    };
+
+    let toggle = SetOrToggle::Toggle.into();
+
+    match command {
+        WindowCommand::Close { .. } => {
+            client.close(window::v1::CloseRequest { window_id }).await?;
+        }
+        WindowCommand::Focus { .. } => {
+            client
+                .set_focused(window::v1::SetFocusedRequest {
+                    window_id,
+                    set_or_toggle: SetOrToggle::Set.into(),
+                })
+                .await?;
+        }
+        WindowCommand::ToggleFloating { .. } => {
+            client
+                .set_floating(window::v1::SetFloatingRequest {
+                    window_id,
+                    set_or_toggle: toggle,
+                })
+                .await?;
+        }
+        WindowCommand::ToggleFullscreen { .. } => {
+            client
+                .set_fullscreen(window::v1::SetFullscreenRequest {
+                    window_id,
+                    set_or_toggle: toggle,
+                })
+                .await?;
+        }
+        WindowCommand::ToggleMaximized { .. } => {
+            client
+                .set_maximized(window::v1::SetMaximizedRequest {
+                    window_id,
+                    set_or_toggle: toggle,
+                })
+                .await?;
+        }
+    }
+
+    Ok(Ack {
+        window_id: Some(window_id),
+        ..Ack::new()
+    })
+}
+
+/// Finds the id of the tag called `name` on `output`, or on the focused output if `None`.
+async fn find_tag(channel: &Channel, name: &str, output: Option<String>) -> anyhow::Result<u32> {
+    let dump = dump(channel).await?;
+
+    let output = match output {
+        Some(output) => output,
+        None => dump
+            .focus
+            .focused_output
+            .clone()
+            .context("no output is focused")?,
+    };
+
+    tags(&dump)
+        .into_iter()
+        .find(|tag| tag.name == name && tag.output == output)
+        .map(|tag| tag.id)
+        .with_context(|| format!("no tag named `{name}` on output {output}"))
+}
+
+async fn tag_command(channel: &Channel, command: TagCommand) -> anyhow::Result<Ack> {
+    let mut client = TagServiceClient::new(channel.clone());
+
+    let tag_id = match command {
+        TagCommand::Switch { name, output } => {
+            let tag_id = find_tag(channel, &name, output).await?;
+            client
+                .switch_to(tag::v1::SwitchToRequest { tag_id })
+                .await?;
+            tag_id
+        }
+        TagCommand::Toggle { name, output } => {
+            let tag_id = find_tag(channel, &name, output).await?;
+            client
+                .set_active(tag::v1::SetActiveRequest {
+                    tag_id,
+                    set_or_toggle: SetOrToggle::Toggle.into(),
+                })
+                .await?;
+            tag_id
+        }
+    };
+
+    Ok(Ack {
+        tag_id: Some(tag_id),
+        ..Ack::new()
+    })
+}
+
+async fn output_command(channel: &Channel, command: OutputCommand) -> anyhow::Result<Ack> {
+    let mut client = OutputServiceClient::new(channel.clone());
+
+    let output_name = match &command {
+        OutputCommand::Focus { name }
+        | OutputCommand::SetScale { name, .. }
+        | OutputCommand::SetPowered { name, .. } => name.clone(),
+    };
+
+    let output_names = client
+        .get(output::v1::GetRequest {})
+        .await?
+        .into_inner()
+        .output_names;
+    anyhow::ensure!(
+        output_names.contains(&output_name),
+        "no output named {output_name}"
+    );
+
+    let ack = Ack {
+        output_name: Some(output_name.clone()),
+        ..Ack::new()
+    };
+
+    match command {
+        OutputCommand::Focus { .. } => {
+            client
+                .focus(output::v1::FocusRequest { output_name })
+                .await?;
+        }
+        OutputCommand::SetScale { scale, .. } => {
+            client
+                .set_scale(output::v1::SetScaleRequest {
+                    output_name,
+                    scale,
+                    abs_or_rel: AbsOrRel::Absolute.into(),
+                })
+                .await?;
+        }
+        OutputCommand::SetPowered { powered, .. } => {
+            client
+                .set_powered(output::v1::SetPoweredRequest {
+                    output_name,
+                    powered,
+                })
+                .await?;
+        }
+    }
+
+    Ok(ack)
+}
+
+async fn subscribe(channel: &Channel, signals: Vec<SignalName>) -> anyhow::Result<()> {
+    use pinnacle_api_defs::pinnacle::signal::v1::*;
+    use serde_json::json;
+
+    let mut tasks = tokio::task::JoinSet::new();
+
+    for signal in signals {
+        let mut client = SignalServiceClient::new(channel.clone());
+
+        macro_rules! stream {
+            ($method:ident, $request:ty, |$resp:ident| $json:expr) => {
+                tasks.spawn(async move {
+                    stream_signal::<$request, _, _, _>(
+                        |out| async move { client.$method(out).await },
+                        |$resp| $json,
+                    )
+                    .await
+                })
+            };
+        }
+
+        match signal {
+            SignalName::OutputConnect => stream!(output_connect, OutputConnectRequest, |r| {
+                json!({ "signal": "output-connect", "output_name": r.output_name })
+            }),
+            SignalName::OutputDisconnect => {
+                stream!(output_disconnect, OutputDisconnectRequest, |r| {
+                    json!({ "signal": "output-disconnect", "output_name": r.output_name })
+                })
+            }
+            SignalName::OutputResize => stream!(output_resize, OutputResizeRequest, |r| {
+                json!({
+                    "signal": "output-resize",
+                    "output_name": r.output_name,
+                    "logical_width": r.logical_width,
+                    "logical_height": r.logical_height,
+                })
+            }),
+            SignalName::OutputMove => stream!(output_move, OutputMoveRequest, |r| {
+                json!({
+                    "signal": "output-move",
+                    "output_name": r.output_name,
+                    "x": r.x,
+                    "y": r.y,
+                })
+            }),
+            SignalName::OutputPointerEnter => {
+                stream!(output_pointer_enter, OutputPointerEnterRequest, |r| {
+                    json!({ "signal": "output-pointer-enter", "output_name": r.output_name })
+                })
+            }
+            SignalName::OutputPointerLeave => {
+                stream!(output_pointer_leave, OutputPointerLeaveRequest, |r| {
+                    json!({ "signal": "output-pointer-leave", "output_name": r.output_name })
+                })
+            }
+            SignalName::OutputFocused => stream!(output_focused, OutputFocusedRequest, |r| {
+                json!({ "signal": "output-focused", "output_name": r.output_name })
+            }),
+            SignalName::WindowPointerEnter => {
+                stream!(window_pointer_enter, WindowPointerEnterRequest, |r| {
+                    json!({ "signal": "window-pointer-enter", "window_id": r.window_id })
+                })
+            }
+            SignalName::WindowPointerLeave => {
+                stream!(window_pointer_leave, WindowPointerLeaveRequest, |r| {
+                    json!({ "signal": "window-pointer-leave", "window_id": r.window_id })
+                })
+            }
+            SignalName::WindowFocused => stream!(window_focused, WindowFocusedRequest, |r| {
+                json!({ "signal": "window-focused", "window_id": r.window_id })
+            }),
+            SignalName::WindowTitleChanged => {
+                stream!(window_title_changed, WindowTitleChangedRequest, |r| {
+                    json!({
+                        "signal": "window-title-changed",
+                        "window_id": r.window_id,
+                        "title": r.title,
+                    })
+                })
+            }
+            SignalName::WindowLayoutModeChanged => stream!(
+                window_layout_mode_changed,
+                WindowLayoutModeChangedRequest,
+                |r| {
+                    json!({
+                        "signal": "window-layout-mode-changed",
+                        "window_id": r.window_id,
+                        "layout_mode": layout_mode_name(r.layout_mode()),
+                    })
+                }
+            ),
+            SignalName::WindowCreated => stream!(window_created, WindowCreatedRequest, |r| {
+                json!({ "signal": "window-created", "window_id": r.window_id })
+            }),
+            SignalName::WindowDestroyed => stream!(window_destroyed, WindowDestroyedRequest, |r| {
+                json!({
+                    "signal": "window-destroyed",
+                    "window_id": r.window_id,
+                    "title": r.title,
+                    "app_id": r.app_id,
+                })
+            }),
+            SignalName::TagActive => stream!(tag_active, TagActiveRequest, |r| {
+                json!({ "signal": "tag-active", "tag_id": r.tag_id, "active": r.active })
+            }),
+            SignalName::TagCreated => stream!(tag_created, TagCreatedRequest, |r| {
+                json!({ "signal": "tag-created", "tag_id": r.tag_id })
+            }),
+            SignalName::TagRemoved => stream!(tag_removed, TagRemovedRequest, |r| {
+                json!({ "signal": "tag-removed", "tag_id": r.tag_id })
+            }),
+            SignalName::InputDeviceAdded => {
+                stream!(input_device_added, InputDeviceAddedRequest, |r| {
+                    json!({ "signal": "input-device-added", "device_sysname": r.device_sysname })
+                })
+            }
+            SignalName::InputLayoutChanged => {
+                stream!(input_layout_changed, InputLayoutChangedRequest, |r| {
+                    json!({
+                        "signal": "input-layout-changed",
+                        "layout_index": r.layout_index,
+                        "layout_name": r.layout_name,
+                    })
+                })
+            }
+            SignalName::ProcessExited => stream!(process_exited, ProcessExitedRequest, |r| {
+                json!({
+                    "signal": "process-exited",
+                    "pid": r.pid,
+                    "name": r.name,
+                    "exit_code": r.exit_code,
+                    "exit_msg": r.exit_msg,
+                    "restarting": r.restarting,
+                })
+            }),
+            SignalName::ScreenCaptureRequested => {
+                stream!(
+                    screen_capture_requested,
+                    ScreenCaptureRequestedRequest,
+                    |r| {
+                        json!({
+                            "signal": "screen-capture-requested",
+                            "executable": r.executable,
+                            "pid": r.pid,
+                        })
+                    }
+                )
+            }
+        };
+    }
+
+    // Return as soon as any stream ends, which happens when the compositor exits
+    // or stdout is closed.
+    if let Some(result) = tasks.join_next().await {
+        result??;
+    }
+
+    Ok(())
+}
+
+/// Connects to a signal stream and prints every received signal as a line of JSON.
+async fn stream_signal<Req, Resp, F, Fut>(
+    connect: F,
+    to_json: impl Fn(Resp) -> serde_json::Value,
+) -> anyhow::Result<()>
+where
+    Req: SignalRequest + Send + 'static,
+    F: FnOnce(UnboundedReceiverStream<Req>) -> Fut,
+    Fut: Future<Output = Result<Response<Streaming<Resp>>, Status>>,
+{
+    let (control_sender, recv) = unbounded_channel::<Req>();
+    let mut stream = connect(UnboundedReceiverStream::new(recv))
+        .await?
+        .into_inner();
+
+    let ready = || {
+        control_sender
+            .send(Req::from_control(StreamControl::Ready))
+            .map_err(|_| anyhow::anyhow!("signal stream closed"))
+    };
+
+    ready()?;
+
+    while let Some(response) = stream.message().await? {
+        let line = serde_json::to_string(&to_json(response))?;
+
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{line}")?;
+        stdout.flush()?;
+
+        ready()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,570 @@
+//! `pinnacle debug replay`, which replays a recorded gRPC trace.
+//!
+//! Each recorded call is opened again at the time it was originally made, and the client's
+//! messages are sent with their original timing. Messages the server sent are not replayed,
+//! so replies to server-initiated requests (like layout requests) are sent as they were recorded.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Context;
+use bytes::{Buf, BufMut};
+use smithay::{
+    reexports::calloop::EventLoop,
+    utils::{Logical, Size, Transform},
+};
+use tokio::time::Instant;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tonic::{
+    Code, Request, Status,
+    codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    codegen::http::uri::PathAndQuery,
+    transport::Channel,
+};
+
+use crate::{
+    api::trace::{TraceEntry, TraceEventKind, TraceStartState, decode_hex},
+    cli::Cli,
+    config::GRPC_SOCKET_ENV,
+    state::{Pinnacle, State, WithState},
+    tag::{Tag, TagId},
+    window::window_state::WindowId,
+};
+
+/// Replay a gRPC trace recorded with the debug API.
+#[derive(clap::Args, Debug)]
+pub struct ReplayArgs {
+    /// The trace file to replay
+    #[arg(value_name("FILE"), value_hint(clap::ValueHint::FilePath))]
+    pub file: PathBuf,
+
+    /// Replay against the gRPC socket at this path
+    ///
+    /// Without this, a headless instance with no config and the recorded outputs and tags
+    /// is started to replay against.
+    #[arg(long, value_name("PATH"))]
+    pub socket: Option<PathBuf>,
+
+    /// Replay at this multiple of the original speed
+    #[arg(long, default_value_t = 1.0)]
+    pub speed: f64,
+}
+
+/// A recorded call and the client's side of it.
+#[derive(Debug, Clone, PartialEq)]
+struct CallPlan {
+    id: u64,
+    method: String,
+    start: u64,
+    requests: Vec<(u64, Vec<u8>)>,
+    /// When the client finished sending messages.
+    request_end: Option<u64>,
+    end: Option<CallEnd>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CallEnd {
+    /// The server finished the call with a status.
+    Server { time: u64, status: Option<i32> },
+    /// The call was dropped before the server finished it.
+    Cancel { time: u64 },
+}
+
+impl CallPlan {
+    /// When the call ended, or `trace_end` if it was still running when the recording stopped.
+    fn end_time(&self, trace_end: u64) -> u64 {
+        match self.end {
+            Some(CallEnd::Server { time, .. } | CallEnd::Cancel { time }) => time,
+            None => trace_end,
+        }
+    }
+}
+
+/// Groups trace entries by call.
+///
+/// Entries for calls whose start wasn't recorded are ignored.
+fn plan(entries: impl IntoIterator<Item = TraceEntry>) -> Vec<CallPlan> {
+    let mut calls = BTreeMap::<u64, CallPlan>::new();
+
+    for entry in entries {
+        if entry.kind == TraceEventKind::Call {
+            calls.insert(
+                entry.call,
+                CallPlan {
+                    id: entry.call,
+                    method: entry.method.unwrap_or_default(),
+                    start: entry.time_ms,
+                    requests: Vec::new(),
+                    request_end: None,
+                    end: None,
+                },
+            );
+            continue;
+        }
+
+        let Some(call) = calls.get_mut(&entry.call) else {
+            continue;
+        };
+
+        match entry.kind {
+            TraceEventKind::Request => {
+                if let Some(message) = entry.message.as_deref().and_then(decode_hex) {
+                    call.requests.push((entry.time_ms, message));
+                }
+            }
+            TraceEventKind::RequestEnd => call.request_end = Some(entry.time_ms),
+            TraceEventKind::End => {
+                call.end = Some(CallEnd::Server {
+                    time: entry.time_ms,
+                    status: entry.status,
+                })
+            }
+            TraceEventKind::Cancel => {
+                call.end = Some(CallEnd::Cancel {
+                    time: entry.time_ms,
+                })
+            }
+            TraceEventKind::Call
+            | TraceEventKind::Response
+            | TraceEventKind::Signal
+            | TraceEventKind::State => (),
+        }
+    }
+
+    calls.into_values().collect()
+}
+
+/// A trace read from a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    /// The state the recording started from, if it was recorded.
+    pub start: Option<TraceStartState>,
+    calls: Vec<CallPlan>,
+}
+
+impl Trace {
+    /// Reads the trace file at `path`.
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        let entries = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str::<TraceEntry>(line)
+                    .with_context(|| format!("invalid trace entry on line {}", i + 1))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let start = entries
+            .iter()
+            .find_map(|entry| entry.state.as_deref().cloned());
+
+        Ok(Self {
+            start,
+            calls: plan(entries),
+        })
+    }
+}
+
+/// Runs `pinnacle debug replay`.
+pub async fn run(args: ReplayArgs) -> anyhow::Result<()> {
+    anyhow::ensure!(args.speed > 0.0, "speed must be greater than 0");
+
+    let trace = Trace::read(&args.file)?;
+
+    println!(
+        "Replaying {} calls from {}",
+        trace.calls.len(),
+        args.file.display()
+    );
+
+    let mismatches = match args.socket {
+        Some(socket) => {
+            if trace.start.is_some() {
+                println!(
+                    "The recorded outputs and tags aren't restored on a running instance, \
+                    so ids may not match"
+                );
+            }
+            replay_to_socket(&socket, trace, args.speed).await?
+        }
+        None => replay_on_dummy(trace, args.speed).await?,
+    };
+
+    println!("Replay finished with {mismatches} mismatched calls");
+
+    Ok(())
+}
+
+/// Replays a trace against the gRPC socket at `socket`.
+///
+/// Returns the number of calls that ended differently than they were recorded.
+pub async fn replay_to_socket(socket: &Path, trace: Trace, speed: f64) -> anyhow::Result<usize> {
+    let channel = super::msg::connect(socket)
+        .await
+        .with_context(|| format!("failed to connect to {}", socket.display()))?;
+    replay(channel, trace.calls, speed).await
+}
+
+/// Recreates the outputs and tags a trace started with, and makes tags and windows
+/// created afterwards get the ids they were recorded with.
+///
+/// Windows that were open when the recording started can't be recreated.
+pub fn restore_start_state(pinnacle: &mut Pinnacle, start: &TraceStartState) {
+    for recorded in start.dump.outputs.iter() {
+        // Disabled outputs
+        let Some(geometry) = recorded.geometry else {
+            continue;
+        };
+
+        let size = Size::<i32, Logical>::from((geometry.width, geometry.height))
+            .to_f64()
+            .to_physical(recorded.scale)
+            .to_i32_round();
+
+        let output = pinnacle.new_output(
+            &recorded.name,
+            "",
+            "",
+            (geometry.x, geometry.y).into(),
+            size,
+            60000,
+            recorded.scale,
+            Transform::Normal,
+        );
+
+        let tags = recorded
+            .tags
+            .iter()
+            .filter(|tag| !tag.defunct)
+            .map(|recorded| {
+                TagId::set_next(TagId::new(recorded.id));
+                let tag = Tag::new(recorded.name.clone());
+                tag.set_active(recorded.active);
+                tag
+            })
+            .collect::<Vec<_>>();
+
+        output.with_state_mut(|state| state.add_tags(tags));
+    }
+
+    let focused_output = start.dump.focus.focused_output.as_ref().and_then(|name| {
+        pinnacle
+            .outputs
+            .iter()
+            .find(|output| output.name() == *name)
+            .cloned()
+    });
+    if let Some(output) = focused_output {
+        pinnacle.focus_output(&output);
+    }
+
+    TagId::set_next(TagId::new(start.next_tag_id));
+    WindowId::set_next(WindowId(start.next_window_id));
+}
+
+/// Starts a dummy instance with no config, then replays against it.
+///
+/// The instance starts with the outputs and tags the trace was recorded with,
+/// or with a single 1920x1080 output if the trace doesn't have them.
+async fn replay_on_dummy(trace: Trace, speed: f64) -> anyhow::Result<usize> {
+    let mut event_loop = EventLoop::<State>::try_new()?;
+
+    let mut state = State::new(
+        super::Backend::Dummy,
+        event_loop.handle(),
+        event_loop.get_signal(),
+        PathBuf::from(""),
+        Some(Cli {
+            no_config: true,
+            ..Default::default()
+        }),
+        false,
+    )?;
+
+    let socket_dir = std::env::temp_dir().join(format!("pinnacle-replay-{}", std::process::id()));
+    state.pinnacle.start_grpc_server(&socket_dir)?;
+    let socket = std::env::var_os(GRPC_SOCKET_ENV)
+        .map(PathBuf::from)
+        .context("gRPC server didn't set its socket")?;
+
+    match trace.start.as_ref() {
+        Some(start) => {
+            restore_start_state(&mut state.pinnacle, start);
+
+            let windows = start.dump.windows.len() + start.dump.unmapped_windows.len();
+            if windows > 0 {
+                println!(
+                    "{windows} windows were open when the recording started; \
+                    calls that refer to them will fail"
+                );
+            }
+        }
+        None => {
+            state.pinnacle.new_output(
+                "replay-1",
+                "",
+                "",
+                (0, 0).into(),
+                (1920, 1080).into(),
+                60000,
+                1.0,
+                Transform::Normal,
+            );
+        }
+    }
+
+    let replay_task = tokio::spawn(async move { replay_to_socket(&socket, trace, speed).await });
+
+    while !replay_task.is_finished() {
+        event_loop.dispatch(Duration::from_millis(5), &mut state)?;
+        state.on_event_loop_cycle_completion();
+    }
+
+    let _ = std::fs::remove_dir_all(&socket_dir);
+
+    replay_task.await?
+}
+
+/// Replays calls and reports the ones that ended differently than they were recorded.
+///
+/// Returns the number of calls that did.
+async fn replay(channel: Channel, calls: Vec<CallPlan>, speed: f64) -> anyhow::Result<usize> {
+    let trace_end = calls
+        .iter()
+        .flat_map(|call| {
+            [call.start, call.end_time(0)]
+                .into_iter()
+                .chain(call.request_end)
+                .chain(call.requests.iter().map(|(time, _)| *time))
+        })
+        .max()
+        .unwrap_or_default();
+
+    let start = Instant::now();
+
+    let tasks = calls
+        .into_iter()
+        .map(|call| {
+            let channel = channel.clone();
+            tokio::spawn(async move {
+                let result = replay_call(channel, &call, start, speed, trace_end).await;
+                (call, result)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut mismatches = 0;
+
+    for task in tasks {
+        let (call, result) = task.await?;
+
+        let Some(CallEnd::Server { status, .. }) = call.end else {
+            continue;
+        };
+
+        let recorded = Code::from_i32(status.unwrap_or_default());
+        let replayed = match &result {
+            Ok(()) => Code::Ok,
+            Err(status) => status.code(),
+        };
+
+        if recorded != replayed {
+            mismatches += 1;
+            let message = result.err().map(|status| status.message().to_string());
+            println!(
+                "call {} {}: recorded {recorded:?}, replayed {replayed:?}{}",
+                call.id,
+                call.method,
+                message.map(|msg| format!(": {msg}")).unwrap_or_default()
+            );
+        }
+    }
+
+    Ok(mismatches)
+}
+
+/// Returns the instant `ms` milliseconds into the replay.
+fn scaled(start: Instant, ms: u64, speed: f64) -> Instant {
+    start + Duration::from_secs_f64(ms as f64 / 1000.0 / speed)
+}
+
+async fn replay_call(
+    channel: Channel,
+    call: &CallPlan,
+    start: Instant,
+    speed: f64,
+    trace_end: u64,
+) -> Result<(), Status> {
+    tokio::time::sleep_until(scaled(start, call.start, speed)).await;
+
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel::<Vec<u8>>();
+
+    let end_time = call.end_time(trace_end);
+
+    let requests = call.requests.clone();
+    // Keep streams open until the client originally closed them
+    let request_end = call.request_end.unwrap_or(end_time);
+
+    tokio::spawn(async move {
+        for (time, message) in requests {
+            tokio::time::sleep_until(scaled(start, time, speed)).await;
+            if sender.send(message).is_err() {
+                return;
+            }
+        }
+        tokio::time::sleep_until(scaled(start, request_end, speed)).await;
+    });
+
+    let path = PathAndQuery::try_from(call.method.as_str())
+        .map_err(|err| Status::invalid_argument(format!("invalid method path: {err}")))?;
+
+    let mut grpc = tonic::client::Grpc::new(channel);
+
+    let drain = async move {
+        grpc.ready()
+            .await
+            .map_err(|err| Status::unknown(format!("service was not ready: {err}")))?;
+
+        let mut responses = grpc
+            .streaming(
+                Request::new(UnboundedReceiverStream::new(receiver)),
+                path,
+                RawCodec,
+            )
+            .await?
+            .into_inner();
+
+        while responses.message().await?.is_some() {}
+
+        Ok(())
+    };
+
+    match call.end {
+        Some(CallEnd::Server { .. }) => drain.await,
+        _ => {
+            tokio::select! {
+                result = drain => result,
+                _ = tokio::time::sleep_until(scaled(start, end_time, speed)) => Ok(()),
+            }
+        }
+    }
+}
+
+/// A codec that passes already encoded messages through.
+#[derive(Debug, Clone, Copy, Default)]
+struct RawCodec;
+
+impl Codec for RawCodec {
+    type Encode = Vec<u8>;
+    type Decode = Vec<u8>;
+    type Encoder = RawCodec;
+    type Decoder = RawCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        *self
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        *self
+    }
+}
+
+impl Encoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put_slice(&item);
+        Ok(())
+    }
+}
+
+impl Decoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        let mut message = vec![0; src.remaining()];
+        src.copy_to_slice(&mut message);
+        Ok(Some(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(time_ms: u64, call: u64, kind: TraceEventKind) -> TraceEntry {
+        TraceEntry {
+            time_ms,
+            call,
+            kind,
+            method: None,
+            pid: None,
+            message: None,
+            status: None,
+            state: None,
+        }
+    }
+
+    #[test]
+    fn plan_groups_entries_by_call() {
+        let entries = [
+            TraceEntry {
+                method: Some("/pinnacle.v1.PinnacleService/Keepalive".into()),
+                ..entry(0, 0, TraceEventKind::Call)
+            },
+            TraceEntry {
+                method: Some("/pinnacle.tag.v1.TagService/SwitchTo".into()),
+                ..entry(5, 1, TraceEventKind::Call)
+            },
+            TraceEntry {
+                message: Some("0801".into()),
+                ..entry(6, 1, TraceEventKind::Request)
+            },
+            entry(6, 1, TraceEventKind::RequestEnd),
+            entry(7, 1, TraceEventKind::Response),
+            TraceEntry {
+                status: Some(0),
+                ..entry(7, 1, TraceEventKind::End)
+            },
+            // Started before the recording
+            entry(8, 9, TraceEventKind::Request),
+            entry(10, 0, TraceEventKind::Cancel),
+        ];
+
+        let calls = plan(entries);
+
+        assert_eq!(
+            calls,
+            vec![
+                CallPlan {
+                    id: 0,
+                    method: "/pinnacle.v1.PinnacleService/Keepalive".into(),
+                    start: 0,
+                    requests: Vec::new(),
+                    request_end: None,
+                    end: Some(CallEnd::Cancel { time: 10 }),
+                },
+                CallPlan {
+                    id: 1,
+                    method: "/pinnacle.tag.v1.TagService/SwitchTo".into(),
+                    start: 5,
+                    requests: vec![(6, vec![0x08, 0x01])],
+                    request_end: Some(6),
+                    end: Some(CallEnd::Server {
+                        time: 7,
+                        status: Some(0)
+                    }),
+                },
+            ]
+        );
+    }
+}
@@ -1,8 +1,18 @@
 use crate::{
     api::{
-        debug::DebugService, input::InputService, layout::LayoutService, output::OutputService,
-        pinnacle::PinnacleService, process::ProcessService, render::RenderService,
-        signal::SignalService, tag::TagService, window::WindowService,
+        auth::{ApiAuthLayer, ApiAuthState, ApiPermissions},
+        connection::{self, ApiClientId},
+        debug::DebugService,
+        input::InputService,
+        layout::LayoutService,
+        output::OutputService,
+        pinnacle::PinnacleService,
+        process::ProcessService,
+        render::RenderService,
+        signal::SignalService,
+        tag::TagService,
+        trace::{TraceLayer, TraceRecorder},
+        window::WindowService,
     },
     cli::Cli,
     output::OutputName,
@@ -15,6 +25,7 @@ use std::{
     io::{self, Write},
     path::{Path, PathBuf},
     process::Stdio,
+    sync::{Arc, RwLock},
 };
 
 use anyhow::Context;
@@ -45,10 +56,16 @@ use tracing::{Instrument, debug, debug_span, error, info, warn};
 use xdg::BaseDirectories;
 
 use crate::{
+    privilege::PrivilegePolicy,
     state::{State, WithState},
     tag::TagId,
 };
 
+use self::static_config::StaticConfig;
+
+pub mod static_config;
+pub mod watch;
+
 const DEFAULT_SOCKET_DIR: &str = "/tmp";
 pub const GRPC_SOCKET_ENV: &str = "PINNACLE_GRPC_SOCKET";
 
@@ -131,11 +148,28 @@ pub fn generate_config(dir: &Path, lang: Lang) -> Result<(), io::Error> {
 /// the target socket directory.
 #[derive(serde::Deserialize, Debug, PartialEq, Default)]
 pub struct StartupConfig {
+    #[serde(default)]
     pub run: Vec<String>,
     pub envs: Option<Table>,
     pub socket_dir: Option<PathBuf>,
     pub no_config: Option<bool>,
     pub no_xwayland: Option<bool>,
+    pub auto_reload: Option<bool>,
+    #[serde(rename = "static")]
+    pub static_config: Option<StaticConfig>,
+    pub privileges: Option<PrivilegePolicy>,
+    pub api: Option<ApiPermissions>,
+    pub snowcap_renderer: Option<SnowcapRenderer>,
+}
+
+/// The renderer Snowcap uses for its surfaces.
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SnowcapRenderer {
+    /// Use wgpu, falling back to tiny-skia if it fails to initialize.
+    Wgpu,
+    /// Always use tiny-skia.
+    TinySkia,
 }
 
 /// A startup config with fields resolved.
@@ -152,6 +186,12 @@ pub struct ResolvedStartupConfig {
     pub socket_dir: PathBuf,
     pub no_config: bool,
     pub no_xwayland: bool,
+    pub auto_reload: bool,
+    pub static_config: StaticConfig,
+    pub privileges: PrivilegePolicy,
+    pub api: ApiPermissions,
+    /// If `None`, Snowcap picks a renderer from its environment.
+    pub snowcap_renderer: Option<SnowcapRenderer>,
 }
 
 impl StartupConfig {
@@ -195,6 +235,11 @@ impl StartupConfig {
                 .and_then(|cli| cli.no_xwayland.then_some(true))
                 .or(self.no_xwayland)
                 .unwrap_or_default(),
+            auto_reload: self.auto_reload.unwrap_or_default(),
+            static_config: self.static_config.unwrap_or_default(),
+            privileges: self.privileges.unwrap_or_default(),
+            api: self.api.unwrap_or_default(),
+            snowcap_renderer: self.snowcap_renderer,
         })
     }
 }
@@ -208,10 +253,29 @@ impl ResolvedStartupConfig {
             socket_dir: PathBuf::from(""),
             no_config,
             no_xwayland,
+            auto_reload: false,
+            static_config: Default::default(),
+            privileges: Default::default(),
+            api: Default::default(),
+            snowcap_renderer: None,
         }
     }
 }
 
+impl ResolvedStartupConfig {
+    /// Returns the string envs with shell expansions applied.
+    pub fn expanded_envs(&self) -> anyhow::Result<Vec<(String, String)>> {
+        self.envs
+            .iter()
+            .filter_map(|(key, val)| match val {
+                toml::Value::String(string) => Some((key, string)),
+                _ => None,
+            })
+            .map(|(key, val)| Ok((key.clone(), shellexpand::full(val)?.to_string())))
+            .collect()
+    }
+}
+
 /// The current state of configuration.
 #[derive(Debug)]
 pub struct Config {
@@ -222,6 +286,8 @@ pub struct Config {
     pub(crate) config_reload_on_crash_token: Option<RegistrationToken>,
 
     pub keepalive_sender: Option<tokio::sync::oneshot::Sender<()>>,
+    /// The client holding the keepalive stream, i.e. the running config.
+    pub(crate) keepalive_owner: Option<ApiClientId>,
 
     pub config_dir: PathBuf,
     pub cli: Option<Cli>,
@@ -232,6 +298,23 @@ pub struct Config {
     pub last_error: Option<String>,
 
     pub process_envs: HashMap<String, String>,
+
+    /// Watches the config dir for changes when `auto_reload` is set.
+    /// This persists across config restarts.
+    pub(crate) watcher: Option<watch::ConfigWatcher>,
+
+    /// The `[static]` section of `pinnacle.toml`.
+    pub static_config: StaticConfig,
+    pub(crate) static_bind_token: Option<RegistrationToken>,
+    /// Whether applying the static config is queued. This persists across config restarts
+    /// so falling back to the builtin config doesn't apply it twice.
+    pub(crate) static_config_apply_queued: bool,
+
+    /// Permissions and the config's pid, shared with the gRPC server.
+    pub api_auth: Arc<RwLock<ApiAuthState>>,
+
+    /// Records gRPC traffic when enabled, shared with the gRPC server.
+    pub trace_recorder: TraceRecorder,
 }
 
 #[derive(Debug, Default)]
@@ -257,12 +340,19 @@ impl Config {
             config_join_handle: None,
             config_reload_on_crash_token: None,
             keepalive_sender: None,
+            keepalive_owner: None,
             config_dir,
             cli,
             socket_path: None,
             debug: Default::default(),
             last_error: None,
             process_envs: Default::default(),
+            watcher: None,
+            static_config: Default::default(),
+            static_bind_token: None,
+            static_config_apply_queued: false,
+            api_auth: Default::default(),
+            trace_recorder: Default::default(),
         }
     }
 
@@ -279,6 +369,11 @@ impl Config {
         if let Some(token) = self.config_reload_on_crash_token.take() {
             loop_handle.remove(token);
         }
+        if let Some(token) = self.static_bind_token.take() {
+            loop_handle.remove(token);
+        }
+
+        std::mem::take(&mut self.static_config);
 
         std::mem::take(&mut self.debug);
 
@@ -342,13 +437,25 @@ impl Pinnacle {
 
         TagId::reset();
 
-        debug!("Clearing input state");
+        // Binds, signals, window rules, and the layout manager belong to the client that
+        // created them and are cleaned up when it disconnects. The old config may not
+        // disconnect (the builtin config shares our process), so clean up after it here.
+        // Static binds have no owner.
+        debug!("Clearing config-owned state");
 
-        self.input_state.clear();
+        if let Some(owner) = self.config.keepalive_owner.take() {
+            self.remove_api_client(owner);
+        }
+        self.input_state.bind_state.remove_owned_by(None);
 
-        self.config.clear(&self.loop_handle);
+        // The new config never saw these requests, so ask it again
+        self.privilege_state.clear_pending_capture_decisions();
 
-        self.signal_state.clear();
+        // The builtin config has no static config of its own, so it keeps
+        // the one from the config it replaces
+        let previous_static_config = std::mem::take(&mut self.config.static_config);
+
+        self.config.clear(&self.loop_handle);
 
         #[cfg(feature = "snowcap")]
         {
@@ -394,6 +501,37 @@ impl Pinnacle {
         let startup_config =
             startup_config.merge_and_resolve(self.config.cli.as_ref(), &self.config.config_dir)?;
 
+        if !builtin {
+            self.set_config_auto_reload(startup_config.auto_reload);
+
+            self.privilege_state
+                .set_policy(startup_config.privileges.clone());
+
+            self.config
+                .api_auth
+                .write()
+                .expect("api auth lock poisoned")
+                .permissions = startup_config.api.clone();
+
+            self.config.static_config = startup_config.static_config.clone();
+        } else {
+            self.config.static_config = previous_static_config;
+        }
+
+        if !self.config.static_config_apply_queued {
+            self.config.static_config_apply_queued = true;
+            self.loop_handle.insert_idle(|state| {
+                state.pinnacle.config.static_config_apply_queued = false;
+                state.apply_static_config();
+            });
+        }
+
+        self.config
+            .api_auth
+            .write()
+            .expect("api auth lock poisoned")
+            .config_pid = None;
+
         if startup_config.no_config {
             info!("`no-config` option was set, not spawning config");
             return Ok(());
@@ -422,6 +560,10 @@ impl Pinnacle {
 
             let arg0 = match command_iter.next() {
                 Some(arg0) => arg0,
+                None if self.config.static_config != StaticConfig::default() => {
+                    info!("No config command specified, using only the static config");
+                    return Ok(());
+                }
                 None => return load_default_config(self, "no command specified"),
             };
 
@@ -429,20 +571,7 @@ impl Pinnacle {
 
             debug!(arg0, ?command_rest);
 
-            let envs = startup_config
-                .envs
-                .clone()
-                .into_iter()
-                .map(|(key, val)| -> anyhow::Result<Option<(String, String)>> {
-                    if let toml::Value::String(string) = val {
-                        Ok(Some((key, shellexpand::full(&string)?.to_string())))
-                    } else {
-                        Ok(None)
-                    }
-                })
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten();
+            let envs = startup_config.expanded_envs()?;
 
             debug!("Config envs are {envs:?}");
 
@@ -506,6 +635,12 @@ impl Pinnacle {
 
             info!("Started config with {:?}", command);
 
+            self.config
+                .api_auth
+                .write()
+                .expect("api auth lock poisoned")
+                .config_pid = child.id();
+
             let (pinger, ping_source) = calloop::ping::make_ping()?;
 
             let token = self
@@ -567,7 +702,7 @@ impl Pinnacle {
             .build_v1()?;
 
         let uds = tokio::net::UnixListener::bind(&socket_path)?;
-        let uds_stream = tokio_stream::wrappers::UnixListenerStream::new(uds);
+        let incoming = connection::incoming(uds, grpc_sender);
 
         // SAFETY: All set_vars occur on the event loop thread
         unsafe {
@@ -575,6 +710,8 @@ impl Pinnacle {
         }
 
         let grpc_server = tonic::transport::Server::builder()
+            .layer(ApiAuthLayer::new(self.config.api_auth.clone()))
+            .layer(TraceLayer::new(self.config.trace_recorder.clone()))
             .add_service(refl_service)
             .add_service(PinnacleServiceServer::new(pinnacle_service))
             .add_service(WindowServiceServer::new(window_service))
@@ -588,7 +725,7 @@ impl Pinnacle {
             .add_service(DebugServiceServer::new(debug_service));
 
         self.grpc_server_join_handle = Some(tokio::spawn(async move {
-            if let Err(err) = grpc_server.serve_with_incoming(uds_stream).await {
+            if let Err(err) = grpc_server.serve_with_incoming(incoming).await {
                 error!("gRPC server error: {err}");
             }
         }));
@@ -695,6 +832,8 @@ mod tests {
 
             no_config = true
             no_xwayland = true
+            auto_reload = true
+            snowcap_renderer = "tiny-skia"
 
             [envs]
             MARCO = "polo"
@@ -716,6 +855,11 @@ mod tests {
             socket_dir: Some("/path/to/socket/dir".into()),
             no_config: Some(true),
             no_xwayland: Some(true),
+            auto_reload: Some(true),
+            static_config: None,
+            privileges: None,
+            api: None,
+            snowcap_renderer: Some(SnowcapRenderer::TinySkia),
         };
 
         assert_eq!(
@@ -744,6 +888,11 @@ mod tests {
             socket_dir: None,
             no_config: None,
             no_xwayland: None,
+            auto_reload: None,
+            static_config: None,
+            privileges: None,
+            api: None,
+            snowcap_renderer: None,
         };
 
         assert_eq!(
@@ -768,5 +917,169 @@ mod tests {
         Ok(())
     }
 
-    // TODO: test for error if `run` isn't present
+    #[test]
+    fn static_config_successfully_parses() -> anyhow::Result<()> {
+        use static_config::*;
+
+        let startup_config_text = r#"
+            [static]
+            tags = ["1", "2"]
+            repeat_rate = 30
+
+            [[static.binds]]
+            mods = ["super", "shift"]
+            key = "q"
+            action = "quit"
+
+            [[static.binds]]
+            mods = ["super"]
+            key = "Return"
+            action = { spawn = ["alacritty"] }
+
+            [[static.outputs]]
+            name = "DP-1"
+            scale = 1.5
+            transform = "90"
+            loc = { x = 1920, y = 0 }
+
+            [[static.window_rules]]
+            app_id = "mpv"
+            floating = true
+        "#;
+
+        let config_dir = tempfile::tempdir()?;
+        std::fs::write(
+            config_dir.path().join(STARTUP_CONFIG_TOML_NAME),
+            startup_config_text,
+        )?;
+
+        let expected_static_config = StaticConfig {
+            binds: vec![
+                StaticBind {
+                    mods: vec![StaticModifier::Super, StaticModifier::Shift],
+                    key: "q".to_string(),
+                    action: StaticAction::Quit,
+                    group: String::new(),
+                    description: String::new(),
+                },
+                StaticBind {
+                    mods: vec![StaticModifier::Super],
+                    key: "Return".to_string(),
+                    action: StaticAction::Spawn(vec!["alacritty".to_string()]),
+                    group: String::new(),
+                    description: String::new(),
+                },
+            ],
+            tags: vec!["1".to_string(), "2".to_string()],
+            outputs: vec![StaticOutput {
+                name: "DP-1".to_string(),
+                tags: None,
+                mode: None,
+                scale: Some(1.5),
+                transform: Some(StaticTransform::Transform90),
+                loc: Some(StaticLoc { x: 1920, y: 0 }),
+            }],
+            repeat_rate: Some(30),
+            window_rules: vec![StaticWindowRule {
+                app_id: Some("mpv".to_string()),
+                floating: Some(true),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let startup_config = parse_startup_config(config_dir.path())?;
+
+        assert!(startup_config.run.is_empty());
+        assert_eq!(startup_config.static_config, Some(expected_static_config));
+
+        Ok(())
+    }
+
+    #[test]
+    fn privileges_successfully_parses() -> anyhow::Result<()> {
+        use crate::privilege::PrivilegedProtocol;
+
+        let startup_config_text = r#"
+            run = ["lua", "init.lua"]
+
+            [privileges]
+            restrict_sandboxed = false
+            allowlist = ["/usr/bin/grim", "/usr/bin/wl-copy"]
+            protected = ["screen_capture", "data_control", "layer_shell"]
+            prompt_screen_capture = true
+        "#;
+
+        let config_dir = tempfile::tempdir()?;
+        std::fs::write(
+            config_dir.path().join(STARTUP_CONFIG_TOML_NAME),
+            startup_config_text,
+        )?;
+
+        let expected_privileges = PrivilegePolicy {
+            restrict_sandboxed: false,
+            allowlist: Some(vec!["/usr/bin/grim".into(), "/usr/bin/wl-copy".into()]),
+            protected: vec![
+                PrivilegedProtocol::ScreenCapture,
+                PrivilegedProtocol::DataControl,
+                PrivilegedProtocol::LayerShell,
+            ],
+            prompt_screen_capture: true,
+        };
+
+        let startup_config = parse_startup_config(config_dir.path())?;
+
+        assert_eq!(startup_config.privileges, Some(expected_privileges));
+
+        Ok(())
+    }
+
+    #[test]
+    fn api_permissions_successfully_parses() -> anyhow::Result<()> {
+        use crate::api::auth::{ApiAccess, ApiClientRule, ApiService};
+
+        let startup_config_text = r#"
+            run = ["lua", "init.lua"]
+
+            [api]
+            default_access = "none"
+
+            [[api.clients]]
+            executable = "/usr/bin/pinnacle"
+            access = "full"
+
+            [[api.clients]]
+            executable = "/usr/local/bin/bar"
+            access = "read_only"
+            services = ["tag", "signal"]
+        "#;
+
+        let config_dir = tempfile::tempdir()?;
+        std::fs::write(
+            config_dir.path().join(STARTUP_CONFIG_TOML_NAME),
+            startup_config_text,
+        )?;
+
+        let expected_api = ApiPermissions {
+            default_access: ApiAccess::None,
+            clients: vec![
+                ApiClientRule {
+                    executable: "/usr/bin/pinnacle".into(),
+                    access: ApiAccess::Full,
+                    services: None,
+                },
+                ApiClientRule {
+                    executable: "/usr/local/bin/bar".into(),
+                    access: ApiAccess::ReadOnly,
+                    services: Some(vec![ApiService::Tag, ApiService::Signal]),
+                },
+            ],
+        };
+
+        let startup_config = parse_startup_config(config_dir.path())?;
+
+        assert_eq!(startup_config.api, Some(expected_api));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,630 @@
+//! Declarative configuration from the `[static]` section of `pinnacle.toml`.
+//!
+//! Everything here is applied directly by the compositor, so a minimal setup works without
+//! a config process. It is applied before the config process starts, so a programmatic
+//! config can add to or override anything set here.
+
+use indexmap::IndexSet;
+use serde::Deserialize;
+use smithay::{
+    input::keyboard::XkbConfig,
+    output::{Output, Scale},
+    reexports::{
+        calloop::{self, channel::Event},
+        input as libinput,
+        wayland_protocols::xdg::decoration::zv1::server::zxdg_toplevel_decoration_v1,
+    },
+    utils::Transform,
+};
+use tracing::{error, info, warn};
+use xkbcommon::xkb::{self, Keysym};
+
+use crate::{
+    api::{tag, window},
+    focus::keyboard::KeyboardFocusTarget,
+    input::{
+        bind::{Edge, ModMask},
+        keyboard,
+    },
+    output::{OutputMode, OutputName},
+    process::{ChildCommand, PipeProcesses},
+    state::{Pinnacle, State, WithState},
+    tag::Tag,
+    window::{Unmapped, UnmappedState, WindowElement, window_state::LayoutMode},
+};
+
+/// The `[static]` section of `pinnacle.toml`.
+#[derive(Deserialize, Debug, PartialEq, Default, Clone)]
+#[serde(default)]
+pub struct StaticConfig {
+    /// Keybinds to built-in actions.
+    pub binds: Vec<StaticBind>,
+    /// Tags added to outputs that don't specify their own.
+    pub tags: Vec<String>,
+    /// Per-output settings, matched by connector name.
+    pub outputs: Vec<StaticOutput>,
+    pub xkb: Option<StaticXkb>,
+    pub repeat_rate: Option<i32>,
+    pub repeat_delay: Option<i32>,
+    /// Libinput settings applied to all devices.
+    pub libinput: Option<StaticLibinput>,
+    pub window_rules: Vec<StaticWindowRule>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct StaticBind {
+    #[serde(default)]
+    pub mods: Vec<StaticModifier>,
+    /// An xkb keysym name or a single character.
+    pub key: String,
+    pub action: StaticAction,
+    #[serde(default)]
+    pub group: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum StaticModifier {
+    Shift,
+    Ctrl,
+    Alt,
+    Super,
+    IsoLevel3Shift,
+    IsoLevel5Shift,
+}
+
+/// A built-in action a static bind can trigger.
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum StaticAction {
+    Quit,
+    ReloadConfig,
+    Close,
+    ToggleFloating,
+    ToggleFullscreen,
+    ToggleMaximized,
+    Spawn(Vec<String>),
+    SwitchToTag(String),
+    ToggleTag(String),
+    MoveToTag(String),
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub struct StaticOutput {
+    /// The connector name, e.g. `DP-1`.
+    pub name: String,
+    pub tags: Option<Vec<String>>,
+    pub mode: Option<StaticMode>,
+    pub scale: Option<f64>,
+    pub transform: Option<StaticTransform>,
+    pub loc: Option<StaticLoc>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
+pub struct StaticMode {
+    pub width: u32,
+    pub height: u32,
+    pub refresh_rate_mhz: Option<u32>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
+pub struct StaticLoc {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
+pub enum StaticTransform {
+    #[serde(rename = "normal")]
+    Normal,
+    #[serde(rename = "90")]
+    Transform90,
+    #[serde(rename = "180")]
+    Transform180,
+    #[serde(rename = "270")]
+    Transform270,
+    #[serde(rename = "flipped")]
+    Flipped,
+    #[serde(rename = "flipped_90")]
+    Flipped90,
+    #[serde(rename = "flipped_180")]
+    Flipped180,
+    #[serde(rename = "flipped_270")]
+    Flipped270,
+}
+
+impl From<StaticTransform> for Transform {
+    fn from(value: StaticTransform) -> Self {
+        match value {
+            StaticTransform::Normal => Transform::Normal,
+            StaticTransform::Transform90 => Transform::_90,
+            StaticTransform::Transform180 => Transform::_180,
+            StaticTransform::Transform270 => Transform::_270,
+            StaticTransform::Flipped => Transform::Flipped,
+            StaticTransform::Flipped90 => Transform::Flipped90,
+            StaticTransform::Flipped180 => Transform::Flipped180,
+            StaticTransform::Flipped270 => Transform::Flipped270,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default, Clone)]
+#[serde(default)]
+pub struct StaticXkb {
+    pub rules: String,
+    pub model: String,
+    pub layout: String,
+    pub variant: String,
+    pub options: Option<String>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum StaticAccelProfile {
+    Flat,
+    Adaptive,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default, Clone)]
+#[serde(default)]
+pub struct StaticLibinput {
+    pub accel_profile: Option<StaticAccelProfile>,
+    pub accel_speed: Option<f64>,
+    pub natural_scroll: Option<bool>,
+    pub tap: Option<bool>,
+    pub tap_drag: Option<bool>,
+    pub disable_while_typing: Option<bool>,
+    pub left_handed: Option<bool>,
+    pub middle_emulation: Option<bool>,
+}
+
+impl StaticLibinput {
+    /// Applies these settings to `device`. Unsupported settings are ignored.
+    pub fn apply(&self, device: &mut libinput::Device) {
+        if let Some(profile) = self.accel_profile {
+            let _ = device.config_accel_set_profile(match profile {
+                StaticAccelProfile::Flat => libinput::AccelProfile::Flat,
+                StaticAccelProfile::Adaptive => libinput::AccelProfile::Adaptive,
+            });
+        }
+        if let Some(speed) = self.accel_speed {
+            let _ = device.config_accel_set_speed(speed);
+        }
+        if let Some(natural_scroll) = self.natural_scroll {
+            let _ = device.config_scroll_set_natural_scroll_enabled(natural_scroll);
+        }
+        if let Some(tap) = self.tap {
+            let _ = device.config_tap_set_enabled(tap);
+        }
+        if let Some(tap_drag) = self.tap_drag {
+            let _ = device.config_tap_set_drag_enabled(tap_drag);
+        }
+        if let Some(dwt) = self.disable_while_typing {
+            let _ = device.config_dwt_set_enabled(dwt);
+        }
+        if let Some(left_handed) = self.left_handed {
+            let _ = device.config_left_handed_set(left_handed);
+        }
+        if let Some(middle_emulation) = self.middle_emulation {
+            let _ = device.config_middle_emulation_set_enabled(middle_emulation);
+        }
+    }
+}
+
+/// A window rule. All specified matchers must match exactly for the rule to apply.
+#[derive(Deserialize, Debug, PartialEq, Default, Clone)]
+#[serde(default)]
+pub struct StaticWindowRule {
+    pub app_id: Option<String>,
+    pub title: Option<String>,
+
+    pub floating: Option<bool>,
+    pub fullscreen: Option<bool>,
+    pub maximized: Option<bool>,
+    /// Whether the window should use server-side decorations.
+    pub ssd: Option<bool>,
+    /// The name of the output to open the window on.
+    pub output: Option<String>,
+    /// Names of tags to open the window on.
+    pub tags: Option<Vec<String>>,
+}
+
+impl StaticWindowRule {
+    fn matches(&self, window: &WindowElement) -> bool {
+        let app_id_matches = self
+            .app_id
+            .as_ref()
+            .is_none_or(|app_id| window.class().as_ref() == Some(app_id));
+        let title_matches = self
+            .title
+            .as_ref()
+            .is_none_or(|title| window.title().as_ref() == Some(title));
+
+        app_id_matches && title_matches
+    }
+}
+
+fn keysym_from_str(key: &str) -> Option<Keysym> {
+    let mut chars = key.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Some(Keysym::from_char(ch));
+    }
+
+    let keysym = xkb::keysym_from_name(key, xkb::KEYSYM_NO_FLAGS);
+    (keysym != Keysym::NoSymbol).then_some(keysym)
+}
+
+fn find_tag(output: &Output, name: &str) -> Option<Tag> {
+    output.with_state(|state| {
+        state
+            .tags
+            .iter()
+            .find(|tag| !tag.defunct() && tag.name() == name)
+            .cloned()
+    })
+}
+
+impl State {
+    /// Applies the static config to input and all current outputs.
+    pub fn apply_static_config(&mut self) {
+        let static_config = self.pinnacle.config.static_config.clone();
+
+        if let Some(xkb) = static_config.xkb.as_ref() {
+            self.apply_static_xkb(xkb);
+        }
+
+        if static_config.repeat_rate.is_some() || static_config.repeat_delay.is_some() {
+            let keyboard_config_state = &mut self.pinnacle.input_state.keyboard_config_state;
+            let (rate, delay) = keyboard_config_state.default_repeat_info;
+            let rate = static_config.repeat_rate.unwrap_or(rate);
+            let delay = static_config.repeat_delay.unwrap_or(delay);
+            keyboard_config_state.default_repeat_info = (rate, delay);
+            if let Some(kb) = self.pinnacle.seat.get_keyboard() {
+                kb.change_repeat_info(rate, delay);
+            }
+        }
+
+        if let Some(libinput) = static_config.libinput.as_ref() {
+            for device in self.pinnacle.input_state.libinput_state.devices.keys() {
+                libinput.apply(&mut device.clone());
+            }
+        }
+
+        self.add_static_binds(&static_config);
+
+        for output in self.pinnacle.outputs.clone() {
+            self.apply_static_output_config(&output);
+        }
+    }
+
+    fn apply_static_xkb(&mut self, xkb: &StaticXkb) {
+        let xkb_config = XkbConfig {
+            rules: &xkb.rules,
+            model: &xkb.model,
+            layout: &xkb.layout,
+            variant: &xkb.variant,
+            options: xkb.options.clone(),
+        };
+
+        let Some(keymap) = keyboard::compile_keymap(&xkb_config) else {
+            error!("Failed to apply static xkb config: invalid config");
+            return;
+        };
+
+        let keyboard_config_state = &mut self.pinnacle.input_state.keyboard_config_state;
+        keyboard_config_state.default_keymap = Some(keymap.clone());
+        keyboard_config_state.reset();
+
+        if let Some(kb) = self.pinnacle.seat.get_keyboard()
+            && let Err(err) = kb.set_keymap_from_string(self, keymap)
+        {
+            error!("Failed to apply static xkb config: {err}");
+        }
+        self.reset_xkb_layout_changed();
+        self.signal_xkb_layout_changed();
+    }
+
+    fn add_static_binds(&mut self, static_config: &StaticConfig) {
+        if static_config.binds.is_empty() {
+            return;
+        }
+
+        let (action_sender, action_receiver) = calloop::channel::channel::<StaticAction>();
+
+        match self
+            .pinnacle
+            .loop_handle
+            .insert_source(action_receiver, |event, _, state| {
+                if let Event::Msg(action) = event {
+                    state.run_static_action(action);
+                }
+            }) {
+            Ok(token) => self.pinnacle.config.static_bind_token = Some(token),
+            Err(err) => {
+                error!("Failed to add static binds: {err}");
+                return;
+            }
+        }
+
+        for bind in static_config.binds.iter() {
+            let Some(keysym) = keysym_from_str(&bind.key) else {
+                warn!("Static bind has unknown key `{}`", bind.key);
+                continue;
+            };
+
+            let mut mods = ModMask::new();
+            for modifier in bind.mods.iter() {
+                match modifier {
+                    StaticModifier::Shift => mods.shift = Some(true),
+                    StaticModifier::Ctrl => mods.ctrl = Some(true),
+                    StaticModifier::Alt => mods.alt = Some(true),
+                    StaticModifier::Super => mods.super_ = Some(true),
+                    StaticModifier::IsoLevel3Shift => mods.iso_level3_shift = Some(true),
+                    StaticModifier::IsoLevel5Shift => mods.iso_level5_shift = Some(true),
+                }
+            }
+
+            let keybinds = &mut self.pinnacle.input_state.bind_state.keybinds;
+
+            let bind_id = keybinds.add_keybind(
+                keysym,
+                mods,
+                None,
+                bind.group.clone(),
+                bind.description.clone(),
+                bind.action == StaticAction::Quit,
+                bind.action == StaticAction::ReloadConfig,
+                false,
+                None,
+            );
+
+            if let StaticAction::Quit | StaticAction::ReloadConfig = bind.action {
+                continue;
+            }
+
+            keybinds.set_keybind_has_on_press(bind_id);
+
+            let Some(mut recv) = keybinds
+                .id_map
+                .get(&bind_id)
+                .and_then(|keybind| keybind.borrow_mut().recv.take())
+            else {
+                continue;
+            };
+
+            let action = bind.action.clone();
+            let action_sender = action_sender.clone();
+
+            tokio::spawn(async move {
+                while let Some(edge) = recv.recv().await {
+                    if edge == Edge::Press && action_sender.send(action.clone()).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+
+    /// Applies static output settings and tags to a newly connected `output`.
+    pub fn apply_static_output_config(&mut self, output: &Output) {
+        let static_config = &self.pinnacle.config.static_config;
+
+        let static_output = static_config
+            .outputs
+            .iter()
+            .find(|static_output| static_output.name == output.name())
+            .cloned();
+
+        let tags = static_output
+            .as_ref()
+            .and_then(|static_output| static_output.tags.clone())
+            .unwrap_or_else(|| static_config.tags.clone());
+
+        if let Some(static_output) = static_output {
+            let mode = static_output.mode.and_then(|mode| {
+                let picked = crate::output::try_pick_mode(
+                    output,
+                    mode.width,
+                    mode.height,
+                    mode.refresh_rate_mhz,
+                );
+                if picked.is_none() {
+                    warn!(
+                        "Output {} has no mode {}x{}",
+                        output.name(),
+                        mode.width,
+                        mode.height
+                    );
+                }
+                picked
+            });
+
+            self.pinnacle.change_output_state(
+                &mut self.backend,
+                output,
+                mode.map(OutputMode::Smithay),
+                static_output.transform.map(Transform::from),
+                static_output
+                    .scale
+                    .map(|scale| Scale::Fractional(f64::max(scale, 0.25))),
+                static_output.loc.map(|loc| (loc.x, loc.y).into()),
+            );
+
+            self.pinnacle.request_layout(output);
+            self.schedule_render(output);
+            self.pinnacle
+                .output_management_manager_state
+                .update::<State>();
+        }
+
+        if !tags.is_empty() {
+            let tags = tag::add(self, tags, OutputName(output.name()));
+            if let Some(first) = tags.first() {
+                tag::set_active(self, first, Some(true));
+            }
+        }
+    }
+
+    fn run_static_action(&mut self, action: StaticAction) {
+        let focused_window = self
+            .pinnacle
+            .seat
+            .get_keyboard()
+            .and_then(|kb| kb.current_focus())
+            .and_then(|focus| match focus {
+                KeyboardFocusTarget::Window(window) => Some(window),
+                _ => None,
+            });
+
+        let focused_output_tag = |state: &State, name: &str| {
+            state
+                .pinnacle
+                .focused_output()
+                .and_then(|output| find_tag(output, name))
+        };
+
+        match action {
+            // These are handled by the input code
+            StaticAction::Quit | StaticAction::ReloadConfig => (),
+            StaticAction::Close => {
+                if let Some(window) = focused_window {
+                    window.close();
+                }
+            }
+            StaticAction::ToggleFloating => {
+                if let Some(window) = focused_window {
+                    self.pinnacle
+                        .update_window_layout_mode(&window, LayoutMode::toggle_floating);
+                }
+            }
+            StaticAction::ToggleFullscreen => {
+                if let Some(window) = focused_window {
+                    self.pinnacle
+                        .update_window_layout_mode(&window, LayoutMode::toggle_fullscreen);
+                }
+            }
+            StaticAction::ToggleMaximized => {
+                if let Some(window) = focused_window {
+                    self.pinnacle
+                        .update_window_layout_mode(&window, LayoutMode::toggle_maximized);
+                }
+            }
+            StaticAction::Spawn(cmd) => {
+                info!("Spawning {cmd:?} from static bind");
+                let command = ChildCommand {
+                    cmd,
+                    shell_cmd: Vec::new(),
+                    envs: self.pinnacle.config.process_envs.clone(),
+                    cwd: None,
+                    systemd_scope: false,
+                };
+                self.pinnacle.process_state.spawn(
+                    command,
+                    false,
+                    false,
+                    &self.pinnacle.xdg_base_dirs,
+                    PipeProcesses {
+                        stdin: false,
+                        stdout: false,
+                        stderr: false,
+                    },
+                    None,
+                    None,
+                );
+            }
+            StaticAction::SwitchToTag(name) => {
+                if let Some(tag) = focused_output_tag(self, &name) {
+                    tag::switch_to(self, &tag);
+                }
+            }
+            StaticAction::ToggleTag(name) => {
+                if let Some(tag) = focused_output_tag(self, &name) {
+                    tag::set_active(self, &tag, None);
+                }
+            }
+            StaticAction::MoveToTag(name) => {
+                if let (Some(window), Some(tag)) = (focused_window, focused_output_tag(self, &name))
+                {
+                    window::move_to_tag(self, &window, &tag);
+                }
+            }
+        }
+    }
+}
+
+impl Pinnacle {
+    /// Applies static settings to a newly added libinput device.
+    pub fn apply_static_libinput_config(&self, device: &libinput::Device) {
+        if let Some(libinput) = self.config.static_config.libinput.as_ref() {
+            libinput.apply(&mut device.clone());
+        }
+    }
+
+    /// Applies matching static window rules to an unmapped window waiting for rules.
+    pub fn apply_static_window_rules(&self, unmapped: &mut Unmapped) {
+        let UnmappedState::WaitingForRules { rules, .. } = &mut unmapped.state else {
+            return;
+        };
+
+        for rule in self.config.static_config.window_rules.iter() {
+            if !rule.matches(&unmapped.window) {
+                continue;
+            }
+
+            let output = rule
+                .output
+                .as_ref()
+                .and_then(|name| OutputName(name.clone()).output(self));
+
+            if let Some(output) = output.as_ref() {
+                unmapped.window.set_tags_to_output(output);
+            }
+
+            if let Some(tag_names) = rule.tags.as_ref() {
+                let output = output
+                    .or_else(|| unmapped.window.output(self))
+                    .or_else(|| self.focused_output().cloned());
+
+                let tags = output
+                    .map(|output| {
+                        tag_names
+                            .iter()
+                            .filter_map(|name| find_tag(&output, name))
+                            .collect::<IndexSet<_>>()
+                    })
+                    .unwrap_or_default();
+
+                if !tags.is_empty() {
+                    rules.tags = Some(tags);
+                }
+            }
+
+            if let Some(floating) = rule.floating {
+                rules
+                    .layout_mode
+                    .get_or_insert(LayoutMode::new_tiled())
+                    .set_floating(floating);
+            }
+            if let Some(maximized) = rule.maximized {
+                rules
+                    .layout_mode
+                    .get_or_insert(LayoutMode::new_tiled())
+                    .set_maximized(maximized);
+            }
+            if let Some(fullscreen) = rule.fullscreen {
+                rules
+                    .layout_mode
+                    .get_or_insert(LayoutMode::new_tiled())
+                    .set_fullscreen(fullscreen);
+            }
+            if let Some(ssd) = rule.ssd {
+                rules.decoration_mode = Some(match ssd {
+                    true => zxdg_toplevel_decoration_v1::Mode::ServerSide,
+                    false => zxdg_toplevel_decoration_v1::Mode::ClientSide,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,584 @@
+//! Automatic config reloading.
+//!
+//! When `auto_reload` is set in `pinnacle.toml`, the config directory is watched with inotify.
+//! Changes to config sources restart the config after a short debounce. Rust configs are
+//! rebuilt with `cargo build` first so that a compile error doesn't crash into the builtin
+//! config. Build and reload failures are stored as the config's last error instead.
+
+use std::{
+    collections::HashMap,
+    mem::MaybeUninit,
+    os::{fd::OwnedFd, unix::ffi::OsStrExt},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use smithay::reexports::{
+    calloop::{
+        self, Interest, LoopHandle, Mode, PostAction, RegistrationToken,
+        channel::Event,
+        generic::Generic,
+        timer::{TimeoutAction, Timer},
+    },
+    rustix::{
+        fs::inotify::{self, CreateFlags, ReadFlags, WatchFlags},
+        io::Errno,
+    },
+};
+use tracing::{debug, error, info, warn};
+
+use crate::state::{Pinnacle, State};
+
+use super::{STARTUP_CONFIG_TOML_NAME, parse_startup_config};
+
+/// How long to wait after the last change before reloading.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// File extensions that trigger a reload when changed.
+const WATCHED_EXTENSIONS: &[&str] = &["lua", "rs", "toml"];
+
+/// Directories that are never watched. Dot-directories are skipped as well.
+const IGNORED_DIRS: &[&str] = &["target"];
+
+const WATCH_FLAGS: WatchFlags = WatchFlags::CLOSE_WRITE
+    .union(WatchFlags::CREATE)
+    .union(WatchFlags::DELETE)
+    .union(WatchFlags::MOVED_FROM)
+    .union(WatchFlags::MOVED_TO)
+    .union(WatchFlags::ONLYDIR);
+
+/// The result of a `cargo build`. On failure, this holds the build output.
+type BuildResult = Result<(), String>;
+
+/// A watcher on the config directory that reloads the config on changes.
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    inotify_token: RegistrationToken,
+    build_token: RegistrationToken,
+    build_sender: calloop::channel::Sender<BuildResult>,
+    debounce_token: Option<RegistrationToken>,
+    building: bool,
+    reload_pending: bool,
+}
+
+impl ConfigWatcher {
+    /// Starts watching `config_dir` recursively.
+    pub fn new(
+        config_dir: &Path,
+        loop_handle: &LoopHandle<'static, State>,
+    ) -> anyhow::Result<Self> {
+        let inotify_fd = inotify::init(CreateFlags::CLOEXEC | CreateFlags::NONBLOCK)?;
+
+        let mut watches = HashMap::new();
+        add_watches_recursive(&inotify_fd, config_dir, &mut watches);
+
+        let inotify_token = loop_handle
+            .insert_source(
+                Generic::new(inotify_fd, Interest::READ, Mode::Level),
+                move |_, inotify_fd, state| {
+                    if read_events(inotify_fd, &mut watches) {
+                        state.pinnacle.schedule_config_reload();
+                    }
+                    Ok(PostAction::Continue)
+                },
+            )
+            .map_err(|err| err.error)?;
+
+        let (build_sender, build_receiver) = calloop::channel::channel::<BuildResult>();
+
+        let build_token = loop_handle
+            .insert_source(build_receiver, |event, _, state| {
+                if let Event::Msg(result) = event {
+                    // Reloading may stop the watcher, which removes this source
+                    state.pinnacle.loop_handle.insert_idle(move |state| {
+                        state.pinnacle.on_config_build_finished(result);
+                    });
+                }
+            })
+            .map_err(|err| err.error)?;
+
+        info!("Watching {} for config changes", config_dir.display());
+
+        Ok(Self {
+            inotify_token,
+            build_token,
+            build_sender,
+            debounce_token: None,
+            building: false,
+            reload_pending: false,
+        })
+    }
+
+    /// Stops watching and removes all event sources.
+    pub fn stop(mut self, loop_handle: &LoopHandle<'static, State>) {
+        loop_handle.remove(self.inotify_token);
+        loop_handle.remove(self.build_token);
+        if let Some(token) = self.debounce_token.take() {
+            loop_handle.remove(token);
+        }
+    }
+}
+
+fn add_watches_recursive(inotify_fd: &OwnedFd, dir: &Path, watches: &mut HashMap<i32, PathBuf>) {
+    match inotify::add_watch(inotify_fd, dir, WATCH_FLAGS) {
+        Ok(wd) => {
+            watches.insert(wd, dir.to_path_buf());
+        }
+        Err(err) => {
+            warn!("Failed to watch {}: {err}", dir.display());
+            return;
+        }
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        if entry.file_type().is_ok_and(|ty| ty.is_dir()) && !is_ignored_dir(&entry.file_name()) {
+            add_watches_recursive(inotify_fd, &entry.path(), watches);
+        }
+    }
+}
+
+fn is_ignored_dir(name: &std::ffi::OsStr) -> bool {
+    name.as_bytes().starts_with(b".") || IGNORED_DIRS.iter().any(|dir| name == *dir)
+}
+
+fn is_watched_file(name: &std::ffi::OsStr) -> bool {
+    Path::new(name)
+        .extension()
+        .is_some_and(|ext| WATCHED_EXTENSIONS.iter().any(|watched| ext == *watched))
+}
+
+/// Drains pending inotify events, returning whether any of them should trigger a reload.
+fn read_events(inotify_fd: &OwnedFd, watches: &mut HashMap<i32, PathBuf>) -> bool {
+    let mut buf = [MaybeUninit::uninit(); 4096];
+    let mut reader = inotify::Reader::new(inotify_fd, &mut buf);
+
+    let mut changed = false;
+
+    loop {
+        let event = match reader.next() {
+            Ok(event) => event,
+            Err(Errno::AGAIN) => break,
+            Err(Errno::INTR) => continue,
+            Err(err) => {
+                warn!("Failed to read config watcher events: {err}");
+                break;
+            }
+        };
+
+        let flags = event.events();
+
+        if flags.contains(ReadFlags::QUEUE_OVERFLOW) {
+            changed = true;
+            continue;
+        }
+
+        if flags.contains(ReadFlags::IGNORED) {
+            watches.remove(&event.wd());
+            continue;
+        }
+
+        let Some(name) = event.file_name() else {
+            continue;
+        };
+        let name = std::ffi::OsStr::from_bytes(name.to_bytes());
+
+        if flags.contains(ReadFlags::ISDIR) {
+            if is_ignored_dir(name) {
+                continue;
+            }
+            if flags.intersects(ReadFlags::CREATE | ReadFlags::MOVED_TO)
+                && let Some(parent) = watches.get(&event.wd()).cloned()
+            {
+                add_watches_recursive(inotify_fd, &parent.join(name), watches);
+            }
+            changed = true;
+        } else if is_watched_file(name) {
+            debug!(?name, ?flags, "Config file changed");
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Returns the arguments for `cargo build` if `run` is a `cargo run` invocation.
+///
+/// Arguments before `--` are forwarded so that profiles and binary selection match.
+fn cargo_build_args(run: &[String]) -> Option<Vec<String>> {
+    let (program, args) = run.split_first()?;
+    if program != "cargo" || args.first().map(String::as_str) != Some("run") {
+        return None;
+    }
+
+    let forwarded = args[1..].iter().take_while(|arg| *arg != "--").cloned();
+
+    Some(
+        [
+            "build".to_string(),
+            "--color".to_string(),
+            "never".to_string(),
+        ]
+        .into_iter()
+        .chain(forwarded)
+        .collect(),
+    )
+}
+
+impl Pinnacle {
+    /// Starts or stops the config watcher depending on `auto_reload`.
+    pub(super) fn set_config_auto_reload(&mut self, auto_reload: bool) {
+        match (auto_reload, self.config.watcher.is_some()) {
+            (true, false) => match ConfigWatcher::new(&self.config.config_dir, &self.loop_handle) {
+                Ok(watcher) => self.config.watcher = Some(watcher),
+                Err(err) => error!("Failed to start config watcher: {err}"),
+            },
+            (false, true) => {
+                if let Some(watcher) = self.config.watcher.take() {
+                    watcher.stop(&self.loop_handle);
+                    info!("Stopped watching config for changes");
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// (Re)arms the debounce timer for a config reload.
+    fn schedule_config_reload(&mut self) {
+        let Some(watcher) = self.config.watcher.as_mut() else {
+            return;
+        };
+
+        if let Some(token) = watcher.debounce_token.take() {
+            self.loop_handle.remove(token);
+        }
+
+        let token =
+            self.loop_handle
+                .insert_source(Timer::from_duration(DEBOUNCE), |_, _, state| {
+                    if let Some(watcher) = state.pinnacle.config.watcher.as_mut() {
+                        watcher.debounce_token.take();
+                    }
+                    state.pinnacle.loop_handle.insert_idle(|state| {
+                        state.pinnacle.reload_config_on_change();
+                    });
+                    TimeoutAction::Drop
+                });
+
+        match token {
+            Ok(token) => watcher.debounce_token = Some(token),
+            Err(err) => error!("Failed to schedule config reload: {err}"),
+        }
+    }
+
+    fn reload_config_on_change(&mut self) {
+        let Some(watcher) = self.config.watcher.as_mut() else {
+            return;
+        };
+
+        if watcher.building {
+            watcher.reload_pending = true;
+            return;
+        }
+
+        let build_sender = watcher.build_sender.clone();
+
+        let startup_config = parse_startup_config(&self.config.config_dir).and_then(|config| {
+            config.merge_and_resolve(self.config.cli.as_ref(), &self.config.config_dir)
+        });
+
+        let (startup_config, build_args) = match startup_config {
+            Ok(config) => {
+                let build_args = cargo_build_args(&config.run)
+                    .filter(|_| self.config.config_dir.join("Cargo.toml").exists());
+                (config, build_args)
+            }
+            // Let `start_config` report the error
+            Err(_) => {
+                self.reload_config_after_change();
+                return;
+            }
+        };
+
+        let Some(build_args) = build_args else {
+            self.reload_config_after_change();
+            return;
+        };
+
+        if let Some(watcher) = self.config.watcher.as_mut() {
+            watcher.building = true;
+        }
+
+        info!(
+            "Config changed, rebuilding with `cargo {}`",
+            build_args.join(" ")
+        );
+
+        let config_dir = self.config.config_dir.clone();
+        let envs = startup_config.expanded_envs().unwrap_or_default();
+
+        tokio::spawn(async move {
+            let output = tokio::process::Command::new("cargo")
+                .args(build_args)
+                .envs(envs)
+                .current_dir(config_dir)
+                .output()
+                .await;
+
+            let result = match output {
+                Ok(output) if output.status.success() => Ok(()),
+                Ok(output) => Err(String::from_utf8_lossy(&output.stderr).into_owned()),
+                Err(err) => Err(format!("Failed to run `cargo build`: {err}")),
+            };
+
+            let _ = build_sender.send(result);
+        });
+    }
+
+    fn on_config_build_finished(&mut self, result: BuildResult) {
+        let Some(watcher) = self.config.watcher.as_mut() else {
+            return;
+        };
+
+        watcher.building = false;
+
+        if std::mem::take(&mut watcher.reload_pending) {
+            debug!("Config changed during rebuild, rebuilding again");
+            self.reload_config_on_change();
+            return;
+        }
+
+        match result {
+            Ok(()) => {
+                self.config.last_error = None;
+                self.reload_config_after_change();
+            }
+            Err(output) => {
+                error!("Config failed to compile:\n{output}");
+
+                // Keep the running config so a typo doesn't take the user's binds with it
+                self.config.last_error = Some(format!("Config failed to compile:\n{output}"));
+            }
+        }
+    }
+
+    fn reload_config_after_change(&mut self) {
+        info!(
+            "Config changed, reloading {}",
+            self.config
+                .config_dir
+                .join(STARTUP_CONFIG_TOML_NAME)
+                .display()
+        );
+
+        if let Err(err) = self.start_config(false) {
+            error!("Failed to reload config: {err}");
+            self.config.last_error = Some(format!("Failed to reload config: {err}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn cargo_run_is_rebuilt_with_forwarded_args() {
+        assert_eq!(
+            cargo_build_args(&args(&["cargo", "run", "--release", "--", "--flag"])),
+            Some(args(&["build", "--color", "never", "--release"]))
+        );
+        assert_eq!(
+            cargo_build_args(&args(&["cargo", "run"])),
+            Some(args(&["build", "--color", "never"]))
+        );
+    }
+
+    #[test]
+    fn non_cargo_run_is_not_rebuilt() {
+        assert_eq!(
+            cargo_build_args(&args(&["lua", "default_config.lua"])),
+            None
+        );
+        assert_eq!(cargo_build_args(&args(&["cargo", "test"])), None);
+        assert_eq!(cargo_build_args(&[]), None);
+    }
+
+    fn watch(dir: &Path) -> (OwnedFd, HashMap<i32, PathBuf>) {
+        let inotify_fd = inotify::init(CreateFlags::CLOEXEC | CreateFlags::NONBLOCK).unwrap();
+        let mut watches = HashMap::new();
+        add_watches_recursive(&inotify_fd, dir, &mut watches);
+        (inotify_fd, watches)
+    }
+
+    #[test]
+    fn only_config_sources_trigger_reloads() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("target")).unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+
+        let (inotify_fd, mut watches) = watch(dir.path());
+        assert_eq!(watches.len(), 1);
+
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+        std::fs::write(dir.path().join("target/main.rs"), "").unwrap();
+        std::fs::write(dir.path().join(".git/config.toml"), "").unwrap();
+        assert!(!read_events(&inotify_fd, &mut watches));
+
+        std::fs::write(dir.path().join("default_config.lua"), "").unwrap();
+        assert!(read_events(&inotify_fd, &mut watches));
+        assert!(!read_events(&inotify_fd, &mut watches));
+    }
+
+    #[test]
+    fn new_directories_are_watched() {
+        let dir = tempfile::tempdir().unwrap();
+        let (inotify_fd, mut watches) = watch(dir.path());
+
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        assert!(read_events(&inotify_fd, &mut watches));
+        assert_eq!(watches.len(), 2);
+
+        std::fs::write(dir.path().join("src/main.rs"), "").unwrap();
+        assert!(read_events(&inotify_fd, &mut watches));
+
+        std::fs::create_dir(dir.path().join("target")).unwrap();
+        assert!(!read_events(&inotify_fd, &mut watches));
+        assert_eq!(watches.len(), 2);
+    }
+
+    #[cfg(feature = "testing")]
+    mod reload {
+        use std::time::Instant;
+
+        use smithay::reexports::calloop::EventLoop;
+        use tempfile::TempDir;
+
+        use crate::cli::{Backend, Cli};
+
+        use super::super::*;
+
+        struct Instance {
+            event_loop: EventLoop<'static, State>,
+            state: State,
+            config_dir: TempDir,
+        }
+
+        impl Instance {
+            /// Starts a dummy instance watching an empty config that never spawns anything.
+            fn new() -> Self {
+                let config_dir = tempfile::tempdir().unwrap();
+                std::fs::write(
+                    config_dir.path().join(STARTUP_CONFIG_TOML_NAME),
+                    "auto_reload = true\n",
+                )
+                .unwrap();
+
+                let event_loop = EventLoop::try_new().unwrap();
+                let mut state = State::new(
+                    Backend::Dummy,
+                    event_loop.handle(),
+                    event_loop.get_signal(),
+                    config_dir.path().to_path_buf(),
+                    Some(Cli {
+                        no_config: true,
+                        ..Default::default()
+                    }),
+                    false,
+                )
+                .unwrap();
+
+                state.pinnacle.set_config_auto_reload(true);
+
+                Self {
+                    event_loop,
+                    state,
+                    config_dir,
+                }
+            }
+
+            fn dispatch_for(&mut self, duration: Duration) {
+                let end = Instant::now() + duration;
+                while Instant::now() < end {
+                    self.event_loop
+                        .dispatch(Duration::from_millis(5), &mut self.state)
+                        .unwrap();
+                }
+            }
+
+            fn watcher(&mut self) -> &mut ConfigWatcher {
+                self.state.pinnacle.config.watcher.as_mut().unwrap()
+            }
+        }
+
+        #[test]
+        fn changes_schedule_a_reload() {
+            let mut instance = Instance::new();
+            assert!(instance.watcher().debounce_token.is_none());
+
+            std::fs::write(instance.config_dir.path().join("default_config.lua"), "").unwrap();
+            instance.dispatch_for(DEBOUNCE / 4);
+
+            assert!(instance.watcher().debounce_token.is_some());
+        }
+
+        #[test]
+        fn changes_within_the_debounce_are_coalesced() {
+            let mut instance = Instance::new();
+
+            instance.state.pinnacle.schedule_config_reload();
+            instance.dispatch_for(DEBOUNCE / 2);
+            instance.state.pinnacle.schedule_config_reload();
+            instance.dispatch_for(DEBOUNCE / 2 + DEBOUNCE / 4);
+
+            // The first change would have reloaded by now
+            assert!(instance.watcher().debounce_token.is_some());
+
+            instance.dispatch_for(DEBOUNCE);
+            assert!(instance.watcher().debounce_token.is_none());
+        }
+
+        #[test]
+        fn changes_during_a_build_rebuild_once_it_finishes() {
+            let mut instance = Instance::new();
+            instance.watcher().building = true;
+
+            instance.state.pinnacle.reload_config_on_change();
+            instance.state.pinnacle.reload_config_on_change();
+            assert!(instance.watcher().reload_pending);
+
+            // The finished build is out of date, so its error isn't reported
+            instance
+                .state
+                .pinnacle
+                .on_config_build_finished(Err("stale".to_string()));
+
+            let watcher = instance.watcher();
+            assert!(!watcher.building);
+            assert!(!watcher.reload_pending);
+            assert_eq!(instance.state.pinnacle.config.last_error, None);
+        }
+
+        #[test]
+        fn failed_builds_are_stored_as_the_last_error() {
+            let mut instance = Instance::new();
+            instance.watcher().building = true;
+
+            instance
+                .state
+                .pinnacle
+                .on_config_build_finished(Err("error[E0425]".to_string()));
+
+            let last_error = instance.state.pinnacle.config.last_error.clone();
+            assert!(last_error.is_some_and(|error| error.contains("error[E0425]")));
+            assert!(instance.state.pinnacle.config.watcher.is_some());
+        }
+    }
+}
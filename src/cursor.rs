@@ -1,12 +1,14 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{collections::HashMap, rc::Rc};
 
 use anyhow::Context;
 use smithay::backend::allocator::Fourcc;
 use smithay::desktop::utils::bbox_from_surface_tree;
 use smithay::input::pointer::CursorImageSurfaceData;
+use smithay::reexports::calloop::RegistrationToken;
+use smithay::reexports::calloop::timer::{TimeoutAction, Timer};
 use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
 use smithay::utils::{Buffer, IsAlive, Logical, Monotonic, Point, Rectangle, Time};
 use smithay::wayland::compositor::{self, SurfaceAttributes};
@@ -15,12 +17,14 @@ use smithay::{
     input::pointer::{CursorIcon, CursorImageStatus},
     utils::Transform,
 };
+use tracing::warn;
 use xcursor::{
     CursorTheme,
     parser::{Image, parse_xcursor},
 };
 
 use crate::render::pointer::PointerElement;
+use crate::state::State;
 
 static FALLBACK_CURSOR_DATA: &[u8] = include_bytes!("../resources/cursor.rgba");
 
@@ -32,6 +36,31 @@ pub struct CursorState {
     /// A map of cursor icons to loaded images
     loaded_images: HashMap<CursorIcon, Option<Rc<XCursor>>>,
     dnd_icon: Option<DndIcon>,
+    hide: CursorHide,
+}
+
+/// Auto-hide state for the cursor.
+#[derive(Debug)]
+struct CursorHide {
+    /// Hide the cursor when a key is pressed while a window has keyboard focus.
+    when_typing: bool,
+    /// Hide the cursor after this long without pointer motion.
+    timeout: Option<Duration>,
+    hidden: bool,
+    last_motion: Instant,
+    timer: Option<RegistrationToken>,
+}
+
+impl Default for CursorHide {
+    fn default() -> Self {
+        Self {
+            when_typing: false,
+            timeout: None,
+            hidden: false,
+            last_motion: Instant::now(),
+            timer: None,
+        }
+    }
 }
 
 pub struct DndIcon {
@@ -56,6 +85,7 @@ impl CursorState {
             mem_buffer_cache: Default::default(),
             loaded_images: Default::default(),
             dnd_icon: Default::default(),
+            hide: Default::default(),
         }
     }
 
@@ -93,6 +123,23 @@ impl CursorState {
         &self.current_cursor_image
     }
 
+    /// Returns whether the cursor is currently auto-hidden.
+    pub fn is_hidden(&self) -> bool {
+        self.hide.hidden
+    }
+
+    pub fn hide_when_typing(&self) -> bool {
+        self.hide.when_typing
+    }
+
+    pub fn set_hide_when_typing(&mut self, hide_when_typing: bool) {
+        self.hide.when_typing = hide_when_typing;
+    }
+
+    pub fn hide_timeout(&self) -> Option<Duration> {
+        self.hide.timeout
+    }
+
     pub fn get_xcursor_images(&mut self, icon: CursorIcon) -> Option<Rc<XCursor>> {
         let _span = tracy_client::span!("CursorState::get_xcursor_images");
 
@@ -136,6 +183,10 @@ impl CursorState {
     pub fn pointer_element(&mut self) -> PointerElement {
         let _span = tracy_client::span!("CursorState::pointer_element");
 
+        if self.hide.hidden {
+            return PointerElement::Hidden;
+        }
+
         match &self.current_cursor_image {
             CursorImageStatus::Hidden => PointerElement::Hidden,
             CursorImageStatus::Named(icon) => {
@@ -242,6 +293,10 @@ impl CursorState {
     pub fn is_current_cursor_animated(&mut self) -> bool {
         let _span = tracy_client::span!("CursorState::is_current_cursor_animated");
 
+        if self.hide.hidden {
+            return false;
+        }
+
         match &self.current_cursor_image {
             CursorImageStatus::Hidden => false,
             CursorImageStatus::Named(icon) => {
@@ -347,6 +402,110 @@ impl CursorState {
     }
 }
 
+impl State {
+    /// Hides the cursor until the pointer next moves.
+    ///
+    /// Pointer focus is not updated while the cursor is hidden.
+    pub fn hide_cursor(&mut self) {
+        if self.pinnacle.cursor_state.hide.hidden {
+            return;
+        }
+
+        self.pinnacle.cursor_state.hide.hidden = true;
+        self.schedule_render_for_cursor();
+    }
+
+    /// Hides the cursor if it should be hidden when typing.
+    pub fn hide_cursor_for_typing(&mut self) {
+        if self.pinnacle.cursor_state.hide.when_typing {
+            self.hide_cursor();
+        }
+    }
+
+    /// Shows the cursor if it was hidden and restarts the inactivity timer.
+    ///
+    /// This should be called on every pointer, touch, and tablet tool motion.
+    pub fn notify_cursor_motion(&mut self) {
+        let hide = &mut self.pinnacle.cursor_state.hide;
+        hide.last_motion = Instant::now();
+
+        if hide.hidden {
+            hide.hidden = false;
+            self.schedule_render_for_cursor();
+        }
+
+        self.arm_cursor_hide_timer();
+    }
+
+    /// Sets how long the pointer must be still before the cursor is hidden.
+    ///
+    /// `None` disables hiding after inactivity.
+    pub fn set_cursor_hide_timeout(&mut self, timeout: Option<Duration>) {
+        let hide = &mut self.pinnacle.cursor_state.hide;
+        hide.timeout = timeout;
+        hide.last_motion = Instant::now();
+
+        if let Some(timer) = hide.timer.take() {
+            self.pinnacle.loop_handle.remove(timer);
+        }
+
+        self.arm_cursor_hide_timer();
+    }
+
+    /// Starts the inactivity timer if there is a timeout and it isn't already running.
+    ///
+    /// Rather than restarting the timer on every motion event, the timer
+    /// reschedules itself relative to the last motion when it fires.
+    fn arm_cursor_hide_timer(&mut self) {
+        let hide = &self.pinnacle.cursor_state.hide;
+        let Some(timeout) = hide.timeout else {
+            return;
+        };
+        if hide.timer.is_some() || hide.hidden {
+            return;
+        }
+
+        let timer = self.pinnacle.loop_handle.insert_source(
+            Timer::from_duration(timeout),
+            |_, _, state| {
+                let hide = &mut state.pinnacle.cursor_state.hide;
+                let Some(timeout) = hide.timeout else {
+                    hide.timer = None;
+                    return TimeoutAction::Drop;
+                };
+
+                let elapsed = hide.last_motion.elapsed();
+                if elapsed < timeout {
+                    return TimeoutAction::ToDuration(timeout - elapsed);
+                }
+
+                hide.timer = None;
+                state.hide_cursor();
+                TimeoutAction::Drop
+            },
+        );
+
+        match timer {
+            Ok(timer) => self.pinnacle.cursor_state.hide.timer = Some(timer),
+            Err(err) => warn!("Failed to insert cursor hide timer: {err}"),
+        }
+    }
+
+    fn schedule_render_for_cursor(&mut self) {
+        let output = self
+            .pinnacle
+            .pointer_contents
+            .output_under
+            .as_ref()
+            .and_then(|op| op.upgrade())
+            .or_else(|| self.pinnacle.focused_output().cloned());
+
+        if let Some(output) = output {
+            self.schedule_render(&output);
+        }
+    }
+}
+
 pub struct XCursor {
     images: Vec<Image>,
 }
@@ -0,0 +1,636 @@
+//! Snapshots of compositor state for bug reports and tests.
+//!
+//! [`Pinnacle::dump_state`] collects windows, tags, outputs, layout trees, focus stacks,
+//! and binds into a [`StateDump`] that serializes to JSON.
+//! Windows, tags, and layout nodes are referred to by their numeric ids.
+
+use std::collections::BTreeMap;
+
+use smithay::{
+    output::Output,
+    reexports::wayland_protocols::xdg::decoration::zv1::server::zxdg_toplevel_decoration_v1,
+    utils::{Logical, Point, Rectangle, Size},
+};
+
+use crate::{
+    api::connection::ApiClientId,
+    input::bind::{BindData, ModMask},
+    layout::tree::LayoutNodeRef,
+    state::{Pinnacle, WithState},
+    util::transaction::{Location, PendingTransaction},
+    window::{
+        UnmappedState, WindowElement, ZIndexElement,
+        rules::{ClientRequests, WindowRules},
+        window_state::{FullscreenOrMaximized, LayoutModeKind},
+    },
+};
+
+/// A snapshot of the compositor's state.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct StateDump {
+    pub outputs: Vec<OutputDump>,
+    /// Mapped windows, in the order they were created.
+    pub windows: Vec<WindowDump>,
+    /// Windows that haven't been mapped yet.
+    pub unmapped_windows: Vec<UnmappedWindowDump>,
+    /// Windows and unmapping snapshots, bottom to top.
+    pub z_index_stack: Vec<ZIndexDump>,
+    pub focus: FocusDump,
+    pub binds: BindsDump,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RectDump {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl From<Rectangle<i32, Logical>> for RectDump {
+    fn from(rect: Rectangle<i32, Logical>) -> Self {
+        Self {
+            x: rect.loc.x,
+            y: rect.loc.y,
+            width: rect.size.w,
+            height: rect.size.h,
+        }
+    }
+}
+
+impl From<Rectangle<f32, Logical>> for RectDump {
+    fn from(rect: Rectangle<f32, Logical>) -> Self {
+        Self {
+            x: rect.loc.x.round() as i32,
+            y: rect.loc.y.round() as i32,
+            width: rect.size.w.round() as i32,
+            height: rect.size.h.round() as i32,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeDump {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl From<Size<i32, Logical>> for SizeDump {
+    fn from(size: Size<i32, Logical>) -> Self {
+        Self {
+            width: size.w,
+            height: size.h,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointDump {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl From<Point<i32, Logical>> for PointDump {
+    fn from(point: Point<i32, Logical>) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct OutputDump {
+    pub name: String,
+    pub enabled: bool,
+    pub powered: bool,
+    pub geometry: Option<RectDump>,
+    pub scale: f64,
+    pub tags: Vec<TagDump>,
+    pub layout: OutputLayoutDump,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TagDump {
+    pub id: u32,
+    pub name: String,
+    pub active: bool,
+    /// Whether this tag is left over from a previous config and will be replaced.
+    pub defunct: bool,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct OutputLayoutDump {
+    pub current_tree_id: Option<u32>,
+    pub trees: Vec<LayoutTreeDump>,
+    pub pending_transactions: Vec<TransactionDump>,
+    /// The number of unmapping window snapshots waiting for the next layout.
+    pub pending_unmaps: usize,
+    /// Window geometries that will be applied along with the next layout.
+    pub pending_window_updates: Vec<Vec<WindowGeometryDump>>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct LayoutTreeDump {
+    pub id: u32,
+    pub root: LayoutNodeDump,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlexDirDump {
+    Row,
+    Column,
+}
+
+/// A node in a layout tree.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct LayoutNodeDump {
+    /// The id windows use to refer to the node they are tiled in.
+    pub id: u64,
+    pub label: Option<String>,
+    pub traversal_index: u32,
+    pub traversal_overrides: BTreeMap<u32, Vec<u32>>,
+    pub flex_dir: FlexDirDump,
+    pub size_proportion: f32,
+    /// The geometry computed in the last layout, relative to the output's usable area.
+    pub geometry: RectDump,
+    /// For leaves, the area a window is tiled in after gaps.
+    pub tile: Option<RectDump>,
+    pub children: Vec<LayoutNodeDump>,
+}
+
+impl From<LayoutNodeRef<'_>> for LayoutNodeDump {
+    fn from(node: LayoutNodeRef<'_>) -> Self {
+        let style = node.style().cloned().unwrap_or_default();
+
+        Self {
+            id: node.id().into(),
+            label: node.label().map(ToString::to_string),
+            traversal_index: node.traversal_index(),
+            traversal_overrides: node
+                .traversal_overrides()
+                .map(|(index, overrides)| (index, overrides.to_vec()))
+                .collect(),
+            flex_dir: match style.flex_direction {
+                taffy::FlexDirection::Row | taffy::FlexDirection::RowReverse => FlexDirDump::Row,
+                taffy::FlexDirection::Column | taffy::FlexDirection::ColumnReverse => {
+                    FlexDirDump::Column
+                }
+            },
+            size_proportion: style.flex_basis.value(),
+            geometry: node.geometry().into(),
+            tile: node.tile().map(RectDump::from),
+            children: node.children().map(LayoutNodeDump::from).collect(),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct TransactionDump {
+    pub completed: bool,
+    pub cancelled: bool,
+    pub swap: bool,
+    pub resize: bool,
+    /// Target locations of the windows in this transaction, sorted by window id.
+    pub targets: Vec<TransactionTargetDump>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct TransactionTargetDump {
+    pub window_id: u32,
+    pub location: LocationDump,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LocationDump {
+    MapTo { x: i32, y: i32 },
+    FloatingResize { initial_geometry: RectDump },
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct WindowGeometryDump {
+    pub window_id: u32,
+    pub geometry: RectDump,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct WindowDump {
+    pub id: u32,
+    pub app_id: Option<String>,
+    pub title: Option<String>,
+    pub pid: Option<u32>,
+    pub x11: bool,
+    pub output: Option<String>,
+    pub tags: Vec<u32>,
+    pub layout_mode: LayoutModeKind,
+    pub minimized: bool,
+    /// Whether the window is currently in the space, i.e. on an active tag.
+    pub mapped: bool,
+    pub geometry: Option<RectDump>,
+    pub floating_loc: Option<PointDump>,
+    pub floating_size: SizeDump,
+    /// The id of the layout node this window is tiled in.
+    pub layout_node: Option<u64>,
+    pub pending_transactions: usize,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct UnmappedWindowDump {
+    pub id: u32,
+    pub app_id: Option<String>,
+    pub title: Option<String>,
+    pub pid: Option<u32>,
+    pub tags: Vec<u32>,
+    #[serde(flatten)]
+    pub state: UnmappedStateDump,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum UnmappedStateDump {
+    WaitingForTags {
+        client_requests: ClientRequestsDump,
+    },
+    WaitingForRules {
+        rules: WindowRulesDump,
+        client_requests: ClientRequestsDump,
+    },
+    PostInitialConfigure {
+        attempt_float_on_map: bool,
+        focus: bool,
+    },
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ClientRequestsDump {
+    pub layout_mode: Option<LayoutModeKind>,
+    pub decoration_mode: Option<DecorationModeDump>,
+}
+
+/// Window rules that have been applied so far.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WindowRulesDump {
+    pub layout_mode: Option<LayoutModeKind>,
+    pub focused: Option<bool>,
+    pub floating_x: Option<i32>,
+    pub floating_y: Option<i32>,
+    pub floating_size: Option<SizeDump>,
+    pub decoration_mode: Option<DecorationModeDump>,
+    pub tags: Option<Vec<u32>>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DecorationModeDump {
+    ClientSide,
+    ServerSide,
+    Unknown,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ZIndexDump {
+    Window { id: u32 },
+    Unmapping,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FocusDump {
+    pub focused_window: Option<u32>,
+    /// Window ids, least to most recently focused.
+    pub keyboard_focus_stack: Vec<u32>,
+    pub focused_output: Option<String>,
+    /// Enabled outputs, least to most recently focused.
+    pub output_focus_stack: Vec<String>,
+    /// The namespace of the layer surface with on-demand keyboard focus.
+    pub layer_focus: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct BindsDump {
+    /// Bind layers from the bottom to the current one.
+    pub layer_stack: Vec<String>,
+    pub keybinds: Vec<KeybindDump>,
+    pub mousebinds: Vec<MousebindDump>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct KeybindDump {
+    pub key: String,
+    #[serde(flatten)]
+    pub data: BindDataDump,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct MousebindDump {
+    pub button: u32,
+    #[serde(flatten)]
+    pub data: BindDataDump,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct BindDataDump {
+    pub id: u32,
+    /// The client that created the bind, or `None` for static binds.
+    pub owner: Option<ApiClientId>,
+    pub mods: ModMask,
+    pub layer: Option<String>,
+    pub group: String,
+    pub description: String,
+    pub quit: bool,
+    pub reload_config: bool,
+    pub allow_when_locked: bool,
+}
+
+impl From<&BindData> for BindDataDump {
+    fn from(data: &BindData) -> Self {
+        Self {
+            id: data.id,
+            owner: data.owner,
+            mods: data.mods,
+            layer: data.layer.clone(),
+            group: data.group.clone(),
+            description: data.desc.clone(),
+            quit: data.is_quit_bind,
+            reload_config: data.is_reload_config_bind,
+            allow_when_locked: data.allow_when_locked,
+        }
+    }
+}
+
+fn window_id(window: &WindowElement) -> u32 {
+    window.with_state(|state| state.id.0)
+}
+
+fn tag_ids<'a>(tags: impl IntoIterator<Item = &'a crate::tag::Tag>) -> Vec<u32> {
+    tags.into_iter().map(|tag| tag.id().to_inner()).collect()
+}
+
+fn decoration_mode(mode: zxdg_toplevel_decoration_v1::Mode) -> DecorationModeDump {
+    match mode {
+        zxdg_toplevel_decoration_v1::Mode::ClientSide => DecorationModeDump::ClientSide,
+        zxdg_toplevel_decoration_v1::Mode::ServerSide => DecorationModeDump::ServerSide,
+        _ => DecorationModeDump::Unknown,
+    }
+}
+
+fn fullscreen_or_maximized(mode: FullscreenOrMaximized) -> LayoutModeKind {
+    match mode {
+        FullscreenOrMaximized::Fullscreen => LayoutModeKind::Fullscreen,
+        FullscreenOrMaximized::Maximized => LayoutModeKind::Maximized,
+    }
+}
+
+impl From<&ClientRequests> for ClientRequestsDump {
+    fn from(requests: &ClientRequests) -> Self {
+        Self {
+            layout_mode: requests.layout_mode.map(fullscreen_or_maximized),
+            decoration_mode: requests.decoration_mode.map(decoration_mode),
+        }
+    }
+}
+
+impl From<&WindowRules> for WindowRulesDump {
+    fn from(rules: &WindowRules) -> Self {
+        Self {
+            layout_mode: rules.layout_mode.map(|mode| mode.current()),
+            focused: rules.focused,
+            floating_x: rules.floating_x,
+            floating_y: rules.floating_y,
+            floating_size: rules.floating_size.map(SizeDump::from),
+            decoration_mode: rules.decoration_mode.map(decoration_mode),
+            tags: rules.tags.as_ref().map(tag_ids),
+        }
+    }
+}
+
+impl From<&PendingTransaction> for TransactionDump {
+    fn from(transaction: &PendingTransaction) -> Self {
+        let mut targets = transaction
+            .target_locs
+            .iter()
+            .map(|(window, loc)| TransactionTargetDump {
+                window_id: window_id(window),
+                location: match *loc {
+                    Location::MapTo(loc) => LocationDump::MapTo { x: loc.x, y: loc.y },
+                    Location::FloatingResize { initial_geo, .. } => LocationDump::FloatingResize {
+                        initial_geometry: initial_geo.into(),
+                    },
+                },
+            })
+            .collect::<Vec<_>>();
+        targets.sort_by_key(|target| target.window_id);
+
+        Self {
+            completed: transaction.is_completed(),
+            cancelled: transaction.is_cancelled(),
+            swap: transaction.is_swap,
+            resize: transaction.is_resize,
+            targets,
+        }
+    }
+}
+
+impl Pinnacle {
+    /// Takes a snapshot of the compositor's state.
+    pub fn dump_state(&self) -> StateDump {
+        let _span = tracy_client::span!("Pinnacle::dump_state");
+
+        StateDump {
+            outputs: self
+                .outputs
+                .iter()
+                .map(|output| self.dump_output(output))
+                .collect(),
+            windows: self
+                .windows
+                .iter()
+                .map(|window| self.dump_window(window))
+                .collect(),
+            unmapped_windows: self
+                .unmapped_windows
+                .iter()
+                .map(|unmapped| UnmappedWindowDump {
+                    id: window_id(&unmapped.window),
+                    app_id: unmapped.window.class(),
+                    title: unmapped.window.title(),
+                    pid: unmapped.window.pid(&self.display_handle),
+                    tags: unmapped.window.with_state(|state| tag_ids(&state.tags)),
+                    state: match &unmapped.state {
+                        UnmappedState::WaitingForTags { client_requests } => {
+                            UnmappedStateDump::WaitingForTags {
+                                client_requests: client_requests.into(),
+                            }
+                        }
+                        UnmappedState::WaitingForRules {
+                            rules,
+                            client_requests,
+                        } => UnmappedStateDump::WaitingForRules {
+                            rules: rules.into(),
+                            client_requests: client_requests.into(),
+                        },
+                        UnmappedState::PostInitialConfigure {
+                            attempt_float_on_map,
+                            focus,
+                        } => UnmappedStateDump::PostInitialConfigure {
+                            attempt_float_on_map: *attempt_float_on_map,
+                            focus: *focus,
+                        },
+                    },
+                })
+                .collect(),
+            z_index_stack: self
+                .z_index_stack
+                .iter()
+                .map(|element| match element {
+                    ZIndexElement::Window(window) => ZIndexDump::Window {
+                        id: window_id(window),
+                    },
+                    ZIndexElement::Unmapping(_) => ZIndexDump::Unmapping,
+                })
+                .collect(),
+            focus: FocusDump {
+                focused_window: self.keyboard_focus_stack.current_focus().map(window_id),
+                keyboard_focus_stack: self.keyboard_focus_stack.windows().map(window_id).collect(),
+                focused_output: self.focused_output().map(Output::name),
+                output_focus_stack: self
+                    .output_focus_stack
+                    .outputs()
+                    .map(Output::name)
+                    .collect(),
+                layer_focus: self
+                    .on_demand_layer_focus
+                    .as_ref()
+                    .map(|layer| layer.namespace().to_string()),
+            },
+            binds: {
+                let bind_state = &self.input_state.bind_state;
+                BindsDump {
+                    layer_stack: bind_state.layer_stack.clone(),
+                    keybinds: bind_state
+                        .keybinds
+                        .id_map
+                        .values()
+                        .map(|keybind| {
+                            let keybind = keybind.borrow();
+                            KeybindDump {
+                                key: xkbcommon::xkb::keysym_get_name(keybind.key),
+                                data: (&keybind.bind_data).into(),
+                            }
+                        })
+                        .collect(),
+                    mousebinds: bind_state
+                        .mousebinds
+                        .id_map
+                        .values()
+                        .map(|mousebind| {
+                            let mousebind = mousebind.borrow();
+                            MousebindDump {
+                                button: mousebind.button,
+                                data: (&mousebind.bind_data).into(),
+                            }
+                        })
+                        .collect(),
+                }
+            },
+        }
+    }
+
+    fn dump_output(&self, output: &Output) -> OutputDump {
+        let weak = output.downgrade();
+        let layout_state = &self.layout_state;
+
+        let mut trees = layout_state
+            .layout_trees
+            .get(&weak)
+            .into_iter()
+            .flatten()
+            .map(|(id, tree)| LayoutTreeDump {
+                id: *id,
+                root: tree.root_node().into(),
+            })
+            .collect::<Vec<_>>();
+        trees.sort_by_key(|tree| tree.id);
+
+        let (enabled, powered, tags) = output.with_state(|state| {
+            (
+                state.enabled_global_id.is_some(),
+                state.powered,
+                state
+                    .tags
+                    .iter()
+                    .map(|tag| TagDump {
+                        id: tag.id().to_inner(),
+                        name: tag.name(),
+                        active: tag.active(),
+                        defunct: tag.defunct(),
+                    })
+                    .collect(),
+            )
+        });
+
+        OutputDump {
+            name: output.name(),
+            enabled,
+            powered,
+            geometry: self.space.output_geometry(output).map(RectDump::from),
+            scale: output.current_scale().fractional_scale(),
+            tags,
+            layout: OutputLayoutDump {
+                current_tree_id: layout_state.current_layout_tree_ids.get(&weak).copied(),
+                trees,
+                pending_transactions: layout_state
+                    .pending_transactions
+                    .for_output(output)
+                    .iter()
+                    .map(TransactionDump::from)
+                    .collect(),
+                pending_unmaps: layout_state
+                    .pending_unmaps
+                    .for_output(output)
+                    .iter()
+                    .map(Vec::len)
+                    .sum(),
+                pending_window_updates: layout_state
+                    .pending_window_updates
+                    .for_output(output)
+                    .iter()
+                    .map(|updates| {
+                        updates
+                            .iter()
+                            .map(|(window, geo)| WindowGeometryDump {
+                                window_id: window_id(window),
+                                geometry: (*geo).into(),
+                            })
+                            .collect()
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    fn dump_window(&self, window: &WindowElement) -> WindowDump {
+        window.with_state(|state| WindowDump {
+            id: state.id.0,
+            app_id: window.class(),
+            title: window.title(),
+            pid: window.pid(&self.display_handle),
+            x11: window.x11_surface().is_some(),
+            output: window.output(self).map(|output| output.name()),
+            tags: tag_ids(&state.tags),
+            layout_mode: state.layout_mode.current(),
+            minimized: state.minimized,
+            mapped: self.space.elements().any(|win| win == window),
+            geometry: self.space.element_geometry(window).map(RectDump::from),
+            floating_loc: state
+                .floating_x
+                .zip(state.floating_y)
+                .map(|(x, y)| PointDump { x, y }),
+            floating_size: state.floating_size.into(),
+            layout_node: state.layout_node.map(u64::from),
+            pending_transactions: state.pending_transactions.len(),
+        })
+    }
+}
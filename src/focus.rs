@@ -7,6 +7,7 @@ use smithay::{
     reexports::wayland_server::Resource,
     utils::{IsAlive, SERIAL_COUNTER},
     wayland::{
+        input_method::InputMethodKeyboardGrab,
         shell::wlr_layer::{self, KeyboardInteractivity},
         xwayland_keyboard_grab::XWaylandKeyboardGrab,
     },
@@ -48,7 +49,7 @@ impl State {
         };
 
         if keyboard.current_focus().is_some_and(|focus| !focus.alive()) {
-            keyboard.set_focus(self, None, SERIAL_COUNTER.next_serial());
+            self.set_keyboard_focus(None, SERIAL_COUNTER.next_serial());
         }
 
         self.pinnacle
@@ -67,7 +68,7 @@ impl State {
                 return;
             }
 
-            keyboard.set_focus(self, lock_surface, SERIAL_COUNTER.next_serial());
+            self.set_keyboard_focus(lock_surface, SERIAL_COUNTER.next_serial());
 
             for win in self.pinnacle.windows.iter() {
                 win.set_activated(false);
@@ -93,7 +94,13 @@ impl State {
             keyboard.unset_grab(self);
         }
 
-        if keyboard.is_grabbed() {
+        // The input method grab only redirects key events to the IME,
+        // so focus should still be allowed to change underneath it.
+        let input_method_grab = keyboard
+            .with_grab(|_, grab| grab.downcast_ref::<InputMethodKeyboardGrab>().is_some())
+            .unwrap_or_default();
+
+        if keyboard.is_grabbed() && !input_method_grab {
             return;
         }
 
@@ -139,7 +146,7 @@ impl State {
                 return;
             }
 
-            keyboard.set_focus(self, Some(layer_target), SERIAL_COUNTER.next_serial());
+            self.set_keyboard_focus(Some(layer_target), SERIAL_COUNTER.next_serial());
 
             for win in self.pinnacle.windows.iter() {
                 win.set_activated(false);
@@ -163,7 +170,7 @@ impl State {
                 return;
             }
 
-            keyboard.set_focus(self, Some(layer_target), SERIAL_COUNTER.next_serial());
+            self.set_keyboard_focus(Some(layer_target), SERIAL_COUNTER.next_serial());
 
             for win in self.pinnacle.windows.iter() {
                 win.set_activated(false);
@@ -199,8 +206,7 @@ impl State {
             }
         }
 
-        keyboard.set_focus(
-            self,
+        self.set_keyboard_focus(
             focused_window.map(KeyboardFocusTarget::Window),
             SERIAL_COUNTER.next_serial(),
         );
@@ -311,7 +317,8 @@ impl OutputFocusStack {
         self.outputs().last()
     }
 
-    fn outputs(&self) -> impl DoubleEndedIterator<Item = &Output> {
+    /// Returns enabled outputs, from least to most recently focused.
+    pub fn outputs(&self) -> impl DoubleEndedIterator<Item = &Output> {
         self.stack
             .iter()
             .filter(|op| op.with_state(|state| state.enabled_global_id.is_some()))
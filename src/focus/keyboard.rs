@@ -5,14 +5,111 @@ use smithay::{
     desktop::{LayerSurface, PopupKind, WindowSurface},
     input::{
         Seat,
-        keyboard::{KeyboardTarget, KeysymHandle, ModifiersState},
+        keyboard::{KeyboardTarget, KeysymHandle, Layout, ModifiersState},
     },
     reexports::wayland_server::{Resource, protocol::wl_surface::WlSurface},
     utils::{IsAlive, Serial},
     wayland::{seat::WaylandFocus, session_lock::LockSurface},
 };
 
-use crate::{state::State, window::WindowElement};
+use crate::{
+    api::signal::Signal,
+    state::{State, WithState},
+    window::WindowElement,
+};
+
+/// State for keeping track of the active xkb layout.
+#[derive(Debug, Default)]
+pub struct XkbLayoutState {
+    /// Whether the active layout is remembered per window.
+    ///
+    /// When enabled, the active layout is saved to a window when it loses keyboard focus
+    /// and restored when it regains it.
+    pub per_window: bool,
+    /// The last layout that was announced through the layout changed signal.
+    last_layout: Option<Layout>,
+}
+
+impl State {
+    /// Sets keyboard focus to `focus`.
+    ///
+    /// If per-window layouts are enabled, this also saves the active layout
+    /// to the window losing focus and restores the layout of the window gaining it.
+    pub fn set_keyboard_focus(&mut self, focus: Option<KeyboardFocusTarget>, serial: Serial) {
+        let Some(keyboard) = self.pinnacle.seat.get_keyboard() else {
+            return;
+        };
+
+        let per_window = self.pinnacle.input_state.xkb_layout_state.per_window;
+
+        if per_window && let Some(KeyboardFocusTarget::Window(old)) = keyboard.current_focus() {
+            let layout = keyboard.with_xkb_state(self, |context| {
+                context.xkb().lock().unwrap().active_layout()
+            });
+            old.with_state_mut(|state| state.xkb_layout = Some(layout));
+        }
+
+        keyboard.set_focus(self, focus.clone(), serial);
+
+        if per_window
+            && let Some(KeyboardFocusTarget::Window(new)) = focus
+            && let Some(layout) = new.with_state(|state| state.xkb_layout)
+        {
+            keyboard.with_xkb_state(self, |mut context| {
+                let layout_count = context.xkb().lock().unwrap().layouts().count();
+                // The keymap may have changed since the layout was saved
+                if (layout.0 as usize) < layout_count {
+                    context.set_layout(layout);
+                }
+            });
+        }
+
+        self.signal_xkb_layout_changed();
+    }
+
+    /// Signals [`InputLayoutChanged`] if the active xkb layout differs
+    /// from the last announced one.
+    ///
+    /// [`InputLayoutChanged`]: crate::api::signal::InputLayoutChanged
+    pub fn signal_xkb_layout_changed(&mut self) {
+        let Some(keyboard) = self.pinnacle.seat.get_keyboard() else {
+            return;
+        };
+
+        let layout = keyboard.with_xkb_state(self, |context| {
+            context.xkb().lock().unwrap().active_layout()
+        });
+
+        let layout_state = &mut self.pinnacle.input_state.xkb_layout_state;
+        if layout_state.last_layout == Some(layout) {
+            return;
+        }
+        layout_state.last_layout = Some(layout);
+
+        // This runs on every key press, so only look up the name when signaling.
+        let name = keyboard.with_xkb_state(self, |context| {
+            context
+                .xkb()
+                .lock()
+                .unwrap()
+                .layout_name(layout)
+                .to_string()
+        });
+
+        self.pinnacle
+            .signal_state
+            .input_layout_changed
+            .signal((layout.0, &name));
+    }
+
+    /// Forgets the last announced layout so the next check signals unconditionally.
+    ///
+    /// This should be called when the keymap changes, as layout names may differ
+    /// at the same index.
+    pub fn reset_xkb_layout_changed(&mut self) {
+        self.pinnacle.input_state.xkb_layout_state.last_layout = None;
+    }
+}
 
 /// Keyboard focusable objects
 #[derive(Debug, Clone, PartialEq)]
@@ -8,6 +8,7 @@ pub mod foreign_toplevel_list;
 pub mod idle;
 pub mod image_capture_source;
 pub mod image_copy_capture;
+mod input_method;
 pub mod session_lock;
 pub mod snowcap_decoration;
 pub mod xdg_activation;
@@ -755,6 +756,13 @@ impl ScreencopyHandler for State {
     fn frame(&mut self, frame: Screencopy) {
         let _span = tracy_client::span!("ScreencopyHandler::frame");
 
+        if let Some(client) = frame.buffer().client()
+            && !self.pinnacle.screen_capture_allowed(&client)
+        {
+            // Dropping the frame fails it
+            return;
+        }
+
         let output = frame.output().clone();
         if !frame.with_damage() {
             self.schedule_render(&output);
@@ -833,14 +841,13 @@ impl SecurityContextHandler for State {
                     ..Default::default()
                 });
 
-                if let Err(err) = state
-                    .pinnacle
-                    .display_handle
-                    .insert_client(client, client_state)
-                {
-                    warn!("Failed to insert a restricted client: {err}");
-                } else {
-                    trace!("Inserted a restricted client, context={context:?}");
+                let dh = state.pinnacle.display_handle.clone();
+                match dh.insert_client(client, client_state) {
+                    Ok(client) => {
+                        ClientState::init_identity(&client, &dh, Some(&context));
+                        trace!("Inserted a restricted client, context={context:?}");
+                    }
+                    Err(err) => warn!("Failed to insert a restricted client: {err}"),
                 }
             })
             .expect("Failed to insert security context listener source into event loop");
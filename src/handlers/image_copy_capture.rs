@@ -13,7 +13,7 @@ use smithay::{
     },
     delegate_image_copy_capture,
     output::Output,
-    reexports::wayland_server::protocol::wl_shm,
+    reexports::wayland_server::{Resource, protocol::wl_shm},
     utils::{Buffer, Physical, Point, Rectangle, Size, Transform},
     wayland::{
         compositor,
@@ -71,6 +71,13 @@ impl ImageCopyCaptureHandler for State {
     }
 
     fn frame(&mut self, session: &SessionRef, frame: Frame) {
+        if let Some(client) = frame.buffer().client()
+            && !self.pinnacle.screen_capture_allowed(&client)
+        {
+            frame.fail(CaptureFailureReason::Stopped);
+            return;
+        }
+
         let f = session.user_data().get_or_insert(|| RefCell::new(None));
         *f.borrow_mut() = Some(frame);
     }
@@ -102,6 +109,13 @@ impl ImageCopyCaptureHandler for State {
     }
 
     fn cursor_frame(&mut self, session: &CursorSessionRef, frame: Frame) {
+        if let Some(client) = frame.buffer().client()
+            && !self.pinnacle.screen_capture_allowed(&client)
+        {
+            frame.fail(CaptureFailureReason::Stopped);
+            return;
+        }
+
         let f = session.user_data().get_or_insert(|| RefCell::new(None));
         *f.borrow_mut() = Some(frame);
     }
@@ -0,0 +1,54 @@
+use smithay::{
+    delegate_input_method_manager, delegate_text_input_manager, delegate_virtual_keyboard_manager,
+    desktop::{PopupKind, PopupManager},
+    reexports::wayland_server::protocol::wl_surface::WlSurface,
+    utils::{Logical, Rectangle},
+    wayland::input_method::{InputMethodHandler, PopupSurface},
+};
+use tracing::warn;
+
+use crate::state::State;
+
+impl InputMethodHandler for State {
+    fn new_popup(&mut self, surface: PopupSurface) {
+        let _span = tracy_client::span!("InputMethodHandler::new_popup");
+
+        if let Err(err) = self
+            .pinnacle
+            .popup_manager
+            .track_popup(PopupKind::from(surface))
+        {
+            warn!("Failed to track input method popup: {err}");
+        }
+    }
+
+    fn popup_repositioned(&mut self, _surface: PopupSurface) {
+        let _span = tracy_client::span!("InputMethodHandler::popup_repositioned");
+
+        // TODO: only schedule on the outputs the popup is on
+        for output in self.pinnacle.space.outputs().cloned().collect::<Vec<_>>() {
+            self.schedule_render(&output);
+        }
+    }
+
+    fn dismiss_popup(&mut self, surface: PopupSurface) {
+        let _span = tracy_client::span!("InputMethodHandler::dismiss_popup");
+
+        if let Some(parent) = surface.get_parent().map(|parent| parent.surface.clone()) {
+            let _ = PopupManager::dismiss_popup(&parent, &PopupKind::from(surface));
+        }
+    }
+
+    fn parent_geometry(&self, parent: &WlSurface) -> Rectangle<i32, Logical> {
+        // The text input cursor rectangle is relative to the parent's wl surface,
+        // so decorations must not be taken into account here.
+        self.pinnacle
+            .window_for_surface(parent)
+            .map(|win| win.geometry_without_decorations())
+            .unwrap_or_default()
+    }
+}
+delegate_input_method_manager!(State);
+
+delegate_text_input_manager!(State);
+delegate_virtual_keyboard_manager!(State);
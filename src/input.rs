@@ -1,17 +1,22 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 pub mod bind;
+pub mod keyboard;
 pub mod libinput;
 
 use std::{any::Any, time::Duration};
 
 use crate::{
     api::signal::Signal as _,
-    focus::pointer::{PointerContents, PointerFocusTarget},
+    focus::{
+        keyboard::{KeyboardFocusTarget, XkbLayoutState},
+        pointer::{PointerContents, PointerFocusTarget},
+    },
     state::{Pinnacle, WithState},
     window::WindowElement,
 };
 use bind::BindState;
+use keyboard::KeyboardConfigState;
 use libinput::LibinputState;
 use smithay::{
     backend::{
@@ -54,12 +59,8 @@ use crate::state::State;
 pub struct InputState {
     pub bind_state: BindState,
     pub libinput_state: LibinputState,
-}
-
-impl InputState {
-    pub fn clear(&mut self) {
-        self.bind_state.clear();
-    }
+    pub xkb_layout_state: XkbLayoutState,
+    pub keyboard_config_state: KeyboardConfigState,
 }
 
 impl InputState {
@@ -328,6 +329,12 @@ impl State {
     pub fn update_pointer_focus(&mut self) {
         let _span = tracy_client::span!("State::update_pointer_focus");
 
+        // Don't change hover state under a hidden cursor;
+        // focus is updated again once the pointer moves.
+        if self.pinnacle.cursor_state.is_hidden() {
+            return;
+        }
+
         let Some(pointer) = self.pinnacle.seat.get_pointer() else {
             return;
         };
@@ -424,9 +431,17 @@ impl State {
         }
     }
 
-    fn on_keyboard<I: InputBackend>(&mut self, event: I::KeyboardKeyEvent) {
+    fn on_keyboard<I: InputBackend>(&mut self, event: I::KeyboardKeyEvent)
+    where
+        I::Device: 'static,
+    {
         let _span = tracy_client::span!("State::on_keyboard");
 
+        let device = event.device();
+        self.apply_device_keyboard_config(<dyn Any>::downcast_ref::<
+            smithay::reexports::input::Device,
+        >(&device));
+
         let Some(keyboard) = self.pinnacle.seat.get_keyboard() else {
             return;
         };
@@ -446,6 +461,9 @@ impl State {
             })
             .is_some_and(|inhibitor| inhibitor.is_active());
 
+        // Binds are checked before the key reaches any active keyboard grab,
+        // so an input method that grabbed the keyboard only receives keys
+        // that weren't intercepted here.
         let action = keyboard.input(
             self,
             event.key_code(),
@@ -490,6 +508,21 @@ impl State {
             },
         );
 
+        // Layouts can be switched by xkb options like `grp:alt_shift_toggle`
+        self.signal_xkb_layout_changed();
+
+        // Only typing into windows hides the cursor; launchers and other layer surfaces
+        // are often used with the pointer too
+        if press_state == KeyState::Pressed
+            && action.is_none()
+            && matches!(
+                keyboard.current_focus(),
+                Some(KeyboardFocusTarget::Window(_))
+            )
+        {
+            self.hide_cursor_for_typing();
+        }
+
         if let Some(action) = action {
             match action {
                 KeyAction::Quit => {
@@ -682,6 +715,8 @@ impl State {
     ) {
         let _span = tracy_client::span!("State::on_pointer_motion_absolute");
 
+        self.notify_cursor_motion();
+
         let Some(pointer) = self.pinnacle.seat.get_pointer() else {
             error!("Pointer motion absolute received with no pointer on seat");
             return;
@@ -728,6 +763,8 @@ impl State {
     fn on_pointer_motion<I: InputBackend>(&mut self, event: I::PointerMotionEvent) {
         let _span = tracy_client::span!("State::on_pointer_motion");
 
+        self.notify_cursor_motion();
+
         let Some(pointer) = self.pinnacle.seat.get_pointer() else {
             error!("Pointer motion received with no pointer on seat");
             return;
@@ -1032,6 +1069,8 @@ impl State {
     where
         I::Device: 'static,
     {
+        self.notify_cursor_motion();
+
         let Some(touch) = self.pinnacle.seat.get_touch() else {
             return;
         };
@@ -1058,6 +1097,8 @@ impl State {
     where
         I::Device: 'static,
     {
+        self.notify_cursor_motion();
+
         let Some(touch) = self.pinnacle.seat.get_touch() else {
             return;
         };
@@ -1114,6 +1155,8 @@ impl State {
     where
         I::Device: 'static,
     {
+        self.notify_cursor_motion();
+
         let Some(loc) = self.transform_device_coords(&event) else {
             return;
         };
@@ -1180,6 +1223,8 @@ impl State {
     where
         I::Device: 'static,
     {
+        self.notify_cursor_motion();
+
         let Some(loc) = self.transform_device_coords(&event) else {
             return;
         };
@@ -10,6 +10,8 @@ use smithay::input::keyboard::ModifiersState;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use xkbcommon::xkb::Keysym;
 
+use crate::api::connection::ApiClientId;
+
 static BIND_ID_COUNTER: AtomicU32 = AtomicU32::new(0);
 
 #[derive(Debug, Default)]
@@ -20,11 +22,24 @@ pub struct BindState {
 }
 
 impl BindState {
-    pub fn clear(&mut self) {
-        self.keybinds.id_map.clear();
-        self.keybinds.keysym_map.clear();
-        self.mousebinds.id_map.clear();
-        self.mousebinds.button_map.clear();
+    /// Removes all binds owned by `owner`.
+    ///
+    /// Binds with no owner come from the static config.
+    pub fn remove_owned_by(&mut self, owner: Option<ApiClientId>) {
+        self.keybinds
+            .id_map
+            .retain(|_, keybind| keybind.borrow().bind_data.owner != owner);
+        self.keybinds.keysym_map.retain(|_, keybinds| {
+            keybinds.retain(|keybind| keybind.strong_count() > 0);
+            !keybinds.is_empty()
+        });
+        self.mousebinds
+            .id_map
+            .retain(|_, mousebind| mousebind.borrow().bind_data.owner != owner);
+        self.mousebinds.button_map.retain(|_, mousebinds| {
+            mousebinds.retain(|mousebind| mousebind.strong_count() > 0);
+            !mousebinds.is_empty()
+        });
     }
 
     pub fn enter_layer(&mut self, layer: Option<String>) {
@@ -92,11 +107,13 @@ pub enum Edge {
     Release,
 }
 
-#[derive(Debug, Copy, Clone)]
+/// Required modifiers. `None` means the modifier is ignored.
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct ModMask {
     pub shift: Option<bool>,
     pub ctrl: Option<bool>,
     pub alt: Option<bool>,
+    #[serde(rename = "super")]
     pub super_: Option<bool>,
     pub iso_level3_shift: Option<bool>,
     pub iso_level5_shift: Option<bool>,
@@ -133,6 +150,8 @@ impl ModMask {
 #[derive(Debug)]
 pub struct BindData {
     pub id: u32,
+    /// The client that created this bind, or `None` if it's from the static config.
+    pub owner: Option<ApiClientId>,
     pub mods: ModMask,
     pub layer: Option<String>,
     pub group: String,
@@ -297,6 +316,7 @@ impl Keybinds {
         is_quit_bind: bool,
         is_reload_config_bind: bool,
         allow_when_locked: bool,
+        owner: Option<ApiClientId>,
     ) -> u32 {
         let id = BIND_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
 
@@ -305,6 +325,7 @@ impl Keybinds {
         let keybind = Rc::new(RefCell::new(Keybind {
             bind_data: BindData {
                 id,
+                owner,
                 mods,
                 layer,
                 group,
@@ -485,6 +506,7 @@ impl Mousebinds {
         is_quit_bind: bool,
         is_reload_config_bind: bool,
         allow_when_locked: bool,
+        owner: Option<ApiClientId>,
     ) -> u32 {
         let id = BIND_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
 
@@ -493,6 +515,7 @@ impl Mousebinds {
         let mousebind = Rc::new(RefCell::new(Mousebind {
             bind_data: BindData {
                 id,
+                owner,
                 mods,
                 layer,
                 group,
@@ -0,0 +1,183 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use smithay::{input::keyboard::XkbConfig, reexports::input::Device};
+use tracing::warn;
+use xkbcommon::xkb;
+
+use crate::state::State;
+
+/// Keeps track of which keyboard config is applied to the seat keyboard.
+///
+/// Smithay only has one keymap per keyboard, so per-device configs are
+/// implemented by swapping the keymap and repeat info whenever a key
+/// is pressed on a different device.
+#[derive(Debug)]
+pub struct KeyboardConfigState {
+    /// The seat-wide keymap, or `None` to use the default xkb config.
+    pub default_keymap: Option<String>,
+    /// The seat-wide repeat rate and delay, in milliseconds.
+    pub default_repeat_info: (i32, i32),
+    /// The device whose config is currently applied, or `None`
+    /// if the seat-wide config is applied.
+    active_device: Option<Device>,
+    /// Whether the applied config is stale and must be reapplied.
+    dirty: bool,
+    /// A hash of the applied keymap, or `None` if it isn't known.
+    applied_keymap: Option<u64>,
+}
+
+impl Default for KeyboardConfigState {
+    fn default() -> Self {
+        Self {
+            default_keymap: None,
+            default_repeat_info: (25, 500),
+            active_device: None,
+            dirty: false,
+            applied_keymap: None,
+        }
+    }
+}
+
+impl KeyboardConfigState {
+    /// Marks the config of `device` as changed so it is reapplied
+    /// on the next key press if it is currently applied.
+    pub fn invalidate_device(&mut self, device: &Device) {
+        if self.active_device.as_ref() == Some(device) {
+            self.dirty = true;
+        }
+    }
+
+    /// Returns the device whose config is currently applied, or `None`
+    /// if the seat-wide config is applied.
+    pub fn active_device(&self) -> Option<&Device> {
+        self.active_device.as_ref()
+    }
+
+    /// Notes that the seat-wide config was just applied to the keyboard.
+    pub fn reset(&mut self) {
+        self.active_device = None;
+        self.dirty = false;
+        self.applied_keymap = Some(keymap_hash(self.default_keymap.as_deref()));
+    }
+}
+
+/// Per-device keyboard config.
+#[derive(Debug, Default, Clone)]
+pub struct DeviceKeyboardConfig {
+    /// The keymap for this device.
+    pub keymap: Option<String>,
+    /// The repeat rate and delay for this device, in milliseconds.
+    pub repeat_info: Option<(i32, i32)>,
+}
+
+impl DeviceKeyboardConfig {
+    fn is_empty(&self) -> bool {
+        self.keymap.is_none() && self.repeat_info.is_none()
+    }
+}
+
+/// Compiles an xkb config into a keymap string.
+///
+/// Returns `None` if the config is invalid.
+pub fn compile_keymap(config: &XkbConfig<'_>) -> Option<String> {
+    let context = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+    let keymap = xkb::Keymap::new_from_names(
+        &context,
+        config.rules,
+        config.model,
+        config.layout,
+        config.variant,
+        config.options.clone(),
+        xkb::KEYMAP_COMPILE_NO_FLAGS,
+    )?;
+    Some(keymap.get_as_string(xkb::KEYMAP_FORMAT_TEXT_V1))
+}
+
+impl State {
+    /// Applies the keyboard config of `device` to the seat keyboard if it
+    /// isn't already applied, falling back to the seat-wide config.
+    pub fn apply_device_keyboard_config(&mut self, device: Option<&Device>) {
+        let _span = tracy_client::span!("State::apply_device_keyboard_config");
+
+        let libinput_state = &self.pinnacle.input_state.libinput_state;
+        let device = device.filter(|device| {
+            libinput_state
+                .devices
+                .get(device)
+                .is_some_and(|state| !state.keyboard_config.is_empty())
+        });
+
+        let config_state = &mut self.pinnacle.input_state.keyboard_config_state;
+        if !config_state.dirty && config_state.active_device.as_ref() == device {
+            return;
+        }
+
+        config_state.active_device = device.cloned();
+        config_state.dirty = false;
+
+        let config = device
+            .and_then(|device| libinput_state.devices.get(device))
+            .map(|state| state.keyboard_config.clone())
+            .unwrap_or_default();
+
+        self.switch_keyboard_config(config);
+    }
+
+    /// Applies `config` to the seat keyboard, falling back to the seat-wide
+    /// config for anything it doesn't set.
+    ///
+    /// The keymap is only swapped if it differs from the applied one,
+    /// and the active layout is kept across the swap.
+    pub fn switch_keyboard_config(&mut self, config: DeviceKeyboardConfig) {
+        let Some(keyboard) = self.pinnacle.seat.get_keyboard() else {
+            return;
+        };
+
+        let config_state = &mut self.pinnacle.input_state.keyboard_config_state;
+
+        let keymap = config
+            .keymap
+            .or_else(|| config_state.default_keymap.clone());
+        let (rate, delay) = config
+            .repeat_info
+            .unwrap_or(config_state.default_repeat_info);
+
+        keyboard.change_repeat_info(rate, delay);
+
+        let keymap_hash = keymap_hash(keymap.as_deref());
+        if config_state.applied_keymap == Some(keymap_hash) {
+            return;
+        }
+        config_state.applied_keymap = Some(keymap_hash);
+
+        // Swapping the keymap resets the active layout, which would undo
+        // per-window layouts whenever another keyboard is typed on.
+        let layout = keyboard.with_xkb_state(self, |context| {
+            context.xkb().lock().unwrap().active_layout()
+        });
+
+        let res = match keymap {
+            Some(keymap) => keyboard.set_keymap_from_string(self, keymap),
+            None => keyboard.set_xkb_config(self, XkbConfig::default()),
+        };
+        if let Err(err) = res {
+            warn!("Failed to apply keyboard keymap: {err}");
+        }
+
+        keyboard.with_xkb_state(self, |mut context| {
+            let layout_count = context.xkb().lock().unwrap().layouts().count();
+            if (layout.0 as usize) < layout_count {
+                context.set_layout(layout);
+            }
+        });
+
+        self.reset_xkb_layout_changed();
+        self.signal_xkb_layout_changed();
+    }
+}
+
+fn keymap_hash(keymap: Option<&str>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    keymap.hash(&mut hasher);
+    hasher.finish()
+}
@@ -1,13 +1,16 @@
+use anyhow::{Context, ensure};
 use indexmap::IndexMap;
 use smithay::{
     desktop::Space,
     output::{Output, WeakOutput},
-    reexports::input::Device,
+    reexports::input::{AsRaw, Device},
     utils::{Logical, Rectangle},
 };
 
 use crate::window::WindowElement;
 
+use super::keyboard::DeviceKeyboardConfig;
+
 #[derive(Debug, Default)]
 pub struct LibinputState {
     pub devices: IndexMap<Device, DeviceState>,
@@ -24,6 +27,11 @@ enum MapTarget {
 #[derive(Debug, Default)]
 pub struct DeviceState {
     map_target: Option<MapTarget>,
+    pub keyboard_config: DeviceKeyboardConfig,
+    /// The custom acceleration functions last applied to this device.
+    ///
+    /// libinput has no way to query these, so they're kept around for reading back.
+    pub custom_accel: Option<Vec<CustomAccelCurve>>,
 }
 
 impl DeviceState {
@@ -103,3 +111,171 @@ pub fn device_type(device: &Device) -> DeviceType {
         DeviceType::Unknown
     }
 }
+
+/// A pointer acceleration profile.
+///
+/// Unlike [`smithay::reexports::input::AccelProfile`], this includes the custom profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccelProfile {
+    Flat,
+    Adaptive,
+    Custom,
+}
+
+impl AccelProfile {
+    fn to_raw(self) -> input_sys::libinput_config_accel_profile {
+        match self {
+            AccelProfile::Flat => {
+                input_sys::libinput_config_accel_profile_LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT
+            }
+            AccelProfile::Adaptive => {
+                input_sys::libinput_config_accel_profile_LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE
+            }
+            AccelProfile::Custom => {
+                input_sys::libinput_config_accel_profile_LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM
+            }
+        }
+    }
+}
+
+/// A type of movement that a custom acceleration function applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccelType {
+    Fallback,
+    Motion,
+    Scroll,
+}
+
+impl AccelType {
+    fn to_raw(self) -> input_sys::libinput_config_accel_type {
+        match self {
+            AccelType::Fallback => {
+                input_sys::libinput_config_accel_type_LIBINPUT_ACCEL_TYPE_FALLBACK
+            }
+            AccelType::Motion => input_sys::libinput_config_accel_type_LIBINPUT_ACCEL_TYPE_MOTION,
+            AccelType::Scroll => input_sys::libinput_config_accel_type_LIBINPUT_ACCEL_TYPE_SCROLL,
+        }
+    }
+}
+
+/// A custom acceleration function for one [`AccelType`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomAccelCurve {
+    pub accel_type: AccelType,
+    pub step: f64,
+    pub points: Vec<f64>,
+}
+
+// Limits from libinput's `libinput_config_accel_set_points`
+const CUSTOM_ACCEL_MIN_POINTS: usize = 2;
+const CUSTOM_ACCEL_MAX_POINTS: usize = 64;
+const CUSTOM_ACCEL_MAX_STEP: f64 = 10000.0;
+const CUSTOM_ACCEL_MAX_POINT: f64 = 10000.0;
+
+impl CustomAccelCurve {
+    /// Checks that this curve is within libinput's limits.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        ensure!(
+            self.step > 0.0 && self.step <= CUSTOM_ACCEL_MAX_STEP,
+            "step must be in (0, {CUSTOM_ACCEL_MAX_STEP}], got {}",
+            self.step
+        );
+        ensure!(
+            (CUSTOM_ACCEL_MIN_POINTS..=CUSTOM_ACCEL_MAX_POINTS).contains(&self.points.len()),
+            "custom accel requires between {CUSTOM_ACCEL_MIN_POINTS} and \
+            {CUSTOM_ACCEL_MAX_POINTS} points, got {}",
+            self.points.len()
+        );
+        ensure!(
+            self.points
+                .iter()
+                .all(|point| (0.0..=CUSTOM_ACCEL_MAX_POINT).contains(point)),
+            "points must be in [0, {CUSTOM_ACCEL_MAX_POINT}]"
+        );
+        Ok(())
+    }
+}
+
+/// Returns the current acceleration profile of `device`, if it has one.
+pub fn accel_profile(device: &Device) -> Option<AccelProfile> {
+    let profile =
+        unsafe { input_sys::libinput_device_config_accel_get_profile(device.as_raw_mut()) };
+
+    [
+        AccelProfile::Flat,
+        AccelProfile::Adaptive,
+        AccelProfile::Custom,
+    ]
+    .into_iter()
+    .find(|p| p.to_raw() == profile)
+}
+
+/// Returns the acceleration profiles `device` supports.
+pub fn accel_profiles(device: &Device) -> Vec<AccelProfile> {
+    let profiles =
+        unsafe { input_sys::libinput_device_config_accel_get_profiles(device.as_raw_mut()) };
+
+    [
+        AccelProfile::Flat,
+        AccelProfile::Adaptive,
+        AccelProfile::Custom,
+    ]
+    .into_iter()
+    .filter(|p| profiles & p.to_raw() != 0)
+    .collect()
+}
+
+/// Switches `device` to the custom acceleration profile with the given functions.
+///
+/// Movement types without a function use libinput's default, which is unaccelerated.
+pub fn apply_custom_accel(device: &Device, curves: &[CustomAccelCurve]) -> anyhow::Result<()> {
+    ensure!(
+        accel_profiles(device).contains(&AccelProfile::Custom),
+        "device does not support custom acceleration"
+    );
+
+    for curve in curves {
+        curve.validate()?;
+    }
+
+    // SAFETY: `config` is checked to be non-null and is destroyed exactly once below.
+    // `set_points` copies the points, so they only have to outlive the call.
+    unsafe {
+        let config = input_sys::libinput_config_accel_create(AccelProfile::Custom.to_raw());
+        let config = (!config.is_null())
+            .then_some(config)
+            .context("failed to create accel config")?;
+
+        let mut result = Ok(());
+
+        for curve in curves {
+            let mut points = curve.points.clone();
+            let status = input_sys::libinput_config_accel_set_points(
+                config,
+                curve.accel_type.to_raw(),
+                curve.step,
+                points.len(),
+                points.as_mut_ptr(),
+            );
+
+            if status != input_sys::libinput_config_status_LIBINPUT_CONFIG_STATUS_SUCCESS {
+                result = Err(anyhow::anyhow!(
+                    "libinput rejected the {:?} accel curve",
+                    curve.accel_type
+                ));
+                break;
+            }
+        }
+
+        if result.is_ok() {
+            let status = input_sys::libinput_device_config_accel_apply(device.as_raw_mut(), config);
+            if status != input_sys::libinput_config_status_LIBINPUT_CONFIG_STATUS_SUCCESS {
+                result = Err(anyhow::anyhow!("failed to apply custom accel"));
+            }
+        }
+
+        input_sys::libinput_config_accel_destroy(config);
+
+        result
+    }
+}
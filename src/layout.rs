@@ -19,6 +19,7 @@ use tracing::warn;
 use tree::{LayoutNode, LayoutTree, ResizeDir};
 
 use crate::{
+    api::connection::ApiClientId,
     backend::Backend,
     output::OutputName,
     state::{Pinnacle, State, WithState},
@@ -202,6 +203,8 @@ impl LayoutRequestId {
 #[derive(Debug, Default)]
 pub struct LayoutState {
     pub layout_request_sender: Option<UnboundedSender<LayoutInfo>>,
+    /// The client managing layouts.
+    pub layout_manager_owner: Option<ApiClientId>,
     pub pending_swap: bool,
     pub pending_resize: bool,
     current_id: LayoutRequestId,
@@ -246,6 +249,14 @@ impl PendingTransactions {
         None
     }
 
+    /// Gets the transactions pending on `output`, oldest first.
+    pub fn for_output(&self, output: &Output) -> &[PendingTransaction] {
+        self.pending
+            .get(&output.downgrade())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     #[cfg(feature = "testing")]
     pub fn is_empty(&self) -> bool {
         self.pending.is_empty() || self.pending.iter().all(|(_, v)| v.is_empty())
@@ -277,6 +288,14 @@ impl PendingUnmaps {
 
         (!entry.is_empty()).then(|| entry.remove(0))
     }
+
+    /// Gets the sets of [`UnmappingWindow`]s pending on `output`, oldest first.
+    pub fn for_output(&self, output: &Output) -> &[Vec<Rc<UnmappingWindow>>] {
+        self.pending
+            .get(&output.downgrade())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
 }
 
 /// Pending window updates.
@@ -311,6 +330,14 @@ impl PendingWindowUpdates {
 
         (!entry.is_empty()).then(|| entry.remove(0))
     }
+
+    /// Gets the sets of windows and target geometries pending on `output`, oldest first.
+    pub fn for_output(&self, output: &Output) -> &[Vec<(WindowElement, Rectangle<i32, Logical>)>] {
+        self.pending
+            .get(&output.downgrade())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
 }
 
 impl LayoutState {
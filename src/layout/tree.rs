@@ -4,7 +4,7 @@ mod tests;
 use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
-use smithay::utils::{Logical, Rectangle, Size};
+use smithay::utils::{Logical, Point, Rectangle, Size};
 use tracing::trace;
 
 use crate::util::treediff::{
@@ -57,6 +57,101 @@ impl PartialEq for LayoutTree {
     }
 }
 
+/// A node in a [`LayoutTree`] along with its geometry from the last layout.
+#[derive(Debug, Clone, Copy)]
+pub struct LayoutNodeRef<'a> {
+    tree: &'a taffy::TaffyTree<NodeContext>,
+    node: taffy::NodeId,
+    layout_node: &'a LayoutNode,
+    /// The absolute location of this node's parent.
+    offset: Point<f32, Logical>,
+}
+
+impl<'a> LayoutNodeRef<'a> {
+    /// Returns the id windows use to refer to the node they are tiled in.
+    pub fn id(&self) -> taffy::NodeId {
+        self.node
+    }
+
+    pub fn label(&self) -> Option<&'a str> {
+        self.layout_node.label.as_deref()
+    }
+
+    pub fn traversal_index(&self) -> u32 {
+        self.context()
+            .map(|context| context.traversal_index)
+            .unwrap_or_default()
+    }
+
+    pub fn traversal_overrides(self) -> impl Iterator<Item = (u32, &'a [u32])> {
+        self.context()
+            .into_iter()
+            .flat_map(|context| &context.traversal_overrides)
+            .map(|(index, overrides)| (*index, overrides.as_slice()))
+    }
+
+    pub fn style(&self) -> Option<&'a taffy::Style> {
+        self.tree.style(self.node).ok()
+    }
+
+    /// Returns the geometry computed in the last layout, relative to the output's usable area.
+    pub fn geometry(&self) -> Rectangle<f32, Logical> {
+        Self::abs_geometry(self.tree, self.node, self.offset)
+    }
+
+    /// For leaves, returns the area a window is tiled in after gaps.
+    pub fn tile(&self) -> Option<Rectangle<f32, Logical>> {
+        if !self.layout_node.children.is_empty() {
+            return None;
+        }
+
+        // Leaves have an extra child that holds their gaps, see `process_leaves`
+        let leaf = self.tree.children(self.node).ok()?.first().copied()?;
+        Some(Self::abs_geometry(self.tree, leaf, self.geometry().loc))
+    }
+
+    pub fn children(self) -> impl Iterator<Item = LayoutNodeRef<'a>> {
+        let tree = self.tree;
+        let offset = self.geometry().loc;
+        let children = if self.layout_node.children.is_empty() {
+            Vec::new()
+        } else {
+            tree.children(self.node).unwrap_or_default()
+        };
+
+        children
+            .into_iter()
+            .zip(&self.layout_node.children)
+            .map(move |(node, layout_node)| LayoutNodeRef {
+                tree,
+                node,
+                layout_node,
+                offset,
+            })
+    }
+
+    fn context(&self) -> Option<&'a NodeContext> {
+        self.tree.get_node_context(self.node)
+    }
+
+    fn abs_geometry(
+        tree: &taffy::TaffyTree<NodeContext>,
+        node: taffy::NodeId,
+        offset: Point<f32, Logical>,
+    ) -> Rectangle<f32, Logical> {
+        let (loc, size) = tree
+            .layout(node)
+            .map(|layout| {
+                (
+                    (layout.location.x, layout.location.y),
+                    (layout.size.width, layout.size.height),
+                )
+            })
+            .unwrap_or_default();
+        Rectangle::new(offset + Point::from(loc), size.into())
+    }
+}
+
 fn taffy_node_partial_eq(
     this: &taffy::TaffyTree<NodeContext>,
     other: &taffy::TaffyTree<NodeContext>,
@@ -364,6 +459,23 @@ impl LayoutTree {
         geos
     }
 
+    /// Returns the root node of this tree.
+    pub fn root_node(&self) -> LayoutNodeRef<'_> {
+        let node = self
+            .taffy_tree
+            .children(self.taffy_root_id)
+            .ok()
+            .and_then(|children| children.first().copied())
+            .unwrap_or(self.taffy_root_id);
+
+        LayoutNodeRef {
+            tree: &self.taffy_tree,
+            node,
+            layout_node: &self.root,
+            offset: Point::default(),
+        }
+    }
+
     pub fn diff(&mut self, new_root: LayoutNode) {
         Self::unprocess_leaves(&mut self.taffy_tree, self.taffy_root_id);
 
@@ -4,6 +4,7 @@ pub mod cli;
 pub mod config;
 pub mod cursor;
 pub mod decoration;
+pub mod dump;
 pub mod focus;
 pub mod grab;
 pub mod handlers;
@@ -11,6 +12,7 @@ pub mod hook;
 pub mod input;
 pub mod layout;
 pub mod output;
+pub mod privilege;
 pub mod process;
 pub mod protocol;
 pub mod render;
@@ -131,6 +131,18 @@ async fn run() -> anyhow::Result<()> {
             CliSubcommand::Debug(DebugSubcommand::Panic) => {
                 pinnacle::util::cause_panic();
             }
+            CliSubcommand::Debug(DebugSubcommand::DumpState { socket }) => {
+                if let Err(err) = cli::msg::dump_state(socket).await {
+                    eprintln!("Error: {err:#}");
+                    std::process::exit(1);
+                }
+            }
+            CliSubcommand::Debug(DebugSubcommand::Replay(replay_args)) => {
+                if let Err(err) = cli::replay::run(replay_args).await {
+                    eprintln!("Error: {err:#}");
+                    std::process::exit(1);
+                }
+            }
             CliSubcommand::GenCompletions { shell } => {
                 clap_complete::generate(
                     shell,
@@ -142,6 +154,12 @@ async fn run() -> anyhow::Result<()> {
             CliSubcommand::Client { execute } => {
                 start_lua_repl(execute);
             }
+            CliSubcommand::Msg(msg_args) => {
+                if let Err(err) = cli::msg::run(msg_args).await {
+                    eprintln!("Error: {err:#}");
+                    std::process::exit(1);
+                }
+            }
         }
         return Ok(());
     }
@@ -259,13 +277,21 @@ async fn run() -> anyhow::Result<()> {
 
     #[cfg(feature = "snowcap")]
     {
+        use pinnacle::config::SnowcapRenderer;
+        use snowcap::compositor::RendererKind;
         use tokio::sync::oneshot::error::TryRecvError;
 
+        let renderer_kind = match startup_config.snowcap_renderer {
+            Some(SnowcapRenderer::Wgpu) => RendererKind::Auto,
+            Some(SnowcapRenderer::TinySkia) => RendererKind::TinySkia,
+            None => RendererKind::from_env(),
+        };
+
         let (sender, mut recv) = tokio::sync::oneshot::channel();
         let join_handle = tokio::task::spawn_blocking(move || {
             let _span = tracing::error_span!("snowcap");
             let _span = _span.enter();
-            snowcap::start(Some(sender));
+            snowcap::start(Some(sender), renderer_kind);
         });
 
         let snowcap_handle = loop {
@@ -0,0 +1,408 @@
+//! Restricting access to privileged protocols.
+//!
+//! Protocols like screencopy and data control let a client observe or manipulate
+//! the rest of the session. The [`PrivilegePolicy`] decides which clients can see
+//! these globals, based on whether they connected through a security context
+//! (i.e. they are sandboxed) and on the path of their executable.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use smithay::{
+    reexports::wayland_server::{Client, DisplayHandle},
+    wayland::security_context::SecurityContext,
+    xwayland::XWaylandClientData,
+};
+use tracing::{debug, info};
+
+use crate::state::{ClientState, Pinnacle};
+
+/// A group of protocols that can be restricted by a [`PrivilegePolicy`].
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivilegedProtocol {
+    /// wlr-screencopy and ext-image-copy-capture.
+    ScreenCapture,
+    /// wlr-data-control and ext-data-control.
+    DataControl,
+    /// wlr-foreign-toplevel-management.
+    ForeignToplevelManagement,
+    /// wlr-output-management.
+    OutputManagement,
+    /// wlr-output-power-management.
+    OutputPowerManagement,
+    /// wlr-gamma-control.
+    GammaControl,
+    /// The virtual keyboard protocol.
+    VirtualInput,
+    /// The input method protocol.
+    InputMethod,
+    /// ext-session-lock.
+    SessionLock,
+    /// wlr-layer-shell.
+    LayerShell,
+    /// ext-workspace.
+    Workspace,
+    /// wp-security-context.
+    SecurityContext,
+}
+
+/// The `[privileges]` section of `pinnacle.toml`.
+#[derive(serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PrivilegePolicy {
+    /// Whether clients connecting through a security context (Flatpak, etc.)
+    /// are denied all privileged protocols.
+    pub restrict_sandboxed: bool,
+    /// Executables that may use protected protocols.
+    ///
+    /// If this is `None`, every non-sandboxed client may use them.
+    pub allowlist: Option<Vec<PathBuf>>,
+    /// Protocols that require the client's executable to be on the allowlist.
+    pub protected: Vec<PrivilegedProtocol>,
+    /// Whether clients not on the allowlist are prompted for
+    /// the first time they try to capture the screen.
+    pub prompt_screen_capture: bool,
+}
+
+impl Default for PrivilegePolicy {
+    fn default() -> Self {
+        Self {
+            restrict_sandboxed: true,
+            allowlist: None,
+            protected: vec![
+                PrivilegedProtocol::ScreenCapture,
+                PrivilegedProtocol::DataControl,
+                PrivilegedProtocol::ForeignToplevelManagement,
+                PrivilegedProtocol::OutputManagement,
+                PrivilegedProtocol::GammaControl,
+                PrivilegedProtocol::VirtualInput,
+            ],
+            prompt_screen_capture: false,
+        }
+    }
+}
+
+impl PrivilegePolicy {
+    /// Returns whether a client may see the global for `protocol`.
+    ///
+    /// When screen capture prompting is enabled, screen capture globals stay visible
+    /// and each capture is checked with [`Pinnacle::screen_capture_allowed`] instead.
+    pub fn allows(
+        &self,
+        protocol: PrivilegedProtocol,
+        is_restricted: bool,
+        identity: Option<&ClientIdentity>,
+    ) -> bool {
+        if is_restricted
+            && (self.restrict_sandboxed || protocol == PrivilegedProtocol::SecurityContext)
+        {
+            return false;
+        }
+
+        if !self.protected.contains(&protocol) {
+            return true;
+        }
+
+        if protocol == PrivilegedProtocol::ScreenCapture && self.prompt_screen_capture {
+            return true;
+        }
+
+        self.is_trusted(identity)
+    }
+
+    /// Returns whether the client is on the allowlist or is the compositor itself.
+    fn is_trusted(&self, identity: Option<&ClientIdentity>) -> bool {
+        let Some(allowlist) = self.allowlist.as_ref() else {
+            return true;
+        };
+
+        let Some(identity) = identity else {
+            return false;
+        };
+
+        // Snowcap and other in-process clients
+        if identity.pid == Some(std::process::id() as i32) {
+            return true;
+        }
+
+        identity
+            .executable
+            .as_ref()
+            .is_some_and(|exe| allowlist.contains(exe))
+    }
+}
+
+/// Who a client is, determined when it connects.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientIdentity {
+    pub pid: Option<i32>,
+    /// The path to the client's executable.
+    pub executable: Option<PathBuf>,
+    /// The sandbox engine from the client's security context, if any.
+    pub sandbox_engine: Option<String>,
+    /// The app id from the client's security context, if any.
+    pub app_id: Option<String>,
+}
+
+impl ClientIdentity {
+    /// Gets the identity of a client from its peer credentials and security context.
+    pub fn new(client: &Client, dh: &DisplayHandle, context: Option<&SecurityContext>) -> Self {
+        let pid = client.get_credentials(dh).ok().map(|creds| creds.pid);
+        let executable = pid.and_then(|pid| std::fs::read_link(format!("/proc/{pid}/exe")).ok());
+
+        Self {
+            pid,
+            executable,
+            sandbox_engine: context.and_then(|ctx| ctx.sandbox_engine.clone()),
+            app_id: context.and_then(|ctx| ctx.app_id.clone()),
+        }
+    }
+}
+
+/// How long a screen capture request may go unanswered before the config is asked again.
+const CAPTURE_PROMPT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A decision on whether an executable may capture the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureDecision {
+    Allowed,
+    Denied,
+    /// The config was asked at this time but has not responded yet.
+    Pending(Instant),
+}
+
+#[derive(Debug, Default)]
+pub struct PrivilegeState {
+    /// The current policy, shared with global filters.
+    pub policy: Arc<RwLock<PrivilegePolicy>>,
+    /// Decisions from screen capture prompts.
+    pub capture_decisions: HashMap<PathBuf, CaptureDecision>,
+}
+
+impl PrivilegeState {
+    /// Creates a global filter for `protocol` that follows the current policy.
+    pub fn filter(
+        &self,
+        protocol: PrivilegedProtocol,
+    ) -> impl Fn(&Client) -> bool + Send + Sync + 'static {
+        let policy = self.policy.clone();
+        move |client| {
+            if client.get_data::<XWaylandClientData>().is_some() {
+                return true;
+            }
+            let Some(state) = client.get_data::<ClientState>() else {
+                panic!("Unknown client data type");
+            };
+
+            policy
+                .read()
+                .expect("privilege policy lock poisoned")
+                .allows(protocol, state.is_restricted, state.identity.get())
+        }
+    }
+
+    /// Replaces the policy, forgetting previous capture decisions if it changed.
+    pub fn set_policy(&mut self, policy: PrivilegePolicy) {
+        let mut current = self.policy.write().expect("privilege policy lock poisoned");
+        if *current != policy {
+            debug!(?policy, "Privilege policy changed");
+            *current = policy;
+            self.capture_decisions.clear();
+        }
+    }
+
+    /// Forgets screen capture requests the config hasn't answered.
+    ///
+    /// This should be called when the config restarts, as the new config never saw them.
+    pub fn clear_pending_capture_decisions(&mut self) {
+        self.capture_decisions
+            .retain(|_, decision| !matches!(decision, CaptureDecision::Pending(_)));
+    }
+
+    /// Returns whether `executable` may capture the screen, or `None` if the config
+    /// should be asked.
+    ///
+    /// Requests left unanswered for too long are asked again.
+    fn capture_allowed(&self, executable: &Path, now: Instant) -> Option<bool> {
+        match self.capture_decisions.get(executable)? {
+            CaptureDecision::Allowed => Some(true),
+            CaptureDecision::Denied => Some(false),
+            CaptureDecision::Pending(asked) => {
+                (now.duration_since(*asked) < CAPTURE_PROMPT_TIMEOUT).then_some(false)
+            }
+        }
+    }
+}
+
+impl Pinnacle {
+    /// Returns whether `client` may capture the screen right now.
+    ///
+    /// If prompting is enabled and the client's executable is unknown, this asks the config
+    /// through the `ScreenCaptureRequested` signal and denies the capture until it responds.
+    pub fn screen_capture_allowed(&mut self, client: &Client) -> bool {
+        let Some(state) = client.get_data::<ClientState>() else {
+            return true;
+        };
+        let identity = state.identity.get();
+
+        let policy = self
+            .privilege_state
+            .policy
+            .read()
+            .expect("privilege policy lock poisoned");
+
+        if !policy.allows(
+            PrivilegedProtocol::ScreenCapture,
+            state.is_restricted,
+            identity,
+        ) {
+            return false;
+        }
+
+        if !policy
+            .protected
+            .contains(&PrivilegedProtocol::ScreenCapture)
+            || !policy.prompt_screen_capture
+            || policy.is_trusted(identity)
+        {
+            return true;
+        }
+
+        drop(policy);
+
+        let Some((pid, executable)) =
+            identity.and_then(|id| Some((id.pid?, id.executable.clone()?)))
+        else {
+            return false;
+        };
+
+        let now = Instant::now();
+
+        match self.privilege_state.capture_allowed(&executable, now) {
+            Some(allowed) => allowed,
+            None => {
+                info!(
+                    "{} requested to capture the screen, asking for permission",
+                    executable.display()
+                );
+                self.signal_state
+                    .screen_capture_requested
+                    .signal((&executable, pid as u32));
+                self.privilege_state
+                    .capture_decisions
+                    .insert(executable, CaptureDecision::Pending(now));
+                false
+            }
+        }
+    }
+
+    /// Records whether `executable` may capture the screen.
+    pub fn set_screen_capture_permission(&mut self, executable: PathBuf, allow: bool) {
+        let decision = if allow {
+            CaptureDecision::Allowed
+        } else {
+            CaptureDecision::Denied
+        };
+        self.privilege_state
+            .capture_decisions
+            .insert(executable, decision);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(exe: &str) -> ClientIdentity {
+        ClientIdentity {
+            pid: Some(1),
+            executable: Some(exe.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn default_policy_only_restricts_sandboxed_clients() {
+        let policy = PrivilegePolicy::default();
+
+        assert!(policy.allows(PrivilegedProtocol::ScreenCapture, false, None));
+        assert!(!policy.allows(PrivilegedProtocol::ScreenCapture, true, None));
+        assert!(!policy.allows(PrivilegedProtocol::LayerShell, true, None));
+    }
+
+    #[test]
+    fn allowlist_gates_protected_protocols() {
+        let policy = PrivilegePolicy {
+            allowlist: Some(vec!["/usr/bin/grim".into()]),
+            ..Default::default()
+        };
+
+        let grim = identity("/usr/bin/grim");
+        let other = identity("/usr/bin/other");
+
+        assert!(policy.allows(PrivilegedProtocol::ScreenCapture, false, Some(&grim)));
+        assert!(!policy.allows(PrivilegedProtocol::ScreenCapture, false, Some(&other)));
+        assert!(!policy.allows(PrivilegedProtocol::DataControl, false, None));
+        // Not protected by default
+        assert!(policy.allows(PrivilegedProtocol::LayerShell, false, Some(&other)));
+    }
+
+    #[test]
+    fn unrestricted_sandboxed_clients_cannot_nest_security_contexts() {
+        let policy = PrivilegePolicy {
+            restrict_sandboxed: false,
+            ..Default::default()
+        };
+
+        assert!(policy.allows(PrivilegedProtocol::LayerShell, true, None));
+        assert!(!policy.allows(PrivilegedProtocol::SecurityContext, true, None));
+    }
+
+    #[test]
+    fn prompting_keeps_screen_capture_visible() {
+        let policy = PrivilegePolicy {
+            allowlist: Some(vec![]),
+            prompt_screen_capture: true,
+            ..Default::default()
+        };
+
+        let other = identity("/usr/bin/other");
+
+        assert!(policy.allows(PrivilegedProtocol::ScreenCapture, false, Some(&other)));
+        assert!(!policy.allows(PrivilegedProtocol::GammaControl, false, Some(&other)));
+    }
+
+    #[test]
+    fn unanswered_capture_requests_are_asked_again() {
+        let mut state = PrivilegeState::default();
+        let exe = Path::new("/usr/bin/grim");
+        let asked = Instant::now();
+
+        state
+            .capture_decisions
+            .insert(exe.into(), CaptureDecision::Pending(asked));
+
+        assert_eq!(state.capture_allowed(exe, asked), Some(false));
+        assert_eq!(
+            state.capture_allowed(exe, asked + CAPTURE_PROMPT_TIMEOUT),
+            None
+        );
+
+        state.clear_pending_capture_decisions();
+        assert_eq!(state.capture_allowed(exe, asked), None);
+
+        state
+            .capture_decisions
+            .insert(exe.into(), CaptureDecision::Denied);
+        state.clear_pending_capture_decisions();
+        assert_eq!(
+            state.capture_allowed(exe, asked + CAPTURE_PROMPT_TIMEOUT),
+            Some(false)
+        );
+    }
+}
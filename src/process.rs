@@ -1,18 +1,35 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     ffi::OsString,
     os::fd::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd},
+    path::PathBuf,
     process::Stdio,
     sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
 };
 
+use indexmap::IndexSet;
 use passfd::FdPassingExt;
+use smithay::reexports::{
+    calloop::{
+        self, RegistrationToken,
+        timer::{TimeoutAction, Timer},
+    },
+    rustix::process::{Pid, Signal, kill_process},
+};
 use sysinfo::{ProcessRefreshKind, ProcessesToUpdate};
 use tokio::sync::oneshot;
-use tracing::warn;
+use tracing::{info, warn};
 use xdg::BaseDirectories;
 
-use crate::util::restore_nofile_rlimit;
+use crate::{
+    api::signal::Signal as _,
+    output::OutputName,
+    state::{Pinnacle, WithState},
+    tag::TagId,
+    util::restore_nofile_rlimit,
+    window::WindowElement,
+};
 
 pub static REMOVE_RUST_BACKTRACE: AtomicBool = AtomicBool::new(false);
 pub static REMOVE_RUST_LIB_BACKTRACE: AtomicBool = AtomicBool::new(false);
@@ -27,24 +44,159 @@ pub struct ExitInfo {
     pub exit_msg: Option<String>,
 }
 
+/// The default delay before restarting a supervised process.
+const DEFAULT_RESTART_BACKOFF: Duration = Duration::from_secs(1);
+/// The maximum delay between restarts of a supervised process.
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(60);
+/// How long a supervised process needs to run before its restart count is reset.
+const STABLE_RUNTIME: Duration = Duration::from_secs(10);
+
 #[derive(Debug)]
 pub struct ProcessState {
     pub system_processes: sysinfo::System,
     // FIXME: If we reload the config then this doesn't get cleared
     spawned: HashMap<u32, tokio::sync::oneshot::Receiver<ExitInfo>>,
     spawned_already: HashSet<String>,
+    /// Supervised processes by name.
+    ///
+    /// These are intentionally kept across config reloads.
+    supervised: BTreeMap<String, SupervisedProcess>,
+    /// Where to place the first window of spawned processes, by pid.
+    spawn_targets: HashMap<u32, SpawnTarget>,
+    /// Notifies the event loop when a spawned process exits.
+    exit_sender: calloop::channel::Sender<(u32, ExitInfo)>,
 }
 
 impl ProcessState {
-    pub fn new(system: sysinfo::System) -> Self {
+    pub fn new(
+        system: sysinfo::System,
+        exit_sender: calloop::channel::Sender<(u32, ExitInfo)>,
+    ) -> Self {
         Self {
             system_processes: system,
             spawned: Default::default(),
             spawned_already: Default::default(),
+            supervised: Default::default(),
+            spawn_targets: Default::default(),
+            exit_sender,
+        }
+    }
+
+    pub fn supervised(&self) -> impl Iterator<Item = &SupervisedProcess> {
+        self.supervised.values()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Never restart the process.
+    Never,
+    /// Restart the process if it exits unsuccessfully.
+    #[default]
+    OnFailure,
+    /// Always restart the process.
+    Always,
+}
+
+#[derive(Debug, Clone)]
+pub struct Supervision {
+    pub name: String,
+    pub restart_policy: RestartPolicy,
+    /// The delay before the first restart, doubled for every consecutive restart.
+    pub backoff: Duration,
+    /// The maximum number of consecutive restarts, or `None` for unlimited.
+    pub max_retries: Option<u32>,
+}
+
+impl Supervision {
+    pub fn new(
+        name: String,
+        restart_policy: RestartPolicy,
+        backoff: Option<Duration>,
+        max_retries: Option<u32>,
+    ) -> Self {
+        Self {
+            name,
+            restart_policy,
+            backoff: backoff.unwrap_or(DEFAULT_RESTART_BACKOFF),
+            max_retries,
         }
     }
 }
 
+#[derive(Debug)]
+pub struct SupervisedProcess {
+    pub supervision: Supervision,
+    pub command: ChildCommand,
+    target: Option<SpawnTarget>,
+    /// The pid of the process if it's running.
+    pub pid: Option<u32>,
+    /// The number of consecutive restarts.
+    pub restart_count: u32,
+    started_at: Instant,
+    restart_timer: Option<RegistrationToken>,
+    /// The process was stopped through the API and shouldn't be restarted.
+    stopped: bool,
+    /// The process was killed through the API to be restarted immediately.
+    restart_requested: bool,
+}
+
+impl SupervisedProcess {
+    /// Whether this process is running or waiting to be restarted.
+    fn is_alive(&self) -> bool {
+        self.pid.is_some() || self.restart_timer.is_some()
+    }
+
+    /// Determines how long to wait before restarting this process after it exited,
+    /// or `None` if it shouldn't be restarted.
+    fn next_restart_delay(&mut self, exit_info: &ExitInfo) -> Option<Duration> {
+        if self.stopped {
+            return None;
+        }
+
+        if std::mem::take(&mut self.restart_requested) {
+            self.restart_count = 0;
+            return Some(Duration::ZERO);
+        }
+
+        let should_restart = match self.supervision.restart_policy {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => exit_info.exit_code != Some(0),
+            RestartPolicy::Always => true,
+        };
+
+        if !should_restart {
+            return None;
+        }
+
+        if self.started_at.elapsed() >= STABLE_RUNTIME {
+            self.restart_count = 0;
+        }
+
+        if self
+            .supervision
+            .max_retries
+            .is_some_and(|max_retries| self.restart_count >= max_retries)
+        {
+            warn!(
+                name = self.supervision.name,
+                "Supervised process exceeded its maximum retries, not restarting"
+            );
+            return None;
+        }
+
+        let delay = self
+            .supervision
+            .backoff
+            .saturating_mul(2u32.saturating_pow(self.restart_count))
+            .min(MAX_RESTART_BACKOFF);
+
+        self.restart_count += 1;
+
+        Some(delay)
+    }
+}
+
 #[derive(Debug)]
 pub struct SpawnData {
     pub pid: u32,
@@ -60,24 +212,148 @@ pub struct WaitOutput {
     pub exit_msg: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy)]
 pub struct PipeProcesses {
     pub stdin: bool,
     pub stdout: bool,
     pub stderr: bool,
 }
 
+/// A command to spawn a child process with.
+#[derive(Debug, Clone, Default)]
+pub struct ChildCommand {
+    pub cmd: Vec<String>,
+    pub shell_cmd: Vec<String>,
+    pub envs: HashMap<String, String>,
+    pub cwd: Option<PathBuf>,
+    /// Whether to spawn the child in its own transient systemd scope.
+    pub systemd_scope: bool,
+}
+
+/// Where to place the first window mapped by a spawned process.
+#[derive(Debug, Clone)]
+pub enum SpawnTarget {
+    Tags(Vec<TagId>),
+    Output(OutputName),
+}
+
+fn spawn_child(
+    command: &ChildCommand,
+    pipe_processes: PipeProcesses,
+) -> Option<(tokio::process::Child, u32)> {
+    let arg0 = command.cmd.first()?;
+
+    let mut cmd = command.shell_cmd.iter().chain(command.cmd.iter());
+    let program = cmd.next()?;
+
+    let mut tokio_cmd = if command.systemd_scope {
+        // `systemd-run --scope` execs the command itself, so the pid stays the same
+        let mut tokio_cmd = tokio::process::Command::new("systemd-run");
+        tokio_cmd
+            .args(["--user", "--scope", "--quiet", "--collect", "--"])
+            .arg(program);
+        tokio_cmd
+    } else {
+        tokio::process::Command::new(OsString::from(program))
+    };
+
+    tokio_cmd.envs(command.envs.clone()).args(cmd);
+
+    if let Some(cwd) = command.cwd.as_ref() {
+        tokio_cmd.current_dir(cwd);
+    }
+
+    tokio_cmd
+        .stdin(match pipe_processes.stdin {
+            true => Stdio::piped(),
+            false => Stdio::null(),
+        })
+        .stdout(match pipe_processes.stdout {
+            true => Stdio::piped(),
+            false => Stdio::null(),
+        })
+        .stderr(match pipe_processes.stderr {
+            true => Stdio::piped(),
+            false => Stdio::null(),
+        });
+
+    if REMOVE_RUST_BACKTRACE.load(Ordering::Relaxed) {
+        tokio_cmd.env_remove("RUST_BACKTRACE");
+    }
+    if REMOVE_RUST_LIB_BACKTRACE.load(Ordering::Relaxed) {
+        tokio_cmd.env_remove("RUST_LIB_BACKTRACE");
+    }
+
+    unsafe {
+        tokio_cmd.pre_exec(|| {
+            restore_nofile_rlimit();
+            Ok(())
+        });
+    }
+
+    let child = match tokio_cmd.spawn() {
+        Ok(child) => child,
+        Err(err) if command.systemd_scope && err.kind() == std::io::ErrorKind::NotFound => {
+            warn!("systemd-run not found, spawning {arg0} without a systemd scope");
+            return spawn_child(
+                &ChildCommand {
+                    systemd_scope: false,
+                    ..command.clone()
+                },
+                pipe_processes,
+            );
+        }
+        Err(_) => {
+            warn!("Tried to run {arg0}, but it doesn't exist");
+            return None;
+        }
+    };
+
+    let pid = child.id().expect("child has not polled to completion");
+
+    Some((child, pid))
+}
+
+/// Returns the parent pid of the given process.
+pub fn parent_pid(pid: u32) -> Option<u32> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // The command name is in parentheses and may contain spaces,
+    // so skip past it before splitting.
+    let (_, rest) = stat.rsplit_once(')')?;
+    rest.split_whitespace().nth(1)?.parse().ok()
+}
+
+fn terminate(pid: u32) {
+    let Some(pid) = i32::try_from(pid).ok().and_then(Pid::from_raw) else {
+        return;
+    };
+
+    if let Err(err) = kill_process(pid, Signal::TERM) {
+        warn!("Failed to terminate process {pid:?}: {err}");
+    }
+}
+
 impl ProcessState {
     pub fn spawn(
         &mut self,
-        cmd: &[String],
-        shell_cmd: &[String],
+        command: ChildCommand,
         unique: bool,
         once: bool,
-        envs: HashMap<String, String>,
         base_dirs: &BaseDirectories,
         pipe_processes: PipeProcesses,
+        supervision: Option<Supervision>,
+        target: Option<SpawnTarget>,
     ) -> Option<SpawnData> {
-        let arg0 = cmd.first()?.to_string();
+        let arg0 = command.cmd.first()?.to_string();
+
+        if let Some(supervision) = supervision.as_ref()
+            && self
+                .supervised
+                .get(&supervision.name)
+                .is_some_and(|proc| proc.is_alive())
+        {
+            return None;
+        }
 
         if once && self.spawned_already.contains(&arg0) {
             return None;
@@ -104,47 +380,7 @@ impl ProcessState {
             }
         }
 
-        let mut cmd = shell_cmd.iter().chain(cmd.iter());
-        let program = cmd.next()?;
-
-        let mut tokio_cmd = tokio::process::Command::new(OsString::from(program));
-
-        tokio_cmd.envs(envs).args(cmd);
-
-        tokio_cmd
-            .stdin(match pipe_processes.stdin {
-                true => Stdio::piped(),
-                false => Stdio::null(),
-            })
-            .stdout(match pipe_processes.stdout {
-                true => Stdio::piped(),
-                false => Stdio::null(),
-            })
-            .stderr(match pipe_processes.stderr {
-                true => Stdio::piped(),
-                false => Stdio::null(),
-            });
-
-        if REMOVE_RUST_BACKTRACE.load(Ordering::Relaxed) {
-            tokio_cmd.env_remove("RUST_BACKTRACE");
-        }
-        if REMOVE_RUST_LIB_BACKTRACE.load(Ordering::Relaxed) {
-            tokio_cmd.env_remove("RUST_LIB_BACKTRACE");
-        }
-
-        unsafe {
-            tokio_cmd.pre_exec(|| {
-                restore_nofile_rlimit();
-                Ok(())
-            });
-        }
-
-        let Ok(mut child) = tokio_cmd.spawn() else {
-            warn!("Tried to run {arg0}, but it doesn't exist");
-            return None;
-        };
-
-        let pid = child.id().expect("child has not polled to completion");
+        let (mut child, pid) = spawn_child(&command, pipe_processes)?;
 
         let socket_dir = base_dirs
             .get_runtime_directory()
@@ -199,6 +435,45 @@ impl ProcessState {
 
         let (oneshot_send, oneshot_recv) = oneshot::channel();
 
+        self.watch_exit(child, pid, Some(oneshot_send));
+
+        self.spawned.insert(pid, oneshot_recv);
+        self.spawned_already.insert(arg0.clone());
+
+        if let Some(target) = target.clone() {
+            self.spawn_targets.insert(pid, target);
+        }
+
+        if let Some(supervision) = supervision {
+            self.supervised.insert(
+                supervision.name.clone(),
+                SupervisedProcess {
+                    supervision,
+                    command,
+                    target,
+                    pid: Some(pid),
+                    restart_count: 0,
+                    started_at: Instant::now(),
+                    restart_timer: None,
+                    stopped: false,
+                    restart_requested: false,
+                },
+            );
+        }
+
+        Some(data)
+    }
+
+    /// Waits on the child in the background, notifying the event loop
+    /// and optionally the config when it exits.
+    fn watch_exit(
+        &self,
+        mut child: tokio::process::Child,
+        pid: u32,
+        config_sender: Option<oneshot::Sender<ExitInfo>>,
+    ) {
+        let exit_sender = self.exit_sender.clone();
+
         tokio::spawn(async move {
             let exit_status = child.wait().await;
             let exit_info = exit_status
@@ -207,15 +482,72 @@ impl ProcessState {
                     exit_msg: Some(status.to_string()),
                 })
                 .unwrap_or_default();
-            if oneshot_send.send(exit_info).is_err() {
+            let _ = exit_sender.send((pid, exit_info.clone()));
+            if let Some(config_sender) = config_sender
+                && config_sender.send(exit_info).is_err()
+            {
                 warn!("Failed to send exit info to config");
             }
         });
+    }
 
-        self.spawned.insert(pid, oneshot_recv);
-        self.spawned_already.insert(arg0.clone());
+    /// Respawns the supervised process with the given name.
+    fn respawn_supervised(&mut self, name: &str) {
+        let Some(proc) = self.supervised.get_mut(name) else {
+            return;
+        };
 
-        Some(data)
+        proc.restart_timer = None;
+
+        let spawned = spawn_child(
+            &proc.command,
+            PipeProcesses {
+                stdin: false,
+                stdout: false,
+                stderr: false,
+            },
+        );
+
+        let Some((child, pid)) = spawned else {
+            warn!(name, "Failed to restart supervised process");
+            return;
+        };
+
+        info!(name, pid, "Restarted supervised process");
+
+        proc.pid = Some(pid);
+        proc.started_at = Instant::now();
+
+        if let Some(target) = proc.target.clone() {
+            self.spawn_targets.insert(pid, target);
+        }
+
+        self.watch_exit(child, pid, None);
+    }
+
+    /// Takes the spawn target for the given process or its closest spawned ancestor.
+    fn take_spawn_target(&mut self, pid: u32) -> Option<SpawnTarget> {
+        if self.spawn_targets.is_empty() {
+            return None;
+        }
+
+        let compositor_pid = std::process::id();
+        let mut pid = pid;
+
+        // Bound the walk in case of a reparenting loop
+        for _ in 0..64 {
+            if let Some(target) = self.spawn_targets.remove(&pid) {
+                return Some(target);
+            }
+
+            pid = parent_pid(pid)?;
+
+            if pid <= 1 || pid == compositor_pid {
+                return None;
+            }
+        }
+
+        None
     }
 
     pub fn wait_on_spawn(
@@ -233,3 +565,129 @@ impl ProcessState {
         Some(oneshot_rx)
     }
 }
+
+impl Pinnacle {
+    /// Places the window on the tags targeted by the process that spawned it, if any.
+    pub fn apply_spawn_target(&mut self, window: &WindowElement) {
+        let Some(pid) = window.pid(&self.display_handle) else {
+            return;
+        };
+
+        let Some(target) = self.process_state.take_spawn_target(pid) else {
+            return;
+        };
+
+        match target {
+            SpawnTarget::Tags(tag_ids) => {
+                let tags = tag_ids
+                    .into_iter()
+                    .filter_map(|tag_id| tag_id.tag(self))
+                    .collect::<IndexSet<_>>();
+
+                if !tags.is_empty() {
+                    window.with_state_mut(|state| state.tags = tags);
+                }
+            }
+            SpawnTarget::Output(output_name) => {
+                if let Some(output) = output_name.output(self) {
+                    window.set_tags_to_output(&output);
+                }
+            }
+        }
+    }
+
+    pub fn on_process_exited(&mut self, pid: u32, exit_info: ExitInfo) {
+        self.process_state.spawn_targets.remove(&pid);
+
+        let Some(proc) = self
+            .process_state
+            .supervised
+            .values_mut()
+            .find(|proc| proc.pid == Some(pid))
+        else {
+            self.signal_state
+                .process_exited
+                .signal((pid, None, &exit_info, false));
+            return;
+        };
+
+        proc.pid = None;
+
+        let name = proc.supervision.name.clone();
+        let restart_delay = proc.next_restart_delay(&exit_info);
+
+        self.signal_state.process_exited.signal((
+            pid,
+            Some(&name),
+            &exit_info,
+            restart_delay.is_some(),
+        ));
+
+        if let Some(delay) = restart_delay {
+            self.schedule_supervised_restart(name, delay);
+        }
+    }
+
+    fn schedule_supervised_restart(&mut self, name: String, delay: Duration) {
+        let token = self
+            .loop_handle
+            .insert_source(Timer::from_duration(delay), {
+                let name = name.clone();
+                move |_, _, state| {
+                    state.pinnacle.process_state.respawn_supervised(&name);
+                    TimeoutAction::Drop
+                }
+            });
+
+        match token {
+            Ok(token) => {
+                if let Some(proc) = self.process_state.supervised.get_mut(&name) {
+                    proc.restart_timer = Some(token);
+                }
+            }
+            Err(err) => warn!(name, "Failed to schedule supervised process restart: {err}"),
+        }
+    }
+
+    /// Stops the supervised process with the given name without restarting it.
+    pub fn stop_supervised(&mut self, name: &str) {
+        let Some(proc) = self.process_state.supervised.get_mut(name) else {
+            warn!(name, "No supervised process with that name");
+            return;
+        };
+
+        proc.stopped = true;
+
+        if let Some(token) = proc.restart_timer.take() {
+            self.loop_handle.remove(token);
+        }
+
+        if let Some(pid) = proc.pid {
+            terminate(pid);
+        }
+    }
+
+    /// Restarts the supervised process with the given name, starting it if it isn't running.
+    pub fn restart_supervised(&mut self, name: &str) {
+        let Some(proc) = self.process_state.supervised.get_mut(name) else {
+            warn!(name, "No supervised process with that name");
+            return;
+        };
+
+        proc.stopped = false;
+
+        if let Some(pid) = proc.pid {
+            proc.restart_requested = true;
+            terminate(pid);
+            return;
+        }
+
+        proc.restart_count = 0;
+
+        if let Some(token) = proc.restart_timer.take() {
+            self.loop_handle.remove(token);
+        }
+
+        self.process_state.respawn_supervised(name);
+    }
+}
@@ -622,7 +622,6 @@ impl State {
             Backend::Winit(winit) => {
                 winit.schedule_render();
             }
-            #[cfg(feature = "testing")]
             Backend::Dummy(_) => (),
         }
     }
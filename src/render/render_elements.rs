@@ -195,7 +195,6 @@ macro_rules! pinnacle_render_elements {
             }
         }
 
-        #[cfg(feature = "testing")]
         impl ::smithay::backend::renderer::element::RenderElement<::smithay::backend::renderer::test::DummyRenderer>
             for $($name_generic<::smithay::backend::renderer::test::DummyRenderer>)? $($name_no_generic)?
         {
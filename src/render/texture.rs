@@ -1,4 +1,3 @@
-#[cfg(feature = "testing")]
 use smithay::backend::renderer::test::DummyRenderer;
 use smithay::{
     backend::renderer::{
@@ -118,7 +117,6 @@ impl<'a> RenderElement<UdevRenderer<'a>> for CommonTextureRenderElement {
     }
 }
 
-#[cfg(feature = "testing")]
 impl RenderElement<DummyRenderer> for CommonTextureRenderElement {
     fn draw(
         &self,
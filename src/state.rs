@@ -16,6 +16,7 @@ use crate::{
         xwayland::XwaylandState,
     },
     layout::LayoutState,
+    privilege::{ClientIdentity, PrivilegeState, PrivilegedProtocol},
     process::ProcessState,
     protocol::{
         drm::WlDrmState,
@@ -44,7 +45,7 @@ use smithay::{
     output::Output,
     reexports::{
         calloop::{
-            Interest, LoopHandle, LoopSignal, Mode, PostAction,
+            self, Interest, LoopHandle, LoopSignal, Mode, PostAction,
             generic::Generic,
             timer::{TimeoutAction, Timer},
         },
@@ -73,12 +74,13 @@ use smithay::{
             ImageCaptureSourceState, OutputCaptureSourceState, ToplevelCaptureSourceState,
         },
         image_copy_capture::{CursorSession, ImageCopyCaptureState, Session},
+        input_method::InputMethodManagerState,
         keyboard_shortcuts_inhibit::KeyboardShortcutsInhibitState,
         output::OutputManagerState,
         pointer_constraints::PointerConstraintsState,
         pointer_gestures::PointerGesturesState,
         relative_pointer::RelativePointerManagerState,
-        security_context::SecurityContextState,
+        security_context::{SecurityContext, SecurityContextState},
         selection::{
             data_device::DataDeviceState, ext_data_control,
             primary_selection::PrimarySelectionState, wlr_data_control,
@@ -93,19 +95,20 @@ use smithay::{
         single_pixel_buffer::SinglePixelBufferState,
         socket::ListeningSocketSource,
         tablet_manager::TabletManagerState,
+        text_input::TextInputManagerState,
         viewporter::ViewporterState,
+        virtual_keyboard::VirtualKeyboardManagerState,
         xdg_activation::XdgActivationState,
         xwayland_keyboard_grab::XWaylandKeyboardGrabState,
         xwayland_shell::XWaylandShellState,
     },
-    xwayland::XWaylandClientData,
 };
 use std::{
     cell::RefCell,
     collections::{HashMap, HashSet},
     ffi::OsString,
     path::PathBuf,
-    sync::Arc,
+    sync::{Arc, OnceLock},
     time::Duration,
 };
 use sysinfo::{ProcessRefreshKind, RefreshKind};
@@ -114,7 +117,6 @@ use xdg::BaseDirectories;
 
 use crate::input::InputState;
 
-#[cfg(feature = "testing")]
 use crate::backend::dummy::Dummy;
 
 // We'll try to send frame callbacks at least once a second. We'll make a timer that fires once a
@@ -170,6 +172,9 @@ pub struct Pinnacle {
     pub tablet_manager_state: TabletManagerState,
     pub keyboard_shortcuts_inhibit_state: KeyboardShortcutsInhibitState,
     pub xwayland_keyboard_grab_state: XWaylandKeyboardGrabState,
+    pub text_input_manager_state: TextInputManagerState,
+    pub input_method_manager_state: InputMethodManagerState,
+    pub virtual_keyboard_manager_state: VirtualKeyboardManagerState,
     pub xdg_activation_state: XdgActivationState,
     pub xdg_decoration_state: XdgDecorationState,
     pub kde_decoration_state: KdeDecorationState,
@@ -247,6 +252,8 @@ pub struct Pinnacle {
 
     pub capture_sessions: Vec<Session>,
     pub cursor_capture_sessions: Vec<CursorSession>,
+
+    pub privilege_state: PrivilegeState,
 }
 
 #[cfg(feature = "snowcap")]
@@ -312,17 +319,6 @@ impl State {
     }
 }
 
-/// Filters clients that are restricted by the security context
-fn filter_restricted_client(client: &Client) -> bool {
-    if let Some(state) = client.get_data::<ClientState>() {
-        return !state.is_restricted;
-    }
-    if client.get_data::<XWaylandClientData>().is_some() {
-        return true;
-    }
-    panic!("Unknown client data type");
-}
-
 impl Pinnacle {
     pub fn new(
         display: Display<State>,
@@ -340,11 +336,11 @@ impl Pinnacle {
             let socket_name = socket.socket_name().to_os_string();
 
             loop_handle.insert_source(socket, |stream, _metadata, state| {
-                state
-                    .pinnacle
-                    .display_handle
+                let dh = state.pinnacle.display_handle.clone();
+                let client = dh
                     .insert_client(stream, Arc::new(ClientState::default()))
                     .expect("Could not insert client into loop handle");
+                ClientState::init_identity(&client, &dh, None);
             })?;
             socket_name
         } else {
@@ -376,15 +372,17 @@ impl Pinnacle {
 
         let primary_selection_state = PrimarySelectionState::new::<State>(&display_handle);
 
+        let privilege_state = PrivilegeState::default();
+
         let wlr_data_control_state = wlr_data_control::DataControlState::new::<State, _>(
             &display_handle,
             Some(&primary_selection_state),
-            filter_restricted_client,
+            privilege_state.filter(PrivilegedProtocol::DataControl),
         );
         let ext_data_control_state = ext_data_control::DataControlState::new::<State, _>(
             &display_handle,
             Some(&primary_selection_state),
-            filter_restricted_client,
+            privilege_state.filter(PrivilegedProtocol::DataControl),
         );
 
         loop_handle
@@ -401,6 +399,16 @@ impl Pinnacle {
                 anyhow::anyhow!("failed to insert xdg activation token cleanup source: {err}")
             })?;
 
+        let (process_exit_tx, process_exit_rx) = calloop::channel::channel();
+
+        loop_handle
+            .insert_source(process_exit_rx, |event, _, state| {
+                if let calloop::channel::Event::Msg((pid, exit_info)) = event {
+                    state.pinnacle.on_process_exited(pid, exit_info);
+                }
+            })
+            .map_err(|err| anyhow::anyhow!("failed to insert process exit source: {err}"))?;
+
         let (blocker_cleared_tx, blocker_cleared_rx) = std::sync::mpsc::channel();
 
         let pinnacle = Pinnacle {
@@ -428,21 +436,21 @@ impl Pinnacle {
             primary_selection_state,
             layer_shell_state: WlrLayerShellState::new_with_filter::<State, _>(
                 &display_handle,
-                filter_restricted_client,
+                privilege_state.filter(PrivilegedProtocol::LayerShell),
             ),
             wlr_data_control_state,
             ext_data_control_state,
             screencopy_manager_state: ScreencopyManagerState::new::<State, _>(
                 &display_handle,
-                filter_restricted_client,
+                privilege_state.filter(PrivilegedProtocol::ScreenCapture),
             ),
             gamma_control_manager_state: GammaControlManagerState::new::<State, _>(
                 &display_handle,
-                filter_restricted_client,
+                privilege_state.filter(PrivilegedProtocol::GammaControl),
             ),
             security_context_state: SecurityContextState::new::<State, _>(
                 &display_handle,
-                filter_restricted_client,
+                privilege_state.filter(PrivilegedProtocol::SecurityContext),
             ),
             relative_pointer_manager_state: RelativePointerManagerState::new::<State>(
                 &display_handle,
@@ -450,28 +458,37 @@ impl Pinnacle {
             pointer_constraints_state: PointerConstraintsState::new::<State>(&display_handle),
             foreign_toplevel_manager_state: ForeignToplevelManagerState::new::<State, _>(
                 &display_handle,
-                filter_restricted_client,
+                privilege_state.filter(PrivilegedProtocol::ForeignToplevelManagement),
             ),
             session_lock_manager_state: SessionLockManagerState::new::<State, _>(
                 &display_handle,
-                filter_restricted_client,
+                privilege_state.filter(PrivilegedProtocol::SessionLock),
             ),
             xwayland_shell_state: XWaylandShellState::new::<State>(&display_handle),
             idle_notifier_state: IdleNotifierState::new(&display_handle, loop_handle),
             idle_inhibit_manager_state: IdleInhibitManagerState::new::<State>(&display_handle),
             output_management_manager_state: OutputManagementManagerState::new::<State, _>(
                 &display_handle,
-                filter_restricted_client,
+                privilege_state.filter(PrivilegedProtocol::OutputManagement),
             ),
             output_power_management_state: OutputPowerManagementState::new::<State, _>(
                 &display_handle,
-                filter_restricted_client,
+                privilege_state.filter(PrivilegedProtocol::OutputPowerManagement),
             ),
             tablet_manager_state: TabletManagerState::new::<State>(&display_handle),
             keyboard_shortcuts_inhibit_state: KeyboardShortcutsInhibitState::new::<State>(
                 &display_handle,
             ),
             xwayland_keyboard_grab_state: XWaylandKeyboardGrabState::new::<State>(&display_handle),
+            text_input_manager_state: TextInputManagerState::new::<State>(&display_handle),
+            input_method_manager_state: InputMethodManagerState::new::<State, _>(
+                &display_handle,
+                privilege_state.filter(PrivilegedProtocol::InputMethod),
+            ),
+            virtual_keyboard_manager_state: VirtualKeyboardManagerState::new::<State, _>(
+                &display_handle,
+                privilege_state.filter(PrivilegedProtocol::VirtualInput),
+            ),
             xdg_activation_state: XdgActivationState::new::<State>(&display_handle),
             xdg_decoration_state: XdgDecorationState::new::<State>(&display_handle),
             kde_decoration_state: KdeDecorationState::new::<State>(
@@ -483,16 +500,23 @@ impl Pinnacle {
             foreign_toplevel_list_state: ForeignToplevelListState::new::<State>(&display_handle),
             ext_workspace_state: ExtWorkspaceManagerState::new::<State, _>(
                 &display_handle,
-                filter_restricted_client,
+                privilege_state.filter(PrivilegedProtocol::Workspace),
             ),
             snowcap_decoration_state: SnowcapDecorationState::new::<State>(&display_handle),
             wl_drm_state: WlDrmState,
             image_capture_source_state: ImageCaptureSourceState::new(),
-            output_capture_source_state: OutputCaptureSourceState::new::<State>(&display_handle),
-            toplevel_capture_source_state: ToplevelCaptureSourceState::new::<State>(
+            output_capture_source_state: OutputCaptureSourceState::new_with_filter::<State, _>(
+                &display_handle,
+                privilege_state.filter(PrivilegedProtocol::ScreenCapture),
+            ),
+            toplevel_capture_source_state: ToplevelCaptureSourceState::new_with_filter::<State, _>(
+                &display_handle,
+                privilege_state.filter(PrivilegedProtocol::ScreenCapture),
+            ),
+            image_copy_capture_state: ImageCopyCaptureState::new_with_filter::<State, _>(
                 &display_handle,
+                privilege_state.filter(PrivilegedProtocol::ScreenCapture),
             ),
-            image_copy_capture_state: ImageCopyCaptureState::new::<State>(&display_handle),
             content_type_state: ContentTypeState::new::<State>(&display_handle),
 
             lock_state: LockState::default(),
@@ -516,9 +540,12 @@ impl Pinnacle {
 
             xwayland_state: None,
 
-            process_state: ProcessState::new(sysinfo::System::new_with_specifics(
-                RefreshKind::nothing().with_processes(ProcessRefreshKind::nothing()),
-            )),
+            process_state: ProcessState::new(
+                sysinfo::System::new_with_specifics(
+                    RefreshKind::nothing().with_processes(ProcessRefreshKind::nothing()),
+                ),
+                process_exit_tx,
+            ),
 
             grpc_server_join_handle: None,
 
@@ -554,6 +581,8 @@ impl Pinnacle {
 
             capture_sessions: Default::default(),
             cursor_capture_sessions: Default::default(),
+
+            privilege_state,
         };
 
         Ok(pinnacle)
@@ -1001,7 +1030,6 @@ impl State {
                 let udev = (uninit_udev.init)(&mut pinnacle)?;
                 (backend::Backend::Udev(udev), pinnacle)
             }
-            #[cfg(feature = "testing")]
             cli::Backend::Dummy => {
                 let uninit_dummy = Dummy::try_new();
                 let mut pinnacle = Pinnacle::new(
@@ -1025,8 +1053,19 @@ impl State {
 #[derive(Default)]
 pub struct ClientState {
     pub compositor_state: CompositorClientState,
-    /// True, if the client may NOT access restricted protocols
+    /// True, if the client connected through a security context
     pub is_restricted: bool,
+    /// Who the client is, set right after it connects
+    pub identity: OnceLock<ClientIdentity>,
+}
+
+impl ClientState {
+    /// Records the identity of a newly inserted client.
+    pub fn init_identity(client: &Client, dh: &DisplayHandle, context: Option<&SecurityContext>) {
+        if let Some(state) = client.get_data::<ClientState>() {
+            let _ = state.identity.set(ClientIdentity::new(client, dh, context));
+        }
+    }
 }
 
 impl ClientData for ClientState {
@@ -36,6 +36,18 @@ impl TagId {
         Self(TAG_ID_COUNTER.fetch_add(1, Ordering::Relaxed))
     }
 
+    /// Returns the id the next created tag will get.
+    pub fn peek_next() -> Self {
+        Self(TAG_ID_COUNTER.load(Ordering::Relaxed))
+    }
+
+    /// Sets the id the next created tag will get.
+    ///
+    /// This is used when replaying a trace to give tags their recorded ids.
+    pub fn set_next(id: Self) {
+        TAG_ID_COUNTER.store(id.0, Ordering::Relaxed);
+    }
+
     /// Get the tag associated with this id.
     pub fn tag(&self, pinnacle: &Pinnacle) -> Option<Tag> {
         let _span = tracy_client::span!("TagId::tag");
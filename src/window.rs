@@ -22,7 +22,7 @@ use smithay::{
                 xdg_toplevel,
             },
         },
-        wayland_server::protocol::wl_surface::WlSurface,
+        wayland_server::{DisplayHandle, Resource, protocol::wl_surface::WlSurface},
     },
     utils::{IsAlive, Logical, Point, Rectangle, Serial, Size},
     wayland::{
@@ -165,6 +165,19 @@ impl WindowElement {
         });
     }
 
+    /// Returns the pid of the client that owns this window, if known.
+    pub fn pid(&self, display_handle: &DisplayHandle) -> Option<u32> {
+        match self.underlying_surface() {
+            WindowSurface::Wayland(toplevel) => toplevel
+                .wl_surface()
+                .client()?
+                .get_credentials(display_handle)
+                .ok()
+                .and_then(|creds| u32::try_from(creds.pid).ok()),
+            WindowSurface::X11(surface) => surface.pid(),
+        }
+    }
+
     /// Takes and returns the most recent transaction that has been committed.
     pub fn take_pending_transaction(&self, commit_serial: Serial) -> Option<Transaction> {
         let mut ret = None;
@@ -6,7 +6,7 @@ use smithay::{
 };
 
 use crate::{
-    api::Sender,
+    api::{Sender, connection::ApiClientId},
     state::{Pinnacle, WithState},
     tag::Tag,
 };
@@ -27,10 +27,19 @@ use std::{
 #[derive(Debug, Default)]
 pub struct WindowRuleState {
     pub pending_windows: HashMap<WindowElement, PendingWindowRuleRequest>,
-    pub senders: Vec<(Sender<WindowRuleRequest>, Arc<AtomicU32>)>,
+    pub senders: Vec<WindowRuleSender>,
     current_request_id: u32,
 }
 
+/// A client's window rule stream.
+#[derive(Debug)]
+pub struct WindowRuleSender {
+    sender: Sender<WindowRuleRequest>,
+    /// The id of the last request this client finished.
+    finished_id: Arc<AtomicU32>,
+    owner: Option<ApiClientId>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct WindowRules {
     pub layout_mode: Option<LayoutMode>,
@@ -69,8 +78,9 @@ impl WindowRuleState {
         self.current_request_id += 1;
 
         let mut waiting_on = Vec::new();
-        self.senders.retain(|(sender, id)| {
+        self.senders.retain(|sender| {
             let sent = sender
+                .sender
                 .send_blocking(WindowRuleRequest {
                     request_id,
                     window_id: window.with_state(|state| state.id),
@@ -78,7 +88,7 @@ impl WindowRuleState {
                 .is_ok();
 
             if sent {
-                waiting_on.push(id.clone());
+                waiting_on.push(sender.finished_id.clone());
             }
 
             sent
@@ -98,8 +108,33 @@ impl WindowRuleState {
         true
     }
 
-    pub fn new_sender(&mut self, sender: Sender<WindowRuleRequest>, id_ctr: Arc<AtomicU32>) {
-        self.senders.push((sender, id_ctr));
+    pub fn new_sender(
+        &mut self,
+        sender: Sender<WindowRuleRequest>,
+        finished_id: Arc<AtomicU32>,
+        owner: Option<ApiClientId>,
+    ) {
+        self.senders.push(WindowRuleSender {
+            sender,
+            finished_id,
+            owner,
+        });
+    }
+
+    /// Removes window rule streams owned by `client_id`.
+    ///
+    /// Windows waiting on them will no longer wait. Returns whether any were removed.
+    pub fn remove_client(&mut self, client_id: ApiClientId) -> bool {
+        let mut removed = false;
+        self.senders.retain(|sender| {
+            if sender.owner != Some(client_id) {
+                return true;
+            }
+            sender.finished_id.store(u32::MAX, Ordering::Release);
+            removed = true;
+            false
+        });
+        removed
     }
 
     pub fn finished_windows(&mut self) -> Vec<WindowElement> {
@@ -222,6 +257,23 @@ impl Pinnacle {
         };
     }
 
+    /// Applies window rules to and configures windows that are no longer waiting on any clients.
+    pub fn apply_finished_window_rules(&mut self) {
+        for win in self.window_rule_state.finished_windows() {
+            let Some(unmapped_idx) = self
+                .unmapped_windows
+                .iter()
+                .position(|unmapped| unmapped.window == win)
+            else {
+                continue;
+            };
+
+            let mut unmapped = self.unmapped_windows.swap_remove(unmapped_idx);
+            self.apply_window_rules_and_send_initial_configure(&mut unmapped);
+            self.unmapped_windows.push(unmapped);
+        }
+    }
+
     /// Request window rules from the config.
     ///
     /// If there are no window rules set, immediately sends the initial configure for toplevels
@@ -236,6 +288,11 @@ impl Pinnacle {
             client_requests: client_requests.clone(),
         };
 
+        // Window rules from the config take precedence, so apply spawn targets
+        // and static rules first
+        self.apply_spawn_target(&unmapped.window);
+        self.apply_static_window_rules(unmapped);
+
         let window_rule_request_sent = self.window_rule_state.new_request(&unmapped.window);
 
         // If the above is false, then there are either
@@ -5,6 +5,7 @@ use std::sync::atomic::{AtomicU32, Ordering};
 use indexmap::IndexSet;
 use smithay::{
     desktop::{WindowSurface, layer_map_for_output},
+    input::keyboard::Layout,
     reexports::wayland_protocols::xdg::{
         decoration::zv1::server::zxdg_toplevel_decoration_v1, shell::server::xdg_toplevel,
     },
@@ -41,6 +42,18 @@ impl WindowId {
         WINDOW_ID_COUNTER.store(0, Ordering::Relaxed);
     }
 
+    /// Returns the id the next created window will get.
+    pub fn peek_next() -> Self {
+        Self(WINDOW_ID_COUNTER.load(Ordering::Relaxed))
+    }
+
+    /// Sets the id the next created window will get.
+    ///
+    /// This is used when replaying a trace so new windows get their recorded ids.
+    pub fn set_next(id: Self) {
+        WINDOW_ID_COUNTER.store(id.0, Ordering::Relaxed);
+    }
+
     /// Gets the mapped window that has this WindowId.
     pub fn window(&self, pinnacle: &Pinnacle) -> Option<WindowElement> {
         let _span = tracy_client::span!("WindowId::window");
@@ -67,7 +80,8 @@ impl WindowId {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum LayoutModeKind {
     Tiled,
     Floating,
@@ -401,6 +415,11 @@ pub struct WindowElementState {
     pub decoration_surfaces: Vec<DecorationSurface>,
 
     pub vrr_demand: Option<VrrDemand>,
+
+    /// The xkb layout that was active when this window last lost keyboard focus.
+    ///
+    /// Only used when per-window layouts are enabled.
+    pub xkb_layout: Option<Layout>,
 }
 
 impl WindowElement {
@@ -671,6 +690,7 @@ impl WindowElementState {
             foreign_toplevel_list_handle: None,
             decoration_surfaces: Vec::new(),
             vrr_demand: None,
+            xkb_layout: None,
         }
     }
 
@@ -1,3 +1,4 @@
+mod desktop_entry;
 mod input;
 mod output;
 mod pinnacle;
@@ -0,0 +1,203 @@
+//! Tests for the Lua port of the launcher's desktop entry handling.
+//!
+//! The Rust side is tested in `pinnacle_api::snowcap::desktop_entry`.
+
+use mlua::Lua;
+
+const PRELUDE: &str = r#"
+    function assert_list(actual, expected)
+        local message = "expected {" .. table.concat(expected, ", ")
+            .. "}, got {" .. table.concat(actual, ", ") .. "}"
+        assert(#actual == #expected, message)
+        for i = 1, #expected do
+            assert(actual[i] == expected[i], message)
+        end
+    end
+
+    PATH = "/usr/share/applications/app.desktop"
+
+    function exec(value)
+        return DesktopEntry.parse_exec(value, "App", "app-icon", PATH)
+    end
+
+    function entry(id, name)
+        return { id = id, name = name, keywords = {}, exec = { id }, terminal = false }
+    end
+"#;
+
+fn run(code: &str) {
+    let lua = Lua::new();
+
+    let desktop_entry: mlua::Table = lua
+        .load(include_str!("../../../api/lua/pinnacle/desktop_entry.lua"))
+        .set_name("desktop_entry.lua")
+        .eval()
+        .unwrap();
+    lua.globals().set("DesktopEntry", desktop_entry).unwrap();
+    lua.load(PRELUDE).exec().unwrap();
+
+    if let Err(err) = lua.load(code).exec() {
+        panic!("lua panicked: {err}");
+    }
+}
+
+#[test]
+fn field_codes_are_expanded_or_dropped() {
+    run(r#"
+        assert_list(exec("app %f"), { "app" })
+        assert_list(exec("app %U --new-window"), { "app", "--new-window" })
+        assert_list(exec("app --name=%c"), { "app", "--name=App" })
+        assert_list(exec("app %k"), { "app", PATH })
+        assert_list(exec("app 100%%"), { "app", "100%" })
+        assert_list(exec("app %i"), { "app", "--icon", "app-icon" })
+        assert_list(DesktopEntry.parse_exec("app %i", "App", nil, PATH), { "app" })
+        assert_list(exec("app a%zb"), { "app", "ab" })
+    "#);
+}
+
+#[test]
+fn quoted_arguments_are_unescaped() {
+    run(r#"
+        assert_list(
+            exec('"/opt/my app/app" --title "Hello world"'),
+            { "/opt/my app/app", "--title", "Hello world" }
+        )
+        assert_list(exec('app ""'), { "app", "" })
+        assert_list(exec("app   --flag\t"), { "app", "--flag" })
+        assert_list(
+            exec([[sh -c "echo \"a b\" \$HOME \\"]]),
+            { "sh", "-c", [[echo "a b" $HOME \]] }
+        )
+        assert_list(
+            exec(DesktopEntry.unescape([[sh -c "echo \\"hi\\""]])),
+            { "sh", "-c", [[echo "hi"]] }
+        )
+    "#);
+}
+
+#[test]
+fn values_and_lists_are_unescaped() {
+    run(r#"
+        assert(DesktopEntry.unescape([[a\sb\tc\nd\re]]) == "a b\tc\nd\re")
+        assert(DesktopEntry.unescape([[back\\slash]]) == [[back\slash]])
+        assert(DesktopEntry.unescape([[trailing\]]) == [[trailing\]])
+
+        assert_list(DesktopEntry.split_list("a;b;c;"), { "a", "b", "c" })
+        assert_list(DesktopEntry.split_list([[a\;b;c]]), { "a;b", "c" })
+        assert_list(DesktopEntry.split_list([[;;a\sb;;]]), { "a b" })
+        assert_list(DesktopEntry.split_list(""), {})
+    "#);
+}
+
+#[test]
+fn the_best_localized_value_is_kept() {
+    run(r#"
+        local contents = [[
+# A comment
+[Desktop Entry]
+Name[fr]=Bonjour
+Name[de]=Hallo
+Name=Hello
+Name[de_DE]=Guten Tag
+Comment = Says hello
+Comment[de]=Sagt Hallo
+
+[Desktop Action new]
+Name=New
+]]
+
+        local groups = DesktopEntry.parse_groups(contents, { de_DE = 1, de = 2 }, 3)
+        assert(groups["Desktop Entry"].Name.value == "Guten Tag")
+        assert(groups["Desktop Entry"].Comment.value == "Sagt Hallo")
+        assert(groups["Desktop Action new"].Name.value == "New")
+
+        groups = DesktopEntry.parse_groups(contents, {}, 1)
+        assert(groups["Desktop Entry"].Name.value == "Hello")
+        assert(groups["Desktop Entry"].Comment.value == "Says hello")
+    "#);
+}
+
+#[test]
+fn desktop_actions_become_entries() {
+    run(r#"
+        local contents = [[
+[Desktop Entry]
+Type=Application
+Name=Browser
+Exec=browser %u
+Keywords=web;internet;
+Actions=private;missing;
+
+[Desktop Action private]
+Name=Private Window
+Exec=browser --private %u
+]]
+
+        local entries = {}
+        local groups = DesktopEntry.parse_groups(contents, {}, 1)
+        DesktopEntry.add_launch_entries("browser.desktop", PATH, groups, {}, entries)
+
+        assert(#entries == 2)
+        assert(entries[1].id == "browser.desktop")
+        assert_list(entries[1].exec, { "browser" })
+        assert_list(entries[1].keywords, { "web", "internet" })
+        assert(entries[2].id == "browser.desktop:private")
+        assert(entries[2].name == "Browser: Private Window")
+        assert_list(entries[2].exec, { "browser", "--private" })
+
+        local function count(extra)
+            local groups = DesktopEntry.parse_groups(
+                "[Desktop Entry]\nType=Application\nName=App\nExec=app\n" .. extra,
+                {},
+                1
+            )
+            local entries = {}
+            DesktopEntry.add_launch_entries("app.desktop", PATH, groups, { gnome = true }, entries)
+            return #entries
+        end
+
+        assert(count("") == 1)
+        assert(count("NoDisplay=true") == 0)
+        assert(count("Hidden=true") == 0)
+        assert(count("OnlyShowIn=KDE;") == 0)
+        assert(count("OnlyShowIn=KDE;GNOME;") == 1)
+        assert(count("NotShowIn=GNOME;") == 0)
+    "#);
+}
+
+#[test]
+fn fuzzy_matches_prefer_word_starts_and_consecutive_characters() {
+    run(r#"
+        local score = DesktopEntry.fuzzy_score
+
+        assert(score("fox", "Firefox"))
+        assert(score("FIRE", "firefox"))
+        assert(score("fire fox", "Firefox"))
+        assert(not score("xf", "Firefox"))
+        assert(not score("foxes", "Firefox"))
+
+        assert(score("fm", "File Manager") > score("fm", "Fireman"))
+        assert(score("fm", "FileManager") > score("fm", "Fireman"))
+        assert(score("fire", "Firefox") > score("fire", "Fixture Editor"))
+        assert(score("term", "Terminal") > score("term", "Terminal Emulator Deluxe"))
+    "#);
+}
+
+#[test]
+fn entries_are_ranked_by_score_launches_and_name() {
+    run(r#"
+        local entries = { entry("manager", "Fireman"), entry("terminal", "Terminal"), entry("files", "File Manager") }
+        assert_list(DesktopEntry.rank(entries, {}, "fm"), { 3, 1 })
+        assert_list(DesktopEntry.rank(entries, {}, "  "), { 3, 1, 2 })
+
+        local by_keyword = entry("browser", "Browser")
+        by_keyword.keywords = { "internet" }
+        assert_list(DesktopEntry.rank({ by_keyword, entry("internet", "Internet") }, {}, "internet"), { 2, 1 })
+
+        entries = { entry("one", "Alpha One"), entry("two", "Alpha Two") }
+        assert_list(DesktopEntry.rank(entries, {}, "alpha"), { 1, 2 })
+        assert_list(DesktopEntry.rank(entries, { two = 3 }, "alpha"), { 2, 1 })
+        assert_list(DesktopEntry.rank(entries, { one = 1, two = 2 }, "alpha"), { 1, 2 })
+        assert_list(DesktopEntry.rank(entries, { two = 1 }, ""), { 2, 1 })
+    "#);
+}
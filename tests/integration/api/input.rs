@@ -1,4 +1,19 @@
-use pinnacle_api::input::Bind as _;
+use std::time::Duration;
+
+use pinnacle::{
+    input::keyboard::{self, DeviceKeyboardConfig},
+    state::WithState,
+    tag::Tag,
+};
+use pinnacle_api::{
+    input::Bind as _,
+    layout::{LayoutGenerator as _, generators::MasterStack},
+};
+use pinnacle_api_defs::pinnacle::input::v1::{
+    Bind, BindRequest, Keybind, bind, input_service_client::InputServiceClient,
+};
+use smithay::{input::keyboard::XkbConfig, utils::Rectangle};
+use tonic::transport::{Endpoint, Uri};
 
 use crate::{
     common::{Lang, fixture::Fixture, for_each_api},
@@ -114,6 +129,169 @@ fn input_switch_xkb_layout() {
     });
 }
 
+#[test_log::test]
+fn input_xkb_layout_per_window() {
+    for_each_api(|lang| {
+        let mut fixture = set_up();
+
+        let output = fixture.add_output(Rectangle::new((0, 0).into(), (1920, 1080).into()));
+        output.with_state_mut(|state| {
+            let tag = Tag::new("1".to_string());
+            tag.set_active(true);
+            state.add_tags([tag]);
+        });
+        fixture.pinnacle().focus_output(&output);
+
+        fixture.spawn_blocking(|| {
+            pinnacle_api::layout::manage(|args| pinnacle_api::layout::LayoutResponse {
+                root_node: MasterStack::default().layout(args.window_count),
+                tree_id: 0,
+            });
+        });
+
+        match lang {
+            Lang::Rust => fixture.spawn_blocking(|| {
+                pinnacle_api::input::set_xkb_config(
+                    pinnacle_api::input::XkbConfig::new().with_layout("us,fr"),
+                );
+                pinnacle_api::input::set_xkb_layout_per_window(true);
+            }),
+            Lang::Lua => spawn_lua_blocking! {
+                fixture,
+                Input.set_xkb_config({
+                    layout = "us,fr",
+                })
+                Input.set_xkb_layout_per_window(true)
+            },
+        }
+
+        let client_id = fixture.add_client();
+        fixture.spawn_windows(2, client_id);
+
+        let kb = fixture.pinnacle().seat.get_keyboard().unwrap();
+        let active_layout = |fixture: &mut Fixture| {
+            kb.with_xkb_state(fixture.state(), |ctx| {
+                let xkb = ctx.xkb().lock().unwrap();
+                xkb.active_layout().0
+            })
+        };
+
+        // The second window is focused, switch it to "fr"
+        match lang {
+            Lang::Rust => fixture.spawn_blocking(|| {
+                pinnacle_api::input::switch_xkb_layout(1);
+                assert_eq!(pinnacle_api::input::xkb_layout().name, "French");
+            }),
+            Lang::Lua => spawn_lua_blocking! {
+                fixture,
+                Input.switch_xkb_layout(1)
+                assert(Input.xkb_layout().name == "French")
+            },
+        }
+
+        // Focus the first window and switch it back to "us"
+        match lang {
+            Lang::Rust => fixture.spawn_blocking(|| {
+                pinnacle_api::window::get_all()
+                    .next()
+                    .unwrap()
+                    .set_focused(true);
+                pinnacle_api::input::switch_xkb_layout(0);
+            }),
+            Lang::Lua => spawn_lua_blocking! {
+                fixture,
+                Window.get_all()[1]:set_focused(true)
+                Input.switch_xkb_layout(0)
+            },
+        }
+
+        fixture.wait_client_configure(client_id);
+        fixture.flush();
+
+        assert_eq!(active_layout(&mut fixture), 0);
+
+        // Refocusing the second window should restore "fr"
+        match lang {
+            Lang::Rust => fixture.spawn_blocking(|| {
+                pinnacle_api::window::get_all()
+                    .nth(1)
+                    .unwrap()
+                    .set_focused(true);
+            }),
+            Lang::Lua => spawn_lua_blocking! {
+                fixture,
+                Window.get_all()[2]:set_focused(true)
+            },
+        }
+
+        fixture.wait_client_configure(client_id);
+        fixture.flush();
+
+        assert_eq!(active_layout(&mut fixture), 1);
+
+        // And refocusing the first window should restore "us"
+        match lang {
+            Lang::Rust => fixture.spawn_blocking(|| {
+                pinnacle_api::window::get_all()
+                    .next()
+                    .unwrap()
+                    .set_focused(true);
+            }),
+            Lang::Lua => spawn_lua_blocking! {
+                fixture,
+                Window.get_all()[1]:set_focused(true)
+            },
+        }
+
+        fixture.wait_client_configure(client_id);
+        fixture.flush();
+
+        assert_eq!(active_layout(&mut fixture), 0);
+    });
+}
+
+#[test_log::test]
+fn input_typing_on_two_keyboards_keeps_layout() {
+    let mut fixture = set_up();
+
+    fixture.spawn_blocking(|| {
+        pinnacle_api::input::set_xkb_config(
+            pinnacle_api::input::XkbConfig::new().with_layout("us,fr"),
+        );
+        pinnacle_api::input::switch_xkb_layout(1);
+    });
+
+    let kb = fixture.pinnacle().seat.get_keyboard().unwrap();
+    let active_layout = |fixture: &mut Fixture| {
+        kb.with_xkb_state(fixture.state(), |ctx| {
+            let xkb = ctx.xkb().lock().unwrap();
+            xkb.active_layout().0
+        })
+    };
+
+    let other_keyboard = DeviceKeyboardConfig {
+        keymap: keyboard::compile_keymap(&XkbConfig {
+            layout: "us,fr",
+            options: Some("caps:escape".to_string()),
+            ..Default::default()
+        }),
+        repeat_info: Some((50, 200)),
+    };
+
+    // Each key press on a keyboard applies its config, so alternate between them
+    for _ in 0..2 {
+        fixture
+            .state()
+            .switch_keyboard_config(other_keyboard.clone());
+        assert_eq!(active_layout(&mut fixture), 1);
+
+        fixture
+            .state()
+            .switch_keyboard_config(DeviceKeyboardConfig::default());
+        assert_eq!(active_layout(&mut fixture), 1);
+    }
+}
+
 #[test_log::test]
 fn input_keybind() {
     for_each_api(|lang| {
@@ -234,3 +412,98 @@ fn input_mousebind() {
         assert!(mousebind.has_on_press)
     });
 }
+
+#[test_log::test]
+fn input_set_cursor_hide() {
+    for_each_api(|lang| {
+        let mut fixture = set_up();
+
+        match lang {
+            Lang::Rust => fixture.spawn_blocking(|| {
+                pinnacle_api::input::set_cursor_hide_when_typing(true);
+                pinnacle_api::input::set_cursor_hide_timeout(Some(Duration::from_secs(5)));
+            }),
+            Lang::Lua => spawn_lua_blocking! {
+                fixture,
+                Input.set_cursor_hide_when_typing(true)
+                Input.set_cursor_hide_timeout(5000)
+            },
+        }
+
+        let cursor_state = &fixture.pinnacle().cursor_state;
+        assert!(cursor_state.hide_when_typing());
+        assert_eq!(cursor_state.hide_timeout(), Some(Duration::from_secs(5)));
+
+        fixture.state().hide_cursor_for_typing();
+        assert!(fixture.pinnacle().cursor_state.is_hidden());
+
+        fixture.state().notify_cursor_motion();
+        assert!(!fixture.pinnacle().cursor_state.is_hidden());
+    });
+}
+
+#[test_log::test]
+fn binds_are_removed_when_their_client_disconnects() {
+    let mut fixture = set_up();
+
+    let handle = fixture.runtime_handle();
+    let _guard = handle.enter();
+
+    fixture.spawn_blocking(|| {
+        pinnacle_api::input::keybind(pinnacle_api::input::Mod::SUPER, 'c').on_press(|| {});
+    });
+
+    // A second client, like a plugin running alongside the config
+    let channel = fixture.spawn_blocking(|| {
+        tokio::runtime::Handle::current().block_on(async {
+            let channel = Endpoint::try_from("http://[::]:50051")
+                .unwrap()
+                .connect_with_connector(tower::service_fn(|_: Uri| async {
+                    let path = std::env::var("PINNACLE_GRPC_SOCKET").unwrap();
+                    Ok::<_, std::io::Error>(hyper_util::rt::TokioIo::new(
+                        tokio::net::UnixStream::connect(path).await?,
+                    ))
+                }))
+                .await
+                .unwrap();
+
+            InputServiceClient::new(channel.clone())
+                .bind(BindRequest {
+                    bind: Some(Bind {
+                        bind: Some(bind::Bind::Key(Keybind {
+                            key_code: None,
+                            xkb_name: Some("d".into()),
+                        })),
+                        ..Default::default()
+                    }),
+                })
+                .await
+                .unwrap();
+
+            channel
+        })
+    });
+
+    let owners = |fixture: &mut Fixture| {
+        fixture
+            .pinnacle()
+            .input_state
+            .bind_state
+            .keybinds
+            .id_map
+            .values()
+            .map(|keybind| keybind.borrow().bind_data.owner)
+            .collect::<Vec<_>>()
+    };
+
+    let before = owners(&mut fixture);
+    assert_eq!(before.len(), 2);
+    assert!(before.iter().all(Option::is_some));
+    assert_ne!(before[0], before[1]);
+
+    drop(channel);
+
+    fixture.dispatch_until(|fixture| owners(fixture).len() == 1);
+
+    assert_eq!(owners(&mut fixture), [before[0]]);
+}
@@ -1095,5 +1095,31 @@ fn output_handle_focus() {
 
 // TODO: for_each_output
 // TODO: connect_signal
-// TODO: keyboard_focus_stack
-// TODO: keyboard_focus_stack_visible
+#[test_log::test]
+fn output_handle_keyboard_focus_stack() {
+    for_each_api(|lang| {
+        let (mut fixture, output1, _) = set_up();
+
+        let client_id = fixture.add_client();
+        fixture.spawn_windows(2, client_id);
+
+        let output1_name = output1.name();
+        match lang {
+            Lang::Rust => fixture.spawn_blocking(move || {
+                let output = pinnacle_api::output::get_by_name(output1_name).unwrap();
+                output.tags().next().unwrap().set_active(false);
+
+                assert_eq!(output.keyboard_focus_stack().count(), 2);
+                assert_eq!(output.keyboard_focus_stack_visible().count(), 0);
+            }),
+            Lang::Lua => spawn_lua_blocking! {
+                fixture,
+                local output = Output.get_by_name($output1_name)
+                output:tags()[1]:set_active(false)
+
+                assert(#output:keyboard_focus_stack() == 2)
+                assert(#output:keyboard_focus_stack_visible() == 0)
+            },
+        }
+    });
+}
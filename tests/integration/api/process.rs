@@ -360,3 +360,198 @@ fn process_set_env() {
         );
     });
 }
+
+#[test_log::test]
+fn process_supervised_restarts_until_max_retries() {
+    for_each_api(|lang| {
+        let (mut fixture, ..) = set_up();
+        let handle = fixture.runtime_handle();
+        let _guard = handle.enter();
+
+        match lang {
+            Lang::Rust => fixture.spawn_blocking(|| {
+                pinnacle_api::process::Command::with_shell(["bash", "-c"], "exit 1")
+                    .supervise("failer")
+                    .restart_backoff(Duration::from_millis(10))
+                    .max_retries(2)
+                    .spawn()
+                    .unwrap();
+            }),
+            Lang::Lua => spawn_lua_blocking! {
+                fixture,
+                assert(Process.command({
+                    cmd = "exit 1",
+                    shell_cmd = { "bash", "-c" },
+                    supervise = "failer",
+                    restart_backoff_ms = 10,
+                    max_retries = 2,
+                }):spawn())
+            },
+        }
+
+        fixture.dispatch_until(|fixture| {
+            fixture
+                .pinnacle()
+                .process_state
+                .supervised()
+                .any(|proc| proc.restart_count == 2 && proc.pid.is_none())
+        });
+
+        // Let any erroneous extra restart happen
+        fixture.dispatch_for(Duration::from_millis(200));
+
+        let supervised = fixture.spawn_blocking(pinnacle_api::process::supervised);
+
+        assert_eq!(supervised.len(), 1);
+        assert_eq!(supervised[0].name, "failer");
+        assert_eq!(supervised[0].pid, None);
+        assert_eq!(supervised[0].restart_count, 2);
+        assert_eq!(
+            supervised[0].restart_policy,
+            pinnacle_api::process::RestartPolicy::OnFailure
+        );
+    });
+}
+
+#[test_log::test]
+fn process_supervised_spawn_while_running_is_noop() {
+    let (mut fixture, ..) = set_up();
+    let handle = fixture.runtime_handle();
+    let _guard = handle.enter();
+
+    fixture.spawn_blocking(|| {
+        pinnacle_api::process::Command::new("sleep")
+            .arg("10")
+            .supervise("sleeper")
+            .spawn()
+            .unwrap();
+
+        assert!(
+            pinnacle_api::process::Command::new("sleep")
+                .arg("10")
+                .supervise("sleeper")
+                .spawn()
+                .is_none()
+        );
+
+        pinnacle_api::process::stop_supervised("sleeper");
+    });
+
+    fixture.dispatch_until(|fixture| {
+        fixture
+            .pinnacle()
+            .process_state
+            .supervised()
+            .all(|proc| proc.pid.is_none())
+    });
+
+    assert_eq!(fixture.pinnacle().process_state.supervised().count(), 1);
+}
+
+#[test_log::test]
+fn process_spawn_with_cwd() {
+    let (mut fixture, ..) = set_up();
+    let handle = fixture.runtime_handle();
+    let _guard = handle.enter();
+
+    fixture.spawn_blocking(|| {
+        let mut child = pinnacle_api::process::Command::new("pwd")
+            .current_dir("/tmp")
+            .pipe_stdout()
+            .spawn()
+            .unwrap();
+        let mut out = String::new();
+        let mut stdout: File = child.stdout.take().unwrap().into_owned_fd().unwrap().into();
+        stdout.read_to_string(&mut out).unwrap();
+        assert_eq!(out, "/tmp\n");
+    });
+
+    spawn_lua_blocking! {
+        fixture,
+
+        local child = Process.command({
+            cmd = "pwd",
+            cwd = "/tmp",
+            pipe_stdout = true,
+        }):spawn()
+        local out = child.stdout:read()
+        assert(out == "/tmp")
+    }
+}
+
+#[test_log::test]
+fn process_spawn_with_activation_token() {
+    let (mut fixture, ..) = set_up();
+    let handle = fixture.runtime_handle();
+    let _guard = handle.enter();
+
+    fixture.spawn_blocking(|| {
+        let mut child = pinnacle_api::process::Command::new("sh")
+            .args(["-c", "echo $XDG_ACTIVATION_TOKEN $DESKTOP_STARTUP_ID"])
+            .activation_token()
+            .pipe_stdout()
+            .spawn()
+            .unwrap();
+        let mut out = String::new();
+        let mut stdout: File = child.stdout.take().unwrap().into_owned_fd().unwrap().into();
+        stdout.read_to_string(&mut out).unwrap();
+        let (token, startup_id) = out.trim().split_once(' ').unwrap();
+        assert!(!token.is_empty());
+        assert_eq!(token, startup_id);
+    });
+
+    spawn_lua_blocking! {
+        fixture,
+
+        local child = Process.command({
+            cmd = { "sh", "-c", "echo $XDG_ACTIVATION_TOKEN $DESKTOP_STARTUP_ID" },
+            activation_token = true,
+            pipe_stdout = true,
+        }):spawn()
+        local out = child.stdout:read()
+        local token, startup_id = out:match("^(%S+) (%S+)$")
+        assert(token and token == startup_id)
+    }
+}
+
+#[test_log::test]
+fn process_spawn_with_target_tags() {
+    for_each_api(|lang| {
+        let (mut fixture, output) = set_up();
+        let handle = fixture.runtime_handle();
+        let _guard = handle.enter();
+
+        let tag_2 = Tag::new("2".to_string());
+        output.with_state_mut(|state| state.add_tags([tag_2.clone()]));
+
+        match lang {
+            Lang::Rust => fixture.spawn_blocking(|| {
+                let tag = pinnacle_api::tag::get("2").unwrap();
+                pinnacle_api::process::Command::new("alacritty")
+                    .args(["-o", "general.ipc_socket=false"])
+                    .target_tags([tag])
+                    .spawn()
+                    .unwrap();
+            }),
+            Lang::Lua => spawn_lua_blocking! {
+                fixture,
+                assert(Process.command({
+                    cmd = { "alacritty", "-o", "general.ipc_socket=false" },
+                    target_tags = { Tag.get("2") },
+                }):spawn())
+            },
+        }
+
+        fixture.dispatch_until(|fixture| !fixture.pinnacle().windows.is_empty());
+
+        let window_tags = fixture.pinnacle().windows[0].with_state(|state| state.tags.clone());
+        assert_eq!(window_tags.into_iter().collect::<Vec<_>>(), vec![tag_2]);
+
+        fixture.dispatch_until(|fixture| {
+            for win in fixture.pinnacle().windows.iter() {
+                win.close();
+            }
+            fixture.pinnacle().windows.is_empty()
+        });
+    });
+}
@@ -18,6 +18,10 @@ use smithay::reexports::{
     wayland_protocols::{
         wp::{
             single_pixel_buffer::v1::client::wp_single_pixel_buffer_manager_v1::WpSinglePixelBufferManagerV1,
+            text_input::zv3::client::{
+                zwp_text_input_manager_v3::ZwpTextInputManagerV3,
+                zwp_text_input_v3::{self, ZwpTextInputV3},
+            },
             viewporter::client::{wp_viewport::WpViewport, wp_viewporter::WpViewporter},
         },
         xdg::shell::client::{
@@ -38,9 +42,16 @@ use wayland_client::{
         wl_display::WlDisplay,
         wl_output::WlOutput,
         wl_registry::{self, WlRegistry},
+        wl_seat::WlSeat,
         wl_surface::WlSurface,
     },
 };
+use wayland_protocols_misc::zwp_input_method_v2::client::{
+    zwp_input_method_keyboard_grab_v2::ZwpInputMethodKeyboardGrabV2,
+    zwp_input_method_manager_v2::ZwpInputMethodManagerV2,
+    zwp_input_method_v2::{self, ZwpInputMethodV2},
+    zwp_input_popup_surface_v2::{self, ZwpInputPopupSurfaceV2},
+};
 
 pub struct Client {
     id: ClientId,
@@ -67,8 +78,13 @@ struct State {
     xdg_wm_base: Option<XdgWmBase>,
     single_pixel_buffer: Option<WpSinglePixelBufferManagerV1>,
     viewporter: Option<WpViewporter>,
+    seat: Option<WlSeat>,
+    text_input_manager: Option<ZwpTextInputManagerV3>,
+    input_method_manager: Option<ZwpInputMethodManagerV2>,
     windows: Vec<Window>,
     outputs: Vec<WlOutput>,
+    text_input: Option<TextInput>,
+    input_method: Option<InputMethod>,
 }
 
 pub struct Window {
@@ -96,6 +112,63 @@ impl Drop for Window {
     }
 }
 
+/// A zwp_text_input_v3 on the client's seat.
+pub struct TextInput {
+    text_input: ZwpTextInputV3,
+
+    pending_commit_string: Option<String>,
+    /// The surface this text input has entered, if any.
+    pub focus: Option<WlSurface>,
+    /// The last string committed by the input method.
+    pub committed_string: Option<String>,
+}
+
+impl Drop for TextInput {
+    fn drop(&mut self) {
+        self.text_input.destroy();
+    }
+}
+
+/// A zwp_input_method_v2 acting as a fake IME.
+pub struct InputMethod {
+    qh: QueueHandle<State>,
+    input_method: ZwpInputMethodV2,
+    single_pixel_buffer: WpSinglePixelBufferManagerV1,
+
+    pending_active: bool,
+    done_count: u32,
+    popups: Vec<InputPopup>,
+    keyboard_grab: Option<ZwpInputMethodKeyboardGrabV2>,
+    /// Whether the input method is active, as of the last `done` event.
+    pub active: bool,
+}
+
+impl Drop for InputMethod {
+    fn drop(&mut self) {
+        if let Some(grab) = self.keyboard_grab.take() {
+            grab.release();
+        }
+        self.popups.clear();
+        self.input_method.destroy();
+    }
+}
+
+pub struct InputPopup {
+    wl_surface: WlSurface,
+    popup_surface: ZwpInputPopupSurfaceV2,
+    viewport: WpViewport,
+    /// The text input cursor rectangle sent by the compositor, as (x, y, w, h).
+    pub text_input_rectangle: Option<(i32, i32, i32, i32)>,
+}
+
+impl Drop for InputPopup {
+    fn drop(&mut self) {
+        self.popup_surface.destroy();
+        self.viewport.destroy();
+        self.wl_surface.destroy();
+    }
+}
+
 #[derive(Default, Debug)]
 struct PendingConfigure {
     size: Option<(i32, i32)>,
@@ -129,8 +202,13 @@ impl Client {
             xdg_wm_base: None,
             single_pixel_buffer: None,
             viewporter: None,
+            seat: None,
+            text_input_manager: None,
+            input_method_manager: None,
             windows: Vec::new(),
             outputs: Vec::new(),
+            text_input: None,
+            input_method: None,
         };
 
         Self {
@@ -199,6 +277,94 @@ impl Client {
     pub fn wl_outputs(&self) -> &Vec<WlOutput> {
         &self.state.outputs
     }
+
+    pub fn create_text_input(&mut self) -> &mut TextInput {
+        let text_input = self
+            .state
+            .text_input_manager
+            .as_ref()
+            .unwrap()
+            .get_text_input(self.state.seat.as_ref().unwrap(), &self.state.qh, ());
+
+        self.state.text_input.insert(TextInput {
+            text_input,
+            pending_commit_string: None,
+            focus: None,
+            committed_string: None,
+        })
+    }
+
+    pub fn text_input(&mut self) -> &mut TextInput {
+        self.state.text_input.as_mut().unwrap()
+    }
+
+    pub fn create_input_method(&mut self) -> &mut InputMethod {
+        let input_method = self
+            .state
+            .input_method_manager
+            .as_ref()
+            .unwrap()
+            .get_input_method(self.state.seat.as_ref().unwrap(), &self.state.qh, ());
+
+        self.state.input_method.insert(InputMethod {
+            qh: self.state.qh.clone(),
+            input_method,
+            single_pixel_buffer: self.state.single_pixel_buffer.clone().unwrap(),
+            pending_active: false,
+            done_count: 0,
+            popups: Vec::new(),
+            keyboard_grab: None,
+            active: false,
+        })
+    }
+
+    pub fn input_method(&mut self) -> &mut InputMethod {
+        self.state.input_method.as_mut().unwrap()
+    }
+
+    /// Creates and maps an input popup surface of the given size for this client's input method.
+    pub fn create_input_popup(&mut self, width: i32, height: i32) -> WlSurface {
+        let wl_surface = self
+            .state
+            .compositor
+            .as_ref()
+            .unwrap()
+            .create_surface(&self.state.qh, ());
+        let viewport =
+            self.state
+                .viewporter
+                .as_ref()
+                .unwrap()
+                .get_viewport(&wl_surface, &self.state.qh, ());
+
+        let input_method = self.state.input_method.as_mut().unwrap();
+
+        let popup_surface =
+            input_method
+                .input_method
+                .get_input_popup_surface(&wl_surface, &input_method.qh, ());
+
+        let buffer = input_method.single_pixel_buffer.create_u32_rgba_buffer(
+            0,
+            0,
+            0,
+            u32::MAX,
+            &input_method.qh,
+            (),
+        );
+        wl_surface.attach(Some(&buffer), 0, 0);
+        viewport.set_destination(width, height);
+        wl_surface.commit();
+
+        input_method.popups.push(InputPopup {
+            wl_surface: wl_surface.clone(),
+            popup_surface,
+            viewport,
+            text_input_rectangle: None,
+        });
+
+        wl_surface
+    }
 }
 
 impl State {
@@ -328,6 +494,41 @@ impl Window {
     }
 }
 
+impl TextInput {
+    /// Enables this text input with the given cursor rectangle, as (x, y, w, h).
+    pub fn enable(&self, cursor_rectangle: (i32, i32, i32, i32)) {
+        let (x, y, w, h) = cursor_rectangle;
+        self.text_input.enable();
+        self.text_input.set_cursor_rectangle(x, y, w, h);
+        self.text_input.commit();
+    }
+
+    pub fn disable(&self) {
+        self.text_input.disable();
+        self.text_input.commit();
+    }
+}
+
+impl InputMethod {
+    /// Commits a string to the focused text input.
+    pub fn commit_string(&self, text: &str) {
+        self.input_method.commit_string(text.to_string());
+        self.input_method.commit(self.done_count);
+    }
+
+    pub fn grab_keyboard(&mut self) {
+        let grab = self.input_method.grab_keyboard(&self.qh, ());
+        self.keyboard_grab = Some(grab);
+    }
+
+    pub fn popup_for_surface(&self, surface: &WlSurface) -> &InputPopup {
+        self.popups
+            .iter()
+            .find(|popup| &popup.wl_surface == surface)
+            .unwrap()
+    }
+}
+
 impl Dispatch<WlRegistry, ()> for State {
     fn event(
         state: &mut Self,
@@ -356,6 +557,15 @@ impl Dispatch<WlRegistry, ()> for State {
                 } else if interface == WpViewporter::interface().name {
                     let version = u32::min(version, WpViewporter::interface().version);
                     state.viewporter = Some(registry.bind(name, version, qhandle, ()));
+                } else if interface == WlSeat::interface().name {
+                    let version = u32::min(version, WlSeat::interface().version);
+                    state.seat = Some(registry.bind(name, version, qhandle, ()));
+                } else if interface == ZwpTextInputManagerV3::interface().name {
+                    let version = u32::min(version, ZwpTextInputManagerV3::interface().version);
+                    state.text_input_manager = Some(registry.bind(name, version, qhandle, ()));
+                } else if interface == ZwpInputMethodManagerV2::interface().name {
+                    let version = u32::min(version, ZwpInputMethodManagerV2::interface().version);
+                    state.input_method_manager = Some(registry.bind(name, version, qhandle, ()));
                 } else if interface == WlOutput::interface().name {
                     let version = u32::min(version, WlOutput::interface().version);
                     state
@@ -525,9 +735,99 @@ impl Dispatch<XdgToplevel, ()> for State {
     }
 }
 
+impl Dispatch<ZwpTextInputV3, ()> for State {
+    fn event(
+        state: &mut Self,
+        _proxy: &ZwpTextInputV3,
+        event: <ZwpTextInputV3 as wayland_client::Proxy>::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qhandle: &QueueHandle<Self>,
+    ) {
+        let text_input = state.text_input.as_mut().unwrap();
+
+        match event {
+            zwp_text_input_v3::Event::Enter { surface } => text_input.focus = Some(surface),
+            zwp_text_input_v3::Event::Leave { surface: _ } => text_input.focus = None,
+            zwp_text_input_v3::Event::CommitString { text } => {
+                text_input.pending_commit_string = text;
+            }
+            zwp_text_input_v3::Event::Done { serial: _ } => {
+                if let Some(text) = text_input.pending_commit_string.take() {
+                    text_input.committed_string = Some(text);
+                }
+            }
+            zwp_text_input_v3::Event::PreeditString { .. }
+            | zwp_text_input_v3::Event::DeleteSurroundingText { .. } => (),
+            _ => panic!(),
+        }
+    }
+}
+
+impl Dispatch<ZwpInputMethodV2, ()> for State {
+    fn event(
+        state: &mut Self,
+        _proxy: &ZwpInputMethodV2,
+        event: <ZwpInputMethodV2 as wayland_client::Proxy>::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qhandle: &QueueHandle<Self>,
+    ) {
+        let input_method = state.input_method.as_mut().unwrap();
+
+        match event {
+            zwp_input_method_v2::Event::Activate => input_method.pending_active = true,
+            zwp_input_method_v2::Event::Deactivate => input_method.pending_active = false,
+            zwp_input_method_v2::Event::Done => {
+                input_method.active = input_method.pending_active;
+                input_method.done_count += 1;
+            }
+            zwp_input_method_v2::Event::Unavailable => panic!("input method unavailable"),
+            zwp_input_method_v2::Event::SurroundingText { .. }
+            | zwp_input_method_v2::Event::TextChangeCause { .. }
+            | zwp_input_method_v2::Event::ContentType { .. } => (),
+            _ => panic!(),
+        }
+    }
+}
+
+impl Dispatch<ZwpInputPopupSurfaceV2, ()> for State {
+    fn event(
+        state: &mut Self,
+        proxy: &ZwpInputPopupSurfaceV2,
+        event: <ZwpInputPopupSurfaceV2 as wayland_client::Proxy>::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qhandle: &QueueHandle<Self>,
+    ) {
+        let popup = state
+            .input_method
+            .as_mut()
+            .unwrap()
+            .popups
+            .iter_mut()
+            .find(|popup| &popup.popup_surface == proxy)
+            .unwrap();
+
+        match event {
+            zwp_input_popup_surface_v2::Event::TextInputRectangle {
+                x,
+                y,
+                width,
+                height,
+            } => popup.text_input_rectangle = Some((x, y, width, height)),
+            _ => panic!(),
+        }
+    }
+}
+
 delegate_noop!(State: WlCompositor);
 delegate_noop!(State: WpSinglePixelBufferManagerV1);
 delegate_noop!(State: WpViewporter);
 delegate_noop!(State: WpViewport);
 delegate_noop!(State: ignore WlBuffer);
 delegate_noop!(State: ignore WlOutput);
+delegate_noop!(State: ignore WlSeat);
+delegate_noop!(State: ZwpTextInputManagerV3);
+delegate_noop!(State: ZwpInputMethodManagerV2);
+delegate_noop!(State: ignore ZwpInputMethodKeyboardGrabV2);
@@ -28,6 +28,9 @@ pub struct Fixture {
     state: State,
     _test_guard: MutexGuard<'static, ()>,
     timeout: Duration,
+    /// Lua states from [`spawn_lua_blocking`], kept so their connections
+    /// and everything they own stay around for the rest of the test.
+    lua_states: Vec<mlua::Lua>,
 }
 
 struct State {
@@ -81,9 +84,14 @@ impl Fixture {
             state,
             _test_guard,
             timeout: DEFAULT_TIMEOUT,
+            lua_states: Vec::new(),
         }
     }
 
+    pub fn keep_lua_alive(&mut self, lua: mlua::Lua) {
+        self.lua_states.push(lua);
+    }
+
     pub fn runtime_handle(&self) -> tokio::runtime::Handle {
         self.state.server.runtime.handle().clone()
     }
@@ -378,9 +386,11 @@ macro_rules! spawn_lua_blocking {
             if let Err(err) = task.exec() {
                 panic!("lua panicked: {err}");
             }
+
+            lua
         });
 
         $fixture.dispatch_until(|_| join.is_finished());
-        join.join().unwrap();
+        $fixture.keep_lua_alive(join.join().unwrap());
     }};
 }
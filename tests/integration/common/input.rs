@@ -0,0 +1,209 @@
+//! An input backend for sending input events straight to the compositor.
+
+use core::hash::Hash;
+
+use smithay::{
+    backend::input::{
+        AbsolutePositionEvent, Device, DeviceCapability, Event, InputBackend, InputEvent, KeyState,
+        KeyboardKeyEvent, PointerMotionEvent, TouchEvent, TouchMotionEvent, TouchSlot, UnusedEvent,
+    },
+    input::keyboard::Keycode,
+    utils::{Logical, Point},
+};
+
+pub struct TestInputBackend;
+
+impl InputBackend for TestInputBackend {
+    type Device = TestDevice;
+    type KeyboardKeyEvent = TestKeyEvent;
+    type PointerAxisEvent = UnusedEvent;
+    type PointerButtonEvent = UnusedEvent;
+    type PointerMotionEvent = TestPointerMotionEvent;
+    type PointerMotionAbsoluteEvent = UnusedEvent;
+    type GestureSwipeBeginEvent = UnusedEvent;
+    type GestureSwipeUpdateEvent = UnusedEvent;
+    type GestureSwipeEndEvent = UnusedEvent;
+    type GesturePinchBeginEvent = UnusedEvent;
+    type GesturePinchUpdateEvent = UnusedEvent;
+    type GesturePinchEndEvent = UnusedEvent;
+    type GestureHoldBeginEvent = UnusedEvent;
+    type GestureHoldEndEvent = UnusedEvent;
+    type TouchDownEvent = UnusedEvent;
+    type TouchUpEvent = UnusedEvent;
+    type TouchMotionEvent = TestTouchMotionEvent;
+    type TouchCancelEvent = UnusedEvent;
+    type TouchFrameEvent = UnusedEvent;
+    type TabletToolAxisEvent = UnusedEvent;
+    type TabletToolProximityEvent = UnusedEvent;
+    type TabletToolTipEvent = UnusedEvent;
+    type TabletToolButtonEvent = UnusedEvent;
+    type SwitchToggleEvent = UnusedEvent;
+    type SpecialEvent = ();
+}
+
+#[derive(PartialEq, Eq)]
+pub struct TestDevice {
+    pub name: &'static str,
+    pub capability: DeviceCapability,
+}
+
+impl Hash for TestDevice {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl Device for TestDevice {
+    fn id(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn has_capability(&self, capability: DeviceCapability) -> bool {
+        self.capability == capability
+    }
+
+    fn usb_id(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    fn syspath(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+}
+
+pub struct TestKeyEvent {
+    /// The evdev keycode.
+    pub key: u32,
+    pub state: KeyState,
+}
+
+impl From<TestKeyEvent> for InputEvent<TestInputBackend> {
+    fn from(event: TestKeyEvent) -> Self {
+        InputEvent::<TestInputBackend>::Keyboard { event }
+    }
+}
+
+impl Event<TestInputBackend> for TestKeyEvent {
+    fn time(&self) -> u64 {
+        0
+    }
+
+    fn device(&self) -> TestDevice {
+        TestDevice {
+            name: "test-keyboard",
+            capability: DeviceCapability::Keyboard,
+        }
+    }
+}
+
+impl KeyboardKeyEvent<TestInputBackend> for TestKeyEvent {
+    fn key_code(&self) -> Keycode {
+        // xkb keycodes are offset from evdev ones by 8
+        Keycode::new(self.key + 8)
+    }
+
+    fn state(&self) -> KeyState {
+        self.state
+    }
+
+    fn count(&self) -> u32 {
+        match self.state {
+            KeyState::Pressed => 1,
+            KeyState::Released => 0,
+        }
+    }
+}
+
+pub struct TestPointerMotionEvent {
+    pub delta: Point<f64, Logical>,
+}
+
+impl From<TestPointerMotionEvent> for InputEvent<TestInputBackend> {
+    fn from(event: TestPointerMotionEvent) -> Self {
+        InputEvent::<TestInputBackend>::PointerMotion { event }
+    }
+}
+
+impl Event<TestInputBackend> for TestPointerMotionEvent {
+    fn time(&self) -> u64 {
+        0
+    }
+
+    fn device(&self) -> TestDevice {
+        TestDevice {
+            name: "test-pointer",
+            capability: DeviceCapability::Pointer,
+        }
+    }
+}
+
+impl PointerMotionEvent<TestInputBackend> for TestPointerMotionEvent {
+    fn delta_x(&self) -> f64 {
+        self.delta.x
+    }
+
+    fn delta_y(&self) -> f64 {
+        self.delta.y
+    }
+
+    fn delta_x_unaccel(&self) -> f64 {
+        self.delta_x()
+    }
+
+    fn delta_y_unaccel(&self) -> f64 {
+        self.delta_y()
+    }
+}
+
+pub struct TestTouchMotionEvent {
+    pub position: Point<f64, Logical>,
+}
+
+impl From<TestTouchMotionEvent> for InputEvent<TestInputBackend> {
+    fn from(event: TestTouchMotionEvent) -> Self {
+        InputEvent::<TestInputBackend>::TouchMotion { event }
+    }
+}
+
+impl Event<TestInputBackend> for TestTouchMotionEvent {
+    fn time(&self) -> u64 {
+        0
+    }
+
+    fn device(&self) -> TestDevice {
+        TestDevice {
+            name: "test-touch",
+            capability: DeviceCapability::Touch,
+        }
+    }
+}
+
+impl TouchEvent<TestInputBackend> for TestTouchMotionEvent {
+    fn slot(&self) -> TouchSlot {
+        None.into()
+    }
+}
+
+impl AbsolutePositionEvent<TestInputBackend> for TestTouchMotionEvent {
+    fn x(&self) -> f64 {
+        self.position.x
+    }
+
+    fn y(&self) -> f64 {
+        self.position.y
+    }
+
+    fn x_transformed(&self, _width: i32) -> f64 {
+        self.x()
+    }
+
+    fn y_transformed(&self, _height: i32) -> f64 {
+        self.y()
+    }
+}
+
+impl TouchMotionEvent<TestInputBackend> for TestTouchMotionEvent {}
@@ -2,6 +2,7 @@ use mlua::{Lua, Variadic};
 
 pub mod client;
 pub mod fixture;
+pub mod input;
 pub mod server;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -0,0 +1,85 @@
+use crate::common::fixture::Fixture;
+use smithay::utils::Rectangle;
+use tempfile::TempDir;
+use test_log::test;
+
+const STATIC_CONFIG: &str = r#"
+    [static]
+    tags = ["1", "2"]
+
+    [[static.binds]]
+    mods = ["super", "shift"]
+    key = "q"
+    action = "quit"
+
+    [[static.binds]]
+    mods = ["super"]
+    key = "Return"
+    action = { spawn = ["alacritty"] }
+"#;
+
+/// Points the fixture at a config dir with a static config.
+///
+/// The fixture runs with `--no-config`, so starting the builtin config doesn't spawn anything.
+fn set_up() -> (Fixture, TempDir) {
+    let mut fixture = Fixture::new();
+    fixture.add_output(Rectangle::new((0, 0).into(), (1920, 1080).into()));
+
+    let config_dir = tempfile::tempdir().unwrap();
+    std::fs::write(config_dir.path().join("pinnacle.toml"), STATIC_CONFIG).unwrap();
+    fixture.pinnacle().config.config_dir = config_dir.path().to_path_buf();
+
+    (fixture, config_dir)
+}
+
+fn static_bind_count(fixture: &mut Fixture) -> usize {
+    fixture
+        .pinnacle()
+        .input_state
+        .bind_state
+        .keybinds
+        .id_map
+        .values()
+        .filter(|keybind| keybind.borrow().bind_data.owner.is_none())
+        .count()
+}
+
+fn tag_names(fixture: &mut Fixture) -> Vec<String> {
+    fixture.pinnacle().dump_state().outputs[0]
+        .tags
+        .iter()
+        .filter(|tag| !tag.defunct)
+        .map(|tag| tag.name.clone())
+        .collect()
+}
+
+#[test]
+fn builtin_config_keeps_the_static_config_of_a_crashed_config() {
+    let (mut fixture, _config_dir) = set_up();
+
+    fixture.pinnacle().start_config(false).unwrap();
+    fixture.dispatch_until(|fixture| static_bind_count(fixture) == 2);
+    assert_eq!(tag_names(&mut fixture), ["1", "2"]);
+
+    // What happens when the config crashes
+    fixture.pinnacle().start_config(true).unwrap();
+    assert_eq!(static_bind_count(&mut fixture), 0);
+
+    fixture.dispatch_until(|fixture| static_bind_count(fixture) == 2);
+    assert_eq!(tag_names(&mut fixture), ["1", "2"]);
+}
+
+#[test]
+fn falling_back_before_the_static_config_is_applied_applies_it_once() {
+    let (mut fixture, _config_dir) = set_up();
+
+    // What happens when the config command fails to start
+    fixture.pinnacle().start_config(false).unwrap();
+    fixture.pinnacle().start_config(true).unwrap();
+
+    fixture.dispatch_until(|fixture| static_bind_count(fixture) == 2);
+    fixture.dispatch_for(std::time::Duration::from_millis(50));
+
+    assert_eq!(static_bind_count(&mut fixture), 2);
+    assert_eq!(tag_names(&mut fixture), ["1", "2"]);
+}
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use crate::common::fixture::Fixture;
+use pinnacle::{cli::msg::connect, config::GRPC_SOCKET_ENV};
+use pinnacle_api_defs::pinnacle::{
+    layout::v1::{LayoutRequest, layout_service_client::LayoutServiceClient},
+    signal::v1::{WindowFocusedRequest, signal_service_client::SignalServiceClient},
+    window::v1::{WindowRuleRequest, window_service_client::WindowServiceClient},
+};
+use smithay::utils::Rectangle;
+use test_log::test;
+use tokio::sync::mpsc::unbounded_channel;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+#[test]
+fn signals_window_rules_and_the_layout_manager_are_removed_when_their_client_disconnects() {
+    let mut fixture = Fixture::new();
+    fixture.add_output(Rectangle::new((0, 0).into(), (1920, 1080).into()));
+
+    let socket = PathBuf::from(std::env::var_os(GRPC_SOCKET_ENV).unwrap());
+
+    // A client other than the config, like a plugin
+    let task = fixture.runtime_handle().spawn(async move {
+        let channel = connect(&socket).await.unwrap();
+
+        let (layout_sender, recv) = unbounded_channel::<LayoutRequest>();
+        let layout = LayoutServiceClient::new(channel.clone())
+            .layout(UnboundedReceiverStream::new(recv))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let (signal_sender, recv) = unbounded_channel::<WindowFocusedRequest>();
+        let signal = SignalServiceClient::new(channel.clone())
+            .window_focused(UnboundedReceiverStream::new(recv))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let (rule_sender, recv) = unbounded_channel::<WindowRuleRequest>();
+        let rule = WindowServiceClient::new(channel.clone())
+            .window_rule(UnboundedReceiverStream::new(recv))
+            .await
+            .unwrap()
+            .into_inner();
+
+        (
+            channel,
+            (layout_sender, layout),
+            (signal_sender, signal),
+            (rule_sender, rule),
+        )
+    });
+    fixture.dispatch_until(|_| task.is_finished());
+    let client = fixture.runtime_handle().block_on(task).unwrap();
+
+    fixture.dispatch_until(|fixture| {
+        let pinnacle = fixture.pinnacle();
+        pinnacle.layout_state.layout_manager_owner.is_some()
+            && !pinnacle.window_rule_state.senders.is_empty()
+    });
+
+    let owner = fixture
+        .pinnacle()
+        .layout_state
+        .layout_manager_owner
+        .unwrap();
+    fixture.dispatch_until(|fixture| fixture.pinnacle().signal_state.has_client(owner));
+    assert_eq!(fixture.pinnacle().window_rule_state.senders.len(), 1);
+
+    drop(client);
+
+    fixture.dispatch_until(|fixture| !fixture.pinnacle().signal_state.has_client(owner));
+
+    let pinnacle = fixture.pinnacle();
+    assert!(pinnacle.window_rule_state.senders.is_empty());
+    assert_eq!(pinnacle.layout_state.layout_manager_owner, None);
+    assert!(pinnacle.layout_state.layout_request_sender.is_none());
+}
@@ -0,0 +1,124 @@
+use crate::common::{
+    fixture::Fixture,
+    input::{TestKeyEvent, TestPointerMotionEvent, TestTouchMotionEvent},
+};
+use pinnacle::{focus::keyboard::KeyboardFocusTarget, state::WithState, tag::Tag};
+use pinnacle_api::layout::{LayoutGenerator, generators::MasterStack};
+use smithay::{backend::input::KeyState, utils::Rectangle};
+use test_log::test;
+
+/// The evdev keycode for `a`.
+const KEY_A: u32 = 30;
+
+fn set_up() -> Fixture {
+    let mut fixture = Fixture::new();
+
+    let output = fixture.add_output(Rectangle::new((0, 0).into(), (1920, 1080).into()));
+    output.with_state_mut(|state| {
+        let tag = Tag::new("1".to_string());
+        tag.set_active(true);
+        state.add_tags([tag]);
+    });
+
+    fixture
+        .runtime_handle()
+        .block_on(pinnacle_api::connect())
+        .unwrap();
+
+    fixture.spawn_blocking(|| {
+        pinnacle_api::layout::manage(|args| pinnacle_api::layout::LayoutResponse {
+            root_node: MasterStack::default().layout(args.window_count),
+            tree_id: 0,
+        });
+    });
+
+    fixture.pinnacle().cursor_state.set_hide_when_typing(true);
+
+    fixture
+}
+
+fn type_key(fixture: &mut Fixture) {
+    for state in [KeyState::Pressed, KeyState::Released] {
+        fixture
+            .state()
+            .process_input_event(TestKeyEvent { key: KEY_A, state }.into());
+    }
+}
+
+fn set_up_with_focused_window() -> Fixture {
+    let mut fixture = set_up();
+
+    let client_id = fixture.add_client();
+    fixture.spawn_windows(1, client_id);
+
+    assert!(matches!(
+        fixture
+            .pinnacle()
+            .seat
+            .get_keyboard()
+            .unwrap()
+            .current_focus(),
+        Some(KeyboardFocusTarget::Window(_))
+    ));
+
+    fixture
+}
+
+#[test]
+fn typing_into_a_window_hides_the_cursor_until_the_pointer_moves() {
+    let mut fixture = set_up_with_focused_window();
+
+    type_key(&mut fixture);
+    assert!(fixture.pinnacle().cursor_state.is_hidden());
+
+    fixture.state().process_input_event(
+        TestPointerMotionEvent {
+            delta: (5.0, 5.0).into(),
+        }
+        .into(),
+    );
+    assert!(!fixture.pinnacle().cursor_state.is_hidden());
+}
+
+#[test]
+fn touch_motion_shows_a_cursor_hidden_by_typing() {
+    let mut fixture = set_up_with_focused_window();
+
+    type_key(&mut fixture);
+    assert!(fixture.pinnacle().cursor_state.is_hidden());
+
+    fixture.state().process_input_event(
+        TestTouchMotionEvent {
+            position: (100.0, 100.0).into(),
+        }
+        .into(),
+    );
+    assert!(!fixture.pinnacle().cursor_state.is_hidden());
+}
+
+#[test]
+fn typing_without_a_focused_window_doesnt_hide_the_cursor() {
+    let mut fixture = set_up();
+
+    assert!(
+        fixture
+            .pinnacle()
+            .seat
+            .get_keyboard()
+            .unwrap()
+            .current_focus()
+            .is_none()
+    );
+
+    type_key(&mut fixture);
+    assert!(!fixture.pinnacle().cursor_state.is_hidden());
+}
+
+#[test]
+fn typing_doesnt_hide_the_cursor_when_disabled() {
+    let mut fixture = set_up_with_focused_window();
+    fixture.pinnacle().cursor_state.set_hide_when_typing(false);
+
+    type_key(&mut fixture);
+    assert!(!fixture.pinnacle().cursor_state.is_hidden());
+}
@@ -0,0 +1,79 @@
+use crate::common::fixture::Fixture;
+use pinnacle::{dump::StateDump, state::WithState, tag::Tag, window::window_state::LayoutModeKind};
+use pinnacle_api::layout::{LayoutGenerator, generators::MasterStack};
+use smithay::utils::Rectangle;
+use test_log::test;
+
+fn set_up() -> Fixture {
+    let mut fixture = Fixture::new();
+
+    let output = fixture.add_output(Rectangle::new((0, 0).into(), (1920, 1080).into()));
+    output.with_state_mut(|state| {
+        let tag = Tag::new("1".to_string());
+        tag.set_active(true);
+        state.add_tags([tag]);
+    });
+
+    fixture
+        .runtime_handle()
+        .block_on(pinnacle_api::connect())
+        .unwrap();
+
+    fixture.spawn_blocking(|| {
+        pinnacle_api::layout::manage(|args| pinnacle_api::layout::LayoutResponse {
+            root_node: MasterStack::default().layout(args.window_count),
+            tree_id: 0,
+        });
+    });
+
+    let client_id = fixture.add_client();
+    fixture.spawn_windows(2, client_id);
+
+    fixture
+}
+
+#[test]
+fn dump_state_includes_windows_and_layout() {
+    let mut fixture = set_up();
+
+    let dump = fixture.pinnacle().dump_state();
+
+    assert_eq!(dump.outputs.len(), 1);
+    let output = &dump.outputs[0];
+    assert_eq!(output.tags.len(), 1);
+    assert!(output.tags[0].active);
+
+    let tree_id = output
+        .layout
+        .current_tree_id
+        .expect("no current layout tree");
+    let tree = output
+        .layout
+        .trees
+        .iter()
+        .find(|tree| tree.id == tree_id)
+        .expect("current tree not dumped");
+    assert_eq!(tree.root.children.len(), 2);
+
+    assert_eq!(dump.windows.len(), 2);
+    assert!(dump.unmapped_windows.is_empty());
+    for window in dump.windows.iter() {
+        assert_eq!(window.layout_mode, LayoutModeKind::Tiled);
+        assert_eq!(window.output.as_deref(), Some(output.name.as_str()));
+        assert_eq!(window.tags, vec![output.tags[0].id]);
+        assert!(window.layout_node.is_some());
+    }
+
+    assert_eq!(dump.focus.focused_window, Some(dump.windows[1].id));
+    assert_eq!(dump.focus.focused_output.as_ref(), Some(&output.name));
+}
+
+#[test]
+fn dump_state_over_api_matches() {
+    let mut fixture = set_up();
+
+    let json = fixture.spawn_blocking(pinnacle_api::debug::dump_state);
+    let from_api: StateDump = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(from_api, fixture.pinnacle().dump_state());
+}
@@ -1,7 +1,13 @@
 mod api;
 mod common;
+mod config;
+mod connection;
+mod cursor;
+mod dump;
 mod focus;
+mod msg;
 mod protocol;
+mod replay;
 mod resize;
 mod rust_api;
 mod window;
@@ -0,0 +1,198 @@
+use std::path::PathBuf;
+
+use crate::common::fixture::Fixture;
+use pinnacle::{
+    cli::msg::{
+        Ack, MsgCommand, OutputCommand, Reply, TagCommand, WindowCommand, connect, execute,
+    },
+    config::GRPC_SOCKET_ENV,
+    state::WithState,
+    tag::Tag,
+    window::window_state::LayoutModeKind,
+};
+use pinnacle_api::layout::{LayoutGenerator, generators::MasterStack};
+use smithay::utils::Rectangle;
+use test_log::test;
+
+fn set_up() -> Fixture {
+    let mut fixture = Fixture::new();
+
+    let output = fixture.add_output(Rectangle::new((0, 0).into(), (1920, 1080).into()));
+    output.with_state_mut(|state| {
+        let tag = Tag::new("1".to_string());
+        tag.set_active(true);
+        state.add_tags([tag, Tag::new("2".to_string())]);
+    });
+
+    fixture
+        .runtime_handle()
+        .block_on(pinnacle_api::connect())
+        .unwrap();
+
+    fixture.spawn_blocking(|| {
+        pinnacle_api::layout::manage(|args| pinnacle_api::layout::LayoutResponse {
+            root_node: MasterStack::default().layout(args.window_count),
+            tree_id: 0,
+        });
+    });
+
+    let client_id = fixture.add_client();
+    fixture.spawn_windows(2, client_id);
+
+    fixture
+}
+
+/// Runs a `pinnacle msg` command against the fixture's gRPC socket.
+fn msg(fixture: &mut Fixture, command: MsgCommand) -> anyhow::Result<Reply> {
+    let socket = PathBuf::from(std::env::var_os(GRPC_SOCKET_ENV).unwrap());
+
+    let task = fixture.runtime_handle().spawn(async move {
+        let channel = connect(&socket).await?;
+        execute(&channel, command).await
+    });
+
+    fixture.dispatch_until(|_| task.is_finished());
+    fixture.runtime_handle().block_on(task).unwrap()
+}
+
+#[test]
+fn msg_windows_lists_windows() {
+    let mut fixture = set_up();
+
+    let dump = fixture.pinnacle().dump_state();
+
+    let Reply::Windows(windows) = msg(&mut fixture, MsgCommand::Windows).unwrap() else {
+        panic!("expected windows");
+    };
+
+    assert_eq!(
+        windows.iter().map(|win| win.id).collect::<Vec<_>>(),
+        dump.windows.iter().map(|win| win.id).collect::<Vec<_>>()
+    );
+
+    let tag_id = dump.outputs[0].tags[0].id;
+    for win in windows.iter() {
+        assert_eq!(win.layout_mode, Some("tiled"));
+        assert_eq!(win.tag_ids, vec![tag_id]);
+        assert!(win.width.is_some_and(|width| width > 0));
+        assert_eq!(win.focused, dump.focus.focused_window == Some(win.id));
+    }
+    assert_eq!(windows.iter().filter(|win| win.focused).count(), 1);
+}
+
+#[test]
+fn msg_outputs_and_tags_list_the_output_and_its_tags() {
+    let mut fixture = set_up();
+
+    let dump = fixture.pinnacle().dump_state();
+    let output = &dump.outputs[0];
+
+    let Reply::Outputs(outputs) = msg(&mut fixture, MsgCommand::Outputs).unwrap() else {
+        panic!("expected outputs");
+    };
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(outputs[0].name, output.name);
+    assert!(outputs[0].focused);
+    assert_eq!(outputs[0].logical_width, Some(1920));
+    assert_eq!(outputs[0].logical_height, Some(1080));
+    assert_eq!(
+        outputs[0].tag_ids,
+        output.tags.iter().map(|tag| tag.id).collect::<Vec<_>>()
+    );
+
+    let Reply::Tags(tags) = msg(&mut fixture, MsgCommand::Tags).unwrap() else {
+        panic!("expected tags");
+    };
+    assert_eq!(
+        tags.iter()
+            .map(|tag| (tag.id, tag.name.as_str(), tag.active, tag.output.as_str()))
+            .collect::<Vec<_>>(),
+        output
+            .tags
+            .iter()
+            .map(|tag| (tag.id, tag.name.as_str(), tag.active, output.name.as_str()))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn msg_tag_switch_acks_with_the_tag() {
+    let mut fixture = set_up();
+
+    let tag_2 = fixture.pinnacle().dump_state().outputs[0].tags[1].id;
+
+    let reply = msg(
+        &mut fixture,
+        MsgCommand::Tag(TagCommand::Switch {
+            name: "2".to_string(),
+            output: None,
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&reply).unwrap(),
+        serde_json::json!({ "ok": true, "tag_id": tag_2 })
+    );
+
+    let tags = fixture.pinnacle().dump_state().outputs[0].tags.clone();
+    assert!(!tags[0].active);
+    assert!(tags[1].active);
+}
+
+#[test]
+fn msg_window_toggle_floating_acts_on_the_focused_window() {
+    let mut fixture = set_up();
+
+    let focused = fixture
+        .pinnacle()
+        .dump_state()
+        .focus
+        .focused_window
+        .unwrap();
+
+    let reply = msg(
+        &mut fixture,
+        MsgCommand::Window(WindowCommand::ToggleFloating { id: None }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        reply,
+        Reply::Ack(Ack {
+            ok: true,
+            window_id: Some(focused),
+            tag_id: None,
+            output_name: None,
+        })
+    );
+
+    let dump = fixture.pinnacle().dump_state();
+    let window = dump.windows.iter().find(|win| win.id == focused).unwrap();
+    assert_eq!(window.layout_mode, LayoutModeKind::Floating);
+}
+
+#[test]
+fn msg_output_focus_acks_with_the_output_and_rejects_unknown_ones() {
+    let mut fixture = set_up();
+
+    let name = fixture.pinnacle().dump_state().outputs[0].name.clone();
+
+    let reply = msg(
+        &mut fixture,
+        MsgCommand::Output(OutputCommand::Focus { name: name.clone() }),
+    )
+    .unwrap();
+    assert_eq!(
+        serde_json::to_value(&reply).unwrap(),
+        serde_json::json!({ "ok": true, "output_name": name })
+    );
+
+    let result = msg(
+        &mut fixture,
+        MsgCommand::Output(OutputCommand::Focus {
+            name: "nonexistent".to_string(),
+        }),
+    );
+    assert!(result.is_err());
+}
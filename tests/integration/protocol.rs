@@ -1 +1,2 @@
+mod input_method;
 mod xdg_shell;
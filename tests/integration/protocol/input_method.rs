@@ -0,0 +1,212 @@
+//! input_method_v2 and text_input_v3 test suite
+//!
+//! A fake input method client is used to check that text inputs activate the input method,
+//! that input method popups are tracked relative to the text input's cursor rectangle, and that
+//! committed text makes its way to the focused text input.
+//!
+//! Resources:
+//! - https://wayland.app/protocols/input-method-unstable-v2
+//! - https://wayland.app/protocols/text-input-unstable-v3
+
+use crate::common::fixture::Fixture;
+use pinnacle::{state::WithState, tag::Tag};
+use pinnacle_api::layout::{LayoutGenerator as _, generators::MasterStack};
+
+use smithay::{
+    desktop::{PopupKind, PopupManager},
+    output::Output,
+    utils::Rectangle,
+};
+
+fn set_up() -> (Fixture, Output) {
+    let mut fixture = Fixture::new();
+
+    let output = fixture.add_output(Rectangle::new((0, 0).into(), (1920, 1080).into()));
+    output.with_state_mut(|state| {
+        let tag = Tag::new("1".to_string());
+        tag.set_active(true);
+        state.add_tags([tag]);
+    });
+
+    fixture.pinnacle().focus_output(&output);
+
+    fixture
+        .runtime_handle()
+        .block_on(pinnacle_api::connect())
+        .unwrap();
+
+    fixture.spawn_blocking(|| {
+        pinnacle_api::layout::manage(|args| pinnacle_api::layout::LayoutResponse {
+            root_node: MasterStack::default().layout(args.window_count),
+            tree_id: 0,
+        });
+    });
+
+    (fixture, output)
+}
+
+#[test_log::test]
+fn enabling_text_input_activates_input_method() {
+    let (mut fixture, _) = set_up();
+
+    let ime_id = fixture.add_client();
+    let client_id = fixture.add_client();
+
+    fixture.client(ime_id).create_input_method();
+    fixture.client(client_id).create_text_input();
+    fixture.roundtrip(ime_id);
+    fixture.roundtrip(client_id);
+
+    let surface = fixture.spawn_windows(1, client_id).remove(0);
+    fixture.roundtrip(client_id);
+
+    assert_eq!(fixture.client(client_id).text_input().focus, Some(surface));
+    assert!(!fixture.client(ime_id).input_method().active);
+
+    fixture
+        .client(client_id)
+        .text_input()
+        .enable((10, 20, 1, 16));
+    fixture.roundtrip(client_id);
+    fixture.roundtrip(ime_id);
+
+    assert!(fixture.client(ime_id).input_method().active);
+
+    fixture.client(client_id).text_input().disable();
+    fixture.roundtrip(client_id);
+    fixture.roundtrip(ime_id);
+
+    assert!(!fixture.client(ime_id).input_method().active);
+}
+
+#[test_log::test]
+fn input_method_popup_follows_cursor_rectangle() {
+    let (mut fixture, _) = set_up();
+
+    let ime_id = fixture.add_client();
+    let client_id = fixture.add_client();
+
+    fixture.client(ime_id).create_input_method();
+    fixture.client(client_id).create_text_input();
+    fixture.roundtrip(ime_id);
+    fixture.roundtrip(client_id);
+
+    fixture.spawn_windows(1, client_id);
+    fixture.roundtrip(client_id);
+
+    fixture
+        .client(client_id)
+        .text_input()
+        .enable((10, 20, 1, 16));
+    fixture.roundtrip(client_id);
+    fixture.roundtrip(ime_id);
+
+    let popup_surface = fixture.client(ime_id).create_input_popup(200, 40);
+    fixture.roundtrip(ime_id);
+    fixture.roundtrip(ime_id);
+
+    assert_eq!(
+        fixture
+            .client(ime_id)
+            .input_method()
+            .popup_for_surface(&popup_surface)
+            .text_input_rectangle,
+        Some((10, 20, 1, 16))
+    );
+
+    let window_surface = fixture.pinnacle().windows[0]
+        .toplevel()
+        .unwrap()
+        .wl_surface()
+        .clone();
+
+    let popups = PopupManager::popups_for_surface(&window_surface)
+        .map(|(popup, _)| popup)
+        .collect::<Vec<_>>();
+
+    assert_eq!(popups.len(), 1);
+    assert!(matches!(popups[0], PopupKind::InputMethod(_)));
+
+    // Moving the cursor should reposition the popup
+    fixture
+        .client(client_id)
+        .text_input()
+        .enable((30, 50, 1, 16));
+    fixture.roundtrip(client_id);
+    fixture.roundtrip(ime_id);
+
+    assert_eq!(
+        fixture
+            .client(ime_id)
+            .input_method()
+            .popup_for_surface(&popup_surface)
+            .text_input_rectangle,
+        Some((30, 50, 1, 16))
+    );
+}
+
+#[test_log::test]
+fn input_method_commit_string_reaches_text_input() {
+    let (mut fixture, _) = set_up();
+
+    let ime_id = fixture.add_client();
+    let client_id = fixture.add_client();
+
+    fixture.client(ime_id).create_input_method();
+    fixture.client(client_id).create_text_input();
+    fixture.roundtrip(ime_id);
+    fixture.roundtrip(client_id);
+
+    fixture.spawn_windows(1, client_id);
+    fixture.roundtrip(client_id);
+
+    fixture.client(client_id).text_input().enable((0, 0, 1, 16));
+    fixture.roundtrip(client_id);
+    fixture.roundtrip(ime_id);
+
+    fixture
+        .client(ime_id)
+        .input_method()
+        .commit_string("ピナクル");
+    fixture.roundtrip(ime_id);
+    fixture.roundtrip(client_id);
+
+    assert_eq!(
+        fixture
+            .client(client_id)
+            .text_input()
+            .committed_string
+            .as_deref(),
+        Some("ピナクル")
+    );
+}
+
+#[test_log::test]
+fn keyboard_focus_changes_while_input_method_grabs_keyboard() {
+    let (mut fixture, _) = set_up();
+
+    let ime_id = fixture.add_client();
+    let client_id = fixture.add_client();
+
+    fixture.client(ime_id).create_input_method();
+    fixture.client(client_id).create_text_input();
+    fixture.roundtrip(ime_id);
+    fixture.roundtrip(client_id);
+
+    let first = fixture.spawn_windows(1, client_id).remove(0);
+    fixture.roundtrip(client_id);
+
+    fixture.client(client_id).text_input().enable((0, 0, 1, 16));
+    fixture.roundtrip(client_id);
+    fixture.roundtrip(ime_id);
+
+    fixture.client(ime_id).input_method().grab_keyboard();
+    fixture.roundtrip(ime_id);
+
+    assert_eq!(fixture.client(client_id).text_input().focus, Some(first));
+
+    let second = fixture.spawn_windows(1, client_id).remove(0);
+    fixture.roundtrip(client_id);
+
+    assert_eq!(fixture.client(client_id).text_input().focus, Some(second));
+}
@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+
+use crate::common::fixture::Fixture;
+use pinnacle::{
+    cli::replay::{Trace, replay_to_socket, restore_start_state},
+    config::GRPC_SOCKET_ENV,
+    state::WithState,
+    tag::Tag,
+};
+use smithay::utils::Rectangle;
+use test_log::test;
+
+fn tags(fixture: &mut Fixture) -> Vec<(u32, String, bool)> {
+    fixture
+        .pinnacle()
+        .dump_state()
+        .outputs
+        .into_iter()
+        .flat_map(|output| output.tags)
+        .map(|tag| (tag.id, tag.name, tag.active))
+        .collect()
+}
+
+#[test]
+fn replaying_a_trace_recreates_its_tags_with_the_recorded_ids() {
+    let trace_dir = tempfile::tempdir().unwrap();
+    let path = trace_dir.path().join("trace.jsonl");
+
+    let recorded = {
+        let mut fixture = Fixture::new();
+
+        let output = fixture.add_output(Rectangle::new((0, 0).into(), (1920, 1080).into()));
+        output.with_state_mut(|state| {
+            let tag = Tag::new("1".to_string());
+            tag.set_active(true);
+            state.add_tags([tag]);
+        });
+
+        fixture
+            .runtime_handle()
+            .block_on(pinnacle_api::connect())
+            .unwrap();
+
+        let trace_path = path.clone();
+        fixture.spawn_blocking(move || {
+            pinnacle_api::debug::start_trace_recording(Some(&trace_path)).unwrap();
+
+            let output = pinnacle_api::output::get_focused().unwrap();
+            let added = pinnacle_api::tag::add(&output, ["2", "3"]).collect::<Vec<_>>();
+            added[1].switch_to();
+
+            pinnacle_api::debug::stop_trace_recording();
+        });
+
+        tags(&mut fixture)
+    };
+
+    assert_eq!(recorded.len(), 3);
+
+    let trace = Trace::read(&path).unwrap();
+    let start = trace.start.clone().expect("trace has no starting state");
+
+    let mut fixture = Fixture::new();
+    restore_start_state(fixture.pinnacle(), &start);
+
+    let socket = PathBuf::from(std::env::var_os(GRPC_SOCKET_ENV).unwrap());
+    let replay = fixture
+        .runtime_handle()
+        .spawn(async move { replay_to_socket(&socket, trace, 10.0).await });
+
+    fixture.dispatch_until(|_| replay.is_finished());
+    let mismatches = fixture.runtime_handle().block_on(replay).unwrap().unwrap();
+
+    assert_eq!(mismatches, 0);
+    assert_eq!(tags(&mut fixture), recorded);
+}
@@ -1,4 +1,8 @@
-use pinnacle::{state::WithState, tag::Tag};
+use pinnacle::{
+    config::static_config::{StaticConfig, StaticOutput, StaticWindowRule},
+    state::WithState,
+    tag::Tag,
+};
 use pinnacle_api::{
     layout::{LayoutGenerator as _, generators::MasterStack},
     output::OutputHandle,
@@ -229,3 +233,64 @@ fn window_floating_pick_size() {
     let size = fixture.pinnacle().windows[0].geometry().size;
     assert_eq!(size, (500, 500).into());
 }
+
+#[test_log::test]
+fn window_static_rule_overrides_float_heuristic() {
+    let (mut fixture, _) = set_up();
+
+    fixture.pinnacle().config.static_config.window_rules = vec![StaticWindowRule {
+        app_id: Some("tiled".to_string()),
+        floating: Some(false),
+        ..Default::default()
+    }];
+
+    let client_id = fixture.add_client();
+
+    fixture.spawn_window_with(client_id, |win| {
+        win.set_app_id("tiled");
+        win.set_min_size(500, 500);
+        win.set_max_size(500, 500);
+    });
+
+    assert!(fixture.pinnacle().windows[0].with_state(|state| state.layout_mode.is_tiled()));
+}
+
+#[test_log::test]
+fn static_output_config_adds_tags_and_scale() {
+    let mut fixture = Fixture::new();
+
+    let output1 = fixture.add_output(Rectangle::new((0, 0).into(), (1920, 1080).into()));
+    let output2 = fixture.add_output(Rectangle::new((1920, 0).into(), (1920, 1080).into()));
+
+    fixture.pinnacle().config.static_config = StaticConfig {
+        tags: vec!["1".to_string(), "2".to_string()],
+        outputs: vec![StaticOutput {
+            name: output2.name(),
+            tags: Some(vec!["web".to_string()]),
+            mode: None,
+            scale: Some(2.0),
+            transform: None,
+            loc: None,
+        }],
+        ..Default::default()
+    };
+
+    fixture.state().apply_static_config();
+
+    let tags = |output: &Output| {
+        output.with_state(|state| {
+            state
+                .tags
+                .iter()
+                .map(|tag| (tag.name(), tag.active()))
+                .collect::<Vec<_>>()
+        })
+    };
+
+    assert_eq!(
+        tags(&output1),
+        [("1".to_string(), true), ("2".to_string(), false)]
+    );
+    assert_eq!(tags(&output2), [("web".to_string(), true)]);
+    assert_eq!(output2.current_scale().fractional_scale(), 2.0);
+}